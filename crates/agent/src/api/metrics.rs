@@ -1,10 +1,13 @@
-use super::state::AgentState;
+use std::fmt::Write;
+
+use super::state::{AgentState, ACK_LATENCY_BUCKETS};
+use crate::buffer::DropReason;
 use axum::extract::State;
 use axum::http::header;
 use axum::response::IntoResponse;
 
 pub async fn metrics(State(state): State<AgentState>) -> impl IntoResponse {
-    let body = format!(
+    let mut body = format!(
         "# HELP sentinel_queue_length Number of unacked records in WAL\n\
          # TYPE sentinel_queue_length gauge\n\
          sentinel_queue_length {}\n\
@@ -27,6 +30,56 @@ pub async fn metrics(State(state): State<AgentState>) -> impl IntoResponse {
         state.batches_failed(),
     );
 
+    let _ = write!(
+        body,
+        "# HELP sentinel_batches_in_flight Batches sent on the stream and waiting for an ack\n\
+         # TYPE sentinel_batches_in_flight gauge\n\
+         sentinel_batches_in_flight {}\n\
+         # HELP sentinel_batch_retransmits_total Batches sent again after an ack timeout or retry\n\
+         # TYPE sentinel_batch_retransmits_total counter\n\
+         sentinel_batch_retransmits_total {}\n\
+         # HELP sentinel_batch_ack_timeouts_total Batches whose ack did not arrive in time\n\
+         # TYPE sentinel_batch_ack_timeouts_total counter\n\
+         sentinel_batch_ack_timeouts_total {}\n",
+        state.in_flight(),
+        state.retransmits(),
+        state.ack_timeouts(),
+    );
+
+    let latency = state.ack_latency();
+    body.push_str(
+        "# HELP sentinel_batch_ack_latency_seconds Time from sending a batch to its accepted ack\n\
+         # TYPE sentinel_batch_ack_latency_seconds histogram\n",
+    );
+    for (le, count) in ACK_LATENCY_BUCKETS.iter().zip(&latency.buckets) {
+        let _ = writeln!(
+            body,
+            "sentinel_batch_ack_latency_seconds_bucket{{le=\"{le}\"}} {count}"
+        );
+    }
+    let _ = write!(
+        body,
+        "sentinel_batch_ack_latency_seconds_bucket{{le=\"+Inf\"}} {count}\n\
+         sentinel_batch_ack_latency_seconds_sum {}\n\
+         sentinel_batch_ack_latency_seconds_count {count}\n",
+        latency.sum_secs,
+        count = latency.count,
+    );
+
+    let dropped = state.wal_dropped();
+    body.push_str(
+        "# HELP sentinel_wal_dropped_batches_total Unacked batches dropped by WAL retention, quota or server rejection\n\
+         # TYPE sentinel_wal_dropped_batches_total counter\n",
+    );
+    for reason in DropReason::ALL {
+        let _ = writeln!(
+            body,
+            "sentinel_wal_dropped_batches_total{{reason=\"{}\"}} {}",
+            reason.as_str(),
+            dropped.get(reason)
+        );
+    }
+
     (
         [(
             header::CONTENT_TYPE,
@@ -47,6 +100,11 @@ mod tests {
         state.set_queue_length(5);
         state.set_wal_size_bytes(2048);
         state.increment_batches_sent();
+        let mut drops = crate::buffer::DropCounts::default();
+        drops.add(DropReason::Age, 4);
+        state.set_wal_dropped(&drops);
+        state.set_in_flight(7);
+        state.observe_ack_latency(std::time::Duration::from_millis(40));
 
         let resp = metrics(State(state)).await.into_response();
         let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
@@ -59,5 +117,12 @@ mod tests {
         assert!(text.contains("sentinel_batches_sent_total 1"));
         assert!(text.contains("# TYPE sentinel_queue_length gauge"));
         assert!(text.contains("# TYPE sentinel_batches_sent_total counter"));
+        assert!(text.contains("sentinel_wal_dropped_batches_total{reason=\"age\"} 4"));
+        assert!(text.contains("sentinel_batches_in_flight 7"));
+        assert!(text.contains("sentinel_batch_ack_latency_seconds_bucket{le=\"0.025\"} 0"));
+        assert!(text.contains("sentinel_batch_ack_latency_seconds_bucket{le=\"0.05\"} 1"));
+        assert!(text.contains("sentinel_batch_ack_latency_seconds_bucket{le=\"+Inf\"} 1"));
+        assert!(text.contains("sentinel_batch_ack_latency_seconds_count 1"));
+        assert!(text.contains("sentinel_wal_dropped_batches_total{reason=\"drop_newest\"} 0"));
     }
 }
@@ -1,5 +1,12 @@
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
+
+use crate::buffer::{DropCounts, DropReason};
+
+pub const ACK_LATENCY_BUCKETS: [f64; 12] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+];
 
 #[derive(Debug, Clone)]
 pub struct AgentState {
@@ -13,6 +20,11 @@ struct Inner {
     last_send_epoch: AtomicU64,
     batches_sent: AtomicU64,
     batches_failed: AtomicU64,
+    wal_dropped: [AtomicU64; 5],
+    in_flight: AtomicU64,
+    retransmits: AtomicU64,
+    ack_timeouts: AtomicU64,
+    ack_latency: LatencyHistogram,
     ready: std::sync::atomic::AtomicBool,
 }
 
@@ -25,6 +37,11 @@ impl AgentState {
                 last_send_epoch: AtomicU64::new(0),
                 batches_sent: AtomicU64::new(0),
                 batches_failed: AtomicU64::new(0),
+                wal_dropped: Default::default(),
+                in_flight: AtomicU64::new(0),
+                retransmits: AtomicU64::new(0),
+                ack_timeouts: AtomicU64::new(0),
+                ack_latency: LatencyHistogram::default(),
                 ready: std::sync::atomic::AtomicBool::new(false),
             }),
         }
@@ -70,6 +87,52 @@ impl AgentState {
         self.inner.batches_failed.load(Ordering::Relaxed)
     }
 
+    pub fn set_wal_dropped(&self, counts: &DropCounts) {
+        for (slot, reason) in self.inner.wal_dropped.iter().zip(DropReason::ALL) {
+            slot.store(counts.get(reason), Ordering::Relaxed);
+        }
+    }
+
+    pub fn wal_dropped(&self) -> DropCounts {
+        let mut counts = DropCounts::default();
+        for (slot, reason) in self.inner.wal_dropped.iter().zip(DropReason::ALL) {
+            counts.add(reason, slot.load(Ordering::Relaxed));
+        }
+        counts
+    }
+
+    pub fn set_in_flight(&self, v: u64) {
+        self.inner.in_flight.store(v, Ordering::Relaxed);
+    }
+
+    pub fn in_flight(&self) -> u64 {
+        self.inner.in_flight.load(Ordering::Relaxed)
+    }
+
+    pub fn increment_retransmits(&self) {
+        self.inner.retransmits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn retransmits(&self) -> u64 {
+        self.inner.retransmits.load(Ordering::Relaxed)
+    }
+
+    pub fn increment_ack_timeouts(&self) {
+        self.inner.ack_timeouts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn ack_timeouts(&self) -> u64 {
+        self.inner.ack_timeouts.load(Ordering::Relaxed)
+    }
+
+    pub fn observe_ack_latency(&self, latency: Duration) {
+        self.inner.ack_latency.observe(latency);
+    }
+
+    pub fn ack_latency(&self) -> LatencySnapshot {
+        self.inner.ack_latency.snapshot()
+    }
+
     pub fn set_ready(&self, v: bool) {
         self.inner
             .ready
@@ -81,6 +144,48 @@ impl AgentState {
     }
 }
 
+#[derive(Debug, Default)]
+struct LatencyHistogram {
+    buckets: [AtomicU64; ACK_LATENCY_BUCKETS.len()],
+    sum_us: AtomicU64,
+    count: AtomicU64,
+}
+
+impl LatencyHistogram {
+    fn observe(&self, latency: Duration) {
+        let secs = latency.as_secs_f64();
+        if let Some(i) = ACK_LATENCY_BUCKETS.iter().position(|le| secs <= *le) {
+            self.buckets[i].fetch_add(1, Ordering::Relaxed);
+        }
+        self.sum_us
+            .fetch_add(latency.as_micros() as u64, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> LatencySnapshot {
+        let mut cumulative = 0;
+        let buckets = self
+            .buckets
+            .iter()
+            .map(|b| {
+                cumulative += b.load(Ordering::Relaxed);
+                cumulative
+            })
+            .collect();
+        LatencySnapshot {
+            buckets,
+            sum_secs: self.sum_us.load(Ordering::Relaxed) as f64 / 1_000_000.0,
+            count: self.count.load(Ordering::Relaxed),
+        }
+    }
+}
+
+pub struct LatencySnapshot {
+    pub buckets: Vec<u64>,
+    pub sum_secs: f64,
+    pub count: u64,
+}
+
 impl Default for AgentState {
     fn default() -> Self {
         Self::new()
@@ -106,6 +211,26 @@ mod tests {
         assert!(state.is_ready());
         assert_eq!(state.batches_sent(), 2);
         assert_eq!(state.batches_failed(), 1);
+
+        let mut drops = DropCounts::default();
+        drops.add(DropReason::Newest, 3);
+        state.set_wal_dropped(&drops);
+        assert_eq!(state.wal_dropped(), drops);
+    }
+
+    #[test]
+    fn ack_latency_buckets_are_cumulative() {
+        let state = AgentState::new();
+        state.observe_ack_latency(Duration::from_millis(3));
+        state.observe_ack_latency(Duration::from_millis(80));
+        state.observe_ack_latency(Duration::from_secs(60));
+
+        let snap = state.ack_latency();
+        assert_eq!(snap.count, 3);
+        assert_eq!(snap.buckets[0], 1);
+        assert_eq!(snap.buckets[4], 2);
+        assert_eq!(*snap.buckets.last().unwrap(), 2);
+        assert!(snap.sum_secs > 60.0);
     }
 
     #[test]
@@ -1,3 +1,4 @@
+use std::io::Write;
 use std::path::Path;
 
 pub fn write_config(config_dir: &Path, config_yaml: &[u8]) -> Result<(), WriteError> {
@@ -22,6 +23,36 @@ pub fn write_config(config_dir: &Path, config_yaml: &[u8]) -> Result<(), WriteEr
     Ok(())
 }
 
+pub fn replace_config(config_path: &Path, config_yaml: &[u8]) -> Result<(), WriteError> {
+    let dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+    std::fs::create_dir_all(dir).map_err(|e| WriteError::Io(e.to_string()))?;
+
+    let file_name = config_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "config.yml".into());
+    let tmp_path = dir.join(format!(".{file_name}.tmp"));
+
+    {
+        let mut f = std::fs::File::create(&tmp_path).map_err(|e| WriteError::Io(e.to_string()))?;
+        f.write_all(config_yaml)
+            .map_err(|e| WriteError::Io(e.to_string()))?;
+        f.sync_all().map_err(|e| WriteError::Io(e.to_string()))?;
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let perms = std::fs::Permissions::from_mode(0o600);
+        let _ = std::fs::set_permissions(&tmp_path, perms);
+    }
+
+    std::fs::rename(&tmp_path, config_path).map_err(|e| WriteError::Io(e.to_string()))?;
+
+    tracing::info!(path = %config_path.display(), "config replaced");
+    Ok(())
+}
+
 #[derive(Debug)]
 pub enum WriteError {
     Io(String),
@@ -38,3 +69,28 @@ impl std::fmt::Display for WriteError {
 }
 
 impl std::error::Error for WriteError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_refuses_to_overwrite() {
+        let dir = tempfile::tempdir().unwrap();
+        write_config(dir.path(), b"a: 1\n").unwrap();
+        let err = write_config(dir.path(), b"a: 2\n").unwrap_err();
+        assert!(matches!(err, WriteError::AlreadyExists));
+    }
+
+    #[test]
+    fn replace_overwrites_and_leaves_no_tmp() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.yml");
+        std::fs::write(&path, b"old").unwrap();
+
+        replace_config(&path, b"new").unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), b"new");
+        assert!(!dir.path().join(".config.yml.tmp").exists());
+    }
+}
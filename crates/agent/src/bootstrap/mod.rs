@@ -5,6 +5,7 @@ mod negotiator;
 
 use std::path::{Path, PathBuf};
 
+pub use config_writer::{replace_config, WriteError};
 pub use detector::{bootstrap_token_from_env, needs_bootstrap, server_url_from_env};
 pub use negotiator::NegotiateError;
 
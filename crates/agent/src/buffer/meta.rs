@@ -1,9 +1,11 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::{self, Write};
 use std::path::Path;
 
+use super::retention::DropCounts;
+
 const META_FILE: &str = "wal.meta.json";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +14,12 @@ pub struct WalMeta {
     pub tail_seq: u64,
     pub last_segment: u64,
     pub acked_ids: Vec<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unacked: Option<u64>,
+    #[serde(default)]
+    pub unreported_drops: DropCounts,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub reporting_drops: HashMap<String, DropCounts>,
 }
 
 impl WalMeta {
@@ -21,10 +29,16 @@ impl WalMeta {
             tail_seq: 0,
             last_segment: 0,
             acked_ids: Vec::new(),
+            unacked: None,
+            unreported_drops: DropCounts::default(),
+            reporting_drops: HashMap::new(),
         }
     }
 
     pub fn unacked_count(&self) -> u64 {
+        if let Some(unacked) = self.unacked {
+            return unacked;
+        }
         let total_records = self.tail_seq - self.head_seq;
         total_records.saturating_sub(self.acked_ids.len() as u64)
     }
@@ -105,6 +119,9 @@ mod tests {
             tail_seq: 5,
             last_segment: 1,
             acked_ids: vec![0, 2],
+            unacked: None,
+            unreported_drops: DropCounts::default(),
+            reporting_drops: HashMap::new(),
         };
         m.save(dir.path()).unwrap();
         assert!(dir.path().join("wal.meta.json").exists());
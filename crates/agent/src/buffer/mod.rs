@@ -1,11 +1,11 @@
-mod compaction;
 mod meta;
 pub(crate) mod record;
+mod retention;
 pub(crate) mod segment;
 mod wal;
 mod wal_metrics;
 
-pub use compaction::{compact, needs_compaction};
 pub use meta::WalMeta;
+pub use retention::{DropCounts, DropPolicy, DropReason, RetentionPolicy};
 pub use wal::Wal;
 pub use wal_metrics::{compute_stats, WalStats};
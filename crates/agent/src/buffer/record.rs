@@ -6,6 +6,10 @@ pub struct Record {
 }
 
 impl Record {
+    pub fn encoded_len(data_len: usize) -> u64 {
+        (4 + 8 + data_len + 4) as u64
+    }
+
     pub fn encode(&self) -> Vec<u8> {
         let len = self.data.len() as u32;
         let crc = crc32fast::hash(&self.data);
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use sentinel_common::wal_drops::{DROPPED_BATCHES_META_KEY, DROP_REASONS_META_KEY};
+
+use crate::config::BufferConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropPolicy {
+    DropOldest,
+    DropNewest,
+    Downsample,
+}
+
+impl DropPolicy {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "drop_oldest" => Ok(Self::DropOldest),
+            "drop_newest" => Ok(Self::DropNewest),
+            "downsample" => Ok(Self::Downsample),
+            other => Err(format!(
+                "buffer.drop_policy must be drop_oldest, drop_newest or downsample, got {other:?}"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub max_age: Option<Duration>,
+    pub max_bytes: u64,
+    pub drop_policy: DropPolicy,
+}
+
+impl RetentionPolicy {
+    pub fn unlimited() -> Self {
+        Self {
+            max_age: None,
+            max_bytes: 0,
+            drop_policy: DropPolicy::DropOldest,
+        }
+    }
+
+    pub fn from_config(cfg: &BufferConfig) -> Self {
+        Self {
+            max_age: (cfg.max_retention_days > 0)
+                .then(|| Duration::from_secs(cfg.max_retention_days * 24 * 3600)),
+            max_bytes: cfg.max_wal_bytes,
+            drop_policy: DropPolicy::parse(&cfg.drop_policy).unwrap_or(DropPolicy::DropOldest),
+        }
+    }
+
+    pub fn over_quota(&self, size_bytes: u64) -> bool {
+        self.max_bytes > 0 && size_bytes > self.max_bytes
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropReason {
+    Age,
+    Oldest,
+    Newest,
+    Downsample,
+    Rejected,
+}
+
+impl DropReason {
+    pub const ALL: [DropReason; 5] = [
+        DropReason::Age,
+        DropReason::Oldest,
+        DropReason::Newest,
+        DropReason::Downsample,
+        DropReason::Rejected,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Age => "age",
+            Self::Oldest => "drop_oldest",
+            Self::Newest => "drop_newest",
+            Self::Downsample => "downsample",
+            Self::Rejected => "rejected",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DropCounts {
+    pub age: u64,
+    pub oldest: u64,
+    pub newest: u64,
+    pub downsample: u64,
+    #[serde(default)]
+    pub rejected: u64,
+}
+
+impl DropCounts {
+    pub fn get(&self, reason: DropReason) -> u64 {
+        match reason {
+            DropReason::Age => self.age,
+            DropReason::Oldest => self.oldest,
+            DropReason::Newest => self.newest,
+            DropReason::Downsample => self.downsample,
+            DropReason::Rejected => self.rejected,
+        }
+    }
+
+    pub fn add(&mut self, reason: DropReason, n: u64) {
+        let slot = match reason {
+            DropReason::Age => &mut self.age,
+            DropReason::Oldest => &mut self.oldest,
+            DropReason::Newest => &mut self.newest,
+            DropReason::Downsample => &mut self.downsample,
+            DropReason::Rejected => &mut self.rejected,
+        };
+        *slot += n;
+    }
+
+    pub fn merge(&mut self, other: &DropCounts) {
+        for reason in DropReason::ALL {
+            self.add(reason, other.get(reason));
+        }
+    }
+
+    pub fn subtract(&mut self, other: &DropCounts) {
+        self.age = self.age.saturating_sub(other.age);
+        self.oldest = self.oldest.saturating_sub(other.oldest);
+        self.newest = self.newest.saturating_sub(other.newest);
+        self.downsample = self.downsample.saturating_sub(other.downsample);
+        self.rejected = self.rejected.saturating_sub(other.rejected);
+    }
+
+    pub fn total(&self) -> u64 {
+        self.age + self.oldest + self.newest + self.downsample + self.rejected
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+
+    pub fn annotate(&self, meta: &mut HashMap<String, String>) {
+        if self.is_empty() {
+            return;
+        }
+        let reasons: Vec<String> = DropReason::ALL
+            .iter()
+            .filter(|r| self.get(**r) > 0)
+            .map(|r| format!("{}:{}", r.as_str(), self.get(*r)))
+            .collect();
+        meta.insert(DROPPED_BATCHES_META_KEY.into(), self.total().to_string());
+        meta.insert(DROP_REASONS_META_KEY.into(), reasons.join(","));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sentinel_common::wal_drops;
+
+    #[test]
+    fn parse_policies() {
+        assert_eq!(
+            DropPolicy::parse("drop_oldest").unwrap(),
+            DropPolicy::DropOldest
+        );
+        assert_eq!(
+            DropPolicy::parse("downsample").unwrap(),
+            DropPolicy::Downsample
+        );
+        assert!(DropPolicy::parse("oldest").is_err());
+    }
+
+    #[test]
+    fn annotate_round_trips_through_meta() {
+        let mut counts = DropCounts::default();
+        counts.add(DropReason::Age, 2);
+        counts.add(DropReason::Downsample, 5);
+        let mut meta = HashMap::new();
+        counts.annotate(&mut meta);
+        assert_eq!(wal_drops::dropped_batches(&meta), 7);
+        assert_eq!(
+            wal_drops::drop_reasons(&meta),
+            vec![("age".to_string(), 2), ("downsample".to_string(), 5)]
+        );
+
+        let mut empty = HashMap::new();
+        DropCounts::default().annotate(&mut empty);
+        assert!(empty.is_empty());
+    }
+}
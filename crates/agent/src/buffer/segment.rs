@@ -11,8 +11,12 @@ pub struct Segment {
 }
 
 impl Segment {
+    pub fn path_for(dir: &Path, index: u64) -> PathBuf {
+        dir.join(format!("wal-{:07}.log", index))
+    }
+
     pub fn create(dir: &Path, index: u64) -> io::Result<Self> {
-        let path = dir.join(format!("wal-{:07}.log", index));
+        let path = Self::path_for(dir, index);
         let file = OpenOptions::new().create(true).append(true).open(&path)?;
         Ok(Self {
             path,
@@ -46,9 +50,20 @@ impl Segment {
         Ok(records)
     }
 
-    #[allow(dead_code)]
-    pub fn path(&self) -> &Path {
-        &self.path
+    pub fn rewrite(path: &Path, records: &[Record]) -> io::Result<()> {
+        let modified = fs::metadata(path)?.modified()?;
+        let tmp = path.with_extension("log.tmp");
+        {
+            let mut writer = BufWriter::new(File::create(&tmp)?);
+            for record in records {
+                writer.write_all(&record.encode())?;
+            }
+            writer.flush()?;
+            let file = writer.into_inner().map_err(|e| e.into_error())?;
+            file.set_modified(modified)?;
+            file.sync_all()?;
+        }
+        fs::rename(&tmp, path)
     }
 
     pub fn size_bytes(&self) -> io::Result<u64> {
@@ -82,7 +97,7 @@ mod tests {
         )
         .unwrap();
 
-        let records = Segment::read_all(seg.path()).unwrap();
+        let records = Segment::read_all(&Segment::path_for(dir.path(), 1)).unwrap();
         assert_eq!(records.len(), 2);
         assert_eq!(records[0].data, b"first");
         assert_eq!(records[1].data, b"second");
@@ -1,20 +1,45 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use prost::Message;
+use sentinel_common::proto::Batch;
 
 use super::meta::WalMeta;
 use super::record::Record;
+use super::retention::{DropCounts, DropPolicy, DropReason, RetentionPolicy};
 use super::segment::Segment;
 
+const COMPACT_MIN_ACKED_RATIO: f64 = 0.5;
+
+struct Pending {
+    segment: u64,
+    batch_id: Option<String>,
+}
+
+#[derive(Default)]
+struct SegmentInfo {
+    acked: BTreeSet<u64>,
+    unacked: BTreeSet<u64>,
+}
+
 pub struct Wal {
     dir: PathBuf,
     current: Segment,
     segment_index: u64,
     next_id: u64,
-    acked: HashSet<u64>,
+    pending: BTreeMap<u64, Pending>,
+    by_batch: HashMap<String, u64>,
+    segments: BTreeMap<u64, SegmentInfo>,
     fsync: bool,
     max_segment_bytes: u64,
+    retention: RetentionPolicy,
+    size_bytes: u64,
+    dropped: DropCounts,
+    unreported: DropCounts,
+    reporting: HashMap<String, DropCounts>,
 }
 
 impl Wal {
@@ -24,7 +49,11 @@ impl Wal {
         let meta = WalMeta::load(dir)?;
         let mut segment_index = meta.last_segment;
         let mut next_id = meta.tail_seq;
-        let acked: HashSet<u64> = meta.acked_set();
+        let acked = meta.acked_set();
+        let mut size_bytes = 0u64;
+        let mut pending = BTreeMap::new();
+        let mut by_batch = HashMap::new();
+        let mut segments = BTreeMap::new();
 
         let mut entries: Vec<_> = fs::read_dir(dir)?
             .filter_map(|e| e.ok())
@@ -38,36 +67,69 @@ impl Wal {
         entries.sort_by_key(|e| e.path());
 
         for entry in &entries {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Some(idx) = name
+                .strip_prefix("wal-")
+                .and_then(|s| s.strip_suffix(".log"))
+                .and_then(|s| s.parse::<u64>().ok())
+            else {
+                continue;
+            };
+            if idx >= segment_index {
+                segment_index = idx + 1;
+            }
+            size_bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+
+            let mut info = SegmentInfo::default();
             if let Ok(records) = Segment::read_all(&entry.path()) {
-                for r in &records {
+                for r in records {
                     if r.id >= next_id {
                         next_id = r.id + 1;
                     }
-                }
-            }
-            let name = entry.file_name().to_string_lossy().to_string();
-            if let Some(idx_str) = name
-                .strip_prefix("wal-")
-                .and_then(|s| s.strip_suffix(".log"))
-            {
-                if let Ok(idx) = idx_str.parse::<u64>() {
-                    if idx >= segment_index {
-                        segment_index = idx + 1;
+                    if r.id < meta.head_seq || acked.contains(&r.id) {
+                        info.acked.insert(r.id);
+                        continue;
+                    }
+                    let batch_id = batch_id_of(&r.data);
+                    if let Some(batch_id) = &batch_id {
+                        by_batch.insert(batch_id.clone(), r.id);
                     }
+                    info.unacked.insert(r.id);
+                    pending.insert(
+                        r.id,
+                        Pending {
+                            segment: idx,
+                            batch_id,
+                        },
+                    );
                 }
             }
+            segments.insert(idx, info);
         }
 
         let current = Segment::create(dir, segment_index)?;
+        segments.insert(segment_index, SegmentInfo::default());
+        let reporting = meta
+            .reporting_drops
+            .into_iter()
+            .filter(|(batch_id, _)| by_batch.contains_key(batch_id))
+            .collect();
 
         Ok(Self {
             dir: dir.to_path_buf(),
             current,
             segment_index,
             next_id,
-            acked,
+            pending,
+            by_batch,
+            segments,
             fsync,
             max_segment_bytes,
+            retention: RetentionPolicy::unlimited(),
+            size_bytes,
+            dropped: DropCounts::default(),
+            unreported: meta.unreported_drops,
+            reporting,
         })
     }
 
@@ -84,30 +146,82 @@ impl Wal {
         }
 
         self.current.append(&record, self.fsync)?;
+        self.size_bytes += Record::encoded_len(record.data.len());
+
+        let batch_id = batch_id_of(&record.data);
+        if let Some(batch_id) = &batch_id {
+            self.by_batch.insert(batch_id.clone(), id);
+        }
+        self.pending.insert(
+            id,
+            Pending {
+                segment: self.segment_index,
+                batch_id,
+            },
+        );
+        self.segments
+            .entry(self.segment_index)
+            .or_default()
+            .unacked
+            .insert(id);
         Ok(id)
     }
 
     pub fn ack(&mut self, record_id: u64) {
-        self.acked.insert(record_id);
+        let Some(p) = self.pending.remove(&record_id) else {
+            return;
+        };
+        if let Some(batch_id) = p.batch_id {
+            self.by_batch.remove(&batch_id);
+            if let Some(reported) = self.reporting.remove(&batch_id) {
+                self.unreported.subtract(&reported);
+            }
+        }
+        if let Some(info) = self.segments.get_mut(&p.segment) {
+            info.unacked.remove(&record_id);
+            info.acked.insert(record_id);
+        }
+        if let Err(e) = self.release_if_acked(p.segment) {
+            tracing::warn!(target: "data", segment = p.segment, error = %e, "Failed to delete acked WAL segment");
+        }
+    }
+
+    pub fn ack_batch(&mut self, batch_id: &str) -> Option<u64> {
+        let record_id = *self.by_batch.get(batch_id)?;
+        self.ack(record_id);
+        Some(record_id)
+    }
+
+    pub fn reject_batch(&mut self, batch_id: &str) -> Option<u64> {
+        let record_id = *self.by_batch.get(batch_id)?;
+        self.reject(record_id);
+        Some(record_id)
+    }
+
+    pub fn reject(&mut self, record_id: u64) {
+        let Some(p) = self.pending.get(&record_id) else {
+            return;
+        };
+        if let Some(batch_id) = &p.batch_id {
+            self.reporting.remove(batch_id);
+        }
+        self.ack(record_id);
+        self.record_drops(DropReason::Rejected, 1);
+    }
+
+    pub fn has_pending_batch(&self, batch_id: &str) -> bool {
+        self.by_batch.contains_key(batch_id)
     }
 
     pub fn iter_unacked(&self) -> io::Result<Vec<(u64, Vec<u8>)>> {
         let mut results = Vec::new();
-        let mut entries: Vec<_> = fs::read_dir(&self.dir)?
-            .filter_map(|e| e.ok())
-            .filter(|e| {
-                e.path()
-                    .extension()
-                    .map(|ext| ext == "log")
-                    .unwrap_or(false)
-            })
-            .collect();
-        entries.sort_by_key(|e| e.path());
-
-        for entry in entries {
-            if let Ok(records) = Segment::read_all(&entry.path()) {
+        for (idx, info) in &self.segments {
+            if info.unacked.is_empty() {
+                continue;
+            }
+            if let Ok(records) = Segment::read_all(&self.segment_path(*idx)) {
                 for r in records {
-                    if !self.acked.contains(&r.id) {
+                    if info.unacked.contains(&r.id) {
                         results.push((r.id, r.data));
                     }
                 }
@@ -116,8 +230,49 @@ impl Wal {
         Ok(results)
     }
 
+    pub fn read_due(
+        &mut self,
+        limit: usize,
+        mut due: impl FnMut(&str) -> bool,
+    ) -> io::Result<Vec<(u64, Vec<u8>)>> {
+        let unbatched: Vec<u64> = self
+            .pending
+            .iter()
+            .filter(|(_, p)| p.batch_id.is_none())
+            .map(|(id, _)| *id)
+            .collect();
+        if !unbatched.is_empty() {
+            tracing::warn!(target: "data", count = unbatched.len(), "Dropping WAL records without a batch id");
+            for id in unbatched {
+                self.reject(id);
+            }
+        }
+
+        let mut selected: BTreeMap<u64, BTreeSet<u64>> = BTreeMap::new();
+        let mut picked = 0;
+        for (id, p) in &self.pending {
+            if picked >= limit {
+                break;
+            }
+            if p.batch_id.as_deref().is_some_and(&mut due) {
+                selected.entry(p.segment).or_default().insert(*id);
+                picked += 1;
+            }
+        }
+
+        let mut results = Vec::with_capacity(picked);
+        for (idx, ids) in selected {
+            for r in Segment::read_all(&self.segment_path(idx))? {
+                if ids.contains(&r.id) {
+                    results.push((r.id, r.data));
+                }
+            }
+        }
+        Ok(results)
+    }
+
     pub fn unacked_count(&self) -> io::Result<usize> {
-        Ok(self.iter_unacked()?.len())
+        Ok(self.pending.len())
     }
 
     pub fn save_meta(&self) -> io::Result<WalMeta> {
@@ -126,7 +281,15 @@ impl Wal {
             head_seq: head,
             tail_seq: self.next_id,
             last_segment: self.segment_index,
-            acked_ids: self.acked.iter().copied().collect(),
+            acked_ids: self
+                .segments
+                .values()
+                .flat_map(|info| info.acked.range(head..))
+                .copied()
+                .collect(),
+            unacked: Some(self.pending.len() as u64),
+            unreported_drops: self.unreported,
+            reporting_drops: self.reporting.clone(),
         };
         meta.save(&self.dir)?;
         Ok(meta)
@@ -140,27 +303,239 @@ impl Wal {
         self.next_id
     }
 
-    fn head_seq(&self) -> u64 {
-        if self.acked.is_empty() {
-            return 0;
+    pub fn max_segment_bytes(&self) -> u64 {
+        self.max_segment_bytes
+    }
+
+    pub fn set_max_segment_bytes(&mut self, bytes: u64) {
+        self.max_segment_bytes = bytes;
+    }
+
+    pub fn size_bytes(&self) -> u64 {
+        self.size_bytes
+    }
+
+    pub fn retention(&self) -> RetentionPolicy {
+        self.retention
+    }
+
+    pub fn set_retention(&mut self, retention: RetentionPolicy) {
+        self.retention = retention;
+    }
+
+    pub fn rejects_writes(&self) -> bool {
+        self.retention.drop_policy == DropPolicy::DropNewest
+            && self.retention.max_bytes > 0
+            && self.size_bytes >= self.retention.max_bytes
+    }
+
+    pub fn record_drops(&mut self, reason: DropReason, n: u64) {
+        self.dropped.add(reason, n);
+        self.unreported.add(reason, n);
+    }
+
+    pub fn dropped(&self) -> DropCounts {
+        self.dropped
+    }
+
+    pub fn unreported_drops(&self) -> DropCounts {
+        let mut drops = self.unreported;
+        for reporting in self.reporting.values() {
+            drops.subtract(reporting);
         }
-        let mut min_unacked = self.next_id;
-        for id in 0..self.next_id {
-            if !self.acked.contains(&id) {
-                min_unacked = id;
-                break;
+        drops
+    }
+
+    pub fn drops_sent_in(&mut self, batch_id: &str, drops: DropCounts) {
+        if !drops.is_empty() {
+            self.reporting.insert(batch_id.to_string(), drops);
+        }
+    }
+
+    pub fn enforce_retention(&mut self, now: SystemTime) -> io::Result<DropCounts> {
+        let mut drops = DropCounts::default();
+        let mut segments = Vec::new();
+
+        for idx in self.closed_segments() {
+            let expired = match self.retention.max_age {
+                Some(max_age) => fs::metadata(self.segment_path(idx))?
+                    .modified()
+                    .ok()
+                    .and_then(|m| now.duration_since(m).ok())
+                    .is_some_and(|age| age > max_age),
+                None => false,
+            };
+            if expired {
+                drops.add(DropReason::Age, self.remove_segment(idx)?);
+            } else if self.segments[&idx].unacked.is_empty() {
+                self.remove_segment(idx)?;
+            } else {
+                segments.push(idx);
+            }
+        }
+
+        match self.retention.drop_policy {
+            DropPolicy::DropOldest => {
+                for idx in segments {
+                    if !self.retention.over_quota(self.size_bytes) {
+                        break;
+                    }
+                    drops.add(DropReason::Oldest, self.remove_segment(idx)?);
+                }
+            }
+            DropPolicy::DropNewest => {}
+            DropPolicy::Downsample => {
+                while self.retention.over_quota(self.size_bytes) && !segments.is_empty() {
+                    let mut thinned = false;
+                    for idx in &segments {
+                        if !self.retention.over_quota(self.size_bytes) {
+                            break;
+                        }
+                        if let Some(n) = self.downsample_segment(*idx)? {
+                            drops.add(DropReason::Downsample, n);
+                            thinned = true;
+                        }
+                    }
+                    if !thinned {
+                        let oldest = segments.remove(0);
+                        drops.add(DropReason::Downsample, self.remove_segment(oldest)?);
+                    }
+                }
+            }
+        }
+
+        self.dropped.merge(&drops);
+        self.unreported.merge(&drops);
+        Ok(drops)
+    }
+
+    pub fn compact_step(&mut self) -> io::Result<Option<u64>> {
+        let candidate = self
+            .segments
+            .iter()
+            .filter(|(idx, info)| {
+                **idx != self.segment_index && !info.acked.is_empty() && !info.unacked.is_empty()
+            })
+            .map(|(idx, info)| {
+                let total = info.acked.len() + info.unacked.len();
+                (*idx, info.acked.len() as f64 / total as f64)
+            })
+            .filter(|(_, ratio)| *ratio >= COMPACT_MIN_ACKED_RATIO)
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        let Some((idx, _)) = candidate else {
+            return Ok(None);
+        };
+
+        let before = fs::metadata(self.segment_path(idx))?.len();
+        let unacked = self.segments[&idx].unacked.clone();
+        let after = self.rewrite_segment(idx, &unacked)?;
+        Ok(Some(before.saturating_sub(after)))
+    }
+
+    fn closed_segments(&self) -> Vec<u64> {
+        self.segments
+            .keys()
+            .copied()
+            .filter(|idx| *idx != self.segment_index)
+            .collect()
+    }
+
+    fn segment_path(&self, index: u64) -> PathBuf {
+        Segment::path_for(&self.dir, index)
+    }
+
+    fn release_if_acked(&mut self, index: u64) -> io::Result<()> {
+        let done = index != self.segment_index
+            && self
+                .segments
+                .get(&index)
+                .is_some_and(|info| info.unacked.is_empty());
+        if done {
+            self.remove_segment(index)?;
+        }
+        Ok(())
+    }
+
+    fn remove_segment(&mut self, index: u64) -> io::Result<u64> {
+        let path = self.segment_path(index);
+        let len = fs::metadata(&path)?.len();
+        fs::remove_file(&path)?;
+        self.size_bytes = self.size_bytes.saturating_sub(len);
+
+        let info = self.segments.remove(&index).unwrap_or_default();
+        for id in &info.unacked {
+            self.forget(*id);
+        }
+        Ok(info.unacked.len() as u64)
+    }
+
+    fn downsample_segment(&mut self, index: u64) -> io::Result<Option<u64>> {
+        let info = &self.segments[&index];
+        if info.unacked.len() <= 1 && info.acked.is_empty() {
+            return Ok(None);
+        }
+
+        let keep: BTreeSet<u64> = if info.unacked.len() > 1 {
+            info.unacked.iter().step_by(2).copied().collect()
+        } else {
+            info.unacked.clone()
+        };
+        let dropped: Vec<u64> = info.unacked.difference(&keep).copied().collect();
+        for id in &dropped {
+            self.forget(*id);
+        }
+        self.rewrite_segment(index, &keep)?;
+        Ok(Some(dropped.len() as u64))
+    }
+
+    fn rewrite_segment(&mut self, index: u64, keep: &BTreeSet<u64>) -> io::Result<u64> {
+        let path = self.segment_path(index);
+        let old_len = fs::metadata(&path)?.len();
+        let records: Vec<Record> = Segment::read_all(&path)?
+            .into_iter()
+            .filter(|r| keep.contains(&r.id))
+            .collect();
+        Segment::rewrite(&path, &records)?;
+        let new_len = fs::metadata(&path)?.len();
+        self.size_bytes = self.size_bytes.saturating_sub(old_len) + new_len;
+
+        if let Some(info) = self.segments.get_mut(&index) {
+            info.acked.clear();
+            info.unacked = keep.clone();
+        }
+        Ok(new_len)
+    }
+
+    fn forget(&mut self, record_id: u64) {
+        if let Some(p) = self.pending.remove(&record_id) {
+            if let Some(batch_id) = p.batch_id {
+                self.by_batch.remove(&batch_id);
+                self.reporting.remove(&batch_id);
             }
         }
-        min_unacked
+    }
+
+    fn head_seq(&self) -> u64 {
+        self.pending.keys().next().copied().unwrap_or(self.next_id)
     }
 
     fn rotate(&mut self) -> io::Result<()> {
+        let previous = self.segment_index;
         self.segment_index += 1;
         self.current = Segment::create(&self.dir, self.segment_index)?;
-        Ok(())
+        self.segments
+            .insert(self.segment_index, SegmentInfo::default());
+        self.release_if_acked(previous)
     }
 }
 
+fn batch_id_of(data: &[u8]) -> Option<String> {
+    Batch::decode(data)
+        .ok()
+        .map(|b| b.batch_id)
+        .filter(|id| !id.is_empty())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -230,4 +605,275 @@ mod tests {
             .collect();
         assert!(logs.len() > 1, "should have rotated into multiple segments");
     }
+
+    fn quota(max_bytes: u64, drop_policy: DropPolicy) -> RetentionPolicy {
+        RetentionPolicy {
+            max_age: None,
+            max_bytes,
+            drop_policy,
+        }
+    }
+
+    fn fill(wal: &mut Wal, n: usize) {
+        for i in 0..n {
+            wal.append(format!("record-{i:03}").into_bytes()).unwrap();
+        }
+    }
+
+    #[test]
+    fn drop_oldest_keeps_wal_under_quota() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut wal = Wal::open(dir.path(), false, 50).unwrap();
+        fill(&mut wal, 20);
+        wal.set_retention(quota(150, DropPolicy::DropOldest));
+
+        let drops = wal.enforce_retention(SystemTime::now()).unwrap();
+
+        assert!(drops.oldest > 0);
+        assert!(wal.size_bytes() <= 150);
+        let unacked = wal.iter_unacked().unwrap();
+        assert_eq!(unacked.len() as u64 + drops.oldest, 20);
+        assert_eq!(unacked.last().unwrap().1, b"record-019");
+        assert_eq!(wal.unreported_drops(), drops);
+    }
+
+    #[test]
+    fn age_retention_drops_expired_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut wal = Wal::open(dir.path(), false, 50).unwrap();
+        fill(&mut wal, 10);
+        wal.set_retention(RetentionPolicy {
+            max_age: Some(std::time::Duration::from_secs(7 * 24 * 3600)),
+            ..RetentionPolicy::unlimited()
+        });
+
+        assert!(wal.enforce_retention(SystemTime::now()).unwrap().is_empty());
+
+        let later = SystemTime::now() + std::time::Duration::from_secs(8 * 24 * 3600);
+        let drops = wal.enforce_retention(later).unwrap();
+        assert!(drops.age > 0);
+        assert_eq!(wal.iter_unacked().unwrap().len() as u64, 10 - drops.age);
+    }
+
+    #[test]
+    fn downsample_thins_old_batches() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut wal = Wal::open(dir.path(), false, 100).unwrap();
+        fill(&mut wal, 20);
+        let before = wal.size_bytes();
+        wal.set_retention(quota(before * 3 / 4, DropPolicy::Downsample));
+
+        let drops = wal.enforce_retention(SystemTime::now()).unwrap();
+
+        assert!(drops.downsample > 0);
+        assert!(wal.size_bytes() <= before * 3 / 4);
+        let unacked = wal.iter_unacked().unwrap();
+        assert_eq!(unacked[0].1, b"record-000");
+        assert_ne!(unacked[1].1, b"record-001");
+        assert_eq!(unacked.last().unwrap().1, b"record-019");
+    }
+
+    #[test]
+    fn drop_newest_rejects_writes_until_acked_segments_are_reclaimed() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut wal = Wal::open(dir.path(), false, 50).unwrap();
+        fill(&mut wal, 10);
+        wal.set_retention(quota(wal.size_bytes(), DropPolicy::DropNewest));
+        assert!(wal.rejects_writes());
+        assert!(wal.enforce_retention(SystemTime::now()).unwrap().is_empty());
+        assert!(wal.rejects_writes());
+
+        for id in 0..10 {
+            wal.ack(id);
+        }
+        wal.enforce_retention(SystemTime::now()).unwrap();
+        assert!(!wal.rejects_writes());
+        assert!(wal.dropped().is_empty());
+    }
+
+    fn log_count(dir: &Path) -> usize {
+        fs::read_dir(dir)
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().extension().is_some_and(|ext| ext == "log"))
+            .count()
+    }
+
+    fn append_batch(wal: &mut Wal, batch_id: &str) -> u64 {
+        let batch = Batch {
+            batch_id: batch_id.into(),
+            ..Default::default()
+        };
+        wal.append(batch.encode_to_vec()).unwrap()
+    }
+
+    #[test]
+    fn ack_batch_uses_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut wal = Wal::open(dir.path(), false, 1024 * 1024).unwrap();
+        append_batch(&mut wal, "b1");
+        let id2 = append_batch(&mut wal, "b2");
+
+        assert_eq!(wal.ack_batch("b2"), Some(id2));
+        assert_eq!(wal.ack_batch("b2"), None);
+        assert_eq!(wal.ack_batch("missing"), None);
+        assert_eq!(wal.unacked_count().unwrap(), 1);
+
+        let reopened = {
+            wal.save_meta().unwrap();
+            Wal::open(dir.path(), false, 1024 * 1024).unwrap()
+        };
+        assert_eq!(reopened.unacked_count().unwrap(), 1);
+        assert!(reopened.by_batch.contains_key("b1"));
+    }
+
+    #[test]
+    fn read_due_selects_from_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut wal = Wal::open(dir.path(), false, 60).unwrap();
+        for i in 0..6 {
+            append_batch(&mut wal, &format!("b{i}"));
+        }
+        wal.append(b"not-a-batch".to_vec()).unwrap();
+
+        let due = wal.read_due(2, |id| id != "b0").unwrap();
+        let ids: Vec<u64> = due.iter().map(|e| e.0).collect();
+        assert_eq!(ids, vec![1, 2]);
+
+        assert_eq!(wal.read_due(usize::MAX, |_| true).unwrap().len(), 6);
+    }
+
+    #[test]
+    fn read_due_drops_records_without_batch_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut wal = Wal::open(dir.path(), false, 1024 * 1024).unwrap();
+        wal.append(b"not-a-batch".to_vec()).unwrap();
+        append_batch(&mut wal, "");
+        append_batch(&mut wal, "b1");
+
+        let due = wal.read_due(usize::MAX, |_| true).unwrap();
+
+        assert_eq!(due.iter().map(|e| e.0).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(wal.unacked_count().unwrap(), 1);
+        assert_eq!(wal.dropped().rejected, 2);
+        assert_eq!(wal.save_meta().unwrap().head_seq, 2);
+    }
+
+    #[test]
+    fn meta_stores_low_water_mark_and_sparse_acks() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut wal = Wal::open(dir.path(), false, 1024 * 1024).unwrap();
+        fill(&mut wal, 10);
+        for id in [0, 1, 2, 4, 7] {
+            wal.ack(id);
+        }
+        let meta = wal.save_meta().unwrap();
+        assert_eq!(meta.head_seq, 3);
+        assert_eq!(meta.acked_ids, vec![4, 7]);
+        assert_eq!(meta.unacked_count(), 5);
+
+        let wal = Wal::open(dir.path(), false, 1024 * 1024).unwrap();
+        let ids: Vec<u64> = wal.iter_unacked().unwrap().iter().map(|e| e.0).collect();
+        assert_eq!(ids, vec![3, 5, 6, 8, 9]);
+    }
+
+    #[test]
+    fn fully_acked_segments_are_deleted() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut wal = Wal::open(dir.path(), false, 50).unwrap();
+        fill(&mut wal, 10);
+        let segments = log_count(dir.path());
+        let size = wal.size_bytes();
+
+        wal.ack(0);
+        wal.ack(1);
+        assert!(log_count(dir.path()) < segments);
+        assert!(wal.size_bytes() < size);
+
+        for id in 2..10 {
+            wal.ack(id);
+        }
+        assert_eq!(log_count(dir.path()), 1);
+        assert_eq!(wal.save_meta().unwrap().head_seq, 10);
+    }
+
+    #[test]
+    fn compact_step_rewrites_mostly_acked_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut wal = Wal::open(dir.path(), false, 100).unwrap();
+        fill(&mut wal, 12);
+        assert_eq!(wal.compact_step().unwrap(), None);
+
+        for id in [0, 1, 2, 6, 7] {
+            wal.ack(id);
+        }
+        let size = wal.size_bytes();
+        let mut reclaimed = 0;
+        while let Some(bytes) = wal.compact_step().unwrap() {
+            reclaimed += bytes;
+        }
+        assert!(reclaimed > 0);
+        assert_eq!(wal.size_bytes(), size - reclaimed);
+
+        let ids: Vec<u64> = wal.iter_unacked().unwrap().iter().map(|e| e.0).collect();
+        assert_eq!(ids, vec![3, 4, 5, 8, 9, 10, 11]);
+        wal.save_meta().unwrap();
+        let wal = Wal::open(dir.path(), false, 100).unwrap();
+        assert_eq!(wal.unacked_count().unwrap(), 7);
+    }
+
+    #[test]
+    fn unreported_drops_clear_only_when_the_carrying_batch_is_acked() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let mut wal = Wal::open(dir.path(), false, 1024).unwrap();
+            wal.record_drops(DropReason::Newest, 3);
+            wal.save_meta().unwrap();
+        }
+        let mut wal = Wal::open(dir.path(), false, 1024).unwrap();
+        let pending = wal.unreported_drops();
+        assert_eq!(pending.newest, 3);
+        append_batch(&mut wal, "b1");
+        wal.drops_sent_in("b1", pending);
+        assert!(wal.unreported_drops().is_empty());
+
+        wal.save_meta().unwrap();
+        let mut wal = Wal::open(dir.path(), false, 1024).unwrap();
+        assert!(wal.unreported_drops().is_empty());
+        wal.ack_batch("b1");
+        wal.save_meta().unwrap();
+        let wal = Wal::open(dir.path(), false, 1024).unwrap();
+        assert!(wal.unreported_drops().is_empty());
+        assert!(wal.dropped().is_empty());
+    }
+
+    #[test]
+    fn rejected_batch_leaves_the_wal_and_keeps_its_drops_unreported() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut wal = Wal::open(dir.path(), false, 1024).unwrap();
+        wal.record_drops(DropReason::Newest, 1);
+        append_batch(&mut wal, "b1");
+        wal.drops_sent_in("b1", wal.unreported_drops());
+
+        assert!(wal.reject_batch("b1").is_some());
+        assert!(!wal.has_pending_batch("b1"));
+        assert_eq!(wal.unreported_drops().newest, 1);
+        assert_eq!(wal.unreported_drops().rejected, 1);
+        assert_eq!(wal.dropped().rejected, 1);
+        assert!(wal.reject_batch("b1").is_none());
+        assert_eq!(wal.dropped().rejected, 1);
+    }
+
+    #[test]
+    fn drops_carried_by_a_dropped_batch_are_reported_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut wal = Wal::open(dir.path(), false, 1024).unwrap();
+        wal.record_drops(DropReason::Age, 2);
+        let id = append_batch(&mut wal, "b1");
+        wal.drops_sent_in("b1", wal.unreported_drops());
+        assert!(wal.unreported_drops().is_empty());
+
+        wal.forget(id);
+        assert_eq!(wal.unreported_drops().age, 2);
+    }
 }
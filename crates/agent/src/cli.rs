@@ -3,6 +3,7 @@ use std::path::PathBuf;
 pub struct Args {
     pub config_path: PathBuf,
     pub legacy_mode: bool,
+    pub insecure_no_secret: bool,
 }
 
 const DEFAULT_CONFIG_PATH: &str = "/etc/sentinel/config.yml";
@@ -11,6 +12,7 @@ pub fn parse() -> Args {
     let mut args = std::env::args().skip(1);
     let mut config_path = None;
     let mut legacy_mode = false;
+    let mut insecure_no_secret = false;
 
     while let Some(arg) = args.next() {
         match arg.as_str() {
@@ -23,6 +25,7 @@ pub fn parse() -> Args {
                 println!("Options:");
                 println!("  -c, --config <PATH>  Configuration file path (default: {DEFAULT_CONFIG_PATH})");
                 println!("  --legacy-mode        Use V1 unary gRPC (no streaming)");
+                println!("  --insecure-no-secret Start without an HMAC secret (unsigned traffic, testing only)");
                 println!("  -V, --version        Print version");
                 println!("  -h, --help           Print help");
                 std::process::exit(0);
@@ -37,6 +40,9 @@ pub fn parse() -> Args {
             "--legacy-mode" | "--legacy" => {
                 legacy_mode = true;
             }
+            "--insecure-no-secret" => {
+                insecure_no_secret = true;
+            }
             other => {
                 eprintln!("error: unknown argument '{other}'");
                 std::process::exit(1);
@@ -47,5 +53,6 @@ pub fn parse() -> Args {
     Args {
         config_path: config_path.unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_PATH)),
         legacy_mode,
+        insecure_no_secret,
     }
 }
@@ -0,0 +1,75 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use sysinfo::System;
+
+use super::sample::gauge;
+use super::traits::Collector;
+use sentinel_common::proto::Metric;
+
+pub struct CpuCollector {
+    sys: Mutex<System>,
+    per_core: bool,
+}
+
+impl CpuCollector {
+    pub fn new(per_core: bool) -> Self {
+        let mut sys = System::new();
+        sys.refresh_cpu_usage();
+        Self {
+            sys: Mutex::new(sys),
+            per_core,
+        }
+    }
+}
+
+impl Collector for CpuCollector {
+    fn collect(&self) -> Vec<Metric> {
+        let mut sys = match self.sys.lock() {
+            Ok(s) => s,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        sys.refresh_cpu_usage();
+
+        if !self.per_core {
+            return vec![gauge(
+                "cpu.usage_percent",
+                sys.global_cpu_usage() as f64,
+                HashMap::new(),
+            )];
+        }
+
+        sys.cpus()
+            .iter()
+            .enumerate()
+            .map(|(i, cpu)| {
+                let mut labels = HashMap::new();
+                labels.insert("core".into(), i.to_string());
+                gauge(
+                    &format!("cpu.core.{}.usage_percent", i),
+                    cpu.cpu_usage() as f64,
+                    labels,
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggregate_mode_emits_single_series() {
+        let metrics = CpuCollector::new(false).collect();
+        assert_eq!(metrics.len(), 1);
+        assert_eq!(metrics[0].name, "cpu.usage_percent");
+    }
+
+    #[test]
+    fn per_core_mode_labels_cores() {
+        for m in CpuCollector::new(true).collect() {
+            assert!(m.name.starts_with("cpu.core."));
+            assert!(m.labels.contains_key("core"));
+        }
+    }
+}
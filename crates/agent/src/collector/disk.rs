@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use sysinfo::Disks;
+
+use super::filter::NameFilter;
+use super::sample::gauge;
+use super::traits::Collector;
+use sentinel_common::proto::Metric;
+
+pub struct DiskCollector {
+    disks: Mutex<Disks>,
+    mounts: NameFilter,
+}
+
+impl DiskCollector {
+    pub fn new(mounts: NameFilter) -> Self {
+        Self {
+            disks: Mutex::new(Disks::new_with_refreshed_list()),
+            mounts,
+        }
+    }
+}
+
+impl Collector for DiskCollector {
+    fn collect(&self) -> Vec<Metric> {
+        let mut disks = match self.disks.lock() {
+            Ok(d) => d,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        disks.refresh(true);
+
+        disks
+            .iter()
+            .filter(|disk| self.mounts.allows(&disk.mount_point().to_string_lossy()))
+            .flat_map(|disk| {
+                let name = disk.name().to_string_lossy().to_string();
+                let dev = if name.is_empty() {
+                    disk.mount_point()
+                        .to_string_lossy()
+                        .replace(['\\', '/'], "_")
+                } else {
+                    name
+                };
+                let mut labels = HashMap::new();
+                labels.insert("device".into(), dev.clone());
+                vec![
+                    gauge(
+                        &format!("disk.{}.total_bytes", dev),
+                        disk.total_space() as f64,
+                        labels.clone(),
+                    ),
+                    gauge(
+                        &format!("disk.{}.available_bytes", dev),
+                        disk.available_space() as f64,
+                        labels,
+                    ),
+                ]
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exclude_all_mounts_emits_nothing() {
+        let collector = DiskCollector::new(NameFilter::new(vec![], vec!["*".into()]));
+        assert!(collector.collect().is_empty());
+    }
+}
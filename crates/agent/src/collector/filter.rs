@@ -0,0 +1,81 @@
+#[derive(Debug, Clone, Default)]
+pub struct NameFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl NameFilter {
+    pub fn new(include: Vec<String>, exclude: Vec<String>) -> Self {
+        Self { include, exclude }
+    }
+
+    pub fn allows(&self, name: &str) -> bool {
+        if self.exclude.iter().any(|p| glob_match(p, name)) {
+            return false;
+        }
+        self.include.is_empty() || self.include.iter().any(|p| glob_match(p, name))
+    }
+}
+
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let n: Vec<char> = name.chars().collect();
+    let (mut pi, mut ni) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while ni < n.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == n[ni]) {
+            pi += 1;
+            ni += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ni));
+            pi += 1;
+        } else if let Some((sp, sn)) = star {
+            pi = sp + 1;
+            ni = sn + 1;
+            star = Some((sp, sn + 1));
+        } else {
+            return false;
+        }
+    }
+
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_wildcards() {
+        assert!(glob_match("veth*", "veth12ab"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("/var/*/docker", "/var/lib/docker"));
+        assert!(glob_match("eth?", "eth0"));
+        assert!(!glob_match("eth?", "eth10"));
+        assert!(!glob_match("docker*", "eth0"));
+        assert!(glob_match("/", "/"));
+        assert!(!glob_match("/", "/boot"));
+    }
+
+    #[test]
+    fn empty_filter_allows_everything() {
+        assert!(NameFilter::default().allows("anything"));
+    }
+
+    #[test]
+    fn exclude_wins_over_include() {
+        let f = NameFilter::new(vec!["*".into()], vec!["veth*".into(), "docker0".into()]);
+        assert!(f.allows("eth0"));
+        assert!(!f.allows("veth9f"));
+        assert!(!f.allows("docker0"));
+    }
+
+    #[test]
+    fn include_restricts() {
+        let f = NameFilter::new(vec!["/".into(), "/data*".into()], vec![]);
+        assert!(f.allows("/"));
+        assert!(f.allows("/data1"));
+        assert!(!f.allows("/boot"));
+    }
+}
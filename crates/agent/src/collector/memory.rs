@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use sysinfo::System;
+
+use super::sample::gauge;
+use super::traits::Collector;
+use sentinel_common::proto::Metric;
+
+pub struct MemoryCollector {
+    sys: Mutex<System>,
+}
+
+impl Default for MemoryCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryCollector {
+    pub fn new() -> Self {
+        Self {
+            sys: Mutex::new(System::new()),
+        }
+    }
+}
+
+impl Collector for MemoryCollector {
+    fn collect(&self) -> Vec<Metric> {
+        let mut sys = match self.sys.lock() {
+            Ok(s) => s,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        sys.refresh_memory();
+
+        let empty = HashMap::new();
+        vec![
+            gauge("mem.total_bytes", sys.total_memory() as f64, empty.clone()),
+            gauge("mem.used_bytes", sys.used_memory() as f64, empty.clone()),
+            gauge(
+                "mem.available_bytes",
+                sys.available_memory() as f64,
+                empty.clone(),
+            ),
+            gauge(
+                "mem.swap_total_bytes",
+                sys.total_swap() as f64,
+                empty.clone(),
+            ),
+            gauge("mem.swap_used_bytes", sys.used_swap() as f64, empty),
+        ]
+    }
+}
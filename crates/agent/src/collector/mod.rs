@@ -1,7 +1,24 @@
+mod cpu;
+mod disk;
+mod filter;
+mod memory;
 mod naming;
+mod network;
+mod plan;
+mod process;
+mod sample;
 mod system;
 mod traits;
+mod uptime;
 
+pub use cpu::CpuCollector;
+pub use disk::DiskCollector;
+pub use filter::{glob_match, NameFilter};
+pub use memory::MemoryCollector;
 pub use naming::normalize_name;
+pub use network::NetworkCollector;
+pub use plan::{plan, CollectorGroup};
+pub use process::ProcessCollector;
 pub use system::SystemCollector;
 pub use traits::Collector;
+pub use uptime::UptimeCollector;
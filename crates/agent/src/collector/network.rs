@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use sysinfo::Networks;
+
+use super::filter::NameFilter;
+use super::sample::counter;
+use super::traits::Collector;
+use sentinel_common::proto::Metric;
+
+pub struct NetworkCollector {
+    networks: Mutex<Networks>,
+    interfaces: NameFilter,
+}
+
+impl NetworkCollector {
+    pub fn new(interfaces: NameFilter) -> Self {
+        Self {
+            networks: Mutex::new(Networks::new_with_refreshed_list()),
+            interfaces,
+        }
+    }
+}
+
+impl Collector for NetworkCollector {
+    fn collect(&self) -> Vec<Metric> {
+        let mut networks = match self.networks.lock() {
+            Ok(n) => n,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        networks.refresh(true);
+
+        networks
+            .iter()
+            .filter(|(iface, _)| self.interfaces.allows(iface))
+            .flat_map(|(iface, data)| {
+                let mut labels = HashMap::new();
+                labels.insert("interface".into(), iface.clone());
+                vec![
+                    counter(
+                        &format!("net.{}.bytes_recv", iface),
+                        data.total_received() as f64,
+                        labels.clone(),
+                    ),
+                    counter(
+                        &format!("net.{}.bytes_sent", iface),
+                        data.total_transmitted() as f64,
+                        labels,
+                    ),
+                ]
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn excluded_interfaces_are_skipped() {
+        let collector = NetworkCollector::new(NameFilter::new(vec![], vec!["lo".into()]));
+        for m in collector.collect() {
+            assert_ne!(m.labels.get("interface").map(String::as_str), Some("lo"));
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use super::cpu::CpuCollector;
+use super::disk::DiskCollector;
+use super::filter::NameFilter;
+use super::memory::MemoryCollector;
+use super::network::NetworkCollector;
+use super::process::ProcessCollector;
+use super::system::SystemCollector;
+use super::traits::Collector;
+use super::uptime::UptimeCollector;
+use crate::config::{CollectConfig, FilteredCollectorConfig};
+
+pub struct CollectorGroup {
+    pub interval: Duration,
+    pub names: Vec<&'static str>,
+    pub collector: SystemCollector,
+}
+
+type Group = (Vec<&'static str>, Vec<Box<dyn Collector>>);
+
+pub fn plan(cfg: &CollectConfig) -> Vec<CollectorGroup> {
+    let base = cfg.interval_seconds;
+    let toggles = &cfg.metrics;
+    let mut groups: BTreeMap<u64, Group> = BTreeMap::new();
+
+    let mut add = |name: &'static str, interval: Option<u64>, collector: Box<dyn Collector>| {
+        let entry = groups.entry(interval.unwrap_or(base)).or_default();
+        entry.0.push(name);
+        entry.1.push(collector);
+    };
+
+    if toggles.cpu {
+        add(
+            "cpu",
+            cfg.cpu.interval_seconds,
+            Box::new(CpuCollector::new(cfg.cpu.per_core)),
+        );
+    }
+    if toggles.mem {
+        add(
+            "mem",
+            cfg.mem.interval_seconds,
+            Box::<MemoryCollector>::default(),
+        );
+    }
+    if toggles.disk {
+        add(
+            "disk",
+            cfg.disk.interval_seconds,
+            Box::new(DiskCollector::new(filter(&cfg.disk))),
+        );
+    }
+    if toggles.network {
+        add(
+            "network",
+            cfg.network.interval_seconds,
+            Box::new(NetworkCollector::new(filter(&cfg.network))),
+        );
+    }
+    if toggles.uptime {
+        add(
+            "uptime",
+            cfg.uptime.interval_seconds,
+            Box::new(UptimeCollector),
+        );
+    }
+    if toggles.process {
+        add(
+            "process",
+            cfg.process.interval_seconds,
+            Box::<ProcessCollector>::default(),
+        );
+    }
+
+    groups
+        .into_iter()
+        .map(|(secs, (names, parts))| CollectorGroup {
+            interval: Duration::from_secs(secs),
+            names,
+            collector: SystemCollector::from_parts(parts),
+        })
+        .collect()
+}
+
+fn filter(cfg: &FilteredCollectorConfig) -> NameFilter {
+    NameFilter::new(cfg.include.clone(), cfg.exclude.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::load_from_str;
+
+    fn collect_config(extra: &str) -> CollectConfig {
+        let yaml = format!(
+            "server: https://s\ncollect:\n  interval_seconds: 10\n{extra}\nbuffer:\n  wal_dir: /tmp/w\nsecurity: {{}}\n"
+        );
+        load_from_str(&yaml).unwrap().collect
+    }
+
+    #[test]
+    fn defaults_to_single_group() {
+        let groups = plan(&collect_config("  metrics: {}"));
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].interval, Duration::from_secs(10));
+        assert_eq!(
+            groups[0].names,
+            vec!["cpu", "mem", "disk", "network", "uptime", "process"]
+        );
+    }
+
+    #[test]
+    fn toggles_remove_collectors() {
+        let groups = plan(&collect_config(
+            "  metrics:\n    cpu: false\n    network: false\n    process: false",
+        ));
+        assert_eq!(groups[0].names, vec!["mem", "disk", "uptime"]);
+    }
+
+    #[test]
+    fn all_disabled_yields_no_groups() {
+        let groups = plan(&collect_config(
+            "  metrics: {cpu: false, mem: false, disk: false, network: false, process: false, uptime: false}",
+        ));
+        assert!(groups.is_empty());
+    }
+
+    #[test]
+    fn per_collector_intervals_split_groups() {
+        let groups = plan(&collect_config(
+            "  metrics: {}\n  disk:\n    interval_seconds: 60\n  uptime:\n    interval_seconds: 60",
+        ));
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].interval, Duration::from_secs(10));
+        assert_eq!(groups[1].interval, Duration::from_secs(60));
+        assert_eq!(groups[1].names, vec!["disk", "uptime"]);
+    }
+}
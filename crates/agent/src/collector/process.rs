@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+
+use super::sample::gauge;
+use super::traits::Collector;
+use sentinel_common::proto::Metric;
+
+pub struct ProcessCollector {
+    sys: Mutex<System>,
+}
+
+impl Default for ProcessCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProcessCollector {
+    pub fn new() -> Self {
+        Self {
+            sys: Mutex::new(System::new()),
+        }
+    }
+}
+
+impl Collector for ProcessCollector {
+    fn collect(&self) -> Vec<Metric> {
+        let mut sys = match self.sys.lock() {
+            Ok(s) => s,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        sys.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing(),
+        );
+
+        vec![gauge(
+            "process.count_total",
+            sys.processes().len() as f64,
+            HashMap::new(),
+        )]
+    }
+}
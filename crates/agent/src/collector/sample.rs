@@ -0,0 +1,29 @@
+use std::collections::HashMap;
+
+use super::naming::normalize_name;
+use sentinel_common::proto::{metric::Value, Metric, MetricType};
+
+pub fn gauge(name: &str, value: f64, labels: HashMap<String, String>) -> Metric {
+    sample(name, MetricType::Gauge, value, labels)
+}
+
+pub fn counter(name: &str, value: f64, labels: HashMap<String, String>) -> Metric {
+    sample(name, MetricType::Counter, value, labels)
+}
+
+fn sample(name: &str, rtype: MetricType, value: f64, labels: HashMap<String, String>) -> Metric {
+    Metric {
+        name: normalize_name(name),
+        labels,
+        rtype: rtype as i32,
+        value: Some(Value::ValueDouble(value)),
+        timestamp_ms: now_ms(),
+    }
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
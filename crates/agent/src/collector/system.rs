@@ -1,14 +1,15 @@
-use std::collections::HashMap;
-use sysinfo::{Disks, Networks, System};
-
-use super::naming::normalize_name;
+use super::cpu::CpuCollector;
+use super::disk::DiskCollector;
+use super::filter::NameFilter;
+use super::memory::MemoryCollector;
+use super::network::NetworkCollector;
+use super::process::ProcessCollector;
 use super::traits::Collector;
-use sentinel_common::proto::{metric::Value, Metric, MetricType};
+use super::uptime::UptimeCollector;
+use sentinel_common::proto::Metric;
 
 pub struct SystemCollector {
-    sys: System,
-    disks: Disks,
-    networks: Networks,
+    parts: Vec<Box<dyn Collector>>,
 }
 
 impl Default for SystemCollector {
@@ -19,164 +20,28 @@ impl Default for SystemCollector {
 
 impl SystemCollector {
     pub fn new() -> Self {
-        Self {
-            sys: System::new_all(),
-            disks: Disks::new_with_refreshed_list(),
-            networks: Networks::new_with_refreshed_list(),
-        }
-    }
-
-    fn now_ms() -> i64 {
-        std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as i64
-    }
-
-    fn gauge(name: &str, value: f64, labels: HashMap<String, String>) -> Metric {
-        Metric {
-            name: normalize_name(name),
-            labels,
-            rtype: MetricType::Gauge as i32,
-            value: Some(Value::ValueDouble(value)),
-            timestamp_ms: Self::now_ms(),
-        }
-    }
-
-    fn counter(name: &str, value: f64, labels: HashMap<String, String>) -> Metric {
-        Metric {
-            name: normalize_name(name),
-            labels,
-            rtype: MetricType::Counter as i32,
-            value: Some(Value::ValueDouble(value)),
-            timestamp_ms: Self::now_ms(),
-        }
-    }
-
-    fn collect_cpu(&self) -> Vec<Metric> {
-        self.sys
-            .cpus()
-            .iter()
-            .enumerate()
-            .map(|(i, cpu)| {
-                let mut labels = HashMap::new();
-                labels.insert("core".into(), i.to_string());
-                Self::gauge(
-                    &format!("cpu.core.{}.usage_percent", i),
-                    cpu.cpu_usage() as f64,
-                    labels,
-                )
-            })
-            .collect()
-    }
-
-    fn collect_memory(&self) -> Vec<Metric> {
-        let empty = HashMap::new();
-        vec![
-            Self::gauge(
-                "mem.total_bytes",
-                self.sys.total_memory() as f64,
-                empty.clone(),
-            ),
-            Self::gauge(
-                "mem.used_bytes",
-                self.sys.used_memory() as f64,
-                empty.clone(),
-            ),
-            Self::gauge(
-                "mem.available_bytes",
-                self.sys.available_memory() as f64,
-                empty.clone(),
-            ),
-            Self::gauge(
-                "mem.swap_total_bytes",
-                self.sys.total_swap() as f64,
-                empty.clone(),
-            ),
-            Self::gauge("mem.swap_used_bytes", self.sys.used_swap() as f64, empty),
-        ]
-    }
-
-    fn collect_disks(&self) -> Vec<Metric> {
-        self.disks
-            .iter()
-            .flat_map(|disk| {
-                let name = disk.name().to_string_lossy().to_string();
-                let dev = if name.is_empty() {
-                    disk.mount_point()
-                        .to_string_lossy()
-                        .replace(['\\', '/'], "_")
-                } else {
-                    name
-                };
-                let mut labels = HashMap::new();
-                labels.insert("device".into(), dev.clone());
-                vec![
-                    Self::gauge(
-                        &format!("disk.{}.total_bytes", dev),
-                        disk.total_space() as f64,
-                        labels.clone(),
-                    ),
-                    Self::gauge(
-                        &format!("disk.{}.available_bytes", dev),
-                        disk.available_space() as f64,
-                        labels,
-                    ),
-                ]
-            })
-            .collect()
-    }
-
-    fn collect_network(&self) -> Vec<Metric> {
-        self.networks
-            .iter()
-            .flat_map(|(iface, data)| {
-                let mut labels = HashMap::new();
-                labels.insert("interface".into(), iface.clone());
-                vec![
-                    Self::counter(
-                        &format!("net.{}.bytes_recv", iface),
-                        data.total_received() as f64,
-                        labels.clone(),
-                    ),
-                    Self::counter(
-                        &format!("net.{}.bytes_sent", iface),
-                        data.total_transmitted() as f64,
-                        labels,
-                    ),
-                ]
-            })
-            .collect()
+        Self::from_parts(vec![
+            Box::new(CpuCollector::new(true)),
+            Box::new(MemoryCollector::new()),
+            Box::new(DiskCollector::new(NameFilter::default())),
+            Box::new(NetworkCollector::new(NameFilter::default())),
+            Box::new(UptimeCollector),
+            Box::new(ProcessCollector::new()),
+        ])
     }
 
-    fn collect_uptime(&self) -> Vec<Metric> {
-        vec![Self::gauge(
-            "uptime_seconds",
-            System::uptime() as f64,
-            HashMap::new(),
-        )]
+    pub fn from_parts(parts: Vec<Box<dyn Collector>>) -> Self {
+        Self { parts }
     }
 
-    fn collect_processes(&self) -> Vec<Metric> {
-        let total = self.sys.processes().len();
-        vec![Self::gauge(
-            "process.count_total",
-            total as f64,
-            HashMap::new(),
-        )]
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
     }
 }
 
 impl Collector for SystemCollector {
     fn collect(&self) -> Vec<Metric> {
-        let mut metrics = Vec::new();
-        metrics.extend(self.collect_cpu());
-        metrics.extend(self.collect_memory());
-        metrics.extend(self.collect_disks());
-        metrics.extend(self.collect_network());
-        metrics.extend(self.collect_uptime());
-        metrics.extend(self.collect_processes());
-        metrics
+        self.parts.iter().flat_map(|p| p.collect()).collect()
     }
 }
 
@@ -0,0 +1,19 @@
+use std::collections::HashMap;
+use sysinfo::System;
+
+use super::sample::gauge;
+use super::traits::Collector;
+use sentinel_common::proto::Metric;
+
+#[derive(Default)]
+pub struct UptimeCollector;
+
+impl Collector for UptimeCollector {
+    fn collect(&self) -> Vec<Metric> {
+        vec![gauge(
+            "uptime_seconds",
+            System::uptime() as f64,
+            HashMap::new(),
+        )]
+    }
+}
@@ -1,4 +1,6 @@
 use super::schema::AgentConfig;
+use crate::buffer::DropPolicy;
+use crate::security::KeyStoreKind;
 use std::path::Path;
 
 #[derive(Debug)]
@@ -52,11 +54,46 @@ fn validate(cfg: &AgentConfig) -> Result<(), LoadError> {
             "collect.interval_seconds must be > 0".into(),
         ));
     }
+    let c = &cfg.collect;
+    for (name, interval) in [
+        ("cpu", c.cpu.interval_seconds),
+        ("mem", c.mem.interval_seconds),
+        ("disk", c.disk.interval_seconds),
+        ("network", c.network.interval_seconds),
+        ("process", c.process.interval_seconds),
+        ("uptime", c.uptime.interval_seconds),
+    ] {
+        if interval == Some(0) {
+            return Err(LoadError::Validation(format!(
+                "collect.{name}.interval_seconds must be > 0"
+            )));
+        }
+    }
     if cfg.buffer.wal_dir.is_empty() {
         return Err(LoadError::Validation(
             "buffer.wal_dir must not be empty".into(),
         ));
     }
+    DropPolicy::parse(&cfg.buffer.drop_policy).map_err(LoadError::Validation)?;
+    let segment_bytes = cfg.buffer.segment_size_mb * 1024 * 1024;
+    if cfg.buffer.max_wal_bytes != 0 && cfg.buffer.max_wal_bytes < 2 * segment_bytes {
+        return Err(LoadError::Validation(
+            "buffer.max_wal_bytes must be 0 (unlimited) or at least two segments".into(),
+        ));
+    }
+    KeyStoreKind::parse(&cfg.security.key_store).map_err(LoadError::Validation)?;
+    if cfg.stream.max_in_flight == 0 || cfg.stream.ack_timeout_secs == 0 {
+        return Err(LoadError::Validation(
+            "stream.max_in_flight and stream.ack_timeout_secs must be > 0".into(),
+        ));
+    }
+    if let Some(url) = &cfg.http_fallback_url {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(LoadError::Validation(
+                "http_fallback_url must be an http(s) URL".into(),
+            ));
+        }
+    }
     Ok(())
 }
 
@@ -113,6 +150,69 @@ security: {}
         assert!(err.to_string().contains("interval_seconds"));
     }
 
+    #[test]
+    fn zero_collector_interval_rejected() {
+        let yaml = r#"
+server: https://localhost
+collect:
+  interval_seconds: 10
+  metrics: {}
+  network:
+    interval_seconds: 0
+buffer:
+  wal_dir: /tmp/wal
+security: {}
+"#;
+        let err = load_from_str(yaml).unwrap_err();
+        assert!(err.to_string().contains("collect.network.interval_seconds"));
+    }
+
+    #[test]
+    fn non_http_fallback_url_rejected() {
+        let yaml = r#"
+server: https://localhost
+http_fallback_url: grpc://localhost:8080
+collect:
+  interval_seconds: 10
+  metrics: {}
+buffer:
+  wal_dir: /tmp/wal
+security: {}
+"#;
+        let err = load_from_str(yaml).unwrap_err();
+        assert!(err.to_string().contains("http_fallback_url"));
+    }
+
+    #[test]
+    fn bad_wal_limits_rejected() {
+        let base = "server: https://localhost\ncollect:\n  interval_seconds: 10\n  metrics: {}\nsecurity: {}\nbuffer:\n  wal_dir: /tmp/wal\n";
+        let err = load_from_str(&format!("{base}  drop_policy: oldest\n")).unwrap_err();
+        assert!(err.to_string().contains("buffer.drop_policy"));
+        let err = load_from_str(&format!("{base}  max_wal_bytes: 1048576\n")).unwrap_err();
+        assert!(err.to_string().contains("buffer.max_wal_bytes"));
+        assert!(load_from_str(&format!(
+            "{base}  max_wal_bytes: 0\n  drop_policy: downsample\n"
+        ))
+        .is_ok());
+    }
+
+    #[test]
+    fn zero_in_flight_window_rejected() {
+        let yaml = r#"
+server: https://localhost
+collect:
+  interval_seconds: 10
+  metrics: {}
+buffer:
+  wal_dir: /tmp/wal
+security: {}
+stream:
+  max_in_flight: 0
+"#;
+        let err = load_from_str(yaml).unwrap_err();
+        assert!(err.to_string().contains("stream.max_in_flight"));
+    }
+
     #[test]
     fn load_from_file_works() {
         let dir = tempfile::tempdir().unwrap();
@@ -5,5 +5,6 @@ mod schema;
 pub use key_store::{EncryptedFileStore, KeyStore, KeyStoreError};
 pub use loader::{load_from_file, load_from_str, LoadError};
 pub use schema::{
-    AgentConfig, BufferConfig, CollectConfig, MetricsToggle, PluginConfig, SecurityConfig,
+    AgentConfig, BufferConfig, CollectConfig, CollectorConfig, CpuCollectorConfig,
+    FilteredCollectorConfig, MetricsToggle, PluginConfig, SecurityConfig, StreamConfig,
 };
@@ -12,6 +12,35 @@ pub struct AgentConfig {
     pub security: SecurityConfig,
     #[serde(default = "default_api_port")]
     pub api_port: u16,
+    #[serde(default)]
+    pub http_fallback_url: Option<String>,
+    #[serde(default)]
+    pub stream: StreamConfig,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct StreamConfig {
+    #[serde(default = "default_max_in_flight")]
+    pub max_in_flight: usize,
+    #[serde(default = "default_ack_timeout_secs")]
+    pub ack_timeout_secs: u64,
+}
+
+impl Default for StreamConfig {
+    fn default() -> Self {
+        Self {
+            max_in_flight: default_max_in_flight(),
+            ack_timeout_secs: default_ack_timeout_secs(),
+        }
+    }
+}
+
+fn default_max_in_flight() -> usize {
+    64
+}
+
+fn default_ack_timeout_secs() -> u64 {
+    30
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -57,6 +86,18 @@ fn default_api_port() -> u16 {
 pub struct CollectConfig {
     pub interval_seconds: u64,
     pub metrics: MetricsToggle,
+    #[serde(default)]
+    pub cpu: CpuCollectorConfig,
+    #[serde(default)]
+    pub mem: CollectorConfig,
+    #[serde(default)]
+    pub disk: FilteredCollectorConfig,
+    #[serde(default)]
+    pub network: FilteredCollectorConfig,
+    #[serde(default)]
+    pub process: CollectorConfig,
+    #[serde(default)]
+    pub uptime: CollectorConfig,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -67,6 +108,45 @@ pub struct MetricsToggle {
     pub mem: bool,
     #[serde(default = "yes")]
     pub disk: bool,
+    #[serde(default = "yes")]
+    pub network: bool,
+    #[serde(default = "yes")]
+    pub process: bool,
+    #[serde(default = "yes")]
+    pub uptime: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct CollectorConfig {
+    #[serde(default)]
+    pub interval_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct CpuCollectorConfig {
+    #[serde(default)]
+    pub interval_seconds: Option<u64>,
+    #[serde(default = "yes")]
+    pub per_core: bool,
+}
+
+impl Default for CpuCollectorConfig {
+    fn default() -> Self {
+        Self {
+            interval_seconds: None,
+            per_core: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+pub struct FilteredCollectorConfig {
+    #[serde(default)]
+    pub interval_seconds: Option<u64>,
+    #[serde(default)]
+    pub include: Vec<String>,
+    #[serde(default)]
+    pub exclude: Vec<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -76,6 +156,10 @@ pub struct BufferConfig {
     pub segment_size_mb: u64,
     #[serde(default = "default_retention_days")]
     pub max_retention_days: u64,
+    #[serde(default = "default_max_wal_bytes")]
+    pub max_wal_bytes: u64,
+    #[serde(default = "default_drop_policy")]
+    pub drop_policy: String,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
@@ -98,6 +182,14 @@ fn default_retention_days() -> u64 {
     7
 }
 
+fn default_max_wal_bytes() -> u64 {
+    1024 * 1024 * 1024
+}
+
+fn default_drop_policy() -> String {
+    "drop_oldest".to_string()
+}
+
 fn default_key_store() -> String {
     "auto".to_string()
 }
@@ -165,10 +257,48 @@ security: {}
         assert!(cfg.collect.metrics.disk);
         assert_eq!(cfg.buffer.segment_size_mb, 16);
         assert_eq!(cfg.buffer.max_retention_days, 7);
+        assert_eq!(cfg.buffer.max_wal_bytes, 1024 * 1024 * 1024);
+        assert_eq!(cfg.buffer.drop_policy, "drop_oldest");
         assert_eq!(cfg.security.key_store, "auto");
         assert_eq!(cfg.security.rotation_check_interval_hours, 24);
         assert!(cfg.plugins.enabled);
         assert_eq!(cfg.plugins.dir, "/var/lib/sentinel/plugins");
         assert_eq!(cfg.plugins.interval_seconds, 30);
+        assert!(cfg.collect.metrics.network);
+        assert!(cfg.collect.metrics.process);
+        assert!(cfg.collect.metrics.uptime);
+        assert!(cfg.collect.cpu.per_core);
+        assert!(cfg.collect.network.exclude.is_empty());
+        assert_eq!(cfg.collect.disk.interval_seconds, None);
+        assert_eq!(cfg.stream.max_in_flight, 64);
+        assert_eq!(cfg.stream.ack_timeout_secs, 30);
+    }
+
+    #[test]
+    fn per_collector_sections() {
+        let yaml = r#"
+server: https://localhost
+collect:
+  interval_seconds: 10
+  metrics:
+    process: false
+  cpu:
+    per_core: false
+  disk:
+    interval_seconds: 60
+    include: ["/", "/data*"]
+  network:
+    exclude: ["veth*", "docker*"]
+buffer:
+  wal_dir: /tmp/wal
+security: {}
+"#;
+        let cfg: AgentConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(!cfg.collect.metrics.process);
+        assert!(cfg.collect.metrics.network);
+        assert!(!cfg.collect.cpu.per_core);
+        assert_eq!(cfg.collect.disk.interval_seconds, Some(60));
+        assert_eq!(cfg.collect.disk.include, vec!["/", "/data*"]);
+        assert_eq!(cfg.collect.network.exclude, vec!["veth*", "docker*"]);
     }
 }
@@ -0,0 +1,105 @@
+use sentinel_common::proto::{command::Action, Command, CommandResult};
+
+use super::controller::RuntimeController;
+
+pub async fn execute_command(cmd: &Command, controller: &RuntimeController) -> CommandResult {
+    let outcome = match &cmd.action {
+        Some(Action::ReloadConfig(_)) => match controller.reload_from_disk().await {
+            Ok(summary) => Ok(summary.to_string()),
+            Err(e) => Err(format!("reload failed: {e}")),
+        },
+        Some(Action::RestartCollector(_)) => {
+            controller.restart_collector().await;
+            Ok("collector restarted".to_string())
+        }
+        Some(Action::UpdateInterval(u)) => {
+            if u.interval_seconds == 0 {
+                Err("interval_seconds must be > 0".to_string())
+            } else {
+                controller.update_interval(u.interval_seconds).await;
+                Ok(format!("interval set to {}s", u.interval_seconds))
+            }
+        }
+        None => Err("command has no action".to_string()),
+    };
+
+    match outcome {
+        Ok(message) => {
+            tracing::info!(target: "system", command_id = %cmd.command_id, %message, "Remote command executed");
+            CommandResult {
+                command_id: cmd.command_id.clone(),
+                success: true,
+                message,
+            }
+        }
+        Err(message) => {
+            tracing::warn!(target: "system", command_id = %cmd.command_id, reason = %message, "Remote command failed");
+            CommandResult {
+                command_id: cmd.command_id.clone(),
+                success: false,
+                message,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sentinel_common::proto::{ReloadConfig, UpdateInterval};
+
+    use crate::control::test_harness::controller;
+
+    fn command(action: Action) -> Command {
+        Command {
+            command_id: "cmd-1".into(),
+            action: Some(action),
+        }
+    }
+
+    #[tokio::test]
+    async fn update_interval_reports_success() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctl = controller(dir.path());
+
+        let result = execute_command(
+            &command(Action::UpdateInterval(UpdateInterval {
+                interval_seconds: 30,
+            })),
+            &ctl,
+        )
+        .await;
+
+        assert!(result.success);
+        assert_eq!(result.command_id, "cmd-1");
+        assert_eq!(ctl.config().await.collect.interval_seconds, 30);
+    }
+
+    #[tokio::test]
+    async fn zero_interval_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctl = controller(dir.path());
+
+        let result = execute_command(
+            &command(Action::UpdateInterval(UpdateInterval {
+                interval_seconds: 0,
+            })),
+            &ctl,
+        )
+        .await;
+
+        assert!(!result.success);
+        assert_eq!(ctl.config().await.collect.interval_seconds, 15);
+    }
+
+    #[tokio::test]
+    async fn reload_without_config_file_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctl = controller(dir.path());
+
+        let result = execute_command(&command(Action::ReloadConfig(ReloadConfig {})), &ctl).await;
+
+        assert!(!result.success);
+        assert!(result.message.contains("reload failed"));
+    }
+}
@@ -0,0 +1,149 @@
+use sentinel_common::proto::{ConfigAck, ConfigUpdate};
+
+use crate::config::{load_from_str, AgentConfig};
+
+use super::controller::RuntimeController;
+
+pub async fn apply_config_update(
+    update: &ConfigUpdate,
+    controller: &RuntimeController,
+) -> ConfigAck {
+    let current = controller.config_version().await;
+    if update.version < current {
+        return nack(
+            update.version,
+            format!("stale version, {current} already applied"),
+        );
+    }
+
+    let yaml = match std::str::from_utf8(&update.config_yaml) {
+        Ok(y) => y,
+        Err(e) => return nack(update.version, format!("config is not valid UTF-8: {e}")),
+    };
+
+    let mut next = match load_from_str(yaml) {
+        Ok(cfg) => cfg,
+        Err(e) => return nack(update.version, format!("invalid config: {e}")),
+    };
+    keep_local_identity(&mut next, &controller.config().await);
+
+    let merged = match serde_yaml::to_string(&next) {
+        Ok(y) => y,
+        Err(e) => return nack(update.version, format!("serialize config: {e}")),
+    };
+
+    if let Err(e) = controller.persist(merged.as_bytes()).await {
+        return nack(update.version, format!("persist failed: {e}"));
+    }
+
+    let summary = controller.apply(next).await;
+
+    if let Err(e) = controller.set_config_version(update.version).await {
+        tracing::warn!(target: "cfg", error = %e, "Failed to persist config version");
+    }
+
+    tracing::info!(target: "cfg", version = update.version, %summary, "Config update applied");
+
+    ConfigAck {
+        version: update.version,
+        applied: true,
+        message: summary.to_string(),
+    }
+}
+
+fn keep_local_identity(next: &mut AgentConfig, local: &AgentConfig) {
+    next.agent_id.clone_from(&local.agent_id);
+    next.secret.clone_from(&local.secret);
+    next.server.clone_from(&local.server);
+    next.http_fallback_url.clone_from(&local.http_fallback_url);
+}
+
+fn nack(version: i64, message: String) -> ConfigAck {
+    tracing::warn!(target: "cfg", version, reason = %message, "Config update rejected");
+    ConfigAck {
+        version,
+        applied: false,
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::load_from_file;
+    use crate::control::test_harness::{controller, controller_with, VALID};
+
+    fn update(version: i64, yaml: &str) -> ConfigUpdate {
+        ConfigUpdate {
+            version,
+            config_yaml: yaml.as_bytes().to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn valid_update_is_persisted_and_acked() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctl = controller(dir.path());
+        let yaml = VALID.replace("interval_seconds: 15", "interval_seconds: 60");
+
+        let ack = apply_config_update(&update(3, &yaml), &ctl).await;
+
+        assert!(ack.applied, "{}", ack.message);
+        assert_eq!(ack.version, 3);
+        let saved = load_from_file(&dir.path().join("config.yml")).unwrap();
+        assert_eq!(saved, load_from_str(&yaml).unwrap());
+        assert_eq!(ctl.config().await.collect.interval_seconds, 60);
+        assert_eq!(ctl.config_version().await, 3);
+    }
+
+    #[tokio::test]
+    async fn push_keeps_local_identity_and_credentials() {
+        let dir = tempfile::tempdir().unwrap();
+        let local = format!("agent_id: agent-1\nsecret: c2VjcmV0\n{VALID}");
+        let ctl = controller_with(dir.path(), &local);
+        let pushed = format!(
+            "agent_id: other\nsecret: b3RoZXI=\n{}",
+            VALID
+                .replace("localhost:8443", "attacker:8443")
+                .replace("interval_seconds: 15", "interval_seconds: 60")
+        );
+
+        let ack = apply_config_update(&update(2, &pushed), &ctl).await;
+
+        assert!(ack.applied, "{}", ack.message);
+        assert!(!ack.message.contains("restart required"), "{}", ack.message);
+        for cfg in [
+            ctl.config().await,
+            load_from_file(&dir.path().join("config.yml")).unwrap(),
+        ] {
+            assert_eq!(cfg.agent_id.as_deref(), Some("agent-1"));
+            assert_eq!(cfg.secret.as_deref(), Some("c2VjcmV0"));
+            assert_eq!(cfg.server, "https://localhost:8443");
+            assert_eq!(cfg.collect.interval_seconds, 60);
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_yaml_is_rejected_without_writing() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctl = controller(dir.path());
+
+        let ack = apply_config_update(&update(1, "server: \"\"\ncollect: nope"), &ctl).await;
+
+        assert!(!ack.applied);
+        assert!(ack.message.contains("invalid config"));
+        assert!(!dir.path().join("config.yml").exists());
+    }
+
+    #[tokio::test]
+    async fn stale_version_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctl = controller(dir.path());
+        ctl.set_config_version(5).await.unwrap();
+
+        let ack = apply_config_update(&update(4, VALID), &ctl).await;
+
+        assert!(!ack.applied);
+        assert!(ack.message.contains("stale"));
+    }
+}
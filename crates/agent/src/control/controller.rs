@@ -0,0 +1,378 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use tokio::sync::{mpsc, Mutex};
+
+use sentinel_common::proto::Metric;
+
+use crate::bootstrap::{replace_config, WriteError};
+use crate::buffer::{RetentionPolicy, Wal};
+use crate::collector::plan;
+use crate::config::{load_from_file, AgentConfig, LoadError};
+use crate::persistence::AgentPersistedState;
+use crate::plugin::scheduler::PluginSchedulerHandle;
+use crate::plugin::PluginScheduler;
+use crate::scheduler::{ScheduledTask, TaskHandle};
+
+const COLLECTOR_JITTER: f64 = 0.1;
+
+#[derive(Clone)]
+pub struct RuntimeController {
+    inner: Arc<Mutex<Inner>>,
+}
+
+struct Inner {
+    config: AgentConfig,
+    config_path: PathBuf,
+    metrics_tx: mpsc::Sender<Vec<Metric>>,
+    wal: Arc<Mutex<Wal>>,
+    persisted: Arc<Mutex<AgentPersistedState>>,
+    state_dir: PathBuf,
+    collectors: Vec<TaskHandle>,
+    plugins: Option<PluginSchedulerHandle>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ApplySummary {
+    pub collector_restarted: bool,
+    pub plugins_restarted: bool,
+    pub buffer_updated: bool,
+    pub restart_required: Vec<&'static str>,
+}
+
+impl std::fmt::Display for ApplySummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut changed = Vec::new();
+        if self.collector_restarted {
+            changed.push("collector");
+        }
+        if self.plugins_restarted {
+            changed.push("plugins");
+        }
+        if self.buffer_updated {
+            changed.push("buffer");
+        }
+
+        if changed.is_empty() {
+            write!(f, "applied (no runtime changes)")?;
+        } else {
+            write!(f, "applied ({})", changed.join(", "))?;
+        }
+        if !self.restart_required.is_empty() {
+            write!(
+                f,
+                "; restart required for: {}",
+                self.restart_required.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl RuntimeController {
+    pub fn new(
+        config: AgentConfig,
+        config_path: PathBuf,
+        metrics_tx: mpsc::Sender<Vec<Metric>>,
+        wal: Arc<Mutex<Wal>>,
+        persisted: Arc<Mutex<AgentPersistedState>>,
+        state_dir: PathBuf,
+    ) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                config,
+                config_path,
+                metrics_tx,
+                wal,
+                persisted,
+                state_dir,
+                collectors: Vec::new(),
+                plugins: None,
+            })),
+        }
+    }
+
+    pub async fn start(&self) {
+        let mut inner = self.inner.lock().await;
+        inner.restart_collector();
+        inner.restart_plugins();
+    }
+
+    pub async fn config(&self) -> AgentConfig {
+        self.inner.lock().await.config.clone()
+    }
+
+    pub async fn config_version(&self) -> i64 {
+        let persisted = self.inner.lock().await.persisted.clone();
+        let version = persisted.lock().await.config_version;
+        version
+    }
+
+    pub async fn set_config_version(&self, version: i64) -> std::io::Result<()> {
+        let (persisted, state_dir) = {
+            let inner = self.inner.lock().await;
+            (inner.persisted.clone(), inner.state_dir.clone())
+        };
+        let mut ps = persisted.lock().await;
+        ps.update_config_version(version);
+        ps.save(&state_dir)
+    }
+
+    pub async fn persist(&self, config_yaml: &[u8]) -> Result<(), WriteError> {
+        let path = self.inner.lock().await.config_path.clone();
+        replace_config(&path, config_yaml)
+    }
+
+    pub async fn restart_collector(&self) {
+        self.inner.lock().await.restart_collector();
+    }
+
+    pub async fn update_interval(&self, interval_seconds: u64) {
+        let mut inner = self.inner.lock().await;
+        inner.config.collect.interval_seconds = interval_seconds;
+        inner.restart_collector();
+    }
+
+    pub async fn reload_from_disk(&self) -> Result<ApplySummary, LoadError> {
+        let path = self.inner.lock().await.config_path.clone();
+        let next = load_from_file(&path)?;
+        Ok(self.apply(next).await)
+    }
+
+    pub async fn apply(&self, next: AgentConfig) -> ApplySummary {
+        let mut inner = self.inner.lock().await;
+        let prev = inner.config.clone();
+        let mut summary = ApplySummary {
+            restart_required: restart_required_fields(&prev, &next),
+            ..Default::default()
+        };
+
+        inner.config = next;
+
+        if prev.collect != inner.config.collect {
+            inner.restart_collector();
+            summary.collector_restarted = true;
+        }
+
+        if prev.plugins != inner.config.plugins {
+            inner.restart_plugins();
+            summary.plugins_restarted = true;
+        }
+
+        if prev.buffer.segment_size_mb != inner.config.buffer.segment_size_mb {
+            let bytes = inner.config.buffer.segment_size_mb * 1024 * 1024;
+            inner.wal.lock().await.set_max_segment_bytes(bytes);
+            summary.buffer_updated = true;
+        }
+
+        let retention = RetentionPolicy::from_config(&inner.config.buffer);
+        if retention != RetentionPolicy::from_config(&prev.buffer) {
+            inner.wal.lock().await.set_retention(retention);
+            summary.buffer_updated = true;
+        }
+
+        if !summary.restart_required.is_empty() {
+            tracing::warn!(
+                target: "cfg",
+                fields = ?summary.restart_required,
+                "Config changes take effect after restart"
+            );
+        }
+
+        summary
+    }
+}
+
+impl Inner {
+    fn restart_collector(&mut self) {
+        for handle in self.collectors.drain(..) {
+            handle.abort();
+        }
+
+        let groups = plan(&self.config.collect);
+        if groups.is_empty() {
+            tracing::warn!(target: "cfg", "All system collectors disabled");
+            return;
+        }
+
+        for group in groups {
+            tracing::info!(
+                target: "cfg",
+                interval_s = group.interval.as_secs(),
+                collectors = ?group.names,
+                "System collector started"
+            );
+            let handle = ScheduledTask {
+                interval: group.interval,
+                jitter_fraction: COLLECTOR_JITTER,
+                collector: Arc::new(group.collector),
+            }
+            .spawn(self.metrics_tx.clone());
+            self.collectors.push(handle);
+        }
+    }
+
+    fn restart_plugins(&mut self) {
+        if let Some(handle) = self.plugins.take() {
+            handle.abort();
+        }
+
+        if !self.config.plugins.enabled {
+            tracing::info!(target: "plugin", "Plugin system disabled");
+            return;
+        }
+
+        let mut scheduler = PluginScheduler::new(self.config.plugins.clone());
+        scheduler.discover();
+
+        if scheduler.loaded_count() > 0 {
+            self.plugins = Some(scheduler.spawn(self.metrics_tx.clone()));
+            tracing::info!(target: "plugin", "Plugin scheduler started");
+        }
+    }
+}
+
+fn restart_required_fields(prev: &AgentConfig, next: &AgentConfig) -> Vec<&'static str> {
+    let mut fields = Vec::new();
+    if prev.agent_id != next.agent_id {
+        fields.push("agent_id");
+    }
+    if prev.server != next.server {
+        fields.push("server");
+    }
+    if prev.secret != next.secret {
+        fields.push("secret");
+    }
+    if prev.api_port != next.api_port {
+        fields.push("api_port");
+    }
+    if prev.http_fallback_url != next.http_fallback_url {
+        fields.push("http_fallback_url");
+    }
+    if prev.buffer.wal_dir != next.buffer.wal_dir {
+        fields.push("buffer.wal_dir");
+    }
+    if prev.security != next.security {
+        fields.push("security");
+    }
+    if prev.stream != next.stream {
+        fields.push("stream");
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::load_from_str;
+    use crate::control::test_harness::{controller, VALID};
+
+    #[tokio::test]
+    async fn identical_config_changes_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctl = controller(dir.path());
+        let summary = ctl.apply(load_from_str(VALID).unwrap()).await;
+        assert_eq!(summary, ApplySummary::default());
+    }
+
+    #[tokio::test]
+    async fn interval_change_restarts_collector() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctl = controller(dir.path());
+        let next =
+            load_from_str(&VALID.replace("interval_seconds: 15", "interval_seconds: 30")).unwrap();
+
+        let summary = ctl.apply(next).await;
+        assert!(summary.collector_restarted);
+        assert!(!summary.plugins_restarted);
+        assert_eq!(ctl.config().await.collect.interval_seconds, 30);
+    }
+
+    #[tokio::test]
+    async fn per_collector_interval_spawns_extra_task() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctl = controller(dir.path());
+        ctl.start().await;
+        assert_eq!(ctl.inner.lock().await.collectors.len(), 1);
+
+        let next = load_from_str(&VALID.replace(
+            "metrics: {}",
+            "metrics: {}\n  disk:\n    interval_seconds: 60",
+        ))
+        .unwrap();
+        let summary = ctl.apply(next).await;
+
+        assert!(summary.collector_restarted);
+        assert_eq!(ctl.inner.lock().await.collectors.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn segment_size_change_updates_wal() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctl = controller(dir.path());
+        let next = load_from_str(&VALID.replace(
+            "wal_dir: /tmp/wal",
+            "wal_dir: /tmp/wal\n  segment_size_mb: 4",
+        ))
+        .unwrap();
+
+        let summary = ctl.apply(next).await;
+        assert!(summary.buffer_updated);
+        let wal = ctl.inner.lock().await.wal.clone();
+        assert_eq!(wal.lock().await.max_segment_bytes(), 4 * 1024 * 1024);
+    }
+
+    #[tokio::test]
+    async fn server_change_requires_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctl = controller(dir.path());
+        let next = load_from_str(&VALID.replace("localhost:8443", "other:8443")).unwrap();
+
+        let summary = ctl.apply(next).await;
+        assert_eq!(summary.restart_required, vec!["server"]);
+        assert!(summary.to_string().contains("restart required for: server"));
+    }
+
+    #[tokio::test]
+    async fn update_interval_overrides_runtime_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctl = controller(dir.path());
+        ctl.update_interval(45).await;
+        assert_eq!(ctl.config().await.collect.interval_seconds, 45);
+    }
+
+    #[tokio::test]
+    async fn reload_from_disk_applies_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctl = controller(dir.path());
+        std::fs::write(
+            dir.path().join("config.yml"),
+            VALID.replace("interval_seconds: 15", "interval_seconds: 20"),
+        )
+        .unwrap();
+
+        let summary = ctl.reload_from_disk().await.unwrap();
+        assert!(summary.collector_restarted);
+        assert_eq!(ctl.config().await.collect.interval_seconds, 20);
+    }
+
+    #[tokio::test]
+    async fn reload_from_disk_missing_file_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctl = controller(dir.path());
+        assert!(ctl.reload_from_disk().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn config_version_is_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let ctl = controller(dir.path());
+        ctl.set_config_version(7).await.unwrap();
+        assert_eq!(ctl.config_version().await, 7);
+
+        let saved = AgentPersistedState::load(&dir.path().join("state"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(saved.config_version, 7);
+    }
+}
@@ -0,0 +1,50 @@
+mod command;
+mod config_update;
+mod controller;
+
+pub use command::execute_command;
+pub use config_update::apply_config_update;
+pub use controller::{ApplySummary, RuntimeController};
+
+#[cfg(test)]
+mod test_harness {
+    use std::path::Path;
+    use std::sync::Arc;
+
+    use tokio::sync::{mpsc, Mutex};
+
+    use crate::buffer::Wal;
+    use crate::config::load_from_str;
+    use crate::persistence::AgentPersistedState;
+
+    use super::RuntimeController;
+
+    pub const VALID: &str = r#"
+server: https://localhost:8443
+collect:
+  interval_seconds: 15
+  metrics: {}
+plugins:
+  enabled: false
+buffer:
+  wal_dir: /tmp/wal
+security: {}
+"#;
+
+    pub fn controller(dir: &Path) -> RuntimeController {
+        controller_with(dir, VALID)
+    }
+
+    pub fn controller_with(dir: &Path, yaml: &str) -> RuntimeController {
+        let (tx, _rx) = mpsc::channel(4);
+        let wal = Wal::open(&dir.join("wal"), false, 1024 * 1024).unwrap();
+        RuntimeController::new(
+            load_from_str(yaml).unwrap(),
+            dir.join("config.yml"),
+            tx,
+            Arc::new(Mutex::new(wal)),
+            Arc::new(Mutex::new(AgentPersistedState::new("a".into(), "s".into()))),
+            dir.join("state"),
+        )
+    }
+}
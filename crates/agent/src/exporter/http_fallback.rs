@@ -1,11 +1,10 @@
-use crate::security::HmacSigner;
-use sentinel_common::proto::{metric::Value, Batch};
+use crate::security::ActiveKey;
+use sentinel_common::proto::Batch;
 
 pub struct HttpFallbackClient {
     base_url: String,
     agent_id: String,
-    signer: HmacSigner,
-    key_id: String,
+    key: ActiveKey,
     http: reqwest::Client,
 }
 
@@ -29,19 +28,19 @@ impl std::fmt::Display for HttpFallbackError {
 impl std::error::Error for HttpFallbackError {}
 
 impl HttpFallbackClient {
-    pub fn new(base_url: &str, agent_id: String, secret: &[u8], key_id: String) -> Self {
+    pub fn new(base_url: &str, agent_id: String, key: ActiveKey) -> Self {
         Self {
             base_url: base_url.trim_end_matches('/').to_string(),
             agent_id,
-            signer: HmacSigner::new(secret),
-            key_id,
+            key,
             http: reqwest::Client::new(),
         }
     }
 
     pub async fn push_metrics(&self, batch: &Batch) -> Result<(), HttpFallbackError> {
         let canonical = sentinel_common::canonicalize::canonical_bytes(batch);
-        let signature = self.signer.sign_base64(&canonical);
+        let (key_id, signer) = self.key.current();
+        let signature = signer.sign_base64(&canonical);
 
         let body = encode_batch_json(batch)?;
 
@@ -51,7 +50,7 @@ impl HttpFallbackClient {
             .header("Content-Type", "application/json")
             .header("X-Agent-Id", &self.agent_id)
             .header("X-Signature", &signature)
-            .header("X-Key-Id", &self.key_id)
+            .header("X-Key-Id", &key_id)
             .body(body)
             .send()
             .await
@@ -67,55 +66,10 @@ impl HttpFallbackClient {
 }
 
 fn encode_batch_json(batch: &Batch) -> Result<Vec<u8>, HttpFallbackError> {
-    serde_json::to_vec(&BatchPayload::from(batch))
+    sentinel_common::batch_json::encode(batch)
         .map_err(|e| HttpFallbackError::Serialize(e.to_string()))
 }
 
-#[derive(serde::Serialize)]
-struct BatchPayload {
-    batch_id: String,
-    agent_id: String,
-    seq_start: u64,
-    seq_end: u64,
-    metrics: Vec<MetricPayload>,
-}
-
-#[derive(serde::Serialize)]
-struct MetricPayload {
-    name: String,
-    value: f64,
-    labels: std::collections::HashMap<String, String>,
-    timestamp: u64,
-}
-
-impl From<&Batch> for BatchPayload {
-    fn from(b: &Batch) -> Self {
-        Self {
-            batch_id: b.batch_id.clone(),
-            agent_id: b.agent_id.clone(),
-            seq_start: b.seq_start,
-            seq_end: b.seq_end,
-            metrics: b
-                .metrics
-                .iter()
-                .map(|m| {
-                    let value = match &m.value {
-                        Some(Value::ValueDouble(v)) => *v,
-                        Some(Value::ValueInt(v)) => *v as f64,
-                        _ => 0.0,
-                    };
-                    MetricPayload {
-                        name: m.name.clone(),
-                        value,
-                        labels: m.labels.clone(),
-                        timestamp: m.timestamp_ms as u64,
-                    }
-                })
-                .collect(),
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -148,6 +102,17 @@ mod tests {
         assert!(text.contains("55.5"));
     }
 
+    #[test]
+    fn payload_decodes_to_identical_batch() {
+        let batch = sample_batch();
+        let json = encode_batch_json(&batch).unwrap();
+        let decoded = sentinel_common::batch_json::decode(&json).unwrap();
+        assert_eq!(
+            sentinel_common::canonicalize::canonical_bytes(&decoded),
+            sentinel_common::canonicalize::canonical_bytes(&batch)
+        );
+    }
+
     #[test]
     fn error_display() {
         let e = HttpFallbackError::Rejected(503);
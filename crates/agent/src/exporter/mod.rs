@@ -5,7 +5,7 @@ mod retry;
 mod send_loop;
 
 pub use client::GrpcClient;
-pub use http_fallback::HttpFallbackClient;
+pub use http_fallback::{HttpFallbackClient, HttpFallbackError};
 pub use interceptor::AuthInterceptor;
 pub use retry::RetryPolicy;
 pub use send_loop::SendLoop;
@@ -5,6 +5,7 @@ pub mod buffer;
 pub mod cli;
 pub mod collector;
 pub mod config;
+pub mod control;
 pub mod exporter;
 pub mod persistence;
 pub mod plugin;
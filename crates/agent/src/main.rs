@@ -63,7 +63,14 @@ async fn main() {
         }
     };
 
-    if let Err(e) = sentinel_agent::run::run(config, args.legacy_mode).await {
+    if let Err(e) = sentinel_agent::run::run(
+        config,
+        config_path,
+        args.legacy_mode,
+        args.insecure_no_secret,
+    )
+    .await
+    {
         tracing::error!(target: "system", error = %e, "Agent fatal error");
         std::process::exit(1);
     }
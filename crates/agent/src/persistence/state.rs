@@ -17,6 +17,10 @@ pub struct AgentPersistedState {
     pub server_url: String,
     #[serde(default)]
     pub clean_shutdown: bool,
+    #[serde(default)]
+    pub config_version: i64,
+    #[serde(default)]
+    pub key_rotated_ms: i64,
 }
 
 impl AgentPersistedState {
@@ -30,6 +34,8 @@ impl AgentPersistedState {
             key_id: "default".into(),
             server_url,
             clean_shutdown: true,
+            config_version: 0,
+            key_rotated_ms: now_ms(),
         }
     }
 
@@ -75,6 +81,15 @@ impl AgentPersistedState {
     pub fn update_seq(&mut self, seq: u64) {
         self.seq_counter = seq;
     }
+
+    pub fn update_config_version(&mut self, version: i64) {
+        self.config_version = version;
+    }
+
+    pub fn record_key_rotation(&mut self, key_id: String) {
+        self.key_id = key_id;
+        self.key_rotated_ms = now_ms();
+    }
 }
 
 fn now_ms() -> i64 {
@@ -118,6 +133,18 @@ mod tests {
         assert!(loaded.is_none());
     }
 
+    #[test]
+    fn config_version_defaults_for_old_state_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tmp.path().join(STATE_FILE),
+            r#"{"agent_id":"a","seq_counter":1,"boot_count":1,"last_connection_ms":0,"last_shutdown_ms":0,"key_id":"default","server_url":"s"}"#,
+        )
+        .unwrap();
+        let loaded = AgentPersistedState::load(tmp.path()).unwrap().unwrap();
+        assert_eq!(loaded.config_version, 0);
+    }
+
     #[test]
     fn record_boot_increments() {
         let mut s = AgentPersistedState::new("a".into(), "s".into());
@@ -1,6 +1,6 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 use base64::engine::general_purpose::STANDARD;
 use base64::Engine;
@@ -9,26 +9,33 @@ use tokio::sync::{mpsc, Mutex};
 
 use crate::api::{self, AgentState};
 use crate::batch::BatchComposer;
-use crate::buffer::{compact, needs_compaction, Wal};
-use crate::collector::SystemCollector;
+use crate::buffer::{DropReason, RetentionPolicy, Wal};
 use crate::config::AgentConfig;
-use crate::exporter::{GrpcClient, RetryPolicy, SendLoop};
+use crate::control::RuntimeController;
+use crate::exporter::{GrpcClient, HttpFallbackClient, RetryPolicy, SendLoop};
 use crate::persistence::{AgentPersistedState, VolumeLayout};
-use crate::plugin::PluginScheduler;
-use crate::scheduler::ScheduledTask;
-use crate::stream::StreamClient;
+use crate::security::{
+    open_key_store, rotated_key_slot, ActiveKey, KeyRotator, KeyStore, KeyStoreError, KeyStoreKind,
+};
+use crate::stream::{InFlightTracker, StreamClient};
 use sentinel_common::logging;
 
-const COMPACTION_THRESHOLD_MB: u64 = 32;
 const STATE_SAVE_INTERVAL_SECS: u64 = 60;
-
-pub async fn run(config: AgentConfig, legacy_mode: bool) -> Result<(), Box<dyn std::error::Error>> {
+const WAL_MAINTENANCE_INTERVAL_SECS: u64 = 60;
+const DEFAULT_KEY_ID: &str = "default";
+
+pub async fn run(
+    config: AgentConfig,
+    config_path: PathBuf,
+    legacy_mode: bool,
+    insecure_no_secret: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
     let agent_id = config
         .agent_id
         .clone()
         .unwrap_or_else(|| sysinfo::System::host_name().unwrap_or_else(|| "unknown".into()));
 
-    let secret = resolve_secret(&config)?;
+    let configured_secret = resolve_secret(&config)?;
 
     let mode_label = if legacy_mode {
         "legacy (V1)"
@@ -46,10 +53,12 @@ pub async fn run(config: AgentConfig, legacy_mode: bool) -> Result<(), Box<dyn s
 
     let segment_bytes = config.buffer.segment_size_mb * 1024 * 1024;
     let sw = logging::stopwatch();
-    let wal = Wal::open(Path::new(&config.buffer.wal_dir), true, segment_bytes)?;
+    let mut wal = Wal::open(Path::new(&config.buffer.wal_dir), true, segment_bytes)?;
+    wal.set_retention(RetentionPolicy::from_config(&config.buffer));
     tracing::info!(target: "boot", "WAL opened{sw}");
 
-    run_compaction_if_needed(&config.buffer.wal_dir, &wal)?;
+    let state = AgentState::new();
+    enforce_retention(&mut wal, &state);
 
     let resume_seq = wal.next_id();
     let pending = wal.unacked_count()?;
@@ -66,39 +75,78 @@ pub async fn run(config: AgentConfig, legacy_mode: bool) -> Result<(), Box<dyn s
 
     let wal = Arc::new(Mutex::new(wal));
 
-    let state = AgentState::new();
-
     let config_parent = Path::new(&config.buffer.wal_dir)
         .parent()
         .unwrap_or_else(|| Path::new("/etc/sentinel"));
     let layout = VolumeLayout::new(config_parent);
-    let persisted = load_or_create_persisted_state(&layout, &agent_id, &config.server, resume_seq)?;
+    let mut persisted =
+        load_or_create_persisted_state(&layout, &agent_id, &config.server, resume_seq)?;
+    let key_store_kind = KeyStoreKind::parse(&config.security.key_store)?;
+    let key_store = open_key_store(&key_store_kind, &layout.state_dir().join("keys"))?;
+    if key_store_kind == KeyStoreKind::Os && config.security.rotation_check_interval_hours > 0 {
+        tracing::warn!(target: "auth", "The os key store does not survive a reboot, rotated keys will be lost with it");
+    }
+    let key = load_active_key(
+        key_store.as_ref(),
+        &agent_id,
+        &mut persisted,
+        &layout.state_dir(),
+        configured_secret.as_deref(),
+        insecure_no_secret,
+    )?;
     let persisted = Arc::new(Mutex::new(persisted));
 
     let (metrics_tx, metrics_rx) = mpsc::channel(256);
 
-    spawn_collector(config.collect.interval_seconds, metrics_tx.clone());
-    spawn_plugin_scheduler(&config, metrics_tx);
+    let controller = RuntimeController::new(
+        config.clone(),
+        config_path,
+        metrics_tx,
+        wal.clone(),
+        persisted.clone(),
+        layout.state_dir(),
+    );
+    controller.start().await;
     spawn_batcher(agent_id.clone(), wal.clone(), metrics_rx, resume_seq);
 
     if legacy_mode {
         spawn_legacy_sender(
             config.server.clone(),
             agent_id.clone(),
-            secret,
+            key.signer().secret_bytes(),
             wal.clone(),
             state.clone(),
         );
     } else {
+        let rotator = KeyRotator::new(
+            agent_id.clone(),
+            key.clone(),
+            key_store,
+            persisted.clone(),
+            layout.state_dir(),
+        )
+        .with_interval(Duration::from_secs(
+            config.security.rotation_check_interval_hours * 3600,
+        ));
+        let in_flight = InFlightTracker::new(
+            config.stream.max_in_flight,
+            Duration::from_secs(config.stream.ack_timeout_secs),
+            state.clone(),
+        );
         spawn_stream_sender(
             config.server.clone(),
+            config.http_fallback_url.clone(),
             agent_id.clone(),
-            secret,
+            key,
             wal.clone(),
             state.clone(),
+            controller,
+            rotator,
+            in_flight,
         );
     }
 
+    spawn_wal_maintenance(wal.clone(), state.clone());
     spawn_api(config.api_port, state).await;
     spawn_state_saver(persisted.clone(), wal.clone(), layout.state_dir());
 
@@ -161,29 +209,6 @@ fn load_or_create_persisted_state(
     Ok(state)
 }
 
-fn run_compaction_if_needed(wal_dir: &str, wal: &Wal) -> Result<(), Box<dyn std::error::Error>> {
-    let threshold = COMPACTION_THRESHOLD_MB * 1024 * 1024;
-    let dir = Path::new(wal_dir);
-    if needs_compaction(dir, threshold)? {
-        let sw = logging::stopwatch();
-        tracing::info!(target: "boot", "WAL compaction needed, running");
-        let meta = wal.save_meta()?;
-        compact(dir, &meta)?;
-        tracing::info!(target: "boot", "WAL compaction complete{sw}");
-    }
-    Ok(())
-}
-
-fn spawn_collector(interval_secs: u64, tx: mpsc::Sender<Vec<sentinel_common::proto::Metric>>) {
-    let collector = Arc::new(SystemCollector::new());
-    let _handle = ScheduledTask {
-        interval: Duration::from_secs(interval_secs),
-        jitter_fraction: 0.1,
-        collector,
-    }
-    .spawn(tx);
-}
-
 fn spawn_batcher(
     agent_id: String,
     wal: Arc<Mutex<Wal>>,
@@ -193,16 +218,74 @@ fn spawn_batcher(
     tokio::spawn(async move {
         let mut composer = BatchComposer::new(agent_id, resume_seq);
         while let Some(metrics) = rx.recv().await {
-            let batch = composer.compose(metrics);
-            let encoded = BatchComposer::encode_batch(&batch);
+            let mut batch = composer.compose(metrics);
             let mut w = wal.lock().await;
-            if let Err(e) = w.append(encoded) {
-                tracing::error!(target: "data", error = %e, "WAL write failed");
+            if w.rejects_writes() {
+                w.record_drops(DropReason::Newest, 1);
+                tracing::debug!(target: "data", batch_id = %batch.batch_id, "WAL full, dropping new batch");
+                continue;
+            }
+            let drops = w.unreported_drops();
+            drops.annotate(&mut batch.meta);
+            let encoded = BatchComposer::encode_batch(&batch);
+            match w.append(encoded) {
+                Ok(_) => w.drops_sent_in(&batch.batch_id, drops),
+                Err(e) => tracing::error!(target: "data", error = %e, "WAL write failed"),
+            }
+        }
+    });
+}
+
+fn spawn_wal_maintenance(wal: Arc<Mutex<Wal>>, state: AgentState) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(Duration::from_secs(WAL_MAINTENANCE_INTERVAL_SECS)).await;
+            enforce_retention(&mut *wal.lock().await, &state);
+
+            let mut reclaimed = 0u64;
+            loop {
+                let step = wal.lock().await.compact_step();
+                match step {
+                    Ok(Some(bytes)) => reclaimed += bytes,
+                    Ok(None) => break,
+                    Err(e) => {
+                        tracing::warn!(target: "data", error = %e, "WAL compaction failed");
+                        break;
+                    }
+                }
+                tokio::task::yield_now().await;
+            }
+            if reclaimed > 0 {
+                tracing::debug!(target: "data", reclaimed, "WAL compacted");
+                state.set_wal_size_bytes(wal.lock().await.size_bytes());
             }
         }
     });
 }
 
+fn enforce_retention(wal: &mut Wal, state: &AgentState) {
+    match wal.enforce_retention(SystemTime::now()) {
+        Ok(drops) if !drops.is_empty() => {
+            tracing::warn!(
+                target: "data",
+                dropped = drops.total(),
+                age = drops.age,
+                oldest = drops.oldest,
+                downsample = drops.downsample,
+                size_bytes = wal.size_bytes(),
+                "WAL retention dropped unsent batches"
+            );
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!(target: "data", error = %e, "WAL retention failed"),
+    }
+    state.set_wal_dropped(&wal.dropped());
+    state.set_wal_size_bytes(wal.size_bytes());
+    if let Ok(n) = wal.unacked_count() {
+        state.set_queue_length(n as u64);
+    }
+}
+
 fn spawn_legacy_sender(
     server: String,
     agent_id: String,
@@ -250,25 +333,38 @@ fn spawn_legacy_sender(
     });
 }
 
+#[allow(clippy::too_many_arguments)]
 fn spawn_stream_sender(
     server: String,
+    http_fallback_url: Option<String>,
     agent_id: String,
-    secret: Vec<u8>,
+    key: ActiveKey,
     wal: Arc<Mutex<Wal>>,
     state: AgentState,
+    controller: RuntimeController,
+    rotator: KeyRotator,
+    in_flight: InFlightTracker,
 ) {
     tokio::spawn(async move {
         let version = env!("CARGO_PKG_VERSION").to_string();
-        let key_id = "default".to_string();
 
-        let client = StreamClient::new(
+        let mut client = StreamClient::new(
             server,
             agent_id.clone(),
             version,
-            key_id,
-            &secret,
+            key.clone(),
             wal.clone(),
-        );
+            state.clone(),
+        )
+        .with_controller(controller)
+        .with_key_rotation(rotator)
+        .with_in_flight(in_flight);
+
+        if let Some(url) = http_fallback_url {
+            tracing::info!(target: "conn", url = %url, "HTTP fallback enabled");
+            client =
+                client.with_http_fallback(HttpFallbackClient::new(&url, agent_id.clone(), key));
+        }
 
         state.set_ready(true);
         client.run(None).await;
@@ -319,34 +415,193 @@ fn spawn_state_saver(
     });
 }
 
-fn resolve_secret(config: &AgentConfig) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+fn load_active_key(
+    store: &dyn KeyStore,
+    agent_id: &str,
+    persisted: &mut AgentPersistedState,
+    state_dir: &Path,
+    configured: Option<&[u8]>,
+    insecure_no_secret: bool,
+) -> Result<ActiveKey, Box<dyn std::error::Error>> {
+    if persisted.key_id != DEFAULT_KEY_ID {
+        let slot = rotated_key_slot(agent_id, &persisted.key_id);
+        match store.load(&slot).or_else(|_| store.load(agent_id)) {
+            Ok(secret) => {
+                tracing::info!(target: "auth", key_id = %persisted.key_id, "Using rotated HMAC key");
+                return Ok(ActiveKey::new(persisted.key_id.clone(), &secret));
+            }
+            Err(e) => {
+                tracing::warn!(target: "auth", key_id = %persisted.key_id, error = %e, "Rotated key unavailable, falling back to configured secret");
+                persisted.record_key_rotation(DEFAULT_KEY_ID.into());
+                persisted.save(state_dir)?;
+            }
+        }
+    }
+
+    if let Some(secret) = configured.filter(|s| !s.is_empty()) {
+        if let Err(e) = store.store(agent_id, secret) {
+            tracing::warn!(target: "auth", error = %e, "Failed to save secret in key store");
+        }
+        return Ok(ActiveKey::new(DEFAULT_KEY_ID, secret));
+    }
+
+    match store.load(agent_id) {
+        Ok(secret) => return Ok(ActiveKey::new(DEFAULT_KEY_ID, &secret)),
+        Err(KeyStoreError::NotFound) => {}
+        Err(e) => {
+            tracing::warn!(target: "auth", error = %e, "Failed to read secret from key store")
+        }
+    }
+
+    if insecure_no_secret {
+        tracing::warn!(target: "auth", "No secret configured, HMAC signing will use an empty key (--insecure-no-secret)");
+        return Ok(ActiveKey::new(DEFAULT_KEY_ID, &[]));
+    }
+    Err("no agent secret: set `secret` in the config file or SENTINEL_AGENT_SECRET, or pass --insecure-no-secret to run unsigned".into())
+}
+
+fn resolve_secret(config: &AgentConfig) -> Result<Option<Vec<u8>>, Box<dyn std::error::Error>> {
     if let Some(ref b64) = config.secret {
-        return Ok(STANDARD.decode(b64)?);
+        return Ok(Some(STANDARD.decode(b64)?));
     }
     if let Ok(val) = std::env::var("SENTINEL_AGENT_SECRET") {
-        return Ok(val.into_bytes());
+        return Ok(Some(val.into_bytes()));
     }
     if let Ok(val) = std::env::var("SENTINEL_MASTER_KEY") {
-        return Ok(val.into_bytes());
+        return Ok(Some(val.into_bytes()));
     }
-    tracing::warn!(target: "auth", "No secret configured, HMAC signing will use an empty key");
-    Ok(Vec::new())
+    Ok(None)
 }
 
-fn spawn_plugin_scheduler(
-    config: &AgentConfig,
-    tx: mpsc::Sender<Vec<sentinel_common::proto::Metric>>,
-) {
-    if !config.plugins.enabled {
-        tracing::info!(target: "plugin", "Plugin system disabled");
-        return;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::MemoryKeyStore;
+    use sentinel_common::key_rotation::seal_secret;
+    use sentinel_common::proto::KeyRotation;
+
+    fn state() -> AgentPersistedState {
+        AgentPersistedState::new("agent-1".into(), "http://server".into())
     }
 
-    let mut scheduler = PluginScheduler::new(config.plugins.clone());
-    scheduler.discover();
+    #[test]
+    fn configured_secret_is_saved_in_key_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = MemoryKeyStore::new();
+        let key = load_active_key(
+            &store,
+            "agent-1",
+            &mut state(),
+            dir.path(),
+            Some(b"s3cret"),
+            false,
+        )
+        .unwrap();
+        assert_eq!(key.key_id(), DEFAULT_KEY_ID);
+        assert_eq!(store.load("agent-1").unwrap(), b"s3cret");
+
+        let key =
+            load_active_key(&store, "agent-1", &mut state(), dir.path(), None, false).unwrap();
+        assert_eq!(key.signer().secret_bytes(), b"s3cret");
+    }
+
+    #[test]
+    fn rotated_key_wins_over_configured_secret() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = MemoryKeyStore::new();
+        store
+            .store(&rotated_key_slot("agent-1", "key-2"), b"rotated")
+            .unwrap();
+        let mut ps = state();
+        ps.record_key_rotation("key-2".into());
+        let key = load_active_key(
+            &store,
+            "agent-1",
+            &mut ps,
+            dir.path(),
+            Some(b"bootstrap"),
+            false,
+        )
+        .unwrap();
+        assert_eq!(key.key_id(), "key-2");
+        assert_eq!(key.signer().secret_bytes(), b"rotated");
+    }
+
+    #[tokio::test]
+    async fn crash_between_key_store_and_state_write_keeps_keys_consistent() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(MemoryKeyStore::new());
+        let mut ps = state();
+        ps.save(dir.path()).unwrap();
+        let key = load_active_key(
+            store.as_ref(),
+            "agent-1",
+            &mut ps,
+            dir.path(),
+            Some(b"bootstrap"),
+            false,
+        )
+        .unwrap();
+
+        let rotator = KeyRotator::new(
+            "agent-1".into(),
+            key,
+            store.clone(),
+            Arc::new(Mutex::new(ps.clone())),
+            dir.path().to_path_buf(),
+        );
+        let rotation = KeyRotation {
+            rotation_id: "r1".into(),
+            key_id: "key-2".into(),
+            sealed_secret: seal_secret(b"bootstrap", "agent-1", "key-2", b"rotated"),
+            issued_at_ms: 0,
+        };
+        assert!(rotator.apply(&rotation).await.applied);
+
+        ps.save(dir.path()).unwrap();
+        let mut restarted = AgentPersistedState::load(dir.path()).unwrap().unwrap();
+        let key = load_active_key(
+            store.as_ref(),
+            "agent-1",
+            &mut restarted,
+            dir.path(),
+            Some(b"bootstrap"),
+            false,
+        )
+        .unwrap();
+        assert_eq!(key.key_id(), DEFAULT_KEY_ID);
+        assert_eq!(key.signer().secret_bytes(), b"bootstrap");
+
+        restarted.record_key_rotation("key-2".into());
+        restarted.save(dir.path()).unwrap();
+        let key = load_active_key(
+            store.as_ref(),
+            "agent-1",
+            &mut restarted,
+            dir.path(),
+            Some(b"bootstrap"),
+            false,
+        )
+        .unwrap();
+        assert_eq!(key.key_id(), "key-2");
+        assert_eq!(key.signer().secret_bytes(), b"rotated");
+    }
 
-    if scheduler.loaded_count() > 0 {
-        let _handle = scheduler.spawn(tx);
-        tracing::info!(target: "plugin", "Plugin scheduler started");
+    #[test]
+    fn refuses_to_start_without_secret_unless_insecure() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = MemoryKeyStore::new();
+        assert!(load_active_key(&store, "agent-1", &mut state(), dir.path(), None, false).is_err());
+        assert!(load_active_key(
+            &store,
+            "agent-1",
+            &mut state(),
+            dir.path(),
+            Some(b""),
+            false
+        )
+        .is_err());
+        let key = load_active_key(&store, "agent-1", &mut state(), dir.path(), None, true).unwrap();
+        assert!(key.signer().secret_bytes().is_empty());
     }
 }
@@ -0,0 +1,57 @@
+use std::sync::{Arc, RwLock};
+
+use super::signer::HmacSigner;
+
+struct KeyMaterial {
+    key_id: String,
+    signer: HmacSigner,
+}
+
+#[derive(Clone)]
+pub struct ActiveKey {
+    inner: Arc<RwLock<KeyMaterial>>,
+}
+
+impl ActiveKey {
+    pub fn new(key_id: impl Into<String>, secret: &[u8]) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(KeyMaterial {
+                key_id: key_id.into(),
+                signer: HmacSigner::new(secret),
+            })),
+        }
+    }
+
+    pub fn key_id(&self) -> String {
+        self.current().0
+    }
+
+    pub fn signer(&self) -> HmacSigner {
+        self.current().1
+    }
+
+    pub fn current(&self) -> (String, HmacSigner) {
+        let key = self.inner.read().unwrap_or_else(|e| e.into_inner());
+        (key.key_id.clone(), key.signer.clone())
+    }
+
+    pub fn replace(&self, key_id: impl Into<String>, secret: &[u8]) {
+        let mut key = self.inner.write().unwrap_or_else(|e| e.into_inner());
+        key.key_id = key_id.into();
+        key.signer = HmacSigner::new(secret);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replace_is_visible_to_clones() {
+        let key = ActiveKey::new("default", b"old");
+        let shared = key.clone();
+        key.replace("key-2", b"new");
+        assert_eq!(shared.key_id(), "key-2");
+        assert_eq!(shared.signer().secret_bytes(), b"new".to_vec());
+    }
+}
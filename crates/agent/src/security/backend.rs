@@ -0,0 +1,134 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::file_keystore::EncryptedFileKeyStore;
+use super::keystore::{KeyStore, KeyStoreError};
+use super::os_keystore::OsKeyStore;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyStoreKind {
+    Auto,
+    File(Option<PathBuf>),
+    Os,
+}
+
+impl KeyStoreKind {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "auto" | "" => Ok(Self::Auto),
+            "file" => Ok(Self::File(None)),
+            "os" => Ok(Self::Os),
+            path if path.contains('/') => Ok(Self::File(Some(PathBuf::from(path)))),
+            other => Err(format!(
+                "security.key_store must be auto, file, os or a directory path, got '{other}'"
+            )),
+        }
+    }
+}
+
+pub fn open_key_store(
+    kind: &KeyStoreKind,
+    default_dir: &Path,
+) -> Result<Arc<dyn KeyStore>, KeyStoreError> {
+    match kind {
+        KeyStoreKind::File(dir) => {
+            let dir = dir.clone().unwrap_or_else(|| default_dir.to_path_buf());
+            Ok(Arc::new(EncryptedFileKeyStore::machine_bound(dir)?))
+        }
+        KeyStoreKind::Os => Ok(Arc::new(OsKeyStore::open()?)),
+        KeyStoreKind::Auto => {
+            let file = EncryptedFileKeyStore::machine_bound(default_dir.to_path_buf());
+            let os = OsKeyStore::open();
+            match (file, os) {
+                (Ok(file), Ok(os)) => Ok(Arc::new(MirroredKeyStore { file, os })),
+                (Ok(file), Err(e)) => {
+                    tracing::debug!(target: "auth", error = %e, "Kernel keyring unavailable, using encrypted file key store");
+                    Ok(Arc::new(file))
+                }
+                (Err(e), Ok(os)) => {
+                    tracing::warn!(target: "auth", error = %e, "Encrypted file key store unavailable, keys will not survive a reboot");
+                    Ok(Arc::new(os))
+                }
+                (Err(e), Err(_)) => Err(e),
+            }
+        }
+    }
+}
+
+struct MirroredKeyStore<F, O> {
+    file: F,
+    os: O,
+}
+
+impl<F: KeyStore, O: KeyStore> KeyStore for MirroredKeyStore<F, O> {
+    fn store(&self, agent_id: &str, secret: &[u8]) -> Result<(), KeyStoreError> {
+        self.file.store(agent_id, secret)?;
+        if let Err(e) = self.os.store(agent_id, secret) {
+            tracing::warn!(target: "auth", error = %e, "Failed to mirror key into kernel keyring, dropping the stale copy");
+            let _ = self.os.delete(agent_id);
+        }
+        Ok(())
+    }
+
+    fn load(&self, agent_id: &str) -> Result<Vec<u8>, KeyStoreError> {
+        match self.file.load(agent_id) {
+            Ok(secret) => Ok(secret),
+            Err(e) => self.os.load(agent_id).map_err(|_| e),
+        }
+    }
+
+    fn delete(&self, agent_id: &str) -> Result<(), KeyStoreError> {
+        let _ = self.os.delete(agent_id);
+        self.file.delete(agent_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::memory_keystore::MemoryKeyStore;
+
+    struct ReadOnly(MemoryKeyStore);
+
+    impl KeyStore for ReadOnly {
+        fn store(&self, _agent_id: &str, _secret: &[u8]) -> Result<(), KeyStoreError> {
+            Err(KeyStoreError::Io("read only".into()))
+        }
+
+        fn load(&self, agent_id: &str) -> Result<Vec<u8>, KeyStoreError> {
+            self.0.load(agent_id)
+        }
+
+        fn delete(&self, agent_id: &str) -> Result<(), KeyStoreError> {
+            self.0.delete(agent_id)
+        }
+    }
+
+    #[test]
+    fn failed_mirror_never_serves_stale_key() {
+        let os = MemoryKeyStore::new();
+        os.store("agent-1", b"old").unwrap();
+        let ks = MirroredKeyStore {
+            file: MemoryKeyStore::new(),
+            os: ReadOnly(os),
+        };
+        ks.store("agent-1", b"rotated").unwrap();
+        assert_eq!(ks.load("agent-1").unwrap(), b"rotated");
+        assert!(matches!(
+            ks.os.load("agent-1"),
+            Err(KeyStoreError::NotFound)
+        ));
+    }
+
+    #[test]
+    fn parse_key_store_setting() {
+        assert_eq!(KeyStoreKind::parse("auto"), Ok(KeyStoreKind::Auto));
+        assert_eq!(KeyStoreKind::parse("file"), Ok(KeyStoreKind::File(None)));
+        assert_eq!(KeyStoreKind::parse("os"), Ok(KeyStoreKind::Os));
+        assert_eq!(
+            KeyStoreKind::parse("/var/lib/sentinel/keys"),
+            Ok(KeyStoreKind::File(Some("/var/lib/sentinel/keys".into())))
+        );
+        assert!(KeyStoreKind::parse("keychain").is_err());
+    }
+}
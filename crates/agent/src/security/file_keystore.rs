@@ -1,12 +1,17 @@
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
 use aes_gcm::aead::{Aead, KeyInit, OsRng};
 use aes_gcm::{Aes256Gcm, Nonce};
 use rand::RngCore;
+use sha2::{Digest, Sha256};
 
 use super::keystore::{KeyStore, KeyStoreError};
 
+const SALT_FILE: &str = ".salt";
+const MACHINE_ID_PATHS: &[&str] = &["/etc/machine-id", "/var/lib/dbus/machine-id"];
+
 pub struct EncryptedFileKeyStore {
     dir: PathBuf,
     master_key: [u8; 32],
@@ -14,14 +19,41 @@ pub struct EncryptedFileKeyStore {
 
 impl EncryptedFileKeyStore {
     pub fn new(dir: PathBuf, master_key: [u8; 32]) -> Result<Self, KeyStoreError> {
-        fs::create_dir_all(&dir).map_err(|e| KeyStoreError::Io(e.to_string()))?;
+        fs::create_dir_all(&dir).map_err(io_error)?;
+        restrict(&dir, 0o700)?;
         Ok(Self { dir, master_key })
     }
 
+    pub fn machine_bound(dir: PathBuf) -> Result<Self, KeyStoreError> {
+        let machine_id = machine_id().unwrap_or_else(|| {
+            tracing::warn!(target: "auth", "No /etc/machine-id, key store is only protected by its salt file");
+            String::new()
+        });
+        Self::bound_to(dir, &machine_id)
+    }
+
+    fn bound_to(dir: PathBuf, machine_id: &str) -> Result<Self, KeyStoreError> {
+        fs::create_dir_all(&dir).map_err(io_error)?;
+        let salt = load_or_create_salt(&dir)?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(b"sentinel-agent-keystore:v1:");
+        hasher.update(machine_id.as_bytes());
+        hasher.update(salt);
+        Self::new(dir, hasher.finalize().into())
+    }
+
     fn path_for(&self, agent_id: &str) -> PathBuf {
         self.dir.join(format!("{agent_id}.key"))
     }
 
+    fn checked_path(&self, agent_id: &str) -> Result<PathBuf, KeyStoreError> {
+        if agent_id.is_empty() || agent_id.starts_with('.') || agent_id.contains(['/', '\\']) {
+            return Err(KeyStoreError::Io(format!("invalid key name '{agent_id}'")));
+        }
+        Ok(self.path_for(agent_id))
+    }
+
     fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, KeyStoreError> {
         let cipher = Aes256Gcm::new_from_slice(&self.master_key)
             .map_err(|e| KeyStoreError::Crypto(e.to_string()))?;
@@ -52,28 +84,87 @@ impl EncryptedFileKeyStore {
 
 impl KeyStore for EncryptedFileKeyStore {
     fn store(&self, agent_id: &str, secret: &[u8]) -> Result<(), KeyStoreError> {
+        let path = self.checked_path(agent_id)?;
         let encrypted = self.encrypt(secret)?;
-        fs::write(self.path_for(agent_id), encrypted).map_err(|e| KeyStoreError::Io(e.to_string()))
+        write_private(&path, &encrypted)
     }
 
     fn load(&self, agent_id: &str) -> Result<Vec<u8>, KeyStoreError> {
-        let path = self.path_for(agent_id);
+        let path = self.checked_path(agent_id)?;
         if !path.exists() {
             return Err(KeyStoreError::NotFound);
         }
-        let data = fs::read(&path).map_err(|e| KeyStoreError::Io(e.to_string()))?;
+        let data = fs::read(&path).map_err(io_error)?;
         self.decrypt(&data)
     }
 
     fn delete(&self, agent_id: &str) -> Result<(), KeyStoreError> {
-        let path = self.path_for(agent_id);
+        let path = self.checked_path(agent_id)?;
         if path.exists() {
-            fs::remove_file(&path).map_err(|e| KeyStoreError::Io(e.to_string()))?;
+            fs::remove_file(&path).map_err(io_error)?;
         }
         Ok(())
     }
 }
 
+fn io_error(e: std::io::Error) -> KeyStoreError {
+    KeyStoreError::Io(e.to_string())
+}
+
+fn machine_id() -> Option<String> {
+    MACHINE_ID_PATHS
+        .iter()
+        .filter_map(|p| fs::read_to_string(p).ok())
+        .map(|id| id.trim().to_string())
+        .find(|id| !id.is_empty())
+}
+
+fn load_or_create_salt(dir: &Path) -> Result<[u8; 32], KeyStoreError> {
+    let path = dir.join(SALT_FILE);
+    if let Ok(existing) = fs::read(&path) {
+        return existing
+            .try_into()
+            .map_err(|_| KeyStoreError::Crypto(format!("{} is corrupt", path.display())));
+    }
+    let mut salt = [0u8; 32];
+    OsRng.fill_bytes(&mut salt);
+    write_private(&path, &salt)?;
+    Ok(salt)
+}
+
+fn write_private(path: &Path, data: &[u8]) -> Result<(), KeyStoreError> {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let tmp = path.with_file_name(format!(".{file_name}.tmp"));
+    {
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut f = options.open(&tmp).map_err(io_error)?;
+        f.write_all(data).map_err(io_error)?;
+        f.sync_all().map_err(io_error)?;
+    }
+    restrict(&tmp, 0o600)?;
+    fs::rename(&tmp, path).map_err(io_error)
+}
+
+#[cfg(unix)]
+fn restrict(path: &Path, mode: u32) -> Result<(), KeyStoreError> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(mode)).map_err(io_error)
+}
+
+#[cfg(not(unix))]
+fn restrict(_path: &Path, _mode: u32) -> Result<(), KeyStoreError> {
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -125,4 +216,58 @@ mod tests {
         fs::write(&path, &data).unwrap();
         assert!(ks.load("agent-1").is_err());
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn key_files_are_private_and_replaced_atomically() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let ks = mk_store(&dir.path().join("keys"));
+        ks.store("agent-1", b"secret").unwrap();
+        ks.store("agent-1", b"rotated").unwrap();
+
+        let mode = |p: &Path| fs::metadata(p).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&ks.path_for("agent-1")), 0o600);
+        assert_eq!(mode(&dir.path().join("keys")), 0o700);
+        let names: Vec<_> = fs::read_dir(dir.path().join("keys"))
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert_eq!(names, vec![std::ffi::OsString::from("agent-1.key")]);
+        assert_eq!(ks.load("agent-1").unwrap(), b"rotated");
+    }
+
+    #[test]
+    fn path_like_names_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let ks = mk_store(dir.path());
+        assert!(ks.store("../agent-1", b"secret").is_err());
+        assert!(ks.store(".salt", b"secret").is_err());
+    }
+
+    #[test]
+    fn keys_do_not_open_on_another_machine() {
+        let dir = tempfile::tempdir().unwrap();
+        let here = EncryptedFileKeyStore::bound_to(dir.path().to_path_buf(), "machine-a").unwrap();
+        here.store("agent-1", b"secret").unwrap();
+        let again = EncryptedFileKeyStore::bound_to(dir.path().to_path_buf(), "machine-a").unwrap();
+        assert_eq!(again.load("agent-1").unwrap(), b"secret");
+
+        let elsewhere =
+            EncryptedFileKeyStore::bound_to(dir.path().to_path_buf(), "machine-b").unwrap();
+        assert!(elsewhere.load("agent-1").is_err());
+    }
+
+    #[test]
+    fn stores_with_different_salts_do_not_share_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = EncryptedFileKeyStore::machine_bound(dir.path().join("a")).unwrap();
+        a.store("agent-1", b"secret").unwrap();
+        let reopened = EncryptedFileKeyStore::machine_bound(dir.path().join("a")).unwrap();
+        assert_eq!(reopened.load("agent-1").unwrap(), b"secret");
+
+        let b = EncryptedFileKeyStore::machine_bound(dir.path().join("b")).unwrap();
+        fs::copy(a.path_for("agent-1"), b.path_for("agent-1")).unwrap();
+        assert!(b.load("agent-1").is_err());
+    }
 }
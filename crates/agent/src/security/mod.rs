@@ -1,11 +1,19 @@
+mod active_key;
+mod backend;
 mod compress;
 mod file_keystore;
 mod keystore;
+mod memory_keystore;
 mod os_keystore;
+mod rotation;
 mod signer;
 
+pub use active_key::ActiveKey;
+pub use backend::{open_key_store, KeyStoreKind};
 pub use compress::{compress, decompress, should_compress};
 pub use file_keystore::EncryptedFileKeyStore;
 pub use keystore::{KeyStore, KeyStoreError};
+pub use memory_keystore::MemoryKeyStore;
 pub use os_keystore::OsKeyStore;
+pub use rotation::{rotated_key_slot, KeyRotator};
 pub use signer::HmacSigner;
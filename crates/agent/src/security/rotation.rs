@@ -0,0 +1,186 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+
+use sentinel_common::key_rotation::open_secret;
+use sentinel_common::proto::{KeyRotation, KeyRotationAck};
+
+use crate::persistence::AgentPersistedState;
+
+use super::active_key::ActiveKey;
+use super::keystore::KeyStore;
+
+pub fn rotated_key_slot(agent_id: &str, key_id: &str) -> String {
+    format!("{agent_id}.{key_id}")
+}
+
+pub struct KeyRotator {
+    agent_id: String,
+    key: ActiveKey,
+    store: Arc<dyn KeyStore>,
+    persisted: Arc<Mutex<AgentPersistedState>>,
+    state_dir: PathBuf,
+    interval: Option<Duration>,
+}
+
+impl KeyRotator {
+    pub fn new(
+        agent_id: String,
+        key: ActiveKey,
+        store: Arc<dyn KeyStore>,
+        persisted: Arc<Mutex<AgentPersistedState>>,
+        state_dir: PathBuf,
+    ) -> Self {
+        Self {
+            agent_id,
+            key,
+            store,
+            persisted,
+            state_dir,
+            interval: None,
+        }
+    }
+
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = (!interval.is_zero()).then_some(interval);
+        self
+    }
+
+    pub fn key(&self) -> &ActiveKey {
+        &self.key
+    }
+
+    pub async fn apply(&self, rotation: &KeyRotation) -> KeyRotationAck {
+        let ack = |applied, message: &str| KeyRotationAck {
+            rotation_id: rotation.rotation_id.clone(),
+            key_id: rotation.key_id.clone(),
+            applied,
+            message: message.to_string(),
+        };
+
+        let (current_key_id, signer) = self.key.current();
+        if current_key_id == rotation.key_id {
+            return ack(true, "key already active");
+        }
+
+        let previous = signer.secret_bytes();
+        let Some(secret) = open_secret(
+            &previous,
+            &self.agent_id,
+            &rotation.key_id,
+            &rotation.sealed_secret,
+        ) else {
+            return ack(
+                false,
+                "rotated key cannot be decrypted with the current key",
+            );
+        };
+
+        let slot = rotated_key_slot(&self.agent_id, &rotation.key_id);
+        if let Err(e) = self.store.store(&slot, &secret) {
+            return ack(false, &format!("key store: {e}"));
+        }
+
+        let mut ps = self.persisted.lock().await;
+        let previous_state = ps.clone();
+        ps.record_key_rotation(rotation.key_id.clone());
+        if let Err(e) = ps.save(&self.state_dir) {
+            *ps = previous_state;
+            let _ = self.store.delete(&slot);
+            return ack(false, &format!("agent state: {e}"));
+        }
+        drop(ps);
+
+        let _ = self
+            .store
+            .delete(&rotated_key_slot(&self.agent_id, &current_key_id));
+
+        self.key.replace(rotation.key_id.clone(), &secret);
+        tracing::info!(target: "auth", key_id = %rotation.key_id, "HMAC key rotated");
+        ack(true, "key rotated")
+    }
+
+    pub async fn next_rotation_in(&self, now_ms: i64) -> Option<Duration> {
+        let interval = self.interval?;
+        let rotated_ms = self.persisted.lock().await.key_rotated_ms;
+        let age = Duration::from_millis(now_ms.saturating_sub(rotated_ms).max(0) as u64);
+        Some(interval.saturating_sub(age))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::MemoryKeyStore;
+    use sentinel_common::key_rotation::seal_secret;
+
+    fn rotator(dir: &std::path::Path) -> (KeyRotator, Arc<MemoryKeyStore>) {
+        let store = Arc::new(MemoryKeyStore::new());
+        let state = AgentPersistedState::new("agent-1".into(), "http://server".into());
+        let rotator = KeyRotator::new(
+            "agent-1".into(),
+            ActiveKey::new("default", b"old"),
+            store.clone(),
+            Arc::new(Mutex::new(state)),
+            dir.to_path_buf(),
+        );
+        (rotator, store)
+    }
+
+    fn rotation(current: &[u8], key_id: &str) -> KeyRotation {
+        KeyRotation {
+            rotation_id: "r1".into(),
+            key_id: key_id.into(),
+            sealed_secret: seal_secret(current, "agent-1", key_id, b"new"),
+            issued_at_ms: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn apply_persists_and_switches_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let (rotator, store) = rotator(dir.path());
+
+        let ack = rotator.apply(&rotation(b"old", "key-2")).await;
+        assert!(ack.applied, "{}", ack.message);
+        assert_eq!(rotator.key().key_id(), "key-2");
+        assert_eq!(rotator.key().signer().secret_bytes(), b"new".to_vec());
+        assert_eq!(
+            store.load(&rotated_key_slot("agent-1", "key-2")).unwrap(),
+            b"new".to_vec()
+        );
+        let saved = AgentPersistedState::load(dir.path()).unwrap().unwrap();
+        assert_eq!(saved.key_id, "key-2");
+    }
+
+    #[tokio::test]
+    async fn apply_rejects_key_sealed_under_another_secret() {
+        let dir = tempfile::tempdir().unwrap();
+        let (rotator, store) = rotator(dir.path());
+
+        let ack = rotator.apply(&rotation(b"other", "key-2")).await;
+        assert!(!ack.applied);
+        assert_eq!(rotator.key().key_id(), "default");
+        assert!(store.load(&rotated_key_slot("agent-1", "key-2")).is_err());
+    }
+
+    #[tokio::test]
+    async fn next_rotation_counts_down_from_last_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let (rotator, _) = rotator(dir.path());
+        assert_eq!(rotator.next_rotation_in(0).await, None);
+
+        let rotator = rotator.with_interval(Duration::from_secs(3600));
+        let rotated_ms = rotator.persisted.lock().await.key_rotated_ms;
+        assert_eq!(
+            rotator.next_rotation_in(rotated_ms + 600_000).await,
+            Some(Duration::from_secs(3000))
+        );
+        assert_eq!(
+            rotator.next_rotation_in(rotated_ms + 7_200_000).await,
+            Some(Duration::ZERO)
+        );
+    }
+}
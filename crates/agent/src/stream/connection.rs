@@ -1,5 +1,5 @@
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use tokio::sync::{mpsc, Mutex};
 use tokio_stream::wrappers::ReceiverStream;
@@ -9,10 +9,16 @@ use tonic::transport::Channel;
 use sentinel_common::proto::sentinel_stream_client::SentinelStreamClient;
 use sentinel_common::proto::AgentMessage;
 
+use crate::api::AgentState;
 use crate::buffer::Wal;
-use crate::security::HmacSigner;
+use crate::config::StreamConfig;
+use crate::control::RuntimeController;
+use crate::exporter::HttpFallbackClient;
+use crate::security::{ActiveKey, KeyRotator};
+use sentinel_common::key_rotation::KEY_ROTATION_CAPABILITY;
 
 use super::handshake::{build_handshake_message, validate_handshake_ack, HandshakeParams};
+use super::inflight::InFlightTracker;
 use super::receiver;
 use super::reconnect::ReconnectPolicy;
 use super::sender::StreamSender;
@@ -20,15 +26,19 @@ use super::wal_drain;
 
 const OUTBOUND_BUFFER: usize = 256;
 const HANDSHAKE_TIMEOUT_SECS: u64 = 15;
+const ROTATION_RETRY_SECS: u64 = 300;
 
 pub struct StreamClient {
     endpoint: String,
     agent_id: String,
     agent_version: String,
-    key_id: String,
-    signer: HmacSigner,
+    key: ActiveKey,
     wal: Arc<Mutex<Wal>>,
+    in_flight: Arc<InFlightTracker>,
     reconnect: ReconnectPolicy,
+    controller: Option<RuntimeController>,
+    fallback: Option<HttpFallbackClient>,
+    rotator: Option<Arc<KeyRotator>>,
 }
 
 impl StreamClient {
@@ -36,32 +46,70 @@ impl StreamClient {
         endpoint: String,
         agent_id: String,
         agent_version: String,
-        key_id: String,
-        secret: &[u8],
+        key: ActiveKey,
         wal: Arc<Mutex<Wal>>,
+        state: AgentState,
     ) -> Self {
+        let defaults = StreamConfig::default();
         Self {
             endpoint,
             agent_id,
             agent_version,
-            key_id,
-            signer: HmacSigner::new(secret),
+            key,
             wal,
+            in_flight: Arc::new(InFlightTracker::new(
+                defaults.max_in_flight,
+                Duration::from_secs(defaults.ack_timeout_secs),
+                state,
+            )),
             reconnect: ReconnectPolicy::default(),
+            controller: None,
+            fallback: None,
+            rotator: None,
         }
     }
 
+    pub fn with_controller(mut self, controller: RuntimeController) -> Self {
+        self.controller = Some(controller);
+        self
+    }
+
+    pub fn with_in_flight(mut self, tracker: InFlightTracker) -> Self {
+        self.in_flight = Arc::new(tracker);
+        self
+    }
+
+    pub fn with_http_fallback(mut self, client: HttpFallbackClient) -> Self {
+        self.fallback = Some(client);
+        self
+    }
+
+    pub fn with_key_rotation(mut self, rotator: KeyRotator) -> Self {
+        self.rotator = Some(Arc::new(rotator));
+        self
+    }
+
     pub async fn run(&self, _heartbeat_sender: Option<StreamSender>) -> ! {
         let mut attempt: u32 = 0;
+        let mut failures: u32 = 0;
 
         loop {
             match self.connect_and_run().await {
                 Ok(()) => {
                     tracing::info!(target: "conn", "Stream closed gracefully");
                     attempt = 0;
+                    failures = 0;
                 }
                 Err(e) => {
                     tracing::warn!(target: "conn", error = %e, attempt, "Stream connection failed");
+                    failures = failures.saturating_add(1);
+                }
+            }
+
+            if let Some(fallback) = &self.fallback {
+                if self.reconnect.should_fallback(failures) {
+                    let pushed = wal_drain::drain_via_http(fallback, &self.wal).await;
+                    tracing::info!(target: "conn", failures, pushed, "Drained WAL via HTTP fallback");
                 }
             }
 
@@ -84,14 +132,25 @@ impl StreamClient {
         let (outbound_tx, outbound_rx) = mpsc::channel::<AgentMessage>(OUTBOUND_BUFFER);
         let outbound_stream = ReceiverStream::new(outbound_rx);
 
+        let mut capabilities = vec![
+            "metrics".to_string(),
+            "heartbeat".into(),
+            "config".into(),
+            "commands".into(),
+        ];
+        if self.rotator.is_some() {
+            capabilities.push(KEY_ROTATION_CAPABILITY.into());
+        }
+
+        let (key_id, signer) = self.key.current();
         let params = HandshakeParams {
             agent_id: self.agent_id.clone(),
             agent_version: self.agent_version.clone(),
-            capabilities: vec!["metrics".into(), "heartbeat".into()],
-            key_id: self.key_id.clone(),
+            capabilities,
+            key_id,
         };
 
-        let handshake_msg = build_handshake_message(&params, &self.signer);
+        let handshake_msg = build_handshake_message(&params, &signer);
         outbound_tx
             .send(handshake_msg)
             .await
@@ -121,11 +180,8 @@ impl StreamClient {
             "Stream authenticated"
         );
 
-        let sender = StreamSender::new(
-            outbound_tx,
-            self.agent_id.clone(),
-            HmacSigner::new(&self.signer_secret()),
-        );
+        let sender = StreamSender::new(outbound_tx, self.agent_id.clone(), self.key.clone());
+        self.in_flight.reset(Instant::now());
 
         let heartbeat_interval = Duration::from_millis(heartbeat_interval_ms.max(1000) as u64);
         let heartbeat_sender = sender.clone();
@@ -140,14 +196,31 @@ impl StreamClient {
 
         let drain_sender = sender.clone();
         let drain_wal = self.wal.clone();
+        let drain_in_flight = self.in_flight.clone();
         let drain_handle = tokio::spawn(async move {
-            wal_drain::drain_loop(drain_sender, drain_wal).await;
+            wal_drain::drain_loop(drain_sender, drain_wal, drain_in_flight).await;
         });
 
-        let recv_result = receiver::receive_loop(inbound, self.wal.clone()).await;
+        let rotation_handle = self
+            .rotator
+            .clone()
+            .map(|rotator| tokio::spawn(schedule_rotation(rotator, sender.clone())));
+
+        let recv_result = receiver::receive_loop(
+            inbound,
+            self.wal.clone(),
+            self.in_flight.clone(),
+            sender,
+            self.controller.clone(),
+            self.rotator.clone(),
+        )
+        .await;
 
         heartbeat_handle.abort();
         drain_handle.abort();
+        if let Some(handle) = rotation_handle {
+            handle.abort();
+        }
 
         match recv_result {
             Ok(()) => Ok(()),
@@ -156,21 +229,34 @@ impl StreamClient {
         }
     }
 
-    fn signer_secret(&self) -> Vec<u8> {
-        self.signer.secret_bytes()
-    }
-
     pub fn create_sender(&self) -> (StreamSender, mpsc::Receiver<AgentMessage>) {
         let (tx, rx) = mpsc::channel(OUTBOUND_BUFFER);
-        let sender = StreamSender::new(
-            tx,
-            self.agent_id.clone(),
-            HmacSigner::new(&self.signer_secret()),
-        );
+        let sender = StreamSender::new(tx, self.agent_id.clone(), self.key.clone());
         (sender, rx)
     }
 }
 
+async fn schedule_rotation(rotator: Arc<KeyRotator>, sender: StreamSender) {
+    let retry = Duration::from_secs(ROTATION_RETRY_SECS);
+    while let Some(wait) = rotator.next_rotation_in(current_time_ms()).await {
+        tokio::time::sleep(wait.max(retry)).await;
+        if rotator.next_rotation_in(current_time_ms()).await != Some(Duration::ZERO) {
+            continue;
+        }
+        tracing::info!(target: "auth", key_id = %rotator.key().key_id(), "Requesting scheduled key rotation");
+        if sender.request_key_rotation().await.is_err() {
+            break;
+        }
+    }
+}
+
+fn current_time_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
 #[derive(Debug)]
 pub enum ConnectionError {
     Config(String),
@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::api::AgentState;
+use crate::exporter::RetryPolicy;
+
+enum Slot {
+    InFlight { sent_at: Instant },
+    Backoff { until: Instant },
+}
+
+struct Entry {
+    slot: Slot,
+    attempts: u32,
+}
+
+pub struct InFlightTracker {
+    entries: Mutex<HashMap<String, Entry>>,
+    max_in_flight: usize,
+    ack_timeout: Duration,
+    backoff: RetryPolicy,
+    state: AgentState,
+}
+
+impl InFlightTracker {
+    pub fn new(max_in_flight: usize, ack_timeout: Duration, state: AgentState) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            max_in_flight,
+            ack_timeout,
+            backoff: RetryPolicy::default(),
+            state,
+        }
+    }
+
+    pub fn with_backoff(mut self, backoff: RetryPolicy) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    pub fn expire(&self, now: Instant) -> usize {
+        let mut entries = self.lock();
+        let mut in_flight = 0;
+        for (batch_id, entry) in entries.iter_mut() {
+            if let Slot::InFlight { sent_at } = entry.slot {
+                if now.saturating_duration_since(sent_at) >= self.ack_timeout {
+                    tracing::debug!(target: "data", batch_id = %batch_id, attempts = entry.attempts, "Batch ack timed out");
+                    self.state.increment_ack_timeouts();
+                    entry.slot = Slot::Backoff {
+                        until: now + self.backoff.delay_for_attempt(entry.attempts - 1),
+                    };
+                } else {
+                    in_flight += 1;
+                }
+            }
+        }
+        self.state.set_in_flight(in_flight as u64);
+        self.max_in_flight.saturating_sub(in_flight)
+    }
+
+    pub fn is_due(&self, batch_id: &str, now: Instant) -> bool {
+        match self.lock().get(batch_id) {
+            None => true,
+            Some(Entry {
+                slot: Slot::Backoff { until },
+                ..
+            }) => now >= *until,
+            Some(_) => false,
+        }
+    }
+
+    pub fn on_sent(&self, batch_id: &str, now: Instant) {
+        let mut entries = self.lock();
+        let entry = entries.entry(batch_id.to_string()).or_insert(Entry {
+            slot: Slot::InFlight { sent_at: now },
+            attempts: 0,
+        });
+        if entry.attempts > 0 {
+            self.state.increment_retransmits();
+        }
+        entry.attempts += 1;
+        entry.slot = Slot::InFlight { sent_at: now };
+        self.publish_in_flight(&entries);
+    }
+
+    pub fn on_accepted(&self, batch_id: &str, now: Instant) {
+        let mut entries = self.lock();
+        if let Some(Entry {
+            slot: Slot::InFlight { sent_at },
+            ..
+        }) = entries.remove(batch_id)
+        {
+            self.state
+                .observe_ack_latency(now.saturating_duration_since(sent_at));
+        }
+        self.publish_in_flight(&entries);
+    }
+
+    pub fn on_rejected(&self, batch_id: &str) {
+        let mut entries = self.lock();
+        entries.remove(batch_id);
+        self.publish_in_flight(&entries);
+    }
+
+    pub fn on_retry(&self, batch_id: &str, now: Instant) {
+        let mut entries = self.lock();
+        if let Some(entry) = entries.get_mut(batch_id) {
+            entry.slot = Slot::Backoff {
+                until: now
+                    + self
+                        .backoff
+                        .delay_for_attempt(entry.attempts.saturating_sub(1)),
+            };
+        }
+        self.publish_in_flight(&entries);
+    }
+
+    pub fn reset(&self, now: Instant) {
+        let mut entries = self.lock();
+        for entry in entries.values_mut() {
+            if let Slot::InFlight { .. } = entry.slot {
+                entry.slot = Slot::Backoff { until: now };
+            }
+        }
+        self.publish_in_flight(&entries);
+    }
+
+    pub fn retain(&self, mut pending: impl FnMut(&str) -> bool) {
+        let mut entries = self.lock();
+        entries.retain(|batch_id, _| pending(batch_id));
+        self.publish_in_flight(&entries);
+    }
+
+    pub fn in_flight(&self) -> usize {
+        count_in_flight(&self.lock())
+    }
+
+    fn publish_in_flight(&self, entries: &HashMap<String, Entry>) {
+        self.state.set_in_flight(count_in_flight(entries) as u64);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Entry>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn count_in_flight(entries: &HashMap<String, Entry>) -> usize {
+    entries
+        .values()
+        .filter(|e| matches!(e.slot, Slot::InFlight { .. }))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker(max: usize) -> (InFlightTracker, AgentState) {
+        let state = AgentState::new();
+        let backoff = RetryPolicy {
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(8),
+            jitter_factor: 0.0,
+            ..Default::default()
+        };
+        let t =
+            InFlightTracker::new(max, Duration::from_secs(10), state.clone()).with_backoff(backoff);
+        (t, state)
+    }
+
+    #[test]
+    fn window_limits_batches_in_flight() {
+        let (t, state) = tracker(2);
+        let now = Instant::now();
+        assert_eq!(t.expire(now), 2);
+        t.on_sent("b1", now);
+        t.on_sent("b2", now);
+        assert_eq!(t.expire(now), 0);
+        assert!(!t.is_due("b1", now));
+        assert!(t.is_due("b3", now));
+        assert_eq!(state.in_flight(), 2);
+
+        t.on_accepted("b1", now + Duration::from_millis(30));
+        assert_eq!(t.expire(now), 1);
+        assert_eq!(state.ack_latency().count, 1);
+        assert_eq!(state.in_flight(), 1);
+    }
+
+    #[test]
+    fn timed_out_batch_is_resent_after_backoff() {
+        let (t, state) = tracker(4);
+        let now = Instant::now();
+        t.on_sent("b1", now);
+
+        let later = now + Duration::from_secs(10);
+        assert_eq!(t.expire(later), 4);
+        assert_eq!(state.ack_timeouts(), 1);
+        assert!(!t.is_due("b1", later));
+        assert!(t.is_due("b1", later + Duration::from_secs(1)));
+
+        t.on_sent("b1", later + Duration::from_secs(1));
+        assert_eq!(state.retransmits(), 1);
+        assert_eq!(t.in_flight(), 1);
+    }
+
+    #[test]
+    fn retry_backs_off_exponentially() {
+        let (t, _) = tracker(4);
+        let now = Instant::now();
+        t.on_sent("b1", now);
+        t.on_retry("b1", now);
+        assert!(t.is_due("b1", now + Duration::from_secs(1)));
+
+        t.on_sent("b1", now);
+        t.on_retry("b1", now);
+        assert!(!t.is_due("b1", now + Duration::from_secs(1)));
+        assert!(t.is_due("b1", now + Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn rejected_batch_is_released() {
+        let (t, state) = tracker(1);
+        let now = Instant::now();
+        t.on_sent("b1", now);
+        assert_eq!(t.expire(now), 0);
+        t.on_rejected("b1");
+        assert_eq!(t.expire(now), 1);
+        assert_eq!(state.in_flight(), 0);
+    }
+
+    #[test]
+    fn reset_makes_batches_from_old_stream_due() {
+        let (t, state) = tracker(4);
+        let now = Instant::now();
+        t.on_sent("b1", now);
+        t.on_sent("b2", now);
+        t.on_retry("b2", now);
+        t.reset(now);
+        assert!(t.is_due("b1", now));
+        assert!(!t.is_due("b2", now));
+        assert_eq!(state.in_flight(), 0);
+
+        t.on_sent("b1", now);
+        assert_eq!(state.retransmits(), 1);
+        t.retain(|batch_id| batch_id != "b1");
+        assert_eq!(t.in_flight(), 0);
+        assert!(t.is_due("b1", now));
+    }
+}
@@ -1,11 +1,13 @@
 mod connection;
 mod handshake;
 pub mod heartbeat;
+mod inflight;
 mod receiver;
 mod reconnect;
 mod sender;
 mod wal_drain;
 
 pub use connection::StreamClient;
+pub use inflight::InFlightTracker;
 pub use reconnect::ReconnectPolicy;
 pub use sender::StreamSender;
@@ -1,18 +1,29 @@
 use tokio_stream::StreamExt;
 use tonic::Streaming;
 
+use sentinel_common::batch_reject;
 use sentinel_common::proto::{
-    server_message::Payload as ServerPayload, Batch, BatchAckStatus, ServerMessage,
+    server_message::Payload as ServerPayload, BatchAckStatus, CommandResult, ConfigAck,
+    KeyRotationAck, ServerMessage,
 };
 
 use crate::buffer::Wal;
-use prost::Message;
+use crate::control::{apply_config_update, execute_command, RuntimeController};
+use crate::security::KeyRotator;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::Mutex;
 
+use super::inflight::InFlightTracker;
+use super::sender::StreamSender;
+
 pub async fn receive_loop(
     mut inbound: Streaming<ServerMessage>,
     wal: Arc<Mutex<Wal>>,
+    in_flight: Arc<InFlightTracker>,
+    sender: StreamSender,
+    controller: Option<RuntimeController>,
+    rotator: Option<Arc<KeyRotator>>,
 ) -> Result<(), RecvError> {
     while let Some(result) = inbound.next().await {
         let msg = result.map_err(|e| RecvError::Transport(e.to_string()))?;
@@ -25,13 +36,23 @@ pub async fn receive_loop(
                 match status {
                     BatchAckStatus::BatchAccepted => {
                         tracing::debug!(target: "data", batch_id = %ack.batch_id, "Batch acknowledged");
+                        in_flight.on_accepted(&ack.batch_id, Instant::now());
                         ack_batch_in_wal(&wal, &ack.batch_id).await;
                     }
+                    BatchAckStatus::BatchRejected if batch_reject::is_retryable(&ack.message) => {
+                        tracing::warn!(target: "data", batch_id = %ack.batch_id, reason = %ack.message, "Batch rejected, retrying with backoff");
+                        in_flight.on_retry(&ack.batch_id, Instant::now());
+                    }
                     BatchAckStatus::BatchRejected => {
-                        tracing::warn!(target: "data", batch_id = %ack.batch_id, reason = %ack.message, "Batch rejected");
+                        tracing::warn!(target: "data", batch_id = %ack.batch_id, reason = %ack.message, "Batch rejected, removing it from the WAL");
+                        in_flight.on_rejected(&ack.batch_id);
+                        if wal.lock().await.reject_batch(&ack.batch_id).is_none() {
+                            tracing::trace!(target: "data", batch_id = %ack.batch_id, "Batch not found in WAL for reject");
+                        }
                     }
                     BatchAckStatus::BatchRetry => {
                         tracing::warn!(target: "data", batch_id = %ack.batch_id, "Batch retry requested");
+                        in_flight.on_retry(&ack.batch_id, Instant::now());
                     }
                 }
             }
@@ -41,9 +62,49 @@ pub async fn receive_loop(
             }
             Some(ServerPayload::ConfigUpdate(update)) => {
                 tracing::info!(target: "cfg", version = update.version, "Config update received");
+                let ack = match controller {
+                    Some(ref c) => apply_config_update(&update, c).await,
+                    None => ConfigAck {
+                        version: update.version,
+                        applied: false,
+                        message: "remote config not supported by this agent".into(),
+                    },
+                };
+                if sender.send_config_ack(ack).await.is_err() {
+                    tracing::warn!(target: "cfg", "Stream closed before config ack was sent");
+                }
             }
             Some(ServerPayload::Command(cmd)) => {
                 tracing::info!(target: "system", command_id = %cmd.command_id, "Remote command received");
+                let result = match controller {
+                    Some(ref c) => execute_command(&cmd, c).await,
+                    None => CommandResult {
+                        command_id: cmd.command_id.clone(),
+                        success: false,
+                        message: "remote commands not supported by this agent".into(),
+                    },
+                };
+                if sender.send_command_result(result).await.is_err() {
+                    tracing::warn!(target: "system", "Stream closed before command result was sent");
+                }
+            }
+            Some(ServerPayload::KeyRotation(rotation)) => {
+                tracing::info!(target: "auth", key_id = %rotation.key_id, "Key rotation received");
+                let ack = match rotator {
+                    Some(ref r) => r.apply(&rotation).await,
+                    None => KeyRotationAck {
+                        rotation_id: rotation.rotation_id.clone(),
+                        key_id: rotation.key_id.clone(),
+                        applied: false,
+                        message: "key rotation not supported by this agent".into(),
+                    },
+                };
+                if !ack.applied {
+                    tracing::warn!(target: "auth", key_id = %ack.key_id, reason = %ack.message, "Key rotation not applied");
+                }
+                if sender.send_key_rotation_ack(ack).await.is_err() {
+                    tracing::warn!(target: "auth", "Stream closed before key rotation ack was sent");
+                }
             }
             Some(ServerPayload::Error(err)) => {
                 if err.fatal {
@@ -92,17 +153,10 @@ fn current_time_ms() -> i64 {
 }
 
 async fn ack_batch_in_wal(wal: &Arc<Mutex<Wal>>, batch_id: &str) {
-    let mut w = wal.lock().await;
-    if let Ok(unacked) = w.iter_unacked() {
-        for (record_id, data) in unacked {
-            if let Ok(batch) = Batch::decode(data.as_slice()) {
-                if batch.batch_id == batch_id {
-                    w.ack(record_id);
-                    tracing::trace!(target: "data", record_id, batch_id, "WAL record acked");
-                    return;
-                }
-            }
+    match wal.lock().await.ack_batch(batch_id) {
+        Some(record_id) => {
+            tracing::trace!(target: "data", record_id, batch_id, "WAL record acked")
         }
+        None => tracing::trace!(target: "data", batch_id, "Batch not found in WAL for ack"),
     }
-    tracing::trace!(target: "data", batch_id, "Batch not found in WAL for ack");
 }
@@ -4,6 +4,7 @@ pub struct ReconnectPolicy {
     pub base_delay: Duration,
     pub max_delay: Duration,
     pub jitter_factor: f64,
+    pub fallback_after_failures: u32,
 }
 
 impl Default for ReconnectPolicy {
@@ -12,6 +13,7 @@ impl Default for ReconnectPolicy {
             base_delay: Duration::from_secs(1),
             max_delay: Duration::from_secs(60),
             jitter_factor: 0.25,
+            fallback_after_failures: 3,
         }
     }
 }
@@ -23,6 +25,10 @@ impl ReconnectPolicy {
         let capped = Duration::from_millis(delay_ms).min(self.max_delay);
         apply_jitter(capped, self.jitter_factor)
     }
+
+    pub fn should_fallback(&self, consecutive_failures: u32) -> bool {
+        self.fallback_after_failures > 0 && consecutive_failures >= self.fallback_after_failures
+    }
 }
 
 fn apply_jitter(base: Duration, factor: f64) -> Duration {
@@ -44,3 +50,41 @@ fn pseudo_random_f64() -> f64 {
         .subsec_nanos();
     (nanos % 10000) as f64 / 10000.0
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> ReconnectPolicy {
+        ReconnectPolicy {
+            jitter_factor: 0.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn delay_grows_and_caps() {
+        let p = policy();
+        assert_eq!(p.delay_for_attempt(0), Duration::from_secs(1));
+        assert_eq!(p.delay_for_attempt(3), Duration::from_secs(8));
+        assert_eq!(p.delay_for_attempt(20), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn fallback_after_threshold() {
+        let p = policy();
+        assert!(!p.should_fallback(0));
+        assert!(!p.should_fallback(2));
+        assert!(p.should_fallback(3));
+        assert!(p.should_fallback(10));
+    }
+
+    #[test]
+    fn zero_threshold_disables_fallback() {
+        let p = ReconnectPolicy {
+            fallback_after_failures: 0,
+            ..policy()
+        };
+        assert!(!p.should_fallback(100));
+    }
+}
@@ -1,10 +1,11 @@
 use tokio::sync::mpsc;
 
 use sentinel_common::proto::{
-    agent_message::Payload as AgentPayload, AgentMessage, HeartbeatPing, MetricsBatch, SystemStats,
+    agent_message::Payload as AgentPayload, AgentMessage, CommandResult, ConfigAck, HeartbeatPing,
+    KeyRotationAck, KeyRotationRequest, MetricsBatch, SystemStats,
 };
 
-use crate::security::HmacSigner;
+use crate::security::ActiveKey;
 
 use super::heartbeat::collect_system_stats;
 
@@ -12,21 +13,18 @@ use super::heartbeat::collect_system_stats;
 pub struct StreamSender {
     tx: mpsc::Sender<AgentMessage>,
     agent_id: String,
-    signer: HmacSigner,
+    key: ActiveKey,
 }
 
 impl StreamSender {
-    pub fn new(tx: mpsc::Sender<AgentMessage>, agent_id: String, signer: HmacSigner) -> Self {
-        Self {
-            tx,
-            agent_id,
-            signer,
-        }
+    pub fn new(tx: mpsc::Sender<AgentMessage>, agent_id: String, key: ActiveKey) -> Self {
+        Self { tx, agent_id, key }
     }
 
     pub async fn send_batch(&self, batch: sentinel_common::proto::Batch) -> Result<(), SendError> {
         let canonical = sentinel_common::canonicalize::canonical_bytes(&batch);
-        let signature = self.signer.sign_base64(&canonical);
+        let (key_id, signer) = self.key.current();
+        let signature = signer.sign_base64(&canonical);
 
         let metrics_batch = MetricsBatch {
             batch_id: batch.batch_id,
@@ -36,6 +34,7 @@ impl StreamSender {
             metrics: batch.metrics,
             meta: batch.meta,
             signature,
+            key_id,
         };
 
         let msg = AgentMessage {
@@ -66,6 +65,48 @@ impl StreamSender {
             .map_err(|_| SendError::ChannelClosed)
     }
 
+    pub async fn send_config_ack(&self, ack: ConfigAck) -> Result<(), SendError> {
+        let msg = AgentMessage {
+            payload: Some(AgentPayload::ConfigAck(ack)),
+        };
+        self.tx
+            .send(msg)
+            .await
+            .map_err(|_| SendError::ChannelClosed)
+    }
+
+    pub async fn send_command_result(&self, result: CommandResult) -> Result<(), SendError> {
+        let msg = AgentMessage {
+            payload: Some(AgentPayload::CommandResult(result)),
+        };
+        self.tx
+            .send(msg)
+            .await
+            .map_err(|_| SendError::ChannelClosed)
+    }
+
+    pub async fn send_key_rotation_ack(&self, ack: KeyRotationAck) -> Result<(), SendError> {
+        let msg = AgentMessage {
+            payload: Some(AgentPayload::KeyRotationAck(ack)),
+        };
+        self.tx
+            .send(msg)
+            .await
+            .map_err(|_| SendError::ChannelClosed)
+    }
+
+    pub async fn request_key_rotation(&self) -> Result<(), SendError> {
+        let msg = AgentMessage {
+            payload: Some(AgentPayload::KeyRotationRequest(KeyRotationRequest {
+                current_key_id: self.key.key_id(),
+            })),
+        };
+        self.tx
+            .send(msg)
+            .await
+            .map_err(|_| SendError::ChannelClosed)
+    }
+
     pub fn agent_id(&self) -> &str {
         &self.agent_id
     }
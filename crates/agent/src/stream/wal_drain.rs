@@ -1,19 +1,25 @@
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use tokio::sync::Mutex;
 
 use crate::batch::BatchComposer;
 use crate::buffer::Wal;
+use crate::exporter::{HttpFallbackClient, HttpFallbackError};
 
+use super::inflight::InFlightTracker;
 use super::sender::StreamSender;
 
-const DRAIN_INTERVAL: Duration = Duration::from_secs(5);
+const DRAIN_INTERVAL: Duration = Duration::from_secs(1);
 const SAVE_INTERVAL_BATCHES: usize = 10;
 
-pub async fn drain_loop(sender: StreamSender, wal: Arc<Mutex<Wal>>) {
+pub async fn drain_loop(
+    sender: StreamSender,
+    wal: Arc<Mutex<Wal>>,
+    in_flight: Arc<InFlightTracker>,
+) {
     loop {
-        let sent = drain_pending(&sender, &wal).await;
+        let sent = drain_pending(&sender, &wal, &in_flight).await;
         if sent > 0 {
             tracing::debug!(target: "data", sent, "WAL batches pushed to stream");
         }
@@ -21,10 +27,20 @@ pub async fn drain_loop(sender: StreamSender, wal: Arc<Mutex<Wal>>) {
     }
 }
 
-async fn drain_pending(sender: &StreamSender, wal: &Arc<Mutex<Wal>>) -> usize {
+async fn drain_pending(
+    sender: &StreamSender,
+    wal: &Arc<Mutex<Wal>>,
+    in_flight: &InFlightTracker,
+) -> usize {
+    let now = Instant::now();
+    let free = in_flight.expire(now);
     let entries = {
-        let w = wal.lock().await;
-        match w.iter_unacked() {
+        let mut w = wal.lock().await;
+        in_flight.retain(|batch_id| w.has_pending_batch(batch_id));
+        if free == 0 {
+            return 0;
+        }
+        match w.read_due(free, |batch_id| in_flight.is_due(batch_id, now)) {
             Ok(e) => e,
             Err(e) => {
                 tracing::error!(target: "data", error = %e, "WAL read failed");
@@ -48,6 +64,7 @@ async fn drain_pending(sender: &StreamSender, wal: &Arc<Mutex<Wal>>) -> usize {
             }
         };
 
+        in_flight.on_sent(&batch.batch_id, Instant::now());
         if sender.send_batch(batch).await.is_err() {
             tracing::warn!(target: "data", "stream channel closed, stopping drain");
             break;
@@ -61,3 +78,170 @@ async fn drain_pending(sender: &StreamSender, wal: &Arc<Mutex<Wal>>) -> usize {
 
     sent
 }
+
+pub async fn drain_via_http(client: &HttpFallbackClient, wal: &Arc<Mutex<Wal>>) -> usize {
+    let entries = {
+        let w = wal.lock().await;
+        match w.iter_unacked() {
+            Ok(e) => e,
+            Err(e) => {
+                tracing::error!(target: "data", error = %e, "WAL read failed");
+                return 0;
+            }
+        }
+    };
+
+    let mut pushed = 0usize;
+
+    for (record_id, data) in entries {
+        let batch = match BatchComposer::decode_batch(&data) {
+            Ok(b) if !b.batch_id.is_empty() => b,
+            Ok(_) => {
+                tracing::warn!(target: "data", "dropping WAL entry without a batch id");
+                wal.lock().await.reject(record_id);
+                continue;
+            }
+            Err(e) => {
+                tracing::warn!(target: "data", error = %e, "dropping undecodable WAL entry");
+                wal.lock().await.reject(record_id);
+                continue;
+            }
+        };
+
+        match client.push_metrics(&batch).await {
+            Ok(()) => {}
+            Err(HttpFallbackError::Rejected(401)) => {
+                tracing::warn!(target: "auth", batch_id = %batch.batch_id, "HTTP fallback batch rejected by auth, keeping it for retry");
+                continue;
+            }
+            Err(HttpFallbackError::Rejected(status)) if (400..500).contains(&status) => {
+                tracing::warn!(target: "data", batch_id = %batch.batch_id, status, "HTTP fallback batch rejected, dropping it");
+                wal.lock().await.reject(record_id);
+                continue;
+            }
+            Err(e) => {
+                tracing::warn!(target: "data", batch_id = %batch.batch_id, error = %e, "HTTP fallback push failed");
+                break;
+            }
+        }
+
+        wal.lock().await.ack(record_id);
+        pushed += 1;
+    }
+
+    pushed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::ActiveKey;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use prost::Message;
+    use sentinel_common::proto::Batch;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn open_wal(dir: &std::path::Path, batches: &[&str]) -> Arc<Mutex<Wal>> {
+        let mut wal = Wal::open(dir, false, 1024 * 1024).unwrap();
+        for id in batches {
+            let batch = Batch {
+                agent_id: "agent-1".into(),
+                batch_id: (*id).into(),
+                ..Default::default()
+            };
+            wal.append(batch.encode_to_vec()).unwrap();
+        }
+        Arc::new(Mutex::new(wal))
+    }
+
+    async fn serve(status: fn(usize) -> StatusCode) -> (String, Arc<AtomicUsize>) {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        let app = axum::Router::new().route(
+            "/v1/agent/metrics",
+            post(move |headers: HeaderMap| {
+                let counter = counter.clone();
+                async move {
+                    assert!(headers.contains_key("x-signature"));
+                    status(counter.fetch_add(1, Ordering::SeqCst))
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{addr}"), hits)
+    }
+
+    fn client(url: &str) -> HttpFallbackClient {
+        HttpFallbackClient::new(url, "agent-1".into(), ActiveKey::new("key-1", b"secret"))
+    }
+
+    #[tokio::test]
+    async fn http_drain_acks_pushed_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let wal = open_wal(dir.path(), &["b1", "b2", "b3"]);
+        let (url, hits) = serve(|_| StatusCode::OK).await;
+
+        let pushed = drain_via_http(&client(&url), &wal).await;
+
+        assert_eq!(pushed, 3);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+        assert_eq!(wal.lock().await.unacked_count().unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn http_drain_stops_on_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let wal = open_wal(dir.path(), &["b1", "b2", "b3"]);
+        let (url, hits) = serve(|hit| match hit {
+            0 => StatusCode::OK,
+            _ => StatusCode::SERVICE_UNAVAILABLE,
+        })
+        .await;
+
+        let pushed = drain_via_http(&client(&url), &wal).await;
+
+        assert_eq!(pushed, 1);
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+        assert_eq!(wal.lock().await.unacked_count().unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn http_drain_drops_rejected_batch_and_continues() {
+        let dir = tempfile::tempdir().unwrap();
+        let wal = open_wal(dir.path(), &["b1", "b2", "b3"]);
+        let (url, hits) = serve(|hit| match hit {
+            0 => StatusCode::BAD_REQUEST,
+            _ => StatusCode::OK,
+        })
+        .await;
+
+        let pushed = drain_via_http(&client(&url), &wal).await;
+
+        assert_eq!(pushed, 2);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+        let w = wal.lock().await;
+        assert_eq!(w.unacked_count().unwrap(), 0);
+        assert_eq!(w.unreported_drops().rejected, 1);
+    }
+
+    #[tokio::test]
+    async fn http_drain_keeps_auth_rejected_batch_for_retry() {
+        let dir = tempfile::tempdir().unwrap();
+        let wal = open_wal(dir.path(), &["b1", "b2"]);
+        let (url, _) = serve(|hit| match hit {
+            0 => StatusCode::UNAUTHORIZED,
+            _ => StatusCode::OK,
+        })
+        .await;
+
+        let pushed = drain_via_http(&client(&url), &wal).await;
+
+        assert_eq!(pushed, 1);
+        let w = wal.lock().await;
+        assert!(w.has_pending_batch("b1"));
+        assert!(!w.has_pending_batch("b2"));
+    }
+}
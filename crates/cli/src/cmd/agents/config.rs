@@ -0,0 +1,130 @@
+use anyhow::{bail, Context, Result};
+use clap::{Args, Subcommand};
+use colored::Colorize;
+
+use crate::client;
+use crate::output::{build_table, print_json, spinner, theme, OutputMode};
+
+#[derive(Subcommand)]
+pub enum AgentConfigCmd {
+    #[command(about = "Push a config file to one agent or a group of agents")]
+    Push(PushArgs),
+}
+
+#[derive(Args)]
+pub struct PushArgs {
+    #[arg(help = "Agent ID (omit to use --pattern or interactive selection)")]
+    pub id: Option<String>,
+
+    #[arg(short, long, help = "Path to the agent config YAML")]
+    pub file: String,
+
+    #[arg(
+        long,
+        help = "Agent ID pattern, e.g. 'web-*' or '*'",
+        conflicts_with = "id"
+    )]
+    pub pattern: Option<String>,
+
+    #[arg(long, help = "Config version (defaults to current time in ms)")]
+    pub version: Option<i64>,
+
+    #[arg(long, help = "Seconds to wait for agent acknowledgement")]
+    pub timeout: Option<u64>,
+}
+
+pub async fn execute(cmd: AgentConfigCmd, mode: OutputMode, server: Option<String>) -> Result<()> {
+    match cmd {
+        AgentConfigCmd::Push(args) => push(args, mode, server).await,
+    }
+}
+
+async fn push(args: PushArgs, mode: OutputMode, server: Option<String>) -> Result<()> {
+    let config_yaml = std::fs::read_to_string(&args.file)
+        .with_context(|| format!("failed to read {}", args.file))?;
+    if config_yaml.trim().is_empty() {
+        bail!("config file {} is empty", args.file);
+    }
+
+    let api = client::build_client(server.as_deref())?;
+
+    let mut body = serde_json::json!({ "config_yaml": config_yaml });
+    if let Some(v) = args.version {
+        body["version"] = v.into();
+    }
+    if let Some(t) = args.timeout {
+        body["timeout_secs"] = t.into();
+    }
+
+    let sp = match mode {
+        OutputMode::Human => Some(spinner::create("Pushing config...")),
+        OutputMode::Json => None,
+    };
+
+    let results = match args.pattern {
+        Some(pattern) => {
+            body["agent_pattern"] = pattern.into();
+            let resp = api.post_json("/v1/agents/config", &body).await?;
+            resp["results"].as_array().cloned().unwrap_or_default()
+        }
+        None => {
+            let agent_id = match args.id {
+                Some(id) => id,
+                None => super::get::pick_agent(&api).await?,
+            };
+            let resp = api
+                .post_json(&format!("/v1/agents/{agent_id}/config"), &body)
+                .await?;
+            vec![resp]
+        }
+    };
+
+    if let Some(sp) = sp {
+        spinner::finish_clear(&sp);
+    }
+
+    match mode {
+        OutputMode::Json => print_json(&serde_json::Value::Array(results))?,
+        OutputMode::Human => render(&results),
+    }
+    Ok(())
+}
+
+fn render(results: &[serde_json::Value]) {
+    if results.is_empty() {
+        theme::print_dim("  No connected agents matched.");
+        return;
+    }
+
+    theme::print_header("Config Push");
+
+    let mut table = build_table(&["Agent", "Version", "Status", "Message"]);
+    for r in results {
+        table.add_row(vec![
+            r["agent_id"].as_str().unwrap_or("-").to_string(),
+            r["version"]
+                .as_i64()
+                .map(|v| v.to_string())
+                .unwrap_or_else(|| "-".into()),
+            status_badge(r["status"].as_str().unwrap_or("-")),
+            r["message"].as_str().unwrap_or("").to_string(),
+        ]);
+    }
+    println!("{table}");
+
+    let applied = results
+        .iter()
+        .filter(|r| r["status"].as_str() == Some("applied"))
+        .count();
+    theme::print_dim(&format!("  {applied}/{} applied", results.len()));
+}
+
+fn status_badge(status: &str) -> String {
+    match status {
+        "applied" => format!("{} Applied", "●".green()),
+        "rejected" => format!("{} Rejected", "●".red()),
+        "timeout" => format!("{} Timeout", "●".yellow()),
+        "unsupported" => format!("{} Unsupported", "●".yellow()),
+        _ => format!("{} Offline", "●".red()),
+    }
+}
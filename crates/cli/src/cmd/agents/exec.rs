@@ -0,0 +1,89 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use colored::Colorize;
+
+use crate::client;
+use crate::output::{print_json, spinner, theme, OutputMode};
+
+#[derive(Args)]
+pub struct ExecArgs {
+    #[arg(help = "Agent ID")]
+    pub id: String,
+
+    #[command(subcommand)]
+    pub action: ExecAction,
+
+    #[arg(long, global = true, help = "Seconds to wait for the command result")]
+    pub timeout: Option<u64>,
+}
+
+#[derive(Subcommand)]
+pub enum ExecAction {
+    #[command(about = "Re-read the agent config file and apply it")]
+    ReloadConfig,
+    #[command(about = "Restart the system metric collector")]
+    RestartCollector,
+    #[command(about = "Change the collection interval until the next reload")]
+    UpdateInterval {
+        #[arg(help = "New interval in seconds")]
+        seconds: u64,
+    },
+}
+
+pub async fn run(args: ExecArgs, mode: OutputMode, server: Option<String>) -> Result<()> {
+    let api = client::build_client(server.as_deref())?;
+
+    let mut body = match args.action {
+        ExecAction::ReloadConfig => serde_json::json!({ "action": "reload_config" }),
+        ExecAction::RestartCollector => serde_json::json!({ "action": "restart_collector" }),
+        ExecAction::UpdateInterval { seconds } => serde_json::json!({
+            "action": "update_interval",
+            "interval_seconds": seconds,
+        }),
+    };
+    if let Some(t) = args.timeout {
+        body["timeout_secs"] = t.into();
+    }
+
+    let sp = match mode {
+        OutputMode::Human => Some(spinner::create("Sending command...")),
+        OutputMode::Json => None,
+    };
+
+    let outcome = api
+        .post_json(&format!("/v1/agents/{}/commands", args.id), &body)
+        .await?;
+
+    if let Some(sp) = sp {
+        spinner::finish_clear(&sp);
+    }
+
+    match mode {
+        OutputMode::Json => print_json(&outcome)?,
+        OutputMode::Human => render(&outcome),
+    }
+    Ok(())
+}
+
+fn render(o: &serde_json::Value) {
+    theme::print_header("Command Result");
+    theme::print_kv("Agent", o["agent_id"].as_str().unwrap_or("-"));
+    theme::print_kv("Command", o["command_id"].as_str().unwrap_or("-"));
+    theme::print_kv("Action", o["action"].as_str().unwrap_or("-"));
+    theme::print_kv("Status", &status_badge(o["status"].as_str().unwrap_or("-")));
+    if let Some(msg) = o["message"].as_str() {
+        if !msg.is_empty() {
+            theme::print_kv("Message", msg);
+        }
+    }
+}
+
+fn status_badge(status: &str) -> String {
+    match status {
+        "succeeded" => format!("{} Succeeded", "●".green()),
+        "failed" => format!("{} Failed", "●".red()),
+        "timeout" => format!("{} Timeout", "●".yellow()),
+        "unsupported" => format!("{} Unsupported", "●".yellow()),
+        _ => format!("{} Offline", "●".red()),
+    }
+}
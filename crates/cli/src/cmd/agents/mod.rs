@@ -1,5 +1,7 @@
 mod add;
+mod config;
 mod delete;
+mod exec;
 mod generate_install;
 mod get;
 mod health;
@@ -30,6 +32,10 @@ pub enum AgentsCmd {
     Status,
     #[command(about = "Detailed health for a specific agent")]
     Health(health::HealthArgs),
+    #[command(subcommand, about = "Push configuration to connected agents")]
+    Config(config::AgentConfigCmd),
+    #[command(about = "Run a remote command on a connected agent")]
+    Exec(exec::ExecArgs),
 }
 
 pub async fn execute(cmd: AgentsCmd, mode: OutputMode, server: Option<String>) -> Result<()> {
@@ -42,5 +48,7 @@ pub async fn execute(cmd: AgentsCmd, mode: OutputMode, server: Option<String>) -
         AgentsCmd::GenerateInstall(args) => generate_install::run(args, mode, server).await,
         AgentsCmd::Status => status::run(mode, server).await,
         AgentsCmd::Health(args) => health::run(args, mode, server).await,
+        AgentsCmd::Config(cmd) => config::execute(cmd, mode, server).await,
+        AgentsCmd::Exec(args) => exec::run(args, mode, server).await,
     }
 }
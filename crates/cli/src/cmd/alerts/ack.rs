@@ -0,0 +1,72 @@
+use anyhow::Result;
+use clap::Args;
+
+use crate::client;
+use crate::output::{print_json, spinner, theme, OutputMode};
+
+#[derive(Args)]
+pub struct AckArgs {
+    #[arg(help = "Alert ID")]
+    pub id: String,
+
+    #[arg(long, short, help = "Note stored with the acknowledgement")]
+    pub comment: Option<String>,
+}
+
+#[derive(Args)]
+pub struct UnackArgs {
+    #[arg(help = "Alert ID")]
+    pub id: String,
+}
+
+pub async fn run(args: AckArgs, mode: OutputMode, server: Option<String>) -> Result<()> {
+    let api = client::build_client(server.as_deref())?;
+
+    let sp = match mode {
+        OutputMode::Human => Some(spinner::create("Acknowledging alert...")),
+        OutputMode::Json => None,
+    };
+
+    let body = serde_json::json!({ "comment": args.comment.unwrap_or_default() });
+    let ack = api
+        .post_json(&format!("/v1/alerts/{}/ack", args.id), &body)
+        .await?;
+
+    if let Some(sp) = sp {
+        spinner::finish_ok(
+            &sp,
+            &format!(
+                "Alert acknowledged by {}",
+                ack["acked_by"].as_str().unwrap_or("-")
+            ),
+        );
+        theme::print_dim("  Further notifications are suppressed until the alert resolves.");
+    } else {
+        print_json(&ack)?;
+    }
+
+    Ok(())
+}
+
+pub async fn run_unack(args: UnackArgs, mode: OutputMode, server: Option<String>) -> Result<()> {
+    let api = client::build_client(server.as_deref())?;
+
+    let status = api
+        .delete_path(&format!("/v1/alerts/{}/ack", args.id))
+        .await?;
+    if status.as_u16() == 404 {
+        anyhow::bail!("alert '{}' is not acknowledged", args.id);
+    }
+
+    match mode {
+        OutputMode::Json => print_json(&serde_json::json!({
+            "unacked": status.is_success(),
+            "id": args.id,
+        }))?,
+        OutputMode::Human => {
+            theme::print_dim(&format!("  Acknowledgement removed from {}", args.id))
+        }
+    }
+
+    Ok(())
+}
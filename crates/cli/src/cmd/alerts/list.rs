@@ -2,7 +2,7 @@ use anyhow::Result;
 use clap::Args;
 
 use crate::client;
-use crate::output::{build_table, print_json, spinner, theme, OutputMode};
+use crate::output::{build_table, print_json, series_name, spinner, theme, OutputMode};
 
 #[derive(Args)]
 pub struct ListArgs {
@@ -60,12 +60,20 @@ pub async fn run(args: ListArgs, mode: OutputMode, server: Option<String>) -> Re
                 "Status", "Severity", "Rule", "Agent", "Metric", "Value", "Fired At",
             ]);
             for a in arr {
+                let mut status = a["status"].as_str().unwrap_or("-").to_string();
+                if a["acked_by"].is_string() {
+                    status.push_str(" (acked)");
+                } else if a["suppressed_by"] == "flapping" {
+                    status.push_str(" (flapping)");
+                } else if a["suppressed_by"].is_string() {
+                    status.push_str(" (silenced)");
+                }
                 table.add_row(vec![
-                    a["status"].as_str().unwrap_or("-"),
+                    &status,
                     a["severity"].as_str().unwrap_or("-"),
                     a["rule_name"].as_str().unwrap_or("-"),
                     a["agent_id"].as_str().unwrap_or("-"),
-                    a["metric_name"].as_str().unwrap_or("-"),
+                    &series_name(a["metric_name"].as_str().unwrap_or("-"), &a["labels"]),
                     &a["value"].to_string(),
                     a["fired_at"].as_str().unwrap_or("-"),
                 ]);
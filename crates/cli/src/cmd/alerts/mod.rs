@@ -1,5 +1,8 @@
+mod ack;
 mod get;
 mod list;
+mod stats;
+mod timeline;
 
 use anyhow::Result;
 use clap::Subcommand;
@@ -12,11 +15,23 @@ pub enum AlertsCmd {
     List(list::ListArgs),
     #[command(about = "Get details for a specific alert", visible_alias = "show")]
     Get(get::GetArgs),
+    #[command(about = "Acknowledge a firing alert")]
+    Ack(ack::AckArgs),
+    #[command(about = "Remove an acknowledgement")]
+    Unack(ack::UnackArgs),
+    #[command(about = "MTTR, firing frequency, noisy rules and flapping series")]
+    Stats(stats::StatsArgs),
+    #[command(about = "Incidents with firing and resolve times")]
+    Timeline(timeline::TimelineArgs),
 }
 
 pub async fn execute(cmd: AlertsCmd, mode: OutputMode, server: Option<String>) -> Result<()> {
     match cmd {
         AlertsCmd::List(args) => list::run(args, mode, server).await,
         AlertsCmd::Get(args) => get::run(args, mode, server).await,
+        AlertsCmd::Ack(args) => ack::run(args, mode, server).await,
+        AlertsCmd::Unack(args) => ack::run_unack(args, mode, server).await,
+        AlertsCmd::Stats(args) => stats::run(args, mode, server).await,
+        AlertsCmd::Timeline(args) => timeline::run(args, mode, server).await,
     }
 }
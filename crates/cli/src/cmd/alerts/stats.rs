@@ -0,0 +1,118 @@
+use anyhow::{bail, Result};
+use clap::Args;
+use sentinel_common::template::humanize_duration_ms;
+
+use crate::client;
+use crate::cmd::metrics::history::parse_duration;
+use crate::output::{build_table, print_json, series_name, spinner, theme, OutputMode};
+
+#[derive(Args)]
+pub struct StatsArgs {
+    #[arg(long, default_value = "rule", help = "Group by rule or agent")]
+    pub by: String,
+
+    #[arg(long, default_value = "24h", help = "Time window, e.g. 6h, 7d")]
+    pub since: String,
+
+    #[arg(long, default_value = "10", help = "Show the N noisiest groups")]
+    pub top: usize,
+
+    #[arg(long, default_value = "1h", help = "Flapping detection window")]
+    pub flap_window: String,
+
+    #[arg(
+        long,
+        default_value = "4",
+        help = "State changes allowed before a series counts as flapping"
+    )]
+    pub flap_threshold: usize,
+}
+
+pub async fn run(args: StatsArgs, mode: OutputMode, server: Option<String>) -> Result<()> {
+    if args.by != "rule" && args.by != "agent" {
+        bail!("--by must be 'rule' or 'agent'");
+    }
+    let window_secs = parse_duration(&args.since)?.num_seconds();
+    let flap_window_secs = parse_duration(&args.flap_window)?.num_seconds();
+
+    let api = client::build_client(server.as_deref())?;
+    let sp = match mode {
+        OutputMode::Human => Some(spinner::create("Computing alert stats...")),
+        OutputMode::Json => None,
+    };
+
+    let stats = api
+        .get_json(&format!(
+            "/v1/alerts/stats?by={}&window_secs={window_secs}&limit={}",
+            args.by, args.top
+        ))
+        .await?;
+    let flapping = api
+        .get_json(&format!(
+            "/v1/alerts/flapping?window_secs={flap_window_secs}&threshold={}",
+            args.flap_threshold
+        ))
+        .await?;
+
+    if let Some(sp) = sp {
+        spinner::finish_clear(&sp);
+    }
+
+    match mode {
+        OutputMode::Json => print_json(&serde_json::json!({
+            "stats": stats,
+            "flapping": flapping,
+        }))?,
+        OutputMode::Human => {
+            theme::print_header(&format!("Alert Stats (last {})", args.since));
+            theme::print_kv("Incidents", &stats["incidents"].to_string());
+            theme::print_kv("MTTR", &secs(&stats["mttr_secs"]));
+
+            let empty = vec![];
+            let groups = stats["groups"].as_array().unwrap_or(&empty);
+            if groups.is_empty() {
+                theme::print_dim("  No incidents in this window.");
+            } else {
+                let label = if args.by == "agent" { "Agent" } else { "Rule" };
+                theme::print_section(&format!("Noisiest by {}", args.by));
+                let mut table =
+                    build_table(&[label, "Incidents", "Open", "Per Day", "MTTR", "Longest"]);
+                for g in groups {
+                    table.add_row(vec![
+                        g["name"].as_str().unwrap_or("-").to_string(),
+                        g["incidents"].to_string(),
+                        g["open"].to_string(),
+                        g["per_day"].to_string(),
+                        secs(&g["mttr_secs"]),
+                        secs(&g["max_duration_secs"]),
+                    ]);
+                }
+                println!("{table}");
+            }
+
+            let series = flapping["series"].as_array().unwrap_or(&empty);
+            if !series.is_empty() {
+                theme::print_section(&format!("Flapping (last {})", args.flap_window));
+                let mut table = build_table(&["Rule", "Agent", "Series", "Changes"]);
+                for s in series {
+                    table.add_row(vec![
+                        s["rule_name"].as_str().unwrap_or("-").to_string(),
+                        s["agent_id"].as_str().unwrap_or("-").to_string(),
+                        series_name(s["metric_name"].as_str().unwrap_or("-"), &s["labels"]),
+                        s["changes"].to_string(),
+                    ]);
+                }
+                println!("{table}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub(super) fn secs(v: &serde_json::Value) -> String {
+    match v.as_i64() {
+        Some(s) => humanize_duration_ms(s * 1000),
+        None => "-".into(),
+    }
+}
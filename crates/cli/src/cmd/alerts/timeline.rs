@@ -0,0 +1,84 @@
+use anyhow::Result;
+use clap::Args;
+
+use super::stats::secs;
+use crate::client;
+use crate::cmd::metrics::history::parse_duration;
+use crate::output::{build_table, print_json, series_name, spinner, theme, OutputMode};
+
+#[derive(Args)]
+pub struct TimelineArgs {
+    #[arg(long, help = "Filter by rule ID")]
+    pub rule: Option<String>,
+
+    #[arg(long, help = "Filter by agent ID")]
+    pub agent: Option<String>,
+
+    #[arg(long, help = "Filter by alert fingerprint")]
+    pub fingerprint: Option<String>,
+
+    #[arg(long, default_value = "24h", help = "Time window, e.g. 6h, 7d")]
+    pub since: String,
+
+    #[arg(long, default_value = "50", help = "Max number of incidents")]
+    pub limit: i64,
+}
+
+pub async fn run(args: TimelineArgs, mode: OutputMode, server: Option<String>) -> Result<()> {
+    let window_secs = parse_duration(&args.since)?.num_seconds();
+    let mut query_parts = vec![
+        format!("window_secs={window_secs}"),
+        format!("limit={}", args.limit),
+    ];
+    if let Some(ref r) = args.rule {
+        query_parts.push(format!("rule_id={r}"));
+    }
+    if let Some(ref a) = args.agent {
+        query_parts.push(format!("agent_id={a}"));
+    }
+    if let Some(ref f) = args.fingerprint {
+        query_parts.push(format!("fingerprint={f}"));
+    }
+
+    let api = client::build_client(server.as_deref())?;
+    let sp = match mode {
+        OutputMode::Human => Some(spinner::create("Fetching incidents...")),
+        OutputMode::Json => None,
+    };
+    let incidents = api
+        .get_json(&format!("/v1/alerts/incidents?{}", query_parts.join("&")))
+        .await?;
+    if let Some(sp) = sp {
+        spinner::finish_clear(&sp);
+    }
+
+    match mode {
+        OutputMode::Json => print_json(&incidents)?,
+        OutputMode::Human => {
+            let empty = vec![];
+            let arr = incidents.as_array().unwrap_or(&empty);
+            if arr.is_empty() {
+                theme::print_dim("  No incidents found.");
+                return Ok(());
+            }
+            theme::print_header(&format!("Incident Timeline (last {})", args.since));
+            let mut table = build_table(&[
+                "Started", "Resolved", "Duration", "Severity", "Rule", "Agent", "Series",
+            ]);
+            for i in arr {
+                table.add_row(vec![
+                    i["started_at"].as_str().unwrap_or("-").to_string(),
+                    i["resolved_at"].as_str().unwrap_or("open").to_string(),
+                    secs(&i["duration_secs"]),
+                    i["severity"].as_str().unwrap_or("-").to_string(),
+                    i["rule_name"].as_str().unwrap_or("-").to_string(),
+                    i["agent_id"].as_str().unwrap_or("-").to_string(),
+                    series_name(i["metric_name"].as_str().unwrap_or("-"), &i["labels"]),
+                ]);
+            }
+            println!("{table}");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use anyhow::Result;
+use sentinel_common::envelope::MasterKey;
+
+use crate::output::{print_json, theme, OutputMode};
+
+pub fn run(mode: OutputMode) -> Result<()> {
+    let key = MasterKey::generate();
+
+    match mode {
+        OutputMode::Json => print_json(&serde_json::json!({
+            "key_id": key.id(),
+            "key": key.to_base64(),
+        }))?,
+        OutputMode::Human => {
+            theme::print_kv("Key ID", key.id());
+            theme::print_kv("Key", &key.to_base64());
+            theme::print_dim("  Export as SENTINEL_ENCRYPTION_KEY on the server and workers.");
+        }
+    }
+
+    Ok(())
+}
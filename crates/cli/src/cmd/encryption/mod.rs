@@ -0,0 +1,26 @@
+mod generate_key;
+mod rotate;
+mod status;
+
+use anyhow::Result;
+use clap::Subcommand;
+
+use crate::output::OutputMode;
+
+#[derive(Subcommand)]
+pub enum EncryptionCmd {
+    #[command(about = "Show master key status and secrets pending re-encryption")]
+    Status,
+    #[command(about = "Re-encrypt stored secrets with the current master key")]
+    Rotate(rotate::RotateArgs),
+    #[command(about = "Generate a new base64 master key")]
+    GenerateKey,
+}
+
+pub async fn execute(cmd: EncryptionCmd, mode: OutputMode, server: Option<String>) -> Result<()> {
+    match cmd {
+        EncryptionCmd::Status => status::run(mode, server).await,
+        EncryptionCmd::Rotate(args) => rotate::run(args, mode, server).await,
+        EncryptionCmd::GenerateKey => generate_key::run(mode),
+    }
+}
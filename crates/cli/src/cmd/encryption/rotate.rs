@@ -0,0 +1,48 @@
+use anyhow::Result;
+use clap::Args;
+
+use crate::client;
+use crate::output::{confirm, print_json, spinner, theme, OutputMode};
+
+#[derive(Args)]
+pub struct RotateArgs {
+    #[arg(long, help = "Skip confirmation")]
+    pub yes: bool,
+}
+
+pub async fn run(args: RotateArgs, mode: OutputMode, server: Option<String>) -> Result<()> {
+    let api = client::build_client(server.as_deref())?;
+
+    if mode == OutputMode::Human
+        && !args.yes
+        && !confirm::confirm_action("Re-encrypt all stored secrets with the current master key?")
+    {
+        theme::print_dim("  Cancelled.");
+        return Ok(());
+    }
+
+    let sp = match mode {
+        OutputMode::Human => Some(spinner::create("Re-encrypting secrets...")),
+        OutputMode::Json => None,
+    };
+
+    let result = api.post_empty("/v1/encryption/rotate").await?;
+
+    if let Some(sp) = sp {
+        spinner::finish_ok(
+            &sp,
+            &format!(
+                "Re-encrypted {} notifiers and {} agents with key {}",
+                result["notifiers"].as_u64().unwrap_or(0),
+                result["agents"].as_u64().unwrap_or(0),
+                result["key_id"].as_str().unwrap_or("-"),
+            ),
+        );
+    }
+
+    if mode == OutputMode::Json {
+        print_json(&result)?;
+    }
+
+    Ok(())
+}
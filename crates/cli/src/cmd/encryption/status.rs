@@ -0,0 +1,58 @@
+use anyhow::Result;
+
+use crate::client;
+use crate::output::{print_json, spinner, theme, OutputMode};
+
+pub async fn run(mode: OutputMode, server: Option<String>) -> Result<()> {
+    let api = client::build_client(server.as_deref())?;
+
+    let sp = match mode {
+        OutputMode::Human => Some(spinner::create("Fetching encryption status...")),
+        OutputMode::Json => None,
+    };
+
+    let status = api.get_json("/v1/encryption").await?;
+
+    if let Some(sp) = sp {
+        spinner::finish_clear(&sp);
+    }
+
+    match mode {
+        OutputMode::Json => print_json(&status)?,
+        OutputMode::Human => {
+            theme::print_header("Encryption at rest");
+            let enabled = status["enabled"].as_bool().unwrap_or(false);
+            theme::print_kv_colored(
+                "Status",
+                if enabled { "enabled" } else { "disabled" },
+                enabled,
+            );
+            theme::print_kv("Master key", status["key_id"].as_str().unwrap_or("-"));
+            let previous: Vec<&str> = status["previous_key_ids"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|k| k.as_str())
+                .collect();
+            if !previous.is_empty() {
+                theme::print_kv("Previous keys", &previous.join(", "));
+            }
+            if let Some(pending) = status.get("pending") {
+                let notifiers = pending["notifiers"].as_u64().unwrap_or(0);
+                let agents = pending["agents"].as_u64().unwrap_or(0);
+                theme::print_kv_colored(
+                    "Pending",
+                    &format!("{notifiers} notifiers, {agents} agents"),
+                    notifiers + agents == 0,
+                );
+            }
+            if !enabled {
+                theme::print_dim(
+                    "  Set SENTINEL_ENCRYPTION_KEY on the server and workers to enable.",
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
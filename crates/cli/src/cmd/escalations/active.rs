@@ -0,0 +1,61 @@
+use anyhow::Result;
+
+use crate::client;
+use crate::output::{build_table, print_json, spinner, theme, OutputMode};
+
+pub async fn run(mode: OutputMode, server: Option<String>) -> Result<()> {
+    let api = client::build_client(server.as_deref())?;
+
+    let sp = match mode {
+        OutputMode::Human => Some(spinner::create("Fetching escalations...")),
+        OutputMode::Json => None,
+    };
+
+    let active = api.get_json("/v1/escalations").await?;
+
+    if let Some(sp) = sp {
+        spinner::finish_clear(&sp);
+    }
+
+    match mode {
+        OutputMode::Json => print_json(&active)?,
+        OutputMode::Human => {
+            let arr = active.as_array().map(|a| a.as_slice()).unwrap_or(&[]);
+            if arr.is_empty() {
+                theme::print_dim("  No running escalations.");
+                return Ok(());
+            }
+            theme::print_header("Escalations");
+            let mut table = build_table(&["Alert", "Policy", "Step", "Next", "Notified"]);
+            for e in arr {
+                let next = match (e["next_at_ms"].as_i64(), e["stopped_reason"].as_str()) {
+                    (Some(ms), _) => format_ms(ms),
+                    (None, Some(reason)) => reason.to_string(),
+                    (None, None) => "-".into(),
+                };
+                let notified: Vec<&str> = e["notified"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|id| id.as_str())
+                    .collect();
+                table.add_row(vec![
+                    e["alert_id"].as_str().unwrap_or("-"),
+                    e["policy_id"].as_str().unwrap_or("-"),
+                    &e["step"].as_i64().unwrap_or(0).to_string(),
+                    &next,
+                    &notified.join(", "),
+                ]);
+            }
+            println!("{table}");
+        }
+    }
+
+    Ok(())
+}
+
+fn format_ms(ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(ms)
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| "-".into())
+}
@@ -0,0 +1,77 @@
+use anyhow::{bail, Context, Result};
+use clap::Args;
+
+use crate::client;
+use crate::cmd::rules::create::parse_secs;
+use crate::output::{print_json, spinner, theme, OutputMode};
+
+#[derive(Args)]
+pub struct CreateArgs {
+    #[arg(long, help = "Policy name")]
+    pub name: String,
+
+    #[arg(long, help = "Free-form description")]
+    pub description: Option<String>,
+
+    #[arg(
+        long = "step",
+        required = true,
+        help = "Step as DELAY=NOTIFIER[,NOTIFIER], delay relative to the previous step, e.g. 0=n1 or 10m=n2 (repeatable, in order)"
+    )]
+    pub steps: Vec<String>,
+}
+
+pub(super) fn parse_steps(raw: &[String]) -> Result<serde_json::Value> {
+    let mut steps = Vec::new();
+    for s in raw {
+        let (delay, ids) = s
+            .split_once('=')
+            .with_context(|| format!("bad --step '{s}', expected DELAY=NOTIFIER[,NOTIFIER]"))?;
+        let ids: Vec<&str> = ids
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .collect();
+        if ids.is_empty() {
+            bail!("--step '{s}' names no notifiers");
+        }
+        steps.push(serde_json::json!({
+            "delay_secs": parse_secs(delay)?,
+            "notifier_ids": ids,
+        }));
+    }
+    Ok(steps.into())
+}
+
+pub async fn run(args: CreateArgs, mode: OutputMode, server: Option<String>) -> Result<()> {
+    let api = client::build_client(server.as_deref())?;
+
+    let body = serde_json::json!({
+        "name": args.name,
+        "description": args.description.unwrap_or_default(),
+        "steps": parse_steps(&args.steps)?,
+    });
+
+    let sp = match mode {
+        OutputMode::Human => Some(spinner::create("Creating escalation policy...")),
+        OutputMode::Json => None,
+    };
+
+    let created = api.post_json("/v1/escalation-policies", &body).await?;
+
+    if let Some(sp) = sp {
+        spinner::finish_ok(
+            &sp,
+            &format!(
+                "Escalation policy '{}' created ({})",
+                created["name"].as_str().unwrap_or("-"),
+                created["id"].as_str().unwrap_or("-")
+            ),
+        );
+        theme::print_kv("Steps", &super::describe_steps(&created));
+    } else {
+        print_json(&created)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anyhow::{bail, Result};
+use clap::Args;
+
+use crate::client;
+use crate::output::{confirm, print_json, spinner, theme, OutputMode};
+
+#[derive(Args)]
+pub struct DeleteArgs {
+    #[arg(help = "Policy ID")]
+    pub id: String,
+
+    #[arg(long, help = "Skip confirmation")]
+    pub yes: bool,
+}
+
+pub async fn run(args: DeleteArgs, mode: OutputMode, server: Option<String>) -> Result<()> {
+    let api = client::build_client(server.as_deref())?;
+
+    if mode == OutputMode::Human
+        && !args.yes
+        && !confirm::confirm_action(&format!("Delete escalation policy '{}'?", args.id))
+    {
+        theme::print_dim("  Cancelled.");
+        return Ok(());
+    }
+
+    let sp = match mode {
+        OutputMode::Human => Some(spinner::create("Deleting escalation policy...")),
+        OutputMode::Json => None,
+    };
+
+    let status = api
+        .delete_path(&format!("/v1/escalation-policies/{}", args.id))
+        .await?;
+    match status.as_u16() {
+        404 => bail!("escalation policy '{}' not found", args.id),
+        409 => bail!(
+            "escalation policy '{}' is still used by a rule or the routing tree",
+            args.id
+        ),
+        _ => {}
+    }
+
+    if let Some(sp) = sp {
+        spinner::finish_ok(&sp, &format!("Escalation policy '{}' deleted", args.id));
+    }
+
+    if mode == OutputMode::Json {
+        print_json(&serde_json::json!({
+            "deleted": status.is_success(),
+            "id": args.id,
+        }))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anyhow::Result;
+use clap::Args;
+
+use crate::client;
+use crate::output::{print_json, spinner, theme, OutputMode};
+
+#[derive(Args)]
+pub struct GetArgs {
+    #[arg(help = "Policy ID")]
+    pub id: String,
+}
+
+pub async fn run(args: GetArgs, mode: OutputMode, server: Option<String>) -> Result<()> {
+    let api = client::build_client(server.as_deref())?;
+
+    let sp = match mode {
+        OutputMode::Human => Some(spinner::create("Fetching escalation policy...")),
+        OutputMode::Json => None,
+    };
+
+    let policy = api
+        .get_json(&format!("/v1/escalation-policies/{}", args.id))
+        .await?;
+
+    if let Some(sp) = sp {
+        spinner::finish_clear(&sp);
+    }
+
+    match mode {
+        OutputMode::Json => print_json(&policy)?,
+        OutputMode::Human => {
+            theme::print_header("Escalation Policy");
+            theme::print_kv("ID", policy["id"].as_str().unwrap_or("-"));
+            theme::print_kv("Name", policy["name"].as_str().unwrap_or("-"));
+            theme::print_kv("Description", policy["description"].as_str().unwrap_or(""));
+            for (i, step) in policy["steps"].as_array().into_iter().flatten().enumerate() {
+                let ids: Vec<&str> = step["notifier_ids"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|id| id.as_str())
+                    .collect();
+                theme::print_kv(
+                    &format!("Step {}", i + 1),
+                    &format!(
+                        "after {}s -> {}",
+                        step["delay_secs"].as_u64().unwrap_or(0),
+                        ids.join(", ")
+                    ),
+                );
+            }
+            println!();
+        }
+    }
+
+    Ok(())
+}
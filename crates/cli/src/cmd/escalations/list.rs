@@ -0,0 +1,42 @@
+use anyhow::Result;
+
+use crate::client;
+use crate::output::{build_table, print_json, spinner, theme, OutputMode};
+
+pub async fn run(mode: OutputMode, server: Option<String>) -> Result<()> {
+    let api = client::build_client(server.as_deref())?;
+
+    let sp = match mode {
+        OutputMode::Human => Some(spinner::create("Fetching escalation policies...")),
+        OutputMode::Json => None,
+    };
+
+    let policies = api.get_json("/v1/escalation-policies").await?;
+
+    if let Some(sp) = sp {
+        spinner::finish_clear(&sp);
+    }
+
+    match mode {
+        OutputMode::Json => print_json(&policies)?,
+        OutputMode::Human => {
+            let arr = policies.as_array().map(|a| a.as_slice()).unwrap_or(&[]);
+            if arr.is_empty() {
+                theme::print_dim("  No escalation policies found.");
+                return Ok(());
+            }
+            theme::print_header("Escalation Policies");
+            let mut table = build_table(&["ID", "Name", "Steps"]);
+            for p in arr {
+                table.add_row(vec![
+                    p["id"].as_str().unwrap_or("-"),
+                    p["name"].as_str().unwrap_or("-"),
+                    &super::describe_steps(p),
+                ]);
+            }
+            println!("{table}");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+mod active;
+mod create;
+mod delete;
+mod get;
+mod list;
+mod routes;
+mod update;
+
+use anyhow::Result;
+use clap::Subcommand;
+
+use crate::output::OutputMode;
+
+#[derive(Subcommand)]
+pub enum EscalationsCmd {
+    #[command(about = "List escalation policies", visible_alias = "ls")]
+    List,
+    #[command(about = "Get details for an escalation policy", visible_alias = "show")]
+    Get(get::GetArgs),
+    #[command(about = "Create an escalation policy", visible_alias = "add")]
+    Create(create::CreateArgs),
+    #[command(about = "Update an escalation policy")]
+    Update(update::UpdateArgs),
+    #[command(about = "Delete an escalation policy", visible_alias = "rm")]
+    Delete(delete::DeleteArgs),
+    #[command(about = "Show or replace the escalation routing tree")]
+    Routes(routes::RoutesArgs),
+    #[command(about = "List running escalations")]
+    Active,
+}
+
+pub async fn execute(cmd: EscalationsCmd, mode: OutputMode, server: Option<String>) -> Result<()> {
+    match cmd {
+        EscalationsCmd::List => list::run(mode, server).await,
+        EscalationsCmd::Get(args) => get::run(args, mode, server).await,
+        EscalationsCmd::Create(args) => create::run(args, mode, server).await,
+        EscalationsCmd::Update(args) => update::run(args, mode, server).await,
+        EscalationsCmd::Delete(args) => delete::run(args, mode, server).await,
+        EscalationsCmd::Routes(args) => routes::run(args, mode, server).await,
+        EscalationsCmd::Active => active::run(mode, server).await,
+    }
+}
+
+pub(super) fn describe_steps(policy: &serde_json::Value) -> String {
+    let steps: Vec<String> = policy["steps"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|s| {
+            let ids: Vec<&str> = s["notifier_ids"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|id| id.as_str())
+                .collect();
+            format!(
+                "{}: {}",
+                format_delay(s["delay_secs"].as_u64().unwrap_or(0)),
+                ids.join(", ")
+            )
+        })
+        .collect();
+    if steps.is_empty() {
+        "-".into()
+    } else {
+        steps.join(" -> ")
+    }
+}
+
+fn format_delay(secs: u64) -> String {
+    match secs {
+        0 => "now".into(),
+        s if s % 3600 == 0 => format!("+{}h", s / 3600),
+        s if s % 60 == 0 => format!("+{}m", s / 60),
+        s => format!("+{s}s"),
+    }
+}
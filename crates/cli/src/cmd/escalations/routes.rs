@@ -0,0 +1,90 @@
+use anyhow::{Context, Result};
+use clap::Args;
+
+use crate::client;
+use crate::output::{print_json, spinner, theme, OutputMode};
+
+#[derive(Args)]
+pub struct RoutesArgs {
+    #[arg(
+        long,
+        help = "Replace the routing tree with this JSON file or inline JSON"
+    )]
+    pub set: Option<String>,
+}
+
+pub async fn run(args: RoutesArgs, mode: OutputMode, server: Option<String>) -> Result<()> {
+    let api = client::build_client(server.as_deref())?;
+
+    let tree = match args.set {
+        Some(ref raw) => {
+            let content = if std::path::Path::new(raw).exists() {
+                std::fs::read_to_string(raw)?
+            } else {
+                raw.clone()
+            };
+            let tree: serde_json::Value =
+                serde_json::from_str(&content).context("routing tree is not valid JSON")?;
+            let sp = match mode {
+                OutputMode::Human => Some(spinner::create("Updating escalation routes...")),
+                OutputMode::Json => None,
+            };
+            let updated = api.put_json("/v1/escalation-routes", &tree).await?;
+            if let Some(sp) = sp {
+                spinner::finish_ok(&sp, "Escalation routes updated");
+            }
+            updated
+        }
+        None => api.get_json("/v1/escalation-routes").await?,
+    };
+
+    match mode {
+        OutputMode::Json => print_json(&tree)?,
+        OutputMode::Human => {
+            theme::print_header("Escalation Routes");
+            print_route(&tree, 0);
+            println!();
+        }
+    }
+
+    Ok(())
+}
+
+fn print_route(route: &serde_json::Value, depth: usize) {
+    let mut matchers = Vec::new();
+    if let Some(a) = route["agent_pattern"].as_str() {
+        matchers.push(format!("agent={a}"));
+    }
+    let severities: Vec<&str> = route["severity"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|s| s.as_str())
+        .collect();
+    if !severities.is_empty() {
+        matchers.push(format!("severity={}", severities.join("|")));
+    }
+    for m in route["label_matchers"].as_array().into_iter().flatten() {
+        matchers.push(format!(
+            "{}{}{}",
+            m["label"].as_str().unwrap_or("?"),
+            m["op"].as_str().unwrap_or("="),
+            m["value"].as_str().unwrap_or("")
+        ));
+    }
+    let matchers = if matchers.is_empty() {
+        "*".to_string()
+    } else {
+        matchers.join(", ")
+    };
+    let policy = route["policy_id"].as_str().unwrap_or("-");
+    let cont = if route["continue"].as_bool().unwrap_or(false) {
+        " (continue)"
+    } else {
+        ""
+    };
+    println!("  {}{matchers} => {policy}{cont}", "  ".repeat(depth));
+    for child in route["routes"].as_array().into_iter().flatten() {
+        print_route(child, depth + 1);
+    }
+}
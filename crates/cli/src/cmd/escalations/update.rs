@@ -0,0 +1,57 @@
+use anyhow::Result;
+use clap::Args;
+
+use crate::client;
+use crate::output::{print_json, spinner, OutputMode};
+
+#[derive(Args)]
+pub struct UpdateArgs {
+    #[arg(help = "Policy ID")]
+    pub id: String,
+
+    #[arg(long, help = "Rename the policy")]
+    pub name: Option<String>,
+
+    #[arg(long, help = "Replace the description")]
+    pub description: Option<String>,
+
+    #[arg(
+        long = "step",
+        help = "Replace all steps (repeatable, same format as create)"
+    )]
+    pub steps: Vec<String>,
+}
+
+pub async fn run(args: UpdateArgs, mode: OutputMode, server: Option<String>) -> Result<()> {
+    let api = client::build_client(server.as_deref())?;
+
+    let sp = match mode {
+        OutputMode::Human => Some(spinner::create("Updating escalation policy...")),
+        OutputMode::Json => None,
+    };
+
+    let path = format!("/v1/escalation-policies/{}", args.id);
+    let mut body = api.get_json(&path).await?;
+    if let Some(obj) = body.as_object_mut() {
+        obj.remove("id");
+    }
+    if let Some(name) = args.name {
+        body["name"] = name.into();
+    }
+    if let Some(description) = args.description {
+        body["description"] = description.into();
+    }
+    if !args.steps.is_empty() {
+        body["steps"] = super::create::parse_steps(&args.steps)?;
+    }
+
+    let updated = api.put_json(&path, &body).await?;
+
+    if let Some(sp) = sp {
+        spinner::finish_ok(&sp, &format!("Escalation policy {} updated", args.id));
+    } else {
+        print_json(&updated)?;
+    }
+
+    Ok(())
+}
@@ -124,8 +124,12 @@ pub(crate) fn parse_range(last: &str) -> Result<(String, String)> {
     Ok((from.to_rfc3339(), now.to_rfc3339()))
 }
 
-fn parse_duration(s: &str) -> Result<chrono::Duration> {
+pub(crate) fn parse_duration(s: &str) -> Result<chrono::Duration> {
     let s = s.trim();
+    if let Some(sec) = s.strip_suffix('s') {
+        let n: i64 = sec.parse()?;
+        return Ok(chrono::Duration::seconds(n));
+    }
     if let Some(h) = s.strip_suffix('h') {
         let n: i64 = h.parse()?;
         return Ok(chrono::Duration::hours(n));
@@ -138,5 +142,5 @@ fn parse_duration(s: &str) -> Result<chrono::Duration> {
         let n: i64 = m.parse()?;
         return Ok(chrono::Duration::minutes(n));
     }
-    anyhow::bail!("Invalid duration '{s}'. Use e.g. 30m, 1h, 6h, 1d")
+    anyhow::bail!("Invalid duration '{s}'. Use e.g. 30s, 30m, 1h, 6h, 1d")
 }
@@ -4,6 +4,8 @@ mod cluster;
 mod completions;
 mod config;
 mod doctor;
+mod encryption;
+mod escalations;
 mod force_send;
 mod health;
 mod init;
@@ -13,6 +15,7 @@ mod notifiers;
 mod plugins;
 mod register;
 mod rules;
+mod silences;
 mod status;
 mod version;
 pub(crate) mod wal;
@@ -50,6 +53,19 @@ pub enum Commands {
     #[command(subcommand, about = "Alert rule management")]
     Rules(rules::RulesCmd),
 
+    #[command(subcommand, about = "Silences and maintenance windows")]
+    Silences(silences::SilencesCmd),
+
+    #[command(
+        subcommand,
+        about = "Escalation policies and routing",
+        visible_alias = "escalation"
+    )]
+    Escalations(escalations::EscalationsCmd),
+
+    #[command(subcommand, about = "Secrets encryption at rest")]
+    Encryption(encryption::EncryptionCmd),
+
     #[command(subcommand, about = "Notifier management", visible_alias = "notify")]
     Notifiers(notifiers::NotifiersCmd),
 
@@ -92,6 +108,9 @@ pub async fn run(opts: crate::Opts) -> Result<()> {
         Commands::Cluster(cmd) => cluster::execute(cmd, mode, opts.server).await,
         Commands::Config(cmd) => config::execute(cmd, mode).await,
         Commands::Rules(cmd) => rules::execute(cmd, mode, opts.server).await,
+        Commands::Silences(cmd) => silences::execute(cmd, mode, opts.server).await,
+        Commands::Escalations(cmd) => escalations::execute(cmd, mode, opts.server).await,
+        Commands::Encryption(cmd) => encryption::execute(cmd, mode, opts.server).await,
         Commands::Notifiers(cmd) => notifiers::execute(cmd, mode, opts.server).await,
         Commands::Plugins(cmd) => plugins::execute(cmd, mode).await,
         Commands::Key(cmd) => {
@@ -2,9 +2,10 @@ use anyhow::Result;
 use clap::Args;
 
 use crate::client;
+use crate::cmd::rules::create::GroupingArgs;
 use crate::output::{input, print_json, select, spinner, theme, OutputMode};
 
-const NOTIFIER_TYPES: &[&str] = &[
+pub(super) const NOTIFIER_TYPES: &[&str] = &[
     "webhook",
     "slack",
     "discord",
@@ -15,6 +16,13 @@ const NOTIFIER_TYPES: &[&str] = &[
     "opsgenie",
     "gotify",
     "ntfy",
+    "mattermost",
+    "rocketchat",
+    "matrix",
+    "googlechat",
+    "pushover",
+    "victorops",
+    "sns",
 ];
 
 #[derive(Args)]
@@ -30,6 +38,66 @@ pub struct CreateArgs {
 
     #[arg(long, help = "Optional secret / token")]
     pub secret: Option<String>,
+
+    #[command(flatten)]
+    pub template: TemplateArgs,
+
+    #[command(flatten)]
+    pub grouping: GroupingArgs,
+}
+
+#[derive(Args)]
+pub struct TemplateArgs {
+    #[arg(
+        long,
+        help = "Message title template, inline or a file path, e.g. '{{rule_name}} on {{agent_id}}'"
+    )]
+    pub title_template: Option<String>,
+
+    #[arg(long, help = "Message body template, inline or a file path")]
+    pub body_template: Option<String>,
+
+    #[arg(
+        long,
+        help = "Title template for grouped notifications, e.g. '[{{firing_count}}] {{group_key}}'"
+    )]
+    pub group_title_template: Option<String>,
+
+    #[arg(
+        long,
+        help = "Body template for grouped notifications, inline or a file path"
+    )]
+    pub group_body_template: Option<String>,
+}
+
+impl TemplateArgs {
+    pub(super) fn is_empty(&self) -> bool {
+        self.title_template.is_none()
+            && self.body_template.is_none()
+            && self.group_title_template.is_none()
+            && self.group_body_template.is_none()
+    }
+
+    pub(super) fn apply(&self, config: &mut serde_json::Value) -> Result<()> {
+        for (key, raw) in [
+            ("title", &self.title_template),
+            ("body", &self.body_template),
+            ("group_title", &self.group_title_template),
+            ("group_body", &self.group_body_template),
+        ] {
+            let Some(raw) = raw else { continue };
+            let source = if std::path::Path::new(raw).is_file() {
+                std::fs::read_to_string(raw)?
+            } else {
+                raw.clone()
+            };
+            if !config["template"].is_object() {
+                config["template"] = serde_json::json!({});
+            }
+            config["template"][key] = source.into();
+        }
+        Ok(())
+    }
 }
 
 pub async fn run(args: CreateArgs, mode: OutputMode, server: Option<String>) -> Result<()> {
@@ -49,7 +117,7 @@ pub async fn run(args: CreateArgs, mode: OutputMode, server: Option<String>) ->
         None => anyhow::bail!("--type is required in JSON mode"),
     };
 
-    let config = if mode == OutputMode::Human && args.target.is_none() {
+    let mut config = if mode == OutputMode::Human && args.target.is_none() {
         build_config_interactive(&ntype, args.secret.as_deref())?
     } else {
         let target = match args.target {
@@ -58,6 +126,8 @@ pub async fn run(args: CreateArgs, mode: OutputMode, server: Option<String>) ->
         };
         build_config(&ntype, &target, args.secret.as_deref())
     };
+    args.template.apply(&mut config)?;
+    args.grouping.apply(&mut config)?;
 
     let body = serde_json::json!({
         "name": name,
@@ -174,6 +244,87 @@ fn build_config_interactive(ntype: &str, secret: Option<&str>) -> Result<serde_j
             }
             Ok(cfg)
         }
+        "mattermost" | "rocketchat" => {
+            let url = input::text_required("Incoming webhook URL")?;
+            let channel = input::text_optional("Channel override (optional)")?;
+            let username = input::text_optional("Display name (optional)")?;
+            let mut cfg = serde_json::json!({ "webhook_url": url });
+            if let Some(c) = channel {
+                cfg["channel"] = serde_json::json!(c);
+            }
+            if let Some(u) = username {
+                cfg["username"] = serde_json::json!(u);
+            }
+            Ok(cfg)
+        }
+        "matrix" => {
+            let homeserver_url = input::text_required("Homeserver URL")?;
+            let access_token = match secret {
+                Some(s) => s.to_string(),
+                None => input::password("Access token")?,
+            };
+            let room_id = input::text_required("Room ID (e.g. !abc:example.org)")?;
+            Ok(serde_json::json!({
+                "homeserver_url": homeserver_url,
+                "access_token": access_token,
+                "room_id": room_id,
+            }))
+        }
+        "googlechat" => {
+            let url = input::text_required("Google Chat webhook URL")?;
+            Ok(serde_json::json!({ "webhook_url": url }))
+        }
+        "pushover" => {
+            let token = match secret {
+                Some(s) => s.to_string(),
+                None => input::password("Application API token")?,
+            };
+            let user_key = input::text_required("User or group key")?;
+            let device = input::text_optional("Device (optional)")?;
+            let mut cfg = serde_json::json!({
+                "token": token,
+                "user_key": user_key,
+            });
+            if let Some(d) = device {
+                cfg["device"] = serde_json::json!(d);
+            }
+            Ok(cfg)
+        }
+        "victorops" => {
+            let api_key = match secret {
+                Some(s) => s.to_string(),
+                None => input::password("REST endpoint API key")?,
+            };
+            let routing_key = input::text_required("Routing key")?;
+            Ok(serde_json::json!({
+                "api_key": api_key,
+                "routing_key": routing_key,
+            }))
+        }
+        "sns" => {
+            let topic_arn = input::text_required("Topic ARN")?;
+            let region = match region_from_arn(&topic_arn) {
+                Some(r) => r,
+                None => input::text_required("Region")?,
+            };
+            let access_key_id = input::text_required("Access key ID")?;
+            let secret_access_key = match secret {
+                Some(s) => s.to_string(),
+                None => input::password("Secret access key")?,
+            };
+            let endpoint =
+                input::text_optional("Endpoint URL (optional, for SNS-compatible services)")?;
+            let mut cfg = serde_json::json!({
+                "topic_arn": topic_arn,
+                "region": region,
+                "access_key_id": access_key_id,
+                "secret_access_key": secret_access_key,
+            });
+            if let Some(e) = endpoint {
+                cfg["endpoint"] = serde_json::json!(e);
+            }
+            Ok(cfg)
+        }
         _ => {
             let url = input::text_required("Target URL")?;
             Ok(serde_json::json!({ "url": url }))
@@ -181,7 +332,7 @@ fn build_config_interactive(ntype: &str, secret: Option<&str>) -> Result<serde_j
     }
 }
 
-fn build_config(ntype: &str, target: &str, secret: Option<&str>) -> serde_json::Value {
+pub(super) fn build_config(ntype: &str, target: &str, secret: Option<&str>) -> serde_json::Value {
     match ntype {
         "webhook" => {
             let mut cfg = serde_json::json!({ "url": target });
@@ -190,13 +341,30 @@ fn build_config(ntype: &str, target: &str, secret: Option<&str>) -> serde_json::
             }
             cfg
         }
-        "slack" | "discord" | "teams" => serde_json::json!({ "webhook_url": target }),
+        "slack" | "discord" | "teams" | "mattermost" | "rocketchat" | "googlechat" => {
+            serde_json::json!({ "webhook_url": target })
+        }
         "smtp" => serde_json::json!({ "host": target }),
         "telegram" => serde_json::json!({ "bot_token": target }),
         "pagerduty" => serde_json::json!({ "routing_key": target }),
         "opsgenie" => serde_json::json!({ "api_key": target }),
         "gotify" => serde_json::json!({ "server_url": target }),
         "ntfy" => serde_json::json!({ "server_url": target, "topic": "sentinel" }),
+        "matrix" => serde_json::json!({ "homeserver_url": target, "access_token": secret }),
+        "pushover" => serde_json::json!({ "user_key": target, "token": secret }),
+        "victorops" => serde_json::json!({ "routing_key": target, "api_key": secret }),
+        "sns" => serde_json::json!({
+            "topic_arn": target,
+            "region": region_from_arn(target),
+            "secret_access_key": secret,
+        }),
         _ => serde_json::json!({ "url": target }),
     }
 }
+
+fn region_from_arn(arn: &str) -> Option<String> {
+    arn.split(':')
+        .nth(3)
+        .filter(|r| !r.is_empty())
+        .map(String::from)
+}
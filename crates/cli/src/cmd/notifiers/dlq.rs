@@ -0,0 +1,286 @@
+use anyhow::{bail, Result};
+use clap::{Args, Subcommand};
+
+use crate::client;
+use crate::cmd::metrics::history::parse_duration;
+use crate::output::{confirm, print_json, spinner, theme, OutputMode};
+
+#[derive(Args)]
+pub struct DlqArgs {
+    #[command(subcommand)]
+    pub action: DlqAction,
+}
+
+#[derive(Subcommand)]
+pub enum DlqAction {
+    #[command(about = "List dead-lettered notifications", visible_alias = "ls")]
+    List {
+        #[command(flatten)]
+        filter: DlqFilterArgs,
+
+        #[arg(long, default_value = "25", help = "Number of entries")]
+        limit: i64,
+    },
+    #[command(about = "Resend dead-lettered notifications through their notifier")]
+    Replay {
+        #[arg(help = "DLQ entry ID")]
+        id: Option<String>,
+
+        #[command(flatten)]
+        filter: DlqFilterArgs,
+
+        #[arg(long, help = "Replay every entry")]
+        all: bool,
+    },
+    #[command(about = "Delete dead-lettered notifications")]
+    Purge {
+        #[arg(help = "DLQ entry ID")]
+        id: Option<String>,
+
+        #[command(flatten)]
+        filter: DlqFilterArgs,
+
+        #[arg(long, help = "Purge every entry")]
+        all: bool,
+
+        #[arg(long, help = "Skip confirmation")]
+        yes: bool,
+    },
+}
+
+#[derive(Args)]
+pub struct DlqFilterArgs {
+    #[arg(long, help = "Filter by notifier ID")]
+    pub notifier_id: Option<String>,
+
+    #[arg(long, help = "Filter by alert ID")]
+    pub alert_id: Option<String>,
+
+    #[arg(long, help = "Only entries older than this (e.g. 30m, 1h, 1d)")]
+    pub older_than: Option<String>,
+}
+
+impl DlqFilterArgs {
+    fn is_empty(&self) -> bool {
+        self.notifier_id.is_none() && self.alert_id.is_none() && self.older_than.is_none()
+    }
+
+    fn older_than_secs(&self) -> Result<Option<i64>> {
+        let Some(ref raw) = self.older_than else {
+            return Ok(None);
+        };
+        let secs = parse_duration(raw)?.num_seconds();
+        if secs <= 0 {
+            bail!("--older-than must be positive");
+        }
+        Ok(Some(secs))
+    }
+
+    fn to_body(&self, all: bool) -> Result<serde_json::Value> {
+        let mut body = serde_json::json!({ "all": all });
+        if let Some(ref nid) = self.notifier_id {
+            body["notifier_id"] = nid.clone().into();
+        }
+        if let Some(ref aid) = self.alert_id {
+            body["alert_id"] = aid.clone().into();
+        }
+        if let Some(secs) = self.older_than_secs()? {
+            body["older_than_secs"] = secs.into();
+        }
+        Ok(body)
+    }
+}
+
+pub async fn run(args: DlqArgs, mode: OutputMode, server: Option<String>) -> Result<()> {
+    match args.action {
+        DlqAction::List { filter, limit } => run_list(&filter, limit, mode, server).await,
+        DlqAction::Replay { id, filter, all } => run_replay(id, &filter, all, mode, server).await,
+        DlqAction::Purge {
+            id,
+            filter,
+            all,
+            yes,
+        } => run_purge(id, &filter, all, yes, mode, server).await,
+    }
+}
+
+async fn run_list(
+    filter: &DlqFilterArgs,
+    limit: i64,
+    mode: OutputMode,
+    server: Option<String>,
+) -> Result<()> {
+    let api = client::build_client(server.as_deref())?;
+
+    let mut url = format!("/v1/notifications/dlq?limit={limit}");
+    if let Some(ref nid) = filter.notifier_id {
+        url.push_str(&format!("&notifier_id={nid}"));
+    }
+    if let Some(ref aid) = filter.alert_id {
+        url.push_str(&format!("&alert_id={aid}"));
+    }
+    if let Some(secs) = filter.older_than_secs()? {
+        url.push_str(&format!("&older_than_secs={secs}"));
+    }
+
+    let sp = match mode {
+        OutputMode::Human => Some(spinner::create("Fetching dead-letter queue...")),
+        OutputMode::Json => None,
+    };
+
+    let entries: serde_json::Value = api.get_json(&url).await?;
+
+    if let Some(sp) = sp {
+        spinner::finish_ok(&sp, "DLQ loaded");
+    }
+
+    match mode {
+        OutputMode::Json => print_json(&entries)?,
+        OutputMode::Human => {
+            let arr = entries.as_array().map(|a| a.as_slice()).unwrap_or(&[]);
+            if arr.is_empty() {
+                theme::print_dim("Dead-letter queue is empty");
+                return Ok(());
+            }
+
+            theme::print_table_header(&[
+                "ID", "Alert", "Notifier", "Attempts", "Retries", "Age", "Error",
+            ]);
+            let now_ms = chrono::Utc::now().timestamp_millis();
+            for e in arr {
+                let mut retries = e["retry_count"].as_i64().unwrap_or(0).to_string();
+                if e["replay_pending"].as_bool().unwrap_or(false) {
+                    retries.push_str(" (queued)");
+                }
+                let age_ms = now_ms - e["created_at_ms"].as_i64().unwrap_or(now_ms);
+                theme::print_table_row(&[
+                    &truncate(e["id"].as_str().unwrap_or("-"), 8),
+                    &truncate(e["alert_id"].as_str().unwrap_or("-"), 8),
+                    &truncate(
+                        e["notifier_id"]
+                            .as_str()
+                            .or(e["notifier"].as_str())
+                            .unwrap_or("-"),
+                        8,
+                    ),
+                    &e["attempts"].as_i64().unwrap_or(0).to_string(),
+                    &retries,
+                    &format_age(age_ms),
+                    &truncate(e["error"].as_str().unwrap_or("-"), 40),
+                ]);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn run_replay(
+    id: Option<String>,
+    filter: &DlqFilterArgs,
+    all: bool,
+    mode: OutputMode,
+    server: Option<String>,
+) -> Result<()> {
+    let api = client::build_client(server.as_deref())?;
+
+    if id.is_none() && filter.is_empty() && !all {
+        bail!("Pass an entry ID, a filter (--notifier-id, --alert-id, --older-than) or --all");
+    }
+
+    let sp = match mode {
+        OutputMode::Human => Some(spinner::create("Queueing replay...")),
+        OutputMode::Json => None,
+    };
+
+    let resp = match id {
+        Some(ref id) => {
+            api.post_empty(&format!("/v1/notifications/dlq/{id}/replay"))
+                .await?
+        }
+        None => {
+            api.post_json("/v1/notifications/dlq/replay", &filter.to_body(all)?)
+                .await?
+        }
+    };
+
+    if let Some(sp) = sp {
+        let queued = resp["queued"].as_u64().unwrap_or(0);
+        spinner::finish_ok(&sp, &format!("{queued} DLQ entries queued for replay"));
+        theme::print_dim("  Workers resend queued entries on their next DLQ pass.");
+    } else {
+        print_json(&resp)?;
+    }
+
+    Ok(())
+}
+
+async fn run_purge(
+    id: Option<String>,
+    filter: &DlqFilterArgs,
+    all: bool,
+    yes: bool,
+    mode: OutputMode,
+    server: Option<String>,
+) -> Result<()> {
+    let api = client::build_client(server.as_deref())?;
+
+    if id.is_none() && filter.is_empty() && !all {
+        bail!("Pass an entry ID, a filter (--notifier-id, --alert-id, --older-than) or --all");
+    }
+
+    if mode == OutputMode::Human && !yes && !confirm::confirm_action("Purge matching DLQ entries?")
+    {
+        theme::print_dim("  Cancelled.");
+        return Ok(());
+    }
+
+    let sp = match mode {
+        OutputMode::Human => Some(spinner::create("Purging DLQ entries...")),
+        OutputMode::Json => None,
+    };
+
+    let purged = match id {
+        Some(ref id) => {
+            let status = api
+                .delete_path(&format!("/v1/notifications/dlq/{id}"))
+                .await?;
+            if status.as_u16() == 404 {
+                bail!("DLQ entry '{id}' not found");
+            }
+            u64::from(status.is_success())
+        }
+        None => {
+            let resp = api
+                .post_json("/v1/notifications/dlq/purge", &filter.to_body(all)?)
+                .await?;
+            resp["purged"].as_u64().unwrap_or(0)
+        }
+    };
+
+    if let Some(sp) = sp {
+        spinner::finish_ok(&sp, &format!("{purged} DLQ entries purged"));
+    } else {
+        print_json(&serde_json::json!({ "purged": purged }))?;
+    }
+
+    Ok(())
+}
+
+fn format_age(ms: i64) -> String {
+    let secs = ms.max(0) / 1000;
+    match secs {
+        s if s < 60 => format!("{s}s"),
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86400 => format!("{}h", s / 3600),
+        s => format!("{}d", s / 86400),
+    }
+}
+
+fn truncate(s: &str, len: usize) -> String {
+    if s.len() <= len {
+        s.to_string()
+    } else {
+        format!("{}…", &s[..len])
+    }
+}
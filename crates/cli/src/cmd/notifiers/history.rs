@@ -83,6 +83,10 @@ async fn run_stats(api: &crate::client::ApiClient, mode: OutputMode) -> Result<(
             theme::print_kv("Total", &stats["total"].as_i64().unwrap_or(0).to_string());
             theme::print_kv("Sent", &stats["sent"].as_i64().unwrap_or(0).to_string());
             theme::print_kv("Failed", &stats["failed"].as_i64().unwrap_or(0).to_string());
+            theme::print_kv(
+                "Dead-lettered",
+                &stats["dead_lettered"].as_i64().unwrap_or(0).to_string(),
+            );
             theme::print_kv(
                 "Avg duration",
                 &format!("{}ms", stats["avg_duration_ms"].as_i64().unwrap_or(0)),
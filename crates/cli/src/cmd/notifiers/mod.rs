@@ -1,11 +1,13 @@
 mod create;
 mod delete;
+mod dlq;
 mod enable;
 mod history;
 mod link;
 mod list;
 mod test;
 mod update;
+mod verify_webhook;
 
 use anyhow::Result;
 use clap::Subcommand;
@@ -37,6 +39,12 @@ pub enum NotifiersCmd {
 
     #[command(about = "Notification delivery history", visible_alias = "log")]
     History(history::HistoryArgs),
+
+    #[command(about = "Inspect, replay or purge the dead-letter queue")]
+    Dlq(dlq::DlqArgs),
+
+    #[command(about = "Verify the signature of a captured webhook payload")]
+    VerifyWebhook(verify_webhook::VerifyWebhookArgs),
 }
 
 pub async fn execute(cmd: NotifiersCmd, mode: OutputMode, server: Option<String>) -> Result<()> {
@@ -49,5 +57,7 @@ pub async fn execute(cmd: NotifiersCmd, mode: OutputMode, server: Option<String>
         NotifiersCmd::Enable(args) => enable::run(args, mode, server).await,
         NotifiersCmd::Update(args) => update::run(args, mode, server).await,
         NotifiersCmd::History(args) => history::run(args, mode, server).await,
+        NotifiersCmd::Dlq(args) => dlq::run(args, mode, server).await,
+        NotifiersCmd::VerifyWebhook(args) => verify_webhook::run(args, mode),
     }
 }
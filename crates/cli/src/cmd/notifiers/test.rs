@@ -1,22 +1,10 @@
 use anyhow::Result;
 use clap::Args;
 
+use super::create::NOTIFIER_TYPES;
 use crate::client;
 use crate::output::{input, print_json, select, spinner, theme, OutputMode};
 
-const NOTIFIER_TYPES: &[&str] = &[
-    "webhook",
-    "slack",
-    "discord",
-    "smtp",
-    "telegram",
-    "pagerduty",
-    "teams",
-    "opsgenie",
-    "gotify",
-    "ntfy",
-];
-
 #[derive(Args)]
 pub struct TestArgs {
     #[arg(long, help = "Notifier type")]
@@ -27,35 +15,56 @@ pub struct TestArgs {
 
     #[arg(long, help = "Optional secret/token")]
     pub secret: Option<String>,
+
+    #[arg(long, conflicts_with_all = ["type", "target"], help = "Test a saved notifier by ID")]
+    pub id: Option<String>,
+
+    #[arg(
+        long,
+        help = "Preview the rendered message for a sample alert without sending"
+    )]
+    pub render: bool,
+
+    #[command(flatten)]
+    pub template: super::create::TemplateArgs,
 }
 
 pub async fn run(args: TestArgs, mode: OutputMode, server: Option<String>) -> Result<()> {
     let api = client::build_client(server.as_deref())?;
 
-    let notifier_type = match args.r#type {
-        Some(t) => t,
-        None if mode == OutputMode::Human => {
-            let idx = select::select_option("Select notifier type", NOTIFIER_TYPES)
-                .ok_or_else(|| anyhow::anyhow!("cancelled"))?;
-            NOTIFIER_TYPES[idx].to_string()
+    let (notifier_type, mut config) = match args.id {
+        Some(ref id) => {
+            let saved = api.get_json(&format!("/v1/notifiers/{id}")).await?;
+            let ntype = saved["ntype"].as_str().unwrap_or_default().to_string();
+            (ntype, saved["config"].clone())
+        }
+        None => {
+            let notifier_type = match args.r#type {
+                Some(t) => t,
+                None if mode == OutputMode::Human => {
+                    let idx = select::select_option("Select notifier type", NOTIFIER_TYPES)
+                        .ok_or_else(|| anyhow::anyhow!("cancelled"))?;
+                    NOTIFIER_TYPES[idx].to_string()
+                }
+                None => anyhow::bail!("--type or --id is required in JSON mode"),
+            };
+            let target = match args.target {
+                Some(t) => t,
+                None => input::text_required("Target URL or address")?,
+            };
+            let config =
+                super::create::build_config(&notifier_type, &target, args.secret.as_deref());
+            (notifier_type, config)
         }
-        None => anyhow::bail!("--type is required in JSON mode"),
-    };
-
-    let target = match args.target {
-        Some(t) => t,
-        None => input::text_required("Target URL or address")?,
     };
+    args.template.apply(&mut config)?;
 
-    let mut body = serde_json::json!({
-        "type": notifier_type,
-        "target": target,
+    let body = serde_json::json!({
+        "notifier_type": notifier_type,
+        "config": config,
+        "render": args.render,
     });
 
-    if let Some(s) = &args.secret {
-        body["secret"] = serde_json::json!(s);
-    }
-
     let sp = match mode {
         OutputMode::Human => Some(spinner::create(&format!(
             "Testing {notifier_type} notifier..."
@@ -81,6 +90,28 @@ pub async fn run(args: TestArgs, mode: OutputMode, server: Option<String>) -> Re
             if let Some(msg) = result["message"].as_str() {
                 theme::print_kv("Detail", msg);
             }
+            for (key, header) in [
+                ("rendered", "Rendered preview (sample alert)"),
+                ("rendered_group", "Rendered preview (sample group)"),
+            ] {
+                let rendered = &result[key];
+                if !rendered.is_object() {
+                    continue;
+                }
+                theme::print_header(header);
+                for (label, key) in [("Title", "title"), ("Body", "body")] {
+                    match rendered[key].as_str() {
+                        Some(text) => {
+                            theme::print_kv(label, "");
+                            for line in text.lines() {
+                                println!("      {line}");
+                            }
+                        }
+                        None => theme::print_kv(label, "(built-in layout)"),
+                    }
+                }
+                println!();
+            }
         }
     }
 
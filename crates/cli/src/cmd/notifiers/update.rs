@@ -2,6 +2,7 @@ use anyhow::Result;
 use clap::Args;
 
 use crate::client;
+use crate::cmd::rules::create::GroupingArgs;
 use crate::output::{input, print_json, spinner, theme, OutputMode};
 
 #[derive(Args)]
@@ -17,6 +18,12 @@ pub struct UpdateArgs {
 
     #[arg(long, help = "Disable notifier")]
     pub disable: bool,
+
+    #[command(flatten)]
+    pub template: super::create::TemplateArgs,
+
+    #[command(flatten)]
+    pub grouping: GroupingArgs,
 }
 
 pub async fn run(args: UpdateArgs, mode: OutputMode, server: Option<String>) -> Result<()> {
@@ -24,7 +31,10 @@ pub async fn run(args: UpdateArgs, mode: OutputMode, server: Option<String>) ->
 
     let name = match args.name {
         Some(n) => Some(n),
-        None if mode == OutputMode::Human => {
+        None if mode == OutputMode::Human
+            && args.template.is_empty()
+            && args.grouping.is_empty() =>
+        {
             input::text_optional("New name (press Enter to keep)")?
         }
         None => None,
@@ -46,6 +56,15 @@ pub async fn run(args: UpdateArgs, mode: OutputMode, server: Option<String>) ->
         body.insert("enabled".into(), serde_json::json!(e));
     }
 
+    let url = format!("/v1/notifiers/{}", args.id);
+    if !args.template.is_empty() || !args.grouping.is_empty() {
+        let existing = api.get_json(&url).await?;
+        let mut config = existing["config"].clone();
+        args.template.apply(&mut config)?;
+        args.grouping.apply(&mut config)?;
+        body.insert("config".into(), config);
+    }
+
     if body.is_empty() {
         anyhow::bail!("nothing to update");
     }
@@ -55,7 +74,6 @@ pub async fn run(args: UpdateArgs, mode: OutputMode, server: Option<String>) ->
         OutputMode::Json => None,
     };
 
-    let url = format!("/v1/notifiers/{}", args.id);
     let result = api.put_json(&url, &serde_json::Value::Object(body)).await?;
 
     if let Some(sp) = sp {
@@ -0,0 +1,51 @@
+use std::io::Read;
+
+use anyhow::{bail, Context, Result};
+use clap::Args;
+use sentinel_common::webhook;
+
+use crate::output::{input, print_json, print_success, OutputMode};
+
+#[derive(Args)]
+pub struct VerifyWebhookArgs {
+    #[arg(long, help = "Captured request body file, or '-' for stdin")]
+    pub body: String,
+
+    #[arg(long, help = "Value of the X-Sentinel-Signature header")]
+    pub signature: String,
+
+    #[arg(long, help = "Webhook secret (prompted when omitted)")]
+    pub secret: Option<String>,
+}
+
+pub fn run(args: VerifyWebhookArgs, mode: OutputMode) -> Result<()> {
+    let body = read_body(&args.body)?;
+    let secret = match args.secret {
+        Some(s) => s,
+        None => input::password("Webhook secret")?,
+    };
+
+    if let Err(e) = webhook::verify(secret.as_bytes(), &body, &args.signature) {
+        bail!("signature verification failed: {e}");
+    }
+
+    match mode {
+        OutputMode::Json => print_json(&serde_json::json!({
+            "valid": true,
+            "bytes": body.len(),
+        }))?,
+        OutputMode::Human => print_success(&format!("Signature valid ({} bytes)", body.len())),
+    }
+    Ok(())
+}
+
+fn read_body(path: &str) -> Result<Vec<u8>> {
+    if path == "-" {
+        let mut buf = Vec::new();
+        std::io::stdin()
+            .read_to_end(&mut buf)
+            .context("failed to read body from stdin")?;
+        return Ok(buf);
+    }
+    std::fs::read(path).with_context(|| format!("failed to read {path}"))
+}
@@ -1,7 +1,11 @@
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use clap::Args;
+use sentinel_common::aggregation::Aggregation;
+use sentinel_common::expr;
+use sentinel_common::labels::LabelMatcher;
 
 use crate::client;
+use crate::cmd::metrics::history::parse_duration;
 use crate::output::{input, print_json, select, spinner, theme, OutputMode};
 
 const CONDITIONS: &[&str] = &[
@@ -12,21 +16,188 @@ const CONDITIONS: &[&str] = &[
     "Equal",
 ];
 
+const RULE_KINDS: &[&str] = &["Metric threshold", "Expression", "Absent metric (no data)"];
+
 const SEVERITIES: &[&str] = &["info", "warning", "critical"];
 
+const AGGREGATIONS: &[&str] = &[
+    "avg", "min", "max", "last", "sum", "count", "p95", "p99", "rate", "increase", "stddev",
+];
+
 #[derive(Args)]
 pub struct CreateArgs {
     #[arg(long, help = "JSON file path or inline JSON (skip for interactive)")]
     pub data: Option<String>,
+
+    #[arg(
+        long = "label",
+        help = "Label matcher, e.g. 'mount!=/boot' or 'device=~sd.*' (repeatable)"
+    )]
+    pub labels: Vec<String>,
+
+    #[arg(
+        long = "expr",
+        help = "Expression rule, e.g. 'mem.used_bytes / mem.total_bytes > 0.9'"
+    )]
+    pub expression: Option<String>,
+
+    #[arg(
+        long = "absent-for",
+        help = "Fire when the metric reports no samples for this long, e.g. 5m"
+    )]
+    pub absent_for: Option<String>,
+
+    #[command(flatten)]
+    pub reducer: ReducerArgs,
+
+    #[command(flatten)]
+    pub grouping: GroupingArgs,
+
+    #[arg(
+        long,
+        help = "Escalation policy ID (replaces notifier_ids for this rule)"
+    )]
+    pub escalation: Option<String>,
+}
+
+#[derive(Args)]
+pub struct ReducerArgs {
+    #[arg(
+        long,
+        help = "Reducer: avg, min, max, last, sum, count, pNN, rate, increase, stddev"
+    )]
+    pub aggregation: Option<String>,
+
+    #[arg(long, help = "Lookback window, e.g. 30s, 5m, 1h")]
+    pub window: Option<String>,
+
+    #[arg(long, help = "Minimum samples in the window before evaluating")]
+    pub min_samples: Option<u32>,
+}
+
+impl ReducerArgs {
+    pub(super) fn apply(&self, body: &mut serde_json::Value) -> Result<()> {
+        if let Some(ref a) = self.aggregation {
+            a.parse::<Aggregation>()?;
+            body["aggregation"] = a.as_str().into();
+        }
+        if let Some(ref w) = self.window {
+            let ms = parse_duration(w)?.num_milliseconds();
+            if ms <= 0 {
+                bail!("--window must be positive");
+            }
+            body["window_ms"] = ms.into();
+        }
+        if let Some(n) = self.min_samples {
+            body["min_samples"] = n.into();
+        }
+        Ok(())
+    }
+}
+
+#[derive(Args)]
+pub struct GroupingArgs {
+    #[arg(
+        long = "group-by",
+        value_delimiter = ',',
+        help = "Group notifications by keys: rule_id, agent_id, severity, metric_name or a label name"
+    )]
+    pub group_by: Vec<String>,
+
+    #[arg(
+        long,
+        help = "Wait before the first notification of a new group, e.g. 30s"
+    )]
+    pub group_wait: Option<String>,
+
+    #[arg(long, help = "Minimum time between updates for a group, e.g. 5m")]
+    pub group_interval: Option<String>,
+
+    #[arg(
+        long,
+        help = "Re-notify still-firing groups after this long, e.g. 4h ('0' disables)"
+    )]
+    pub repeat_interval: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["group_by", "group_wait", "group_interval", "repeat_interval"],
+        help = "Remove grouping and notify on every transition"
+    )]
+    pub no_grouping: bool,
+}
+
+impl GroupingArgs {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.group_by.is_empty()
+            && self.group_wait.is_none()
+            && self.group_interval.is_none()
+            && self.repeat_interval.is_none()
+            && !self.no_grouping
+    }
+
+    pub(crate) fn apply(&self, target: &mut serde_json::Value) -> Result<()> {
+        if self.no_grouping {
+            target["grouping"] = serde_json::Value::Null;
+            return Ok(());
+        }
+        if self.is_empty() {
+            return Ok(());
+        }
+        if !target["grouping"].is_object() {
+            target["grouping"] = serde_json::json!({});
+        }
+        let grouping = &mut target["grouping"];
+        if !self.group_by.is_empty() {
+            grouping["group_by"] = serde_json::json!(self.group_by);
+        }
+        for (key, raw) in [
+            ("group_wait_secs", &self.group_wait),
+            ("group_interval_secs", &self.group_interval),
+            ("repeat_interval_secs", &self.repeat_interval),
+        ] {
+            let Some(raw) = raw else { continue };
+            grouping[key] = parse_secs(raw)?.into();
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn parse_secs(raw: &str) -> Result<i64> {
+    if raw.trim() == "0" {
+        return Ok(0);
+    }
+    let secs = parse_duration(raw)?.num_seconds();
+    if secs < 0 {
+        bail!("durations must not be negative");
+    }
+    Ok(secs)
 }
 
 pub async fn run(args: CreateArgs, mode: OutputMode, server: Option<String>) -> Result<()> {
     let api = client::build_client(server.as_deref())?;
 
-    let body = match args.data {
+    let mut body = match args.data {
         Some(ref raw) => parse_json_data(raw)?,
-        None => build_interactive()?,
+        None => build_interactive(args.expression.is_some())?,
     };
+    if !args.labels.is_empty() {
+        body["label_matchers"] = parse_matchers(&args.labels)?;
+    }
+    if let Some(ref e) = args.expression {
+        body["expression"] = e.as_str().into();
+    }
+    if let Some(e) = body["expression"].as_str() {
+        check_expression(e)?;
+    }
+    if let Some(ref a) = args.absent_for {
+        body["absent_for_ms"] = parse_absent_for(a)?.into();
+    }
+    args.reducer.apply(&mut body)?;
+    args.grouping.apply(&mut body)?;
+    if let Some(ref e) = args.escalation {
+        body["escalation_policy_id"] = e.as_str().into();
+    }
 
     let sp = match mode {
         OutputMode::Human => Some(spinner::create("Creating rule...")),
@@ -49,22 +220,99 @@ pub async fn run(args: CreateArgs, mode: OutputMode, server: Option<String>) ->
     Ok(())
 }
 
-fn build_interactive() -> Result<serde_json::Value> {
+fn build_interactive(has_expression: bool) -> Result<serde_json::Value> {
+    let kind = if has_expression {
+        1
+    } else {
+        select::select_option("Rule type", RULE_KINDS).unwrap_or(0)
+    };
+    if kind == 1 {
+        return build_interactive_expression(has_expression);
+    }
+    if kind == 2 {
+        return build_interactive_absent();
+    }
+
     let name = input::text_required("Rule name")?;
     let metric_name = input::text_required("Metric name")?;
+    let matchers = input::text_optional("Label matchers (comma separated, e.g. mount!=/boot)")?;
     let cond_idx = select::select_option("Condition", CONDITIONS).unwrap_or(0);
     let threshold = input::text("Threshold", "80.0")?;
+    let agg_idx = select::select_option("Aggregation", AGGREGATIONS).unwrap_or(0);
+    let window = input::text("Window", "2m")?;
     let sev_idx = select::select_option("Severity", SEVERITIES).unwrap_or(1);
 
+    let matchers: Vec<String> = matchers
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .map(String::from)
+        .collect();
+
     Ok(serde_json::json!({
         "name": name,
         "metric_name": metric_name,
+        "label_matchers": parse_matchers(&matchers)?,
         "condition": CONDITIONS[cond_idx],
         "threshold": threshold.parse::<f64>().unwrap_or(80.0),
+        "aggregation": AGGREGATIONS[agg_idx],
+        "window_ms": parse_duration(&window)?.num_milliseconds(),
         "severity": SEVERITIES[sev_idx],
     }))
 }
 
+fn build_interactive_expression(has_expression: bool) -> Result<serde_json::Value> {
+    let name = input::text_required("Rule name")?;
+    let mut body = serde_json::json!({ "name": name });
+    if !has_expression {
+        let expression = input::text_required("Expression")?;
+        check_expression(&expression)?;
+        body["expression"] = expression.into();
+    }
+    let window = input::text("Default window", "2m")?;
+    let sev_idx = select::select_option("Severity", SEVERITIES).unwrap_or(1);
+    body["window_ms"] = parse_duration(&window)?.num_milliseconds().into();
+    body["severity"] = SEVERITIES[sev_idx].into();
+    Ok(body)
+}
+
+fn build_interactive_absent() -> Result<serde_json::Value> {
+    let name = input::text_required("Rule name")?;
+    let metric_name = input::text_required("Metric name")?;
+    let matchers = input::text_optional("Label matchers (comma separated, e.g. mount!=/boot)")?;
+    let absent_for = input::text("Absent for", "5m")?;
+    let sev_idx = select::select_option("Severity", SEVERITIES).unwrap_or(2);
+
+    let matchers: Vec<String> = matchers
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .map(String::from)
+        .collect();
+
+    Ok(serde_json::json!({
+        "name": name,
+        "metric_name": metric_name,
+        "label_matchers": parse_matchers(&matchers)?,
+        "absent_for_ms": parse_absent_for(&absent_for)?,
+        "severity": SEVERITIES[sev_idx],
+    }))
+}
+
+pub(super) fn parse_absent_for(raw: &str) -> Result<i64> {
+    let ms = parse_duration(raw)?.num_milliseconds();
+    if ms <= 0 {
+        bail!("--absent-for must be positive");
+    }
+    Ok(ms)
+}
+
+pub(super) fn check_expression(source: &str) -> Result<()> {
+    expr::validate(source).with_context(|| format!("bad expression '{source}'"))
+}
+
 fn parse_json_data(raw: &str) -> Result<serde_json::Value> {
     if std::path::Path::new(raw).exists() {
         let content = std::fs::read_to_string(raw)?;
@@ -73,3 +321,11 @@ fn parse_json_data(raw: &str) -> Result<serde_json::Value> {
         Ok(serde_json::from_str(raw)?)
     }
 }
+
+pub(crate) fn parse_matchers(raw: &[String]) -> Result<serde_json::Value> {
+    let matchers = raw
+        .iter()
+        .map(|m| LabelMatcher::parse(m).with_context(|| format!("bad --label '{m}'")))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(serde_json::to_value(matchers)?)
+}
@@ -1,7 +1,7 @@
 use anyhow::Result;
 
 use crate::client;
-use crate::output::{build_table, print_json, spinner, theme, OutputMode};
+use crate::output::{build_table, print_json, rule_selector, spinner, theme, OutputMode};
 
 pub async fn run(mode: OutputMode, server: Option<String>) -> Result<()> {
     let api = client::build_client(server.as_deref())?;
@@ -27,14 +27,39 @@ pub async fn run(mode: OutputMode, server: Option<String>) -> Result<()> {
                 return Ok(());
             }
             theme::print_header("Alert Rules");
-            let mut table = build_table(&["ID", "Name", "Metric", "Condition", "Threshold"]);
+            let mut table =
+                build_table(&["ID", "Name", "Metric", "Reducer", "Condition", "Threshold"]);
             for r in arr {
+                let expression = r["expression"].as_str();
+                let absent_for = r["absent_for_ms"].as_i64();
+                let (condition, threshold) = match (expression, absent_for) {
+                    (Some(_), _) => ("-".to_string(), "-".to_string()),
+                    (None, Some(ms)) => ("absent".to_string(), format_duration_ms(ms)),
+                    (None, None) => (
+                        r["condition"].as_str().unwrap_or("-").to_string(),
+                        r["threshold"].to_string(),
+                    ),
+                };
                 table.add_row(vec![
                     r["id"].as_str().unwrap_or("-"),
                     r["name"].as_str().unwrap_or("-"),
-                    r["metric_name"].as_str().unwrap_or("-"),
-                    r["condition"].as_str().unwrap_or("-"),
-                    &r["threshold"].to_string(),
+                    &match expression {
+                        Some(e) => e.to_string(),
+                        None => rule_selector(
+                            r["metric_name"].as_str().unwrap_or("-"),
+                            &r["label_matchers"],
+                        ),
+                    },
+                    &match absent_for {
+                        Some(_) => "-".to_string(),
+                        None => format!(
+                            "{} over {}",
+                            r["aggregation"].as_str().unwrap_or("avg"),
+                            format_duration_ms(r["window_ms"].as_i64().unwrap_or(0))
+                        ),
+                    },
+                    &condition,
+                    &threshold,
                 ]);
             }
             println!("{table}");
@@ -43,3 +68,12 @@ pub async fn run(mode: OutputMode, server: Option<String>) -> Result<()> {
 
     Ok(())
 }
+
+fn format_duration_ms(ms: i64) -> String {
+    let secs = ms / 1000;
+    match secs {
+        s if s > 0 && s % 3600 == 0 => format!("{}h", s / 3600),
+        s if s > 0 && s % 60 == 0 => format!("{}m", s / 60),
+        s => format!("{s}s"),
+    }
+}
@@ -1,4 +1,4 @@
-mod create;
+pub(crate) mod create;
 mod delete;
 mod get;
 mod list;
@@ -10,18 +10,77 @@ pub struct UpdateArgs {
     pub id: String,
 
     #[arg(long, help = "JSON file path or inline JSON")]
-    pub data: String,
+    pub data: Option<String>,
+
+    #[arg(long = "label", help = "Replace label matchers (repeatable)")]
+    pub labels: Vec<String>,
+
+    #[arg(long = "expr", help = "Replace the rule expression ('' clears it)")]
+    pub expression: Option<String>,
+
+    #[arg(
+        long = "absent-for",
+        help = "Replace the no-data duration, e.g. 10m ('0' turns it back into a threshold rule)"
+    )]
+    pub absent_for: Option<String>,
+
+    #[command(flatten)]
+    pub reducer: super::create::ReducerArgs,
+
+    #[command(flatten)]
+    pub grouping: super::create::GroupingArgs,
+
+    #[arg(long, help = "Escalation policy ID")]
+    pub escalation: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with = "escalation",
+        help = "Detach the escalation policy and notify notifier_ids directly"
+    )]
+    pub no_escalation: bool,
 }
 
 pub async fn run(args: UpdateArgs, mode: OutputMode, server: Option<String>) -> Result<()> {
     let api = client::build_client(server.as_deref())?;
 
-    let body = if std::path::Path::new(&args.data).exists() {
-        let content = std::fs::read_to_string(&args.data)?;
-        serde_json::from_str(&content)?
-    } else {
-        serde_json::from_str(&args.data)?
+    let mut body: serde_json::Value = match args.data {
+        Some(ref data) if std::path::Path::new(data).exists() => {
+            let content = std::fs::read_to_string(data)?;
+            serde_json::from_str(&content)?
+        }
+        Some(ref data) => serde_json::from_str(data)?,
+        None => serde_json::json!({}),
     };
+    if !args.labels.is_empty() {
+        body["label_matchers"] = super::create::parse_matchers(&args.labels)?;
+    }
+    if let Some(ref e) = args.expression {
+        if !e.is_empty() {
+            super::create::check_expression(e)?;
+        }
+        body["expression"] = e.as_str().into();
+    }
+    if let Some(ref a) = args.absent_for {
+        body["absent_for_ms"] = match a.trim() {
+            "0" => 0.into(),
+            a => super::create::parse_absent_for(a)?.into(),
+        };
+    }
+    args.reducer.apply(&mut body)?;
+    if !args.grouping.is_empty() {
+        if !args.grouping.no_grouping && body.get("grouping").is_none() {
+            let existing = api.get_json(&format!("/v1/rules/{}", args.id)).await?;
+            body["grouping"] = existing["grouping"].clone();
+        }
+        args.grouping.apply(&mut body)?;
+    }
+    if let Some(ref e) = args.escalation {
+        body["escalation_policy_id"] = e.as_str().into();
+    }
+    if args.no_escalation {
+        body["escalation_policy_id"] = serde_json::Value::Null;
+    }
 
     let sp = match mode {
         OutputMode::Human => Some(spinner::create("Updating rule...")),
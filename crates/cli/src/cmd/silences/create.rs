@@ -0,0 +1,150 @@
+use anyhow::{bail, Context, Result};
+use clap::Args;
+
+use crate::client;
+use crate::cmd::metrics::history::parse_duration;
+use crate::cmd::rules::create::parse_matchers;
+use crate::output::{print_json, spinner, theme, OutputMode};
+
+#[derive(Args)]
+pub struct CreateArgs {
+    #[command(flatten)]
+    pub silence: SilenceArgs,
+}
+
+#[derive(Args)]
+pub struct SilenceArgs {
+    #[arg(long, help = "Agent ID or prefix pattern, e.g. 'web-*'")]
+    pub agent: Option<String>,
+
+    #[arg(long, help = "Rule ID")]
+    pub rule: Option<String>,
+
+    #[arg(long, help = "Severity (info / warning / critical)")]
+    pub severity: Option<String>,
+
+    #[arg(
+        long = "label",
+        help = "Label matcher, e.g. 'mount=/data' or 'device=~sd.*' (repeatable)"
+    )]
+    pub labels: Vec<String>,
+
+    #[arg(long, help = "Start time (RFC 3339), defaults to now")]
+    pub starts: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with = "ends",
+        help = "Length from the start, e.g. 2h, 1d"
+    )]
+    pub duration: Option<String>,
+
+    #[arg(long, help = "End time (RFC 3339)")]
+    pub ends: Option<String>,
+
+    #[arg(
+        long,
+        requires_all = ["at", "window"],
+        help = "Recurring weekly window on these days, e.g. sat,sun"
+    )]
+    pub weekly: Option<String>,
+
+    #[arg(long, requires = "weekly", help = "Window start time in UTC (HH:MM)")]
+    pub at: Option<String>,
+
+    #[arg(long, requires = "weekly", help = "Window length, e.g. 2h")]
+    pub window: Option<String>,
+
+    #[arg(long, short, help = "Reason for the silence")]
+    pub comment: Option<String>,
+}
+
+impl SilenceArgs {
+    pub(super) fn apply(&self, body: &mut serde_json::Value) -> Result<()> {
+        if let Some(ref a) = self.agent {
+            body["agent_pattern"] = a.as_str().into();
+        }
+        if let Some(ref r) = self.rule {
+            body["rule_id"] = r.as_str().into();
+        }
+        if let Some(ref s) = self.severity {
+            body["severity"] = s.to_lowercase().into();
+        }
+        if !self.labels.is_empty() {
+            body["label_matchers"] = parse_matchers(&self.labels)?;
+        }
+        if let Some(ref s) = self.starts {
+            body["starts_at_ms"] = parse_time(s, "--starts")?.into();
+        }
+        if let Some(ref d) = self.duration {
+            let secs = parse_duration(d)?.num_seconds();
+            if secs <= 0 {
+                bail!("--duration must be positive");
+            }
+            body["duration_secs"] = secs.into();
+            if let Some(obj) = body.as_object_mut() {
+                obj.remove("ends_at_ms");
+            }
+        }
+        if let Some(ref e) = self.ends {
+            body["ends_at_ms"] = parse_time(e, "--ends")?.into();
+        }
+        if let (Some(days), Some(at), Some(window)) = (&self.weekly, &self.at, &self.window) {
+            let mins = parse_duration(window)?.num_minutes();
+            if mins <= 0 {
+                bail!("--window must be at least one minute");
+            }
+            let days: Vec<String> = days
+                .split(',')
+                .map(|d| d.trim().to_lowercase())
+                .filter(|d| !d.is_empty())
+                .collect();
+            body["recurrence"] = serde_json::json!({
+                "days": days,
+                "start": at,
+                "duration_mins": mins,
+            });
+        }
+        if let Some(ref c) = self.comment {
+            body["comment"] = c.as_str().into();
+        }
+        Ok(())
+    }
+}
+
+fn parse_time(raw: &str, flag: &str) -> Result<i64> {
+    let t = chrono::DateTime::parse_from_rfc3339(raw)
+        .with_context(|| format!("bad {flag} '{raw}', expected RFC 3339"))?;
+    Ok(t.timestamp_millis())
+}
+
+pub async fn run(args: CreateArgs, mode: OutputMode, server: Option<String>) -> Result<()> {
+    let api = client::build_client(server.as_deref())?;
+
+    let mut body = serde_json::json!({});
+    args.silence.apply(&mut body)?;
+
+    let sp = match mode {
+        OutputMode::Human => Some(spinner::create("Creating silence...")),
+        OutputMode::Json => None,
+    };
+
+    let created = api.post_json("/v1/silences", &body).await?;
+
+    if let Some(sp) = sp {
+        spinner::finish_ok(
+            &sp,
+            &format!(
+                "Silence {} created ({})",
+                created["id"].as_str().unwrap_or("-"),
+                created["state"].as_str().unwrap_or("-")
+            ),
+        );
+        theme::print_kv("Matchers", &super::describe_matchers(&created));
+        theme::print_kv("Window", &super::describe_window(&created));
+    } else {
+        print_json(&created)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anyhow::Result;
+use clap::Args;
+
+use crate::client;
+use crate::output::{print_json, spinner, theme, OutputMode};
+
+#[derive(Args)]
+pub struct GetArgs {
+    #[arg(help = "Silence ID")]
+    pub id: String,
+}
+
+pub async fn run(args: GetArgs, mode: OutputMode, server: Option<String>) -> Result<()> {
+    let api = client::build_client(server.as_deref())?;
+
+    let sp = match mode {
+        OutputMode::Human => Some(spinner::create("Fetching silence...")),
+        OutputMode::Json => None,
+    };
+
+    let silence = api.get_json(&format!("/v1/silences/{}", args.id)).await?;
+
+    if let Some(sp) = sp {
+        spinner::finish_clear(&sp);
+    }
+
+    match mode {
+        OutputMode::Json => print_json(&silence)?,
+        OutputMode::Human => {
+            theme::print_header("Silence Details");
+            theme::print_kv("ID", silence["id"].as_str().unwrap_or("-"));
+            theme::print_kv("State", silence["state"].as_str().unwrap_or("-"));
+            theme::print_kv("Matchers", &super::describe_matchers(&silence));
+            theme::print_kv(
+                "Starts",
+                &super::format_ms(silence["starts_at_ms"].as_i64().unwrap_or(0)),
+            );
+            theme::print_kv("Window", &super::describe_window(&silence));
+            theme::print_kv("Created by", silence["created_by"].as_str().unwrap_or("-"));
+            theme::print_kv("Comment", silence["comment"].as_str().unwrap_or(""));
+            println!();
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anyhow::Result;
+use clap::Args;
+
+use crate::client;
+use crate::output::{build_table, print_json, spinner, theme, OutputMode};
+
+#[derive(Args)]
+pub struct ListArgs {
+    #[arg(
+        long,
+        default_value = "current",
+        help = "Filter by state (current / active / pending / expired / all)"
+    )]
+    pub state: String,
+}
+
+pub async fn run(args: ListArgs, mode: OutputMode, server: Option<String>) -> Result<()> {
+    let api = client::build_client(server.as_deref())?;
+
+    let sp = match mode {
+        OutputMode::Human => Some(spinner::create("Fetching silences...")),
+        OutputMode::Json => None,
+    };
+
+    let silences = api
+        .get_json(&format!("/v1/silences?state={}", args.state))
+        .await?;
+
+    if let Some(sp) = sp {
+        spinner::finish_clear(&sp);
+    }
+
+    match mode {
+        OutputMode::Json => print_json(&silences)?,
+        OutputMode::Human => {
+            let arr = silences.as_array().map(|a| a.as_slice()).unwrap_or(&[]);
+            if arr.is_empty() {
+                theme::print_dim("  No silences found.");
+                return Ok(());
+            }
+            theme::print_header("Silences");
+            let mut table = build_table(&["ID", "State", "Matchers", "Window", "Comment"]);
+            for s in arr {
+                table.add_row(vec![
+                    s["id"].as_str().unwrap_or("-"),
+                    s["state"].as_str().unwrap_or("-"),
+                    &super::describe_matchers(s),
+                    &super::describe_window(s),
+                    s["comment"].as_str().unwrap_or(""),
+                ]);
+            }
+            println!("{table}");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+mod create;
+mod delete;
+mod get;
+mod list;
+mod update;
+
+use anyhow::Result;
+use clap::Subcommand;
+
+use crate::output::OutputMode;
+
+#[derive(Subcommand)]
+pub enum SilencesCmd {
+    #[command(about = "List silences and maintenance windows", visible_alias = "ls")]
+    List(list::ListArgs),
+    #[command(about = "Get details for a specific silence", visible_alias = "show")]
+    Get(get::GetArgs),
+    #[command(
+        about = "Create a silence or recurring maintenance window",
+        visible_alias = "add"
+    )]
+    Create(create::CreateArgs),
+    #[command(about = "Update an existing silence")]
+    Update(update::UpdateArgs),
+    #[command(about = "Delete a silence", visible_alias = "rm")]
+    Delete(delete::DeleteArgs),
+}
+
+pub async fn execute(cmd: SilencesCmd, mode: OutputMode, server: Option<String>) -> Result<()> {
+    match cmd {
+        SilencesCmd::List(args) => list::run(args, mode, server).await,
+        SilencesCmd::Get(args) => get::run(args, mode, server).await,
+        SilencesCmd::Create(args) => create::run(args, mode, server).await,
+        SilencesCmd::Update(args) => update::run(args, mode, server).await,
+        SilencesCmd::Delete(args) => delete::run(args, mode, server).await,
+    }
+}
+
+pub(super) fn describe_matchers(s: &serde_json::Value) -> String {
+    let mut parts = Vec::new();
+    if let Some(a) = s["agent_pattern"].as_str() {
+        parts.push(format!("agent={a}"));
+    }
+    if let Some(r) = s["rule_id"].as_str() {
+        parts.push(format!("rule={r}"));
+    }
+    if let Some(sev) = s["severity"].as_str() {
+        parts.push(format!("severity={sev}"));
+    }
+    for m in s["label_matchers"].as_array().into_iter().flatten() {
+        parts.push(format!(
+            "{}{}{}",
+            m["label"].as_str().unwrap_or("?"),
+            m["op"].as_str().unwrap_or("="),
+            m["value"].as_str().unwrap_or("")
+        ));
+    }
+    if parts.is_empty() {
+        "-".into()
+    } else {
+        parts.join(", ")
+    }
+}
+
+pub(super) fn describe_window(s: &serde_json::Value) -> String {
+    let r = &s["recurrence"];
+    if r.is_object() {
+        let days: Vec<&str> = r["days"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|d| d.as_str())
+            .collect();
+        return format!(
+            "weekly {} {} UTC for {}m",
+            days.join(","),
+            r["start"].as_str().unwrap_or("-"),
+            r["duration_mins"].as_u64().unwrap_or(0)
+        );
+    }
+    match s["ends_at_ms"].as_i64() {
+        Some(ms) => format!("until {}", format_ms(ms)),
+        None => "-".into(),
+    }
+}
+
+pub(super) fn format_ms(ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(ms)
+        .map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_else(|| "-".into())
+}
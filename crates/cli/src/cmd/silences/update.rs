@@ -0,0 +1,55 @@
+use anyhow::Result;
+use clap::Args;
+
+use crate::client;
+use crate::output::{print_json, spinner, OutputMode};
+
+#[derive(Args)]
+pub struct UpdateArgs {
+    #[arg(help = "Silence ID")]
+    pub id: String,
+
+    #[command(flatten)]
+    pub silence: super::create::SilenceArgs,
+
+    #[arg(long, help = "Turn a maintenance window back into a one-off silence")]
+    pub no_recurrence: bool,
+}
+
+pub async fn run(args: UpdateArgs, mode: OutputMode, server: Option<String>) -> Result<()> {
+    let api = client::build_client(server.as_deref())?;
+
+    let sp = match mode {
+        OutputMode::Human => Some(spinner::create("Updating silence...")),
+        OutputMode::Json => None,
+    };
+
+    let path = format!("/v1/silences/{}", args.id);
+    let mut body = api.get_json(&path).await?;
+    if let Some(obj) = body.as_object_mut() {
+        for key in ["id", "state", "created_by"] {
+            obj.remove(key);
+        }
+        if args.no_recurrence {
+            obj.remove("recurrence");
+        }
+    }
+    args.silence.apply(&mut body)?;
+
+    let updated = api.put_json(&path, &body).await?;
+
+    if let Some(sp) = sp {
+        spinner::finish_ok(
+            &sp,
+            &format!(
+                "Silence {} updated ({})",
+                args.id,
+                updated["state"].as_str().unwrap_or("-")
+            ),
+        );
+    } else {
+        print_json(&updated)?;
+    }
+
+    Ok(())
+}
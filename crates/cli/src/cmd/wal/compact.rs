@@ -1,10 +1,12 @@
 use anyhow::Result;
 use clap::Args;
+use std::path::Path;
+use std::time::SystemTime;
 
 use crate::output::{print_error, print_success, theme, OutputMode};
-use sentinel_agent::buffer::{compact, needs_compaction, WalMeta};
+use sentinel_agent::buffer::Wal;
 
-use super::helpers::wal_dir;
+use super::helpers::{format_bytes, load_agent_config};
 
 #[derive(Args)]
 pub struct CompactArgs {
@@ -16,14 +18,13 @@ pub struct CompactArgs {
 }
 
 pub fn run(args: CompactArgs, _mode: OutputMode, config_path: Option<String>) -> Result<()> {
-    let dir = wal_dir(config_path.as_deref())?;
+    let cfg = load_agent_config(config_path.as_deref())?;
+    let segment_bytes = cfg.buffer.segment_size_mb * 1024 * 1024;
+    let mut wal = Wal::open(Path::new(&cfg.buffer.wal_dir), true, segment_bytes)?;
 
-    if !args.force {
-        let should = needs_compaction(&dir, 64 * 1024 * 1024)?;
-        if !should {
-            print_success("WAL does not need compaction");
-            return Ok(());
-        }
+    if !args.force && wal.size_bytes() < 64 * 1024 * 1024 {
+        print_success("WAL does not need compaction");
+        return Ok(());
     }
 
     if !args.yes {
@@ -34,13 +35,17 @@ pub fn run(args: CompactArgs, _mode: OutputMode, config_path: Option<String>) ->
     }
 
     theme::print_header("WAL Compaction");
-    let meta = WalMeta::load(&dir)?;
-    match compact(&dir, &meta) {
-        Ok(new_meta) => {
+    let before = wal.size_bytes();
+    let compacted = wal.enforce_retention(SystemTime::now()).and_then(|_| {
+        while wal.compact_step()?.is_some() {}
+        wal.save_meta()
+    });
+    match compacted {
+        Ok(meta) => {
             print_success(&format!(
-                "Compacted WAL — new head_seq={}, unacked={}",
-                new_meta.head_seq,
-                new_meta.unacked_count()
+                "Compacted WAL — reclaimed {}, unacked={}",
+                format_bytes(before.saturating_sub(wal.size_bytes())),
+                meta.unacked_count()
             ));
         }
         Err(e) => print_error(&format!("Compaction failed: {e}")),
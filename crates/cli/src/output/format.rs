@@ -23,3 +23,38 @@ pub fn print_error(msg: &str) {
 pub fn print_info(label: &str, value: &str) {
     println!("  {}: {}", label.bold(), value);
 }
+
+pub fn series_name(metric: &str, labels: &serde_json::Value) -> String {
+    let pairs: Vec<String> = labels
+        .as_object()
+        .into_iter()
+        .flatten()
+        .map(|(k, v)| format!("{k}=\"{}\"", v.as_str().unwrap_or_default()))
+        .collect();
+    if pairs.is_empty() {
+        metric.to_string()
+    } else {
+        format!("{metric}{{{}}}", pairs.join(","))
+    }
+}
+
+pub fn rule_selector(metric: &str, matchers: &serde_json::Value) -> String {
+    let parts: Vec<String> = matchers
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|m| {
+            format!(
+                "{}{}\"{}\"",
+                m["label"].as_str().unwrap_or_default(),
+                m["op"].as_str().unwrap_or("="),
+                m["value"].as_str().unwrap_or_default()
+            )
+        })
+        .collect();
+    if parts.is_empty() {
+        metric.to_string()
+    } else {
+        format!("{metric}{{{}}}", parts.join(","))
+    }
+}
@@ -10,5 +10,7 @@ pub mod table;
 pub mod theme;
 pub mod time_ago;
 
-pub use format::{print_error, print_info, print_json, print_success, OutputMode};
+pub use format::{
+    print_error, print_info, print_json, print_success, rule_selector, series_name, OutputMode,
+};
 pub use table::build_table;
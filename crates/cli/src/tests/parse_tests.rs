@@ -124,6 +124,45 @@ mod tests {
         assert!(matches!(opts.cmd, crate::cmd::Commands::Agents(_)));
     }
 
+    #[test]
+    fn parse_agents_config_push() {
+        let opts = parse(&["agents", "config", "push", "agent-1", "--file", "agent.yml"]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Agents(_)));
+    }
+
+    #[test]
+    fn parse_agents_config_push_pattern() {
+        let opts = parse(&[
+            "agents",
+            "config",
+            "push",
+            "--file",
+            "agent.yml",
+            "--pattern",
+            "web-*",
+        ]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Agents(_)));
+    }
+
+    #[test]
+    fn parse_agents_exec_update_interval() {
+        let opts = parse(&["agents", "exec", "agent-1", "update-interval", "30"]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Agents(_)));
+    }
+
+    #[test]
+    fn parse_agents_exec_reload_with_timeout() {
+        let opts = parse(&[
+            "agents",
+            "exec",
+            "agent-1",
+            "reload-config",
+            "--timeout",
+            "5",
+        ]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Agents(_)));
+    }
+
     #[test]
     fn parse_cluster_status() {
         let opts = parse(&["cluster", "status"]);
@@ -154,6 +193,76 @@ mod tests {
         assert!(matches!(opts.cmd, crate::cmd::Commands::Rules(_)));
     }
 
+    #[test]
+    fn parse_rules_create_with_labels() {
+        let opts = parse(&[
+            "rules",
+            "create",
+            "--data",
+            "{}",
+            "--label",
+            "mount!=/boot",
+            "--label",
+            "device=~sd.*",
+        ]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Rules(_)));
+    }
+
+    #[test]
+    fn parse_rules_create_with_reducer() {
+        let opts = parse(&[
+            "rules",
+            "create",
+            "--data",
+            "{}",
+            "--aggregation",
+            "p95",
+            "--window",
+            "5m",
+            "--min-samples",
+            "10",
+        ]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Rules(_)));
+    }
+
+    #[test]
+    fn parse_rules_create_and_update_with_expression() {
+        let opts = parse(&[
+            "rules",
+            "create",
+            "--data",
+            r#"{"name":"mem"}"#,
+            "--expr",
+            "mem.used_bytes / mem.total_bytes > 0.9",
+        ]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Rules(_)));
+
+        let opts = parse(&[
+            "rules",
+            "update",
+            "r-1",
+            "--expr",
+            "rate(net.rx_bytes, 5m) > 1e6",
+        ]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Rules(_)));
+    }
+
+    #[test]
+    fn parse_rules_absent_for() {
+        let opts = parse(&[
+            "rules",
+            "create",
+            "--data",
+            r#"{"name":"cpu-missing","metric_name":"cpu"}"#,
+            "--absent-for",
+            "5m",
+        ]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Rules(_)));
+
+        let opts = parse(&["rules", "update", "r-1", "--absent-for", "0"]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Rules(_)));
+    }
+
     #[test]
     fn parse_rules_delete() {
         let opts = parse(&["rules", "delete", "rule-1"]);
@@ -173,6 +282,194 @@ mod tests {
         assert!(matches!(opts.cmd, crate::cmd::Commands::Notifiers(_)));
     }
 
+    #[test]
+    fn parse_notifiers_create_backends() {
+        for ntype in [
+            "mattermost",
+            "matrix",
+            "googlechat",
+            "pushover",
+            "victorops",
+            "sns",
+        ] {
+            let opts = parse(&[
+                "notifiers",
+                "create",
+                "--name",
+                "n",
+                "--type",
+                ntype,
+                "--target",
+                "https://example.com",
+                "--secret",
+                "s",
+            ]);
+            assert!(matches!(opts.cmd, crate::cmd::Commands::Notifiers(_)));
+        }
+    }
+
+    #[test]
+    fn parse_notifiers_verify_webhook() {
+        let opts = parse(&[
+            "notifiers",
+            "verify-webhook",
+            "--body",
+            "payload.json",
+            "--signature",
+            "c2ln",
+            "--secret",
+            "s",
+        ]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Notifiers(_)));
+    }
+
+    #[test]
+    fn parse_notifiers_dlq() {
+        let opts = parse(&["notifiers", "dlq", "list", "--older-than", "1h"]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Notifiers(_)));
+
+        let opts = parse(&["notifiers", "dlq", "replay", "--notifier-id", "n1"]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Notifiers(_)));
+
+        let opts = parse(&["notifiers", "dlq", "purge", "--all", "--yes"]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Notifiers(_)));
+    }
+
+    #[test]
+    fn parse_alerts_ack() {
+        let opts = parse(&["alerts", "ack", "a1", "-c", "looking into it"]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Alerts(_)));
+
+        let opts = parse(&["alerts", "unack", "a1"]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Alerts(_)));
+    }
+
+    #[test]
+    fn parse_alerts_stats_and_timeline() {
+        let opts = parse(&[
+            "alerts", "stats", "--by", "agent", "--since", "7d", "--top", "5",
+        ]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Alerts(_)));
+
+        let opts = parse(&["alerts", "timeline", "--rule", "r1", "--since", "6h"]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Alerts(_)));
+    }
+
+    #[test]
+    fn parse_silences_create() {
+        let opts = parse(&[
+            "silences",
+            "create",
+            "--agent",
+            "web-*",
+            "--label",
+            "mount=/data",
+            "--duration",
+            "2h",
+        ]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Silences(_)));
+
+        let opts = parse(&[
+            "silences", "create", "--rule", "r1", "--weekly", "sat,sun", "--at", "02:00",
+            "--window", "3h",
+        ]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Silences(_)));
+
+        let opts = parse(&["silences", "list", "--state", "all"]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Silences(_)));
+    }
+
+    #[test]
+    fn parse_notifiers_test_render() {
+        let opts = parse(&[
+            "notifiers",
+            "test",
+            "--id",
+            "n1",
+            "--render",
+            "--title-template",
+            "{{rule_name}} on {{agent_id}}",
+        ]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Notifiers(_)));
+    }
+
+    #[test]
+    fn parse_grouping_flags() {
+        let opts = parse(&[
+            "rules",
+            "update",
+            "r1",
+            "--group-by",
+            "rule_id,mount",
+            "--group-wait",
+            "30s",
+            "--repeat-interval",
+            "0",
+        ]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Rules(_)));
+
+        let opts = parse(&["notifiers", "update", "n1", "--no-grouping"]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Notifiers(_)));
+
+        assert!(Opts::try_parse_from([
+            "sentinel",
+            "rules",
+            "update",
+            "r1",
+            "--no-grouping",
+            "--group-wait",
+            "1m",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn parse_escalations() {
+        let opts = parse(&[
+            "escalations",
+            "create",
+            "--name",
+            "db-oncall",
+            "--step",
+            "0=slack",
+            "--step",
+            "10m=pagerduty,smtp",
+        ]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Escalations(_)));
+
+        let opts = parse(&["escalation", "routes", "--set", "routes.json"]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Escalations(_)));
+
+        let opts = parse(&["rules", "update", "r1", "--escalation", "p1"]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Rules(_)));
+
+        assert!(
+            Opts::try_parse_from(["sentinel", "escalations", "create", "--name", "x"]).is_err()
+        );
+        assert!(Opts::try_parse_from([
+            "sentinel",
+            "rules",
+            "update",
+            "r1",
+            "--escalation",
+            "p1",
+            "--no-escalation",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn parse_encryption() {
+        let opts = parse(&["encryption", "status"]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Encryption(_)));
+
+        let opts = parse(&["encryption", "rotate", "--yes"]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Encryption(_)));
+
+        let opts = parse(&["encryption", "generate-key"]);
+        assert!(matches!(opts.cmd, crate::cmd::Commands::Encryption(_)));
+    }
+
     #[test]
     fn parse_key_rotate() {
         let opts = parse(&["key", "rotate", "--key-id", "k1", "--secret", "c2VjcmV0"]);
@@ -0,0 +1,123 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Aggregation {
+    #[default]
+    Avg,
+    Min,
+    Max,
+    Last,
+    Sum,
+    Count,
+    Percentile(f64),
+    Rate,
+    Increase,
+    StdDev,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParseAggregationError(pub String);
+
+impl std::fmt::Display for ParseAggregationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown aggregation '{}': expected avg, min, max, last, sum, count, pNN, rate, increase or stddev",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseAggregationError {}
+
+impl std::str::FromStr for Aggregation {
+    type Err = ParseAggregationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_ascii_lowercase();
+        let agg = match lower.as_str() {
+            "avg" | "mean" => Self::Avg,
+            "min" => Self::Min,
+            "max" => Self::Max,
+            "last" => Self::Last,
+            "sum" => Self::Sum,
+            "count" => Self::Count,
+            "rate" => Self::Rate,
+            "increase" => Self::Increase,
+            "stddev" => Self::StdDev,
+            other => {
+                let q = other
+                    .strip_prefix('p')
+                    .and_then(|q| q.parse::<f64>().ok())
+                    .filter(|q| *q > 0.0 && *q <= 100.0)
+                    .ok_or_else(|| ParseAggregationError(s.to_string()))?;
+                Self::Percentile(q)
+            }
+        };
+        Ok(agg)
+    }
+}
+
+impl std::fmt::Display for Aggregation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Avg => write!(f, "avg"),
+            Self::Min => write!(f, "min"),
+            Self::Max => write!(f, "max"),
+            Self::Last => write!(f, "last"),
+            Self::Sum => write!(f, "sum"),
+            Self::Count => write!(f, "count"),
+            Self::Percentile(q) => write!(f, "p{q}"),
+            Self::Rate => write!(f, "rate"),
+            Self::Increase => write!(f, "increase"),
+            Self::StdDev => write!(f, "stddev"),
+        }
+    }
+}
+
+impl Serialize for Aggregation {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Aggregation {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(d)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_named_reducers() {
+        for name in [
+            "avg", "min", "max", "last", "sum", "count", "rate", "increase", "stddev",
+        ] {
+            let agg: Aggregation = name.parse().unwrap();
+            assert_eq!(agg.to_string(), name);
+        }
+    }
+
+    #[test]
+    fn parse_percentiles() {
+        assert_eq!("p95".parse(), Ok(Aggregation::Percentile(95.0)));
+        assert_eq!("P99.9".parse(), Ok(Aggregation::Percentile(99.9)));
+        assert_eq!(Aggregation::Percentile(99.9).to_string(), "p99.9");
+        assert!("p0".parse::<Aggregation>().is_err());
+        assert!("p101".parse::<Aggregation>().is_err());
+        assert!("median".parse::<Aggregation>().is_err());
+    }
+
+    #[test]
+    fn serde_as_string() {
+        let json = serde_json::to_string(&Aggregation::Percentile(90.0)).unwrap();
+        assert_eq!(json, r#""p90""#);
+        let back: Aggregation = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, Aggregation::Percentile(90.0));
+        assert!(serde_json::from_str::<Aggregation>(r#""bogus""#).is_err());
+    }
+}
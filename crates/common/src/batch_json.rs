@@ -0,0 +1,187 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::proto;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BatchJson {
+    pub agent_id: String,
+    pub batch_id: String,
+    pub seq_start: u64,
+    pub seq_end: u64,
+    #[serde(default)]
+    pub created_at_ms: i64,
+    #[serde(default)]
+    pub meta: HashMap<String, String>,
+    pub metrics: Vec<BatchMetricJson>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BatchMetricJson {
+    pub name: String,
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+    #[serde(default)]
+    pub rtype: i32,
+    pub value: Option<BatchValueJson>,
+    pub timestamp_ms: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchValueJson {
+    Double(f64),
+    Int(i64),
+    Histogram {
+        boundaries: Vec<f64>,
+        counts: Vec<u64>,
+        count: u64,
+        sum: f64,
+    },
+}
+
+impl From<&proto::Batch> for BatchJson {
+    fn from(b: &proto::Batch) -> Self {
+        Self {
+            agent_id: b.agent_id.clone(),
+            batch_id: b.batch_id.clone(),
+            seq_start: b.seq_start,
+            seq_end: b.seq_end,
+            created_at_ms: b.created_at_ms,
+            meta: b.meta.clone(),
+            metrics: b.metrics.iter().map(BatchMetricJson::from).collect(),
+        }
+    }
+}
+
+impl From<BatchJson> for proto::Batch {
+    fn from(j: BatchJson) -> Self {
+        Self {
+            agent_id: j.agent_id,
+            batch_id: j.batch_id,
+            seq_start: j.seq_start,
+            seq_end: j.seq_end,
+            created_at_ms: j.created_at_ms,
+            meta: j.meta,
+            metrics: j.metrics.into_iter().map(proto::Metric::from).collect(),
+        }
+    }
+}
+
+impl From<&proto::Metric> for BatchMetricJson {
+    fn from(m: &proto::Metric) -> Self {
+        let value = m.value.as_ref().map(|v| match v {
+            proto::metric::Value::ValueDouble(d) => BatchValueJson::Double(*d),
+            proto::metric::Value::ValueInt(i) => BatchValueJson::Int(*i),
+            proto::metric::Value::Histogram(h) => BatchValueJson::Histogram {
+                boundaries: h.boundaries.clone(),
+                counts: h.counts.clone(),
+                count: h.count,
+                sum: h.sum,
+            },
+        });
+        Self {
+            name: m.name.clone(),
+            labels: m.labels.clone(),
+            rtype: m.rtype,
+            value,
+            timestamp_ms: m.timestamp_ms,
+        }
+    }
+}
+
+impl From<BatchMetricJson> for proto::Metric {
+    fn from(j: BatchMetricJson) -> Self {
+        let value = j.value.map(|v| match v {
+            BatchValueJson::Double(d) => proto::metric::Value::ValueDouble(d),
+            BatchValueJson::Int(i) => proto::metric::Value::ValueInt(i),
+            BatchValueJson::Histogram {
+                boundaries,
+                counts,
+                count,
+                sum,
+            } => proto::metric::Value::Histogram(proto::Histogram {
+                boundaries,
+                counts,
+                count,
+                sum,
+            }),
+        });
+        Self {
+            name: j.name,
+            labels: j.labels,
+            rtype: j.rtype,
+            value,
+            timestamp_ms: j.timestamp_ms,
+        }
+    }
+}
+
+pub fn encode(batch: &proto::Batch) -> serde_json::Result<Vec<u8>> {
+    serde_json::to_vec(&BatchJson::from(batch))
+}
+
+pub fn decode(bytes: &[u8]) -> serde_json::Result<proto::Batch> {
+    let j: BatchJson = serde_json::from_slice(bytes)?;
+    Ok(j.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::canonicalize::canonical_bytes;
+
+    fn sample_batch() -> proto::Batch {
+        proto::Batch {
+            agent_id: "agent-1".into(),
+            batch_id: "batch-1".into(),
+            seq_start: 3,
+            seq_end: 5,
+            created_at_ms: 1_709_251_200_123,
+            meta: [("host".to_string(), "srv1".to_string())].into(),
+            metrics: vec![
+                proto::Metric {
+                    name: "cpu.usage".into(),
+                    labels: [("core".to_string(), "0".to_string())].into(),
+                    rtype: proto::MetricType::Gauge as i32,
+                    value: Some(proto::metric::Value::ValueDouble(55.5)),
+                    timestamp_ms: 1000,
+                },
+                proto::Metric {
+                    name: "process.count_total".into(),
+                    labels: HashMap::new(),
+                    rtype: proto::MetricType::Counter as i32,
+                    value: Some(proto::metric::Value::ValueInt(42)),
+                    timestamp_ms: 1001,
+                },
+                proto::Metric {
+                    name: "request.latency".into(),
+                    labels: HashMap::new(),
+                    rtype: proto::MetricType::Histogram as i32,
+                    value: Some(proto::metric::Value::Histogram(proto::Histogram {
+                        boundaries: vec![10.0, 50.0],
+                        counts: vec![5, 10],
+                        count: 15,
+                        sum: 0.1 + 0.2,
+                    })),
+                    timestamp_ms: 1002,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn roundtrip_preserves_canonical_bytes() {
+        let batch = sample_batch();
+        let restored = decode(&encode(&batch).unwrap()).unwrap();
+        assert_eq!(restored, batch);
+        assert_eq!(canonical_bytes(&restored), canonical_bytes(&batch));
+    }
+
+    #[test]
+    fn int_values_stay_int() {
+        let json = String::from_utf8(encode(&sample_batch()).unwrap()).unwrap();
+        assert!(json.contains("{\"int\":42}"));
+        assert!(json.contains("{\"double\":55.5}"));
+    }
+}
@@ -0,0 +1,22 @@
+pub const MISSING_BATCH_ID: &str = "batch_id is required";
+
+pub const UNKNOWN_KEY: &str = "unknown or expired key";
+
+pub const INVALID_SIGNATURE: &str = "invalid batch signature";
+
+pub fn is_retryable(message: &str) -> bool {
+    matches!(message, UNKNOWN_KEY | INVALID_SIGNATURE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_errors_are_retryable() {
+        assert!(is_retryable(UNKNOWN_KEY));
+        assert!(is_retryable(INVALID_SIGNATURE));
+        assert!(!is_retryable(MISSING_BATCH_ID));
+        assert!(!is_retryable("invalid payload"));
+    }
+}
@@ -0,0 +1,466 @@
+use std::fmt;
+use std::sync::Arc;
+
+use aes_gcm::aead::{Aead, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use rand::RngCore;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::redact::secret_values_mut;
+
+pub const ENCRYPTION_KEY_ENV: &str = "SENTINEL_ENCRYPTION_KEY";
+pub const ENCRYPTION_KEY_FILE_ENV: &str = "SENTINEL_ENCRYPTION_KEY_FILE";
+pub const PREVIOUS_ENCRYPTION_KEYS_ENV: &str = "SENTINEL_ENCRYPTION_KEY_PREVIOUS";
+
+const PREFIX: &str = "enc:v1:";
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvelopeError {
+    InvalidKey(String),
+    KeyFile(String),
+    MissingCurrentKey,
+    Disabled,
+    UnknownKey(String),
+    Malformed,
+    Crypto,
+}
+
+impl fmt::Display for EnvelopeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidKey(msg) => write!(f, "invalid master key: {msg}"),
+            Self::KeyFile(msg) => write!(f, "cannot read master key file: {msg}"),
+            Self::MissingCurrentKey => write!(
+                f,
+                "{PREVIOUS_ENCRYPTION_KEYS_ENV} is set but no current master key is configured"
+            ),
+            Self::Disabled => write!(f, "value is encrypted but no master key is configured"),
+            Self::UnknownKey(id) => write!(f, "value is encrypted with unknown master key {id}"),
+            Self::Malformed => write!(f, "malformed encrypted value"),
+            Self::Crypto => write!(f, "encryption or decryption failed"),
+        }
+    }
+}
+
+impl std::error::Error for EnvelopeError {}
+
+#[derive(Clone)]
+pub struct MasterKey {
+    id: String,
+    key: [u8; KEY_LEN],
+}
+
+impl MasterKey {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EnvelopeError> {
+        let key: [u8; KEY_LEN] = bytes.try_into().map_err(|_| {
+            EnvelopeError::InvalidKey(format!("expected {KEY_LEN} bytes, got {}", bytes.len()))
+        })?;
+        let digest = Sha256::digest(key);
+        let id = digest[..4].iter().map(|b| format!("{b:02x}")).collect();
+        Ok(Self { id, key })
+    }
+
+    pub fn from_base64(encoded: &str) -> Result<Self, EnvelopeError> {
+        let bytes = STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| EnvelopeError::InvalidKey(e.to_string()))?;
+        Self::from_bytes(&bytes)
+    }
+
+    pub fn generate() -> Self {
+        let mut key = [0u8; KEY_LEN];
+        OsRng.fill_bytes(&mut key);
+        Self::from_bytes(&key).expect("generated key has the right length")
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn to_base64(&self) -> String {
+        STANDARD.encode(self.key)
+    }
+
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, EnvelopeError> {
+        encrypt_with(&self.key, plaintext)
+    }
+
+    fn decrypt(&self, blob: &[u8]) -> Result<Vec<u8>, EnvelopeError> {
+        decrypt_with(&self.key, blob)
+    }
+}
+
+impl fmt::Debug for MasterKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MasterKey")
+            .field("id", &self.id)
+            .field("key", &"***")
+            .finish()
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Envelope {
+    keys: Arc<Vec<MasterKey>>,
+}
+
+impl fmt::Debug for Envelope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Envelope")
+            .field("key_id", &self.key_id())
+            .field("previous", &self.previous_key_ids())
+            .finish()
+    }
+}
+
+impl Envelope {
+    pub fn new(current: MasterKey, previous: Vec<MasterKey>) -> Self {
+        let mut keys = vec![current];
+        keys.extend(previous);
+        Self {
+            keys: Arc::new(keys),
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    pub fn from_env() -> Result<Self, EnvelopeError> {
+        let var = |name| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        Self::from_sources(
+            var(ENCRYPTION_KEY_ENV),
+            var(ENCRYPTION_KEY_FILE_ENV),
+            var(PREVIOUS_ENCRYPTION_KEYS_ENV),
+        )
+    }
+
+    pub fn from_sources(
+        key: Option<String>,
+        key_file: Option<String>,
+        previous: Option<String>,
+    ) -> Result<Self, EnvelopeError> {
+        let current = match (key, key_file) {
+            (Some(encoded), _) => Some(MasterKey::from_base64(&encoded)?),
+            (None, Some(path)) => {
+                let encoded = std::fs::read_to_string(&path)
+                    .map_err(|e| EnvelopeError::KeyFile(format!("{path}: {e}")))?;
+                Some(MasterKey::from_base64(&encoded)?)
+            }
+            (None, None) => None,
+        };
+        let previous = previous
+            .iter()
+            .flat_map(|list| list.split(','))
+            .map(str::trim)
+            .filter(|k| !k.is_empty())
+            .map(MasterKey::from_base64)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match current {
+            Some(current) => Ok(Self::new(current, previous)),
+            None if previous.is_empty() => Ok(Self::disabled()),
+            None => Err(EnvelopeError::MissingCurrentKey),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.keys.is_empty()
+    }
+
+    pub fn key_id(&self) -> Option<&str> {
+        self.keys.first().map(MasterKey::id)
+    }
+
+    pub fn previous_key_ids(&self) -> Vec<&str> {
+        self.keys.iter().skip(1).map(MasterKey::id).collect()
+    }
+
+    pub fn is_sealed(value: &str) -> bool {
+        value.starts_with(PREFIX)
+    }
+
+    pub fn seal_str(&self, plaintext: &str) -> Result<String, EnvelopeError> {
+        let Some(current) = self.keys.first() else {
+            return Ok(plaintext.to_string());
+        };
+        if Self::is_sealed(plaintext) {
+            return Ok(plaintext.to_string());
+        }
+        let mut dek = [0u8; KEY_LEN];
+        OsRng.fill_bytes(&mut dek);
+        let data = encrypt_with(&dek, plaintext.as_bytes())?;
+        let wrapped = current.encrypt(&dek)?;
+        Ok(format!(
+            "{PREFIX}{}:{}:{}",
+            current.id,
+            STANDARD.encode(wrapped),
+            STANDARD.encode(data)
+        ))
+    }
+
+    pub fn open_str(&self, value: &str) -> Result<String, EnvelopeError> {
+        let Some(sealed) = Sealed::parse(value)? else {
+            return Ok(value.to_string());
+        };
+        let dek = self.unwrap_dek(&sealed)?;
+        let plaintext = decrypt_with(&dek, &sealed.data)?;
+        String::from_utf8(plaintext).map_err(|_| EnvelopeError::Malformed)
+    }
+
+    pub fn seal_bytes(&self, plaintext: &[u8]) -> Result<Vec<u8>, EnvelopeError> {
+        if !self.is_enabled() {
+            return Ok(plaintext.to_vec());
+        }
+        self.seal_str(&STANDARD.encode(plaintext))
+            .map(String::into_bytes)
+    }
+
+    pub fn open_bytes(&self, value: &[u8]) -> Result<Vec<u8>, EnvelopeError> {
+        let Some(text) = sealed_text(value) else {
+            return Ok(value.to_vec());
+        };
+        let encoded = self.open_str(text)?;
+        STANDARD
+            .decode(encoded)
+            .map_err(|_| EnvelopeError::Malformed)
+    }
+
+    pub fn reseal_str(&self, value: &str) -> Result<Option<String>, EnvelopeError> {
+        let Some(current) = self.keys.first() else {
+            return Ok(None);
+        };
+        let Some(sealed) = Sealed::parse(value)? else {
+            return self.seal_str(value).map(Some);
+        };
+        if sealed.key_id == current.id {
+            return Ok(None);
+        }
+        let dek = self.unwrap_dek(&sealed)?;
+        let wrapped = current.encrypt(&dek)?;
+        Ok(Some(format!(
+            "{PREFIX}{}:{}:{}",
+            current.id,
+            STANDARD.encode(wrapped),
+            STANDARD.encode(&sealed.data)
+        )))
+    }
+
+    pub fn reseal_bytes(&self, value: &[u8]) -> Result<Option<Vec<u8>>, EnvelopeError> {
+        match sealed_text(value) {
+            Some(text) => Ok(self.reseal_str(text)?.map(String::into_bytes)),
+            None if self.is_enabled() => self.seal_bytes(value).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub fn seal_config(&self, config: &Value) -> Result<Value, EnvelopeError> {
+        map_secrets(config, |v| self.seal_str(v).map(Some))
+    }
+
+    pub fn open_config(&self, config: &Value) -> Result<Value, EnvelopeError> {
+        map_secrets(config, |v| self.open_str(v).map(Some))
+    }
+
+    pub fn reseal_config(&self, config: &Value) -> Result<Option<Value>, EnvelopeError> {
+        let mut changed = false;
+        let resealed = map_secrets(config, |v| {
+            let out = self.reseal_str(v)?;
+            changed |= out.is_some();
+            Ok(out)
+        })?;
+        Ok(changed.then_some(resealed))
+    }
+
+    fn unwrap_dek(&self, sealed: &Sealed) -> Result<Vec<u8>, EnvelopeError> {
+        if !self.is_enabled() {
+            return Err(EnvelopeError::Disabled);
+        }
+        let key = self
+            .keys
+            .iter()
+            .find(|k| k.id == sealed.key_id)
+            .ok_or_else(|| EnvelopeError::UnknownKey(sealed.key_id.clone()))?;
+        key.decrypt(&sealed.wrapped_dek)
+    }
+}
+
+struct Sealed {
+    key_id: String,
+    wrapped_dek: Vec<u8>,
+    data: Vec<u8>,
+}
+
+impl Sealed {
+    fn parse(value: &str) -> Result<Option<Self>, EnvelopeError> {
+        let Some(rest) = value.strip_prefix(PREFIX) else {
+            return Ok(None);
+        };
+        let mut parts = rest.splitn(3, ':');
+        let (Some(key_id), Some(dek), Some(data)) = (parts.next(), parts.next(), parts.next())
+        else {
+            return Err(EnvelopeError::Malformed);
+        };
+        let decode = |s: &str| STANDARD.decode(s).map_err(|_| EnvelopeError::Malformed);
+        Ok(Some(Self {
+            key_id: key_id.to_string(),
+            wrapped_dek: decode(dek)?,
+            data: decode(data)?,
+        }))
+    }
+}
+
+fn sealed_text(value: &[u8]) -> Option<&str> {
+    if !value.starts_with(PREFIX.as_bytes()) {
+        return None;
+    }
+    std::str::from_utf8(value).ok()
+}
+
+fn map_secrets<F>(config: &Value, mut f: F) -> Result<Value, EnvelopeError>
+where
+    F: FnMut(&str) -> Result<Option<String>, EnvelopeError>,
+{
+    let mut out = config.clone();
+    for (_, s) in secret_values_mut(&mut out) {
+        if let Some(mapped) = f(s)? {
+            *s = mapped;
+        }
+    }
+    Ok(out)
+}
+
+fn encrypt_with(key: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, EnvelopeError> {
+    let cipher = Aes256Gcm::new_from_slice(key).map_err(|_| EnvelopeError::Crypto)?;
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce_bytes), plaintext)
+        .map_err(|_| EnvelopeError::Crypto)?;
+    let mut blob = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+    blob.extend_from_slice(&nonce_bytes);
+    blob.extend_from_slice(&ciphertext);
+    Ok(blob)
+}
+
+fn decrypt_with(key: &[u8], blob: &[u8]) -> Result<Vec<u8>, EnvelopeError> {
+    if blob.len() < NONCE_LEN {
+        return Err(EnvelopeError::Malformed);
+    }
+    let (nonce, ciphertext) = blob.split_at(NONCE_LEN);
+    let cipher = Aes256Gcm::new_from_slice(key).map_err(|_| EnvelopeError::Crypto)?;
+    cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| EnvelopeError::Crypto)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn envelope() -> (Envelope, MasterKey) {
+        let key = MasterKey::generate();
+        (Envelope::new(key.clone(), vec![]), key)
+    }
+
+    #[test]
+    fn seal_and_open_roundtrip() {
+        let (env, key) = envelope();
+        let sealed = env.seal_str("hunter2").unwrap();
+        assert!(sealed.starts_with(&format!("enc:v1:{}:", key.id())));
+        assert!(!sealed.contains("hunter2"));
+        assert_eq!(env.open_str(&sealed).unwrap(), "hunter2");
+        assert_ne!(env.seal_str("hunter2").unwrap(), sealed);
+    }
+
+    #[test]
+    fn plaintext_passes_through() {
+        let (env, _) = envelope();
+        assert_eq!(env.open_str("legacy").unwrap(), "legacy");
+        assert_eq!(env.open_bytes(b"\x01\x02").unwrap(), vec![1, 2]);
+
+        let disabled = Envelope::disabled();
+        assert_eq!(disabled.seal_str("plain").unwrap(), "plain");
+        let sealed = env.seal_str("secret").unwrap();
+        assert_eq!(disabled.open_str(&sealed), Err(EnvelopeError::Disabled));
+    }
+
+    #[test]
+    fn bytes_roundtrip() {
+        let (env, _) = envelope();
+        let secret = crate::crypto::generate_secret();
+        let sealed = env.seal_bytes(&secret).unwrap();
+        assert_ne!(sealed, secret);
+        assert_eq!(env.open_bytes(&sealed).unwrap(), secret);
+    }
+
+    #[test]
+    fn wrong_key_is_rejected() {
+        let (env, _) = envelope();
+        let (other, _) = envelope();
+        let sealed = env.seal_str("secret").unwrap();
+        assert!(matches!(
+            other.open_str(&sealed),
+            Err(EnvelopeError::UnknownKey(_))
+        ));
+    }
+
+    #[test]
+    fn reseal_rotates_to_current_key() {
+        let old = MasterKey::generate();
+        let new = MasterKey::generate();
+        let before = Envelope::new(old.clone(), vec![]);
+        let after = Envelope::new(new.clone(), vec![old]);
+
+        let sealed = before.seal_str("token-value").unwrap();
+        let resealed = after.reseal_str(&sealed).unwrap().unwrap();
+        assert!(resealed.starts_with(&format!("enc:v1:{}:", new.id())));
+        assert_eq!(after.open_str(&resealed).unwrap(), "token-value");
+        assert_eq!(after.reseal_str(&resealed).unwrap(), None);
+
+        let plain = after.reseal_str("legacy").unwrap().unwrap();
+        assert_eq!(after.open_str(&plain).unwrap(), "legacy");
+    }
+
+    #[test]
+    fn config_secrets_only() {
+        let (env, _) = envelope();
+        let config = json!({"url": "https://example.com/hook", "secret": "s3cr3t", "timeout": 5, "host": "smtp", "headers": {"X-Key": "k1"}});
+        let sealed = env.seal_config(&config).unwrap();
+        assert!(Envelope::is_sealed(sealed["url"].as_str().unwrap()));
+        assert!(Envelope::is_sealed(sealed["secret"].as_str().unwrap()));
+        assert!(Envelope::is_sealed(
+            sealed["headers"]["X-Key"].as_str().unwrap()
+        ));
+        assert_eq!(sealed["host"], "smtp");
+        assert_eq!(sealed["timeout"], 5);
+        assert_eq!(env.open_config(&sealed).unwrap(), config);
+        assert_eq!(env.reseal_config(&sealed).unwrap(), None);
+    }
+
+    #[test]
+    fn sources() {
+        let key = MasterKey::generate();
+        let env = Envelope::from_sources(Some(key.to_base64()), None, None).unwrap();
+        assert_eq!(env.key_id(), Some(key.id()));
+        assert!(!Envelope::from_sources(None, None, None)
+            .unwrap()
+            .is_enabled());
+        assert_eq!(
+            Envelope::from_sources(None, None, Some(key.to_base64())).unwrap_err(),
+            EnvelopeError::MissingCurrentKey
+        );
+        assert!(matches!(
+            Envelope::from_sources(Some("c2hvcnQ=".into()), None, None),
+            Err(EnvelopeError::InvalidKey(_))
+        ));
+        let debug = format!("{:?}", key);
+        assert!(!debug.contains(&key.to_base64()));
+    }
+}
@@ -0,0 +1,275 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::labels::{LabelMatcher, LabelSelector, MatcherError};
+use crate::silence::agent_matches;
+
+pub const MAX_STEPS: usize = 10;
+pub const MAX_ROUTE_DEPTH: usize = 8;
+const SEVERITIES: &[&str] = &["info", "warning", "critical"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EscalationStep {
+    #[serde(default)]
+    pub delay_secs: u64,
+    pub notifier_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EscalationPolicy {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub steps: Vec<EscalationStep>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Route {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent_pattern: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub severity: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub label_matchers: Vec<LabelMatcher>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy_id: Option<String>,
+    #[serde(default, rename = "continue")]
+    pub continue_matching: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub routes: Vec<Route>,
+}
+
+pub struct RouteTarget<'a> {
+    pub agent_id: &'a str,
+    pub severity: &'a str,
+    pub labels: &'a BTreeMap<String, String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum EscalationError {
+    EmptyName,
+    NoSteps,
+    TooManySteps(usize),
+    EmptyStep(usize),
+    InvalidSeverity(String),
+    TooDeep,
+    Matcher(MatcherError),
+}
+
+impl std::fmt::Display for EscalationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyName => write!(f, "policy name is required"),
+            Self::NoSteps => write!(f, "policy needs at least one step"),
+            Self::TooManySteps(n) => write!(f, "policy has {n} steps, at most {MAX_STEPS} allowed"),
+            Self::EmptyStep(i) => write!(f, "step {} has no notifier_ids", i + 1),
+            Self::InvalidSeverity(s) => write!(f, "unknown severity '{s}'"),
+            Self::TooDeep => write!(f, "routes nest deeper than {MAX_ROUTE_DEPTH} levels"),
+            Self::Matcher(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for EscalationError {}
+
+impl EscalationPolicy {
+    pub fn validate(&self) -> Result<(), EscalationError> {
+        if self.name.trim().is_empty() {
+            return Err(EscalationError::EmptyName);
+        }
+        if self.steps.is_empty() {
+            return Err(EscalationError::NoSteps);
+        }
+        if self.steps.len() > MAX_STEPS {
+            return Err(EscalationError::TooManySteps(self.steps.len()));
+        }
+        if let Some(i) = self.steps.iter().position(|s| s.notifier_ids.is_empty()) {
+            return Err(EscalationError::EmptyStep(i));
+        }
+        Ok(())
+    }
+
+    pub fn next_at_ms(&self, step: usize, from_ms: i64) -> Option<i64> {
+        self.steps
+            .get(step)
+            .map(|s| from_ms + (s.delay_secs as i64).saturating_mul(1000))
+    }
+
+    pub fn notifier_ids(&self) -> impl Iterator<Item = &str> {
+        self.steps
+            .iter()
+            .flat_map(|s| s.notifier_ids.iter().map(String::as_str))
+    }
+}
+
+impl Route {
+    pub fn validate(&self) -> Result<(), EscalationError> {
+        self.validate_at(0)
+    }
+
+    fn validate_at(&self, depth: usize) -> Result<(), EscalationError> {
+        if depth > MAX_ROUTE_DEPTH {
+            return Err(EscalationError::TooDeep);
+        }
+        if let Some(s) = self
+            .severity
+            .iter()
+            .find(|s| !SEVERITIES.contains(&s.as_str()))
+        {
+            return Err(EscalationError::InvalidSeverity(s.clone()));
+        }
+        crate::labels::validate(&self.label_matchers).map_err(EscalationError::Matcher)?;
+        self.routes
+            .iter()
+            .try_for_each(|r| r.validate_at(depth + 1))
+    }
+
+    pub fn policy_ids(&self) -> Vec<&str> {
+        let mut ids: Vec<&str> = self.policy_id.iter().map(String::as_str).collect();
+        for r in &self.routes {
+            ids.extend(r.policy_ids());
+        }
+        ids
+    }
+
+    pub fn resolve(&self, target: &RouteTarget<'_>) -> Vec<String> {
+        let mut out = Vec::new();
+        self.walk(target, &mut out);
+        out.dedup();
+        out
+    }
+
+    fn walk(&self, target: &RouteTarget<'_>, out: &mut Vec<String>) -> bool {
+        if !self.matches(target) {
+            return false;
+        }
+        let mut matched_child = false;
+        for child in &self.routes {
+            if child.walk(target, out) {
+                matched_child = true;
+                if !child.continue_matching {
+                    break;
+                }
+            }
+        }
+        if !matched_child {
+            if let Some(ref id) = self.policy_id {
+                if !out.contains(id) {
+                    out.push(id.clone());
+                }
+            }
+        }
+        true
+    }
+
+    fn matches(&self, target: &RouteTarget<'_>) -> bool {
+        if let Some(ref pattern) = self.agent_pattern {
+            if !agent_matches(pattern, target.agent_id) {
+                return false;
+            }
+        }
+        if !self.severity.is_empty() && !self.severity.iter().any(|s| s == target.severity) {
+            return false;
+        }
+        match LabelSelector::compile(&self.label_matchers) {
+            Ok(selector) => selector.matches(target.labels),
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(steps: &[(u64, &[&str])]) -> EscalationPolicy {
+        EscalationPolicy {
+            id: "p1".into(),
+            name: "default".into(),
+            description: String::new(),
+            steps: steps
+                .iter()
+                .map(|(delay, ids)| EscalationStep {
+                    delay_secs: *delay,
+                    notifier_ids: ids.iter().map(|s| s.to_string()).collect(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn validates_policy_steps() {
+        assert!(policy(&[(0, &["slack"]), (600, &["pd"])])
+            .validate()
+            .is_ok());
+        assert_eq!(policy(&[]).validate(), Err(EscalationError::NoSteps));
+        assert_eq!(
+            policy(&[(0, &["slack"]), (600, &[])]).validate(),
+            Err(EscalationError::EmptyStep(1))
+        );
+        let p = policy(&[(0, &["slack"]), (600, &["pd"])]);
+        assert_eq!(p.next_at_ms(1, 1_000), Some(601_000));
+        assert_eq!(p.next_at_ms(2, 1_000), None);
+    }
+
+    #[test]
+    fn routes_pick_first_matching_branch() {
+        let tree: Route = serde_json::from_value(serde_json::json!({
+            "policy_id": "default",
+            "routes": [
+                { "severity": ["critical"], "policy_id": "page", "routes": [
+                    { "label_matchers": [{ "label": "team", "op": "=", "value": "db" }],
+                      "policy_id": "dba" }
+                ]},
+                { "agent_pattern": "web-*", "policy_id": "web", "continue": true },
+                { "agent_pattern": "web-*", "policy_id": "web-audit" }
+            ]
+        }))
+        .unwrap();
+        tree.validate().unwrap();
+
+        let labels = BTreeMap::new();
+        let db = BTreeMap::from([("team".to_string(), "db".to_string())]);
+        let resolve = |agent: &str, severity: &str, labels: &BTreeMap<String, String>| {
+            tree.resolve(&RouteTarget {
+                agent_id: agent,
+                severity,
+                labels,
+            })
+        };
+
+        assert_eq!(resolve("db-01", "critical", &db), vec!["dba"]);
+        assert_eq!(resolve("db-01", "critical", &labels), vec!["page"]);
+        assert_eq!(
+            resolve("web-01", "warning", &labels),
+            vec!["web", "web-audit"]
+        );
+        assert_eq!(resolve("db-01", "info", &labels), vec!["default"]);
+        assert_eq!(
+            tree.policy_ids(),
+            vec!["default", "page", "dba", "web", "web-audit"]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_routes() {
+        let bad = Route {
+            severity: vec!["urgent".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            bad.validate(),
+            Err(EscalationError::InvalidSeverity("urgent".into()))
+        );
+
+        let mut deep = Route::default();
+        for _ in 0..=MAX_ROUTE_DEPTH {
+            deep = Route {
+                routes: vec![deep],
+                ..Default::default()
+            };
+        }
+        assert_eq!(deep.validate(), Err(EscalationError::TooDeep));
+    }
+}
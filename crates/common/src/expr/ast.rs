@@ -0,0 +1,175 @@
+use std::collections::BTreeMap;
+
+use crate::aggregation::Aggregation;
+use crate::labels::{LabelMatcher, LabelSelector};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    Gt,
+    Lt,
+    Ge,
+    Le,
+    Eq,
+    Ne,
+    And,
+    Or,
+}
+
+impl BinOp {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+            Self::Div => "/",
+            Self::Mod => "%",
+            Self::Gt => ">",
+            Self::Lt => "<",
+            Self::Ge => ">=",
+            Self::Le => "<=",
+            Self::Eq => "==",
+            Self::Ne => "!=",
+            Self::And => "and",
+            Self::Or => "or",
+        }
+    }
+
+    pub fn is_comparison(&self) -> bool {
+        matches!(
+            self,
+            Self::Gt | Self::Lt | Self::Ge | Self::Le | Self::Eq | Self::Ne
+        )
+    }
+
+    pub fn is_logical(&self) -> bool {
+        matches!(self, Self::And | Self::Or)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Selector {
+    pub metric: String,
+    pub matchers: Vec<LabelMatcher>,
+    compiled: LabelSelector,
+}
+
+impl Selector {
+    pub(crate) fn new(
+        metric: String,
+        matchers: Vec<LabelMatcher>,
+        compiled: LabelSelector,
+    ) -> Self {
+        Self {
+            metric,
+            matchers,
+            compiled,
+        }
+    }
+
+    pub fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        self.compiled.matches(labels)
+    }
+}
+
+impl std::fmt::Display for Selector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.metric)?;
+        if !self.matchers.is_empty() {
+            let matchers: Vec<String> = self.matchers.iter().map(|m| m.to_string()).collect();
+            write!(f, "{{{}}}", matchers.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Expr {
+    Number(f64),
+    Series(Selector),
+    Reduce {
+        agg: Aggregation,
+        selector: Selector,
+        window_ms: Option<i64>,
+    },
+    Abs(Box<Expr>),
+    Neg(Box<Expr>),
+    Binary {
+        op: BinOp,
+        lhs: Box<Expr>,
+        rhs: Box<Expr>,
+    },
+}
+
+impl Expr {
+    pub fn selectors(&self) -> Vec<&Selector> {
+        let mut out = Vec::new();
+        self.collect_selectors(&mut out);
+        out
+    }
+
+    fn collect_selectors<'a>(&'a self, out: &mut Vec<&'a Selector>) {
+        match self {
+            Self::Number(_) => {}
+            Self::Series(s) | Self::Reduce { selector: s, .. } => out.push(s),
+            Self::Abs(e) | Self::Neg(e) => e.collect_selectors(out),
+            Self::Binary { lhs, rhs, .. } => {
+                lhs.collect_selectors(out);
+                rhs.collect_selectors(out);
+            }
+        }
+    }
+
+    pub fn max_window_ms(&self) -> Option<i64> {
+        match self {
+            Self::Number(_) | Self::Series(_) => None,
+            Self::Reduce { window_ms, .. } => *window_ms,
+            Self::Abs(e) | Self::Neg(e) => e.max_window_ms(),
+            Self::Binary { lhs, rhs, .. } => lhs.max_window_ms().max(rhs.max_window_ms()),
+        }
+    }
+}
+
+impl std::fmt::Display for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "{n}"),
+            Self::Series(s) => write!(f, "{s}"),
+            Self::Reduce {
+                agg,
+                selector,
+                window_ms,
+            } => {
+                match agg {
+                    Aggregation::Rate | Aggregation::Increase => write!(f, "{agg}({selector}")?,
+                    _ => write!(f, "{agg}_over({selector}")?,
+                }
+                if let Some(ms) = window_ms {
+                    write!(f, ", {}", format_duration(*ms))?;
+                }
+                f.write_str(")")
+            }
+            Self::Abs(e) => write!(f, "abs({e})"),
+            Self::Neg(e) => write!(f, "-{e}"),
+            Self::Binary { op, lhs, rhs } => write!(f, "({lhs} {} {rhs})", op.as_str()),
+        }
+    }
+}
+
+fn format_duration(ms: i64) -> String {
+    const UNITS: &[(i64, &str)] = &[
+        (86_400_000, "d"),
+        (3_600_000, "h"),
+        (60_000, "m"),
+        (1000, "s"),
+    ];
+    UNITS
+        .iter()
+        .find(|(size, _)| ms % size == 0)
+        .map(|(size, unit)| format!("{}{unit}", ms / size))
+        .unwrap_or_else(|| format!("{ms}ms"))
+}
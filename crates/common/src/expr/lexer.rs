@@ -0,0 +1,271 @@
+use super::ExprError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Token {
+    Number(f64),
+    Duration(i64),
+    Ident(String),
+    Str(String),
+    LParen,
+    RParen,
+    LBrace,
+    RBrace,
+    Comma,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Percent,
+    Gt,
+    Lt,
+    Ge,
+    Le,
+    EqEq,
+    Ne,
+    Assign,
+    ReMatch,
+    ReNotMatch,
+    And,
+    Or,
+    Eof,
+}
+
+impl Token {
+    pub(super) fn describe(&self) -> String {
+        match self {
+            Self::Number(n) => format!("number {n}"),
+            Self::Duration(ms) => format!("duration {ms}ms"),
+            Self::Ident(s) => format!("'{s}'"),
+            Self::Str(s) => format!("string \"{s}\""),
+            Self::Eof => "end of expression".into(),
+            other => format!("'{}'", other.symbol()),
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            Self::LParen => "(",
+            Self::RParen => ")",
+            Self::LBrace => "{",
+            Self::RBrace => "}",
+            Self::Comma => ",",
+            Self::Plus => "+",
+            Self::Minus => "-",
+            Self::Star => "*",
+            Self::Slash => "/",
+            Self::Percent => "%",
+            Self::Gt => ">",
+            Self::Lt => "<",
+            Self::Ge => ">=",
+            Self::Le => "<=",
+            Self::EqEq => "==",
+            Self::Ne => "!=",
+            Self::Assign => "=",
+            Self::ReMatch => "=~",
+            Self::ReNotMatch => "!~",
+            Self::And => "and",
+            Self::Or => "or",
+            _ => "",
+        }
+    }
+}
+
+pub(super) fn tokenize(src: &str) -> Result<Vec<(usize, Token)>, ExprError> {
+    let bytes = src.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i] as char;
+        if c.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        let next = bytes.get(i + 1).map(|b| *b as char);
+
+        let token = match (c, next) {
+            ('(', _) => Token::LParen,
+            (')', _) => Token::RParen,
+            ('{', _) => Token::LBrace,
+            ('}', _) => Token::RBrace,
+            (',', _) => Token::Comma,
+            ('+', _) => Token::Plus,
+            ('-', _) => Token::Minus,
+            ('*', _) => Token::Star,
+            ('/', _) => Token::Slash,
+            ('%', _) => Token::Percent,
+            ('>', Some('=')) => Token::Ge,
+            ('>', _) => Token::Gt,
+            ('<', Some('=')) => Token::Le,
+            ('<', _) => Token::Lt,
+            ('=', Some('=')) => Token::EqEq,
+            ('=', Some('~')) => Token::ReMatch,
+            ('=', _) => Token::Assign,
+            ('!', Some('=')) => Token::Ne,
+            ('!', Some('~')) => Token::ReNotMatch,
+            ('&', Some('&')) => Token::And,
+            ('|', Some('|')) => Token::Or,
+            ('"', _) => {
+                let (s, end) = lex_string(src, i)?;
+                tokens.push((start, Token::Str(s)));
+                i = end;
+                continue;
+            }
+            (c, _) if c.is_ascii_digit() || c == '.' => {
+                let (tok, end) = lex_number(src, i)?;
+                tokens.push((start, tok));
+                i = end;
+                continue;
+            }
+            (c, _) if c.is_ascii_alphabetic() || c == '_' => {
+                let end = src[i..]
+                    .find(|ch: char| !(ch.is_ascii_alphanumeric() || matches!(ch, '_' | '.' | ':')))
+                    .map(|n| i + n)
+                    .unwrap_or(src.len());
+                let tok = match &src[i..end] {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    ident => Token::Ident(ident.to_string()),
+                };
+                tokens.push((start, tok));
+                i = end;
+                continue;
+            }
+            (c, _) => return Err(ExprError::new(i, format!("unexpected character '{c}'"))),
+        };
+
+        i += match token {
+            Token::Ge
+            | Token::Le
+            | Token::EqEq
+            | Token::Ne
+            | Token::ReMatch
+            | Token::ReNotMatch
+            | Token::And
+            | Token::Or => 2,
+            _ => 1,
+        };
+        tokens.push((start, token));
+    }
+
+    tokens.push((src.len(), Token::Eof));
+    Ok(tokens)
+}
+
+fn lex_string(src: &str, start: usize) -> Result<(String, usize), ExprError> {
+    let mut out = String::new();
+    let mut chars = src[start + 1..].char_indices();
+    while let Some((off, c)) = chars.next() {
+        match c {
+            '"' => return Ok((out, start + 1 + off + 1)),
+            '\\' => match chars.next() {
+                Some((_, c)) => out.push(c),
+                None => break,
+            },
+            c => out.push(c),
+        }
+    }
+    Err(ExprError::new(start, "unterminated string"))
+}
+
+fn lex_number(src: &str, start: usize) -> Result<(Token, usize), ExprError> {
+    let bytes = src.as_bytes();
+    let mut i = start;
+    while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'.') {
+        i += 1;
+    }
+    if i < bytes.len() && matches!(bytes[i], b'e' | b'E') {
+        let mut j = i + 1;
+        if j < bytes.len() && matches!(bytes[j], b'+' | b'-') {
+            j += 1;
+        }
+        if j < bytes.len() && bytes[j].is_ascii_digit() {
+            i = j;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+        }
+    }
+
+    let value: f64 = src[start..i]
+        .parse()
+        .map_err(|_| ExprError::new(start, format!("invalid number '{}'", &src[start..i])))?;
+
+    let unit_end = src[i..]
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .map(|n| i + n)
+        .unwrap_or(src.len());
+    if unit_end == i {
+        return Ok((Token::Number(value), i));
+    }
+
+    let scale = match &src[i..unit_end] {
+        "ms" => 1.0,
+        "s" => 1000.0,
+        "m" => 60_000.0,
+        "h" => 3_600_000.0,
+        "d" => 86_400_000.0,
+        unit => {
+            return Err(ExprError::new(
+                i,
+                format!("unknown duration unit '{unit}' (use ms, s, m, h or d)"),
+            ))
+        }
+    };
+    Ok((Token::Duration((value * scale) as i64), unit_end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(src: &str) -> Vec<Token> {
+        tokenize(src).unwrap().into_iter().map(|(_, t)| t).collect()
+    }
+
+    #[test]
+    fn lexes_operators_and_idents() {
+        assert_eq!(
+            kinds("mem.used_bytes / mem.total_bytes >= 0.9"),
+            vec![
+                Token::Ident("mem.used_bytes".into()),
+                Token::Slash,
+                Token::Ident("mem.total_bytes".into()),
+                Token::Ge,
+                Token::Number(0.9),
+                Token::Eof,
+            ]
+        );
+        assert_eq!(
+            kinds("a && b || c and d or e")
+                .into_iter()
+                .filter(|t| matches!(t, Token::And | Token::Or))
+                .count(),
+            4
+        );
+    }
+
+    #[test]
+    fn lexes_durations_and_strings() {
+        assert_eq!(
+            kinds(r#"5m 30s 1.5h 250ms "sd\"a""#),
+            vec![
+                Token::Duration(300_000),
+                Token::Duration(30_000),
+                Token::Duration(5_400_000),
+                Token::Duration(250),
+                Token::Str("sd\"a".into()),
+                Token::Eof,
+            ]
+        );
+        assert_eq!(kinds("1e3"), vec![Token::Number(1000.0), Token::Eof]);
+    }
+
+    #[test]
+    fn reports_error_positions() {
+        assert_eq!(tokenize("cpu > 5x").unwrap_err().pos, 7);
+        assert_eq!(tokenize("cpu # 1").unwrap_err().pos, 4);
+        assert_eq!(tokenize(r#"a{m="x}"#).unwrap_err().pos, 4);
+    }
+}
@@ -0,0 +1,33 @@
+mod ast;
+mod lexer;
+mod parser;
+
+pub use ast::{BinOp, Expr, Selector};
+pub use parser::parse;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExprError {
+    pub pos: usize,
+    pub message: String,
+}
+
+impl ExprError {
+    pub(crate) fn new(pos: usize, message: impl Into<String>) -> Self {
+        Self {
+            pos,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ExprError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "at column {}: {}", self.pos + 1, self.message)
+    }
+}
+
+impl std::error::Error for ExprError {}
+
+pub fn validate(source: &str) -> Result<(), ExprError> {
+    parse(source).map(|_| ())
+}
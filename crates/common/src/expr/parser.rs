@@ -0,0 +1,371 @@
+use crate::aggregation::Aggregation;
+use crate::labels::{LabelMatcher, LabelSelector, MatchOp};
+
+use super::ast::{BinOp, Expr, Selector};
+use super::lexer::{tokenize, Token};
+use super::ExprError;
+
+pub fn parse(source: &str) -> Result<Expr, ExprError> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        pos: 0,
+    };
+    if parser.peek() == &Token::Eof {
+        return Err(ExprError::new(0, "empty expression"));
+    }
+    let expr = parser.parse_or()?;
+    match parser.peek() {
+        Token::Eof => Ok(expr),
+        other => Err(parser.error(format!("unexpected {}", other.describe()))),
+    }
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos].1
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens[self.pos].0
+    }
+
+    fn advance(&mut self) -> Token {
+        let tok = self.tokens[self.pos].1.clone();
+        if tok != Token::Eof {
+            self.pos += 1;
+        }
+        tok
+    }
+
+    fn error(&self, message: impl Into<String>) -> ExprError {
+        ExprError::new(self.offset(), message)
+    }
+
+    fn expect(&mut self, want: Token) -> Result<(), ExprError> {
+        if self.peek() == &want {
+            self.advance();
+            Ok(())
+        } else {
+            Err(self.error(format!(
+                "expected {}, found {}",
+                want.describe(),
+                self.peek().describe()
+            )))
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, ExprError> {
+        let mut lhs = self.parse_and()?;
+        while self.peek() == &Token::Or {
+            self.advance();
+            let rhs = self.parse_and()?;
+            lhs = binary(BinOp::Or, lhs, rhs);
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, ExprError> {
+        let mut lhs = self.parse_comparison()?;
+        while self.peek() == &Token::And {
+            self.advance();
+            let rhs = self.parse_comparison()?;
+            lhs = binary(BinOp::And, lhs, rhs);
+        }
+        Ok(lhs)
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, ExprError> {
+        let lhs = self.parse_additive()?;
+        let Some(op) = comparison_op(self.peek()) else {
+            return Ok(lhs);
+        };
+        self.advance();
+        let rhs = self.parse_additive()?;
+        if comparison_op(self.peek()).is_some() {
+            return Err(self.error("comparisons cannot be chained; use 'and'"));
+        }
+        Ok(binary(op, lhs, rhs))
+    }
+
+    fn parse_additive(&mut self) -> Result<Expr, ExprError> {
+        let mut lhs = self.parse_multiplicative()?;
+        loop {
+            let op = match self.peek() {
+                Token::Plus => BinOp::Add,
+                Token::Minus => BinOp::Sub,
+                _ => return Ok(lhs),
+            };
+            self.advance();
+            let rhs = self.parse_multiplicative()?;
+            lhs = binary(op, lhs, rhs);
+        }
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Expr, ExprError> {
+        let mut lhs = self.parse_unary()?;
+        loop {
+            let op = match self.peek() {
+                Token::Star => BinOp::Mul,
+                Token::Slash => BinOp::Div,
+                Token::Percent => BinOp::Mod,
+                _ => return Ok(lhs),
+            };
+            self.advance();
+            let rhs = self.parse_unary()?;
+            lhs = binary(op, lhs, rhs);
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, ExprError> {
+        if self.peek() == &Token::Minus {
+            self.advance();
+            return Ok(match self.parse_unary()? {
+                Expr::Number(n) => Expr::Number(-n),
+                e => Expr::Neg(Box::new(e)),
+            });
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, ExprError> {
+        let start = self.offset();
+        match self.advance() {
+            Token::Number(n) => Ok(Expr::Number(n)),
+            Token::LParen => {
+                let e = self.parse_or()?;
+                self.expect(Token::RParen)?;
+                Ok(e)
+            }
+            Token::Ident(name) if self.peek() == &Token::LParen => self.parse_call(start, name),
+            Token::Ident(name) => Ok(Expr::Series(self.parse_selector(start, name)?)),
+            Token::Duration(_) => Err(ExprError::new(
+                start,
+                "durations are only valid as a function window",
+            )),
+            other => Err(ExprError::new(
+                start,
+                format!(
+                    "expected a number, metric or function, found {}",
+                    other.describe()
+                ),
+            )),
+        }
+    }
+
+    fn parse_call(&mut self, start: usize, name: String) -> Result<Expr, ExprError> {
+        self.expect(Token::LParen)?;
+
+        if name == "abs" {
+            let arg = self.parse_or()?;
+            self.expect(Token::RParen)?;
+            return Ok(Expr::Abs(Box::new(arg)));
+        }
+
+        let agg = match name.as_str() {
+            "rate" => Aggregation::Rate,
+            "increase" => Aggregation::Increase,
+            other => other
+                .strip_suffix("_over")
+                .and_then(|a| a.parse::<Aggregation>().ok())
+                .ok_or_else(|| {
+                    ExprError::new(
+                        start,
+                        format!(
+                            "unknown function '{name}' (expected abs, rate, increase or <aggregation>_over)"
+                        ),
+                    )
+                })?,
+        };
+
+        let arg_start = self.offset();
+        let selector = match self.advance() {
+            Token::Ident(metric) if self.peek() != &Token::LParen => {
+                self.parse_selector(arg_start, metric)?
+            }
+            _ => {
+                return Err(ExprError::new(
+                    arg_start,
+                    format!("{name}() takes a metric selector as its first argument"),
+                ))
+            }
+        };
+
+        let window_ms = if self.peek() == &Token::Comma {
+            self.advance();
+            match self.advance() {
+                Token::Duration(ms) if ms > 0 => Some(ms),
+                _ => {
+                    return Err(ExprError::new(
+                        self.tokens[self.pos.saturating_sub(1)].0,
+                        "expected a positive window such as 30s or 5m",
+                    ))
+                }
+            }
+        } else {
+            None
+        };
+        self.expect(Token::RParen)?;
+
+        Ok(Expr::Reduce {
+            agg,
+            selector,
+            window_ms,
+        })
+    }
+
+    fn parse_selector(&mut self, start: usize, metric: String) -> Result<Selector, ExprError> {
+        let mut matchers = Vec::new();
+        if self.peek() == &Token::LBrace {
+            self.advance();
+            while self.peek() != &Token::RBrace {
+                matchers.push(self.parse_matcher()?);
+                if self.peek() == &Token::Comma {
+                    self.advance();
+                } else {
+                    break;
+                }
+            }
+            self.expect(Token::RBrace)?;
+        }
+        let compiled =
+            LabelSelector::compile(&matchers).map_err(|e| ExprError::new(start, e.to_string()))?;
+        Ok(Selector::new(metric, matchers, compiled))
+    }
+
+    fn parse_matcher(&mut self) -> Result<LabelMatcher, ExprError> {
+        let label = match self.advance() {
+            Token::Ident(l) => l,
+            other => {
+                return Err(ExprError::new(
+                    self.tokens[self.pos.saturating_sub(1)].0,
+                    format!("expected a label name, found {}", other.describe()),
+                ))
+            }
+        };
+        let op = match self.advance() {
+            Token::Assign => MatchOp::Equal,
+            Token::Ne => MatchOp::NotEqual,
+            Token::ReMatch => MatchOp::Regex,
+            Token::ReNotMatch => MatchOp::NotRegex,
+            other => {
+                return Err(ExprError::new(
+                    self.tokens[self.pos.saturating_sub(1)].0,
+                    format!("expected =, !=, =~ or !~, found {}", other.describe()),
+                ))
+            }
+        };
+        match self.advance() {
+            Token::Str(value) => Ok(LabelMatcher { label, op, value }),
+            other => Err(ExprError::new(
+                self.tokens[self.pos.saturating_sub(1)].0,
+                format!("expected a quoted label value, found {}", other.describe()),
+            )),
+        }
+    }
+}
+
+fn comparison_op(tok: &Token) -> Option<BinOp> {
+    match tok {
+        Token::Gt => Some(BinOp::Gt),
+        Token::Lt => Some(BinOp::Lt),
+        Token::Ge => Some(BinOp::Ge),
+        Token::Le => Some(BinOp::Le),
+        Token::EqEq => Some(BinOp::Eq),
+        Token::Ne => Some(BinOp::Ne),
+        _ => None,
+    }
+}
+
+fn binary(op: BinOp, lhs: Expr, rhs: Expr) -> Expr {
+    Expr::Binary {
+        op,
+        lhs: Box::new(lhs),
+        rhs: Box::new(rhs),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn canon(src: &str) -> String {
+        parse(src).unwrap().to_string()
+    }
+
+    #[test]
+    fn precedence_and_associativity() {
+        assert_eq!(
+            canon("mem.used_bytes / mem.total_bytes > 0.9"),
+            "((mem.used_bytes / mem.total_bytes) > 0.9)"
+        );
+        assert_eq!(canon("1 + 2 * 3 - 4"), "((1 + (2 * 3)) - 4)");
+        assert_eq!(
+            canon("a > 1 and b < 2 or c == 3"),
+            "(((a > 1) and (b < 2)) or (c == 3))"
+        );
+        assert_eq!(canon("-(a - -2)"), "-(a - -2)");
+    }
+
+    #[test]
+    fn functions_and_windows() {
+        assert_eq!(canon("rate(net.rx_bytes)"), "rate(net.rx_bytes)");
+        assert_eq!(canon("rate(net.rx_bytes, 300s)"), "rate(net.rx_bytes, 5m)");
+        assert_eq!(
+            canon("avg_over(cpu, 10m) > 90"),
+            "(avg_over(cpu, 10m) > 90)"
+        );
+        assert_eq!(
+            canon("p95_over(latency_ms, 1h)"),
+            "p95_over(latency_ms, 1h)"
+        );
+        assert_eq!(canon("abs(a - b)"), "abs((a - b))");
+
+        let expr = parse("avg_over(a, 5m) + max_over(b, 15m) + c").unwrap();
+        assert_eq!(expr.max_window_ms(), Some(900_000));
+        assert_eq!(expr.selectors().len(), 3);
+    }
+
+    #[test]
+    fn selectors_with_label_matchers() {
+        let expr = parse(r#"disk.available_bytes{mount="/", device=~"sd.*"} < 0.05 * disk.total_bytes{mount="/"}"#)
+            .unwrap();
+        let selectors = expr.selectors();
+        assert_eq!(selectors[0].matchers.len(), 2);
+        assert_eq!(selectors[0].matchers[1].op, MatchOp::Regex);
+
+        let labels: BTreeMap<String, String> = [("mount", "/"), ("device", "sda1")]
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        assert!(selectors[0].matches(&labels));
+        assert!(selectors[1].matches(&labels));
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_problem() {
+        let cases = [
+            ("", 0),
+            ("cpu >", 5),
+            ("cpu > > 1", 6),
+            ("(cpu > 1", 8),
+            ("cpu 1", 4),
+            ("a < b < c", 6),
+            ("median_over(cpu)", 0),
+            ("rate(1 + 2)", 5),
+            ("rate(cpu, 5)", 10),
+            ("cpu > 5m", 6),
+            (r#"cpu{mount=/}"#, 10),
+            (r#"cpu{device=~"("}"#, 0),
+        ];
+        for (src, pos) in cases {
+            let err = parse(src).unwrap_err();
+            assert_eq!(err.pos, pos, "{src}: {err}");
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_WINDOW_SECS: u64 = 3600;
+pub const DEFAULT_THRESHOLD: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlapPolicy {
+    pub window_secs: u64,
+    pub threshold: usize,
+}
+
+impl Default for FlapPolicy {
+    fn default() -> Self {
+        Self {
+            window_secs: DEFAULT_WINDOW_SECS,
+            threshold: DEFAULT_THRESHOLD,
+        }
+    }
+}
+
+impl FlapPolicy {
+    pub fn window_ms(&self) -> i64 {
+        self.window_secs.saturating_mul(1000) as i64
+    }
+
+    pub fn changes_within(&self, changes_ms: &[i64], now_ms: i64) -> usize {
+        let start = now_ms - self.window_ms();
+        changes_ms
+            .iter()
+            .filter(|t| **t > start && **t <= now_ms)
+            .count()
+    }
+
+    pub fn is_flapping(&self, changes_ms: &[i64], now_ms: i64) -> bool {
+        self.changes_within(changes_ms, now_ms) > self.threshold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_changes_inside_window() {
+        let policy = FlapPolicy {
+            window_secs: 60,
+            threshold: 2,
+        };
+        let changes = [0, 30_000, 50_000, 70_000];
+        assert_eq!(policy.changes_within(&changes, 70_000), 3);
+        assert!(policy.is_flapping(&changes, 70_000));
+        assert_eq!(policy.changes_within(&changes, 100_000), 2);
+        assert!(!policy.is_flapping(&changes, 100_000));
+    }
+}
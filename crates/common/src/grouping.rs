@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+
+pub const MAX_WAIT_SECS: u64 = 3600;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupPolicy {
+    #[serde(default = "default_group_by")]
+    pub group_by: Vec<String>,
+    #[serde(default = "default_group_wait_secs")]
+    pub group_wait_secs: u64,
+    #[serde(default = "default_group_interval_secs")]
+    pub group_interval_secs: u64,
+    #[serde(default = "default_repeat_interval_secs")]
+    pub repeat_interval_secs: u64,
+}
+
+impl Default for GroupPolicy {
+    fn default() -> Self {
+        Self {
+            group_by: default_group_by(),
+            group_wait_secs: default_group_wait_secs(),
+            group_interval_secs: default_group_interval_secs(),
+            repeat_interval_secs: default_repeat_interval_secs(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupingError(pub String);
+
+impl std::fmt::Display for GroupingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "grouping: {}", self.0)
+    }
+}
+
+impl std::error::Error for GroupingError {}
+
+impl GroupPolicy {
+    pub fn from_config(config: &serde_json::Value) -> Result<Option<Self>, GroupingError> {
+        match config.get("grouping") {
+            None | Some(serde_json::Value::Null) => Ok(None),
+            Some(v) => {
+                let policy: Self =
+                    serde_json::from_value(v.clone()).map_err(|e| GroupingError(e.to_string()))?;
+                policy.validate()?;
+                Ok(Some(policy))
+            }
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), GroupingError> {
+        if let Some(key) = self.group_by.iter().find(|k| k.trim().is_empty()) {
+            return Err(GroupingError(format!("invalid group_by key '{key}'")));
+        }
+        if self.group_wait_secs > MAX_WAIT_SECS {
+            return Err(GroupingError(format!(
+                "group_wait_secs must be at most {MAX_WAIT_SECS}"
+            )));
+        }
+        if self.group_interval_secs == 0 {
+            return Err(GroupingError("group_interval_secs must be positive".into()));
+        }
+        if self.repeat_interval_secs != 0 && self.repeat_interval_secs < self.group_interval_secs {
+            return Err(GroupingError(
+                "repeat_interval_secs must be 0 (off) or at least group_interval_secs".into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn default_group_by() -> Vec<String> {
+    vec!["rule_id".into()]
+}
+
+fn default_group_wait_secs() -> u64 {
+    30
+}
+
+fn default_group_interval_secs() -> u64 {
+    300
+}
+
+fn default_repeat_interval_secs() -> u64 {
+    4 * 3600
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_config_fills_defaults() {
+        let cfg = serde_json::json!({ "grouping": { "group_by": ["rule_id", "mount"] } });
+        let p = GroupPolicy::from_config(&cfg).unwrap().unwrap();
+        assert_eq!(p.group_by, vec!["rule_id", "mount"]);
+        assert_eq!(p.group_wait_secs, 30);
+        assert_eq!(p.repeat_interval_secs, 14_400);
+
+        assert_eq!(
+            GroupPolicy::from_config(&serde_json::json!({})).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn validate_rejects_bad_intervals() {
+        let cfg = serde_json::json!({ "grouping": { "group_interval_secs": 0 } });
+        assert!(GroupPolicy::from_config(&cfg).is_err());
+
+        let cfg = serde_json::json!({
+            "grouping": { "group_interval_secs": 600, "repeat_interval_secs": 60 }
+        });
+        assert!(GroupPolicy::from_config(&cfg).is_err());
+
+        let cfg = serde_json::json!({ "grouping": { "repeat_interval_secs": 0 } });
+        assert!(GroupPolicy::from_config(&cfg).is_ok());
+
+        let cfg = serde_json::json!({ "grouping": { "group_wait_secs": "soon" } });
+        assert!(GroupPolicy::from_config(&cfg).is_err());
+    }
+}
@@ -0,0 +1,86 @@
+use aes_gcm::aead::{Aead, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+pub const KEY_ROTATION_CAPABILITY: &str = "key_rotation";
+
+const NONCE_LEN: usize = 12;
+
+fn cipher(current_secret: &[u8]) -> Aes256Gcm {
+    let mut hasher = Sha256::new();
+    hasher.update(b"sentinel-key-rotation:");
+    hasher.update(current_secret);
+    Aes256Gcm::new_from_slice(&hasher.finalize()).expect("sha256 output is a valid AES-256 key")
+}
+
+fn aad(agent_id: &str, key_id: &str) -> Vec<u8> {
+    format!("{agent_id}:{key_id}").into_bytes()
+}
+
+pub fn seal_secret(
+    current_secret: &[u8],
+    agent_id: &str,
+    key_id: &str,
+    new_secret: &[u8],
+) -> String {
+    let mut nonce = [0u8; NONCE_LEN];
+    OsRng.fill_bytes(&mut nonce);
+    let aad = aad(agent_id, key_id);
+    let ciphertext = cipher(current_secret)
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: new_secret,
+                aad: &aad,
+            },
+        )
+        .expect("AES-GCM encryption cannot fail for in-memory buffers");
+
+    let mut out = nonce.to_vec();
+    out.extend(ciphertext);
+    STANDARD.encode(out)
+}
+
+pub fn open_secret(
+    current_secret: &[u8],
+    agent_id: &str,
+    key_id: &str,
+    sealed: &str,
+) -> Option<Vec<u8>> {
+    let raw = STANDARD.decode(sealed).ok()?;
+    if raw.len() <= NONCE_LEN {
+        return None;
+    }
+    let (nonce, ciphertext) = raw.split_at(NONCE_LEN);
+    let aad = aad(agent_id, key_id);
+    cipher(current_secret)
+        .decrypt(
+            Nonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad: &aad,
+            },
+        )
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sealed_secret_opens_only_with_matching_key_and_binding() {
+        let sealed = seal_secret(b"old", "agent-1", "key-2", b"new-secret");
+        assert_eq!(
+            open_secret(b"old", "agent-1", "key-2", &sealed),
+            Some(b"new-secret".to_vec())
+        );
+        assert_eq!(open_secret(b"wrong", "agent-1", "key-2", &sealed), None);
+        assert_eq!(open_secret(b"old", "agent-2", "key-2", &sealed), None);
+        assert_eq!(open_secret(b"old", "agent-1", "key-3", &sealed), None);
+        assert_eq!(open_secret(b"old", "agent-1", "key-2", "not base64!"), None);
+    }
+}
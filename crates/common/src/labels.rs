@@ -0,0 +1,239 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MatchOp {
+    #[serde(rename = "=")]
+    Equal,
+    #[serde(rename = "!=")]
+    NotEqual,
+    #[serde(rename = "=~")]
+    Regex,
+    #[serde(rename = "!~")]
+    NotRegex,
+}
+
+impl MatchOp {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Equal => "=",
+            Self::NotEqual => "!=",
+            Self::Regex => "=~",
+            Self::NotRegex => "!~",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LabelMatcher {
+    pub label: String,
+    pub op: MatchOp,
+    pub value: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum MatcherError {
+    Syntax(String),
+    InvalidRegex { label: String, reason: String },
+}
+
+impl std::fmt::Display for MatcherError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Syntax(s) => write!(f, "invalid label matcher: {s}"),
+            Self::InvalidRegex { label, reason } => {
+                write!(f, "invalid regex for label '{label}': {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MatcherError {}
+
+impl LabelMatcher {
+    pub fn parse(s: &str) -> Result<Self, MatcherError> {
+        let s = s.trim();
+        let (pos, op) = ["!=", "=~", "!~", "="]
+            .iter()
+            .filter_map(|tok| s.find(tok).map(|p| (p, *tok)))
+            .min_by_key(|(p, tok)| (*p, std::cmp::Reverse(tok.len())))
+            .ok_or_else(|| MatcherError::Syntax(s.to_string()))?;
+
+        let label = s[..pos].trim();
+        if label.is_empty() {
+            return Err(MatcherError::Syntax(s.to_string()));
+        }
+        let raw = s[pos + op.len()..].trim();
+        let value = raw
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .unwrap_or(raw);
+
+        let op = match op {
+            "=" => MatchOp::Equal,
+            "!=" => MatchOp::NotEqual,
+            "=~" => MatchOp::Regex,
+            _ => MatchOp::NotRegex,
+        };
+
+        let matcher = Self {
+            label: label.to_string(),
+            op,
+            value: value.to_string(),
+        };
+        matcher.compile()?;
+        Ok(matcher)
+    }
+
+    pub fn compile(&self) -> Result<CompiledMatcher, MatcherError> {
+        let regex = match self.op {
+            MatchOp::Regex | MatchOp::NotRegex => {
+                Some(Regex::new(&format!("^(?:{})$", self.value)).map_err(|e| {
+                    MatcherError::InvalidRegex {
+                        label: self.label.clone(),
+                        reason: e.to_string(),
+                    }
+                })?)
+            }
+            MatchOp::Equal | MatchOp::NotEqual => None,
+        };
+        Ok(CompiledMatcher {
+            matcher: self.clone(),
+            regex,
+        })
+    }
+}
+
+impl std::fmt::Display for LabelMatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}\"{}\"", self.label, self.op.as_str(), self.value)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CompiledMatcher {
+    matcher: LabelMatcher,
+    regex: Option<Regex>,
+}
+
+impl CompiledMatcher {
+    pub fn matches(&self, value: &str) -> bool {
+        match (self.matcher.op, &self.regex) {
+            (MatchOp::Equal, _) => value == self.matcher.value,
+            (MatchOp::NotEqual, _) => value != self.matcher.value,
+            (MatchOp::Regex, Some(re)) => re.is_match(value),
+            (MatchOp::NotRegex, Some(re)) => !re.is_match(value),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LabelSelector {
+    matchers: Vec<CompiledMatcher>,
+}
+
+impl LabelSelector {
+    pub fn compile(matchers: &[LabelMatcher]) -> Result<Self, MatcherError> {
+        let matchers = matchers
+            .iter()
+            .map(LabelMatcher::compile)
+            .collect::<Result<_, _>>()?;
+        Ok(Self { matchers })
+    }
+
+    pub fn matches(&self, labels: &BTreeMap<String, String>) -> bool {
+        self.matchers.iter().all(|m| {
+            let value = labels.get(&m.matcher.label).map(String::as_str);
+            m.matches(value.unwrap_or(""))
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.matchers.is_empty()
+    }
+}
+
+pub fn validate(matchers: &[LabelMatcher]) -> Result<(), MatcherError> {
+    LabelSelector::compile(matchers).map(|_| ())
+}
+
+pub fn sorted(labels: &HashMap<String, String>) -> BTreeMap<String, String> {
+    labels.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parse_operators() {
+        assert_eq!(LabelMatcher::parse("mount=/").unwrap().op, MatchOp::Equal);
+        assert_eq!(
+            LabelMatcher::parse("mount!=/boot").unwrap().op,
+            MatchOp::NotEqual
+        );
+        let re = LabelMatcher::parse(r#"device=~"sd.*""#).unwrap();
+        assert_eq!(re.op, MatchOp::Regex);
+        assert_eq!(re.value, "sd.*");
+        assert_eq!(
+            LabelMatcher::parse("iface!~lo|docker.*").unwrap().op,
+            MatchOp::NotRegex
+        );
+    }
+
+    #[test]
+    fn parse_rejects_garbage() {
+        assert!(LabelMatcher::parse("mount").is_err());
+        assert!(LabelMatcher::parse("=/").is_err());
+        assert!(matches!(
+            LabelMatcher::parse("device=~(sd"),
+            Err(MatcherError::InvalidRegex { .. })
+        ));
+    }
+
+    #[test]
+    fn selector_matches_all() {
+        let sel = LabelSelector::compile(&[
+            LabelMatcher::parse("mount!=/boot").unwrap(),
+            LabelMatcher::parse("device=~sd.*").unwrap(),
+        ])
+        .unwrap();
+
+        assert!(sel.matches(&labels(&[("mount", "/"), ("device", "sda1")])));
+        assert!(!sel.matches(&labels(&[("mount", "/boot"), ("device", "sda1")])));
+        assert!(!sel.matches(&labels(&[("mount", "/"), ("device", "nvme0n1")])));
+    }
+
+    #[test]
+    fn regex_is_anchored() {
+        let sel = LabelSelector::compile(&[LabelMatcher::parse("iface=~eth").unwrap()]).unwrap();
+        assert!(sel.matches(&labels(&[("iface", "eth")])));
+        assert!(!sel.matches(&labels(&[("iface", "veth0")])));
+    }
+
+    #[test]
+    fn missing_label_is_empty_string() {
+        let eq = LabelSelector::compile(&[LabelMatcher::parse("mount=/").unwrap()]).unwrap();
+        let ne = LabelSelector::compile(&[LabelMatcher::parse("mount!=/").unwrap()]).unwrap();
+        assert!(!eq.matches(&labels(&[])));
+        assert!(ne.matches(&labels(&[])));
+    }
+
+    #[test]
+    fn serde_uses_operator_symbols() {
+        let m = LabelMatcher::parse("mount=~/data.*").unwrap();
+        let json = serde_json::to_string(&m).unwrap();
+        assert!(json.contains(r#""op":"=~""#));
+        let back: LabelMatcher = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, m);
+    }
+}
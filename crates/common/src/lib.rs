@@ -2,14 +2,29 @@ pub mod proto {
     include!(concat!(env!("OUT_DIR"), "/sentinel.common.rs"));
 }
 
+pub mod aggregation;
 pub mod batch_id;
+pub mod batch_json;
+pub mod batch_reject;
 pub mod canonicalize;
 pub mod crypto;
+pub mod envelope;
+pub mod escalation;
+pub mod expr;
+pub mod flapping;
+pub mod grouping;
+pub mod key_rotation;
+pub mod labels;
 pub mod logging;
 pub mod metric_json;
 pub mod nats_config;
 pub mod pool_config;
+pub mod presence;
 pub mod redact;
 pub mod retry;
 pub mod seq;
+pub mod silence;
+pub mod template;
 pub mod trace_id;
+pub mod wal_drops;
+pub mod webhook;
@@ -2,11 +2,17 @@ pub const STREAM_NAME: &str = "SENTINEL_METRICS";
 pub const SUBJECT: &str = "sentinel.metrics.>";
 pub const SUBJECT_PREFIX: &str = "sentinel.metrics";
 pub const CONSUMER_NAME: &str = "sentinel-workers";
+pub const PRESENCE_SUBJECT: &str = "sentinel.presence.>";
+pub const PRESENCE_SUBJECT_PREFIX: &str = "sentinel.presence";
 
 pub fn subject_for_agent(agent_id: &str) -> String {
     format!("{SUBJECT_PREFIX}.{agent_id}")
 }
 
+pub fn presence_subject(agent_id: &str) -> String {
+    format!("{PRESENCE_SUBJECT_PREFIX}.{agent_id}")
+}
+
 #[derive(Debug, Clone)]
 pub struct StreamConfig {
     pub name: String,
@@ -53,6 +59,12 @@ mod tests {
         assert_eq!(subject_for_agent("agent-abc"), "sentinel.metrics.agent-abc");
     }
 
+    #[test]
+    fn presence_subject_is_outside_the_metrics_stream() {
+        assert_eq!(presence_subject("agent-abc"), "sentinel.presence.agent-abc");
+        assert!(!presence_subject("agent-abc").starts_with(SUBJECT_PREFIX));
+    }
+
     #[test]
     fn default_stream_config() {
         let cfg = StreamConfig::default();
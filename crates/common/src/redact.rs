@@ -1,5 +1,25 @@
 use std::fmt;
 
+use serde_json::Value;
+
+pub const SECRET_FIELDS: &[&str] = &[
+    "webhook_url",
+    "url",
+    "secret",
+    "password",
+    "bot_token",
+    "routing_key",
+    "api_key",
+    "token",
+    "access_token",
+    "user_key",
+    "secret_access_key",
+    "session_token",
+    "bearer_token",
+    "client_key",
+    "headers",
+];
+
 const MIN_REVEAL_LEN: usize = 8;
 const PREFIX_LEN: usize = 4;
 const SUFFIX_LEN: usize = 4;
@@ -28,6 +48,85 @@ pub fn mask_url(url: &str) -> String {
     url.to_string()
 }
 
+pub fn mask_url_path(url: &str) -> String {
+    let Some(scheme_end) = url.find("://") else {
+        return mask_token(url);
+    };
+    let host_start = scheme_end + 3;
+    let host_end = url[host_start..]
+        .find(['/', '?', '#'])
+        .map_or(url.len(), |i| host_start + i);
+    let host = &url[host_start..host_end];
+    let host = host.rsplit_once('@').map_or(host, |(_, h)| h);
+    format!("{}{}/***", &url[..host_start], host)
+}
+
+fn mask_field(field: &str, value: &str) -> String {
+    if field.ends_with("url") {
+        mask_url_path(value)
+    } else {
+        mask_token(value)
+    }
+}
+
+pub(crate) fn secret_values_mut(config: &mut Value) -> Vec<(&'static str, &mut String)> {
+    let mut out = Vec::new();
+    let Some(obj) = config.as_object_mut() else {
+        return out;
+    };
+    for (key, value) in obj.iter_mut() {
+        let Some(field) = SECRET_FIELDS.iter().find(|f| **f == key) else {
+            continue;
+        };
+        match value {
+            Value::String(s) => out.push((*field, s)),
+            Value::Object(map) => out.extend(map.values_mut().filter_map(|v| match v {
+                Value::String(s) => Some((*field, s)),
+                _ => None,
+            })),
+            _ => {}
+        }
+    }
+    out
+}
+
+pub fn redact_config(config: &Value) -> Value {
+    let mut out = config.clone();
+    for (field, s) in secret_values_mut(&mut out) {
+        *s = mask_field(field, s);
+    }
+    out
+}
+
+pub fn restore_redacted(config: &mut Value, existing: &Value) {
+    let Some(obj) = config.as_object_mut() else {
+        return;
+    };
+    for field in SECRET_FIELDS {
+        match (obj.get_mut(*field), existing.get(*field)) {
+            (Some(Value::String(s)), Some(Value::String(original))) => {
+                restore_value(field, s, original);
+            }
+            (Some(Value::Object(map)), Some(Value::Object(originals))) => {
+                for (name, value) in map.iter_mut() {
+                    if let (Value::String(s), Some(Value::String(original))) =
+                        (value, originals.get(name))
+                    {
+                        restore_value(field, s, original);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn restore_value(field: &str, value: &mut String, original: &str) {
+    if *value == mask_field(field, original) {
+        *value = original.to_string();
+    }
+}
+
 pub struct RedactedSecret<'a>(pub &'a str);
 
 impl fmt::Display for RedactedSecret<'_> {
@@ -99,6 +198,60 @@ mod tests {
         assert_eq!(mask_url("http://localhost:8080"), "http://localhost:8080");
     }
 
+    #[test]
+    fn mask_url_path_keeps_host() {
+        assert_eq!(
+            mask_url_path("https://hooks.slack.com/services/T000/B000/XXXX"),
+            "https://hooks.slack.com/***"
+        );
+        assert_eq!(
+            mask_url_path("https://user:pw@example.com?token=x"),
+            "https://example.com/***"
+        );
+    }
+
+    #[test]
+    fn redact_and_restore_config() {
+        let config = serde_json::json!({
+            "webhook_url": "https://hooks.slack.com/services/T000/B000/XXXX",
+            "bot_token": "123456:ABCDEFGHIJ",
+            "chat_id": "42",
+        });
+        let redacted = redact_config(&config);
+        assert_eq!(redacted["webhook_url"], "https://hooks.slack.com/***");
+        assert_eq!(redacted["bot_token"], "1234***GHIJ");
+        assert_eq!(redacted["chat_id"], "42");
+
+        let mut update = redacted.clone();
+        update["chat_id"] = "43".into();
+        restore_redacted(&mut update, &config);
+        assert_eq!(update["webhook_url"], config["webhook_url"]);
+        assert_eq!(update["bot_token"], config["bot_token"]);
+        assert_eq!(update["chat_id"], "43");
+
+        let mut replaced = redacted;
+        replaced["bot_token"] = "999999:NEWTOKEN00".into();
+        restore_redacted(&mut replaced, &config);
+        assert_eq!(replaced["bot_token"], "999999:NEWTOKEN00");
+    }
+
+    #[test]
+    fn redact_and_restore_header_values() {
+        let config = serde_json::json!({
+            "url": "https://hooks.example.com/in",
+            "headers": { "X-Api-Key": "abcdef0123456789", "X-Team": "ops" },
+        });
+        let redacted = redact_config(&config);
+        assert_eq!(redacted["headers"]["X-Api-Key"], "abcd***6789");
+        assert_eq!(redacted["headers"]["X-Team"], "***");
+
+        let mut update = redacted;
+        update["headers"]["X-Team"] = "sre".into();
+        restore_redacted(&mut update, &config);
+        assert_eq!(update["headers"]["X-Api-Key"], "abcdef0123456789");
+        assert_eq!(update["headers"]["X-Team"], "sre");
+    }
+
     #[test]
     fn redacted_secret_display() {
         let s = RedactedSecret("super-secret-token-12345");
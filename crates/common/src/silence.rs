@@ -0,0 +1,336 @@
+use chrono::{Datelike, NaiveTime, TimeZone, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::labels::{LabelMatcher, LabelSelector, MatcherError};
+
+const MAX_WINDOW_MINS: u32 = 7 * 24 * 60;
+const SEVERITIES: &[&str] = &["info", "warning", "critical"];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recurrence {
+    pub days: Vec<String>,
+    pub start: String,
+    pub duration_mins: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Silence {
+    pub id: String,
+    #[serde(default)]
+    pub agent_pattern: Option<String>,
+    #[serde(default)]
+    pub rule_id: Option<String>,
+    #[serde(default)]
+    pub severity: Option<String>,
+    #[serde(default)]
+    pub label_matchers: Vec<LabelMatcher>,
+    pub starts_at_ms: i64,
+    #[serde(default)]
+    pub ends_at_ms: Option<i64>,
+    #[serde(default)]
+    pub recurrence: Option<Recurrence>,
+    #[serde(default)]
+    pub comment: String,
+    #[serde(default)]
+    pub created_by: String,
+}
+
+pub struct SilenceTarget<'a> {
+    pub agent_id: &'a str,
+    pub rule_id: &'a str,
+    pub severity: &'a str,
+    pub labels: &'a BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SilenceState {
+    Pending,
+    Active,
+    Expired,
+}
+
+impl SilenceState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Active => "active",
+            Self::Expired => "expired",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SilenceError {
+    NoMatchers,
+    MissingEnd,
+    InvalidRange,
+    InvalidSeverity(String),
+    InvalidDay(String),
+    InvalidStart(String),
+    InvalidDuration(u32),
+    Matcher(MatcherError),
+}
+
+impl std::fmt::Display for SilenceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoMatchers => write!(
+                f,
+                "silence needs at least one of agent_pattern, rule_id, severity or label_matchers"
+            ),
+            Self::MissingEnd => write!(f, "one-off silence needs ends_at_ms"),
+            Self::InvalidRange => write!(f, "ends_at_ms must be after starts_at_ms"),
+            Self::InvalidSeverity(s) => write!(f, "unknown severity '{s}'"),
+            Self::InvalidDay(d) => write!(f, "unknown weekday '{d}'"),
+            Self::InvalidStart(s) => write!(f, "invalid start time '{s}', expected HH:MM"),
+            Self::InvalidDuration(m) => {
+                write!(
+                    f,
+                    "window duration must be 1..={MAX_WINDOW_MINS} minutes, got {m}"
+                )
+            }
+            Self::Matcher(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for SilenceError {}
+
+impl Silence {
+    pub fn validate(&self) -> Result<(), SilenceError> {
+        if self.agent_pattern.is_none()
+            && self.rule_id.is_none()
+            && self.severity.is_none()
+            && self.label_matchers.is_empty()
+        {
+            return Err(SilenceError::NoMatchers);
+        }
+        if let Some(ref s) = self.severity {
+            if !SEVERITIES.contains(&s.as_str()) {
+                return Err(SilenceError::InvalidSeverity(s.clone()));
+            }
+        }
+        crate::labels::validate(&self.label_matchers).map_err(SilenceError::Matcher)?;
+
+        match (self.ends_at_ms, &self.recurrence) {
+            (None, None) => return Err(SilenceError::MissingEnd),
+            (Some(end), _) if end <= self.starts_at_ms => return Err(SilenceError::InvalidRange),
+            _ => {}
+        }
+        if let Some(ref r) = self.recurrence {
+            r.validate()?;
+        }
+        Ok(())
+    }
+
+    pub fn state_at(&self, now_ms: i64) -> SilenceState {
+        if self.ends_at_ms.is_some_and(|end| now_ms >= end) {
+            return SilenceState::Expired;
+        }
+        if now_ms < self.starts_at_ms {
+            return SilenceState::Pending;
+        }
+        match self.recurrence {
+            Some(ref r) if !r.covers(now_ms) => SilenceState::Pending,
+            _ => SilenceState::Active,
+        }
+    }
+
+    pub fn is_active(&self, now_ms: i64) -> bool {
+        self.state_at(now_ms) == SilenceState::Active
+    }
+
+    pub fn matches(&self, target: &SilenceTarget<'_>) -> bool {
+        if let Some(ref pattern) = self.agent_pattern {
+            if !agent_matches(pattern, target.agent_id) {
+                return false;
+            }
+        }
+        if self.rule_id.as_deref().is_some_and(|r| r != target.rule_id) {
+            return false;
+        }
+        if self
+            .severity
+            .as_deref()
+            .is_some_and(|s| s != target.severity)
+        {
+            return false;
+        }
+        match LabelSelector::compile(&self.label_matchers) {
+            Ok(selector) => selector.matches(target.labels),
+            Err(_) => false,
+        }
+    }
+}
+
+impl Recurrence {
+    pub fn validate(&self) -> Result<(), SilenceError> {
+        if self.days.is_empty() {
+            return Err(SilenceError::InvalidDay(String::new()));
+        }
+        for d in &self.days {
+            parse_day(d)?;
+        }
+        parse_start(&self.start)?;
+        if self.duration_mins == 0 || self.duration_mins > MAX_WINDOW_MINS {
+            return Err(SilenceError::InvalidDuration(self.duration_mins));
+        }
+        Ok(())
+    }
+
+    pub fn covers(&self, now_ms: i64) -> bool {
+        let (Ok(start), Some(now)) = (
+            parse_start(&self.start),
+            Utc.timestamp_millis_opt(now_ms).single(),
+        ) else {
+            return false;
+        };
+        let days: Vec<Weekday> = self.days.iter().filter_map(|d| parse_day(d).ok()).collect();
+        let len_ms = i64::from(self.duration_mins) * 60_000;
+
+        (0..=7).any(|back| {
+            let date = now.date_naive() - chrono::Duration::days(back);
+            if !days.contains(&date.weekday()) {
+                return false;
+            }
+            let begin = date.and_time(start).and_utc().timestamp_millis();
+            begin <= now_ms && now_ms < begin + len_ms
+        })
+    }
+}
+
+fn parse_day(d: &str) -> Result<Weekday, SilenceError> {
+    d.trim()
+        .parse::<Weekday>()
+        .map_err(|_| SilenceError::InvalidDay(d.to_string()))
+}
+
+fn parse_start(s: &str) -> Result<NaiveTime, SilenceError> {
+    NaiveTime::parse_from_str(s.trim(), "%H:%M").map_err(|_| SilenceError::InvalidStart(s.into()))
+}
+
+pub(crate) fn agent_matches(pattern: &str, agent_id: &str) -> bool {
+    if pattern == "*" {
+        return true;
+    }
+    if let Some(prefix) = pattern.strip_suffix('*') {
+        return agent_id.starts_with(prefix);
+    }
+    pattern == agent_id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::labels::MatchOp;
+
+    const MON_2024_01_01: i64 = 1_704_067_200_000;
+    const HOUR: i64 = 3_600_000;
+
+    fn silence() -> Silence {
+        Silence {
+            id: "s1".into(),
+            agent_pattern: Some("web-*".into()),
+            rule_id: None,
+            severity: None,
+            label_matchers: Vec::new(),
+            starts_at_ms: 0,
+            ends_at_ms: Some(10 * HOUR),
+            recurrence: None,
+            comment: String::new(),
+            created_by: "ops".into(),
+        }
+    }
+
+    fn target<'a>(agent_id: &'a str, labels: &'a BTreeMap<String, String>) -> SilenceTarget<'a> {
+        SilenceTarget {
+            agent_id,
+            rule_id: "r1",
+            severity: "critical",
+            labels,
+        }
+    }
+
+    #[test]
+    fn matches_agent_pattern_labels_and_severity() {
+        let mut labels = BTreeMap::new();
+        labels.insert("mount".to_string(), "/data".to_string());
+
+        let mut s = silence();
+        assert!(s.matches(&target("web-1", &labels)));
+        assert!(!s.matches(&target("db-1", &labels)));
+
+        s.label_matchers.push(LabelMatcher {
+            label: "mount".into(),
+            op: MatchOp::Equal,
+            value: "/boot".into(),
+        });
+        assert!(!s.matches(&target("web-1", &labels)));
+
+        s.label_matchers.clear();
+        s.severity = Some("warning".into());
+        assert!(!s.matches(&target("web-1", &labels)));
+    }
+
+    #[test]
+    fn one_off_window_states() {
+        let s = Silence {
+            starts_at_ms: HOUR,
+            ..silence()
+        };
+        assert_eq!(s.state_at(0), SilenceState::Pending);
+        assert_eq!(s.state_at(2 * HOUR), SilenceState::Active);
+        assert_eq!(s.state_at(10 * HOUR), SilenceState::Expired);
+    }
+
+    #[test]
+    fn weekly_window_spans_midnight() {
+        let s = Silence {
+            ends_at_ms: None,
+            recurrence: Some(Recurrence {
+                days: vec!["sun".into()],
+                start: "23:00".into(),
+                duration_mins: 120,
+            }),
+            ..silence()
+        };
+        assert!(s.validate().is_ok());
+        assert!(s.is_active(MON_2024_01_01 - HOUR / 2));
+        assert!(s.is_active(MON_2024_01_01 + HOUR / 2));
+        assert!(!s.is_active(MON_2024_01_01 + 2 * HOUR));
+        assert!(!s.is_active(MON_2024_01_01 - 2 * HOUR));
+    }
+
+    #[test]
+    fn validate_rejects_bad_silences() {
+        let mut s = silence();
+        s.agent_pattern = None;
+        assert_eq!(s.validate(), Err(SilenceError::NoMatchers));
+
+        let s = Silence {
+            ends_at_ms: None,
+            ..silence()
+        };
+        assert_eq!(s.validate(), Err(SilenceError::MissingEnd));
+
+        let s = Silence {
+            severity: Some("page".into()),
+            ..silence()
+        };
+        assert!(matches!(
+            s.validate(),
+            Err(SilenceError::InvalidSeverity(_))
+        ));
+
+        let s = Silence {
+            recurrence: Some(Recurrence {
+                days: vec!["funday".into()],
+                start: "02:00".into(),
+                duration_mins: 60,
+            }),
+            ..silence()
+        };
+        assert!(matches!(s.validate(), Err(SilenceError::InvalidDay(_))));
+    }
+}
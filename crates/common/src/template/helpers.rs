@@ -0,0 +1,151 @@
+use chrono::{TimeZone, Utc};
+
+use super::parser::Arg;
+use super::TemplateError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Helper {
+    Upper,
+    Lower,
+    Trim,
+    Default(String),
+    Round(usize),
+    Duration,
+    Bytes,
+    Datetime,
+    Truncate(usize),
+    Json,
+}
+
+impl Helper {
+    pub(crate) fn parse(name: &str, args: &[Arg], pos: usize) -> Result<Self, TemplateError> {
+        let helper = match (name, args) {
+            ("upper", []) => Self::Upper,
+            ("lower", []) => Self::Lower,
+            ("trim", []) => Self::Trim,
+            ("default", [Arg::Str(s)]) => Self::Default(s.clone()),
+            ("round", []) => Self::Round(2),
+            ("round", [Arg::Num(n)]) if *n >= 0.0 => Self::Round(*n as usize),
+            ("duration", []) => Self::Duration,
+            ("bytes", []) => Self::Bytes,
+            ("datetime", []) => Self::Datetime,
+            ("truncate", [Arg::Num(n)]) if *n >= 1.0 => Self::Truncate(*n as usize),
+            ("json", []) => Self::Json,
+            (
+                "upper" | "lower" | "trim" | "default" | "round" | "duration" | "bytes"
+                | "datetime" | "truncate" | "json",
+                _,
+            ) => {
+                return Err(TemplateError::new(
+                    pos,
+                    format!("wrong arguments for helper '{name}'"),
+                ))
+            }
+            _ => return Err(TemplateError::new(pos, format!("unknown helper '{name}'"))),
+        };
+        Ok(helper)
+    }
+
+    pub(crate) fn apply(&self, v: serde_json::Value) -> serde_json::Value {
+        use serde_json::Value;
+
+        match self {
+            Self::Upper => super::stringify(&v).to_uppercase().into(),
+            Self::Lower => super::stringify(&v).to_lowercase().into(),
+            Self::Trim => super::stringify(&v).trim().into(),
+            Self::Default(d) => match v {
+                Value::Null => d.clone().into(),
+                Value::String(ref s) if s.is_empty() => d.clone().into(),
+                other => other,
+            },
+            Self::Round(places) => match as_f64(&v) {
+                Some(f) => format!("{f:.places$}").into(),
+                None => v,
+            },
+            Self::Duration => match as_f64(&v) {
+                Some(ms) => humanize_duration_ms(ms as i64).into(),
+                None => v,
+            },
+            Self::Bytes => match as_f64(&v) {
+                Some(b) => humanize_bytes(b).into(),
+                None => v,
+            },
+            Self::Datetime => {
+                match as_f64(&v).and_then(|ms| Utc.timestamp_millis_opt(ms as i64).single()) {
+                    Some(t) => t.format("%Y-%m-%d %H:%M:%S UTC").to_string().into(),
+                    None => v,
+                }
+            }
+            Self::Truncate(len) => {
+                let s = super::stringify(&v);
+                if s.chars().count() <= *len {
+                    s.into()
+                } else {
+                    format!("{}…", s.chars().take(*len).collect::<String>()).into()
+                }
+            }
+            Self::Json => serde_json::to_string(&v).unwrap_or_default().into(),
+        }
+    }
+}
+
+fn as_f64(v: &serde_json::Value) -> Option<f64> {
+    match v {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+pub fn humanize_duration_ms(ms: i64) -> String {
+    let secs = ms.max(0) / 1000;
+    let (d, h, m, s) = (
+        secs / 86400,
+        secs % 86400 / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+    );
+    let parts: Vec<String> = [(d, "d"), (h, "h"), (m, "m"), (s, "s")]
+        .into_iter()
+        .filter(|(n, _)| *n > 0)
+        .take(2)
+        .map(|(n, unit)| format!("{n}{unit}"))
+        .collect();
+    if parts.is_empty() {
+        "0s".into()
+    } else {
+        parts.join(" ")
+    }
+}
+
+pub fn humanize_bytes(bytes: f64) -> String {
+    const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+    let mut value = bytes;
+    let mut unit = 0;
+    while value.abs() >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{value:.0} B")
+    } else {
+        let s = format!("{value:.1}");
+        format!("{} {}", s.trim_end_matches(".0"), UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn humanizes_durations_and_bytes() {
+        assert_eq!(humanize_duration_ms(0), "0s");
+        assert_eq!(humanize_duration_ms(45_000), "45s");
+        assert_eq!(humanize_duration_ms(3_725_000), "1h 2m");
+        assert_eq!(humanize_duration_ms(90_061_000), "1d 1h");
+        assert_eq!(humanize_bytes(512.0), "512 B");
+        assert_eq!(humanize_bytes(2048.0), "2 KiB");
+        assert_eq!(humanize_bytes(1_610_612_736.0), "1.5 GiB");
+    }
+}
@@ -0,0 +1,430 @@
+mod helpers;
+mod parser;
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+pub use helpers::{humanize_bytes, humanize_duration_ms};
+
+use parser::{Node, Operand};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateError {
+    pub field: &'static str,
+    pub pos: usize,
+    pub message: String,
+}
+
+impl TemplateError {
+    pub(crate) fn new(pos: usize, message: impl Into<String>) -> Self {
+        Self {
+            field: "",
+            pos,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.field.is_empty() {
+            write!(f, "template.{} ", self.field)?;
+        }
+        write!(f, "at column {}: {}", self.pos + 1, self.message)
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+#[derive(Debug, Clone)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+impl Template {
+    pub fn parse(source: &str) -> Result<Self, TemplateError> {
+        Ok(Self {
+            nodes: parser::parse(source)?,
+        })
+    }
+
+    pub fn render(&self, ctx: &serde_json::Value) -> String {
+        let mut out = String::new();
+        render_nodes(&self.nodes, ctx, &mut Vec::new(), &mut out);
+        out
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MessageTemplate {
+    title: Option<Template>,
+    body: Option<Template>,
+    group_title: Option<Template>,
+    group_body: Option<Template>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Rendered {
+    pub title: Option<String>,
+    pub body: Option<String>,
+}
+
+impl MessageTemplate {
+    pub fn from_config(config: &serde_json::Value) -> Result<Self, TemplateError> {
+        let section = &config["template"];
+        Ok(Self {
+            title: parse_field(section, "title")?,
+            body: parse_field(section, "body")?,
+            group_title: parse_field(section, "group_title")?,
+            group_body: parse_field(section, "group_body")?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.body.is_none()
+    }
+
+    pub fn has_group(&self) -> bool {
+        self.group_title.is_some() || self.group_body.is_some()
+    }
+
+    pub fn render(&self, ctx: &AlertContext) -> Rendered {
+        if self.is_empty() {
+            return Rendered::default();
+        }
+        let value = serde_json::to_value(ctx).unwrap_or_default();
+        Rendered {
+            title: self.title.as_ref().map(|t| t.render(&value)),
+            body: self.body.as_ref().map(|t| t.render(&value)),
+        }
+    }
+
+    pub fn render_group(&self, ctx: &GroupContext) -> Rendered {
+        if !self.has_group() {
+            return Rendered::default();
+        }
+        let value = serde_json::to_value(ctx).unwrap_or_default();
+        Rendered {
+            title: self.group_title.as_ref().map(|t| t.render(&value)),
+            body: self.group_body.as_ref().map(|t| t.render(&value)),
+        }
+    }
+}
+
+fn parse_field(
+    section: &serde_json::Value,
+    field: &'static str,
+) -> Result<Option<Template>, TemplateError> {
+    match section[field].as_str() {
+        Some(src) if !src.trim().is_empty() => Template::parse(src)
+            .map(Some)
+            .map_err(|e| TemplateError { field, ..e }),
+        _ => Ok(None),
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertContext {
+    pub id: String,
+    pub fingerprint: String,
+    pub rule_id: String,
+    pub rule_name: String,
+    pub agent_id: String,
+    pub metric_name: String,
+    pub series: String,
+    pub labels: BTreeMap<String, String>,
+    pub annotations: BTreeMap<String, String>,
+    pub severity: String,
+    pub severity_short: String,
+    pub status: String,
+    pub value: f64,
+    pub threshold: f64,
+    pub fired_at_ms: i64,
+    pub resolved_at_ms: Option<i64>,
+    pub duration_ms: i64,
+}
+
+impl AlertContext {
+    pub fn sample() -> Self {
+        let fired_at_ms = 1_709_553_600_000;
+        Self {
+            id: "00000000-0000-0000-0000-000000000000".into(),
+            fingerprint: "sample".into(),
+            rule_id: "sample-rule".into(),
+            rule_name: "High CPU".into(),
+            agent_id: "web-01".into(),
+            metric_name: "cpu.usage_percent".into(),
+            series: "cpu.usage_percent{core=\"0\"}".into(),
+            labels: BTreeMap::from([("core".to_string(), "0".to_string())]),
+            annotations: BTreeMap::from([
+                ("summary".to_string(), "CPU above 90% on web-01".to_string()),
+                (
+                    "runbook_url".to_string(),
+                    "https://runbooks.example.com/cpu".to_string(),
+                ),
+            ]),
+            severity: "critical".into(),
+            severity_short: "CRIT".into(),
+            status: "firing".into(),
+            value: 95.2,
+            threshold: 90.0,
+            fired_at_ms,
+            resolved_at_ms: None,
+            duration_ms: 15 * 60_000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupContext {
+    pub group_key: String,
+    pub group_labels: BTreeMap<String, String>,
+    pub status: String,
+    pub severity: String,
+    pub firing_count: usize,
+    pub resolved_count: usize,
+    pub repeat: bool,
+    pub alerts: Vec<AlertContext>,
+}
+
+impl GroupContext {
+    pub fn sample() -> Self {
+        let first = AlertContext::sample();
+        let second = AlertContext {
+            id: "00000000-0000-0000-0000-000000000001".into(),
+            fingerprint: "sample-2".into(),
+            agent_id: "web-02".into(),
+            value: 91.7,
+            ..AlertContext::sample()
+        };
+        Self {
+            group_key: "rule_id=sample-rule".into(),
+            group_labels: BTreeMap::from([("rule_id".to_string(), "sample-rule".to_string())]),
+            status: "firing".into(),
+            severity: "critical".into(),
+            firing_count: 2,
+            resolved_count: 0,
+            repeat: false,
+            alerts: vec![first, second],
+        }
+    }
+}
+
+struct Scope {
+    this: serde_json::Value,
+    key: Option<String>,
+    index: usize,
+}
+
+fn render_nodes(
+    nodes: &[Node],
+    root: &serde_json::Value,
+    scopes: &mut Vec<Scope>,
+    out: &mut String,
+) {
+    for node in nodes {
+        match node {
+            Node::Text(t) => out.push_str(t),
+            Node::Expr(e) => out.push_str(&stringify(&eval(e, root, scopes))),
+            Node::If {
+                cond,
+                then,
+                otherwise,
+            } => {
+                let branch = if truthy(&eval(cond, root, scopes)) {
+                    then
+                } else {
+                    otherwise
+                };
+                render_nodes(branch, root, scopes, out);
+            }
+            Node::Each { path, body } => {
+                let items: Vec<(Option<String>, serde_json::Value)> =
+                    match lookup(path, root, scopes) {
+                        serde_json::Value::Object(map) => {
+                            map.into_iter().map(|(k, v)| (Some(k), v)).collect()
+                        }
+                        serde_json::Value::Array(arr) => {
+                            arr.into_iter().map(|v| (None, v)).collect()
+                        }
+                        _ => Vec::new(),
+                    };
+                for (index, (key, this)) in items.into_iter().enumerate() {
+                    scopes.push(Scope { this, key, index });
+                    render_nodes(body, root, scopes, out);
+                    scopes.pop();
+                }
+            }
+        }
+    }
+}
+
+fn eval(expr: &parser::Expr, root: &serde_json::Value, scopes: &[Scope]) -> serde_json::Value {
+    let value = match &expr.operand {
+        Operand::Literal(v) => v.clone(),
+        Operand::Path(path) => lookup(path, root, scopes),
+    };
+    expr.helpers.iter().fold(value, |v, h| h.apply(v))
+}
+
+fn lookup(path: &[String], root: &serde_json::Value, scopes: &[Scope]) -> serde_json::Value {
+    let Some((first, rest)) = path.split_first() else {
+        return serde_json::Value::Null;
+    };
+    let scope = scopes.last();
+    let (base, rest) = match first.as_str() {
+        "@key" => return scope.and_then(|s| s.key.clone()).into(),
+        "@index" => return scope.map(|s| s.index).into(),
+        "@root" => (root, rest),
+        "this" => (scope.map(|s| &s.this).unwrap_or(root), rest),
+        name => match scope {
+            Some(s) if s.this.get(name).is_some() => (&s.this, path),
+            _ => (root, path),
+        },
+    };
+    let mut current = base;
+    for seg in rest {
+        current = match current {
+            serde_json::Value::Array(arr) => seg
+                .parse::<usize>()
+                .ok()
+                .and_then(|i| arr.get(i))
+                .unwrap_or(&serde_json::Value::Null),
+            other => &other[seg.as_str()],
+        };
+    }
+    current.clone()
+}
+
+fn truthy(v: &serde_json::Value) -> bool {
+    match v {
+        serde_json::Value::Null => false,
+        serde_json::Value::Bool(b) => *b,
+        serde_json::Value::Number(n) => n.as_f64().is_some_and(|f| f != 0.0),
+        serde_json::Value::String(s) => !s.is_empty(),
+        serde_json::Value::Array(a) => !a.is_empty(),
+        serde_json::Value::Object(o) => !o.is_empty(),
+    }
+}
+
+pub(crate) fn stringify(v: &serde_json::Value) -> String {
+    match v {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => i.to_string(),
+            None => n.as_f64().map(|f| f.to_string()).unwrap_or_default(),
+        },
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(src: &str) -> String {
+        let ctx = serde_json::to_value(AlertContext::sample()).unwrap();
+        Template::parse(src).unwrap().render(&ctx)
+    }
+
+    #[test]
+    fn renders_fields_labels_and_annotations() {
+        assert_eq!(
+            render("[{{ severity | upper }}] {{rule_name}} on {{agent_id}}"),
+            "[CRITICAL] High CPU on web-01"
+        );
+        assert_eq!(
+            render("core {{labels.core}}: {{annotations.runbook_url}}"),
+            "core 0: https://runbooks.example.com/cpu"
+        );
+        assert_eq!(render("{{annotations.missing}}"), "");
+        assert_eq!(render("{{ annotations.missing | default \"n/a\" }}"), "n/a");
+    }
+
+    #[test]
+    fn renders_helpers() {
+        assert_eq!(render("{{ value | round 1 }}"), "95.2");
+        assert_eq!(render("{{ threshold }}"), "90");
+        assert_eq!(render("for {{ duration_ms | duration }}"), "for 15m");
+        assert_eq!(
+            render("{{ fired_at_ms | datetime }}"),
+            "2024-03-04 12:00:00 UTC"
+        );
+        assert_eq!(render("{{ 1610612736 | bytes }}"), "1.5 GiB");
+        assert_eq!(render("{{ rule_name | truncate 4 }}"), "High…");
+        assert_eq!(
+            render("{{ annotations.summary | json }}"),
+            "\"CPU above 90% on web-01\""
+        );
+    }
+
+    #[test]
+    fn renders_conditionals_and_loops() {
+        assert_eq!(
+            render("{{#if resolved_at_ms}}resolved{{else}}still firing{{/if}}"),
+            "still firing"
+        );
+        assert_eq!(
+            render("{{#each annotations}}{{@key}}={{this}};{{/each}}"),
+            "runbook_url=https://runbooks.example.com/cpu;summary=CPU above 90% on web-01;"
+        );
+        assert_eq!(
+            render("{{#each labels}}{{@key}} on {{agent_id}}{{/each}}"),
+            "core on web-01"
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_templates() {
+        for src in [
+            "{{ rule_name",
+            "{{ rule_name | shout }}",
+            "{{#if status}}open",
+            "{{/each}}",
+            "{{#each labels}}{{/if}}",
+            "{{ value | round x }}",
+            "{{}}",
+        ] {
+            assert!(Template::parse(src).is_err(), "{src}");
+        }
+    }
+
+    #[test]
+    fn message_template_reads_notifier_config() {
+        let cfg = serde_json::json!({
+            "webhook_url": "https://hooks.slack.com/x",
+            "template": { "title": "{{rule_name}} is {{status}}" }
+        });
+        let t = MessageTemplate::from_config(&cfg).unwrap();
+        let r = t.render(&AlertContext::sample());
+        assert_eq!(r.title.as_deref(), Some("High CPU is firing"));
+        assert_eq!(r.body, None);
+
+        assert!(MessageTemplate::from_config(&serde_json::json!({}))
+            .unwrap()
+            .is_empty());
+
+        let err = MessageTemplate::from_config(&serde_json::json!({
+            "template": { "body": "{{ value | nope }}" }
+        }))
+        .unwrap_err();
+        assert_eq!(err.field, "body");
+    }
+
+    #[test]
+    fn group_template_iterates_alerts() {
+        let cfg = serde_json::json!({
+            "template": {
+                "group_title": "[{{firing_count}}] {{group_labels.rule_id}}",
+                "group_body": "{{#each alerts}}{{agent_id}}={{value}};{{/each}}"
+            }
+        });
+        let t = MessageTemplate::from_config(&cfg).unwrap();
+        assert!(t.is_empty());
+        assert!(t.has_group());
+        let r = t.render_group(&GroupContext::sample());
+        assert_eq!(r.title.as_deref(), Some("[2] sample-rule"));
+        assert_eq!(r.body.as_deref(), Some("web-01=95.2;web-02=91.7;"));
+    }
+}
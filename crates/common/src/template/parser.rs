@@ -0,0 +1,261 @@
+use super::helpers::Helper;
+use super::TemplateError;
+
+#[derive(Debug, Clone)]
+pub(crate) enum Node {
+    Text(String),
+    Expr(Expr),
+    If {
+        cond: Expr,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+    Each {
+        path: Vec<String>,
+        body: Vec<Node>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Expr {
+    pub operand: Operand,
+    pub helpers: Vec<Helper>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum Operand {
+    Path(Vec<String>),
+    Literal(serde_json::Value),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Arg {
+    Str(String),
+    Num(f64),
+    Ident(String),
+}
+
+enum Block {
+    If { cond: Expr, then: Option<Vec<Node>> },
+    Each { path: Vec<String> },
+}
+
+struct Frame {
+    block: Block,
+    nodes: Vec<Node>,
+    pos: usize,
+}
+
+pub(crate) fn parse(source: &str) -> Result<Vec<Node>, TemplateError> {
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut rest = source;
+    let mut offset = 0;
+
+    while !rest.is_empty() {
+        let Some(open) = rest.find("{{") else {
+            current(&mut stack, &mut nodes).push(Node::Text(rest.to_string()));
+            break;
+        };
+        if open > 0 {
+            current(&mut stack, &mut nodes).push(Node::Text(rest[..open].to_string()));
+        }
+        let tag_pos = offset + open;
+        let after = &rest[open + 2..];
+        let close = after
+            .find("}}")
+            .ok_or_else(|| TemplateError::new(tag_pos, "unclosed '{{'"))?;
+        let tag = after[..close].trim();
+        let inner_pos = tag_pos + 2;
+
+        if let Some(cond) = tag.strip_prefix("#if") {
+            stack.push(Frame {
+                block: Block::If {
+                    cond: parse_expr(cond, inner_pos)?,
+                    then: None,
+                },
+                nodes: Vec::new(),
+                pos: tag_pos,
+            });
+        } else if let Some(path) = tag.strip_prefix("#each") {
+            let path = path.trim();
+            if !is_path(path) {
+                return Err(TemplateError::new(inner_pos, "#each expects a field path"));
+            }
+            stack.push(Frame {
+                block: Block::Each {
+                    path: split_path(path),
+                },
+                nodes: Vec::new(),
+                pos: tag_pos,
+            });
+        } else if tag == "else" {
+            match stack.last_mut() {
+                Some(Frame {
+                    block: Block::If { then, .. },
+                    nodes,
+                    ..
+                }) if then.is_none() => *then = Some(std::mem::take(nodes)),
+                _ => return Err(TemplateError::new(tag_pos, "'else' outside of #if")),
+            }
+        } else if let Some(name) = tag.strip_prefix('/') {
+            let frame = stack
+                .pop()
+                .ok_or_else(|| TemplateError::new(tag_pos, format!("unexpected '/{name}'")))?;
+            let node = match (frame.block, name.trim()) {
+                (Block::If { cond, then }, "if") => match then {
+                    Some(then) => Node::If {
+                        cond,
+                        then,
+                        otherwise: frame.nodes,
+                    },
+                    None => Node::If {
+                        cond,
+                        then: frame.nodes,
+                        otherwise: Vec::new(),
+                    },
+                },
+                (Block::Each { path }, "each") => Node::Each {
+                    path,
+                    body: frame.nodes,
+                },
+                (_, other) => {
+                    return Err(TemplateError::new(
+                        tag_pos,
+                        format!("'/{other}' does not close the open block"),
+                    ))
+                }
+            };
+            current(&mut stack, &mut nodes).push(node);
+        } else if !tag.starts_with('!') {
+            let expr = parse_expr(tag, inner_pos)?;
+            current(&mut stack, &mut nodes).push(Node::Expr(expr));
+        }
+
+        let consumed = open + 2 + close + 2;
+        rest = &rest[consumed..];
+        offset += consumed;
+    }
+
+    if let Some(frame) = stack.last() {
+        let name = match frame.block {
+            Block::If { .. } => "#if",
+            Block::Each { .. } => "#each",
+        };
+        return Err(TemplateError::new(frame.pos, format!("unclosed {name}")));
+    }
+    Ok(nodes)
+}
+
+fn current<'a>(stack: &'a mut [Frame], root: &'a mut Vec<Node>) -> &'a mut Vec<Node> {
+    match stack.last_mut() {
+        Some(frame) => &mut frame.nodes,
+        None => root,
+    }
+}
+
+fn parse_expr(src: &str, pos: usize) -> Result<Expr, TemplateError> {
+    let mut stages = split_pipes(src).into_iter();
+    let head = split_words(&stages.next().unwrap_or_default(), pos)?;
+    let operand = match <[Arg; 1]>::try_from(head) {
+        Ok([arg]) => arg,
+        Err(v) if v.is_empty() => return Err(TemplateError::new(pos, "empty expression")),
+        Err(_) => {
+            return Err(TemplateError::new(
+                pos,
+                "expected a single field or literal before '|'",
+            ))
+        }
+    };
+
+    let operand = match operand {
+        Arg::Ident(path) if is_path(&path) => Operand::Path(split_path(&path)),
+        Arg::Str(s) => Operand::Literal(s.into()),
+        Arg::Num(n) => Operand::Literal(n.into()),
+        Arg::Ident(other) => {
+            return Err(TemplateError::new(pos, format!("invalid field '{other}'")))
+        }
+    };
+
+    let helpers = stages
+        .map(|stage| {
+            let words = split_words(&stage, pos)?;
+            let (name, args) = words
+                .split_first()
+                .ok_or_else(|| TemplateError::new(pos, "missing helper after '|'"))?;
+            match name {
+                Arg::Ident(name) => Helper::parse(name, args, pos),
+                _ => Err(TemplateError::new(pos, "expected a helper name after '|'")),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Expr { operand, helpers })
+}
+
+fn split_pipes(src: &str) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut in_str = false;
+    for c in src.chars() {
+        match c {
+            '"' => {
+                in_str = !in_str;
+                parts.last_mut().unwrap().push(c);
+            }
+            '|' if !in_str => parts.push(String::new()),
+            _ => parts.last_mut().unwrap().push(c),
+        }
+    }
+    parts
+}
+
+fn split_words(src: &str, pos: usize) -> Result<Vec<Arg>, TemplateError> {
+    let mut words = Vec::new();
+    let mut chars = src.trim().chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let mut word = String::new();
+        if c == '"' {
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => word.extend(chars.next()),
+                    Some(ch) => word.push(ch),
+                    None => return Err(TemplateError::new(pos, "unterminated string")),
+                }
+            }
+            words.push(Arg::Str(word));
+            continue;
+        }
+        while let Some(&ch) = chars.peek() {
+            if ch.is_whitespace() {
+                break;
+            }
+            word.push(ch);
+            chars.next();
+        }
+        words.push(match word.parse::<f64>() {
+            Ok(n) => Arg::Num(n),
+            Err(_) => Arg::Ident(word),
+        });
+    }
+    Ok(words)
+}
+
+fn is_path(s: &str) -> bool {
+    !s.is_empty()
+        && s.split('.').all(|seg| {
+            !seg.is_empty()
+                && seg
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '@')
+        })
+}
+
+fn split_path(s: &str) -> Vec<String> {
+    s.split('.').map(String::from).collect()
+}
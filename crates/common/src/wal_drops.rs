@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+
+pub const DROPPED_BATCHES_META_KEY: &str = "wal_dropped_batches";
+
+pub const DROP_REASONS_META_KEY: &str = "wal_drop_reasons";
+
+pub fn dropped_batches(meta: &HashMap<String, String>) -> u64 {
+    meta.get(DROPPED_BATCHES_META_KEY)
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0)
+}
+
+pub fn drop_reasons(meta: &HashMap<String, String>) -> Vec<(String, u64)> {
+    let Some(raw) = meta.get(DROP_REASONS_META_KEY) else {
+        return Vec::new();
+    };
+    raw.split(',')
+        .filter_map(|part| {
+            let (reason, count) = part.split_once(':')?;
+            Some((reason.trim().to_string(), count.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_drop_meta() {
+        let mut meta = HashMap::new();
+        assert_eq!(dropped_batches(&meta), 0);
+        meta.insert(DROPPED_BATCHES_META_KEY.into(), "15".into());
+        meta.insert(
+            DROP_REASONS_META_KEY.into(),
+            "age:3,drop_oldest:12,bad".into(),
+        );
+        assert_eq!(dropped_batches(&meta), 15);
+        assert_eq!(
+            drop_reasons(&meta),
+            vec![("age".to_string(), 3), ("drop_oldest".to_string(), 12)]
+        );
+    }
+}
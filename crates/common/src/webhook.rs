@@ -0,0 +1,73 @@
+use std::fmt;
+
+use crate::crypto::{sign_data, verify_signature};
+
+pub const SIGNATURE_HEADER: &str = "X-Sentinel-Signature";
+pub const IDEMPOTENCY_HEADER: &str = "Idempotency-Key";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyError {
+    MissingSignature,
+    Mismatch,
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSignature => write!(f, "missing {SIGNATURE_HEADER} value"),
+            Self::Mismatch => write!(f, "signature does not match payload"),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+pub fn sign(secret: &[u8], body: &[u8]) -> String {
+    sign_data(secret, body)
+}
+
+pub fn verify(secret: &[u8], body: &[u8], signature: &str) -> Result<(), VerifyError> {
+    let signature = signature.trim();
+    if signature.is_empty() {
+        return Err(VerifyError::MissingSignature);
+    }
+    if verify_signature(secret, body, signature) {
+        Ok(())
+    } else {
+        Err(VerifyError::Mismatch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_accepts_signed_body() {
+        let body = br#"{"status":"firing"}"#;
+        let sig = sign(b"hook-secret", body);
+        assert_eq!(verify(b"hook-secret", body, &sig), Ok(()));
+        assert_eq!(verify(b"hook-secret", body, &format!(" {sig}\n")), Ok(()));
+    }
+
+    #[test]
+    fn verify_rejects_tampering() {
+        let sig = sign(b"hook-secret", b"{\"a\":1}");
+        assert_eq!(
+            verify(b"hook-secret", b"{\"a\":2}", &sig),
+            Err(VerifyError::Mismatch)
+        );
+        assert_eq!(
+            verify(b"other", b"{\"a\":1}", &sig),
+            Err(VerifyError::Mismatch)
+        );
+        assert_eq!(
+            verify(b"hook-secret", b"{}", "not base64!"),
+            Err(VerifyError::Mismatch)
+        );
+        assert_eq!(
+            verify(b"hook-secret", b"{}", "  "),
+            Err(VerifyError::MissingSignature)
+        );
+    }
+}
@@ -41,7 +41,10 @@ pub async fn ensure_stream(
     Ok(js.get_or_create_stream(stream_config).await?)
 }
 
-pub async fn connect_jetstream(url: &str) -> Result<jetstream::Context, BoxError> {
+pub async fn connect_jetstream(
+    url: &str,
+) -> Result<(jetstream::Context, async_nats::Client), BoxError> {
     let client = async_nats::connect(url).await?;
-    Ok(jetstream::new(client))
+    let js = jetstream::new(client.clone());
+    Ok((js, client))
 }
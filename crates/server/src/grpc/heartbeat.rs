@@ -44,6 +44,7 @@ mod tests {
             agent_version: "0.1.0".into(),
             registered_at_ms: 1000,
             deprecated_keys: Vec::new(),
+            pending_key: None,
             last_seen: None,
         });
         store
@@ -46,6 +46,7 @@ pub async fn handle_register(
         agent_version: req.agent_version,
         registered_at_ms: now_ms,
         deprecated_keys: Vec::new(),
+        pending_key: None,
         last_seen: None,
     };
 
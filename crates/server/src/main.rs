@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use sentinel_common::envelope::Envelope;
 use sentinel_common::logging::{self, Component, LogConfig};
 use sentinel_common::nats_config::StreamConfig;
 use sentinel_common::pool_config::PoolConfig;
@@ -18,7 +19,8 @@ use sentinel_server::provisioning::TokenStore;
 use sentinel_server::rest::{self, AppState};
 use sentinel_server::store::{AgentStore, IdempotencyStore, RuleStore};
 use sentinel_server::stream::{
-    spawn_watchdog, PresenceEventBus, SessionRegistry, StreamService, WatchdogConfig,
+    spawn_presence_relay, spawn_watchdog, PresenceEventBus, SessionRegistry, StreamService,
+    WatchdogConfig,
 };
 use sentinel_server::tls::TlsIdentity;
 
@@ -46,6 +48,20 @@ async fn main() {
         );
     }
 
+    let envelope = Envelope::from_env().unwrap_or_else(|e| {
+        tracing::error!(target: "auth", error = %e, "Failed to load master key");
+        std::process::exit(1);
+    });
+    match envelope.key_id() {
+        Some(key_id) => {
+            tracing::info!(target: "auth", key_id, "Secrets at rest encrypted with master key")
+        }
+        None => tracing::warn!(
+            target: "auth",
+            "No master key configured — notifier credentials and agent secrets are stored in plaintext (set SENTINEL_ENCRYPTION_KEY)"
+        ),
+    }
+
     let (pool, agent_repo, rule_repo, notifier_repo, history_repo, metrics_qrepo) = match config
         .database_url
     {
@@ -80,9 +96,9 @@ async fn main() {
                 }
             }
 
-            let repo = Arc::new(AgentRepo::new(pool.clone()));
+            let repo = Arc::new(AgentRepo::new(pool.clone(), envelope.clone()));
             let r_repo = Arc::new(RuleRepo::new(pool.clone()));
-            let n_repo = Arc::new(NotifierRepo::new(pool.clone()));
+            let n_repo = Arc::new(NotifierRepo::new(pool.clone(), envelope.clone()));
             let h_repo = Arc::new(NotificationHistoryRepo::new(pool.clone()));
             let m_repo = Arc::new(MetricsQueryRepo::new(pool.clone()));
             (
@@ -101,7 +117,7 @@ async fn main() {
     };
 
     let sw = logging::stopwatch();
-    let js = connect_jetstream(&config.nats_url)
+    let (js, nats_client) = connect_jetstream(&config.nats_url)
         .await
         .unwrap_or_else(|e| {
             tracing::error!(
@@ -185,10 +201,12 @@ async fn main() {
     );
     tracing::info!(target: "conn", "Watchdog active");
 
+    spawn_presence_relay(presence_events.clone(), nats_client);
+
     let stream_service = StreamService::new(
         agents.clone(),
-        idempotency,
-        broker,
+        idempotency.clone(),
+        broker.clone(),
         session_registry.clone(),
         presence_events.clone(),
         config.key_grace_period_ms,
@@ -241,6 +259,11 @@ async fn main() {
         grpc_public_url,
         registry: session_registry,
         events: presence_events,
+        broker: Some(broker),
+        idempotency,
+        key_grace_period_ms: config.key_grace_period_ms,
+        replay_window_ms: config.replay_window_ms,
+        envelope,
     };
     let rest_app = rest::router(app_state);
     let rest_addr = config.rest_addr;
@@ -49,6 +49,11 @@ pub fn render_prometheus(m: &Arc<ServerMetrics>) -> String {
         "sentinel_server_broker_publish_errors_total",
         m.broker_publish_errors_total(),
     );
+    write_counter(
+        &mut out,
+        "sentinel_server_agent_wal_dropped_batches_total",
+        m.agent_wal_dropped_total(),
+    );
 
     let (sum, count) = m.grpc_latency_vals();
     write_summary(&mut out, "sentinel_server_grpc_latency_us", sum, count);
@@ -13,6 +13,7 @@ pub struct ServerMetrics {
     heartbeats_total: AtomicU64,
     key_rotations_total: AtomicU64,
     broker_publish_errors_total: AtomicU64,
+    agent_wal_dropped_total: AtomicU64,
     grpc_latency_sum_us: AtomicU64,
     grpc_latency_count: AtomicU64,
 }
@@ -59,6 +60,10 @@ impl ServerMetrics {
             .fetch_add(1, Ordering::Relaxed);
     }
 
+    pub fn add_agent_wal_dropped(&self, n: u64) {
+        self.agent_wal_dropped_total.fetch_add(n, Ordering::Relaxed);
+    }
+
     pub fn record_grpc_latency(&self, start: Instant) {
         let us = start.elapsed().as_micros() as u64;
         self.grpc_latency_sum_us.fetch_add(us, Ordering::Relaxed);
@@ -101,6 +106,10 @@ impl ServerMetrics {
         self.broker_publish_errors_total.load(Ordering::Relaxed)
     }
 
+    pub fn agent_wal_dropped_total(&self) -> u64 {
+        self.agent_wal_dropped_total.load(Ordering::Relaxed)
+    }
+
     pub fn grpc_latency_vals(&self) -> (u64, u64) {
         (
             self.grpc_latency_sum_us.load(Ordering::Relaxed),
@@ -121,6 +130,7 @@ impl Default for ServerMetrics {
             heartbeats_total: AtomicU64::new(0),
             key_rotations_total: AtomicU64::new(0),
             broker_publish_errors_total: AtomicU64::new(0),
+            agent_wal_dropped_total: AtomicU64::new(0),
             grpc_latency_sum_us: AtomicU64::new(0),
             grpc_latency_count: AtomicU64::new(0),
         }
@@ -145,6 +155,7 @@ mod tests {
         m.inc_key_rotations();
         m.inc_broker_publish_errors();
         m.inc_rest_requests();
+        m.add_agent_wal_dropped(5);
 
         assert_eq!(m.grpc_requests_total(), 2);
         assert_eq!(m.grpc_errors_total(), 1);
@@ -155,6 +166,7 @@ mod tests {
         assert_eq!(m.key_rotations_total(), 1);
         assert_eq!(m.broker_publish_errors_total(), 1);
         assert_eq!(m.rest_requests_total(), 1);
+        assert_eq!(m.agent_wal_dropped_total(), 5);
     }
 
     #[test]
@@ -5,8 +5,11 @@ use axum::response::Response;
 
 use crate::auth::{validate_token, TokenError};
 
+#[derive(Debug, Clone)]
+pub struct AuthSubject(pub String);
+
 pub async fn auth_middleware(
-    request: Request,
+    mut request: Request,
     next: Next,
     jwt_secret: &[u8],
 ) -> Result<Response, StatusCode> {
@@ -17,11 +20,12 @@ pub async fn auth_middleware(
         .and_then(|v| v.strip_prefix("Bearer "))
         .ok_or(StatusCode::UNAUTHORIZED)?;
 
-    validate_token(jwt_secret, token).map_err(|e| match e {
+    let subject = validate_token(jwt_secret, token).map_err(|e| match e {
         TokenError::Expired => StatusCode::UNAUTHORIZED,
         TokenError::InvalidSignature => StatusCode::UNAUTHORIZED,
         TokenError::Malformed => StatusCode::BAD_REQUEST,
     })?;
+    request.extensions_mut().insert(AuthSubject(subject));
 
     Ok(next.run(request).await)
 }
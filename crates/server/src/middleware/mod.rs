@@ -1,5 +1,5 @@
 mod auth_layer;
 mod rate_limit;
 
-pub use auth_layer::{auth_middleware, require_auth};
+pub use auth_layer::{auth_middleware, require_auth, AuthSubject};
 pub use rate_limit::RateLimiter;
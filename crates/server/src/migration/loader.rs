@@ -81,5 +81,53 @@ pub fn load_all() -> Vec<MigrationFile> {
             filename: "018_compatibility_views.sql",
             sql: include_str!("../../../../migrations/018_compatibility_views.sql"),
         },
+        MigrationFile {
+            filename: "019_label_matchers.sql",
+            sql: include_str!("../../../../migrations/019_label_matchers.sql"),
+        },
+        MigrationFile {
+            filename: "020_rule_aggregation.sql",
+            sql: include_str!("../../../../migrations/020_rule_aggregation.sql"),
+        },
+        MigrationFile {
+            filename: "021_rule_expressions.sql",
+            sql: include_str!("../../../../migrations/021_rule_expressions.sql"),
+        },
+        MigrationFile {
+            filename: "022_alert_states.sql",
+            sql: include_str!("../../../../migrations/022_alert_states.sql"),
+        },
+        MigrationFile {
+            filename: "023_rule_absent_for.sql",
+            sql: include_str!("../../../../migrations/023_rule_absent_for.sql"),
+        },
+        MigrationFile {
+            filename: "024_dlq_history_link.sql",
+            sql: include_str!("../../../../migrations/024_dlq_history_link.sql"),
+        },
+        MigrationFile {
+            filename: "025_dlq_replay.sql",
+            sql: include_str!("../../../../migrations/025_dlq_replay.sql"),
+        },
+        MigrationFile {
+            filename: "026_silences_and_acks.sql",
+            sql: include_str!("../../../../migrations/026_silences_and_acks.sql"),
+        },
+        MigrationFile {
+            filename: "027_rule_grouping.sql",
+            sql: include_str!("../../../../migrations/027_rule_grouping.sql"),
+        },
+        MigrationFile {
+            filename: "028_escalation_policies.sql",
+            sql: include_str!("../../../../migrations/028_escalation_policies.sql"),
+        },
+        MigrationFile {
+            filename: "029_alert_state_labels.sql",
+            sql: include_str!("../../../../migrations/029_alert_state_labels.sql"),
+        },
+        MigrationFile {
+            filename: "030_agent_pending_keys.sql",
+            sql: include_str!("../../../../migrations/030_agent_pending_keys.sql"),
+        },
     ]
 }
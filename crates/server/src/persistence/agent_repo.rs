@@ -1,14 +1,28 @@
+use sentinel_common::envelope::Envelope;
 use sqlx::PgPool;
 
-use crate::store::{AgentRecord, AgentStore, DeprecatedKey};
+use crate::store::{AgentRecord, AgentStore, DeprecatedKey, PendingKey};
 
 pub struct AgentRepo {
     pool: PgPool,
+    envelope: Envelope,
 }
 
 impl AgentRepo {
-    pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+    pub fn new(pool: PgPool, envelope: Envelope) -> Self {
+        Self { pool, envelope }
+    }
+
+    fn seal(&self, secret: &[u8]) -> Result<Vec<u8>, sqlx::Error> {
+        self.envelope
+            .seal_bytes(secret)
+            .map_err(|e| sqlx::Error::Encode(Box::new(e)))
+    }
+
+    fn open(&self, secret: &[u8]) -> Result<Vec<u8>, sqlx::Error> {
+        self.envelope
+            .open_bytes(secret)
+            .map_err(|e| sqlx::Error::Decode(Box::new(e)))
     }
 
     pub fn pool(&self) -> &PgPool {
@@ -17,24 +31,33 @@ impl AgentRepo {
 
     pub async fn load_all(&self, store: &AgentStore) -> Result<usize, sqlx::Error> {
         let rows = sqlx::query_as::<_, AgentRow>(
-            "SELECT agent_id, hw_id, secret, key_id, agent_version, registered_at_ms, deprecated_keys, last_seen FROM agents",
+            "SELECT agent_id, hw_id, secret, key_id, agent_version, registered_at_ms, deprecated_keys, pending_key, last_seen FROM agents",
         )
         .fetch_all(&self.pool)
         .await?;
 
         let count = rows.len();
         for row in rows {
-            let deprecated_keys: Vec<DeprecatedKey> =
+            let mut deprecated_keys: Vec<DeprecatedKey> =
                 serde_json::from_value(row.deprecated_keys).unwrap_or_default();
+            for key in &mut deprecated_keys {
+                key.secret = self.open(&key.secret)?;
+            }
+            let mut pending_key: Option<PendingKey> =
+                row.pending_key.and_then(|v| serde_json::from_value(v).ok());
+            if let Some(key) = &mut pending_key {
+                key.acked_secret = self.open(&key.acked_secret)?;
+            }
 
             store.insert(AgentRecord {
                 agent_id: row.agent_id,
                 hw_id: row.hw_id,
-                secret: row.secret,
+                secret: self.open(&row.secret)?,
                 key_id: row.key_id,
                 agent_version: row.agent_version,
                 registered_at_ms: row.registered_at_ms,
                 deprecated_keys,
+                pending_key,
                 last_seen: row.last_seen,
             });
         }
@@ -43,32 +66,120 @@ impl AgentRepo {
     }
 
     pub async fn upsert(&self, record: &AgentRecord) -> Result<(), sqlx::Error> {
-        let deprecated_json = serde_json::to_value(&record.deprecated_keys)
+        let deprecated_keys = record
+            .deprecated_keys
+            .iter()
+            .map(|k| {
+                Ok(DeprecatedKey {
+                    secret: self.seal(&k.secret)?,
+                    ..k.clone()
+                })
+            })
+            .collect::<Result<Vec<_>, sqlx::Error>>()?;
+        let deprecated_json = serde_json::to_value(&deprecated_keys)
             .unwrap_or_else(|_| serde_json::Value::Array(vec![]));
+        let pending_json = record
+            .pending_key
+            .as_ref()
+            .map(|k| {
+                Ok::<_, sqlx::Error>(PendingKey {
+                    acked_secret: self.seal(&k.acked_secret)?,
+                    ..k.clone()
+                })
+            })
+            .transpose()?
+            .and_then(|k| serde_json::to_value(k).ok());
 
         sqlx::query(
-            r#"INSERT INTO agents (agent_id, hw_id, secret, key_id, agent_version, registered_at_ms, deprecated_keys, last_seen)
-               VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())
+            r#"INSERT INTO agents (agent_id, hw_id, secret, key_id, agent_version, registered_at_ms, deprecated_keys, pending_key, last_seen)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, NOW())
                ON CONFLICT (agent_id) DO UPDATE SET
                  hw_id = EXCLUDED.hw_id,
                  secret = EXCLUDED.secret,
                  key_id = EXCLUDED.key_id,
                  agent_version = EXCLUDED.agent_version,
                  deprecated_keys = EXCLUDED.deprecated_keys,
+                 pending_key = EXCLUDED.pending_key,
                  last_seen = NOW()"#,
         )
         .bind(&record.agent_id)
         .bind(&record.hw_id)
-        .bind(&record.secret)
+        .bind(self.seal(&record.secret)?)
         .bind(&record.key_id)
         .bind(&record.agent_version)
         .bind(record.registered_at_ms)
         .bind(&deprecated_json)
+        .bind(&pending_json)
         .execute(&self.pool)
         .await?;
 
         Ok(())
     }
+
+    pub async fn reencrypt(&self, dry_run: bool) -> Result<u64, sqlx::Error> {
+        let agent_ids: Vec<String> = sqlx::query_scalar("SELECT agent_id FROM agents")
+            .fetch_all(&self.pool)
+            .await?;
+
+        let reseal = |secret: &[u8]| {
+            self.envelope
+                .reseal_bytes(secret)
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))
+        };
+
+        let mut changed = 0;
+        for agent_id in agent_ids {
+            let mut tx = self.pool.begin().await?;
+            let row: Option<(Vec<u8>, serde_json::Value, Option<serde_json::Value>)> =
+                sqlx::query_as(
+                    "SELECT secret, deprecated_keys, pending_key FROM agents WHERE agent_id = $1 FOR UPDATE",
+                )
+                .bind(&agent_id)
+                .fetch_optional(&mut *tx)
+                .await?;
+            let Some((secret, deprecated, pending)) = row else {
+                continue;
+            };
+            let new_secret = reseal(&secret)?;
+            let mut keys: Vec<DeprecatedKey> =
+                serde_json::from_value(deprecated).unwrap_or_default();
+            let mut keys_changed = false;
+            for key in &mut keys {
+                if let Some(sealed) = reseal(&key.secret)? {
+                    key.secret = sealed;
+                    keys_changed = true;
+                }
+            }
+            let mut pending: Option<PendingKey> =
+                pending.and_then(|v| serde_json::from_value(v).ok());
+            if let Some(key) = &mut pending {
+                if let Some(sealed) = reseal(&key.acked_secret)? {
+                    key.acked_secret = sealed;
+                    keys_changed = true;
+                }
+            }
+            if new_secret.is_none() && !keys_changed {
+                continue;
+            }
+            if !dry_run {
+                let keys_json = serde_json::to_value(&keys)
+                    .unwrap_or_else(|_| serde_json::Value::Array(vec![]));
+                let pending_json = pending.and_then(|k| serde_json::to_value(k).ok());
+                sqlx::query(
+                    "UPDATE agents SET secret = $1, deprecated_keys = $2, pending_key = $3 WHERE agent_id = $4",
+                )
+                .bind(new_secret.unwrap_or(secret))
+                .bind(&keys_json)
+                .bind(&pending_json)
+                .bind(&agent_id)
+                .execute(&mut *tx)
+                .await?;
+                tx.commit().await?;
+            }
+            changed += 1;
+        }
+        Ok(changed)
+    }
 }
 
 #[derive(sqlx::FromRow)]
@@ -80,5 +191,6 @@ struct AgentRow {
     agent_version: String,
     registered_at_ms: i64,
     deprecated_keys: serde_json::Value,
+    pending_key: Option<serde_json::Value>,
     last_seen: Option<chrono::DateTime<chrono::Utc>>,
 }
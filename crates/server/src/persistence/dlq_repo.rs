@@ -0,0 +1,132 @@
+use sqlx::PgPool;
+
+pub struct DlqRecord {
+    pub id: String,
+    pub alert_id: String,
+    pub notifier_id: Option<String>,
+    pub notifier: String,
+    pub error: String,
+    pub attempts: i32,
+    pub retry_count: i32,
+    pub replay_pending: bool,
+    pub created_at_ms: i64,
+    pub last_attempt_ms: i64,
+    pub next_retry_at_ms: Option<i64>,
+}
+
+#[derive(Default)]
+pub struct DlqFilter {
+    pub id: Option<String>,
+    pub notifier_id: Option<String>,
+    pub alert_id: Option<String>,
+    pub older_than_secs: Option<i64>,
+}
+
+const FILTER: &str = "($1::text IS NULL OR d.id = $1)
+       AND ($2::text IS NULL OR h.notifier_id = $2)
+       AND ($3::text IS NULL OR d.alert_id = $3)
+       AND ($4::bigint IS NULL OR d.created_at < NOW() - ($4::bigint * INTERVAL '1 second'))";
+
+pub struct DlqRepo {
+    pool: PgPool,
+}
+
+impl DlqRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list(&self, f: &DlqFilter, limit: i64) -> Result<Vec<DlqRecord>, sqlx::Error> {
+        let sql = format!(
+            "SELECT d.id, d.alert_id, h.notifier_id, d.notifier, d.error, d.attempts,
+                    d.retry_count, d.replay_requested_at IS NOT NULL AS replay_pending,
+                    (EXTRACT(EPOCH FROM d.created_at) * 1000)::bigint AS created_at_ms,
+                    (EXTRACT(EPOCH FROM d.last_attempt) * 1000)::bigint AS last_attempt_ms,
+                    (EXTRACT(EPOCH FROM d.next_retry_at) * 1000)::bigint AS next_retry_at_ms
+             FROM notifications_dlq d
+             LEFT JOIN notification_history h ON h.id = d.history_id
+             WHERE {FILTER}
+             ORDER BY d.created_at DESC
+             LIMIT $5"
+        );
+        let rows = sqlx::query_as::<_, DlqRow>(&sql)
+            .bind(&f.id)
+            .bind(&f.notifier_id)
+            .bind(&f.alert_id)
+            .bind(f.older_than_secs)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    pub async fn request_replay(&self, f: &DlqFilter) -> Result<u64, sqlx::Error> {
+        let sql = format!(
+            "UPDATE notifications_dlq SET replay_requested_at = NOW()
+             WHERE id IN (
+                 SELECT d.id FROM notifications_dlq d
+                 JOIN notification_history h ON h.id = d.history_id
+                 WHERE {FILTER})"
+        );
+        let result = sqlx::query(&sql)
+            .bind(&f.id)
+            .bind(&f.notifier_id)
+            .bind(&f.alert_id)
+            .bind(f.older_than_secs)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn purge(&self, f: &DlqFilter) -> Result<u64, sqlx::Error> {
+        let sql = format!(
+            "DELETE FROM notifications_dlq
+             WHERE id IN (
+                 SELECT d.id FROM notifications_dlq d
+                 LEFT JOIN notification_history h ON h.id = d.history_id
+                 WHERE {FILTER})"
+        );
+        let result = sqlx::query(&sql)
+            .bind(&f.id)
+            .bind(&f.notifier_id)
+            .bind(&f.alert_id)
+            .bind(f.older_than_secs)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct DlqRow {
+    id: String,
+    alert_id: String,
+    notifier_id: Option<String>,
+    notifier: String,
+    error: String,
+    attempts: i32,
+    retry_count: i32,
+    replay_pending: bool,
+    created_at_ms: i64,
+    last_attempt_ms: i64,
+    next_retry_at_ms: Option<i64>,
+}
+
+impl From<DlqRow> for DlqRecord {
+    fn from(r: DlqRow) -> Self {
+        Self {
+            id: r.id,
+            alert_id: r.alert_id,
+            notifier_id: r.notifier_id,
+            notifier: r.notifier,
+            error: r.error,
+            attempts: r.attempts,
+            retry_count: r.retry_count,
+            replay_pending: r.replay_pending,
+            created_at_ms: r.created_at_ms,
+            last_attempt_ms: r.last_attempt_ms,
+            next_retry_at_ms: r.next_retry_at_ms,
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use sentinel_common::escalation::{EscalationPolicy, Route};
+use sqlx::PgPool;
+
+pub struct ActiveEscalation {
+    pub fingerprint: String,
+    pub policy_id: String,
+    pub alert_id: String,
+    pub rule_id: String,
+    pub step: i32,
+    pub notified: Vec<String>,
+    pub started_at_ms: i64,
+    pub next_at_ms: Option<i64>,
+    pub stopped_reason: Option<String>,
+}
+
+pub struct EscalationRepo {
+    pool: PgPool,
+}
+
+impl EscalationRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn insert(&self, p: &EscalationPolicy) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO escalation_policies (id, name, description, steps)
+             VALUES ($1, $2, $3, $4)",
+        )
+        .bind(&p.id)
+        .bind(&p.name)
+        .bind(&p.description)
+        .bind(steps_json(p))
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn update(&self, p: &EscalationPolicy) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE escalation_policies
+             SET name = $2, description = $3, steps = $4, updated_at = NOW()
+             WHERE id = $1",
+        )
+        .bind(&p.id)
+        .bind(&p.name)
+        .bind(&p.description)
+        .bind(steps_json(p))
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn get(&self, id: &str) -> Result<Option<EscalationPolicy>, sqlx::Error> {
+        let row = sqlx::query_as::<_, PolicyRow>(
+            "SELECT id, name, description, steps FROM escalation_policies WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(row.map(Into::into))
+    }
+
+    pub async fn list(&self) -> Result<Vec<EscalationPolicy>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, PolicyRow>(
+            "SELECT id, name, description, steps FROM escalation_policies ORDER BY name",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    pub async fn delete(&self, id: &str) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let result = sqlx::query("DELETE FROM escalation_policies WHERE id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM alert_escalations WHERE policy_id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn routes(&self) -> Result<Option<Route>, sqlx::Error> {
+        let tree: Option<serde_json::Value> =
+            sqlx::query_scalar("SELECT tree FROM escalation_routes WHERE id = 'default'")
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(tree.and_then(|t| serde_json::from_value(t).ok()))
+    }
+
+    pub async fn put_routes(&self, tree: &Route) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO escalation_routes (id, tree) VALUES ('default', $1)
+             ON CONFLICT (id) DO UPDATE SET tree = EXCLUDED.tree, updated_at = NOW()",
+        )
+        .bind(serde_json::to_value(tree).unwrap_or_default())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn list_active(&self) -> Result<Vec<ActiveEscalation>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, ActiveRow>(
+            "SELECT fingerprint, policy_id, alert_id, rule_id, step, notified,
+                    (EXTRACT(EPOCH FROM started_at) * 1000)::bigint AS started_at_ms,
+                    (EXTRACT(EPOCH FROM next_at) * 1000)::bigint AS next_at_ms,
+                    stopped_reason
+             FROM alert_escalations
+             ORDER BY started_at DESC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+}
+
+fn steps_json(p: &EscalationPolicy) -> serde_json::Value {
+    serde_json::to_value(&p.steps).unwrap_or_default()
+}
+
+#[derive(sqlx::FromRow)]
+struct PolicyRow {
+    id: String,
+    name: String,
+    description: String,
+    steps: serde_json::Value,
+}
+
+impl From<PolicyRow> for EscalationPolicy {
+    fn from(r: PolicyRow) -> Self {
+        Self {
+            id: r.id,
+            name: r.name,
+            description: r.description,
+            steps: serde_json::from_value(r.steps).unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct ActiveRow {
+    fingerprint: String,
+    policy_id: String,
+    alert_id: String,
+    rule_id: String,
+    step: i32,
+    notified: serde_json::Value,
+    started_at_ms: i64,
+    next_at_ms: Option<i64>,
+    stopped_reason: Option<String>,
+}
+
+impl From<ActiveRow> for ActiveEscalation {
+    fn from(r: ActiveRow) -> Self {
+        Self {
+            fingerprint: r.fingerprint,
+            policy_id: r.policy_id,
+            alert_id: r.alert_id,
+            rule_id: r.rule_id,
+            step: r.step,
+            notified: serde_json::from_value(r.notified).unwrap_or_default(),
+            started_at_ms: r.started_at_ms,
+            next_at_ms: r.next_at_ms,
+            stopped_reason: r.stopped_reason,
+        }
+    }
+}
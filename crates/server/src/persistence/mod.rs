@@ -1,11 +1,16 @@
 mod agent_repo;
+mod dlq_repo;
+mod escalation_repo;
 mod metrics_repo;
 mod notification_history_repo;
 mod notifier_repo;
 mod pg_pool;
 mod rule_repo;
+mod silence_repo;
 
 pub use agent_repo::AgentRepo;
+pub use dlq_repo::{DlqFilter, DlqRecord, DlqRepo};
+pub use escalation_repo::{ActiveEscalation, EscalationRepo};
 pub use metrics_repo::MetricsQueryRepo;
 pub use notification_history_repo::{
     HistoryStats, NotificationHistoryRecord, NotificationHistoryRepo,
@@ -13,3 +18,4 @@ pub use notification_history_repo::{
 pub use notifier_repo::{NotifierConfigRecord, NotifierRepo};
 pub use pg_pool::create_pool;
 pub use rule_repo::RuleRepo;
+pub use silence_repo::SilenceRepo;
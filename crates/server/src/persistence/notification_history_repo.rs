@@ -109,7 +109,8 @@ impl NotificationHistoryRepo {
             "SELECT
                 COUNT(*)::bigint AS total,
                 COUNT(*) FILTER (WHERE status = 'sent')::bigint AS sent,
-                COUNT(*) FILTER (WHERE status = 'failed')::bigint AS failed,
+                COUNT(*) FILTER (WHERE status IN ('failed', 'dead_lettered'))::bigint AS failed,
+                COUNT(*) FILTER (WHERE status = 'dead_lettered')::bigint AS dead_lettered,
                 COALESCE(AVG(duration_ms) FILTER (WHERE status = 'sent'), 0)::bigint AS avg_duration_ms
              FROM notification_history",
         )
@@ -120,6 +121,7 @@ impl NotificationHistoryRepo {
             total: row.total,
             sent: row.sent,
             failed: row.failed,
+            dead_lettered: row.dead_lettered,
             avg_duration_ms: row.avg_duration_ms,
         })
     }
@@ -129,6 +131,7 @@ pub struct HistoryStats {
     pub total: i64,
     pub sent: i64,
     pub failed: i64,
+    pub dead_lettered: i64,
     pub avg_duration_ms: i64,
 }
 
@@ -166,5 +169,6 @@ struct StatsRow {
     total: i64,
     sent: i64,
     failed: i64,
+    dead_lettered: i64,
     avg_duration_ms: i64,
 }
@@ -1,3 +1,4 @@
+use sentinel_common::envelope::Envelope;
 use sqlx::PgPool;
 
 pub struct NotifierConfigRecord {
@@ -11,11 +12,33 @@ pub struct NotifierConfigRecord {
 
 pub struct NotifierRepo {
     pool: PgPool,
+    envelope: Envelope,
 }
 
 impl NotifierRepo {
-    pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+    pub fn new(pool: PgPool, envelope: Envelope) -> Self {
+        Self { pool, envelope }
+    }
+
+    fn seal(&self, config: &serde_json::Value) -> Result<serde_json::Value, sqlx::Error> {
+        self.envelope
+            .seal_config(config)
+            .map_err(|e| sqlx::Error::Encode(Box::new(e)))
+    }
+
+    fn open(&self, row: NotifierRow) -> Result<NotifierConfigRecord, sqlx::Error> {
+        let config = self
+            .envelope
+            .open_config(&row.config)
+            .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+        Ok(NotifierConfigRecord {
+            config,
+            ..row.into()
+        })
+    }
+
+    fn open_all(&self, rows: Vec<NotifierRow>) -> Result<Vec<NotifierConfigRecord>, sqlx::Error> {
+        rows.into_iter().map(|row| self.open(row)).collect()
     }
 
     pub async fn insert(&self, r: &NotifierConfigRecord) -> Result<(), sqlx::Error> {
@@ -27,7 +50,7 @@ impl NotifierRepo {
         .bind(&r.id)
         .bind(&r.name)
         .bind(&r.ntype)
-        .bind(&r.config)
+        .bind(self.seal(&r.config)?)
         .bind(r.enabled)
         .bind(r.created_at_ms)
         .execute(&self.pool)
@@ -44,7 +67,7 @@ impl NotifierRepo {
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(rows.into_iter().map(Into::into).collect())
+        self.open_all(rows)
     }
 
     pub async fn get(&self, id: &str) -> Result<Option<NotifierConfigRecord>, sqlx::Error> {
@@ -57,7 +80,7 @@ impl NotifierRepo {
         .fetch_optional(&self.pool)
         .await?;
 
-        Ok(row.map(Into::into))
+        row.map(|row| self.open(row)).transpose()
     }
 
     pub async fn get_by_ids(
@@ -76,7 +99,7 @@ impl NotifierRepo {
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(rows.into_iter().map(Into::into).collect())
+        self.open_all(rows)
     }
 
     pub async fn delete(&self, id: &str) -> Result<bool, sqlx::Error> {
@@ -92,13 +115,49 @@ impl NotifierRepo {
             "UPDATE notifier_configs SET name = $1, config = $2, enabled = $3 WHERE id = $4",
         )
         .bind(&r.name)
-        .bind(&r.config)
+        .bind(self.seal(&r.config)?)
         .bind(r.enabled)
         .bind(&r.id)
         .execute(&self.pool)
         .await?;
         Ok(())
     }
+
+    pub async fn reencrypt(&self, dry_run: bool) -> Result<u64, sqlx::Error> {
+        let ids: Vec<String> = sqlx::query_scalar("SELECT id FROM notifier_configs")
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut changed = 0;
+        for id in ids {
+            let mut tx = self.pool.begin().await?;
+            let config: Option<serde_json::Value> =
+                sqlx::query_scalar("SELECT config FROM notifier_configs WHERE id = $1 FOR UPDATE")
+                    .bind(&id)
+                    .fetch_optional(&mut *tx)
+                    .await?;
+            let Some(config) = config else {
+                continue;
+            };
+            let Some(resealed) = self
+                .envelope
+                .reseal_config(&config)
+                .map_err(|e| sqlx::Error::Decode(Box::new(e)))?
+            else {
+                continue;
+            };
+            if !dry_run {
+                sqlx::query("UPDATE notifier_configs SET config = $1 WHERE id = $2")
+                    .bind(&resealed)
+                    .bind(&id)
+                    .execute(&mut *tx)
+                    .await?;
+                tx.commit().await?;
+            }
+            changed += 1;
+        }
+        Ok(changed)
+    }
 }
 
 #[derive(sqlx::FromRow)]
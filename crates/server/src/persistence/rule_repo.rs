@@ -14,14 +14,22 @@ impl RuleRepo {
     pub async fn insert(&self, r: &RuleRecord) -> Result<(), sqlx::Error> {
         let annotations = serde_json::to_value(&r.annotations).unwrap_or_default();
         let notifier_ids = serde_json::to_value(&r.notifier_ids).unwrap_or_default();
+        let label_matchers = serde_json::to_value(&r.label_matchers).unwrap_or_default();
+        let grouping = r
+            .grouping
+            .as_ref()
+            .and_then(|g| serde_json::to_value(g).ok());
         sqlx::query(
             r#"INSERT INTO alert_rules
                (id, name, agent_pattern, metric_name, condition, threshold,
                 for_duration_ms, severity, annotations, enabled, notifier_ids,
-                created_at, updated_at)
+                created_at, updated_at, label_matchers, aggregation, window_ms,
+                min_samples, expression, absent_for_ms, grouping,
+                escalation_policy_id)
                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11,
                        to_timestamp($12::double precision / 1000),
-                       to_timestamp($13::double precision / 1000))
+                       to_timestamp($13::double precision / 1000), $14, $15, $16, $17,
+                       $18, $19, $20, $21)
                ON CONFLICT (id) DO NOTHING"#,
         )
         .bind(&r.id)
@@ -37,6 +45,14 @@ impl RuleRepo {
         .bind(&notifier_ids)
         .bind(r.created_at_ms)
         .bind(r.updated_at_ms)
+        .bind(&label_matchers)
+        .bind(&r.aggregation)
+        .bind(r.window_ms)
+        .bind(r.min_samples as i32)
+        .bind(&r.expression)
+        .bind(r.absent_for_ms)
+        .bind(&grouping)
+        .bind(&r.escalation_policy_id)
         .execute(&self.pool)
         .await?;
         Ok(())
@@ -45,13 +61,21 @@ impl RuleRepo {
     pub async fn update(&self, r: &RuleRecord) -> Result<(), sqlx::Error> {
         let annotations = serde_json::to_value(&r.annotations).unwrap_or_default();
         let notifier_ids = serde_json::to_value(&r.notifier_ids).unwrap_or_default();
+        let label_matchers = serde_json::to_value(&r.label_matchers).unwrap_or_default();
+        let grouping = r
+            .grouping
+            .as_ref()
+            .and_then(|g| serde_json::to_value(g).ok());
         sqlx::query(
             r#"UPDATE alert_rules SET
                  name = $2, agent_pattern = $3, metric_name = $4,
                  condition = $5, threshold = $6, for_duration_ms = $7,
                  severity = $8, annotations = $9, enabled = $10,
                  notifier_ids = $11,
-                 updated_at = to_timestamp($12::double precision / 1000)
+                 updated_at = to_timestamp($12::double precision / 1000),
+                 label_matchers = $13, aggregation = $14, window_ms = $15,
+                 min_samples = $16, expression = $17, absent_for_ms = $18,
+                 grouping = $19, escalation_policy_id = $20
                WHERE id = $1"#,
         )
         .bind(&r.id)
@@ -66,6 +90,14 @@ impl RuleRepo {
         .bind(r.enabled)
         .bind(&notifier_ids)
         .bind(r.updated_at_ms)
+        .bind(&label_matchers)
+        .bind(&r.aggregation)
+        .bind(r.window_ms)
+        .bind(r.min_samples as i32)
+        .bind(&r.expression)
+        .bind(r.absent_for_ms)
+        .bind(&grouping)
+        .bind(&r.escalation_policy_id)
         .execute(&self.pool)
         .await?;
         Ok(())
@@ -83,6 +115,8 @@ impl RuleRepo {
         let rows = sqlx::query_as::<_, RuleRow>(
             "SELECT id, name, agent_pattern, metric_name, condition, threshold,
                     for_duration_ms, severity, annotations, enabled, notifier_ids,
+                    label_matchers, aggregation, window_ms, min_samples, expression,
+                    absent_for_ms, grouping, escalation_policy_id,
                     EXTRACT(EPOCH FROM created_at)::bigint * 1000 AS created_at_ms,
                     EXTRACT(EPOCH FROM updated_at)::bigint * 1000 AS updated_at_ms
              FROM alert_rules",
@@ -96,18 +130,28 @@ impl RuleRepo {
                 serde_json::from_value(row.annotations).unwrap_or_default();
             let notifier_ids: Vec<String> =
                 serde_json::from_value(row.notifier_ids).unwrap_or_default();
+            let label_matchers = serde_json::from_value(row.label_matchers).unwrap_or_default();
+            let grouping = row.grouping.and_then(|v| serde_json::from_value(v).ok());
             store.insert(RuleRecord {
                 id: row.id,
                 name: row.name,
                 agent_pattern: row.agent_pattern,
                 metric_name: row.metric_name,
+                label_matchers,
+                expression: row.expression,
+                absent_for_ms: row.absent_for_ms,
                 condition: row.condition,
                 threshold: row.threshold,
+                aggregation: row.aggregation,
+                window_ms: row.window_ms,
+                min_samples: row.min_samples as i64,
                 for_duration_ms: row.for_duration_ms,
                 severity: row.severity,
                 annotations,
                 enabled: row.enabled,
                 notifier_ids,
+                grouping,
+                escalation_policy_id: row.escalation_policy_id,
                 created_at_ms: row.created_at_ms,
                 updated_at_ms: row.updated_at_ms,
             });
@@ -129,6 +173,14 @@ struct RuleRow {
     annotations: serde_json::Value,
     enabled: bool,
     notifier_ids: serde_json::Value,
+    label_matchers: serde_json::Value,
+    aggregation: String,
+    window_ms: i64,
+    min_samples: i32,
+    expression: Option<String>,
+    absent_for_ms: Option<i64>,
+    grouping: Option<serde_json::Value>,
+    escalation_policy_id: Option<String>,
     created_at_ms: i64,
     updated_at_ms: i64,
 }
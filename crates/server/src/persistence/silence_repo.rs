@@ -0,0 +1,136 @@
+use sentinel_common::labels::LabelMatcher;
+use sentinel_common::silence::{Recurrence, Silence};
+use sqlx::PgPool;
+
+const COLUMNS: &str = "id, agent_pattern, rule_id, severity, label_matchers,
+       (EXTRACT(EPOCH FROM starts_at) * 1000)::bigint AS starts_at_ms,
+       (EXTRACT(EPOCH FROM ends_at) * 1000)::bigint AS ends_at_ms,
+       recurrence, comment, created_by";
+
+pub struct SilenceRepo {
+    pool: PgPool,
+}
+
+impl SilenceRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn insert(&self, s: &Silence) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"INSERT INTO silences
+               (id, agent_pattern, rule_id, severity, label_matchers, starts_at, ends_at,
+                recurrence, comment, created_by)
+               VALUES ($1, $2, $3, $4, $5,
+                       to_timestamp($6::double precision / 1000),
+                       to_timestamp($7::double precision / 1000),
+                       $8, $9, $10)"#,
+        )
+        .bind(&s.id)
+        .bind(&s.agent_pattern)
+        .bind(&s.rule_id)
+        .bind(&s.severity)
+        .bind(matchers_json(s))
+        .bind(s.starts_at_ms)
+        .bind(s.ends_at_ms)
+        .bind(recurrence_json(s))
+        .bind(&s.comment)
+        .bind(&s.created_by)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn update(&self, s: &Silence) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"UPDATE silences
+               SET agent_pattern = $2, rule_id = $3, severity = $4, label_matchers = $5,
+                   starts_at = to_timestamp($6::double precision / 1000),
+                   ends_at = to_timestamp($7::double precision / 1000),
+                   recurrence = $8, comment = $9, updated_at = NOW()
+               WHERE id = $1"#,
+        )
+        .bind(&s.id)
+        .bind(&s.agent_pattern)
+        .bind(&s.rule_id)
+        .bind(&s.severity)
+        .bind(matchers_json(s))
+        .bind(s.starts_at_ms)
+        .bind(s.ends_at_ms)
+        .bind(recurrence_json(s))
+        .bind(&s.comment)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn get(&self, id: &str) -> Result<Option<Silence>, sqlx::Error> {
+        let sql = format!("SELECT {COLUMNS} FROM silences WHERE id = $1");
+        let row = sqlx::query_as::<_, SilenceRow>(&sql)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.map(Into::into))
+    }
+
+    pub async fn list(&self) -> Result<Vec<Silence>, sqlx::Error> {
+        let sql = format!("SELECT {COLUMNS} FROM silences ORDER BY starts_at DESC");
+        let rows = sqlx::query_as::<_, SilenceRow>(&sql)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    pub async fn delete(&self, id: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM silences WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+fn matchers_json(s: &Silence) -> serde_json::Value {
+    serde_json::to_value(&s.label_matchers).unwrap_or_default()
+}
+
+fn recurrence_json(s: &Silence) -> Option<serde_json::Value> {
+    s.recurrence
+        .as_ref()
+        .and_then(|r| serde_json::to_value(r).ok())
+}
+
+#[derive(sqlx::FromRow)]
+struct SilenceRow {
+    id: String,
+    agent_pattern: Option<String>,
+    rule_id: Option<String>,
+    severity: Option<String>,
+    label_matchers: serde_json::Value,
+    starts_at_ms: i64,
+    ends_at_ms: Option<i64>,
+    recurrence: Option<serde_json::Value>,
+    comment: String,
+    created_by: String,
+}
+
+impl From<SilenceRow> for Silence {
+    fn from(r: SilenceRow) -> Self {
+        let label_matchers: Vec<LabelMatcher> =
+            serde_json::from_value(r.label_matchers).unwrap_or_default();
+        let recurrence: Option<Recurrence> =
+            r.recurrence.and_then(|v| serde_json::from_value(v).ok());
+        Self {
+            id: r.id,
+            agent_pattern: r.agent_pattern,
+            rule_id: r.rule_id,
+            severity: r.severity,
+            label_matchers,
+            starts_at_ms: r.starts_at_ms,
+            ends_at_ms: r.ends_at_ms,
+            recurrence,
+            comment: r.comment,
+            created_by: r.created_by,
+        }
+    }
+}
@@ -55,6 +55,7 @@ pub async fn handle_bootstrap(
         agent_version: agent_version.into(),
         registered_at_ms: now_ms,
         deprecated_keys: Vec::new(),
+        pending_key: None,
         last_seen: None,
     };
 
@@ -0,0 +1,44 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::Deserialize;
+
+use crate::rest::AppState;
+use crate::stream::commands::{send_command, CommandAction, CommandOutcome};
+
+use super::agent_config::ack_timeout;
+
+#[derive(Deserialize)]
+pub struct CommandRequest {
+    #[serde(flatten)]
+    pub action: CommandAction,
+    pub timeout_secs: Option<u64>,
+}
+
+pub async fn exec_command(
+    State(state): State<AppState>,
+    Path(agent_id): Path<String>,
+    Json(body): Json<CommandRequest>,
+) -> Result<Json<CommandOutcome>, StatusCode> {
+    if matches!(
+        body.action,
+        CommandAction::UpdateInterval {
+            interval_seconds: 0
+        }
+    ) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if !state.registry.contains(&agent_id) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let outcome = send_command(
+        &state.registry,
+        &agent_id,
+        body.action,
+        ack_timeout(body.timeout_secs),
+    )
+    .await;
+
+    Ok(Json(outcome))
+}
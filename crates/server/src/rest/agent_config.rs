@@ -0,0 +1,100 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::rest::AppState;
+use crate::stream::config_push::{push_config, push_config_to_pattern, ConfigPushResult};
+
+const DEFAULT_ACK_TIMEOUT_SECS: u64 = 10;
+const MAX_ACK_TIMEOUT_SECS: u64 = 120;
+
+#[derive(Deserialize)]
+pub struct PushConfigRequest {
+    pub config_yaml: String,
+    pub version: Option<i64>,
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct PushGroupConfigRequest {
+    pub agent_pattern: String,
+    pub config_yaml: String,
+    pub version: Option<i64>,
+    pub timeout_secs: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct PushGroupConfigResponse {
+    pub version: i64,
+    pub results: Vec<ConfigPushResult>,
+}
+
+pub async fn push_agent_config(
+    State(state): State<AppState>,
+    Path(agent_id): Path<String>,
+    Json(body): Json<PushConfigRequest>,
+) -> Result<Json<ConfigPushResult>, StatusCode> {
+    if body.config_yaml.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if !state.registry.contains(&agent_id) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let version = body.version.unwrap_or_else(current_time_ms);
+    let result = push_config(
+        &state.registry,
+        &agent_id,
+        version,
+        body.config_yaml.into_bytes(),
+        ack_timeout(body.timeout_secs),
+    )
+    .await;
+
+    Ok(Json(result))
+}
+
+pub async fn push_group_config(
+    State(state): State<AppState>,
+    Json(body): Json<PushGroupConfigRequest>,
+) -> Result<Json<PushGroupConfigResponse>, StatusCode> {
+    if body.config_yaml.trim().is_empty() || body.agent_pattern.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let version = body.version.unwrap_or_else(current_time_ms);
+    let results = push_config_to_pattern(
+        &state.registry,
+        &body.agent_pattern,
+        version,
+        body.config_yaml.into_bytes(),
+        ack_timeout(body.timeout_secs),
+    )
+    .await;
+
+    tracing::info!(
+        target: "rest",
+        pattern = %body.agent_pattern,
+        version,
+        agents = results.len(),
+        "Group config push completed"
+    );
+
+    Ok(Json(PushGroupConfigResponse { version, results }))
+}
+
+pub(super) fn ack_timeout(secs: Option<u64>) -> Duration {
+    let secs = secs
+        .unwrap_or(DEFAULT_ACK_TIMEOUT_SECS)
+        .clamp(1, MAX_ACK_TIMEOUT_SECS);
+    Duration::from_secs(secs)
+}
+
+fn current_time_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
@@ -0,0 +1,93 @@
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::Json;
+use serde::Serialize;
+
+use sentinel_common::proto::BatchAckStatus;
+
+use crate::rest::AppState;
+use crate::stream::metrics_handler::{ingest_batch, MetricsHandlerCtx};
+
+#[derive(Serialize)]
+pub struct IngestResponse {
+    pub batch_id: String,
+    pub status: &'static str,
+    pub message: &'static str,
+}
+
+pub async fn ingest_metrics(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<IngestResponse>, StatusCode> {
+    let broker = state
+        .broker
+        .as_ref()
+        .ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    let agent_id = header(&headers, "x-agent-id").ok_or(StatusCode::UNAUTHORIZED)?;
+    let signature = header(&headers, "x-signature").ok_or(StatusCode::UNAUTHORIZED)?;
+    let key_id = header(&headers, "x-key-id").ok_or(StatusCode::UNAUTHORIZED)?;
+
+    let batch = sentinel_common::batch_json::decode(&body).map_err(|e| {
+        tracing::warn!(target: "rest", %agent_id, error = %e, "Invalid metrics payload");
+        StatusCode::BAD_REQUEST
+    })?;
+
+    if batch.agent_id != agent_id {
+        tracing::warn!(target: "auth", %agent_id, body_agent = %batch.agent_id, "Agent id mismatch on HTTP ingest");
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+
+    if state.replay_window_ms > 0 && batch.created_at_ms > 0 {
+        let age = (current_time_ms() - batch.created_at_ms).abs();
+        if age > state.replay_window_ms {
+            tracing::warn!(target: "auth", %agent_id, age, "HTTP batch outside replay window");
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+    }
+
+    let ctx = MetricsHandlerCtx {
+        agents: &state.agents,
+        idempotency: &state.idempotency,
+        broker: broker.as_ref(),
+        grace_period_ms: state.key_grace_period_ms,
+        metrics: &state.metrics,
+    };
+    let outcome = ingest_batch(key_id, &batch, Some(signature), &ctx).await;
+
+    match outcome.status {
+        BatchAckStatus::BatchAccepted => {
+            tracing::debug!(target: "data", %agent_id, batch_id = %batch.batch_id, "Batch accepted over HTTP");
+            Ok(Json(IngestResponse {
+                batch_id: batch.batch_id,
+                status: "accepted",
+                message: outcome.message,
+            }))
+        }
+        BatchAckStatus::BatchRetry => Err(StatusCode::SERVICE_UNAVAILABLE),
+        BatchAckStatus::BatchRejected => {
+            tracing::warn!(target: "auth", %agent_id, reason = outcome.message, "HTTP batch rejected");
+            if batch.batch_id.is_empty() {
+                Err(StatusCode::BAD_REQUEST)
+            } else {
+                Err(StatusCode::UNAUTHORIZED)
+            }
+        }
+    }
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .filter(|v| !v.is_empty())
+}
+
+fn current_time_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
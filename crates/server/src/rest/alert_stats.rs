@@ -0,0 +1,434 @@
+use std::collections::HashMap;
+
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use sentinel_common::flapping::FlapPolicy;
+use serde::{Deserialize, Serialize};
+
+use crate::rest::AppState;
+
+const DEFAULT_WINDOW_SECS: i64 = 86_400;
+const MAX_WINDOW_SECS: i64 = 90 * 86_400;
+const MAX_ROWS: i64 = 50_000;
+
+#[derive(Deserialize)]
+pub struct IncidentQuery {
+    pub fingerprint: Option<String>,
+    pub rule_id: Option<String>,
+    pub agent_id: Option<String>,
+    pub window_secs: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct StatsQuery {
+    pub by: Option<String>,
+    pub window_secs: Option<i64>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+pub struct FlappingQuery {
+    pub window_secs: Option<u64>,
+    pub threshold: Option<usize>,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct StateRow {
+    pub id: String,
+    pub fingerprint: String,
+    pub rule_id: String,
+    pub rule_name: String,
+    pub agent_id: String,
+    pub metric_name: String,
+    pub severity: String,
+    pub status: String,
+    pub value: f64,
+    pub labels: serde_json::Value,
+    pub at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Incident {
+    pub fingerprint: String,
+    pub rule_id: String,
+    pub rule_name: String,
+    pub agent_id: String,
+    pub metric_name: String,
+    pub labels: serde_json::Value,
+    pub severity: String,
+    pub firing_alert_id: String,
+    pub resolved_alert_id: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub duration_secs: i64,
+    pub value: f64,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct StatsGroup {
+    pub key: String,
+    pub name: String,
+    pub incidents: usize,
+    pub resolved: usize,
+    pub open: usize,
+    pub mttr_secs: Option<i64>,
+    pub max_duration_secs: Option<i64>,
+    pub per_day: f64,
+}
+
+#[derive(Serialize)]
+pub struct StatsResponse {
+    pub by: String,
+    pub window_secs: i64,
+    pub incidents: usize,
+    pub mttr_secs: Option<i64>,
+    pub groups: Vec<StatsGroup>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FlappingSeries {
+    pub fingerprint: String,
+    pub rule_id: String,
+    pub rule_name: String,
+    pub agent_id: String,
+    pub metric_name: String,
+    pub labels: serde_json::Value,
+    pub changes: usize,
+    pub last_change: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+pub struct FlappingResponse {
+    pub window_secs: u64,
+    pub threshold: usize,
+    pub series: Vec<FlappingSeries>,
+}
+
+pub async fn list_incidents(
+    State(state): State<AppState>,
+    Query(q): Query<IncidentQuery>,
+) -> Result<Json<Vec<Incident>>, StatusCode> {
+    let window_secs = window(q.window_secs)?;
+    let pool = state.pool.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let limit = q.limit.unwrap_or(100).clamp(1, 1000) as usize;
+
+    let rows = sqlx::query_as::<_, StateRow>(
+        "SELECT id, fingerprint, rule_id, rule_name, agent_id, metric_name, severity, status,
+                value, labels, COALESCE(resolved_at, fired_at) AS at
+         FROM alerts
+         WHERE COALESCE(resolved_at, fired_at) >= NOW() - make_interval(secs => $1)
+           AND ($2::text IS NULL OR fingerprint = $2)
+           AND ($3::text IS NULL OR rule_id = $3)
+           AND ($4::text IS NULL OR agent_id = $4)
+         ORDER BY fingerprint, at, created_at
+         LIMIT $5",
+    )
+    .bind(window_secs as f64)
+    .bind(&q.fingerprint)
+    .bind(&q.rule_id)
+    .bind(&q.agent_id)
+    .bind(MAX_ROWS)
+    .fetch_all(pool)
+    .await
+    .map_err(query_failed)?;
+
+    let mut incidents = pair_incidents(&rows, Utc::now());
+    incidents.sort_by_key(|i| std::cmp::Reverse(i.started_at));
+    incidents.truncate(limit);
+    Ok(Json(incidents))
+}
+
+pub async fn alert_stats(
+    State(state): State<AppState>,
+    Query(q): Query<StatsQuery>,
+) -> Result<Json<StatsResponse>, StatusCode> {
+    let window_secs = window(q.window_secs)?;
+    let by = q.by.unwrap_or_else(|| "rule".into());
+    if by != "rule" && by != "agent" {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let pool = state.pool.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    let rows = fetch_window(pool, window_secs).await?;
+    let incidents = pair_incidents(&rows, Utc::now());
+    let mut groups = summarize(&incidents, &by, window_secs);
+    if let Some(limit) = q.limit {
+        groups.truncate(limit);
+    }
+
+    Ok(Json(StatsResponse {
+        by,
+        window_secs,
+        incidents: incidents.len(),
+        mttr_secs: mttr(incidents.iter()),
+        groups,
+    }))
+}
+
+pub async fn list_flapping(
+    State(state): State<AppState>,
+    Query(q): Query<FlappingQuery>,
+) -> Result<Json<FlappingResponse>, StatusCode> {
+    let defaults = FlapPolicy::default();
+    let policy = FlapPolicy {
+        window_secs: q.window_secs.unwrap_or(defaults.window_secs),
+        threshold: q.threshold.unwrap_or(defaults.threshold),
+    };
+    let window_secs = window(Some(policy.window_secs as i64))?;
+    let pool = state.pool.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    let rows = fetch_window(pool, window_secs).await?;
+    Ok(Json(FlappingResponse {
+        window_secs: policy.window_secs,
+        threshold: policy.threshold,
+        series: flapping(&rows, &policy, Utc::now()),
+    }))
+}
+
+async fn fetch_window(pool: &sqlx::PgPool, window_secs: i64) -> Result<Vec<StateRow>, StatusCode> {
+    sqlx::query_as::<_, StateRow>(
+        "SELECT id, fingerprint, rule_id, rule_name, agent_id, metric_name, severity, status,
+                value, labels, COALESCE(resolved_at, fired_at) AS at
+         FROM alerts
+         WHERE COALESCE(resolved_at, fired_at) >= NOW() - make_interval(secs => $1)
+         ORDER BY fingerprint, at, created_at
+         LIMIT $2",
+    )
+    .bind(window_secs as f64)
+    .bind(MAX_ROWS)
+    .fetch_all(pool)
+    .await
+    .map_err(query_failed)
+}
+
+fn window(window_secs: Option<i64>) -> Result<i64, StatusCode> {
+    let secs = window_secs.unwrap_or(DEFAULT_WINDOW_SECS);
+    if !(1..=MAX_WINDOW_SECS).contains(&secs) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    Ok(secs)
+}
+
+fn query_failed(e: sqlx::Error) -> StatusCode {
+    tracing::error!(target: "rest", error = %e, "alert stats query failed");
+    StatusCode::INTERNAL_SERVER_ERROR
+}
+
+pub(crate) fn pair_incidents(rows: &[StateRow], now: DateTime<Utc>) -> Vec<Incident> {
+    let mut incidents = Vec::new();
+    let mut open: Option<Incident> = None;
+
+    for row in rows {
+        if open
+            .as_ref()
+            .is_some_and(|i| i.fingerprint != row.fingerprint)
+        {
+            incidents.extend(open.take());
+        }
+        match (row.status.as_str(), open.as_mut()) {
+            ("firing", None) => {
+                open = Some(Incident {
+                    fingerprint: row.fingerprint.clone(),
+                    rule_id: row.rule_id.clone(),
+                    rule_name: row.rule_name.clone(),
+                    agent_id: row.agent_id.clone(),
+                    metric_name: row.metric_name.clone(),
+                    labels: row.labels.clone(),
+                    severity: row.severity.clone(),
+                    firing_alert_id: row.id.clone(),
+                    resolved_alert_id: None,
+                    started_at: row.at,
+                    resolved_at: None,
+                    duration_secs: 0,
+                    value: row.value,
+                });
+            }
+            ("resolved", Some(incident)) => {
+                incident.resolved_alert_id = Some(row.id.clone());
+                incident.resolved_at = Some(row.at);
+                incidents.extend(open.take());
+            }
+            _ => {}
+        }
+    }
+    incidents.extend(open);
+
+    for incident in &mut incidents {
+        let end = incident.resolved_at.unwrap_or(now);
+        incident.duration_secs = (end - incident.started_at).num_seconds().max(0);
+    }
+    incidents
+}
+
+pub(crate) fn summarize(incidents: &[Incident], by: &str, window_secs: i64) -> Vec<StatsGroup> {
+    let mut buckets: HashMap<&str, Vec<&Incident>> = HashMap::new();
+    for incident in incidents {
+        let key = match by {
+            "agent" => incident.agent_id.as_str(),
+            _ => incident.rule_id.as_str(),
+        };
+        buckets.entry(key).or_default().push(incident);
+    }
+
+    let days = window_secs as f64 / 86_400.0;
+    let mut groups: Vec<StatsGroup> = buckets
+        .into_iter()
+        .map(|(key, items)| {
+            let resolved: Vec<&&Incident> =
+                items.iter().filter(|i| i.resolved_at.is_some()).collect();
+            StatsGroup {
+                key: key.to_string(),
+                name: match by {
+                    "agent" => key.to_string(),
+                    _ => items[0].rule_name.clone(),
+                },
+                incidents: items.len(),
+                resolved: resolved.len(),
+                open: items.len() - resolved.len(),
+                mttr_secs: mttr(resolved.iter().map(|i| **i)),
+                max_duration_secs: resolved.iter().map(|i| i.duration_secs).max(),
+                per_day: (items.len() as f64 / days * 100.0).round() / 100.0,
+            }
+        })
+        .collect();
+    groups.sort_by(|a, b| b.incidents.cmp(&a.incidents).then(a.key.cmp(&b.key)));
+    groups
+}
+
+fn mttr<'a>(incidents: impl Iterator<Item = &'a Incident>) -> Option<i64> {
+    let durations: Vec<i64> = incidents
+        .filter(|i| i.resolved_at.is_some())
+        .map(|i| i.duration_secs)
+        .collect();
+    if durations.is_empty() {
+        return None;
+    }
+    Some(durations.iter().sum::<i64>() / durations.len() as i64)
+}
+
+pub(crate) fn flapping(
+    rows: &[StateRow],
+    policy: &FlapPolicy,
+    now: DateTime<Utc>,
+) -> Vec<FlappingSeries> {
+    let mut series: Vec<FlappingSeries> = Vec::new();
+    for chunk in rows.chunk_by(|a, b| a.fingerprint == b.fingerprint) {
+        let changes: Vec<i64> = chunk.iter().map(|r| r.at.timestamp_millis()).collect();
+        if !policy.is_flapping(&changes, now.timestamp_millis()) {
+            continue;
+        }
+        let last = &chunk[chunk.len() - 1];
+        series.push(FlappingSeries {
+            fingerprint: last.fingerprint.clone(),
+            rule_id: last.rule_id.clone(),
+            rule_name: last.rule_name.clone(),
+            agent_id: last.agent_id.clone(),
+            metric_name: last.metric_name.clone(),
+            labels: last.labels.clone(),
+            changes: policy.changes_within(&changes, now.timestamp_millis()),
+            last_change: last.at,
+        });
+    }
+    series.sort_by_key(|s| std::cmp::Reverse(s.changes));
+    series
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn row(id: &str, fp: &str, status: &str, secs: i64) -> StateRow {
+        StateRow {
+            id: id.into(),
+            fingerprint: fp.into(),
+            rule_id: format!("rule-{fp}"),
+            rule_name: "cpu high".into(),
+            agent_id: "web-1".into(),
+            metric_name: "cpu".into(),
+            severity: "warning".into(),
+            status: status.into(),
+            value: 95.0,
+            labels: serde_json::json!({}),
+            at: Utc.timestamp_opt(secs, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn pairs_firing_and_resolved_rows() {
+        let rows = vec![
+            row("r0", "a", "resolved", 50),
+            row("f1", "a", "firing", 100),
+            row("f1b", "a", "firing", 110),
+            row("r1", "a", "resolved", 160),
+            row("f2", "a", "firing", 200),
+            row("f3", "b", "firing", 120),
+            row("r3", "b", "resolved", 180),
+        ];
+        let now = Utc.timestamp_opt(300, 0).unwrap();
+        let incidents = pair_incidents(&rows, now);
+        assert_eq!(incidents.len(), 3);
+
+        assert_eq!(incidents[0].firing_alert_id, "f1");
+        assert_eq!(incidents[0].resolved_alert_id.as_deref(), Some("r1"));
+        assert_eq!(incidents[0].duration_secs, 60);
+
+        assert_eq!(incidents[1].firing_alert_id, "f2");
+        assert!(incidents[1].resolved_at.is_none());
+        assert_eq!(incidents[1].duration_secs, 100);
+
+        assert_eq!(incidents[2].fingerprint, "b");
+        assert_eq!(incidents[2].duration_secs, 60);
+    }
+
+    #[test]
+    fn summarizes_mttr_and_frequency() {
+        let rows = vec![
+            row("f1", "a", "firing", 0),
+            row("r1", "a", "resolved", 60),
+            row("f2", "a", "firing", 100),
+            row("r2", "a", "resolved", 220),
+            row("f3", "b", "firing", 100),
+        ];
+        let incidents = pair_incidents(&rows, Utc.timestamp_opt(400, 0).unwrap());
+
+        let by_rule = summarize(&incidents, "rule", 86_400);
+        assert_eq!(by_rule.len(), 2);
+        assert_eq!(by_rule[0].key, "rule-a");
+        assert_eq!(by_rule[0].incidents, 2);
+        assert_eq!(by_rule[0].mttr_secs, Some(90));
+        assert_eq!(by_rule[0].max_duration_secs, Some(120));
+        assert_eq!(by_rule[0].per_day, 2.0);
+        assert_eq!(by_rule[1].open, 1);
+        assert_eq!(by_rule[1].mttr_secs, None);
+
+        let by_agent = summarize(&incidents, "agent", 43_200);
+        assert_eq!(by_agent.len(), 1);
+        assert_eq!(by_agent[0].incidents, 3);
+        assert_eq!(by_agent[0].per_day, 6.0);
+    }
+
+    #[test]
+    fn detects_flapping_series() {
+        let mut rows: Vec<StateRow> = (0..6)
+            .map(|i| {
+                let status = if i % 2 == 0 { "firing" } else { "resolved" };
+                row(&format!("a{i}"), "a", status, 1000 + i * 60)
+            })
+            .collect();
+        rows.push(row("b0", "b", "firing", 1000));
+        rows.push(row("b1", "b", "resolved", 1100));
+
+        let policy = FlapPolicy {
+            window_secs: 3600,
+            threshold: 4,
+        };
+        let series = flapping(&rows, &policy, Utc.timestamp_opt(2000, 0).unwrap());
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].fingerprint, "a");
+        assert_eq!(series[0].changes, 6);
+    }
+}
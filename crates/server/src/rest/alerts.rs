@@ -1,10 +1,18 @@
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
-use axum::Json;
+use axum::{Extension, Json};
 use serde::{Deserialize, Serialize};
 
+use crate::middleware::AuthSubject;
 use crate::rest::AppState;
 
+const ALERT_SELECT: &str = "SELECT a.id, a.fingerprint, a.rule_id, a.rule_name, a.agent_id,
+        a.metric_name, a.severity, a.status, a.value, a.threshold, a.fired_at,
+        a.resolved_at, a.annotations, a.labels, a.created_at, a.suppressed_by,
+        k.acked_by, k.acked_at, k.comment AS ack_comment
+ FROM alerts a
+ LEFT JOIN alert_acks k ON k.alert_id = a.id";
+
 #[derive(Deserialize)]
 pub struct AlertQuery {
     pub status: Option<String>,
@@ -28,7 +36,26 @@ pub struct AlertRow {
     pub fired_at: chrono::DateTime<chrono::Utc>,
     pub resolved_at: Option<chrono::DateTime<chrono::Utc>>,
     pub annotations: serde_json::Value,
+    pub labels: serde_json::Value,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    pub suppressed_by: Option<String>,
+    pub acked_by: Option<String>,
+    pub acked_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub ack_comment: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+pub struct AckRequest {
+    #[serde(default)]
+    pub comment: String,
+}
+
+#[derive(Serialize)]
+pub struct AckResponse {
+    pub alert_id: String,
+    pub fingerprint: String,
+    pub acked_by: String,
+    pub comment: String,
 }
 
 pub async fn list_alerts(
@@ -41,51 +68,36 @@ pub async fn list_alerts(
 
     let rows = match (&q.status, &q.agent_id, &q.rule_id) {
         (Some(status), _, _) => {
-            sqlx::query_as::<_, AlertRow>(
-                "SELECT id, fingerprint, rule_id, rule_name, agent_id, metric_name,
-                        severity, status, value, threshold, fired_at, resolved_at,
-                        annotations, created_at
-                 FROM alerts WHERE status = $1
-                 ORDER BY created_at DESC LIMIT $2",
-            )
+            sqlx::query_as::<_, AlertRow>(&format!(
+                "{ALERT_SELECT} WHERE a.status = $1 ORDER BY a.created_at DESC LIMIT $2"
+            ))
             .bind(status)
             .bind(limit)
             .fetch_all(pool)
             .await
         }
         (_, Some(agent_id), _) => {
-            sqlx::query_as::<_, AlertRow>(
-                "SELECT id, fingerprint, rule_id, rule_name, agent_id, metric_name,
-                        severity, status, value, threshold, fired_at, resolved_at,
-                        annotations, created_at
-                 FROM alerts WHERE agent_id = $1
-                 ORDER BY created_at DESC LIMIT $2",
-            )
+            sqlx::query_as::<_, AlertRow>(&format!(
+                "{ALERT_SELECT} WHERE a.agent_id = $1 ORDER BY a.created_at DESC LIMIT $2"
+            ))
             .bind(agent_id)
             .bind(limit)
             .fetch_all(pool)
             .await
         }
         (_, _, Some(rule_id)) => {
-            sqlx::query_as::<_, AlertRow>(
-                "SELECT id, fingerprint, rule_id, rule_name, agent_id, metric_name,
-                        severity, status, value, threshold, fired_at, resolved_at,
-                        annotations, created_at
-                 FROM alerts WHERE rule_id = $1
-                 ORDER BY created_at DESC LIMIT $2",
-            )
+            sqlx::query_as::<_, AlertRow>(&format!(
+                "{ALERT_SELECT} WHERE a.rule_id = $1 ORDER BY a.created_at DESC LIMIT $2"
+            ))
             .bind(rule_id)
             .bind(limit)
             .fetch_all(pool)
             .await
         }
         _ => {
-            sqlx::query_as::<_, AlertRow>(
-                "SELECT id, fingerprint, rule_id, rule_name, agent_id, metric_name,
-                        severity, status, value, threshold, fired_at, resolved_at,
-                        annotations, created_at
-                 FROM alerts ORDER BY created_at DESC LIMIT $1",
-            )
+            sqlx::query_as::<_, AlertRow>(&format!(
+                "{ALERT_SELECT} ORDER BY a.created_at DESC LIMIT $1"
+            ))
             .bind(limit)
             .fetch_all(pool)
             .await
@@ -104,11 +116,34 @@ pub async fn get_alert(
 ) -> Result<Json<AlertRow>, StatusCode> {
     let pool = state.pool.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
 
-    sqlx::query_as::<_, AlertRow>(
-        "SELECT id, fingerprint, rule_id, rule_name, agent_id, metric_name,
-                severity, status, value, threshold, fired_at, resolved_at,
-                annotations, created_at
-         FROM alerts WHERE id = $1",
+    sqlx::query_as::<_, AlertRow>(&format!("{ALERT_SELECT} WHERE a.id = $1"))
+        .bind(&alert_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(target: "rest", error = %e, "alert query failed");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+pub async fn ack_alert(
+    State(state): State<AppState>,
+    Path(alert_id): Path<String>,
+    Extension(AuthSubject(subject)): Extension<AuthSubject>,
+    body: Option<Json<AckRequest>>,
+) -> Result<Json<AckResponse>, StatusCode> {
+    let pool = state.pool.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let comment = body.map(|Json(b)| b.comment).unwrap_or_default();
+
+    let row: Option<(String, String, bool)> = sqlx::query_as(
+        "SELECT a.fingerprint, a.status,
+                EXISTS (SELECT 1 FROM alerts r
+                        WHERE r.fingerprint = a.fingerprint
+                          AND r.status = 'resolved'
+                          AND r.created_at > a.created_at)
+         FROM alerts a WHERE a.id = $1",
     )
     .bind(&alert_id)
     .fetch_optional(pool)
@@ -116,7 +151,57 @@ pub async fn get_alert(
     .map_err(|e| {
         tracing::error!(target: "rest", error = %e, "alert query failed");
         StatusCode::INTERNAL_SERVER_ERROR
-    })?
-    .map(Json)
-    .ok_or(StatusCode::NOT_FOUND)
+    })?;
+
+    let (fingerprint, status, resolved) = row.ok_or(StatusCode::NOT_FOUND)?;
+    if status != "firing" || resolved {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    sqlx::query(
+        "INSERT INTO alert_acks (fingerprint, alert_id, acked_by, comment)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (fingerprint) DO UPDATE
+         SET alert_id = $2, acked_by = $3, comment = $4, acked_at = NOW()",
+    )
+    .bind(&fingerprint)
+    .bind(&alert_id)
+    .bind(&subject)
+    .bind(&comment)
+    .execute(pool)
+    .await
+    .map_err(|e| {
+        tracing::error!(target: "rest", error = %e, "alert ack failed");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    tracing::info!(target: "rest", alert_id = %alert_id, acked_by = %subject, "Alert acknowledged");
+    Ok(Json(AckResponse {
+        alert_id,
+        fingerprint,
+        acked_by: subject,
+        comment,
+    }))
+}
+
+pub async fn unack_alert(
+    State(state): State<AppState>,
+    Path(alert_id): Path<String>,
+) -> Result<StatusCode, StatusCode> {
+    let pool = state.pool.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+
+    let result = sqlx::query("DELETE FROM alert_acks WHERE alert_id = $1")
+        .bind(&alert_id)
+        .execute(pool)
+        .await
+        .map_err(|e| {
+            tracing::error!(target: "rest", error = %e, "alert unack failed");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    if result.rows_affected() == 0 {
+        Err(StatusCode::NOT_FOUND)
+    } else {
+        Ok(StatusCode::NO_CONTENT)
+    }
 }
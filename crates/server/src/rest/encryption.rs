@@ -0,0 +1,115 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::Json;
+use serde::Serialize;
+
+use crate::persistence::{AgentRepo, NotifierRepo};
+use crate::rest::AppState;
+
+#[derive(Serialize)]
+pub struct EncryptionStatus {
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_id: Option<String>,
+    pub previous_key_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pending: Option<PendingCounts>,
+}
+
+#[derive(Serialize)]
+pub struct PendingCounts {
+    pub notifiers: u64,
+    pub agents: u64,
+}
+
+#[derive(Serialize)]
+pub struct RotateResponse {
+    pub key_id: String,
+    pub notifiers: u64,
+    pub agents: u64,
+}
+
+#[derive(Serialize)]
+pub struct EncryptionErrorBody {
+    pub error: String,
+}
+
+type EncryptionResult<T> = Result<T, (StatusCode, Json<EncryptionErrorBody>)>;
+
+fn reject(status: StatusCode, error: impl Into<String>) -> (StatusCode, Json<EncryptionErrorBody>) {
+    (
+        status,
+        Json(EncryptionErrorBody {
+            error: error.into(),
+        }),
+    )
+}
+
+fn internal(e: sqlx::Error) -> (StatusCode, Json<EncryptionErrorBody>) {
+    tracing::error!(target: "db", error = %e, "re-encryption failed");
+    reject(StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+}
+
+fn repos(state: &AppState) -> EncryptionResult<(NotifierRepo, AgentRepo)> {
+    let pool = state
+        .pool
+        .as_ref()
+        .ok_or_else(|| reject(StatusCode::SERVICE_UNAVAILABLE, "database not configured"))?;
+    Ok((
+        NotifierRepo::new(pool.clone(), state.envelope.clone()),
+        AgentRepo::new(pool.clone(), state.envelope.clone()),
+    ))
+}
+
+async fn reencrypt(state: &AppState, dry_run: bool) -> EncryptionResult<PendingCounts> {
+    let (notifiers, agents) = repos(state)?;
+    Ok(PendingCounts {
+        notifiers: notifiers.reencrypt(dry_run).await.map_err(internal)?,
+        agents: agents.reencrypt(dry_run).await.map_err(internal)?,
+    })
+}
+
+pub async fn encryption_status(
+    State(state): State<AppState>,
+) -> EncryptionResult<Json<EncryptionStatus>> {
+    let pending = if state.pool.is_some() && state.envelope.is_enabled() {
+        Some(reencrypt(&state, true).await?)
+    } else {
+        None
+    };
+    Ok(Json(EncryptionStatus {
+        enabled: state.envelope.is_enabled(),
+        key_id: state.envelope.key_id().map(str::to_string),
+        previous_key_ids: state
+            .envelope
+            .previous_key_ids()
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+        pending,
+    }))
+}
+
+pub async fn rotate_master_key(
+    State(state): State<AppState>,
+) -> EncryptionResult<Json<RotateResponse>> {
+    let Some(key_id) = state.envelope.key_id().map(str::to_string) else {
+        return Err(reject(
+            StatusCode::CONFLICT,
+            "no master key configured (set SENTINEL_ENCRYPTION_KEY)",
+        ));
+    };
+    let counts = reencrypt(&state, false).await?;
+    tracing::info!(
+        target: "auth",
+        key_id = %key_id,
+        notifiers = counts.notifiers,
+        agents = counts.agents,
+        "Secrets re-encrypted with current master key"
+    );
+    Ok(Json(RotateResponse {
+        key_id,
+        notifiers: counts.notifiers,
+        agents: counts.agents,
+    }))
+}
@@ -0,0 +1,235 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use sentinel_common::escalation::{EscalationPolicy, EscalationStep, Route};
+
+use crate::persistence::{ActiveEscalation, EscalationRepo};
+use crate::rest::AppState;
+
+#[derive(Deserialize)]
+pub struct PolicyRequest {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub steps: Vec<EscalationStep>,
+}
+
+#[derive(Serialize)]
+pub struct ActiveEscalationResponse {
+    pub fingerprint: String,
+    pub policy_id: String,
+    pub alert_id: String,
+    pub rule_id: String,
+    pub step: i32,
+    pub notified: Vec<String>,
+    pub started_at_ms: i64,
+    pub next_at_ms: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stopped_reason: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct EscalationErrorBody {
+    pub error: String,
+}
+
+type EscalationResult<T> = Result<T, (StatusCode, Json<EscalationErrorBody>)>;
+
+fn reject(status: StatusCode, error: impl Into<String>) -> (StatusCode, Json<EscalationErrorBody>) {
+    (
+        status,
+        Json(EscalationErrorBody {
+            error: error.into(),
+        }),
+    )
+}
+
+fn internal(e: sqlx::Error) -> (StatusCode, Json<EscalationErrorBody>) {
+    if let sqlx::Error::Database(ref db) = e {
+        if db.is_unique_violation() {
+            return reject(StatusCode::CONFLICT, "policy name already exists");
+        }
+    }
+    tracing::error!(target: "rest", error = %e, "escalation query failed");
+    reject(StatusCode::INTERNAL_SERVER_ERROR, "database error")
+}
+
+pub async fn list_policies(
+    State(state): State<AppState>,
+) -> EscalationResult<Json<Vec<EscalationPolicy>>> {
+    let repo = repo(&state)?;
+    Ok(Json(repo.list().await.map_err(internal)?))
+}
+
+pub async fn get_policy(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> EscalationResult<Json<EscalationPolicy>> {
+    let repo = repo(&state)?;
+    repo.get(&id)
+        .await
+        .map_err(internal)?
+        .map(Json)
+        .ok_or_else(|| reject(StatusCode::NOT_FOUND, "escalation policy not found"))
+}
+
+pub async fn create_policy(
+    State(state): State<AppState>,
+    Json(body): Json<PolicyRequest>,
+) -> EscalationResult<(StatusCode, Json<EscalationPolicy>)> {
+    let policy = build(uuid::Uuid::new_v4().to_string(), body)?;
+    let repo = repo(&state)?;
+    check_notifiers(&state, &policy).await?;
+
+    repo.insert(&policy).await.map_err(internal)?;
+    tracing::info!(target: "rest", id = %policy.id, name = %policy.name, "Escalation policy created");
+    Ok((StatusCode::CREATED, Json(policy)))
+}
+
+pub async fn update_policy(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(body): Json<PolicyRequest>,
+) -> EscalationResult<Json<EscalationPolicy>> {
+    let policy = build(id, body)?;
+    let repo = repo(&state)?;
+    check_notifiers(&state, &policy).await?;
+
+    if repo.update(&policy).await.map_err(internal)? {
+        Ok(Json(policy))
+    } else {
+        Err(reject(StatusCode::NOT_FOUND, "escalation policy not found"))
+    }
+}
+
+pub async fn delete_policy(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> EscalationResult<StatusCode> {
+    let repo = repo(&state)?;
+    if let Some(rule) = state
+        .rules
+        .list()
+        .into_iter()
+        .find(|r| r.escalation_policy_id.as_deref() == Some(id.as_str()))
+    {
+        return Err(reject(
+            StatusCode::CONFLICT,
+            format!("policy is used by rule '{}'", rule.name),
+        ));
+    }
+    if let Some(tree) = repo.routes().await.map_err(internal)? {
+        if tree.policy_ids().contains(&id.as_str()) {
+            return Err(reject(
+                StatusCode::CONFLICT,
+                "policy is referenced by the escalation routes",
+            ));
+        }
+    }
+    if repo.delete(&id).await.map_err(internal)? {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(reject(StatusCode::NOT_FOUND, "escalation policy not found"))
+    }
+}
+
+pub async fn get_routes(State(state): State<AppState>) -> EscalationResult<Json<Route>> {
+    let repo = repo(&state)?;
+    Ok(Json(
+        repo.routes().await.map_err(internal)?.unwrap_or_default(),
+    ))
+}
+
+pub async fn put_routes(
+    State(state): State<AppState>,
+    Json(tree): Json<Route>,
+) -> EscalationResult<Json<Route>> {
+    tree.validate()
+        .map_err(|e| reject(StatusCode::BAD_REQUEST, e.to_string()))?;
+    let repo = repo(&state)?;
+    for id in tree.policy_ids() {
+        if repo.get(id).await.map_err(internal)?.is_none() {
+            return Err(reject(
+                StatusCode::BAD_REQUEST,
+                format!("unknown escalation policy '{id}'"),
+            ));
+        }
+    }
+    repo.put_routes(&tree).await.map_err(internal)?;
+    tracing::info!(target: "rest", "Escalation routes updated");
+    Ok(Json(tree))
+}
+
+pub async fn list_active(
+    State(state): State<AppState>,
+) -> EscalationResult<Json<Vec<ActiveEscalationResponse>>> {
+    let repo = repo(&state)?;
+    let rows = repo.list_active().await.map_err(internal)?;
+    Ok(Json(rows.into_iter().map(to_response).collect()))
+}
+
+pub(crate) async fn policy_exists(state: &AppState, id: &str) -> Result<bool, StatusCode> {
+    let Some(ref pool) = state.pool else {
+        return Ok(true);
+    };
+    EscalationRepo::new(pool.clone())
+        .get(id)
+        .await
+        .map(|p| p.is_some())
+        .map_err(|e| {
+            tracing::error!(target: "rest", error = %e, "escalation policy lookup failed");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })
+}
+
+fn build(id: String, body: PolicyRequest) -> EscalationResult<EscalationPolicy> {
+    let policy = EscalationPolicy {
+        id,
+        name: body.name.trim().to_string(),
+        description: body.description,
+        steps: body.steps,
+    };
+    policy
+        .validate()
+        .map_err(|e| reject(StatusCode::BAD_REQUEST, e.to_string()))?;
+    Ok(policy)
+}
+
+async fn check_notifiers(state: &AppState, policy: &EscalationPolicy) -> EscalationResult<()> {
+    let Some(ref notifiers) = state.notifier_repo else {
+        return Ok(());
+    };
+    for id in policy.notifier_ids() {
+        if notifiers.get(id).await.map_err(internal)?.is_none() {
+            return Err(reject(
+                StatusCode::BAD_REQUEST,
+                format!("unknown notifier '{id}'"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn repo(state: &AppState) -> EscalationResult<EscalationRepo> {
+    let pool = state
+        .pool
+        .as_ref()
+        .ok_or_else(|| reject(StatusCode::SERVICE_UNAVAILABLE, "database not configured"))?;
+    Ok(EscalationRepo::new(pool.clone()))
+}
+
+fn to_response(e: ActiveEscalation) -> ActiveEscalationResponse {
+    ActiveEscalationResponse {
+        fingerprint: e.fingerprint,
+        policy_id: e.policy_id,
+        alert_id: e.alert_id,
+        rule_id: e.rule_id,
+        step: e.step,
+        notified: e.notified,
+        started_at_ms: e.started_at_ms,
+        next_at_ms: e.next_at_ms,
+        stopped_reason: e.stopped_reason,
+    }
+}
@@ -1,38 +1,68 @@
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::Json;
 use base64::engine::general_purpose::STANDARD;
 use base64::Engine;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
+use crate::persistence::AgentRepo;
 use crate::rest::AppState;
+use sentinel_common::key_rotation::KEY_ROTATION_CAPABILITY;
+
+use crate::stream::key_push::{self, push_key, KeyPushResult};
+
+use super::agent_config::ack_timeout;
+
+#[derive(Deserialize)]
+pub struct RotateKeyQuery {
+    pub timeout_secs: Option<u64>,
+}
 
 #[derive(Serialize)]
 pub struct RotateKeyResponse {
     pub agent_id: String,
     pub new_key_id: String,
     pub new_secret: String,
+    pub push: KeyPushResult,
 }
 
 pub async fn rotate_key(
     State(state): State<AppState>,
     Path(agent_id): Path<String>,
+    Query(query): Query<RotateKeyQuery>,
 ) -> Result<Json<RotateKeyResponse>, StatusCode> {
-    let (new_key_id, new_secret) = state
-        .agents
-        .rotate_key(&agent_id)
+    let repo = state
+        .pool
+        .as_ref()
+        .map(|pool| AgentRepo::new(pool.clone(), state.envelope.clone()));
+    let in_band = state.registry.supports(&agent_id, KEY_ROTATION_CAPABILITY) != Some(false);
+    let rotated = key_push::rotate_key(&state.agents, repo.as_ref(), &agent_id, in_band)
+        .await
+        .map_err(|e| {
+            tracing::error!(target: "auth", %agent_id, error = %e, "Failed to persist rotated key");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
         .ok_or(StatusCode::NOT_FOUND)?;
 
     tracing::info!(
         target: "auth",
         %agent_id,
-        %new_key_id,
+        new_key_id = %rotated.key_id,
         "HMAC key rotated"
     );
 
+    let push = push_key(
+        &state.registry,
+        &agent_id,
+        &rotated,
+        ack_timeout(query.timeout_secs),
+    )
+    .await;
+
     Ok(Json(RotateKeyResponse {
         agent_id,
-        new_key_id,
-        new_secret: STANDARD.encode(&new_secret),
+        new_key_id: rotated.key_id,
+        new_secret: STANDARD.encode(&rotated.secret),
+        push,
     }))
 }
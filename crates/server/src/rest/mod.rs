@@ -1,20 +1,28 @@
+mod agent_commands;
+mod agent_config;
 mod agent_health;
+mod agent_ingest;
 mod agent_metrics;
 mod agent_queries;
 mod agent_types;
 mod agents;
+mod alert_stats;
 mod alerts;
 mod cluster;
+mod encryption;
+mod escalations;
 mod fleet;
 mod health;
 mod key_rotation;
 mod metrics;
+mod notification_dlq;
 mod notification_history;
 mod notifier_configs;
 mod notifiers;
 mod provisioning;
 mod router;
 mod rules;
+mod silences;
 mod token;
 
 pub use router::{router, AppState};
@@ -0,0 +1,186 @@
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::persistence::{DlqFilter, DlqRecord, DlqRepo};
+use crate::rest::AppState;
+
+#[derive(Deserialize)]
+pub struct DlqQuery {
+    pub limit: Option<i64>,
+    pub notifier_id: Option<String>,
+    pub alert_id: Option<String>,
+    pub older_than_secs: Option<i64>,
+}
+
+#[derive(Deserialize, Default)]
+pub struct DlqSelector {
+    pub notifier_id: Option<String>,
+    pub alert_id: Option<String>,
+    pub older_than_secs: Option<i64>,
+    #[serde(default)]
+    pub all: bool,
+}
+
+#[derive(Serialize)]
+pub struct DlqEntryResponse {
+    pub id: String,
+    pub alert_id: String,
+    pub notifier_id: Option<String>,
+    pub notifier: String,
+    pub error: String,
+    pub attempts: i32,
+    pub retry_count: i32,
+    pub replay_pending: bool,
+    pub created_at_ms: i64,
+    pub last_attempt_ms: i64,
+    pub next_retry_at_ms: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct ReplayResponse {
+    pub queued: u64,
+}
+
+#[derive(Serialize)]
+pub struct PurgeResponse {
+    pub purged: u64,
+}
+
+pub async fn list_dlq(
+    State(state): State<AppState>,
+    Query(q): Query<DlqQuery>,
+) -> Result<Json<Vec<DlqEntryResponse>>, StatusCode> {
+    if q.older_than_secs.is_some_and(|s| s <= 0) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let repo = repo(&state)?;
+
+    let filter = DlqFilter {
+        id: None,
+        notifier_id: q.notifier_id,
+        alert_id: q.alert_id,
+        older_than_secs: q.older_than_secs,
+    };
+    let limit = q.limit.unwrap_or(50).min(500);
+
+    let records = repo.list(&filter, limit).await.map_err(|e| {
+        tracing::error!(target: "rest", error = %e, "dlq list failed");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(records.into_iter().map(to_response).collect()))
+}
+
+pub async fn replay_dlq(
+    State(state): State<AppState>,
+    Json(body): Json<DlqSelector>,
+) -> Result<Json<ReplayResponse>, StatusCode> {
+    let filter = selector_filter(body)?;
+    let repo = repo(&state)?;
+
+    let queued = repo.request_replay(&filter).await.map_err(|e| {
+        tracing::error!(target: "rest", error = %e, "dlq replay failed");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    tracing::info!(target: "rest", queued, "DLQ replay requested");
+    Ok(Json(ReplayResponse { queued }))
+}
+
+pub async fn purge_dlq(
+    State(state): State<AppState>,
+    Json(body): Json<DlqSelector>,
+) -> Result<Json<PurgeResponse>, StatusCode> {
+    let filter = selector_filter(body)?;
+    let repo = repo(&state)?;
+
+    let purged = repo.purge(&filter).await.map_err(|e| {
+        tracing::error!(target: "rest", error = %e, "dlq purge failed");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    tracing::info!(target: "rest", purged, "DLQ entries purged");
+    Ok(Json(PurgeResponse { purged }))
+}
+
+pub async fn replay_dlq_entry(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ReplayResponse>, StatusCode> {
+    let repo = repo(&state)?;
+    let filter = DlqFilter {
+        id: Some(id),
+        ..Default::default()
+    };
+
+    let queued = repo.request_replay(&filter).await.map_err(|e| {
+        tracing::error!(target: "rest", error = %e, "dlq replay failed");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    if queued == 0 {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    Ok(Json(ReplayResponse { queued }))
+}
+
+pub async fn delete_dlq_entry(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, StatusCode> {
+    let repo = repo(&state)?;
+    let filter = DlqFilter {
+        id: Some(id),
+        ..Default::default()
+    };
+
+    let purged = repo.purge(&filter).await.map_err(|e| {
+        tracing::error!(target: "rest", error = %e, "dlq delete failed");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    if purged == 0 {
+        Err(StatusCode::NOT_FOUND)
+    } else {
+        Ok(StatusCode::NO_CONTENT)
+    }
+}
+
+fn repo(state: &AppState) -> Result<DlqRepo, StatusCode> {
+    let pool = state.pool.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    Ok(DlqRepo::new(pool.clone()))
+}
+
+fn selector_filter(s: DlqSelector) -> Result<DlqFilter, StatusCode> {
+    if s.older_than_secs.is_some_and(|secs| secs <= 0) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let unfiltered = s.notifier_id.is_none() && s.alert_id.is_none() && s.older_than_secs.is_none();
+    if unfiltered && !s.all {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    Ok(DlqFilter {
+        id: None,
+        notifier_id: s.notifier_id,
+        alert_id: s.alert_id,
+        older_than_secs: s.older_than_secs,
+    })
+}
+
+fn to_response(r: DlqRecord) -> DlqEntryResponse {
+    DlqEntryResponse {
+        id: r.id,
+        alert_id: r.alert_id,
+        notifier_id: r.notifier_id,
+        notifier: r.notifier,
+        error: r.error,
+        attempts: r.attempts,
+        retry_count: r.retry_count,
+        replay_pending: r.replay_pending,
+        created_at_ms: r.created_at_ms,
+        last_attempt_ms: r.last_attempt_ms,
+        next_retry_at_ms: r.next_retry_at_ms,
+    }
+}
@@ -31,6 +31,7 @@ pub struct HistoryStatsResponse {
     pub total: i64,
     pub sent: i64,
     pub failed: i64,
+    pub dead_lettered: i64,
     pub avg_duration_ms: i64,
 }
 
@@ -79,6 +80,7 @@ pub async fn notification_stats(
         total: stats.total,
         sent: stats.sent,
         failed: stats.failed,
+        dead_lettered: stats.dead_lettered,
         avg_duration_ms: stats.avg_duration_ms,
     }))
 }
@@ -1,9 +1,13 @@
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
 use axum::Json;
+use sentinel_common::grouping::GroupPolicy;
+use sentinel_common::redact::{redact_config, restore_redacted};
+use sentinel_common::template::MessageTemplate;
 use serde::{Deserialize, Serialize};
 
 use crate::persistence::NotifierConfigRecord;
+use crate::rest::notifiers::validate_config;
 use crate::rest::AppState;
 
 #[derive(Deserialize)]
@@ -36,7 +40,7 @@ fn to_response(r: NotifierConfigRecord) -> NotifierConfigResponse {
         id: r.id,
         name: r.name,
         ntype: r.ntype,
-        config: r.config,
+        config: redact_config(&r.config),
         enabled: r.enabled,
         created_at_ms: r.created_at_ms,
     }
@@ -53,8 +57,35 @@ const VALID_TYPES: &[&str] = &[
     "opsgenie",
     "gotify",
     "ntfy",
+    "mattermost",
+    "rocketchat",
+    "matrix",
+    "googlechat",
+    "pushover",
+    "victorops",
+    "sns",
 ];
 
+fn check_fields(ntype: &str, config: &serde_json::Value) -> Result<(), StatusCode> {
+    validate_config(ntype, config).map_err(|e| {
+        tracing::warn!(target: "rest", ntype, error = %e, "rejected notifier config");
+        StatusCode::BAD_REQUEST
+    })?;
+    Ok(())
+}
+
+fn check_config(config: &serde_json::Value) -> Result<(), StatusCode> {
+    MessageTemplate::from_config(config).map_err(|e| {
+        tracing::warn!(target: "rest", error = %e, "rejected notifier template");
+        StatusCode::BAD_REQUEST
+    })?;
+    GroupPolicy::from_config(config).map_err(|e| {
+        tracing::warn!(target: "rest", error = %e, "rejected notifier grouping");
+        StatusCode::BAD_REQUEST
+    })?;
+    Ok(())
+}
+
 pub async fn list_notifier_configs(
     State(state): State<AppState>,
 ) -> Result<Json<Vec<NotifierConfigResponse>>, StatusCode> {
@@ -93,6 +124,8 @@ pub async fn create_notifier_config(
     if !VALID_TYPES.contains(&body.ntype.as_str()) {
         return Err(StatusCode::BAD_REQUEST);
     }
+    check_fields(&body.ntype, &body.config)?;
+    check_config(&body.config)?;
 
     let repo = state
         .notifier_repo
@@ -126,6 +159,10 @@ pub async fn update_notifier_config(
     Path(id): Path<String>,
     Json(body): Json<UpdateNotifierRequest>,
 ) -> Result<Json<NotifierConfigResponse>, StatusCode> {
+    if let Some(ref config) = body.config {
+        check_config(config)?;
+    }
+
     let repo = state
         .notifier_repo
         .as_ref()
@@ -140,11 +177,20 @@ pub async fn update_notifier_config(
         })?
         .ok_or(StatusCode::NOT_FOUND)?;
 
+    let config = match body.config {
+        Some(mut config) => {
+            restore_redacted(&mut config, &existing.config);
+            check_fields(&existing.ntype, &config)?;
+            config
+        }
+        None => existing.config,
+    };
+
     let updated = NotifierConfigRecord {
         id: existing.id,
         name: body.name.unwrap_or(existing.name),
         ntype: existing.ntype,
-        config: body.config.unwrap_or(existing.config),
+        config,
         enabled: body.enabled.unwrap_or(existing.enabled),
         created_at_ms: existing.created_at_ms,
     };
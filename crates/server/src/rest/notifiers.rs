@@ -1,6 +1,8 @@
 use axum::extract::State;
-use axum::http::StatusCode;
+use axum::http::{HeaderName, HeaderValue, StatusCode};
 use axum::Json;
+use sentinel_common::grouping::GroupPolicy;
+use sentinel_common::template::{AlertContext, GroupContext, MessageTemplate, Rendered, Template};
 use serde::{Deserialize, Serialize};
 
 use crate::rest::AppState;
@@ -9,41 +11,81 @@ use crate::rest::AppState;
 pub struct TestNotifierRequest {
     pub notifier_type: String,
     pub config: serde_json::Value,
+    #[serde(default)]
+    pub render: bool,
 }
 
 #[derive(Serialize)]
 pub struct TestNotifierResponse {
     pub success: bool,
     pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rendered: Option<Rendered>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rendered_group: Option<Rendered>,
 }
 
 pub async fn test_notifier(
     State(_state): State<AppState>,
     Json(body): Json<TestNotifierRequest>,
 ) -> Result<Json<TestNotifierResponse>, StatusCode> {
-    let result = match body.notifier_type.as_str() {
-        "webhook" => validate_webhook(&body.config),
-        "slack" => validate_slack(&body.config),
-        "discord" => validate_discord(&body.config),
-        "smtp" => validate_smtp(&body.config),
-        "telegram" => validate_telegram(&body.config),
-        "pagerduty" => validate_pagerduty(&body.config),
-        "teams" => validate_teams(&body.config),
-        "opsgenie" => validate_opsgenie(&body.config),
-        "gotify" => validate_gotify(&body.config),
-        "ntfy" => validate_ntfy(&body.config),
-        _ => Err("unknown notifier type".into()),
+    let result = validate_config(&body.notifier_type, &body.config);
+
+    let checked = MessageTemplate::from_config(&body.config)
+        .map_err(|e| e.to_string())
+        .and_then(|t| {
+            GroupPolicy::from_config(&body.config)
+                .map(|_| t)
+                .map_err(|e| e.to_string())
+        });
+    let template = match checked {
+        Ok(t) => t,
+        Err(e) => {
+            return Ok(Json(TestNotifierResponse {
+                success: false,
+                message: e,
+                rendered: None,
+                rendered_group: None,
+            }))
+        }
+    };
+    let rendered = body
+        .render
+        .then(|| template.render(&AlertContext::sample()));
+    let rendered_group = (body.render && template.has_group())
+        .then(|| template.render_group(&GroupContext::sample()));
+
+    let (success, message) = match result {
+        Ok(msg) => (true, msg),
+        Err(msg) => (false, msg),
     };
+    Ok(Json(TestNotifierResponse {
+        success,
+        message,
+        rendered,
+        rendered_group,
+    }))
+}
 
-    match result {
-        Ok(msg) => Ok(Json(TestNotifierResponse {
-            success: true,
-            message: msg,
-        })),
-        Err(msg) => Ok(Json(TestNotifierResponse {
-            success: false,
-            message: msg,
-        })),
+pub(crate) fn validate_config(ntype: &str, config: &serde_json::Value) -> Result<String, String> {
+    match ntype {
+        "webhook" => validate_webhook(config),
+        "slack" => validate_slack(config),
+        "discord" => validate_discord(config),
+        "smtp" => validate_smtp(config),
+        "telegram" => validate_telegram(config),
+        "pagerduty" => validate_pagerduty(config),
+        "teams" => validate_teams(config),
+        "opsgenie" => validate_opsgenie(config),
+        "gotify" => validate_gotify(config),
+        "ntfy" => validate_ntfy(config),
+        "mattermost" | "rocketchat" => validate_chat_webhook(ntype, config),
+        "matrix" => validate_matrix(config),
+        "googlechat" => validate_googlechat(config),
+        "pushover" => validate_pushover(config),
+        "victorops" => validate_victorops(config),
+        "sns" => validate_sns(config),
+        _ => Err("unknown notifier type".into()),
     }
 }
 
@@ -53,6 +95,51 @@ fn validate_webhook(config: &serde_json::Value) -> Result<String, String> {
         .and_then(|v| v.as_str())
         .ok_or("missing 'url' field")?;
     validate_url(url)?;
+    if let Some(method) = config.get("method").and_then(|v| v.as_str()) {
+        if !["POST", "PUT", "PATCH"].contains(&method.to_ascii_uppercase().as_str()) {
+            return Err("method must be POST, PUT or PATCH".into());
+        }
+    }
+    if let Some(headers) = config.get("headers") {
+        let headers = headers.as_object().ok_or("headers must be an object")?;
+        for (name, value) in headers {
+            let value = value
+                .as_str()
+                .ok_or_else(|| format!("header '{name}' must be a string"))?;
+            HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| format!("invalid header name '{name}'"))?;
+            HeaderValue::from_str(value)
+                .map_err(|_| format!("invalid value for header '{name}'"))?;
+        }
+    }
+    for key in ["timeout_secs", "connect_timeout_secs"] {
+        if let Some(v) = config.get(key) {
+            if !v.as_u64().is_some_and(|secs| (1..=300).contains(&secs)) {
+                return Err(format!("{key} must be between 1 and 300"));
+            }
+        }
+    }
+    for key in ["body_template", "group_body_template"] {
+        if let Some(src) = config.get(key).and_then(|v| v.as_str()) {
+            Template::parse(src).map_err(|e| format!("{key} {e}"))?;
+        }
+    }
+    match (config.get("client_cert"), config.get("client_key")) {
+        (Some(_), Some(_)) => {
+            for key in ["client_cert", "client_key"] {
+                if !required(config, key)?.contains("-----BEGIN ") {
+                    return Err(format!("{key} must be PEM encoded"));
+                }
+            }
+        }
+        (None, None) => {}
+        _ => return Err("client_cert and client_key must be set together".into()),
+    }
+    if let Some(ca) = config.get("ca_cert") {
+        if !ca.as_str().is_some_and(|pem| pem.contains("-----BEGIN ")) {
+            return Err("ca_cert must be PEM encoded".into());
+        }
+    }
     Ok(format!("webhook config valid (url: {url})"))
 }
 
@@ -163,6 +250,70 @@ fn validate_ntfy(config: &serde_json::Value) -> Result<String, String> {
     Ok("ntfy config valid".into())
 }
 
+fn validate_chat_webhook(ntype: &str, config: &serde_json::Value) -> Result<String, String> {
+    let url = required(config, "webhook_url")?;
+    validate_url(url)?;
+    Ok(format!("{ntype} config valid"))
+}
+
+fn validate_matrix(config: &serde_json::Value) -> Result<String, String> {
+    validate_url(required(config, "homeserver_url")?)?;
+    required(config, "access_token")?;
+    let room = required(config, "room_id")?;
+    if !(room.starts_with('!') || room.starts_with('#')) || !room.contains(':') {
+        return Err("room_id must look like !room:server or #alias:server".into());
+    }
+    Ok("matrix config valid".into())
+}
+
+fn validate_googlechat(config: &serde_json::Value) -> Result<String, String> {
+    let url = required(config, "webhook_url")?;
+    if !url.starts_with("https://chat.googleapis.com/") {
+        return Err("webhook_url must start with https://chat.googleapis.com/".into());
+    }
+    Ok("googlechat config valid".into())
+}
+
+fn validate_pushover(config: &serde_json::Value) -> Result<String, String> {
+    required(config, "token")?;
+    required(config, "user_key")?;
+    optional_url(config, "api_url")?;
+    Ok("pushover config valid".into())
+}
+
+fn validate_victorops(config: &serde_json::Value) -> Result<String, String> {
+    required(config, "api_key")?;
+    required(config, "routing_key")?;
+    optional_url(config, "api_url")?;
+    Ok("victorops config valid".into())
+}
+
+fn validate_sns(config: &serde_json::Value) -> Result<String, String> {
+    required(config, "region")?;
+    if !required(config, "topic_arn")?.starts_with("arn:") {
+        return Err("topic_arn must be an ARN".into());
+    }
+    required(config, "access_key_id")?;
+    required(config, "secret_access_key")?;
+    optional_url(config, "endpoint")?;
+    Ok("sns config valid".into())
+}
+
+fn required<'a>(config: &'a serde_json::Value, key: &str) -> Result<&'a str, String> {
+    config
+        .get(key)
+        .and_then(|v| v.as_str())
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| format!("missing '{key}' field"))
+}
+
+fn optional_url(config: &serde_json::Value, key: &str) -> Result<(), String> {
+    match config.get(key).and_then(|v| v.as_str()) {
+        Some(url) => validate_url(url),
+        None => Ok(()),
+    }
+}
+
 fn validate_url(url: &str) -> Result<(), String> {
     if !url.starts_with("http://") && !url.starts_with("https://") {
         return Err("url must start with http:// or https://".into());
@@ -187,6 +338,31 @@ mod tests {
         assert!(validate_webhook(&cfg).is_err());
     }
 
+    #[test]
+    fn webhook_options() {
+        let cfg = json!({
+            "url": "https://example.com/hook",
+            "method": "put",
+            "headers": {"X-Team": "ops"},
+            "timeout_secs": 30,
+            "body_template": "{\"text\": {{ rule_name | json }}}",
+        });
+        assert!(validate_webhook(&cfg).is_ok());
+        for bad in [
+            json!({"method": "DELETE"}),
+            json!({"headers": {"bad name": "x"}}),
+            json!({"headers": ["X-Team"]}),
+            json!({"timeout_secs": 0}),
+            json!({"body_template": "{{ rule_name"}),
+            json!({"client_cert": "-----BEGIN CERTIFICATE-----"}),
+            json!({"client_cert": "cert", "client_key": "key"}),
+        ] {
+            let mut cfg = bad.clone();
+            cfg["url"] = "https://example.com/hook".into();
+            assert!(validate_webhook(&cfg).is_err(), "{bad}");
+        }
+    }
+
     #[test]
     fn webhook_bad_scheme() {
         let cfg = json!({"url": "ftp://example.com"});
@@ -288,4 +464,65 @@ mod tests {
         let cfg = json!({"server_url": "https://ntfy.sh"});
         assert!(validate_ntfy(&cfg).is_err());
     }
+
+    #[test]
+    fn mattermost_and_rocketchat_valid() {
+        let cfg = json!({"webhook_url": "https://chat.example.com/hooks/abc"});
+        assert!(validate_config("mattermost", &cfg).is_ok());
+        assert!(validate_config("rocketchat", &cfg).is_ok());
+        assert!(validate_config("mattermost", &json!({})).is_err());
+    }
+
+    #[test]
+    fn matrix_room_id() {
+        let cfg = json!({
+            "homeserver_url": "https://matrix.example.org",
+            "access_token": "syt_abc",
+            "room_id": "!ops:example.org"
+        });
+        assert!(validate_matrix(&cfg).is_ok());
+        let cfg = json!({
+            "homeserver_url": "https://matrix.example.org",
+            "access_token": "syt_abc",
+            "room_id": "ops"
+        });
+        assert!(validate_matrix(&cfg).is_err());
+    }
+
+    #[test]
+    fn googlechat_requires_google_host() {
+        let cfg =
+            json!({"webhook_url": "https://chat.googleapis.com/v1/spaces/AAA/messages?key=k"});
+        assert!(validate_googlechat(&cfg).is_ok());
+        let cfg = json!({"webhook_url": "https://example.com/hook"});
+        assert!(validate_googlechat(&cfg).is_err());
+    }
+
+    #[test]
+    fn pushover_and_victorops_keys() {
+        assert!(validate_pushover(&json!({"token": "t", "user_key": "u"})).is_ok());
+        assert!(validate_pushover(&json!({"token": "t"})).is_err());
+        assert!(validate_victorops(&json!({"api_key": "k", "routing_key": "r"})).is_ok());
+        assert!(validate_victorops(
+            &json!({"api_key": "k", "routing_key": "r", "api_url": "ftp://x"})
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn sns_requires_arn_and_credentials() {
+        let cfg = json!({
+            "region": "us-east-1",
+            "topic_arn": "arn:aws:sns:us-east-1:123456789012:alerts",
+            "access_key_id": "AKID",
+            "secret_access_key": "secret"
+        });
+        assert!(validate_sns(&cfg).is_ok());
+        let mut bad = cfg.clone();
+        bad["topic_arn"] = "alerts".into();
+        assert!(validate_sns(&bad).is_err());
+        let mut bad = cfg;
+        bad.as_object_mut().unwrap().remove("secret_access_key");
+        assert!(validate_sns(&bad).is_err());
+    }
 }
@@ -1,18 +1,22 @@
 use axum::middleware;
-use axum::routing::{get, post};
+use axum::routing::{delete, get, post};
 use axum::Router;
+use sentinel_common::envelope::Envelope;
 use sqlx::PgPool;
 use std::sync::Arc;
 
 use super::{
-    agent_health, agent_metrics, agents, alerts, cluster, fleet, health, key_rotation, metrics,
-    notification_history, notifier_configs, notifiers, provisioning, rules, token,
+    agent_commands, agent_config, agent_health, agent_ingest, agent_metrics, agents, alert_stats,
+    alerts, cluster, encryption, escalations, fleet, health, key_rotation, metrics,
+    notification_dlq, notification_history, notifier_configs, notifiers, provisioning, rules,
+    silences, token,
 };
+use crate::broker::BrokerPublisher;
 use crate::metrics::server_metrics::ServerMetrics;
 use crate::middleware::require_auth;
 use crate::persistence::{MetricsQueryRepo, NotificationHistoryRepo, NotifierRepo, RuleRepo};
 use crate::provisioning::TokenStore;
-use crate::store::{AgentStore, RuleStore};
+use crate::store::{AgentStore, IdempotencyStore, RuleStore};
 use crate::stream::{PresenceEventBus, SessionRegistry};
 
 #[derive(Clone)]
@@ -30,6 +34,11 @@ pub struct AppState {
     pub grpc_public_url: String,
     pub registry: SessionRegistry,
     pub events: PresenceEventBus,
+    pub broker: Option<Arc<dyn BrokerPublisher>>,
+    pub idempotency: IdempotencyStore,
+    pub key_grace_period_ms: i64,
+    pub replay_window_ms: i64,
+    pub envelope: Envelope,
 }
 
 pub fn router(state: AppState) -> Router {
@@ -44,11 +53,21 @@ pub fn router(state: AppState) -> Router {
             "/v1/agents/generate-install",
             post(provisioning::generate_install),
         )
-        .route("/v1/auth/token", post(token::create_api_token));
+        .route("/v1/auth/token", post(token::create_api_token))
+        .route("/v1/agent/metrics", post(agent_ingest::ingest_metrics));
 
     let protected = Router::new()
         .route("/v1/agents", get(agents::list_agents))
+        .route("/v1/agents/config", post(agent_config::push_group_config))
         .route("/v1/agents/:agent_id", get(agents::get_agent))
+        .route(
+            "/v1/agents/:agent_id/config",
+            post(agent_config::push_agent_config),
+        )
+        .route(
+            "/v1/agents/:agent_id/commands",
+            post(agent_commands::exec_command),
+        )
         .route(
             "/v1/agents/:agent_id/health",
             get(agent_health::agent_health),
@@ -66,7 +85,41 @@ pub fn router(state: AppState) -> Router {
                 .delete(rules::delete_rule),
         )
         .route("/v1/alerts", get(alerts::list_alerts))
+        .route("/v1/alerts/incidents", get(alert_stats::list_incidents))
+        .route("/v1/alerts/stats", get(alert_stats::alert_stats))
+        .route("/v1/alerts/flapping", get(alert_stats::list_flapping))
         .route("/v1/alerts/:alert_id", get(alerts::get_alert))
+        .route(
+            "/v1/alerts/:alert_id/ack",
+            post(alerts::ack_alert).delete(alerts::unack_alert),
+        )
+        .route(
+            "/v1/silences",
+            get(silences::list_silences).post(silences::create_silence),
+        )
+        .route(
+            "/v1/silences/:silence_id",
+            get(silences::get_silence)
+                .put(silences::update_silence)
+                .delete(silences::delete_silence),
+        )
+        .route(
+            "/v1/escalation-policies",
+            get(escalations::list_policies).post(escalations::create_policy),
+        )
+        .route(
+            "/v1/escalation-policies/:policy_id",
+            get(escalations::get_policy)
+                .put(escalations::update_policy)
+                .delete(escalations::delete_policy),
+        )
+        .route(
+            "/v1/escalation-routes",
+            get(escalations::get_routes).put(escalations::put_routes),
+        )
+        .route("/v1/escalations", get(escalations::list_active))
+        .route("/v1/encryption", get(encryption::encryption_status))
+        .route("/v1/encryption/rotate", post(encryption::rotate_master_key))
         .route("/v1/notifiers/test", post(notifiers::test_notifier))
         .route(
             "/v1/notifiers",
@@ -91,6 +144,23 @@ pub fn router(state: AppState) -> Router {
             "/v1/notifications/stats",
             get(notification_history::notification_stats),
         )
+        .route("/v1/notifications/dlq", get(notification_dlq::list_dlq))
+        .route(
+            "/v1/notifications/dlq/replay",
+            post(notification_dlq::replay_dlq),
+        )
+        .route(
+            "/v1/notifications/dlq/purge",
+            post(notification_dlq::purge_dlq),
+        )
+        .route(
+            "/v1/notifications/dlq/:id",
+            delete(notification_dlq::delete_dlq_entry),
+        )
+        .route(
+            "/v1/notifications/dlq/:id/replay",
+            post(notification_dlq::replay_dlq_entry),
+        )
         .route(
             "/v1/metrics/agents/:agent_id/latest",
             get(agent_metrics::latest_metrics),
@@ -1,24 +1,37 @@
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
 use axum::Json;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
 
-use crate::rest::AppState;
-use crate::store::rule_record::RuleRecord;
+use sentinel_common::aggregation::Aggregation;
+use sentinel_common::expr;
+use sentinel_common::grouping::GroupPolicy;
+use sentinel_common::labels::{self, LabelMatcher};
+
+use crate::rest::{escalations, AppState};
+use crate::store::rule_record::{RuleRecord, DEFAULT_MIN_SAMPLES, DEFAULT_WINDOW_MS};
 
 #[derive(Deserialize)]
 pub struct CreateRuleRequest {
     pub name: String,
     pub agent_pattern: Option<String>,
-    pub metric_name: String,
-    pub condition: String,
-    pub threshold: f64,
+    pub metric_name: Option<String>,
+    pub label_matchers: Option<Vec<LabelMatcher>>,
+    pub expression: Option<String>,
+    pub absent_for_ms: Option<i64>,
+    pub condition: Option<String>,
+    pub threshold: Option<f64>,
+    pub aggregation: Option<String>,
+    pub window_ms: Option<i64>,
+    pub min_samples: Option<i64>,
     pub for_duration_ms: Option<i64>,
     pub severity: Option<String>,
     pub annotations: Option<HashMap<String, String>>,
     pub enabled: Option<bool>,
     pub notifier_ids: Option<Vec<String>>,
+    pub grouping: Option<GroupPolicy>,
+    pub escalation_policy_id: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -26,13 +39,31 @@ pub struct UpdateRuleRequest {
     pub name: Option<String>,
     pub agent_pattern: Option<String>,
     pub metric_name: Option<String>,
+    pub label_matchers: Option<Vec<LabelMatcher>>,
+    pub expression: Option<String>,
+    pub absent_for_ms: Option<i64>,
     pub condition: Option<String>,
     pub threshold: Option<f64>,
+    pub aggregation: Option<String>,
+    pub window_ms: Option<i64>,
+    pub min_samples: Option<i64>,
     pub for_duration_ms: Option<i64>,
     pub severity: Option<String>,
     pub annotations: Option<HashMap<String, String>>,
     pub enabled: Option<bool>,
     pub notifier_ids: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub grouping: Option<Option<GroupPolicy>>,
+    #[serde(default, deserialize_with = "nullable")]
+    pub escalation_policy_id: Option<Option<String>>,
+}
+
+fn nullable<'de, D, T>(d: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(d).map(Some)
 }
 
 #[derive(Serialize)]
@@ -41,30 +72,70 @@ pub struct RuleResponse {
     pub name: String,
     pub agent_pattern: String,
     pub metric_name: String,
+    pub label_matchers: Vec<LabelMatcher>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expression: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub absent_for_ms: Option<i64>,
     pub condition: String,
     pub threshold: f64,
+    pub aggregation: String,
+    pub window_ms: i64,
+    pub min_samples: i64,
     pub for_duration_ms: i64,
     pub severity: String,
     pub annotations: HashMap<String, String>,
     pub enabled: bool,
     pub notifier_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grouping: Option<GroupPolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub escalation_policy_id: Option<String>,
     pub created_at_ms: i64,
     pub updated_at_ms: i64,
 }
 
+#[derive(Serialize)]
+pub struct RuleError {
+    pub error: String,
+}
+
+type RuleResult<T> = Result<T, (StatusCode, Json<RuleError>)>;
+
+fn reject(status: StatusCode, error: impl Into<String>) -> (StatusCode, Json<RuleError>) {
+    (
+        status,
+        Json(RuleError {
+            error: error.into(),
+        }),
+    )
+}
+
+fn bad_request(error: impl Into<String>) -> (StatusCode, Json<RuleError>) {
+    reject(StatusCode::BAD_REQUEST, error)
+}
+
 fn to_response(r: RuleRecord) -> RuleResponse {
     RuleResponse {
         id: r.id,
         name: r.name,
         agent_pattern: r.agent_pattern,
         metric_name: r.metric_name,
+        label_matchers: r.label_matchers,
+        expression: r.expression,
+        absent_for_ms: r.absent_for_ms,
         condition: r.condition,
         threshold: r.threshold,
+        aggregation: r.aggregation,
+        window_ms: r.window_ms,
+        min_samples: r.min_samples,
         for_duration_ms: r.for_duration_ms,
         severity: r.severity,
         annotations: r.annotations,
         enabled: r.enabled,
         notifier_ids: r.notifier_ids,
+        grouping: r.grouping,
+        escalation_policy_id: r.escalation_policy_id,
         created_at_ms: r.created_at_ms,
         updated_at_ms: r.updated_at_ms,
     }
@@ -81,6 +152,61 @@ fn validate_severity(s: &str) -> bool {
     matches!(s, "info" | "warning" | "critical")
 }
 
+fn validate_aggregation(a: &str) -> bool {
+    a.parse::<Aggregation>().is_ok()
+}
+
+fn validate_window(window_ms: i64, min_samples: i64) -> bool {
+    window_ms > 0 && min_samples >= 1
+}
+
+fn validate_matchers(m: &[LabelMatcher]) -> bool {
+    m.iter().all(|m| !m.label.is_empty()) && labels::validate(m).is_ok()
+}
+
+fn validate_expression(e: &str) -> Result<(), (StatusCode, Json<RuleError>)> {
+    expr::validate(e).map_err(|err| bad_request(format!("invalid expression {err}")))
+}
+
+fn validate_target(metric_name: &str, expression: Option<&str>) -> RuleResult<()> {
+    match expression {
+        Some(e) => validate_expression(e),
+        None if metric_name.is_empty() => {
+            Err(bad_request("either metric_name or expression is required"))
+        }
+        None => Ok(()),
+    }
+}
+
+fn validate_grouping(grouping: Option<&GroupPolicy>) -> RuleResult<()> {
+    match grouping {
+        Some(g) => g.validate().map_err(|e| bad_request(e.to_string())),
+        None => Ok(()),
+    }
+}
+
+async fn validate_escalation(state: &AppState, policy_id: Option<&str>) -> RuleResult<()> {
+    let Some(id) = policy_id else {
+        return Ok(());
+    };
+    match escalations::policy_exists(state, id).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(bad_request(format!("unknown escalation policy '{id}'"))),
+        Err(status) => Err(reject(status, "escalation policy lookup failed")),
+    }
+}
+
+fn validate_absent(absent_for_ms: Option<i64>, expression: Option<&str>) -> RuleResult<()> {
+    match absent_for_ms {
+        None => Ok(()),
+        Some(ms) if ms <= 0 => Err(bad_request("absent_for_ms must be positive")),
+        Some(_) if expression.is_some() => Err(bad_request(
+            "absent_for_ms applies to metric rules and cannot be combined with an expression",
+        )),
+        Some(_) => Ok(()),
+    }
+}
+
 pub async fn list_rules(State(state): State<AppState>) -> Json<Vec<RuleResponse>> {
     let rules = state.rules.list().into_iter().map(to_response).collect();
     Json(rules)
@@ -100,14 +226,43 @@ pub async fn get_rule(
 pub async fn create_rule(
     State(state): State<AppState>,
     Json(body): Json<CreateRuleRequest>,
-) -> Result<(StatusCode, Json<RuleResponse>), StatusCode> {
-    if !validate_condition(&body.condition) {
-        return Err(StatusCode::BAD_REQUEST);
+) -> RuleResult<(StatusCode, Json<RuleResponse>)> {
+    let expression = body.expression.filter(|e| !e.trim().is_empty());
+    let metric_name = body.metric_name.unwrap_or_default();
+    validate_target(&metric_name, expression.as_deref())?;
+    validate_absent(body.absent_for_ms, expression.as_deref())?;
+    validate_grouping(body.grouping.as_ref())?;
+    let escalation_policy_id = body.escalation_policy_id.filter(|id| !id.trim().is_empty());
+    let (condition, threshold) = match (body.condition, body.threshold) {
+        (Some(c), Some(t)) => (c, t),
+        (c, t) if expression.is_some() || body.absent_for_ms.is_some() => {
+            (c.unwrap_or_else(|| "GreaterThan".into()), t.unwrap_or(0.0))
+        }
+        _ => return Err(bad_request("condition and threshold are required")),
+    };
+    if !validate_condition(&condition) {
+        return Err(bad_request(format!("invalid condition '{condition}'")));
     }
     let severity = body.severity.as_deref().unwrap_or("warning");
     if !validate_severity(severity) {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(bad_request(format!("invalid severity '{severity}'")));
+    }
+    let label_matchers = body.label_matchers.unwrap_or_default();
+    if !validate_matchers(&label_matchers) {
+        return Err(bad_request("invalid label matchers"));
     }
+    let aggregation = body.aggregation.unwrap_or_else(|| "avg".into());
+    if !validate_aggregation(&aggregation) {
+        return Err(bad_request(format!("invalid aggregation '{aggregation}'")));
+    }
+    let window_ms = body.window_ms.unwrap_or(DEFAULT_WINDOW_MS);
+    let min_samples = body.min_samples.unwrap_or(DEFAULT_MIN_SAMPLES);
+    if !validate_window(window_ms, min_samples) {
+        return Err(bad_request(
+            "window_ms must be positive and min_samples at least 1",
+        ));
+    }
+    validate_escalation(&state, escalation_policy_id.as_deref()).await?;
 
     let now_ms = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -118,14 +273,22 @@ pub async fn create_rule(
         id: uuid::Uuid::new_v4().to_string(),
         name: body.name,
         agent_pattern: body.agent_pattern.unwrap_or_else(|| "*".into()),
-        metric_name: body.metric_name,
-        condition: body.condition,
-        threshold: body.threshold,
+        metric_name,
+        label_matchers,
+        expression,
+        absent_for_ms: body.absent_for_ms,
+        condition,
+        threshold,
+        aggregation,
+        window_ms,
+        min_samples,
         for_duration_ms: body.for_duration_ms.unwrap_or(0),
         severity: severity.to_string(),
         annotations: body.annotations.unwrap_or_default(),
         enabled: body.enabled.unwrap_or(true),
         notifier_ids: body.notifier_ids.unwrap_or_default(),
+        grouping: body.grouping,
+        escalation_policy_id,
         created_at_ms: now_ms,
         updated_at_ms: now_ms,
     };
@@ -133,7 +296,7 @@ pub async fn create_rule(
     if let Some(ref repo) = state.rule_repo {
         repo.insert(&record).await.map_err(|e| {
             tracing::error!(target: "rest", error = %e, "rule insert failed");
-            StatusCode::INTERNAL_SERVER_ERROR
+            reject(StatusCode::INTERNAL_SERVER_ERROR, "rule insert failed")
         })?;
     }
 
@@ -146,19 +309,61 @@ pub async fn update_rule(
     State(state): State<AppState>,
     Path(rule_id): Path<String>,
     Json(body): Json<UpdateRuleRequest>,
-) -> Result<Json<RuleResponse>, StatusCode> {
-    let existing = state.rules.get(&rule_id).ok_or(StatusCode::NOT_FOUND)?;
+) -> RuleResult<Json<RuleResponse>> {
+    let existing = state
+        .rules
+        .get(&rule_id)
+        .ok_or_else(|| reject(StatusCode::NOT_FOUND, "rule not found"))?;
 
     if let Some(ref c) = body.condition {
         if !validate_condition(c) {
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(bad_request(format!("invalid condition '{c}'")));
         }
     }
     if let Some(ref s) = body.severity {
         if !validate_severity(s) {
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(bad_request(format!("invalid severity '{s}'")));
+        }
+    }
+    if let Some(ref m) = body.label_matchers {
+        if !validate_matchers(m) {
+            return Err(bad_request("invalid label matchers"));
         }
     }
+    if let Some(ref a) = body.aggregation {
+        if !validate_aggregation(a) {
+            return Err(bad_request(format!("invalid aggregation '{a}'")));
+        }
+    }
+    let window_ms = body.window_ms.unwrap_or(existing.window_ms);
+    let min_samples = body.min_samples.unwrap_or(existing.min_samples);
+    if !validate_window(window_ms, min_samples) {
+        return Err(bad_request(
+            "window_ms must be positive and min_samples at least 1",
+        ));
+    }
+    let metric_name = body.metric_name.unwrap_or(existing.metric_name.clone());
+    let expression = match body.expression {
+        Some(e) if e.trim().is_empty() => None,
+        Some(e) => Some(e),
+        None => existing.expression.clone(),
+    };
+    validate_target(&metric_name, expression.as_deref())?;
+    let absent_for_ms = match body.absent_for_ms {
+        Some(0) => None,
+        Some(ms) => Some(ms),
+        None => existing.absent_for_ms,
+    };
+    validate_absent(absent_for_ms, expression.as_deref())?;
+    let grouping = body.grouping.unwrap_or(existing.grouping.clone());
+    validate_grouping(grouping.as_ref())?;
+    let escalation_policy_id = match body.escalation_policy_id {
+        Some(id) => id.filter(|id| !id.trim().is_empty()),
+        None => existing.escalation_policy_id.clone(),
+    };
+    if escalation_policy_id != existing.escalation_policy_id {
+        validate_escalation(&state, escalation_policy_id.as_deref()).await?;
+    }
 
     let now_ms = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -169,14 +374,24 @@ pub async fn update_rule(
         id: existing.id.clone(),
         name: body.name.unwrap_or(existing.name.clone()),
         agent_pattern: body.agent_pattern.unwrap_or(existing.agent_pattern.clone()),
-        metric_name: body.metric_name.unwrap_or(existing.metric_name.clone()),
+        metric_name,
+        label_matchers: body
+            .label_matchers
+            .unwrap_or(existing.label_matchers.clone()),
+        expression,
+        absent_for_ms,
         condition: body.condition.unwrap_or(existing.condition.clone()),
         threshold: body.threshold.unwrap_or(existing.threshold),
+        aggregation: body.aggregation.unwrap_or(existing.aggregation.clone()),
+        window_ms,
+        min_samples,
         for_duration_ms: body.for_duration_ms.unwrap_or(existing.for_duration_ms),
         severity: body.severity.unwrap_or(existing.severity.clone()),
         annotations: body.annotations.unwrap_or(existing.annotations.clone()),
         enabled: body.enabled.unwrap_or(existing.enabled),
         notifier_ids: body.notifier_ids.unwrap_or(existing.notifier_ids.clone()),
+        grouping,
+        escalation_policy_id,
         created_at_ms: existing.created_at_ms,
         updated_at_ms: now_ms,
     };
@@ -184,7 +399,7 @@ pub async fn update_rule(
     if let Some(ref repo) = state.rule_repo {
         repo.update(&updated).await.map_err(|e| {
             tracing::error!(target: "rest", error = %e, "rule update failed");
-            StatusCode::INTERNAL_SERVER_ERROR
+            reject(StatusCode::INTERNAL_SERVER_ERROR, "rule update failed")
         })?;
     }
 
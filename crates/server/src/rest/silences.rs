@@ -0,0 +1,208 @@
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use serde::{Deserialize, Serialize};
+
+use sentinel_common::labels::LabelMatcher;
+use sentinel_common::silence::{Recurrence, Silence};
+
+use crate::middleware::AuthSubject;
+use crate::persistence::SilenceRepo;
+use crate::rest::AppState;
+
+#[derive(Deserialize)]
+pub struct SilenceQuery {
+    pub state: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SilenceRequest {
+    pub agent_pattern: Option<String>,
+    pub rule_id: Option<String>,
+    pub severity: Option<String>,
+    #[serde(default)]
+    pub label_matchers: Vec<LabelMatcher>,
+    pub starts_at_ms: Option<i64>,
+    pub ends_at_ms: Option<i64>,
+    pub duration_secs: Option<i64>,
+    pub recurrence: Option<Recurrence>,
+    #[serde(default)]
+    pub comment: String,
+}
+
+#[derive(Serialize)]
+pub struct SilenceResponse {
+    #[serde(flatten)]
+    pub silence: Silence,
+    pub state: &'static str,
+}
+
+#[derive(Serialize)]
+pub struct SilenceErrorBody {
+    pub error: String,
+}
+
+type SilenceResult<T> = Result<T, (StatusCode, Json<SilenceErrorBody>)>;
+
+fn reject(status: StatusCode, error: impl Into<String>) -> (StatusCode, Json<SilenceErrorBody>) {
+    (
+        status,
+        Json(SilenceErrorBody {
+            error: error.into(),
+        }),
+    )
+}
+
+fn internal(e: sqlx::Error) -> (StatusCode, Json<SilenceErrorBody>) {
+    tracing::error!(target: "rest", error = %e, "silence query failed");
+    reject(StatusCode::INTERNAL_SERVER_ERROR, "database error")
+}
+
+pub async fn list_silences(
+    State(state): State<AppState>,
+    Query(q): Query<SilenceQuery>,
+) -> SilenceResult<Json<Vec<SilenceResponse>>> {
+    let wanted = q.state.as_deref().unwrap_or("current");
+    if !["current", "active", "pending", "expired", "all"].contains(&wanted) {
+        return Err(reject(
+            StatusCode::BAD_REQUEST,
+            format!("invalid state '{wanted}'"),
+        ));
+    }
+    let repo = repo(&state)?;
+    let now = now_ms();
+
+    let silences = repo.list().await.map_err(internal)?;
+    Ok(Json(
+        silences
+            .into_iter()
+            .map(|s| to_response(s, now))
+            .filter(|r| match wanted {
+                "all" => true,
+                "current" => r.state != "expired",
+                w => r.state == w,
+            })
+            .collect(),
+    ))
+}
+
+pub async fn get_silence(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> SilenceResult<Json<SilenceResponse>> {
+    let repo = repo(&state)?;
+    repo.get(&id)
+        .await
+        .map_err(internal)?
+        .map(|s| Json(to_response(s, now_ms())))
+        .ok_or_else(|| reject(StatusCode::NOT_FOUND, "silence not found"))
+}
+
+pub async fn create_silence(
+    State(state): State<AppState>,
+    Extension(AuthSubject(subject)): Extension<AuthSubject>,
+    Json(body): Json<SilenceRequest>,
+) -> SilenceResult<(StatusCode, Json<SilenceResponse>)> {
+    let silence = build(uuid::Uuid::new_v4().to_string(), subject, body)?;
+    let repo = repo(&state)?;
+
+    repo.insert(&silence).await.map_err(internal)?;
+    tracing::info!(
+        target: "rest",
+        id = %silence.id,
+        created_by = %silence.created_by,
+        "Silence created"
+    );
+    Ok((StatusCode::CREATED, Json(to_response(silence, now_ms()))))
+}
+
+pub async fn update_silence(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(body): Json<SilenceRequest>,
+) -> SilenceResult<Json<SilenceResponse>> {
+    let repo = repo(&state)?;
+    let existing = repo
+        .get(&id)
+        .await
+        .map_err(internal)?
+        .ok_or_else(|| reject(StatusCode::NOT_FOUND, "silence not found"))?;
+
+    let silence = build(existing.id, existing.created_by, body)?;
+    repo.update(&silence).await.map_err(internal)?;
+    Ok(Json(to_response(silence, now_ms())))
+}
+
+pub async fn delete_silence(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> SilenceResult<StatusCode> {
+    let repo = repo(&state)?;
+    if repo.delete(&id).await.map_err(internal)? {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(reject(StatusCode::NOT_FOUND, "silence not found"))
+    }
+}
+
+fn build(id: String, created_by: String, body: SilenceRequest) -> SilenceResult<Silence> {
+    let starts_at_ms = body.starts_at_ms.unwrap_or_else(now_ms);
+    let ends_at_ms = match (body.ends_at_ms, body.duration_secs) {
+        (Some(_), Some(_)) => {
+            return Err(reject(
+                StatusCode::BAD_REQUEST,
+                "set either ends_at_ms or duration_secs, not both",
+            ))
+        }
+        (Some(end), None) => Some(end),
+        (None, Some(secs)) if secs > 0 => Some(starts_at_ms + secs * 1000),
+        (None, Some(_)) => {
+            return Err(reject(
+                StatusCode::BAD_REQUEST,
+                "duration_secs must be positive",
+            ))
+        }
+        (None, None) => None,
+    };
+
+    let silence = Silence {
+        id,
+        agent_pattern: non_empty(body.agent_pattern),
+        rule_id: non_empty(body.rule_id),
+        severity: non_empty(body.severity),
+        label_matchers: body.label_matchers,
+        starts_at_ms,
+        ends_at_ms,
+        recurrence: body.recurrence,
+        comment: body.comment,
+        created_by,
+    };
+    silence
+        .validate()
+        .map_err(|e| reject(StatusCode::BAD_REQUEST, e.to_string()))?;
+    Ok(silence)
+}
+
+fn non_empty(v: Option<String>) -> Option<String> {
+    v.filter(|s| !s.trim().is_empty())
+}
+
+fn repo(state: &AppState) -> SilenceResult<SilenceRepo> {
+    let pool = state
+        .pool
+        .as_ref()
+        .ok_or_else(|| reject(StatusCode::SERVICE_UNAVAILABLE, "database not configured"))?;
+    Ok(SilenceRepo::new(pool.clone()))
+}
+
+fn to_response(silence: Silence, now_ms: i64) -> SilenceResponse {
+    let state = silence.state_at(now_ms).as_str();
+    SilenceResponse { silence, state }
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 use super::deprecated_key::DeprecatedKey;
+use super::pending_key::PendingKey;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentRecord {
@@ -13,5 +14,7 @@ pub struct AgentRecord {
     pub registered_at_ms: i64,
     #[serde(default)]
     pub deprecated_keys: Vec<DeprecatedKey>,
+    #[serde(default)]
+    pub pending_key: Option<PendingKey>,
     pub last_seen: Option<DateTime<Utc>>,
 }
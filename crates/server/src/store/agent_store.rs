@@ -1,10 +1,18 @@
 use super::agent_record::AgentRecord;
 use super::deprecated_key::DeprecatedKey;
+use super::pending_key::PendingKey;
 use dashmap::DashMap;
 use std::sync::Arc;
 
 use sentinel_common::crypto::generate_secret;
 
+#[derive(Debug, Clone)]
+pub struct RotatedKey {
+    pub key_id: String,
+    pub secret: Vec<u8>,
+    pub previous_secret: Vec<u8>,
+}
+
 #[derive(Clone)]
 pub struct AgentStore {
     agents: Arc<DashMap<String, AgentRecord>>,
@@ -48,25 +56,54 @@ impl AgentStore {
         self.agents.len()
     }
 
-    pub fn rotate_key(&self, agent_id: &str) -> Option<(String, Vec<u8>)> {
-        let mut entry = self.agents.get_mut(agent_id)?;
+    pub fn rotated(&self, agent_id: &str, in_band: bool) -> Option<(AgentRecord, RotatedKey)> {
+        let mut record = self.get(agent_id)?;
         let now_ms = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
             .as_millis() as i64;
 
-        let old = DeprecatedKey {
-            key_id: entry.key_id.clone(),
-            secret: entry.secret.clone(),
+        let acked = record.pending_key.take().unwrap_or_else(|| PendingKey {
+            acked_key_id: record.key_id.clone(),
+            acked_secret: record.secret.clone(),
+            rotated_at_ms: now_ms,
+        });
+        record.deprecated_keys.push(DeprecatedKey {
+            key_id: record.key_id.clone(),
+            secret: record.secret.clone(),
             deprecated_at_ms: now_ms,
-        };
-        entry.deprecated_keys.push(old);
+        });
 
         let new_secret = generate_secret();
         let new_key_id = format!("key-{}", uuid::Uuid::new_v4());
-        entry.secret = new_secret.clone();
-        entry.key_id = new_key_id.clone();
-        Some((new_key_id, new_secret))
+        record.secret = new_secret.clone();
+        record.key_id = new_key_id.clone();
+        let rotated = RotatedKey {
+            key_id: new_key_id,
+            secret: new_secret,
+            previous_secret: acked.acked_secret.clone(),
+        };
+        record.pending_key = in_band.then_some(acked);
+        Some((record, rotated))
+    }
+
+    pub fn pending_rotation(&self, agent_id: &str) -> Option<RotatedKey> {
+        let record = self.agents.get(agent_id)?;
+        let pending = record.pending_key.as_ref()?;
+        Some(RotatedKey {
+            key_id: record.key_id.clone(),
+            secret: record.secret.clone(),
+            previous_secret: pending.acked_secret.clone(),
+        })
+    }
+
+    pub fn acked(&self, agent_id: &str, key_id: &str) -> Option<AgentRecord> {
+        let mut record = self.get(agent_id)?;
+        if record.pending_key.is_none() || record.key_id != key_id {
+            return None;
+        }
+        record.pending_key = None;
+        Some(record)
     }
 
     pub fn find_key_secret(
@@ -77,6 +114,12 @@ impl AgentStore {
     ) -> Option<Vec<u8>> {
         let record = self.agents.get(agent_id)?;
 
+        if let Some(pending) = &record.pending_key {
+            if matches!(key_id, None | Some("default")) || key_id == Some(&pending.acked_key_id) {
+                return Some(pending.acked_secret.clone());
+            }
+        }
+
         match key_id {
             None | Some("default") => Some(record.secret.clone()),
             Some(kid) if kid == record.key_id => Some(record.secret.clone()),
@@ -120,6 +163,7 @@ mod tests {
             agent_version: "0.1.0".into(),
             registered_at_ms: 1000,
             deprecated_keys: Vec::new(),
+            pending_key: None,
             last_seen: None,
         }
     }
@@ -169,4 +213,61 @@ mod tests {
         let secret = store.find_key_secret("agent-1", None, 86400000);
         assert_eq!(secret, Some(b"secret".to_vec()));
     }
+
+    #[test]
+    fn rotate_key_keeps_previous_secret_within_grace() {
+        let store = AgentStore::new();
+        store.insert(sample_record());
+        let (record, rotated) = store.rotated("agent-1", false).unwrap();
+        assert_eq!(store.get("agent-1").unwrap().key_id, "key-1");
+        store.insert(record);
+        assert_eq!(rotated.previous_secret, b"secret".to_vec());
+        assert_eq!(
+            store.find_key_secret("agent-1", Some(&rotated.key_id), 86400000),
+            Some(rotated.secret)
+        );
+        assert_eq!(
+            store.find_key_secret("agent-1", Some("key-1"), 86400000),
+            Some(b"secret".to_vec())
+        );
+        assert_eq!(store.find_key_secret("agent-1", Some("key-1"), 0), None);
+    }
+
+    #[test]
+    fn pending_rotation_chains_off_acked_key() {
+        let store = AgentStore::new();
+        store.insert(sample_record());
+        let (record, first) = store.rotated("agent-1", true).unwrap();
+        store.insert(record);
+        let (record, second) = store.rotated("agent-1", true).unwrap();
+        store.insert(record);
+
+        assert_eq!(first.previous_secret, b"secret".to_vec());
+        assert_eq!(second.previous_secret, b"secret".to_vec());
+        let pending = store.pending_rotation("agent-1").unwrap();
+        assert_eq!(pending.key_id, second.key_id);
+        assert_eq!(pending.previous_secret, b"secret".to_vec());
+
+        assert_eq!(
+            store.find_key_secret("agent-1", Some("key-1"), 0),
+            Some(b"secret".to_vec())
+        );
+        assert_eq!(
+            store.find_key_secret("agent-1", Some("default"), 0),
+            Some(b"secret".to_vec())
+        );
+        assert_eq!(
+            store.find_key_secret("agent-1", Some(&first.key_id), 0),
+            None
+        );
+
+        assert!(store.acked("agent-1", &first.key_id).is_none());
+        store.insert(store.acked("agent-1", &second.key_id).unwrap());
+        assert!(store.pending_rotation("agent-1").is_none());
+        assert_eq!(store.find_key_secret("agent-1", Some("key-1"), 0), None);
+        assert_eq!(
+            store.find_key_secret("agent-1", Some(&second.key_id), 0),
+            Some(second.secret)
+        );
+    }
 }
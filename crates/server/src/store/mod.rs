@@ -2,11 +2,13 @@ mod agent_record;
 mod agent_store;
 mod deprecated_key;
 mod idempotency_store;
+mod pending_key;
 pub mod rule_record;
 mod rule_store;
 
 pub use agent_record::AgentRecord;
-pub use agent_store::AgentStore;
+pub use agent_store::{AgentStore, RotatedKey};
 pub use deprecated_key::DeprecatedKey;
 pub use idempotency_store::IdempotencyStore;
+pub use pending_key::PendingKey;
 pub use rule_store::RuleStore;
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingKey {
+    pub acked_key_id: String,
+    pub acked_secret: Vec<u8>,
+    pub rotated_at_ms: i64,
+}
@@ -1,3 +1,5 @@
+use sentinel_common::grouping::GroupPolicy;
+use sentinel_common::labels::LabelMatcher;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -7,14 +9,45 @@ pub struct RuleRecord {
     pub name: String,
     pub agent_pattern: String,
     pub metric_name: String,
+    #[serde(default)]
+    pub label_matchers: Vec<LabelMatcher>,
+    #[serde(default)]
+    pub expression: Option<String>,
+    #[serde(default)]
+    pub absent_for_ms: Option<i64>,
     pub condition: String,
     pub threshold: f64,
+    #[serde(default = "default_aggregation")]
+    pub aggregation: String,
+    #[serde(default = "default_window_ms")]
+    pub window_ms: i64,
+    #[serde(default = "default_min_samples")]
+    pub min_samples: i64,
     pub for_duration_ms: i64,
     pub severity: String,
     pub annotations: HashMap<String, String>,
     pub enabled: bool,
     #[serde(default)]
     pub notifier_ids: Vec<String>,
+    #[serde(default)]
+    pub grouping: Option<GroupPolicy>,
+    #[serde(default)]
+    pub escalation_policy_id: Option<String>,
     pub created_at_ms: i64,
     pub updated_at_ms: i64,
 }
+
+pub const DEFAULT_WINDOW_MS: i64 = 120_000;
+pub const DEFAULT_MIN_SAMPLES: i64 = 2;
+
+fn default_aggregation() -> String {
+    "avg".into()
+}
+
+fn default_window_ms() -> i64 {
+    DEFAULT_WINDOW_MS
+}
+
+fn default_min_samples() -> i64 {
+    DEFAULT_MIN_SAMPLES
+}
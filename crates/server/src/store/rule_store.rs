@@ -70,13 +70,21 @@ mod tests {
             name: "High CPU".into(),
             agent_pattern: "*".into(),
             metric_name: "cpu".into(),
+            label_matchers: Vec::new(),
+            expression: None,
+            absent_for_ms: None,
             condition: "GreaterThan".into(),
             threshold: 80.0,
+            aggregation: "avg".into(),
+            window_ms: 120_000,
+            min_samples: 2,
             for_duration_ms: 0,
             severity: "warning".into(),
             annotations: HashMap::new(),
             enabled: true,
             notifier_ids: Vec::new(),
+            grouping: None,
+            escalation_policy_id: None,
             created_at_ms: 1000,
             updated_at_ms: 1000,
         }
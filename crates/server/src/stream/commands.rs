@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use sentinel_common::proto::{
+    command::Action, server_message::Payload, Command, CommandResult, ReloadConfig,
+    RestartCollector, ServerMessage, UpdateInterval,
+};
+
+use super::registry::SessionRegistry;
+use super::replies::{command_reply_key, Reply, WaitError};
+
+pub const COMMANDS_CAPABILITY: &str = "commands";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum CommandAction {
+    ReloadConfig,
+    RestartCollector,
+    UpdateInterval { interval_seconds: u64 },
+}
+
+impl CommandAction {
+    fn to_proto(&self) -> Action {
+        match self {
+            Self::ReloadConfig => Action::ReloadConfig(ReloadConfig {}),
+            Self::RestartCollector => Action::RestartCollector(RestartCollector {}),
+            Self::UpdateInterval { interval_seconds } => Action::UpdateInterval(UpdateInterval {
+                interval_seconds: *interval_seconds,
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandStatus {
+    Succeeded,
+    Failed,
+    Timeout,
+    Offline,
+    Unsupported,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandOutcome {
+    pub command_id: String,
+    pub agent_id: String,
+    #[serde(flatten)]
+    pub action: CommandAction,
+    pub status: CommandStatus,
+    pub message: String,
+}
+
+pub async fn send_command(
+    registry: &SessionRegistry,
+    agent_id: &str,
+    action: CommandAction,
+    timeout: Duration,
+) -> CommandOutcome {
+    let command_id = uuid::Uuid::new_v4().to_string();
+    let outcome = |status, message: &str| CommandOutcome {
+        command_id: command_id.clone(),
+        agent_id: agent_id.to_string(),
+        action: action.clone(),
+        status,
+        message: message.to_string(),
+    };
+
+    match registry.supports(agent_id, COMMANDS_CAPABILITY) {
+        None => return outcome(CommandStatus::Offline, "agent not connected"),
+        Some(false) => {
+            return outcome(
+                CommandStatus::Unsupported,
+                "agent does not support remote commands",
+            )
+        }
+        Some(true) => {}
+    }
+
+    let pending = registry
+        .replies()
+        .register(command_reply_key(agent_id, &command_id));
+
+    let msg = ServerMessage {
+        payload: Some(Payload::Command(Command {
+            command_id: command_id.clone(),
+            action: Some(action.to_proto()),
+        })),
+    };
+    if registry.send_to(agent_id, msg).await.is_err() {
+        return outcome(CommandStatus::Offline, "agent stream closed");
+    }
+
+    tracing::info!(target: "system", %agent_id, %command_id, ?action, "Command sent");
+
+    match pending.wait(timeout).await {
+        Ok(reply) if reply.success => outcome(CommandStatus::Succeeded, &reply.message),
+        Ok(reply) => outcome(CommandStatus::Failed, &reply.message),
+        Err(WaitError::Timeout) => outcome(CommandStatus::Timeout, "no result before timeout"),
+        Err(WaitError::Dropped) => outcome(CommandStatus::Offline, "agent disconnected"),
+    }
+}
+
+pub fn handle_command_result(agent_id: &str, result: &CommandResult, registry: &SessionRegistry) {
+    if result.success {
+        tracing::info!(target: "system", %agent_id, command_id = %result.command_id, message = %result.message, "Command succeeded");
+    } else {
+        tracing::warn!(target: "system", %agent_id, command_id = %result.command_id, reason = %result.message, "Command failed");
+    }
+
+    registry.replies().resolve(
+        &command_reply_key(agent_id, &result.command_id),
+        Reply {
+            success: result.success,
+            message: result.message.clone(),
+        },
+    );
+}
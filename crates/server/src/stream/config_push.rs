@@ -0,0 +1,118 @@
+use serde::Serialize;
+use std::time::Duration;
+use tokio::task::JoinSet;
+
+use sentinel_common::proto::{server_message::Payload, ConfigAck, ConfigUpdate, ServerMessage};
+
+use super::registry::SessionRegistry;
+use super::replies::{config_reply_key, Reply, WaitError};
+
+pub const CONFIG_CAPABILITY: &str = "config";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PushStatus {
+    Applied,
+    Rejected,
+    Timeout,
+    Offline,
+    Unsupported,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigPushResult {
+    pub agent_id: String,
+    pub version: i64,
+    pub status: PushStatus,
+    pub message: String,
+}
+
+pub async fn push_config(
+    registry: &SessionRegistry,
+    agent_id: &str,
+    version: i64,
+    config_yaml: Vec<u8>,
+    timeout: Duration,
+) -> ConfigPushResult {
+    let result = |status, message: &str| ConfigPushResult {
+        agent_id: agent_id.to_string(),
+        version,
+        status,
+        message: message.to_string(),
+    };
+
+    match registry.supports(agent_id, CONFIG_CAPABILITY) {
+        None => return result(PushStatus::Offline, "agent not connected"),
+        Some(false) => {
+            return result(
+                PushStatus::Unsupported,
+                "agent does not support remote config",
+            )
+        }
+        Some(true) => {}
+    }
+
+    let pending = registry
+        .replies()
+        .register(config_reply_key(agent_id, version));
+
+    let msg = ServerMessage {
+        payload: Some(Payload::ConfigUpdate(ConfigUpdate {
+            version,
+            config_yaml,
+        })),
+    };
+    if registry.send_to(agent_id, msg).await.is_err() {
+        return result(PushStatus::Offline, "agent stream closed");
+    }
+
+    tracing::info!(target: "cfg", %agent_id, version, "Config update pushed");
+
+    match pending.wait(timeout).await {
+        Ok(reply) if reply.success => result(PushStatus::Applied, &reply.message),
+        Ok(reply) => result(PushStatus::Rejected, &reply.message),
+        Err(WaitError::Timeout) => result(PushStatus::Timeout, "no ack before timeout"),
+        Err(WaitError::Dropped) => result(PushStatus::Offline, "agent disconnected"),
+    }
+}
+
+pub async fn push_config_to_pattern(
+    registry: &SessionRegistry,
+    pattern: &str,
+    version: i64,
+    config_yaml: Vec<u8>,
+    timeout: Duration,
+) -> Vec<ConfigPushResult> {
+    let mut tasks = JoinSet::new();
+    for agent_id in registry.matching_agent_ids(pattern) {
+        let registry = registry.clone();
+        let yaml = config_yaml.clone();
+        tasks.spawn(async move { push_config(&registry, &agent_id, version, yaml, timeout).await });
+    }
+
+    let mut results = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok(r) = joined {
+            results.push(r);
+        }
+    }
+    results.sort_by(|a, b| a.agent_id.cmp(&b.agent_id));
+    results
+}
+
+pub fn handle_config_ack(agent_id: &str, ack: &ConfigAck, registry: &SessionRegistry) {
+    if ack.applied {
+        registry.record_config_version(agent_id, ack.version);
+        tracing::info!(target: "cfg", %agent_id, version = ack.version, message = %ack.message, "Config applied by agent");
+    } else {
+        tracing::warn!(target: "cfg", %agent_id, version = ack.version, reason = %ack.message, "Config rejected by agent");
+    }
+
+    registry.replies().resolve(
+        &config_reply_key(agent_id, ack.version),
+        Reply {
+            success: ack.applied,
+            message: ack.message.clone(),
+        },
+    );
+}
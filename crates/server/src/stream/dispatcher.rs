@@ -7,9 +7,13 @@ use sentinel_common::proto::{
 
 use crate::broker::BrokerPublisher;
 use crate::metrics::server_metrics::ServerMetrics;
+use crate::persistence::AgentRepo;
 use crate::store::{AgentStore, IdempotencyStore};
 
+use super::commands::handle_command_result;
+use super::config_push::handle_config_ack;
 use super::heartbeat_handler::handle_heartbeat_ping;
+use super::key_push::{handle_key_rotation_ack, handle_key_rotation_request};
 use super::metrics_handler::{handle_metrics_batch, MetricsHandlerCtx};
 use super::presence::PresenceEventBus;
 use super::registry::SessionRegistry;
@@ -20,6 +24,7 @@ pub async fn dispatch(
     key_id: &str,
     msg: AgentMessage,
     agents: &AgentStore,
+    agent_repo: Option<&AgentRepo>,
     idempotency: &IdempotencyStore,
     broker: &dyn BrokerPublisher,
     registry: &SessionRegistry,
@@ -48,6 +53,21 @@ pub async fn dispatch(
             let response = handle_heartbeat_ping(agent_id, &ping, registry, events);
             Some(response)
         }
+        AgentPayload::ConfigAck(ack) => {
+            handle_config_ack(agent_id, &ack, registry);
+            None
+        }
+        AgentPayload::CommandResult(result) => {
+            handle_command_result(agent_id, &result, registry);
+            None
+        }
+        AgentPayload::KeyRotationAck(ack) => {
+            handle_key_rotation_ack(agent_id, &ack, agents, agent_repo, registry).await;
+            None
+        }
+        AgentPayload::KeyRotationRequest(req) => {
+            handle_key_rotation_request(agent_id, &req, agents, agent_repo).await
+        }
         AgentPayload::Handshake(_) => Some(error_message(
             400,
             "unexpected handshake on authenticated stream",
@@ -61,7 +81,7 @@ pub async fn dispatch(
     }
 }
 
-fn error_message(code: u32, message: &str, fatal: bool) -> ServerMessage {
+pub(super) fn error_message(code: u32, message: &str, fatal: bool) -> ServerMessage {
     ServerMessage {
         payload: Some(ServerPayload::Error(ServerError {
             code,
@@ -21,6 +21,7 @@ use crate::store::{AgentStore, IdempotencyStore};
 
 use super::authenticator::{authenticate_handshake, AuthOutcome};
 use super::dispatcher;
+use super::key_push::resume_rotation;
 use super::presence::{DisconnectReason, PresenceEvent, PresenceEventBus};
 use super::registry::SessionRegistry;
 use super::session::Session;
@@ -168,6 +169,15 @@ async fn run_stream<B: BrokerPublisher>(
 
     tracing::info!(target: "conn", %agent_id, "Stream authenticated");
 
+    resume_rotation(
+        &agents,
+        agent_repo.as_deref(),
+        &registry,
+        &agent_id,
+        &key_id,
+    )
+    .await;
+
     let version = registry
         .snapshot(&agent_id)
         .map(|s| s.agent_version)
@@ -185,6 +195,7 @@ async fn run_stream<B: BrokerPublisher>(
         &mut inbound,
         &tx,
         &agents,
+        agent_repo.as_deref(),
         &idempotency,
         broker.as_ref(),
         &registry,
@@ -364,6 +375,7 @@ async fn message_loop<B: BrokerPublisher>(
     inbound: &mut Streaming<AgentMessage>,
     tx: &Arc<mpsc::Sender<Result<ServerMessage, Status>>>,
     agents: &AgentStore,
+    agent_repo: Option<&AgentRepo>,
     idempotency: &IdempotencyStore,
     broker: &B,
     registry: &SessionRegistry,
@@ -379,6 +391,7 @@ async fn message_loop<B: BrokerPublisher>(
             key_id,
             msg,
             agents,
+            agent_repo,
             idempotency,
             broker,
             registry,
@@ -0,0 +1,220 @@
+use serde::Serialize;
+use std::time::Duration;
+
+use sentinel_common::key_rotation::{seal_secret, KEY_ROTATION_CAPABILITY};
+use sentinel_common::proto::{
+    server_message::Payload, KeyRotation, KeyRotationAck, KeyRotationRequest, ServerMessage,
+};
+
+use crate::persistence::AgentRepo;
+use crate::store::{AgentStore, RotatedKey};
+
+use super::config_push::PushStatus;
+use super::dispatcher::error_message;
+use super::registry::SessionRegistry;
+use super::replies::{key_reply_key, Reply, WaitError};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyPushResult {
+    pub status: PushStatus,
+    pub message: String,
+}
+
+pub fn rotation_message(agent_id: &str, rotated: &RotatedKey) -> (String, ServerMessage) {
+    let rotation_id = uuid::Uuid::new_v4().to_string();
+    let msg = ServerMessage {
+        payload: Some(Payload::KeyRotation(KeyRotation {
+            rotation_id: rotation_id.clone(),
+            key_id: rotated.key_id.clone(),
+            sealed_secret: seal_secret(
+                &rotated.previous_secret,
+                agent_id,
+                &rotated.key_id,
+                &rotated.secret,
+            ),
+            issued_at_ms: current_time_ms(),
+        })),
+    };
+    (rotation_id, msg)
+}
+
+pub async fn push_key(
+    registry: &SessionRegistry,
+    agent_id: &str,
+    rotated: &RotatedKey,
+    timeout: Duration,
+) -> KeyPushResult {
+    let result = |status, message: &str| KeyPushResult {
+        status,
+        message: message.to_string(),
+    };
+
+    match registry.supports(agent_id, KEY_ROTATION_CAPABILITY) {
+        None => return result(PushStatus::Offline, "agent not connected"),
+        Some(false) => {
+            return result(
+                PushStatus::Unsupported,
+                "agent does not support in-band key rotation",
+            )
+        }
+        Some(true) => {}
+    }
+
+    let (rotation_id, msg) = rotation_message(agent_id, rotated);
+    let pending = registry
+        .replies()
+        .register(key_reply_key(agent_id, &rotation_id));
+
+    if registry.send_to(agent_id, msg).await.is_err() {
+        return result(PushStatus::Offline, "agent stream closed");
+    }
+
+    tracing::info!(target: "auth", %agent_id, key_id = %rotated.key_id, "Rotated key pushed");
+
+    match pending.wait(timeout).await {
+        Ok(reply) if reply.success => result(PushStatus::Applied, &reply.message),
+        Ok(reply) => result(PushStatus::Rejected, &reply.message),
+        Err(WaitError::Timeout) => result(PushStatus::Timeout, "no ack before timeout"),
+        Err(WaitError::Dropped) => result(PushStatus::Offline, "agent disconnected"),
+    }
+}
+
+pub async fn rotate_key(
+    agents: &AgentStore,
+    repo: Option<&AgentRepo>,
+    agent_id: &str,
+    in_band: bool,
+) -> Result<Option<RotatedKey>, sqlx::Error> {
+    let Some((record, rotated)) = agents.rotated(agent_id, in_band) else {
+        return Ok(None);
+    };
+    if let Some(repo) = repo {
+        repo.upsert(&record).await?;
+    }
+    agents.insert(record);
+    Ok(Some(rotated))
+}
+
+pub async fn confirm_key(
+    agents: &AgentStore,
+    repo: Option<&AgentRepo>,
+    agent_id: &str,
+    key_id: &str,
+) {
+    let Some(record) = agents.acked(agent_id, key_id) else {
+        return;
+    };
+    if let Some(repo) = repo {
+        if let Err(e) = repo.upsert(&record).await {
+            tracing::error!(target: "auth", %agent_id, error = %e, "Failed to persist acknowledged key");
+            return;
+        }
+    }
+    agents.insert(record);
+    tracing::info!(target: "auth", %agent_id, %key_id, "Rotated key confirmed by agent");
+}
+
+pub async fn resume_rotation(
+    agents: &AgentStore,
+    repo: Option<&AgentRepo>,
+    registry: &SessionRegistry,
+    agent_id: &str,
+    key_id: &str,
+) {
+    let Some(rotated) = agents.pending_rotation(agent_id) else {
+        return;
+    };
+    if rotated.key_id == key_id {
+        confirm_key(agents, repo, agent_id, key_id).await;
+        return;
+    }
+    if registry.supports(agent_id, KEY_ROTATION_CAPABILITY) != Some(true) {
+        tracing::warn!(target: "auth", %agent_id, key_id = %rotated.key_id, "Rotated key pending but agent does not support in-band key rotation");
+        return;
+    }
+    let (_, msg) = rotation_message(agent_id, &rotated);
+    if registry.send_to(agent_id, msg).await.is_ok() {
+        tracing::info!(target: "auth", %agent_id, key_id = %rotated.key_id, "Pending rotated key re-sent");
+    }
+}
+
+pub async fn handle_key_rotation_request(
+    agent_id: &str,
+    req: &KeyRotationRequest,
+    agents: &AgentStore,
+    agent_repo: Option<&AgentRepo>,
+) -> Option<ServerMessage> {
+    let rotated = match rotate_key(agents, agent_repo, agent_id, true).await {
+        Ok(rotated) => rotated?,
+        Err(e) => {
+            tracing::error!(target: "auth", %agent_id, error = %e, "Failed to persist rotated key");
+            return Some(error_message(500, "failed to persist rotated key", false));
+        }
+    };
+
+    tracing::info!(
+        target: "auth",
+        %agent_id,
+        previous_key_id = %req.current_key_id,
+        key_id = %rotated.key_id,
+        "HMAC key rotated on agent request"
+    );
+
+    let (_, msg) = rotation_message(agent_id, &rotated);
+    Some(msg)
+}
+
+pub async fn handle_key_rotation_ack(
+    agent_id: &str,
+    ack: &KeyRotationAck,
+    agents: &AgentStore,
+    agent_repo: Option<&AgentRepo>,
+    registry: &SessionRegistry,
+) {
+    if ack.applied {
+        tracing::info!(target: "auth", %agent_id, key_id = %ack.key_id, "Rotated key applied by agent");
+        confirm_key(agents, agent_repo, agent_id, &ack.key_id).await;
+    } else {
+        tracing::warn!(target: "auth", %agent_id, key_id = %ack.key_id, reason = %ack.message, "Rotated key rejected by agent");
+    }
+
+    registry.replies().resolve(
+        &key_reply_key(agent_id, &ack.rotation_id),
+        Reply {
+            success: ack.applied,
+            message: ack.message.clone(),
+        },
+    );
+}
+
+fn current_time_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sentinel_common::key_rotation::open_secret;
+
+    #[test]
+    fn rotation_message_seals_secret_under_previous_key() {
+        let rotated = RotatedKey {
+            key_id: "key-2".into(),
+            secret: b"new".to_vec(),
+            previous_secret: b"old".to_vec(),
+        };
+        let (rotation_id, msg) = rotation_message("agent-1", &rotated);
+        let Some(Payload::KeyRotation(rotation)) = msg.payload else {
+            panic!("expected key rotation payload");
+        };
+        assert_eq!(rotation.rotation_id, rotation_id);
+        assert_eq!(rotation.key_id, "key-2");
+        assert_eq!(
+            open_secret(b"old", "agent-1", "key-2", &rotation.sealed_secret),
+            Some(b"new".to_vec())
+        );
+    }
+}
@@ -4,6 +4,8 @@ use sentinel_common::proto::{
     server_message::Payload, Batch, BatchAck, BatchAckStatus, MetricsBatch, ServerMessage,
 };
 
+use sentinel_common::{batch_reject, wal_drops};
+
 use crate::auth::verify_signature;
 use crate::broker::BrokerPublisher;
 use crate::metrics::server_metrics::ServerMetrics;
@@ -17,12 +19,45 @@ pub struct MetricsHandlerCtx<'a> {
     pub metrics: &'a Arc<ServerMetrics>,
 }
 
+pub struct IngestOutcome {
+    pub status: BatchAckStatus,
+    pub message: &'static str,
+}
+
+impl IngestOutcome {
+    fn new(status: BatchAckStatus, message: &'static str) -> Self {
+        Self { status, message }
+    }
+}
+
 pub async fn handle_metrics_batch(
     agent_id: &str,
     key_id: &str,
     batch: MetricsBatch,
     ctx: &MetricsHandlerCtx<'_>,
 ) -> ServerMessage {
+    let key_id = if batch.key_id.is_empty() {
+        key_id
+    } else {
+        batch.key_id.as_str()
+    };
+    let legacy_batch = to_legacy_batch(agent_id, &batch);
+    let signature = if batch.signature.is_empty() {
+        None
+    } else {
+        Some(batch.signature.as_str())
+    };
+
+    let outcome = ingest_batch(key_id, &legacy_batch, signature, ctx).await;
+    ack_message(&batch.batch_id, outcome.status, outcome.message)
+}
+
+pub async fn ingest_batch(
+    key_id: &str,
+    batch: &Batch,
+    signature: Option<&str>,
+    ctx: &MetricsHandlerCtx<'_>,
+) -> IngestOutcome {
     let MetricsHandlerCtx {
         agents,
         idempotency,
@@ -32,55 +67,39 @@ pub async fn handle_metrics_batch(
     } = ctx;
     if batch.batch_id.is_empty() {
         metrics.inc_pushes_rejected();
-        return ack_message(
-            &batch.batch_id,
+        return IngestOutcome::new(
             BatchAckStatus::BatchRejected,
-            "batch_id is required",
-        );
-    }
-
-    if idempotency.is_duplicate(&batch.batch_id) {
-        return ack_message(
-            &batch.batch_id,
-            BatchAckStatus::BatchAccepted,
-            "duplicate, already processed",
+            batch_reject::MISSING_BATCH_ID,
         );
     }
 
-    let secret = match agents.find_key_secret(agent_id, Some(key_id), *grace_period_ms) {
+    let secret = match agents.find_key_secret(&batch.agent_id, Some(key_id), *grace_period_ms) {
         Some(s) => s,
         None => {
             metrics.inc_pushes_rejected();
-            return ack_message(
-                &batch.batch_id,
-                BatchAckStatus::BatchRejected,
-                "unknown or expired key",
-            );
+            return IngestOutcome::new(BatchAckStatus::BatchRejected, batch_reject::UNKNOWN_KEY);
         }
     };
 
-    let legacy_batch = to_legacy_batch(agent_id, &batch);
-    let canonical = sentinel_common::canonicalize::canonical_bytes(&legacy_batch);
+    if let Some(sig) = signature {
+        let canonical = sentinel_common::canonicalize::canonical_bytes(batch);
+        if !verify_signature(&secret, &canonical, sig) {
+            metrics.inc_pushes_rejected();
+            return IngestOutcome::new(
+                BatchAckStatus::BatchRejected,
+                batch_reject::INVALID_SIGNATURE,
+            );
+        }
+    }
 
-    if !batch.signature.is_empty() && !verify_signature(&secret, &canonical, &batch.signature) {
-        metrics.inc_pushes_rejected();
-        return ack_message(
-            &batch.batch_id,
-            BatchAckStatus::BatchRejected,
-            "invalid batch signature",
+    if idempotency.is_duplicate(&batch.batch_id) {
+        return IngestOutcome::new(
+            BatchAckStatus::BatchAccepted,
+            "duplicate, already processed",
         );
     }
 
-    let signature_str = if batch.signature.is_empty() {
-        None
-    } else {
-        Some(batch.signature.as_str())
-    };
-
-    if let Err(e) = broker
-        .publish(&legacy_batch, signature_str, Some(key_id))
-        .await
-    {
+    if let Err(e) = broker.publish(batch, signature, Some(key_id)).await {
         tracing::error!(
             target: "data",
             batch_id = %batch.batch_id,
@@ -89,11 +108,7 @@ pub async fn handle_metrics_batch(
             sentinel_common::logging::actionable::broker_publish_failed(&batch.batch_id, &e)
         );
         metrics.inc_broker_publish_errors();
-        return ack_message(
-            &batch.batch_id,
-            BatchAckStatus::BatchRetry,
-            "broker unavailable",
-        );
+        return IngestOutcome::new(BatchAckStatus::BatchRetry, "broker unavailable");
     }
 
     let now_ms = current_time_ms();
@@ -101,7 +116,20 @@ pub async fn handle_metrics_batch(
 
     metrics.inc_pushes_accepted();
 
-    ack_message(&batch.batch_id, BatchAckStatus::BatchAccepted, "accepted")
+    let dropped = wal_drops::dropped_batches(&batch.meta);
+    if dropped > 0 {
+        tracing::warn!(
+            target: "data",
+            agent_id = %batch.agent_id,
+            batch_id = %batch.batch_id,
+            dropped,
+            reasons = ?wal_drops::drop_reasons(&batch.meta),
+            "Agent dropped unsent batches from its WAL, expect a data gap"
+        );
+        metrics.add_agent_wal_dropped(dropped);
+    }
+
+    IngestOutcome::new(BatchAckStatus::BatchAccepted, "accepted")
 }
 
 fn to_legacy_batch(agent_id: &str, mb: &MetricsBatch) -> Batch {
@@ -1,16 +1,22 @@
 mod authenticator;
+pub mod commands;
+pub mod config_push;
 mod dispatcher;
 mod handler;
 mod heartbeat_handler;
+pub mod key_push;
 pub mod latency;
-mod metrics_handler;
+pub mod metrics_handler;
 pub mod presence;
+pub mod presence_relay;
 pub mod registry;
+pub mod replies;
 pub mod session;
 pub mod watchdog;
 
 pub use handler::StreamService;
 pub use presence::{DisconnectReason, PresenceEvent, PresenceEventBus};
+pub use presence_relay::spawn_presence_relay;
 pub use registry::{ClusterStats, SessionRegistry};
 pub use session::Session;
 pub use watchdog::{spawn_watchdog, WatchdogConfig};
@@ -48,6 +48,17 @@ pub enum DisconnectReason {
     ServerShutdown,
 }
 
+impl DisconnectReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::StreamClosed => "stream_closed",
+            Self::StaleTimeout => "stale_timeout",
+            Self::Evicted => "evicted",
+            Self::ServerShutdown => "server_shutdown",
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct PresenceEventBus {
     tx: broadcast::Sender<PresenceEvent>,
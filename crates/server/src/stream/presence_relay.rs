@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+
+use sentinel_common::nats_config::presence_subject;
+use sentinel_common::presence::{AgentPresence, PresenceStatus};
+use tokio::sync::broadcast::error::RecvError;
+
+use super::presence::{DisconnectReason, PresenceEvent, PresenceEventBus};
+
+#[derive(Default)]
+pub struct PresenceRelay {
+    last: HashMap<String, PresenceStatus>,
+}
+
+impl PresenceRelay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn next(&mut self, event: &PresenceEvent) -> Option<AgentPresence> {
+        let presence = match event {
+            PresenceEvent::AgentConnected { agent_id, at, .. } => AgentPresence {
+                agent_id: agent_id.clone(),
+                status: PresenceStatus::Online,
+                reason: None,
+                last_seen_ms_ago: None,
+                at_ms: at.timestamp_millis(),
+            },
+            PresenceEvent::AgentStale {
+                agent_id,
+                last_ping_ms_ago,
+                at,
+                ..
+            } => AgentPresence {
+                agent_id: agent_id.clone(),
+                status: PresenceStatus::Stale,
+                reason: None,
+                last_seen_ms_ago: Some(*last_ping_ms_ago),
+                at_ms: at.timestamp_millis(),
+            },
+            PresenceEvent::AgentDisconnected {
+                reason: DisconnectReason::ServerShutdown,
+                ..
+            } => return None,
+            PresenceEvent::AgentDisconnected {
+                agent_id,
+                reason,
+                at,
+                ..
+            } => AgentPresence {
+                agent_id: agent_id.clone(),
+                status: PresenceStatus::Offline,
+                reason: Some(reason.as_str().to_string()),
+                last_seen_ms_ago: None,
+                at_ms: at.timestamp_millis(),
+            },
+            PresenceEvent::HeartbeatReceived { .. } => return None,
+        };
+
+        if self.last.get(&presence.agent_id) == Some(&presence.status) {
+            return None;
+        }
+        self.last.insert(presence.agent_id.clone(), presence.status);
+        Some(presence)
+    }
+}
+
+pub fn spawn_presence_relay(
+    events: PresenceEventBus,
+    client: async_nats::Client,
+) -> tokio::task::JoinHandle<()> {
+    let mut rx = events.subscribe();
+    tokio::spawn(async move {
+        let mut relay = PresenceRelay::new();
+        loop {
+            let event = match rx.recv().await {
+                Ok(e) => e,
+                Err(RecvError::Lagged(n)) => {
+                    tracing::warn!(target: "conn", skipped = n, "Presence relay lagged");
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+            let Some(presence) = relay.next(&event) else {
+                continue;
+            };
+            let subject = presence_subject(&presence.agent_id);
+            if let Err(e) = client.publish(subject, presence.to_json().into()).await {
+                tracing::warn!(
+                    target: "net",
+                    agent_id = %presence.agent_id,
+                    error = %e,
+                    "Failed to relay presence change"
+                );
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn stale(agent: &str) -> PresenceEvent {
+        PresenceEvent::AgentStale {
+            agent_id: agent.into(),
+            last_ping_ms_ago: 35_000,
+            expected_interval_ms: 10_000,
+            at: Utc::now(),
+        }
+    }
+
+    fn disconnected(agent: &str, reason: DisconnectReason) -> PresenceEvent {
+        PresenceEvent::AgentDisconnected {
+            agent_id: agent.into(),
+            reason,
+            connected_duration_ms: 0,
+            at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn relays_status_changes_once() {
+        let mut relay = PresenceRelay::new();
+        let first = relay.next(&stale("a1")).unwrap();
+        assert_eq!(first.status, PresenceStatus::Stale);
+        assert_eq!(first.last_seen_ms_ago, Some(35_000));
+        assert!(relay.next(&stale("a1")).is_none());
+
+        let offline = relay
+            .next(&disconnected("a1", DisconnectReason::StaleTimeout))
+            .unwrap();
+        assert_eq!(offline.status, PresenceStatus::Offline);
+        assert_eq!(offline.reason.as_deref(), Some("stale_timeout"));
+
+        let online = relay
+            .next(&PresenceEvent::AgentConnected {
+                agent_id: "a1".into(),
+                agent_version: "1.0".into(),
+                at: Utc::now(),
+            })
+            .unwrap();
+        assert_eq!(online.status, PresenceStatus::Online);
+    }
+
+    #[test]
+    fn ignores_heartbeats_and_server_shutdown() {
+        let mut relay = PresenceRelay::new();
+        assert!(relay
+            .next(&disconnected("a1", DisconnectReason::ServerShutdown))
+            .is_none());
+        assert!(relay
+            .next(&PresenceEvent::HeartbeatReceived {
+                agent_id: "a1".into(),
+                latency_ms: 3,
+                cpu_percent: 1.0,
+                memory_percent: 2.0,
+                at: Utc::now(),
+            })
+            .is_none());
+    }
+}
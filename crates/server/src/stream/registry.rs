@@ -1,11 +1,15 @@
 use dashmap::DashMap;
 use std::sync::Arc;
 
-use super::session::{Session, SessionSnapshot};
+use sentinel_common::proto::ServerMessage;
+
+use super::replies::ReplyTracker;
+use super::session::{SendError, Session, SessionSnapshot};
 
 #[derive(Clone)]
 pub struct SessionRegistry {
     sessions: Arc<DashMap<String, Session>>,
+    replies: ReplyTracker,
 }
 
 impl Default for SessionRegistry {
@@ -18,6 +22,7 @@ impl SessionRegistry {
     pub fn new() -> Self {
         Self {
             sessions: Arc::new(DashMap::new()),
+            replies: ReplyTracker::new(),
         }
     }
 
@@ -62,6 +67,39 @@ impl SessionRegistry {
         }
     }
 
+    pub fn replies(&self) -> &ReplyTracker {
+        &self.replies
+    }
+
+    pub fn supports(&self, agent_id: &str, capability: &str) -> Option<bool> {
+        self.sessions.get(agent_id).map(|s| s.supports(capability))
+    }
+
+    pub fn record_config_version(&self, agent_id: &str, version: i64) {
+        if let Some(mut session) = self.sessions.get_mut(agent_id) {
+            session.config_version = Some(version);
+        }
+    }
+
+    pub async fn send_to(&self, agent_id: &str, msg: ServerMessage) -> Result<(), SendError> {
+        let tx = match self.sessions.get(agent_id) {
+            Some(session) => session.tx.clone(),
+            None => return Err(SendError::ChannelClosed),
+        };
+        tx.send(Ok(msg)).await.map_err(|_| SendError::ChannelClosed)
+    }
+
+    pub fn matching_agent_ids(&self, pattern: &str) -> Vec<String> {
+        let mut ids: Vec<String> = self
+            .sessions
+            .iter()
+            .filter(|r| agent_matches(pattern, r.key()))
+            .map(|r| r.key().clone())
+            .collect();
+        ids.sort();
+        ids
+    }
+
     pub fn connected_count(&self) -> usize {
         self.sessions.len()
     }
@@ -127,6 +165,16 @@ impl SessionRegistry {
     }
 }
 
+fn agent_matches(pattern: &str, agent_id: &str) -> bool {
+    if pattern == "*" {
+        return true;
+    }
+    if let Some(prefix) = pattern.strip_suffix('*') {
+        return agent_id.starts_with(prefix);
+    }
+    pattern == agent_id
+}
+
 #[derive(Debug, Clone, Default, serde::Serialize)]
 pub struct ClusterStats {
     pub connected_agents: usize,
@@ -0,0 +1,111 @@
+use dashmap::DashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reply {
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Debug)]
+pub enum WaitError {
+    Timeout,
+    Dropped,
+}
+
+#[derive(Clone, Default)]
+pub struct ReplyTracker {
+    pending: Arc<DashMap<String, (u64, oneshot::Sender<Reply>)>>,
+    next_id: Arc<AtomicU64>,
+}
+
+pub struct PendingReply {
+    key: String,
+    id: u64,
+    rx: oneshot::Receiver<Reply>,
+    tracker: ReplyTracker,
+}
+
+impl ReplyTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&self, key: String) -> PendingReply {
+        let (tx, rx) = oneshot::channel();
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.pending.insert(key.clone(), (id, tx));
+        PendingReply {
+            key,
+            id,
+            rx,
+            tracker: self.clone(),
+        }
+    }
+
+    pub fn resolve(&self, key: &str, reply: Reply) -> bool {
+        match self.pending.remove(key) {
+            Some((_, (_, tx))) => tx.send(reply).is_ok(),
+            None => false,
+        }
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+impl PendingReply {
+    pub async fn wait(mut self, timeout: Duration) -> Result<Reply, WaitError> {
+        match tokio::time::timeout(timeout, &mut self.rx).await {
+            Ok(Ok(reply)) => Ok(reply),
+            Ok(Err(_)) => Err(WaitError::Dropped),
+            Err(_) => Err(WaitError::Timeout),
+        }
+    }
+}
+
+impl Drop for PendingReply {
+    fn drop(&mut self) {
+        self.tracker
+            .pending
+            .remove_if(&self.key, |_, (id, _)| *id == self.id);
+    }
+}
+
+pub fn config_reply_key(agent_id: &str, version: i64) -> String {
+    format!("{agent_id}/config/{version}")
+}
+
+pub fn key_reply_key(agent_id: &str, rotation_id: &str) -> String {
+    format!("{agent_id}/key/{rotation_id}")
+}
+
+pub fn command_reply_key(agent_id: &str, command_id: &str) -> String {
+    format!("{agent_id}/command/{command_id}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn dropping_replaced_waiter_keeps_newer_registration() {
+        let tracker = ReplyTracker::new();
+        let first = tracker.register("a/config/1".into());
+        let second = tracker.register("a/config/1".into());
+        drop(first);
+        assert_eq!(tracker.pending_count(), 1);
+
+        let reply = Reply {
+            success: true,
+            message: String::new(),
+        };
+        assert!(tracker.resolve("a/config/1", reply.clone()));
+        assert_eq!(second.wait(Duration::from_secs(1)).await.unwrap(), reply);
+        assert_eq!(tracker.pending_count(), 0);
+    }
+}
@@ -41,6 +41,7 @@ pub struct Session {
     pub heartbeat_count: u64,
     pub system_stats: LiveSystemStats,
     pub latency: LatencyTracker,
+    pub config_version: Option<i64>,
     pub tx: Arc<mpsc::Sender<Result<ServerMessage, tonic::Status>>>,
 }
 
@@ -66,6 +67,7 @@ impl Session {
             heartbeat_count: 0,
             system_stats: LiveSystemStats::default(),
             latency: LatencyTracker::new(),
+            config_version: None,
             tx,
         }
     }
@@ -83,6 +85,10 @@ impl Session {
         self.system_stats = stats;
     }
 
+    pub fn supports(&self, capability: &str) -> bool {
+        self.capabilities.iter().any(|c| c == capability)
+    }
+
     pub fn is_stale(&self, timeout_ms: i64) -> bool {
         let elapsed = Utc::now()
             .signed_duration_since(self.last_ping)
@@ -150,6 +156,7 @@ impl Session {
             connection_quality: self.connection_quality(),
             memory_percent: self.memory_percent(),
             disk_percent: self.disk_percent(),
+            config_version: self.config_version,
         }
     }
 
@@ -186,6 +193,7 @@ pub struct SessionSnapshot {
     pub connection_quality: ConnectionQuality,
     pub memory_percent: f64,
     pub disk_percent: f64,
+    pub config_version: Option<i64>,
 }
 
 #[derive(Debug)]
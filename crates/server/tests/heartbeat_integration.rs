@@ -92,6 +92,7 @@ impl HeartbeatTestServer {
             agent_version: "1.0.0".into(),
             registered_at_ms: now_ms(),
             deprecated_keys: Vec::new(),
+            pending_key: None,
             last_seen: None,
         };
         self.agents.insert(record.clone());
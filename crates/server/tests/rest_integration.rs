@@ -2,11 +2,15 @@ use axum::body::Body;
 use axum::http::{Request, StatusCode};
 use tower::ServiceExt;
 
+use sentinel_common::crypto::sign_data;
+use sentinel_common::envelope::Envelope;
+use sentinel_common::proto::{metric::Value, Batch, Metric};
 use sentinel_server::auth::create_token;
+use sentinel_server::broker::InMemoryBroker;
 use sentinel_server::metrics::server_metrics::ServerMetrics;
 use sentinel_server::provisioning::TokenStore;
 use sentinel_server::rest::{router, AppState};
-use sentinel_server::store::{AgentRecord, AgentStore, RuleStore};
+use sentinel_server::store::{AgentRecord, AgentStore, IdempotencyStore, RuleStore};
 use sentinel_server::stream::{PresenceEventBus, SessionRegistry};
 
 const TEST_SECRET: &[u8] = b"test-secret";
@@ -32,6 +36,11 @@ fn app_state() -> AppState {
         grpc_public_url: "http://localhost:50051".into(),
         registry: SessionRegistry::new(),
         events: PresenceEventBus::new(),
+        broker: None,
+        idempotency: IdempotencyStore::new(),
+        key_grace_period_ms: 24 * 60 * 60 * 1000,
+        replay_window_ms: 5 * 60 * 1000,
+        envelope: Envelope::disabled(),
     }
 }
 
@@ -48,6 +57,7 @@ fn seed_agent(state: &AppState) {
         agent_version: "0.1.0".into(),
         registered_at_ms: 1_700_000_000_000,
         deprecated_keys: Vec::new(),
+        pending_key: None,
         last_seen: None,
     });
 }
@@ -244,6 +254,195 @@ async fn create_rule_invalid_condition() {
     assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
 }
 
+fn post_rule(body: &serde_json::Value) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/v1/rules")
+        .header("content-type", "application/json")
+        .header("authorization", test_bearer())
+        .body(Body::from(serde_json::to_vec(body).unwrap()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn create_rule_with_label_matchers() {
+    let body = serde_json::json!({
+        "name": "disk-full",
+        "metric_name": "disk.used_pct",
+        "label_matchers": [
+            { "label": "mount", "op": "!~", "value": "/boot.*" },
+            { "label": "fstype", "op": "=", "value": "ext4" }
+        ],
+        "condition": "GreaterThan",
+        "threshold": 90.0
+    });
+
+    let resp = app().oneshot(post_rule(&body)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let rule: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(rule["label_matchers"].as_array().unwrap().len(), 2);
+    assert_eq!(rule["label_matchers"][0]["op"], "!~");
+}
+
+#[tokio::test]
+async fn create_rule_invalid_label_regex() {
+    let body = serde_json::json!({
+        "name": "bad-matcher",
+        "metric_name": "disk.used_pct",
+        "label_matchers": [{ "label": "mount", "op": "=~", "value": "(" }],
+        "condition": "GreaterThan",
+        "threshold": 90.0
+    });
+
+    let resp = app().oneshot(post_rule(&body)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn create_rule_with_aggregation() {
+    let body = serde_json::json!({
+        "name": "p95-latency",
+        "metric_name": "http.latency_ms",
+        "condition": "GreaterThan",
+        "threshold": 500.0,
+        "aggregation": "p95",
+        "window_ms": 300000,
+        "min_samples": 10
+    });
+
+    let resp = app().oneshot(post_rule(&body)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let rule: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(rule["aggregation"], "p95");
+    assert_eq!(rule["window_ms"], 300000);
+    assert_eq!(rule["min_samples"], 10);
+}
+
+#[tokio::test]
+async fn create_rule_defaults_aggregation() {
+    let body = serde_json::json!({
+        "name": "cpu",
+        "metric_name": "cpu.usage",
+        "condition": "GreaterThan",
+        "threshold": 90.0
+    });
+
+    let resp = app().oneshot(post_rule(&body)).await.unwrap();
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let rule: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(rule["aggregation"], "avg");
+    assert_eq!(rule["window_ms"], 120000);
+    assert_eq!(rule["min_samples"], 2);
+}
+
+#[tokio::test]
+async fn create_rule_rejects_bad_aggregation() {
+    for (aggregation, window_ms, min_samples) in
+        [("median", 60000, 2), ("max", 0, 2), ("rate", 60000, 0)]
+    {
+        let body = serde_json::json!({
+            "name": "bad",
+            "metric_name": "cpu.usage",
+            "condition": "GreaterThan",
+            "threshold": 90.0,
+            "aggregation": aggregation,
+            "window_ms": window_ms,
+            "min_samples": min_samples
+        });
+        let resp = app().oneshot(post_rule(&body)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{aggregation}");
+    }
+}
+
+#[tokio::test]
+async fn create_rule_with_expression() {
+    let body = serde_json::json!({
+        "name": "mem-pressure",
+        "expression": "mem.used_bytes / mem.total_bytes > 0.9 and rate(swap.in_bytes, 5m) > 0"
+    });
+
+    let resp = app().oneshot(post_rule(&body)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let rule: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        rule["expression"],
+        "mem.used_bytes / mem.total_bytes > 0.9 and rate(swap.in_bytes, 5m) > 0"
+    );
+    assert_eq!(rule["metric_name"], "");
+}
+
+#[tokio::test]
+async fn create_rule_returns_expression_parse_error() {
+    let body = serde_json::json!({
+        "name": "broken",
+        "expression": "mem.used_bytes / > 0.9"
+    });
+
+    let resp = app().oneshot(post_rule(&body)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let err: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    let message = err["error"].as_str().unwrap();
+    assert!(message.contains("column 18"), "{message}");
+}
+
+#[tokio::test]
+async fn create_rule_requires_metric_or_expression() {
+    let body = serde_json::json!({
+        "name": "empty",
+        "condition": "GreaterThan",
+        "threshold": 1.0
+    });
+    let resp = app().oneshot(post_rule(&body)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let body = serde_json::json!({ "name": "no-threshold", "metric_name": "cpu" });
+    let resp = app().oneshot(post_rule(&body)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn create_absent_rule() {
+    let body = serde_json::json!({
+        "name": "cpu-missing",
+        "metric_name": "cpu.usage",
+        "absent_for_ms": 300000
+    });
+    let resp = app().oneshot(post_rule(&body)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let rule: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(rule["absent_for_ms"], 300000);
+
+    for body in [
+        serde_json::json!({ "name": "zero", "metric_name": "cpu", "absent_for_ms": 0 }),
+        serde_json::json!({ "name": "expr", "expression": "cpu > 1", "absent_for_ms": 60000 }),
+    ] {
+        let resp = app().oneshot(post_rule(&body)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+}
+
 #[tokio::test]
 async fn delete_rule_not_found() {
     let resp = app()
@@ -317,6 +516,76 @@ async fn test_notifier_unknown_type() {
     assert_eq!(result["success"], false);
 }
 
+fn post_notifier_test(body: &serde_json::Value) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/v1/notifiers/test")
+        .header("content-type", "application/json")
+        .header("authorization", test_bearer())
+        .body(Body::from(serde_json::to_vec(body).unwrap()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_notifier_renders_template() {
+    let body = serde_json::json!({
+        "notifier_type": "slack",
+        "config": {
+            "webhook_url": "https://hooks.slack.com/services/T/B/X",
+            "template": {
+                "title": "{{ severity | upper }}: {{ rule_name }} on {{ agent_id }}",
+                "body": "Runbook: {{ annotations.runbook_url }}"
+            }
+        },
+        "render": true
+    });
+    let resp = app().oneshot(post_notifier_test(&body)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let result: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(result["success"], true);
+    assert_eq!(result["rendered"]["title"], "CRITICAL: High CPU on web-01");
+    assert_eq!(
+        result["rendered"]["body"],
+        "Runbook: https://runbooks.example.com/cpu"
+    );
+
+    let bad = serde_json::json!({
+        "notifier_type": "slack",
+        "config": {
+            "webhook_url": "https://hooks.slack.com/services/T/B/X",
+            "template": { "title": "{{ rule_name | shout }}" }
+        }
+    });
+    let resp = app().oneshot(post_notifier_test(&bad)).await.unwrap();
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let result: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(result["success"], false);
+    assert!(result["message"]
+        .as_str()
+        .unwrap()
+        .contains("unknown helper 'shout'"));
+
+    let grouped = serde_json::json!({
+        "notifier_type": "slack",
+        "config": {
+            "webhook_url": "https://hooks.slack.com/services/T/B/X",
+            "template": { "group_title": "{{firing_count}} firing" }
+        },
+        "render": true
+    });
+    let resp = app().oneshot(post_notifier_test(&grouped)).await.unwrap();
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let result: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(result["rendered_group"]["title"], "2 firing");
+}
+
 #[tokio::test]
 async fn update_rule() {
     let state = app_state();
@@ -373,3 +642,553 @@ async fn update_rule() {
     assert_eq!(updated["name"], "updated-rule");
     assert_eq!(updated["threshold"], 95.0);
 }
+
+#[tokio::test]
+async fn rule_grouping_defaults_and_clears() {
+    let state = app_state();
+    let body = serde_json::json!({
+        "name": "disk-full",
+        "metric_name": "disk.used_pct",
+        "condition": "GreaterThan",
+        "threshold": 90.0,
+        "grouping": { "group_by": ["rule_id", "mount"], "group_wait_secs": 10 }
+    });
+    let resp = router(state.clone())
+        .oneshot(post_rule(&body))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let created: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(created["grouping"]["group_wait_secs"], 10);
+    assert_eq!(created["grouping"]["group_interval_secs"], 300);
+    assert_eq!(created["grouping"]["repeat_interval_secs"], 14_400);
+    let rule_id = created["id"].as_str().unwrap();
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::builder()
+                .method("PUT")
+                .uri(format!("/v1/rules/{rule_id}"))
+                .header("content-type", "application/json")
+                .header("authorization", test_bearer())
+                .body(Body::from(r#"{"grouping":null}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let updated: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(updated.get("grouping").is_none());
+}
+
+#[tokio::test]
+async fn create_rule_rejects_invalid_grouping() {
+    let body = serde_json::json!({
+        "name": "disk-full",
+        "metric_name": "disk.used_pct",
+        "condition": "GreaterThan",
+        "threshold": 90.0,
+        "grouping": { "group_interval_secs": 600, "repeat_interval_secs": 60 }
+    });
+    let resp = app().oneshot(post_rule(&body)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let err: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(err["error"]
+        .as_str()
+        .unwrap()
+        .contains("repeat_interval_secs"));
+}
+
+#[tokio::test]
+async fn push_config_to_disconnected_agent() {
+    let body = serde_json::json!({ "config_yaml": "server: https://localhost:8443" });
+    let resp = app()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/agents/agent-1/config")
+                .header("content-type", "application/json")
+                .header("authorization", test_bearer())
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn push_group_config_without_matches() {
+    let body = serde_json::json!({
+        "agent_pattern": "web-*",
+        "config_yaml": "server: https://localhost:8443",
+        "version": 7
+    });
+    let resp = app()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/agents/config")
+                .header("content-type", "application/json")
+                .header("authorization", test_bearer())
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let out: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(out["version"], 7);
+    assert_eq!(out["results"].as_array().unwrap().len(), 0);
+}
+
+#[tokio::test]
+async fn exec_command_on_disconnected_agent() {
+    let body = serde_json::json!({ "action": "restart_collector" });
+    let resp = app()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/agents/agent-1/commands")
+                .header("content-type", "application/json")
+                .header("authorization", test_bearer())
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn exec_command_rejects_zero_interval() {
+    let body = serde_json::json!({ "action": "update_interval", "interval_seconds": 0 });
+    let resp = app()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/agents/agent-1/commands")
+                .header("content-type", "application/json")
+                .header("authorization", test_bearer())
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+fn ingest_state() -> (AppState, InMemoryBroker) {
+    let broker = InMemoryBroker::new();
+    let mut state = app_state();
+    state.broker = Some(std::sync::Arc::new(broker.clone()));
+    seed_agent(&state);
+    (state, broker)
+}
+
+fn signed_batch(batch_id: &str) -> (Vec<u8>, String) {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+    let batch = Batch {
+        agent_id: "agent-1".into(),
+        batch_id: batch_id.into(),
+        seq_start: 1,
+        seq_end: 1,
+        created_at_ms: now,
+        metrics: vec![Metric {
+            name: "cpu.usage".into(),
+            labels: Default::default(),
+            rtype: 1,
+            value: Some(Value::ValueDouble(42.0)),
+            timestamp_ms: now,
+        }],
+        meta: Default::default(),
+    };
+    let canonical = sentinel_common::canonicalize::canonical_bytes(&batch);
+    let signature = sign_data(b"secret", &canonical);
+    (
+        sentinel_common::batch_json::encode(&batch).unwrap(),
+        signature,
+    )
+}
+
+fn ingest_request(body: Vec<u8>, signature: &str) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/v1/agent/metrics")
+        .header("content-type", "application/json")
+        .header("x-agent-id", "agent-1")
+        .header("x-key-id", "key-1")
+        .header("x-signature", signature)
+        .body(Body::from(body))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn http_ingest_accepts_signed_batch_once() {
+    let (state, broker) = ingest_state();
+    let (body, sig) = signed_batch("http-batch-1");
+
+    let resp = router(state.clone())
+        .oneshot(ingest_request(body.clone(), &sig))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = router(state)
+        .oneshot(ingest_request(body, &sig))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let out: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(out["message"].as_str().unwrap().contains("duplicate"));
+
+    assert_eq!(broker.published_count(), 1);
+}
+
+#[tokio::test]
+async fn http_ingest_checks_signature_before_dedup() {
+    let (state, broker) = ingest_state();
+    let (body, sig) = signed_batch("http-batch-3");
+
+    let resp = router(state.clone())
+        .oneshot(ingest_request(body.clone(), &sig))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = router(state)
+        .oneshot(ingest_request(body, "bm90LWEtc2lnbmF0dXJl"))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(broker.published_count(), 1);
+}
+
+#[tokio::test]
+async fn http_ingest_rejects_bad_signature() {
+    let (state, broker) = ingest_state();
+    let (body, _) = signed_batch("http-batch-2");
+
+    let resp = router(state)
+        .oneshot(ingest_request(body, "bm90LWEtc2lnbmF0dXJl"))
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(broker.published_count(), 0);
+}
+
+#[tokio::test]
+async fn http_ingest_requires_signature_headers() {
+    let (state, _) = ingest_state();
+    let (body, _) = signed_batch("http-batch-3");
+
+    let resp = router(state)
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/agent/metrics")
+                .header("x-agent-id", "agent-1")
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn http_ingest_unavailable_without_broker() {
+    let state = app_state();
+    seed_agent(&state);
+    let (body, sig) = signed_batch("http-batch-4");
+
+    let resp = router(state)
+        .oneshot(ingest_request(body, &sig))
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+fn post_dlq(path: &str, body: &serde_json::Value) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri(path)
+        .header("content-type", "application/json")
+        .header("authorization", test_bearer())
+        .body(Body::from(serde_json::to_vec(body).unwrap()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn dlq_bulk_actions_require_a_filter() {
+    for path in [
+        "/v1/notifications/dlq/replay",
+        "/v1/notifications/dlq/purge",
+    ] {
+        let resp = app()
+            .oneshot(post_dlq(path, &serde_json::json!({})))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{path}");
+
+        let resp = app()
+            .oneshot(post_dlq(path, &serde_json::json!({ "older_than_secs": 0 })))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{path}");
+
+        let resp = app()
+            .oneshot(post_dlq(path, &serde_json::json!({ "all": true })))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE, "{path}");
+    }
+}
+
+fn post_silence(body: &serde_json::Value) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/v1/silences")
+        .header("content-type", "application/json")
+        .header("authorization", test_bearer())
+        .body(Body::from(serde_json::to_vec(body).unwrap()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn create_silence_validates_before_persisting() {
+    let cases = [
+        serde_json::json!({ "duration_secs": 3600 }),
+        serde_json::json!({ "agent_pattern": "web-*" }),
+        serde_json::json!({ "agent_pattern": "web-*", "duration_secs": 0 }),
+        serde_json::json!({ "severity": "page", "duration_secs": 3600 }),
+        serde_json::json!({
+            "agent_pattern": "web-*",
+            "recurrence": { "days": ["sat"], "start": "25:00", "duration_mins": 60 }
+        }),
+    ];
+    for body in cases {
+        let resp = app().oneshot(post_silence(&body)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{body}");
+    }
+
+    let resp = app()
+        .oneshot(post_silence(&serde_json::json!({
+            "agent_pattern": "web-*",
+            "recurrence": { "days": ["sat", "sun"], "start": "02:00", "duration_mins": 120 }
+        })))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn ack_requires_auth_and_database() {
+    let resp = app()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/alerts/a1/ack")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    let resp = app()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/alerts/a1/ack")
+                .header("authorization", test_bearer())
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn alert_stats_validate_before_querying() {
+    let get = |uri: &str| {
+        Request::builder()
+            .uri(uri)
+            .header("authorization", test_bearer())
+            .body(Body::empty())
+            .unwrap()
+    };
+    for uri in [
+        "/v1/alerts/stats?by=metric",
+        "/v1/alerts/stats?window_secs=0",
+        "/v1/alerts/incidents?window_secs=99999999",
+        "/v1/alerts/flapping?window_secs=0",
+    ] {
+        let resp = app().oneshot(get(uri)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{uri}");
+    }
+    for uri in [
+        "/v1/alerts/stats?by=agent&window_secs=3600",
+        "/v1/alerts/incidents?rule_id=r1",
+        "/v1/alerts/flapping?threshold=3",
+    ] {
+        let resp = app().oneshot(get(uri)).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE, "{uri}");
+    }
+}
+
+fn escalation_request(method: &str, uri: &str, body: &serde_json::Value) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .header("authorization", test_bearer())
+        .body(Body::from(serde_json::to_vec(body).unwrap()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn escalation_policies_validate_before_persisting() {
+    let cases = [
+        serde_json::json!({ "name": "", "steps": [{ "notifier_ids": ["n1"] }] }),
+        serde_json::json!({ "name": "ops", "steps": [] }),
+        serde_json::json!({ "name": "ops", "steps": [{ "delay_secs": 600, "notifier_ids": [] }] }),
+    ];
+    for body in cases {
+        let resp = app()
+            .oneshot(escalation_request("POST", "/v1/escalation-policies", &body))
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{body}");
+    }
+
+    let valid = serde_json::json!({
+        "name": "ops",
+        "steps": [
+            { "notifier_ids": ["slack"] },
+            { "delay_secs": 600, "notifier_ids": ["pagerduty"] }
+        ]
+    });
+    let resp = app()
+        .oneshot(escalation_request(
+            "POST",
+            "/v1/escalation-policies",
+            &valid,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    let resp = app()
+        .oneshot(escalation_request(
+            "PUT",
+            "/v1/escalation-routes",
+            &serde_json::json!({ "routes": [{ "severity": ["page"], "policy_id": "ops" }] }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = app()
+        .oneshot(escalation_request(
+            "PUT",
+            "/v1/escalation-routes",
+            &serde_json::json!({ "routes": [{ "severity": ["critical"], "policy_id": "ops" }] }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn rule_escalation_policy_sets_and_clears() {
+    let state = app_state();
+    let body = serde_json::json!({
+        "name": "cpu-high",
+        "metric_name": "cpu.usage",
+        "condition": "GreaterThan",
+        "threshold": 90.0,
+        "escalation_policy_id": "ops"
+    });
+    let resp = router(state.clone())
+        .oneshot(post_rule(&body))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let created: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(created["escalation_policy_id"], "ops");
+    let rule_id = created["id"].as_str().unwrap();
+
+    let resp = router(state.clone())
+        .oneshot(escalation_request(
+            "PUT",
+            &format!("/v1/rules/{rule_id}"),
+            &serde_json::json!({ "escalation_policy_id": null }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let updated: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert!(updated.get("escalation_policy_id").is_none());
+}
+
+#[tokio::test]
+async fn encryption_status_reports_disabled_key() {
+    let resp = app()
+        .oneshot(escalation_request(
+            "GET",
+            "/v1/encryption",
+            &serde_json::Value::Null,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let status: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(status["enabled"], false);
+    assert!(status.get("key_id").is_none());
+
+    let resp = app()
+        .oneshot(escalation_request(
+            "POST",
+            "/v1/encryption/rotate",
+            &serde_json::Value::Null,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+}
@@ -1,13 +1,16 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
+use sentinel_common::canonicalize::canonical_bytes;
 use sentinel_common::crypto::sign_data;
+use sentinel_common::key_rotation::{open_secret, KEY_ROTATION_CAPABILITY};
 use sentinel_common::proto::agent_message::Payload as AgentPayload;
 use sentinel_common::proto::sentinel_stream_client::SentinelStreamClient;
 use sentinel_common::proto::sentinel_stream_server::SentinelStreamServer;
 use sentinel_common::proto::server_message::Payload as ServerPayload;
 use sentinel_common::proto::{
-    AgentMessage, BatchAckStatus, HandshakeRequest, HandshakeStatus, Metric, MetricsBatch,
+    AgentMessage, Batch, BatchAckStatus, CommandResult, ConfigAck, HandshakeRequest,
+    HandshakeStatus, KeyRotationAck, KeyRotationRequest, Metric, MetricsBatch,
 };
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::transport::{Channel, Server};
@@ -15,6 +18,9 @@ use tonic::transport::{Channel, Server};
 use sentinel_server::broker::InMemoryBroker;
 use sentinel_server::metrics::server_metrics::ServerMetrics;
 use sentinel_server::store::{AgentRecord, AgentStore, IdempotencyStore};
+use sentinel_server::stream::commands::{send_command, CommandAction, CommandStatus};
+use sentinel_server::stream::config_push::{push_config, PushStatus};
+use sentinel_server::stream::key_push::{push_key, rotate_key};
 use sentinel_server::stream::{PresenceEventBus, SessionRegistry, StreamService};
 
 struct StreamTestServer {
@@ -94,6 +100,7 @@ impl StreamTestServer {
             agent_version: "1.0.0".into(),
             registered_at_ms: now_ms(),
             deprecated_keys: Vec::new(),
+            pending_key: None,
             last_seen: None,
         };
         self.agents.insert(record.clone());
@@ -115,6 +122,15 @@ fn now_ms() -> i64 {
 }
 
 fn build_handshake(agent_id: &str, key_id: &str, secret: &[u8]) -> AgentMessage {
+    build_handshake_with_caps(agent_id, key_id, secret, &["metrics"])
+}
+
+fn build_handshake_with_caps(
+    agent_id: &str,
+    key_id: &str,
+    secret: &[u8],
+    capabilities: &[&str],
+) -> AgentMessage {
     let ts = now_ms();
     let canonical = format!("{agent_id}:{ts}:{key_id}");
     let signature = sign_data(secret, canonical.as_bytes());
@@ -122,7 +138,7 @@ fn build_handshake(agent_id: &str, key_id: &str, secret: &[u8]) -> AgentMessage
         payload: Some(AgentPayload::Handshake(HandshakeRequest {
             agent_id: agent_id.into(),
             agent_version: "1.0.0".into(),
-            capabilities: vec!["metrics".into()],
+            capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
             key_id: key_id.into(),
             timestamp_ms: ts,
             signature,
@@ -146,10 +162,28 @@ fn build_metrics_batch(batch_id: &str) -> AgentMessage {
             }],
             meta: Default::default(),
             signature: String::new(),
+            key_id: String::new(),
         })),
     }
 }
 
+fn sign_metrics_batch(agent_id: &str, msg: &mut AgentMessage, key_id: &str, secret: &[u8]) {
+    let Some(AgentPayload::MetricsBatch(mb)) = msg.payload.as_mut() else {
+        panic!("expected metrics batch");
+    };
+    let batch = Batch {
+        agent_id: agent_id.into(),
+        batch_id: mb.batch_id.clone(),
+        seq_start: mb.seq_start,
+        seq_end: mb.seq_end,
+        created_at_ms: mb.created_at_ms,
+        metrics: mb.metrics.clone(),
+        meta: mb.meta.clone(),
+    };
+    mb.signature = sign_data(secret, &canonical_bytes(&batch));
+    mb.key_id = key_id.into();
+}
+
 #[tokio::test]
 async fn handshake_then_batch_ack() {
     let server = StreamTestServer::start().await;
@@ -302,3 +336,319 @@ async fn presence_events_on_connect_disconnect() {
         other => panic!("expected AgentDisconnected, got {other:?}"),
     }
 }
+
+#[tokio::test]
+async fn config_push_round_trip() {
+    let server = StreamTestServer::start().await;
+    let secret = b"config-secret-01";
+    let record = server.insert_agent("agent-cfg", secret);
+
+    let mut client = server.client().await;
+    let (tx, rx) = tokio::sync::mpsc::channel(32);
+
+    tx.send(build_handshake_with_caps(
+        "agent-cfg",
+        &record.key_id,
+        secret,
+        &["metrics", "config"],
+    ))
+    .await
+    .unwrap();
+
+    let response = client.open_stream(ReceiverStream::new(rx)).await.unwrap();
+    let mut stream = response.into_inner();
+    let _ack = stream.message().await.unwrap().unwrap();
+
+    let registry = server.registry.clone();
+    let push = tokio::spawn(async move {
+        push_config(
+            &registry,
+            "agent-cfg",
+            42,
+            b"server: x".to_vec(),
+            std::time::Duration::from_secs(5),
+        )
+        .await
+    });
+
+    let update = stream.message().await.unwrap().unwrap();
+    match update.payload.unwrap() {
+        ServerPayload::ConfigUpdate(u) => {
+            assert_eq!(u.version, 42);
+            assert_eq!(u.config_yaml, b"server: x");
+        }
+        other => panic!("expected ConfigUpdate, got {other:?}"),
+    }
+
+    tx.send(AgentMessage {
+        payload: Some(AgentPayload::ConfigAck(ConfigAck {
+            version: 42,
+            applied: true,
+            message: "applied (collector)".into(),
+        })),
+    })
+    .await
+    .unwrap();
+
+    let result = push.await.unwrap();
+    assert_eq!(result.status, PushStatus::Applied);
+    assert_eq!(result.message, "applied (collector)");
+    assert_eq!(
+        server
+            .registry
+            .snapshot("agent-cfg")
+            .unwrap()
+            .config_version,
+        Some(42)
+    );
+}
+
+#[tokio::test]
+async fn config_push_requires_capability() {
+    let server = StreamTestServer::start().await;
+    let secret = b"config-secret-02";
+    let record = server.insert_agent("agent-old", secret);
+
+    let mut client = server.client().await;
+    let (tx, rx) = tokio::sync::mpsc::channel(32);
+    tx.send(build_handshake("agent-old", &record.key_id, secret))
+        .await
+        .unwrap();
+    let response = client.open_stream(ReceiverStream::new(rx)).await.unwrap();
+    let mut stream = response.into_inner();
+    let _ack = stream.message().await.unwrap().unwrap();
+
+    let timeout = std::time::Duration::from_secs(1);
+    let result = push_config(&server.registry, "agent-old", 1, b"x".to_vec(), timeout).await;
+    assert_eq!(result.status, PushStatus::Unsupported);
+
+    let result = push_config(&server.registry, "missing", 1, b"x".to_vec(), timeout).await;
+    assert_eq!(result.status, PushStatus::Offline);
+}
+
+#[tokio::test]
+async fn command_round_trip() {
+    let server = StreamTestServer::start().await;
+    let secret = b"command-secret-1";
+    let record = server.insert_agent("agent-cmd", secret);
+
+    let mut client = server.client().await;
+    let (tx, rx) = tokio::sync::mpsc::channel(32);
+
+    tx.send(build_handshake_with_caps(
+        "agent-cmd",
+        &record.key_id,
+        secret,
+        &["metrics", "commands"],
+    ))
+    .await
+    .unwrap();
+
+    let response = client.open_stream(ReceiverStream::new(rx)).await.unwrap();
+    let mut stream = response.into_inner();
+    let _ack = stream.message().await.unwrap().unwrap();
+
+    let registry = server.registry.clone();
+    let exec = tokio::spawn(async move {
+        send_command(
+            &registry,
+            "agent-cmd",
+            CommandAction::UpdateInterval {
+                interval_seconds: 30,
+            },
+            std::time::Duration::from_secs(5),
+        )
+        .await
+    });
+
+    let msg = stream.message().await.unwrap().unwrap();
+    let command_id = match msg.payload.unwrap() {
+        ServerPayload::Command(cmd) => {
+            assert!(matches!(
+                cmd.action,
+                Some(sentinel_common::proto::command::Action::UpdateInterval(ref u))
+                    if u.interval_seconds == 30
+            ));
+            cmd.command_id
+        }
+        other => panic!("expected Command, got {other:?}"),
+    };
+
+    tx.send(AgentMessage {
+        payload: Some(AgentPayload::CommandResult(CommandResult {
+            command_id: command_id.clone(),
+            success: true,
+            message: "interval set to 30s".into(),
+        })),
+    })
+    .await
+    .unwrap();
+
+    let outcome = exec.await.unwrap();
+    assert_eq!(outcome.command_id, command_id);
+    assert_eq!(outcome.status, CommandStatus::Succeeded);
+    assert_eq!(outcome.message, "interval set to 30s");
+}
+
+#[tokio::test]
+async fn rotated_key_is_pushed_and_used_for_batches() {
+    let server = StreamTestServer::start().await;
+    let secret = b"rotate-secret-01";
+    let record = server.insert_agent("agent-rot", secret);
+
+    let mut client = server.client().await;
+    let (tx, rx) = tokio::sync::mpsc::channel(32);
+
+    tx.send(build_handshake_with_caps(
+        "agent-rot",
+        &record.key_id,
+        secret,
+        &["metrics", KEY_ROTATION_CAPABILITY],
+    ))
+    .await
+    .unwrap();
+
+    let response = client.open_stream(ReceiverStream::new(rx)).await.unwrap();
+    let mut stream = response.into_inner();
+    let _ack = stream.message().await.unwrap().unwrap();
+
+    let rotated = rotate_key(&server.agents, None, "agent-rot", true)
+        .await
+        .unwrap()
+        .unwrap();
+    let registry = server.registry.clone();
+    let pushed = rotated.clone();
+    let push = tokio::spawn(async move {
+        push_key(
+            &registry,
+            "agent-rot",
+            &pushed,
+            std::time::Duration::from_secs(5),
+        )
+        .await
+    });
+
+    let rotation = match stream.message().await.unwrap().unwrap().payload.unwrap() {
+        ServerPayload::KeyRotation(r) => r,
+        other => panic!("expected KeyRotation, got {other:?}"),
+    };
+    assert_eq!(rotation.key_id, rotated.key_id);
+    let new_secret = open_secret(
+        secret,
+        "agent-rot",
+        &rotation.key_id,
+        &rotation.sealed_secret,
+    )
+    .unwrap();
+    assert_eq!(new_secret, rotated.secret);
+
+    tx.send(AgentMessage {
+        payload: Some(AgentPayload::KeyRotationAck(KeyRotationAck {
+            rotation_id: rotation.rotation_id.clone(),
+            key_id: rotation.key_id.clone(),
+            applied: true,
+            message: "key rotated".into(),
+        })),
+    })
+    .await
+    .unwrap();
+    assert_eq!(push.await.unwrap().status, PushStatus::Applied);
+
+    let mut old = build_metrics_batch("batch-old-key");
+    sign_metrics_batch("agent-rot", &mut old, &record.key_id, secret);
+    let mut new = build_metrics_batch("batch-new-key");
+    sign_metrics_batch("agent-rot", &mut new, &rotation.key_id, &new_secret);
+    for (msg, batch_id) in [(old, "batch-old-key"), (new, "batch-new-key")] {
+        tx.send(msg).await.unwrap();
+        match stream.message().await.unwrap().unwrap().payload.unwrap() {
+            ServerPayload::BatchAck(ba) => {
+                assert_eq!(ba.batch_id, batch_id);
+                assert_eq!(ba.status, BatchAckStatus::BatchAccepted as i32);
+            }
+            other => panic!("expected BatchAck, got {other:?}"),
+        }
+    }
+
+    tx.send(AgentMessage {
+        payload: Some(AgentPayload::KeyRotationRequest(KeyRotationRequest {
+            current_key_id: rotation.key_id.clone(),
+        })),
+    })
+    .await
+    .unwrap();
+    match stream.message().await.unwrap().unwrap().payload.unwrap() {
+        ServerPayload::KeyRotation(r) => {
+            assert_ne!(r.key_id, rotation.key_id);
+            assert_eq!(r.key_id, server.agents.get("agent-rot").unwrap().key_id);
+            assert!(open_secret(&new_secret, "agent-rot", &r.key_id, &r.sealed_secret).is_some());
+        }
+        other => panic!("expected KeyRotation, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn pending_key_is_resent_on_reconnect_until_acked() {
+    let server = StreamTestServer::start().await;
+    let secret = b"offline-secret-1";
+    let record = server.insert_agent("agent-off", secret);
+
+    rotate_key(&server.agents, None, "agent-off", true)
+        .await
+        .unwrap()
+        .unwrap();
+    let rotated = rotate_key(&server.agents, None, "agent-off", true)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let mut client = server.client().await;
+    let (tx, rx) = tokio::sync::mpsc::channel(32);
+    tx.send(build_handshake_with_caps(
+        "agent-off",
+        &record.key_id,
+        secret,
+        &["metrics", KEY_ROTATION_CAPABILITY],
+    ))
+    .await
+    .unwrap();
+
+    let response = client.open_stream(ReceiverStream::new(rx)).await.unwrap();
+    let mut stream = response.into_inner();
+    let _ack = stream.message().await.unwrap().unwrap();
+
+    let rotation = match stream.message().await.unwrap().unwrap().payload.unwrap() {
+        ServerPayload::KeyRotation(r) => r,
+        other => panic!("expected KeyRotation, got {other:?}"),
+    };
+    assert_eq!(rotation.key_id, rotated.key_id);
+    let new_secret = open_secret(
+        secret,
+        "agent-off",
+        &rotation.key_id,
+        &rotation.sealed_secret,
+    )
+    .unwrap();
+    assert_eq!(new_secret, rotated.secret);
+
+    tx.send(AgentMessage {
+        payload: Some(AgentPayload::KeyRotationAck(KeyRotationAck {
+            rotation_id: rotation.rotation_id.clone(),
+            key_id: rotation.key_id.clone(),
+            applied: true,
+            message: "key rotated".into(),
+        })),
+    })
+    .await
+    .unwrap();
+
+    let mut batch = build_metrics_batch("batch-after-ack");
+    sign_metrics_batch("agent-off", &mut batch, &rotation.key_id, &new_secret);
+    tx.send(batch).await.unwrap();
+    match stream.message().await.unwrap().unwrap().payload.unwrap() {
+        ServerPayload::BatchAck(ba) => {
+            assert_eq!(ba.status, BatchAckStatus::BatchAccepted as i32);
+        }
+        other => panic!("expected BatchAck, got {other:?}"),
+    }
+    assert!(server.agents.pending_rotation("agent-off").is_none());
+}
@@ -1,3 +1,4 @@
+use sentinel_common::aggregation::Aggregation;
 use std::collections::VecDeque;
 
 pub struct RollingSeries {
@@ -42,6 +43,57 @@ impl RollingSeries {
         self.samples.len()
     }
 
+    pub fn set_window_ms(&mut self, window_ms: i64) {
+        self.window_ms = window_ms;
+        if let Some(&(ts, _)) = self.samples.back() {
+            self.evict(ts);
+        }
+    }
+
+    pub fn count_within(&self, window_ms: i64) -> usize {
+        self.within(window_ms).count()
+    }
+
+    pub fn reduce(&self, agg: Aggregation, window_ms: i64) -> Option<f64> {
+        let samples: Vec<(i64, f64)> = self.within(window_ms).copied().collect();
+        if samples.is_empty() {
+            return None;
+        }
+        let values = || samples.iter().map(|(_, v)| *v);
+        match agg {
+            Aggregation::Avg => Some(values().sum::<f64>() / samples.len() as f64),
+            Aggregation::Min => values().reduce(f64::min),
+            Aggregation::Max => values().reduce(f64::max),
+            Aggregation::Last => samples.last().map(|(_, v)| *v),
+            Aggregation::Sum => Some(values().sum()),
+            Aggregation::Count => Some(samples.len() as f64),
+            Aggregation::Percentile(q) => percentile(values().collect(), q),
+            Aggregation::Increase => increase(&samples),
+            Aggregation::Rate => {
+                let elapsed_ms = samples.last()?.0 - samples.first()?.0;
+                if elapsed_ms <= 0 {
+                    return None;
+                }
+                Some(increase(&samples)? / (elapsed_ms as f64 / 1000.0))
+            }
+            Aggregation::StdDev => {
+                let n = samples.len() as f64;
+                let mean = values().sum::<f64>() / n;
+                let var = values().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+                Some(var.sqrt())
+            }
+        }
+    }
+
+    fn within(&self, window_ms: i64) -> impl Iterator<Item = &(i64, f64)> {
+        let cutoff = self
+            .samples
+            .back()
+            .map(|(ts, _)| ts - window_ms)
+            .unwrap_or(i64::MIN);
+        self.samples.iter().filter(move |(ts, _)| *ts >= cutoff)
+    }
+
     fn evict(&mut self, now_ms: i64) {
         let cutoff = now_ms - self.window_ms;
         while let Some(&(ts, _)) = self.samples.front() {
@@ -54,6 +106,35 @@ impl RollingSeries {
     }
 }
 
+fn percentile(mut values: Vec<f64>, q: f64) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    let rank = (q / 100.0).clamp(0.0, 1.0) * (values.len() - 1) as f64;
+    let lo = rank.floor() as usize;
+    let hi = rank.ceil() as usize;
+    Some(values[lo] + (values[hi] - values[lo]) * (rank - lo as f64))
+}
+
+fn increase(samples: &[(i64, f64)]) -> Option<f64> {
+    if samples.len() < 2 {
+        return None;
+    }
+    let total = samples
+        .windows(2)
+        .map(|w| {
+            let delta = w[1].1 - w[0].1;
+            if delta < 0.0 {
+                w[1].1
+            } else {
+                delta
+            }
+        })
+        .sum();
+    Some(total)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -95,4 +176,69 @@ mod tests {
         assert_eq!(s.max(), None);
         assert_eq!(s.last(), None);
     }
+
+    fn series(values: &[(i64, f64)]) -> RollingSeries {
+        let mut s = RollingSeries::new(60_000);
+        for (ts, v) in values {
+            s.push(*ts, *v);
+        }
+        s
+    }
+
+    #[test]
+    fn reduce_basic_reducers() {
+        let s = series(&[
+            (1000, 2.0),
+            (2000, 4.0),
+            (3000, 4.0),
+            (4000, 4.0),
+            (5000, 5.0),
+        ]);
+        assert_eq!(s.reduce(Aggregation::Avg, 60_000), Some(3.8));
+        assert_eq!(s.reduce(Aggregation::Min, 60_000), Some(2.0));
+        assert_eq!(s.reduce(Aggregation::Max, 60_000), Some(5.0));
+        assert_eq!(s.reduce(Aggregation::Last, 60_000), Some(5.0));
+        assert_eq!(s.reduce(Aggregation::Sum, 60_000), Some(19.0));
+        assert_eq!(s.reduce(Aggregation::Count, 60_000), Some(5.0));
+        let sd = s.reduce(Aggregation::StdDev, 60_000).unwrap();
+        assert!((sd - 0.9798).abs() < 1e-3);
+    }
+
+    #[test]
+    fn reduce_respects_window() {
+        let s = series(&[(1000, 100.0), (9000, 10.0), (10_000, 20.0)]);
+        assert_eq!(s.reduce(Aggregation::Avg, 2000), Some(15.0));
+        assert_eq!(s.count_within(2000), 2);
+        assert_eq!(s.count_within(60_000), 3);
+    }
+
+    #[test]
+    fn percentile_interpolates() {
+        let s = series(&[(1, 10.0), (2, 20.0), (3, 30.0), (4, 40.0), (5, 50.0)]);
+        assert_eq!(s.reduce(Aggregation::Percentile(50.0), 60_000), Some(30.0));
+        assert_eq!(s.reduce(Aggregation::Percentile(100.0), 60_000), Some(50.0));
+        let p90 = s.reduce(Aggregation::Percentile(90.0), 60_000).unwrap();
+        assert!((p90 - 46.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn increase_and_rate_handle_counter_reset() {
+        let s = series(&[(0, 100.0), (5000, 150.0), (10_000, 20.0)]);
+        assert_eq!(s.reduce(Aggregation::Increase, 60_000), Some(70.0));
+        assert_eq!(s.reduce(Aggregation::Rate, 60_000), Some(7.0));
+    }
+
+    #[test]
+    fn rate_needs_two_samples() {
+        let s = series(&[(0, 100.0)]);
+        assert_eq!(s.reduce(Aggregation::Rate, 60_000), None);
+        assert_eq!(s.reduce(Aggregation::Increase, 60_000), None);
+    }
+
+    #[test]
+    fn shrinking_window_evicts() {
+        let mut s = series(&[(1000, 1.0), (5000, 2.0)]);
+        s.set_window_ms(1000);
+        assert_eq!(s.count(), 1);
+    }
 }
@@ -1,4 +1,7 @@
 use dashmap::DashMap;
+use sentinel_common::aggregation::Aggregation;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicI64, Ordering};
 use std::sync::Arc;
 
 use super::rolling::RollingSeries;
@@ -7,70 +10,140 @@ use super::rolling::RollingSeries;
 pub struct MetricKey {
     pub agent_id: String,
     pub name: String,
+    pub labels: BTreeMap<String, String>,
+}
+
+impl MetricKey {
+    pub fn new(agent_id: &str, name: &str) -> Self {
+        Self {
+            agent_id: agent_id.to_string(),
+            name: name.to_string(),
+            labels: BTreeMap::new(),
+        }
+    }
 }
 
 pub struct AggregatorStore {
     series: Arc<DashMap<MetricKey, RollingSeries>>,
-    window_ms: i64,
+    last_seen: DashMap<MetricKey, i64>,
+    agents_first_seen: DashMap<String, i64>,
+    window_ms: AtomicI64,
 }
 
 impl AggregatorStore {
     pub fn new(window_ms: i64) -> Self {
         Self {
             series: Arc::new(DashMap::new()),
-            window_ms,
+            last_seen: DashMap::new(),
+            agents_first_seen: DashMap::new(),
+            window_ms: AtomicI64::new(window_ms),
+        }
+    }
+
+    pub fn window_ms(&self) -> i64 {
+        self.window_ms.load(Ordering::Relaxed)
+    }
+
+    pub fn set_window_ms(&self, window_ms: i64) {
+        if self.window_ms.swap(window_ms, Ordering::Relaxed) == window_ms {
+            return;
+        }
+        for mut series in self.series.iter_mut() {
+            series.set_window_ms(window_ms);
         }
     }
 
     pub fn ingest(&self, agent_id: &str, name: &str, timestamp_ms: i64, value: f64) {
-        let key = MetricKey {
-            agent_id: agent_id.to_string(),
-            name: name.to_string(),
-        };
+        self.ingest_series(MetricKey::new(agent_id, name), timestamp_ms, value);
+    }
+
+    pub fn ingest_series(&self, key: MetricKey, timestamp_ms: i64, value: f64) {
         self.series
             .entry(key)
-            .or_insert_with(|| RollingSeries::new(self.window_ms))
+            .or_insert_with(|| RollingSeries::new(self.window_ms()))
             .push(timestamp_ms, value);
     }
 
+    pub fn observe(&self, key: &MetricKey, received_ms: i64) {
+        self.agents_first_seen
+            .entry(key.agent_id.clone())
+            .or_insert(received_ms);
+        let mut seen = self.last_seen.entry(key.clone()).or_insert(received_ms);
+        *seen = (*seen).max(received_ms);
+    }
+
+    pub fn last_seen(&self, key: &MetricKey) -> Option<i64> {
+        self.last_seen.get(key).map(|t| *t)
+    }
+
+    pub fn agent_first_seen(&self, agent_id: &str) -> Option<i64> {
+        self.agents_first_seen.get(agent_id).map(|t| *t)
+    }
+
+    pub fn agents(&self) -> Vec<String> {
+        self.agents_first_seen
+            .iter()
+            .map(|e| e.key().clone())
+            .collect()
+    }
+
+    pub fn series_keys(&self, agent_id: &str, name: &str) -> Vec<MetricKey> {
+        self.series
+            .iter()
+            .filter(|e| e.key().agent_id == agent_id && e.key().name == name)
+            .map(|e| e.key().clone())
+            .collect()
+    }
+
+    pub fn series_avg(&self, key: &MetricKey) -> Option<f64> {
+        self.series.get(key).and_then(|s| s.avg())
+    }
+
+    pub fn series_count(&self, key: &MetricKey) -> usize {
+        self.series.get(key).map(|s| s.count()).unwrap_or(0)
+    }
+
+    pub fn series_reduce(&self, key: &MetricKey, agg: Aggregation, window_ms: i64) -> Option<f64> {
+        self.series.get(key).and_then(|s| s.reduce(agg, window_ms))
+    }
+
+    pub fn series_count_within(&self, key: &MetricKey, window_ms: i64) -> usize {
+        self.series
+            .get(key)
+            .map(|s| s.count_within(window_ms))
+            .unwrap_or(0)
+    }
+
+    pub fn retain_agents(&self, keep: impl Fn(&str) -> bool) {
+        self.series.retain(|key, _| keep(&key.agent_id));
+        self.last_seen.retain(|key, _| keep(&key.agent_id));
+        self.agents_first_seen.retain(|agent, _| keep(agent));
+    }
+
     pub fn avg(&self, agent_id: &str, name: &str) -> Option<f64> {
-        let key = MetricKey {
-            agent_id: agent_id.to_string(),
-            name: name.to_string(),
-        };
-        self.series.get(&key).and_then(|s| s.avg())
+        self.series_avg(&MetricKey::new(agent_id, name))
     }
 
     pub fn min(&self, agent_id: &str, name: &str) -> Option<f64> {
-        let key = MetricKey {
-            agent_id: agent_id.to_string(),
-            name: name.to_string(),
-        };
-        self.series.get(&key).and_then(|s| s.min())
+        self.series
+            .get(&MetricKey::new(agent_id, name))
+            .and_then(|s| s.min())
     }
 
     pub fn max(&self, agent_id: &str, name: &str) -> Option<f64> {
-        let key = MetricKey {
-            agent_id: agent_id.to_string(),
-            name: name.to_string(),
-        };
-        self.series.get(&key).and_then(|s| s.max())
+        self.series
+            .get(&MetricKey::new(agent_id, name))
+            .and_then(|s| s.max())
     }
 
     pub fn last(&self, agent_id: &str, name: &str) -> Option<f64> {
-        let key = MetricKey {
-            agent_id: agent_id.to_string(),
-            name: name.to_string(),
-        };
-        self.series.get(&key).and_then(|s| s.last())
+        self.series
+            .get(&MetricKey::new(agent_id, name))
+            .and_then(|s| s.last())
     }
 
     pub fn count(&self, agent_id: &str, name: &str) -> usize {
-        let key = MetricKey {
-            agent_id: agent_id.to_string(),
-            name: name.to_string(),
-        };
-        self.series.get(&key).map(|s| s.count()).unwrap_or(0)
+        self.series_count(&MetricKey::new(agent_id, name))
     }
 }
 
@@ -102,4 +175,61 @@ mod tests {
         assert_eq!(store.avg("a1", "cpu"), Some(10.0));
         assert_eq!(store.avg("a2", "cpu"), Some(90.0));
     }
+
+    #[test]
+    fn label_sets_are_separate_series() {
+        let store = AggregatorStore::new(10000);
+        let mut root = MetricKey::new("a1", "disk.used_pct");
+        root.labels.insert("mount".into(), "/".into());
+        let mut data = MetricKey::new("a1", "disk.used_pct");
+        data.labels.insert("mount".into(), "/data".into());
+
+        store.ingest_series(root.clone(), 100, 40.0);
+        store.ingest_series(data.clone(), 100, 95.0);
+
+        assert_eq!(store.series_avg(&root), Some(40.0));
+        assert_eq!(store.series_avg(&data), Some(95.0));
+        assert_eq!(store.series_keys("a1", "disk.used_pct").len(), 2);
+        assert_eq!(store.avg("a1", "disk.used_pct"), None);
+    }
+
+    #[test]
+    fn observe_tracks_receipt_times() {
+        let store = AggregatorStore::new(10000);
+        let key = MetricKey::new("a1", "cpu");
+        store.observe(&key, 5_000);
+        store.observe(&MetricKey::new("a1", "mem"), 7_000);
+        store.observe(&key, 9_000);
+        store.observe(&key, 8_000);
+
+        assert_eq!(store.last_seen(&key), Some(9_000));
+        assert_eq!(store.agent_first_seen("a1"), Some(5_000));
+        assert_eq!(store.agents(), vec!["a1".to_string()]);
+        assert_eq!(store.last_seen(&MetricKey::new("a2", "cpu")), None);
+    }
+
+    #[test]
+    fn retain_agents_drops_other_series() {
+        let store = AggregatorStore::new(10000);
+        store.ingest("a1", "cpu", 100, 10.0);
+        store.ingest("a2", "cpu", 100, 90.0);
+        store.retain_agents(|a| a == "a2");
+        assert_eq!(store.avg("a1", "cpu"), None);
+        assert_eq!(store.avg("a2", "cpu"), Some(90.0));
+    }
+
+    #[test]
+    fn widening_window_keeps_more_samples() {
+        let store = AggregatorStore::new(1000);
+        store.set_window_ms(10_000);
+        store.ingest("a1", "cpu", 1000, 10.0);
+        store.ingest("a1", "cpu", 5000, 30.0);
+        let key = MetricKey::new("a1", "cpu");
+        assert_eq!(store.series_count(&key), 2);
+        assert_eq!(
+            store.series_reduce(&key, Aggregation::Max, 10_000),
+            Some(30.0)
+        );
+        assert_eq!(store.series_count_within(&key, 1000), 1);
+    }
 }
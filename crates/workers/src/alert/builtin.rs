@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use sentinel_common::aggregation::Aggregation;
+
+use super::rule::{Condition, Rule};
+use crate::config::AgentOfflineConfig;
+
+pub const BUILTIN_RULE_PREFIX: &str = "builtin:";
+pub const AGENT_OFFLINE_RULE_ID: &str = "builtin:agent-offline";
+pub const AGENT_UP_METRIC: &str = "agent.up";
+
+pub fn is_builtin(rule_id: &str) -> bool {
+    rule_id.starts_with(BUILTIN_RULE_PREFIX)
+}
+
+pub fn agent_offline_rule(config: &AgentOfflineConfig) -> Rule {
+    let mut annotations = HashMap::new();
+    annotations.insert(
+        "summary".to_string(),
+        "Agent stopped reporting to the server".to_string(),
+    );
+    Rule {
+        id: AGENT_OFFLINE_RULE_ID.into(),
+        name: "Agent offline".into(),
+        agent_pattern: "*".into(),
+        metric_name: AGENT_UP_METRIC.into(),
+        label_matchers: Vec::new(),
+        expression: None,
+        absent_for_ms: None,
+        condition: Condition::LessThan,
+        threshold: 1.0,
+        aggregation: Aggregation::Last,
+        window_ms: 0,
+        min_samples: 1,
+        for_duration_ms: config.for_duration.as_millis() as i64,
+        severity: config.severity,
+        annotations,
+        notifier_ids: config.notifier_ids.clone(),
+        grouping: None,
+        escalation_policy_id: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offline_rule_follows_config() {
+        let config = AgentOfflineConfig {
+            notifier_ids: vec!["n-1".into()],
+            ..Default::default()
+        };
+        let rule = agent_offline_rule(&config);
+        assert!(is_builtin(&rule.id));
+        assert_eq!(rule.for_duration_ms, 60_000);
+        assert_eq!(rule.notifier_ids, vec!["n-1".to_string()]);
+        assert!(!is_builtin("r-1"));
+    }
+}
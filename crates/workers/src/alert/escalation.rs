@@ -0,0 +1,391 @@
+use std::collections::HashMap;
+
+use sentinel_common::escalation::{EscalationPolicy, Route, RouteTarget};
+use sqlx::PgPool;
+
+use super::event::AlertEvent;
+
+const LEASE_SECS: i64 = 60;
+
+#[derive(Default)]
+pub struct Escalations {
+    policies: HashMap<String, EscalationPolicy>,
+    routes: Option<Route>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct EscalationPlan {
+    pub notify: Vec<String>,
+    pub step: usize,
+    pub next_at_ms: Option<i64>,
+}
+
+pub struct DueEscalation {
+    pub fingerprint: String,
+    pub policy_id: String,
+    pub event: AlertEvent,
+    pub step: usize,
+    pub notified: Vec<String>,
+}
+
+pub struct EscalationStore {
+    pool: PgPool,
+}
+
+impl Escalations {
+    pub fn new(policies: Vec<EscalationPolicy>, routes: Option<Route>) -> Self {
+        Self {
+            policies: policies.into_iter().map(|p| (p.id.clone(), p)).collect(),
+            routes,
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&EscalationPolicy> {
+        self.policies.get(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.policies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.policies.is_empty()
+    }
+
+    pub fn resolve(&self, rule_policy: Option<&str>, event: &AlertEvent) -> Vec<EscalationPolicy> {
+        let ids = match (rule_policy, &self.routes) {
+            (Some(id), _) => vec![id.to_string()],
+            (None, Some(tree)) => tree.resolve(&RouteTarget {
+                agent_id: &event.agent_id,
+                severity: event.severity.as_str(),
+                labels: &event.labels,
+            }),
+            (None, None) => Vec::new(),
+        };
+        ids.iter().filter_map(|id| self.get(id)).cloned().collect()
+    }
+}
+
+pub fn plan_start(policy: &EscalationPolicy, now_ms: i64) -> EscalationPlan {
+    match policy.next_at_ms(0, now_ms) {
+        Some(at) if at > now_ms => EscalationPlan {
+            notify: Vec::new(),
+            step: 0,
+            next_at_ms: Some(at),
+        },
+        _ => plan_step(policy, 0, now_ms).unwrap_or(EscalationPlan {
+            notify: Vec::new(),
+            step: 0,
+            next_at_ms: None,
+        }),
+    }
+}
+
+pub fn plan_step(policy: &EscalationPolicy, step: usize, now_ms: i64) -> Option<EscalationPlan> {
+    let current = policy.steps.get(step)?;
+    Some(EscalationPlan {
+        notify: current.notifier_ids.clone(),
+        step: step + 1,
+        next_at_ms: policy.next_at_ms(step + 1, now_ms),
+    })
+}
+
+impl EscalationStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn load(&self) -> Result<Escalations, sqlx::Error> {
+        let rows = sqlx::query_as::<_, PolicyRow>(
+            "SELECT id, name, description, steps FROM escalation_policies",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        let tree: Option<serde_json::Value> =
+            sqlx::query_scalar("SELECT tree FROM escalation_routes WHERE id = 'default'")
+                .fetch_optional(&self.pool)
+                .await?;
+
+        let policies = rows
+            .into_iter()
+            .filter_map(|r| {
+                let steps = serde_json::from_value(r.steps).ok()?;
+                Some(EscalationPolicy {
+                    id: r.id,
+                    name: r.name,
+                    description: r.description,
+                    steps,
+                })
+            })
+            .collect();
+        let routes = tree.and_then(|t| serde_json::from_value(t).ok());
+        Ok(Escalations::new(policies, routes))
+    }
+
+    pub async fn start(
+        &self,
+        event: &AlertEvent,
+        policy_id: &str,
+        plan: &EscalationPlan,
+    ) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query(
+            r#"INSERT INTO alert_escalations
+               (fingerprint, policy_id, alert_id, rule_id, event, step, notified,
+                next_at, stopped_reason)
+               VALUES ($1, $2, $3, $4, $5, $6, $7,
+                       to_timestamp($8::double precision / 1000),
+                       CASE WHEN $8 IS NULL THEN 'completed' END)
+               ON CONFLICT (fingerprint, policy_id) DO NOTHING"#,
+        )
+        .bind(&event.fingerprint)
+        .bind(policy_id)
+        .bind(&event.id)
+        .bind(&event.rule_id)
+        .bind(serde_json::to_value(event).unwrap_or_default())
+        .bind(plan.step as i32)
+        .bind(serde_json::to_value(&plan.notify).unwrap_or_default())
+        .bind(plan.next_at_ms)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn finish(&self, fingerprint: &str) -> Result<Vec<String>, sqlx::Error> {
+        let rows: Vec<serde_json::Value> = sqlx::query_scalar(
+            "DELETE FROM alert_escalations WHERE fingerprint = $1 RETURNING notified",
+        )
+        .bind(fingerprint)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut notified: Vec<String> = Vec::new();
+        for ids in rows {
+            for id in serde_json::from_value::<Vec<String>>(ids).unwrap_or_default() {
+                if !notified.contains(&id) {
+                    notified.push(id);
+                }
+            }
+        }
+        Ok(notified)
+    }
+
+    pub async fn stop_acked(&self) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE alert_escalations
+             SET next_at = NULL, leased_until = NULL, stopped_reason = 'acked'
+             WHERE next_at IS NOT NULL
+               AND fingerprint IN (SELECT fingerprint FROM alert_acks)",
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn resume_unacked(&self) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE alert_escalations
+             SET next_at = NOW(), stopped_reason = NULL
+             WHERE stopped_reason = 'acked'
+               AND fingerprint NOT IN (SELECT fingerprint FROM alert_acks)",
+        )
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn claim_due(&self, limit: i64) -> Result<Vec<DueEscalation>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, DueRow>(
+            "UPDATE alert_escalations
+             SET leased_until = NOW() + make_interval(secs => $2)
+             WHERE (fingerprint, policy_id) IN (
+                 SELECT fingerprint, policy_id FROM alert_escalations
+                 WHERE next_at <= NOW()
+                   AND (leased_until IS NULL OR leased_until < NOW())
+                 ORDER BY next_at
+                 LIMIT $1
+                 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING fingerprint, policy_id, event, step, notified",
+        )
+        .bind(limit)
+        .bind(LEASE_SECS as f64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|r| {
+                Some(DueEscalation {
+                    event: serde_json::from_value(r.event).ok()?,
+                    fingerprint: r.fingerprint,
+                    policy_id: r.policy_id,
+                    step: r.step.max(0) as usize,
+                    notified: serde_json::from_value(r.notified).unwrap_or_default(),
+                })
+            })
+            .collect())
+    }
+
+    pub async fn record(
+        &self,
+        due: &DueEscalation,
+        step: usize,
+        notified: &[String],
+        next_at_ms: Option<i64>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE alert_escalations
+             SET step = $3, notified = $4,
+                 next_at = to_timestamp($5::double precision / 1000),
+                 stopped_reason = CASE WHEN $5 IS NULL THEN 'completed' END,
+                 leased_until = NULL
+             WHERE fingerprint = $1 AND policy_id = $2",
+        )
+        .bind(&due.fingerprint)
+        .bind(&due.policy_id)
+        .bind(step as i32)
+        .bind(serde_json::to_value(notified).unwrap_or_default())
+        .bind(next_at_ms)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn hold(&self, due: &DueEscalation, until_ms: i64) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE alert_escalations
+             SET next_at = to_timestamp($3::double precision / 1000), leased_until = NULL
+             WHERE fingerprint = $1 AND policy_id = $2",
+        )
+        .bind(&due.fingerprint)
+        .bind(&due.policy_id)
+        .bind(until_ms)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn remove(&self, due: &DueEscalation) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM alert_escalations WHERE fingerprint = $1 AND policy_id = $2")
+            .bind(&due.fingerprint)
+            .bind(&due.policy_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PolicyRow {
+    id: String,
+    name: String,
+    description: String,
+    steps: serde_json::Value,
+}
+
+#[derive(sqlx::FromRow)]
+struct DueRow {
+    fingerprint: String,
+    policy_id: String,
+    event: serde_json::Value,
+    step: i32,
+    notified: serde_json::Value,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alert::{AlertStatus, Severity};
+    use sentinel_common::escalation::EscalationStep;
+    use std::collections::BTreeMap;
+
+    fn policy(id: &str, steps: &[(u64, &str)]) -> EscalationPolicy {
+        EscalationPolicy {
+            id: id.into(),
+            name: id.into(),
+            description: String::new(),
+            steps: steps
+                .iter()
+                .map(|(delay, nid)| EscalationStep {
+                    delay_secs: *delay,
+                    notifier_ids: vec![nid.to_string()],
+                })
+                .collect(),
+        }
+    }
+
+    fn event(agent: &str, severity: Severity) -> AlertEvent {
+        AlertEvent {
+            id: "a-1".into(),
+            fingerprint: "fp-1".into(),
+            rule_id: "r-1".into(),
+            rule_name: "cpu".into(),
+            agent_id: agent.into(),
+            metric_name: "cpu.usage".into(),
+            labels: BTreeMap::new(),
+            severity,
+            status: AlertStatus::Firing,
+            value: 95.0,
+            threshold: 90.0,
+            fired_at_ms: 0,
+            resolved_at_ms: None,
+            annotations: Default::default(),
+        }
+    }
+
+    #[test]
+    fn plans_steps_relative_to_previous() {
+        let p = policy("p", &[(0, "slack"), (600, "pagerduty"), (300, "smtp")]);
+        let start = plan_start(&p, 1_000);
+        assert_eq!(start.notify, vec!["slack"]);
+        assert_eq!(start.step, 1);
+        assert_eq!(start.next_at_ms, Some(601_000));
+
+        let next = plan_step(&p, 1, 601_000).unwrap();
+        assert_eq!(next.notify, vec!["pagerduty"]);
+        assert_eq!(next.next_at_ms, Some(901_000));
+
+        let last = plan_step(&p, 2, 901_000).unwrap();
+        assert_eq!(last.next_at_ms, None);
+        assert!(plan_step(&p, 3, 901_000).is_none());
+
+        let delayed = plan_start(&policy("d", &[(120, "slack")]), 1_000);
+        assert!(delayed.notify.is_empty());
+        assert_eq!(delayed.step, 0);
+        assert_eq!(delayed.next_at_ms, Some(121_000));
+    }
+
+    #[test]
+    fn rule_policy_wins_over_routes() {
+        let routes: Route = serde_json::from_value(serde_json::json!({
+            "policy_id": "default",
+            "routes": [{ "severity": ["critical"], "policy_id": "page" }]
+        }))
+        .unwrap();
+        let set = Escalations::new(
+            vec![
+                policy("default", &[(0, "slack")]),
+                policy("page", &[(0, "pagerduty")]),
+                policy("db", &[(0, "smtp")]),
+            ],
+            Some(routes),
+        );
+
+        let ids = |rule: Option<&str>, e: &AlertEvent| -> Vec<String> {
+            set.resolve(rule, e).into_iter().map(|p| p.id).collect()
+        };
+        assert_eq!(
+            ids(Some("db"), &event("web-01", Severity::Critical)),
+            vec!["db"]
+        );
+        assert_eq!(
+            ids(None, &event("web-01", Severity::Critical)),
+            vec!["page"]
+        );
+        assert_eq!(ids(None, &event("web-01", Severity::Info)), vec!["default"]);
+        assert!(ids(Some("gone"), &event("web-01", Severity::Info)).is_empty());
+        assert!(Escalations::default()
+            .resolve(None, &event("web-01", Severity::Info))
+            .is_empty());
+    }
+}
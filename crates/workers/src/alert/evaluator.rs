@@ -1,30 +1,115 @@
 use dashmap::DashMap;
-use std::sync::Arc;
+use sentinel_common::expr::{self, Expr};
+use sentinel_common::grouping::GroupPolicy;
+use sentinel_common::labels::LabelSelector;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
 
+use super::builtin::{is_builtin, AGENT_OFFLINE_RULE_ID};
 use super::event::{AlertEvent, AlertStatus};
-use super::fingerprint::fingerprint_string;
+use super::expr::{evaluate as evaluate_expr, ExprContext};
+use super::fingerprint::series_fingerprint_string;
 use super::rule::Rule;
-use super::state::RuleState;
-use crate::aggregator::AggregatorStore;
+use super::state::{RuleState, StateChange, TrackedState};
+use crate::aggregator::{AggregatorStore, MetricKey};
 
 pub struct Evaluator {
     rules: Vec<Rule>,
-    states: Arc<DashMap<String, RuleState>>,
+    selectors: HashMap<String, LabelSelector>,
+    exprs: HashMap<String, Expr>,
+    revisions: HashMap<String, String>,
+    states: Arc<DashMap<String, TrackedState>>,
+    changes: Mutex<Vec<StateChange>>,
 }
 
-const MIN_SAMPLES: usize = 2;
-
 impl Evaluator {
     pub fn new(rules: Vec<Rule>) -> Self {
+        let selectors = compile_selectors(&rules);
+        let exprs = compile_exprs(&rules);
+        let revisions = rule_revisions(&rules);
         Self {
             rules,
+            selectors,
+            exprs,
+            revisions,
             states: Arc::new(DashMap::new()),
+            changes: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn set_rules(&mut self, rules: Vec<Rule>, now_ms: i64) -> Vec<AlertEvent> {
+        let previous = std::mem::replace(&mut self.rules, rules);
+        self.selectors = compile_selectors(&self.rules);
+        self.exprs = compile_exprs(&self.rules);
+        self.revisions = rule_revisions(&self.rules);
+
+        let mut events = Vec::new();
+        let mut changes = Vec::new();
+        self.states.retain(|fp, tracked| {
+            if self.revisions.get(&tracked.rule_id) == Some(&tracked.revision) {
+                return true;
+            }
+            let rule = previous.iter().find(|r| r.id == tracked.rule_id);
+            if let Some(rule) = rule {
+                events.extend(resolve_dropped(rule, fp, tracked, now_ms));
+            }
+            changes.push(StateChange {
+                fingerprint: fp.clone(),
+                state: None,
+            });
+            false
+        });
+        self.changes
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .extend(changes);
+        events
+    }
+
+    pub fn restore_states(
+        &self,
+        states: Vec<(String, TrackedState)>,
+        now_ms: i64,
+    ) -> (Vec<String>, Vec<AlertEvent>) {
+        let mut rejected = Vec::new();
+        let mut events = Vec::new();
+        for (fp, tracked) in states {
+            if self.revisions.get(&tracked.rule_id) != Some(&tracked.revision) {
+                if let Some(rule) = self.rules.iter().find(|r| r.id == tracked.rule_id) {
+                    events.extend(resolve_dropped(rule, &fp, &tracked, now_ms));
+                }
+                rejected.push(fp);
+                continue;
+            }
+            self.states.entry(fp).or_insert(tracked);
         }
+        (rejected, events)
+    }
+
+    pub fn retain_agents(&self, keep: impl Fn(&str) -> bool) -> usize {
+        let before = self.states.len();
+        self.states.retain(|_, tracked| keep(&tracked.agent_id));
+        before - self.states.len()
     }
 
-    pub fn set_rules(&mut self, rules: Vec<Rule>) {
-        self.rules = rules;
-        self.states.clear();
+    pub fn state_count(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn drain_state_changes(&self) -> Vec<StateChange> {
+        let mut changes = self.changes.lock().unwrap_or_else(|e| e.into_inner());
+        std::mem::take(&mut *changes)
+    }
+
+    pub fn max_window_ms(&self) -> i64 {
+        self.rules
+            .iter()
+            .map(|r| {
+                let expr_window = self.exprs.get(&r.id).and_then(Expr::max_window_ms);
+                expr_window.unwrap_or(0).max(r.window_ms)
+            })
+            .max()
+            .unwrap_or(0)
     }
 
     pub fn notifier_ids_for_rule(&self, rule_id: &str) -> &[String] {
@@ -35,6 +120,20 @@ impl Evaluator {
             .unwrap_or(&[])
     }
 
+    pub fn grouping_for_rule(&self, rule_id: &str) -> Option<&GroupPolicy> {
+        self.rules
+            .iter()
+            .find(|r| r.id == rule_id)
+            .and_then(|r| r.grouping.as_ref())
+    }
+
+    pub fn escalation_policy_for_rule(&self, rule_id: &str) -> Option<&str> {
+        self.rules
+            .iter()
+            .find(|r| r.id == rule_id)
+            .and_then(|r| r.escalation_policy_id.as_deref())
+    }
+
     pub fn evaluate(
         &self,
         agent_id: &str,
@@ -44,66 +143,386 @@ impl Evaluator {
         let mut events = Vec::new();
 
         for rule in &self.rules {
-            if !agent_matches(&rule.agent_pattern, agent_id) {
+            if is_builtin(&rule.id) || !agent_matches(&rule.agent_pattern, agent_id) {
                 continue;
             }
+            let Some(selector) = self.selectors.get(&rule.id) else {
+                continue;
+            };
 
-            let fp = fingerprint_string(&rule.id, agent_id, &rule.metric_name);
-
-            if aggregator.count(agent_id, &rule.metric_name) < MIN_SAMPLES {
+            if let Some(absent_for_ms) = rule.absent_for_ms {
+                self.evaluate_absent(
+                    rule,
+                    selector,
+                    absent_for_ms,
+                    agent_id,
+                    aggregator,
+                    now_ms,
+                    &mut events,
+                );
                 continue;
             }
 
-            let value = match aggregator.avg(agent_id, &rule.metric_name) {
-                Some(v) => v,
-                None => continue,
-            };
+            if rule.expression.is_some() {
+                let Some(expr) = self.exprs.get(&rule.id) else {
+                    continue;
+                };
+                let ctx = ExprContext {
+                    agent_id,
+                    aggregator,
+                    aggregation: rule.aggregation,
+                    window_ms: rule.window_ms,
+                    min_samples: rule.min_samples,
+                };
+                for result in evaluate_expr(expr, &ctx) {
+                    if !selector.matches(&result.labels) {
+                        continue;
+                    }
+                    let threshold = result.threshold.unwrap_or(rule.threshold);
+                    let outcome = Outcome {
+                        value: result.value,
+                        threshold,
+                        condition_met: result.firing,
+                    };
+                    self.transition(rule, agent_id, result.labels, outcome, now_ms, &mut events);
+                }
+                continue;
+            }
 
-            let condition_met = rule.condition.evaluate(value, rule.threshold);
-
-            let current = self.states.get(&fp).map(|s| *s).unwrap_or(RuleState::Ok);
-            let next = current.transition(condition_met, now_ms, rule.for_duration_ms);
-            self.states.insert(fp.clone(), next);
-
-            if next.is_firing() && !current.is_firing() {
-                events.push(AlertEvent {
-                    id: uuid::Uuid::new_v4().to_string(),
-                    fingerprint: fp.clone(),
-                    rule_id: rule.id.clone(),
-                    rule_name: rule.name.clone(),
-                    agent_id: agent_id.to_string(),
-                    metric_name: rule.metric_name.clone(),
-                    severity: rule.severity,
-                    status: AlertStatus::Firing,
-                    value,
-                    threshold: rule.threshold,
-                    fired_at_ms: now_ms,
-                    resolved_at_ms: None,
-                    annotations: rule.annotations.clone(),
-                });
+            for key in aggregator.series_keys(agent_id, &rule.metric_name) {
+                if !selector.matches(&key.labels) {
+                    continue;
+                }
+                self.evaluate_series(rule, &key, aggregator, now_ms, &mut events);
             }
+        }
+
+        events
+    }
+
+    pub fn sweep(&self, aggregator: &AggregatorStore, now_ms: i64) -> Vec<AlertEvent> {
+        let mut events = Vec::new();
 
-            if next.just_resolved() {
-                events.push(AlertEvent {
-                    id: uuid::Uuid::new_v4().to_string(),
-                    fingerprint: fp,
-                    rule_id: rule.id.clone(),
-                    rule_name: rule.name.clone(),
-                    agent_id: agent_id.to_string(),
-                    metric_name: rule.metric_name.clone(),
-                    severity: rule.severity,
-                    status: AlertStatus::Resolved,
-                    value,
-                    threshold: rule.threshold,
-                    fired_at_ms: now_ms,
-                    resolved_at_ms: Some(now_ms),
-                    annotations: rule.annotations.clone(),
-                });
+        for agent_id in aggregator.agents() {
+            for rule in &self.rules {
+                let Some(absent_for_ms) = rule.absent_for_ms else {
+                    continue;
+                };
+                if !agent_matches(&rule.agent_pattern, &agent_id) {
+                    continue;
+                }
+                let Some(selector) = self.selectors.get(&rule.id) else {
+                    continue;
+                };
+                self.evaluate_absent(
+                    rule,
+                    selector,
+                    absent_for_ms,
+                    &agent_id,
+                    aggregator,
+                    now_ms,
+                    &mut events,
+                );
             }
         }
 
+        let down: Vec<String> = self
+            .states
+            .iter()
+            .filter(|e| e.rule_id == AGENT_OFFLINE_RULE_ID)
+            .filter(|e| {
+                matches!(
+                    e.state,
+                    RuleState::Pending { .. } | RuleState::Firing { .. }
+                )
+            })
+            .map(|e| e.agent_id.clone())
+            .collect();
+        for agent_id in down {
+            events.extend(self.evaluate_presence(&agent_id, true, now_ms));
+        }
+
+        events
+    }
+
+    pub fn evaluate_presence(&self, agent_id: &str, down: bool, now_ms: i64) -> Vec<AlertEvent> {
+        let mut events = Vec::new();
+        let Some(rule) = self.rules.iter().find(|r| r.id == AGENT_OFFLINE_RULE_ID) else {
+            return events;
+        };
+        if !agent_matches(&rule.agent_pattern, agent_id) {
+            return events;
+        }
+        let outcome = Outcome {
+            value: if down { 0.0 } else { 1.0 },
+            threshold: rule.threshold,
+            condition_met: down,
+        };
+        self.transition(
+            rule,
+            agent_id,
+            BTreeMap::new(),
+            outcome,
+            now_ms,
+            &mut events,
+        );
         events
     }
+
+    #[allow(clippy::too_many_arguments)]
+    fn evaluate_absent(
+        &self,
+        rule: &Rule,
+        selector: &LabelSelector,
+        absent_for_ms: i64,
+        agent_id: &str,
+        aggregator: &AggregatorStore,
+        now_ms: i64,
+        events: &mut Vec<AlertEvent>,
+    ) {
+        let outcome = |last_seen_ms: i64| {
+            let silent_ms = (now_ms - last_seen_ms).max(0);
+            Outcome {
+                value: silent_ms as f64 / 1000.0,
+                threshold: absent_for_ms as f64 / 1000.0,
+                condition_met: silent_ms >= absent_for_ms,
+            }
+        };
+
+        let keys: Vec<MetricKey> = aggregator
+            .series_keys(agent_id, &rule.metric_name)
+            .into_iter()
+            .filter(|key| selector.matches(&key.labels))
+            .collect();
+
+        if keys.is_empty() {
+            let Some(first_seen) = aggregator.agent_first_seen(agent_id) else {
+                return;
+            };
+            self.transition(
+                rule,
+                agent_id,
+                BTreeMap::new(),
+                outcome(first_seen),
+                now_ms,
+                events,
+            );
+            return;
+        }
+
+        let never_reported =
+            series_fingerprint_string(&rule.id, agent_id, rule.series_name(), &BTreeMap::new());
+        if !keys.iter().any(|k| k.labels.is_empty()) && self.states.contains_key(&never_reported) {
+            let present = Outcome {
+                value: 0.0,
+                threshold: absent_for_ms as f64 / 1000.0,
+                condition_met: false,
+            };
+            self.transition(rule, agent_id, BTreeMap::new(), present, now_ms, events);
+        }
+
+        for key in keys {
+            let last_seen = aggregator.last_seen(&key).unwrap_or(now_ms);
+            self.transition(
+                rule,
+                agent_id,
+                key.labels,
+                outcome(last_seen),
+                now_ms,
+                events,
+            );
+        }
+    }
+
+    fn evaluate_series(
+        &self,
+        rule: &Rule,
+        key: &MetricKey,
+        aggregator: &AggregatorStore,
+        now_ms: i64,
+        events: &mut Vec<AlertEvent>,
+    ) {
+        if aggregator.series_count_within(key, rule.window_ms) < rule.min_samples {
+            return;
+        }
+
+        let value = match aggregator.series_reduce(key, rule.aggregation, rule.window_ms) {
+            Some(v) => v,
+            None => return,
+        };
+
+        let outcome = Outcome {
+            value,
+            threshold: rule.threshold,
+            condition_met: rule.condition.evaluate(value, rule.threshold),
+        };
+        self.transition(
+            rule,
+            &key.agent_id,
+            key.labels.clone(),
+            outcome,
+            now_ms,
+            events,
+        );
+    }
+
+    fn transition(
+        &self,
+        rule: &Rule,
+        agent_id: &str,
+        labels: BTreeMap<String, String>,
+        outcome: Outcome,
+        now_ms: i64,
+        events: &mut Vec<AlertEvent>,
+    ) {
+        let fp = series_fingerprint_string(&rule.id, agent_id, rule.series_name(), &labels);
+
+        let current = self
+            .states
+            .get(&fp)
+            .map(|t| t.state)
+            .unwrap_or(RuleState::Ok);
+        let next = current.transition(outcome.condition_met, now_ms, rule.for_duration_ms);
+        self.record_state(rule, agent_id, &labels, &fp, current, next);
+
+        let status = if next.is_firing() && !current.is_firing() {
+            AlertStatus::Firing
+        } else if next.just_resolved() {
+            AlertStatus::Resolved
+        } else {
+            return;
+        };
+
+        events.push(alert_event(
+            rule, &fp, agent_id, labels, status, &outcome, now_ms,
+        ));
+    }
+}
+
+impl Evaluator {
+    fn record_state(
+        &self,
+        rule: &Rule,
+        agent_id: &str,
+        labels: &BTreeMap<String, String>,
+        fp: &str,
+        current: RuleState,
+        next: RuleState,
+    ) {
+        if next == current {
+            return;
+        }
+        let state = if next == RuleState::Ok {
+            self.states.remove(fp);
+            None
+        } else {
+            let tracked = TrackedState {
+                rule_id: rule.id.clone(),
+                revision: self.revisions.get(&rule.id).cloned().unwrap_or_default(),
+                agent_id: agent_id.to_string(),
+                labels: labels.clone(),
+                state: next,
+            };
+            self.states.insert(fp.to_string(), tracked.clone());
+            Some(tracked)
+        };
+        let mut changes = self.changes.lock().unwrap_or_else(|e| e.into_inner());
+        changes.push(StateChange {
+            fingerprint: fp.to_string(),
+            state,
+        });
+    }
+}
+
+struct Outcome {
+    value: f64,
+    threshold: f64,
+    condition_met: bool,
+}
+
+fn alert_event(
+    rule: &Rule,
+    fp: &str,
+    agent_id: &str,
+    labels: BTreeMap<String, String>,
+    status: AlertStatus,
+    outcome: &Outcome,
+    now_ms: i64,
+) -> AlertEvent {
+    AlertEvent {
+        id: uuid::Uuid::new_v4().to_string(),
+        fingerprint: fp.to_string(),
+        rule_id: rule.id.clone(),
+        rule_name: rule.name.clone(),
+        agent_id: agent_id.to_string(),
+        metric_name: rule.series_name().to_string(),
+        labels,
+        severity: rule.severity,
+        status,
+        value: outcome.value,
+        threshold: outcome.threshold,
+        fired_at_ms: now_ms,
+        resolved_at_ms: (status == AlertStatus::Resolved).then_some(now_ms),
+        annotations: rule.annotations.clone(),
+    }
+}
+
+fn resolve_dropped(
+    rule: &Rule,
+    fp: &str,
+    tracked: &TrackedState,
+    now_ms: i64,
+) -> Option<AlertEvent> {
+    if !tracked.state.is_firing() {
+        return None;
+    }
+    let outcome = Outcome {
+        value: 0.0,
+        threshold: rule.threshold,
+        condition_met: false,
+    };
+    Some(alert_event(
+        rule,
+        fp,
+        &tracked.agent_id,
+        tracked.labels.clone(),
+        AlertStatus::Resolved,
+        &outcome,
+        now_ms,
+    ))
+}
+
+fn compile_selectors(rules: &[Rule]) -> HashMap<String, LabelSelector> {
+    rules
+        .iter()
+        .filter_map(|r| match LabelSelector::compile(&r.label_matchers) {
+            Ok(sel) => Some((r.id.clone(), sel)),
+            Err(e) => {
+                tracing::warn!(target: "alert", rule_id = %r.id, error = %e, "Skipping rule with invalid label matchers");
+                None
+            }
+        })
+        .collect()
+}
+
+fn rule_revisions(rules: &[Rule]) -> HashMap<String, String> {
+    rules.iter().map(|r| (r.id.clone(), r.revision())).collect()
+}
+
+fn compile_exprs(rules: &[Rule]) -> HashMap<String, Expr> {
+    rules
+        .iter()
+        .filter_map(|r| {
+            let source = r.expression.as_deref()?;
+            match expr::parse(source) {
+                Ok(e) => Some((r.id.clone(), e)),
+                Err(e) => {
+                    tracing::warn!(target: "alert", rule_id = %r.id, error = %e, "Skipping rule with invalid expression");
+                    None
+                }
+            }
+        })
+        .collect()
 }
 
 fn agent_matches(pattern: &str, agent_id: &str) -> bool {
@@ -119,8 +538,9 @@ fn agent_matches(pattern: &str, agent_id: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::alert::rule::{Condition, Severity};
-    use std::collections::HashMap;
+    use crate::alert::rule::{Condition, Severity, DEFAULT_MIN_SAMPLES, DEFAULT_WINDOW_MS};
+    use sentinel_common::aggregation::Aggregation;
+    use sentinel_common::labels::LabelMatcher;
 
     fn cpu_rule() -> Rule {
         Rule {
@@ -128,12 +548,20 @@ mod tests {
             name: "high cpu".into(),
             agent_pattern: "*".into(),
             metric_name: "cpu".into(),
+            label_matchers: Vec::new(),
+            expression: None,
+            absent_for_ms: None,
             condition: Condition::GreaterThan,
             threshold: 80.0,
+            aggregation: Aggregation::Avg,
+            window_ms: DEFAULT_WINDOW_MS,
+            min_samples: DEFAULT_MIN_SAMPLES,
             for_duration_ms: 0,
             severity: Severity::Warning,
             annotations: HashMap::new(),
             notifier_ids: Vec::new(),
+            grouping: None,
+            escalation_policy_id: None,
         }
     }
 
@@ -220,4 +648,451 @@ mod tests {
         assert_eq!(events2.len(), 1);
         assert_eq!(events2[0].status, AlertStatus::Firing);
     }
+
+    fn disk_key(agent: &str, mount: &str) -> MetricKey {
+        let mut key = MetricKey::new(agent, "disk");
+        key.labels.insert("mount".into(), mount.into());
+        key
+    }
+
+    fn disk_rule(matchers: &[&str]) -> Rule {
+        Rule {
+            metric_name: "disk".into(),
+            threshold: 90.0,
+            label_matchers: matchers
+                .iter()
+                .map(|m| LabelMatcher::parse(m).unwrap())
+                .collect(),
+            ..cpu_rule()
+        }
+    }
+
+    fn ingest_disks(agg: &AggregatorStore, root: f64, data: f64) {
+        for ts in [500, 1000] {
+            agg.ingest_series(disk_key("a", "/"), ts, root);
+            agg.ingest_series(disk_key("a", "/data"), ts, data);
+        }
+    }
+
+    #[test]
+    fn fires_per_series_with_labels() {
+        let agg = AggregatorStore::new(10000);
+        ingest_disks(&agg, 50.0, 95.0);
+
+        let eval = Evaluator::new(vec![disk_rule(&[])]);
+        let events = eval.evaluate("a", &agg, 1000);
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].labels.get("mount").unwrap(), "/data");
+        assert_eq!(events[0].value, 95.0);
+    }
+
+    #[test]
+    fn each_series_has_own_state_and_fingerprint() {
+        let agg = AggregatorStore::new(10000);
+        ingest_disks(&agg, 95.0, 95.0);
+
+        let eval = Evaluator::new(vec![disk_rule(&[])]);
+        let events = eval.evaluate("a", &agg, 1000);
+        assert_eq!(events.len(), 2);
+        assert_ne!(events[0].fingerprint, events[1].fingerprint);
+
+        agg.ingest_series(disk_key("a", "/"), 2000, 10.0);
+        agg.ingest_series(disk_key("a", "/"), 2001, 10.0);
+        let events = eval.evaluate("a", &agg, 2001);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].status, AlertStatus::Resolved);
+        assert_eq!(events[0].labels.get("mount").unwrap(), "/");
+    }
+
+    #[test]
+    fn label_matchers_filter_series() {
+        let agg = AggregatorStore::new(10000);
+        ingest_disks(&agg, 95.0, 95.0);
+
+        let eq = Evaluator::new(vec![disk_rule(&["mount=/"])]);
+        let events = eq.evaluate("a", &agg, 1000);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].labels.get("mount").unwrap(), "/");
+
+        let ne = Evaluator::new(vec![disk_rule(&["mount!=/"])]);
+        let events = ne.evaluate("a", &agg, 1000);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].labels.get("mount").unwrap(), "/data");
+
+        let re = Evaluator::new(vec![disk_rule(&["mount=~/d.*"])]);
+        assert_eq!(re.evaluate("a", &agg, 1000).len(), 1);
+    }
+
+    #[test]
+    fn invalid_matcher_skips_rule() {
+        let agg = AggregatorStore::new(10000);
+        ingest_disks(&agg, 95.0, 95.0);
+
+        let mut rule = disk_rule(&[]);
+        rule.label_matchers.push(LabelMatcher {
+            label: "mount".into(),
+            op: sentinel_common::labels::MatchOp::Regex,
+            value: "(".into(),
+        });
+        let eval = Evaluator::new(vec![rule]);
+        assert!(eval.evaluate("a", &agg, 1000).is_empty());
+    }
+
+    #[test]
+    fn rule_selects_reducer() {
+        let agg = AggregatorStore::new(10000);
+        agg.ingest("a", "cpu", 500, 50.0);
+        agg.ingest("a", "cpu", 1000, 95.0);
+
+        let avg = Evaluator::new(vec![cpu_rule()]);
+        assert!(avg.evaluate("a", &agg, 1000).is_empty());
+
+        let max = Evaluator::new(vec![Rule {
+            aggregation: Aggregation::Max,
+            ..cpu_rule()
+        }]);
+        let events = max.evaluate("a", &agg, 1000);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].value, 95.0);
+    }
+
+    fn mem_ratio_rule(expression: &str) -> Rule {
+        Rule {
+            metric_name: String::new(),
+            expression: Some(expression.into()),
+            absent_for_ms: None,
+            min_samples: 1,
+            ..cpu_rule()
+        }
+    }
+
+    #[test]
+    fn expression_rule_fires_and_resolves() {
+        let agg = AggregatorStore::new(10000);
+        agg.ingest("a", "mem.used_bytes", 1000, 950.0);
+        agg.ingest("a", "mem.total_bytes", 1000, 1000.0);
+
+        let expr = "mem.used_bytes / mem.total_bytes > 0.9";
+        let eval = Evaluator::new(vec![mem_ratio_rule(expr)]);
+        let events = eval.evaluate("a", &agg, 1000);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].status, AlertStatus::Firing);
+        assert_eq!(events[0].metric_name, expr);
+        assert_eq!(events[0].value, 0.95);
+        assert_eq!(events[0].threshold, 0.9);
+
+        agg.ingest("a", "mem.used_bytes", 1001, 100.0);
+        agg.ingest("a", "mem.total_bytes", 1001, 1000.0);
+        let events = eval.evaluate("a", &agg, 1001);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].status, AlertStatus::Resolved);
+    }
+
+    #[test]
+    fn expression_rule_per_series_and_matchers() {
+        let agg = AggregatorStore::new(10000);
+        ingest_disks(&agg, 99.0, 97.0);
+
+        let eval = Evaluator::new(vec![Rule {
+            label_matchers: vec![LabelMatcher::parse("mount!=/").unwrap()],
+            ..mem_ratio_rule("disk > 95")
+        }]);
+        let events = eval.evaluate("a", &agg, 1000);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].labels.get("mount").unwrap(), "/data");
+    }
+
+    #[test]
+    fn invalid_expression_skips_rule() {
+        let agg = AggregatorStore::new(10000);
+        agg.ingest("a", "cpu", 1000, 95.0);
+        agg.ingest("a", "cpu", 1001, 95.0);
+
+        let eval = Evaluator::new(vec![mem_ratio_rule("cpu >"), cpu_rule()]);
+        let events = eval.evaluate("a", &agg, 1001);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].rule_id, "r-1");
+    }
+
+    #[test]
+    fn expression_windows_extend_retention() {
+        let eval = Evaluator::new(vec![mem_ratio_rule("rate(net.rx_bytes, 15m) > 1000")]);
+        assert_eq!(eval.max_window_ms(), 900_000);
+    }
+
+    #[test]
+    fn reload_keeps_state_of_unchanged_rules() {
+        let agg = AggregatorStore::new(10000);
+        agg.ingest("a", "cpu", 500, 90.0);
+        agg.ingest("a", "cpu", 1000, 90.0);
+
+        let mut eval = Evaluator::new(vec![cpu_rule()]);
+        assert_eq!(eval.evaluate("a", &agg, 1000).len(), 1);
+
+        let renamed = Rule {
+            name: "renamed".into(),
+            ..cpu_rule()
+        };
+        assert!(eval.set_rules(vec![renamed], 1500).is_empty());
+        agg.ingest("a", "cpu", 2000, 95.0);
+        assert!(eval.evaluate("a", &agg, 2000).is_empty());
+    }
+
+    #[test]
+    fn reload_carries_state_over_to_changed_rules() {
+        let agg = AggregatorStore::new(10000);
+        agg.ingest("a", "cpu", 500, 90.0);
+        agg.ingest("a", "cpu", 1000, 90.0);
+
+        let mut eval = Evaluator::new(vec![cpu_rule()]);
+        assert_eq!(eval.evaluate("a", &agg, 1000).len(), 1);
+        eval.drain_state_changes();
+
+        let retuned = Rule {
+            threshold: 85.0,
+            ..cpu_rule()
+        };
+        assert!(eval.set_rules(vec![retuned], 1500).is_empty());
+        assert!(eval.drain_state_changes().is_empty());
+        assert_eq!(eval.state_count(), 1);
+        assert!(eval.evaluate("a", &agg, 2000).is_empty());
+
+        let raised = Rule {
+            threshold: 95.0,
+            ..cpu_rule()
+        };
+        eval.set_rules(vec![raised], 2500);
+        let events = eval.evaluate("a", &agg, 3000);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].status, AlertStatus::Resolved);
+    }
+
+    #[test]
+    fn reload_resolves_state_of_rules_with_new_selection() {
+        let agg = AggregatorStore::new(10000);
+        ingest_disks(&agg, 95.0, 95.0);
+
+        let mut eval = Evaluator::new(vec![disk_rule(&[])]);
+        let fired = eval.evaluate("a", &agg, 1000);
+        assert_eq!(fired.len(), 2);
+        eval.drain_state_changes();
+
+        let resolved = eval.set_rules(vec![disk_rule(&["mount=/"])], 2000);
+        assert_eq!(resolved.len(), 2);
+        assert!(resolved
+            .iter()
+            .all(|e| e.status == AlertStatus::Resolved && e.resolved_at_ms == Some(2000)));
+        assert_eq!(eval.state_count(), 0);
+        let changes = eval.drain_state_changes();
+        assert_eq!(changes.len(), 2);
+        assert!(changes.iter().all(|c| c.state.is_none()));
+    }
+
+    #[test]
+    fn reload_drops_state_of_deleted_rules() {
+        let agg = AggregatorStore::new(10000);
+        agg.ingest("a", "cpu", 500, 90.0);
+        agg.ingest("a", "cpu", 1000, 90.0);
+
+        let mut eval = Evaluator::new(vec![cpu_rule()]);
+        let fired = eval.evaluate("a", &agg, 1000);
+        assert_eq!(eval.state_count(), 1);
+        eval.drain_state_changes();
+
+        let resolved = eval.set_rules(Vec::new(), 2000);
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].status, AlertStatus::Resolved);
+        assert_eq!(resolved[0].fingerprint, fired[0].fingerprint);
+        assert_eq!(resolved[0].resolved_at_ms, Some(2000));
+        assert_eq!(eval.state_count(), 0);
+        let changes = eval.drain_state_changes();
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].state.is_none());
+    }
+
+    #[test]
+    fn pending_timer_survives_reload() {
+        let rule = Rule {
+            for_duration_ms: 5000,
+            ..cpu_rule()
+        };
+        let agg = AggregatorStore::new(20000);
+        agg.ingest("a", "cpu", 500, 90.0);
+        agg.ingest("a", "cpu", 1000, 90.0);
+
+        let mut eval = Evaluator::new(vec![rule.clone()]);
+        assert!(eval.evaluate("a", &agg, 1000).is_empty());
+        eval.set_rules(vec![rule], 1000);
+
+        agg.ingest("a", "cpu", 6000, 90.0);
+        assert_eq!(eval.evaluate("a", &agg, 6000).len(), 1);
+    }
+
+    #[test]
+    fn state_changes_are_drained_and_restored() {
+        let agg = AggregatorStore::new(10000);
+        agg.ingest("a", "cpu", 500, 90.0);
+        agg.ingest("a", "cpu", 1000, 90.0);
+
+        let eval = Evaluator::new(vec![cpu_rule()]);
+        eval.evaluate("a", &agg, 1000);
+        let changes = eval.drain_state_changes();
+        assert_eq!(changes.len(), 1);
+        assert!(eval.drain_state_changes().is_empty());
+        let tracked = changes[0].state.clone().unwrap();
+        assert!(tracked.state.is_firing());
+        assert_eq!(tracked.revision, cpu_rule().revision());
+
+        agg.ingest("a", "cpu", 1500, 95.0);
+        eval.evaluate("a", &agg, 1500);
+        assert!(eval.drain_state_changes().is_empty());
+
+        let restarted = Evaluator::new(vec![cpu_rule()]);
+        let orphan = TrackedState {
+            rule_id: "deleted".into(),
+            ..tracked.clone()
+        };
+        let outdated = TrackedState {
+            revision: "0000000000000000".into(),
+            ..tracked.clone()
+        };
+        let (rejected, resolved) = restarted.restore_states(
+            vec![("outdated".into(), outdated), ("other".into(), orphan)],
+            1200,
+        );
+        assert_eq!(rejected, vec!["outdated".to_string(), "other".to_string()]);
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].fingerprint, "outdated");
+        assert_eq!(resolved[0].status, AlertStatus::Resolved);
+        assert_eq!(restarted.state_count(), 0);
+
+        let (rejected, resolved) =
+            restarted.restore_states(vec![(changes[0].fingerprint.clone(), tracked)], 1200);
+        assert!(rejected.is_empty() && resolved.is_empty());
+        assert!(restarted.drain_state_changes().is_empty());
+        assert!(restarted.evaluate("a", &agg, 1500).is_empty());
+
+        agg.ingest("a", "cpu", 9000, 10.0);
+        agg.ingest("a", "cpu", 9001, 10.0);
+        let events = restarted.evaluate("a", &agg, 9001);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].status, AlertStatus::Resolved);
+    }
+
+    #[test]
+    fn retain_agents_forgets_handed_off_states() {
+        let agg = AggregatorStore::new(10000);
+        for agent in ["a", "b"] {
+            agg.ingest(agent, "cpu", 500, 90.0);
+            agg.ingest(agent, "cpu", 1000, 90.0);
+        }
+        let eval = Evaluator::new(vec![cpu_rule()]);
+        eval.evaluate("a", &agg, 1000);
+        eval.evaluate("b", &agg, 1000);
+        eval.drain_state_changes();
+
+        assert_eq!(eval.retain_agents(|a| a == "a"), 1);
+        assert_eq!(eval.state_count(), 1);
+        assert!(eval.drain_state_changes().is_empty());
+    }
+
+    #[test]
+    fn rule_window_and_min_samples() {
+        let agg = AggregatorStore::new(60_000);
+        agg.ingest("a", "cpu", 1000, 10.0);
+        agg.ingest("a", "cpu", 30_000, 90.0);
+        agg.ingest("a", "cpu", 31_000, 90.0);
+
+        let short = Evaluator::new(vec![Rule {
+            window_ms: 5000,
+            ..cpu_rule()
+        }]);
+        assert_eq!(short.evaluate("a", &agg, 31_000).len(), 1);
+
+        let strict = Evaluator::new(vec![Rule {
+            window_ms: 5000,
+            min_samples: 3,
+            ..cpu_rule()
+        }]);
+        assert!(strict.evaluate("a", &agg, 31_000).is_empty());
+    }
+
+    fn absent_rule(absent_for_ms: i64) -> Rule {
+        Rule {
+            id: "r-absent".into(),
+            name: "cpu missing".into(),
+            absent_for_ms: Some(absent_for_ms),
+            ..cpu_rule()
+        }
+    }
+
+    #[test]
+    fn absent_rule_fires_after_silence_and_resolves_on_data() {
+        let agg = AggregatorStore::new(10000);
+        let key = MetricKey::new("a", "cpu");
+        agg.observe(&key, 1_000);
+        agg.ingest_series(key.clone(), 1_000, 10.0);
+
+        let eval = Evaluator::new(vec![absent_rule(60_000)]);
+        assert!(eval.evaluate("a", &agg, 1_000).is_empty());
+        assert!(eval.sweep(&agg, 30_000).is_empty());
+
+        let events = eval.sweep(&agg, 61_000);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].status, AlertStatus::Firing);
+        assert_eq!(events[0].value, 60.0);
+        assert_eq!(events[0].threshold, 60.0);
+        assert!(eval.sweep(&agg, 90_000).is_empty());
+
+        agg.observe(&key, 95_000);
+        let events = eval.evaluate("a", &agg, 95_000);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].status, AlertStatus::Resolved);
+    }
+
+    #[test]
+    fn absent_rule_fires_for_metric_never_reported() {
+        let agg = AggregatorStore::new(10000);
+        let mem = MetricKey::new("a", "mem");
+        agg.observe(&mem, 1_000);
+
+        let eval = Evaluator::new(vec![absent_rule(60_000)]);
+        let events = eval.sweep(&agg, 62_000);
+        assert_eq!(events.len(), 1);
+        assert!(events[0].labels.is_empty());
+
+        let mut cpu = MetricKey::new("a", "cpu");
+        cpu.labels.insert("core".into(), "0".into());
+        agg.observe(&cpu, 63_000);
+        agg.ingest_series(cpu, 63_000, 5.0);
+        let events = eval.evaluate("a", &agg, 63_000);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].status, AlertStatus::Resolved);
+        assert!(events[0].labels.is_empty());
+    }
+
+    #[test]
+    fn agent_offline_follows_presence_with_grace_period() {
+        let config = crate::config::AgentOfflineConfig {
+            for_duration: std::time::Duration::from_secs(30),
+            ..Default::default()
+        };
+        let agg = AggregatorStore::new(10000);
+        let eval = Evaluator::new(vec![crate::alert::agent_offline_rule(&config)]);
+
+        assert!(eval.evaluate_presence("a", true, 1_000).is_empty());
+        assert!(eval.sweep(&agg, 20_000).is_empty());
+        let events = eval.sweep(&agg, 31_000);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].rule_id, AGENT_OFFLINE_RULE_ID);
+        assert_eq!(events[0].status, AlertStatus::Firing);
+
+        let events = eval.evaluate_presence("a", false, 40_000);
+        assert_eq!(events[0].status, AlertStatus::Resolved);
+
+        assert!(eval.evaluate_presence("b", true, 50_000).is_empty());
+        assert!(eval.evaluate_presence("b", false, 55_000).is_empty());
+        assert!(eval.sweep(&agg, 90_000).is_empty());
+    }
 }
@@ -1,4 +1,6 @@
+use sentinel_common::template::AlertContext;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 use super::rule::Severity;
 
@@ -10,6 +12,8 @@ pub struct AlertEvent {
     pub rule_name: String,
     pub agent_id: String,
     pub metric_name: String,
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
     pub severity: Severity,
     pub status: AlertStatus,
     pub value: f64,
@@ -34,10 +38,111 @@ impl AlertEvent {
         }
     }
 
+    pub fn series_name(&self) -> String {
+        if self.labels.is_empty() {
+            return self.metric_name.clone();
+        }
+        let labels: Vec<String> = self
+            .labels
+            .iter()
+            .map(|(k, v)| format!("{k}=\"{v}\""))
+            .collect();
+        format!("{}{{{}}}", self.metric_name, labels.join(","))
+    }
+
     pub fn status_str(&self) -> &str {
         match self.status {
             AlertStatus::Firing => "firing",
             AlertStatus::Resolved => "resolved",
         }
     }
+
+    pub fn template_context(&self) -> AlertContext {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        AlertContext {
+            id: self.id.clone(),
+            fingerprint: self.fingerprint.clone(),
+            rule_id: self.rule_id.clone(),
+            rule_name: self.rule_name.clone(),
+            agent_id: self.agent_id.clone(),
+            metric_name: self.metric_name.clone(),
+            series: self.series_name(),
+            labels: self.labels.clone(),
+            annotations: self
+                .annotations
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+            severity: self.severity.as_str().into(),
+            severity_short: self.severity_str().into(),
+            status: self.status_str().into(),
+            value: self.value,
+            threshold: self.threshold,
+            fired_at_ms: self.fired_at_ms,
+            resolved_at_ms: self.resolved_at_ms,
+            duration_ms: self.resolved_at_ms.unwrap_or(now_ms) - self.fired_at_ms,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(labels: &[(&str, &str)]) -> AlertEvent {
+        AlertEvent {
+            id: "e-1".into(),
+            fingerprint: "fp".into(),
+            rule_id: "r-1".into(),
+            rule_name: "disk full".into(),
+            agent_id: "a-1".into(),
+            metric_name: "disk.used_pct".into(),
+            labels: labels
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            severity: Severity::Critical,
+            status: AlertStatus::Firing,
+            value: 95.0,
+            threshold: 90.0,
+            fired_at_ms: 1000,
+            resolved_at_ms: None,
+            annotations: Default::default(),
+        }
+    }
+
+    #[test]
+    fn series_name_without_labels() {
+        assert_eq!(event(&[]).series_name(), "disk.used_pct");
+    }
+
+    #[test]
+    fn series_name_lists_labels_sorted() {
+        let e = event(&[("mount", "/data"), ("device", "sdb1")]);
+        assert_eq!(
+            e.series_name(),
+            r#"disk.used_pct{device="sdb1",mount="/data"}"#
+        );
+    }
+
+    #[test]
+    fn template_context_exposes_event_fields() {
+        use sentinel_common::template::Template;
+
+        let mut e = event(&[("mount", "/data")]);
+        e.status = AlertStatus::Resolved;
+        e.resolved_at_ms = Some(e.fired_at_ms + 125_000);
+        e.annotations
+            .insert("runbook".into(), "https://runbooks/disk".into());
+
+        let ctx = serde_json::to_value(e.template_context()).unwrap();
+        let t = Template::parse(
+            "[{{severity_short}}] {{rule_name}} {{labels.mount}} {{status}} after {{duration_ms | duration}} {{annotations.runbook}}",
+        )
+        .unwrap();
+        assert_eq!(
+            t.render(&ctx),
+            "[CRIT] disk full /data resolved after 2m 5s https://runbooks/disk"
+        );
+    }
 }
@@ -0,0 +1,295 @@
+use std::collections::BTreeMap;
+
+use sentinel_common::aggregation::Aggregation;
+use sentinel_common::expr::{BinOp, Expr, Selector};
+
+use crate::aggregator::AggregatorStore;
+
+type Labels = BTreeMap<String, String>;
+
+pub struct ExprContext<'a> {
+    pub agent_id: &'a str,
+    pub aggregator: &'a AggregatorStore,
+    pub aggregation: Aggregation,
+    pub window_ms: i64,
+    pub min_samples: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExprResult {
+    pub labels: Labels,
+    pub value: f64,
+    pub threshold: Option<f64>,
+    pub firing: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    value: f64,
+    threshold: Option<f64>,
+    truth: Option<bool>,
+}
+
+impl Sample {
+    fn plain(value: f64) -> Self {
+        Self {
+            value,
+            threshold: None,
+            truth: None,
+        }
+    }
+
+    fn is_true(&self) -> bool {
+        self.truth.unwrap_or(self.value != 0.0)
+    }
+}
+
+enum Value {
+    Scalar(Sample),
+    Vector(BTreeMap<Labels, Sample>),
+}
+
+pub fn evaluate(expr: &Expr, ctx: &ExprContext) -> Vec<ExprResult> {
+    let into_result = |labels: Labels, s: Sample| ExprResult {
+        labels,
+        value: s.value,
+        threshold: s.threshold,
+        firing: s.is_true(),
+    };
+    match eval(expr, ctx) {
+        Value::Scalar(s) if s.value.is_finite() => vec![into_result(Labels::new(), s)],
+        Value::Scalar(_) => Vec::new(),
+        Value::Vector(v) => v.into_iter().map(|(l, s)| into_result(l, s)).collect(),
+    }
+}
+
+fn eval(expr: &Expr, ctx: &ExprContext) -> Value {
+    match expr {
+        Expr::Number(n) => Value::Scalar(Sample::plain(*n)),
+        Expr::Series(sel) => reduce(sel, ctx.aggregation, ctx.window_ms, ctx),
+        Expr::Reduce {
+            agg,
+            selector,
+            window_ms,
+        } => reduce(selector, *agg, window_ms.unwrap_or(ctx.window_ms), ctx),
+        Expr::Abs(e) => map(eval(e, ctx), |s| Some(Sample::plain(s.value.abs()))),
+        Expr::Neg(e) => map(eval(e, ctx), |s| Some(Sample::plain(-s.value))),
+        Expr::Binary { op, lhs, rhs } => {
+            let (lhs, rhs) = (eval(lhs, ctx), eval(rhs, ctx));
+            combine(*op, lhs, rhs)
+        }
+    }
+}
+
+fn reduce(sel: &Selector, agg: Aggregation, window_ms: i64, ctx: &ExprContext) -> Value {
+    let series = ctx
+        .aggregator
+        .series_keys(ctx.agent_id, &sel.metric)
+        .into_iter()
+        .filter(|key| sel.matches(&key.labels))
+        .filter(|key| ctx.aggregator.series_count_within(key, window_ms) >= ctx.min_samples)
+        .filter_map(|key| {
+            let value = ctx.aggregator.series_reduce(&key, agg, window_ms)?;
+            Some((key.labels, Sample::plain(value)))
+        })
+        .collect();
+    Value::Vector(series)
+}
+
+fn map(value: Value, f: impl Fn(Sample) -> Option<Sample>) -> Value {
+    match value {
+        Value::Scalar(s) => f(s)
+            .map(Value::Scalar)
+            .unwrap_or(Value::Vector(BTreeMap::new())),
+        Value::Vector(v) => Value::Vector(
+            v.into_iter()
+                .filter_map(|(l, s)| Some((l, f(s)?)))
+                .collect(),
+        ),
+    }
+}
+
+fn combine(op: BinOp, lhs: Value, rhs: Value) -> Value {
+    match (lhs, rhs) {
+        (Value::Scalar(a), Value::Scalar(b)) => map(Value::Scalar(a), |a| apply(op, a, b)),
+        (Value::Vector(a), Value::Scalar(b)) => map(Value::Vector(a), |a| apply(op, a, b)),
+        (Value::Scalar(a), Value::Vector(b)) => map(Value::Vector(b), |b| apply(op, a, b)),
+        (Value::Vector(mut a), Value::Vector(mut b)) => {
+            let mut out = BTreeMap::new();
+            let keys: Vec<Labels> = a.keys().chain(b.keys()).cloned().collect();
+            for labels in keys {
+                let sample = match (a.remove(&labels), b.remove(&labels)) {
+                    (Some(x), Some(y)) => apply(op, x, y),
+                    (Some(x), None) | (None, Some(x)) if op == BinOp::Or => Some(x),
+                    _ => None,
+                };
+                if let Some(s) = sample {
+                    out.insert(labels, s);
+                }
+            }
+            Value::Vector(out)
+        }
+    }
+}
+
+fn apply(op: BinOp, a: Sample, b: Sample) -> Option<Sample> {
+    let compare = |truth: bool| Sample {
+        value: a.value,
+        threshold: Some(b.value),
+        truth: Some(truth),
+    };
+    let logical = |truth: bool| {
+        let side = if op == BinOp::Or && !a.is_true() {
+            b
+        } else {
+            a
+        };
+        Sample {
+            truth: Some(truth),
+            ..side
+        }
+    };
+    let sample = match op {
+        BinOp::Add => Sample::plain(a.value + b.value),
+        BinOp::Sub => Sample::plain(a.value - b.value),
+        BinOp::Mul => Sample::plain(a.value * b.value),
+        BinOp::Div if b.value == 0.0 => return None,
+        BinOp::Div => Sample::plain(a.value / b.value),
+        BinOp::Mod if b.value == 0.0 => return None,
+        BinOp::Mod => Sample::plain(a.value % b.value),
+        BinOp::Gt => compare(a.value > b.value),
+        BinOp::Lt => compare(a.value < b.value),
+        BinOp::Ge => compare(a.value >= b.value),
+        BinOp::Le => compare(a.value <= b.value),
+        BinOp::Eq => compare((a.value - b.value).abs() < f64::EPSILON),
+        BinOp::Ne => compare((a.value - b.value).abs() >= f64::EPSILON),
+        BinOp::And => logical(a.is_true() && b.is_true()),
+        BinOp::Or => logical(a.is_true() || b.is_true()),
+    };
+    sample.value.is_finite().then_some(sample)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregator::MetricKey;
+    use sentinel_common::expr::parse;
+
+    fn ctx(agg: &AggregatorStore) -> ExprContext<'_> {
+        ExprContext {
+            agent_id: "a",
+            aggregator: agg,
+            aggregation: Aggregation::Avg,
+            window_ms: 60_000,
+            min_samples: 1,
+        }
+    }
+
+    fn run(agg: &AggregatorStore, src: &str) -> Vec<ExprResult> {
+        evaluate(&parse(src).unwrap(), &ctx(agg))
+    }
+
+    fn disk(agg: &AggregatorStore, metric: &str, mount: &str, value: f64) {
+        let mut key = MetricKey::new("a", metric);
+        key.labels.insert("mount".into(), mount.into());
+        agg.ingest_series(key, 1000, value);
+    }
+
+    #[test]
+    fn ratio_of_two_metrics() {
+        let agg = AggregatorStore::new(60_000);
+        agg.ingest("a", "mem.used_bytes", 1000, 950.0);
+        agg.ingest("a", "mem.total_bytes", 1000, 1000.0);
+
+        let results = run(&agg, "mem.used_bytes / mem.total_bytes > 0.9");
+        assert_eq!(results.len(), 1);
+        assert!(results[0].firing);
+        assert_eq!(results[0].value, 0.95);
+        assert_eq!(results[0].threshold, Some(0.9));
+
+        assert!(!run(&agg, "mem.used_bytes / mem.total_bytes > 0.99")[0].firing);
+    }
+
+    #[test]
+    fn vectors_join_on_labels() {
+        let agg = AggregatorStore::new(60_000);
+        disk(&agg, "disk.available_bytes", "/", 3.0);
+        disk(&agg, "disk.total_bytes", "/", 100.0);
+        disk(&agg, "disk.available_bytes", "/data", 50.0);
+        disk(&agg, "disk.total_bytes", "/data", 100.0);
+        disk(&agg, "disk.available_bytes", "/tmp", 1.0);
+
+        let results = run(&agg, "disk.available_bytes < 0.05 * disk.total_bytes");
+        assert_eq!(results.len(), 2);
+        let firing: Vec<&str> = results
+            .iter()
+            .filter(|r| r.firing)
+            .map(|r| r.labels["mount"].as_str())
+            .collect();
+        assert_eq!(firing, vec!["/"]);
+
+        let filtered = run(&agg, r#"disk.available_bytes{mount!="/"} < 10"#);
+        assert_eq!(filtered.len(), 2);
+    }
+
+    #[test]
+    fn logical_operators() {
+        let agg = AggregatorStore::new(60_000);
+        agg.ingest("a", "cpu", 1000, 95.0);
+        agg.ingest("a", "load", 1000, 2.0);
+
+        assert!(run(&agg, "cpu > 90 and load > 1")[0].firing);
+        assert!(!run(&agg, "cpu > 90 and load > 4")[0].firing);
+        assert!(run(&agg, "cpu > 99 or load > 1")[0].firing);
+        assert!(!run(&agg, "cpu > 99 || load > 4")[0].firing);
+        assert_eq!(run(&agg, "cpu > 99 or load > 1")[0].value, 2.0);
+    }
+
+    #[test]
+    fn or_keeps_one_sided_series() {
+        let agg = AggregatorStore::new(60_000);
+        agg.ingest("a", "cpu", 1000, 95.0);
+
+        assert!(run(&agg, "cpu > 90 or missing > 1")[0].firing);
+        assert!(run(&agg, "cpu > 90 and missing > 1").is_empty());
+    }
+
+    #[test]
+    fn functions_use_their_own_window() {
+        let agg = AggregatorStore::new(600_000);
+        for (ts, v) in [
+            (0, 0.0),
+            (60_000, 600.0),
+            (120_000, 1200.0),
+            (180_000, 1300.0),
+        ] {
+            agg.ingest("a", "net.rx_bytes", ts, v);
+        }
+
+        let rate = run(&agg, "rate(net.rx_bytes, 1m)");
+        assert_eq!(rate[0].value, 100.0 / 60.0);
+        let rate = run(&agg, "rate(net.rx_bytes, 10m)");
+        assert!((rate[0].value - 1300.0 / 180.0).abs() < 1e-9);
+
+        assert_eq!(run(&agg, "avg_over(net.rx_bytes, 1m)")[0].value, 1250.0);
+        assert_eq!(run(&agg, "max_over(net.rx_bytes)")[0].value, 1300.0);
+        assert_eq!(
+            run(&agg, "abs(-increase(net.rx_bytes, 2m))")[0].value,
+            700.0
+        );
+    }
+
+    #[test]
+    fn division_by_zero_and_min_samples_drop_series() {
+        let agg = AggregatorStore::new(60_000);
+        agg.ingest("a", "used", 1000, 5.0);
+        agg.ingest("a", "total", 1000, 0.0);
+        assert!(run(&agg, "used / total > 0.5").is_empty());
+
+        let strict = ExprContext {
+            min_samples: 2,
+            ..ctx(&agg)
+        };
+        assert!(evaluate(&parse("used > 1").unwrap(), &strict).is_empty());
+    }
+}
@@ -1,17 +1,63 @@
-use std::hash::{Hash, Hasher};
+use std::collections::BTreeMap;
+
+use sha2::{Digest, Sha256};
 
 pub fn fingerprint(rule_id: &str, agent_id: &str, metric_name: &str) -> u64 {
-    let mut hasher = std::hash::DefaultHasher::new();
-    rule_id.hash(&mut hasher);
-    agent_id.hash(&mut hasher);
-    metric_name.hash(&mut hasher);
-    hasher.finish()
+    let mut hasher = Sha256::new();
+    update_field(&mut hasher, rule_id);
+    update_field(&mut hasher, agent_id);
+    update_field(&mut hasher, metric_name);
+    finish(hasher)
 }
 
 pub fn fingerprint_string(rule_id: &str, agent_id: &str, metric_name: &str) -> String {
     format!("{:016x}", fingerprint(rule_id, agent_id, metric_name))
 }
 
+pub fn series_fingerprint(
+    rule_id: &str,
+    agent_id: &str,
+    metric_name: &str,
+    labels: &BTreeMap<String, String>,
+) -> u64 {
+    if labels.is_empty() {
+        return fingerprint(rule_id, agent_id, metric_name);
+    }
+    let mut hasher = Sha256::new();
+    update_field(&mut hasher, rule_id);
+    update_field(&mut hasher, agent_id);
+    update_field(&mut hasher, metric_name);
+    for (key, value) in labels {
+        update_field(&mut hasher, key);
+        update_field(&mut hasher, value);
+    }
+    finish(hasher)
+}
+
+pub fn series_fingerprint_string(
+    rule_id: &str,
+    agent_id: &str,
+    metric_name: &str,
+    labels: &BTreeMap<String, String>,
+) -> String {
+    format!(
+        "{:016x}",
+        series_fingerprint(rule_id, agent_id, metric_name, labels)
+    )
+}
+
+fn update_field(hasher: &mut Sha256, field: &str) {
+    hasher.update((field.len() as u64).to_be_bytes());
+    hasher.update(field.as_bytes());
+}
+
+fn finish(hasher: Sha256) -> u64 {
+    let digest = hasher.finalize();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(bytes)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -23,6 +69,19 @@ mod tests {
         assert_eq!(a, b);
     }
 
+    #[test]
+    fn stable_across_builds() {
+        assert_eq!(
+            fingerprint_string("r1", "agent-1", "cpu"),
+            "9827337ceb95d11b"
+        );
+    }
+
+    #[test]
+    fn field_boundaries_matter() {
+        assert_ne!(fingerprint("r1", "ab", "c"), fingerprint("r1", "a", "bc"));
+    }
+
     #[test]
     fn different_inputs_different_fingerprint() {
         let a = fingerprint("r1", "agent-1", "cpu");
@@ -36,4 +95,22 @@ mod tests {
         assert_eq!(s.len(), 16);
         assert!(s.chars().all(|c| c.is_ascii_hexdigit()));
     }
+
+    #[test]
+    fn unlabelled_series_keeps_legacy_fingerprint() {
+        assert_eq!(
+            series_fingerprint("r1", "a1", "cpu", &BTreeMap::new()),
+            fingerprint("r1", "a1", "cpu")
+        );
+    }
+
+    #[test]
+    fn labels_distinguish_series() {
+        let root = BTreeMap::from([("mount".to_string(), "/".to_string())]);
+        let data = BTreeMap::from([("mount".to_string(), "/data".to_string())]);
+        assert_ne!(
+            series_fingerprint("r1", "a1", "disk", &root),
+            series_fingerprint("r1", "a1", "disk", &data)
+        );
+    }
 }
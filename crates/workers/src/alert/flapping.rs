@@ -0,0 +1,115 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Mutex;
+
+use sentinel_common::flapping::FlapPolicy;
+
+use super::event::{AlertEvent, AlertStatus};
+
+pub const FLAPPING_REASON: &str = "flapping";
+
+pub struct FlapDetector {
+    policy: FlapPolicy,
+    inner: Mutex<FlapState>,
+}
+
+#[derive(Default)]
+struct FlapState {
+    changes: HashMap<String, VecDeque<i64>>,
+    muted: HashSet<String>,
+}
+
+impl FlapDetector {
+    pub fn new(policy: FlapPolicy) -> Self {
+        Self {
+            policy,
+            inner: Mutex::new(FlapState::default()),
+        }
+    }
+
+    pub fn observe(&self, event: &AlertEvent, now_ms: i64) -> bool {
+        let window_start = now_ms - self.policy.window_ms();
+        let mut state = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let FlapState { changes, muted } = &mut *state;
+
+        changes.retain(|fp, times| {
+            while times.front().is_some_and(|t| *t <= window_start) {
+                times.pop_front();
+            }
+            !times.is_empty() || muted.contains(fp)
+        });
+        let times = changes.entry(event.fingerprint.clone()).or_default();
+        times.push_back(now_ms);
+
+        match event.status {
+            AlertStatus::Firing => {
+                let flapping = times.len() > self.policy.threshold;
+                if flapping {
+                    muted.insert(event.fingerprint.clone());
+                } else {
+                    muted.remove(&event.fingerprint);
+                }
+                flapping
+            }
+            AlertStatus::Resolved => muted.remove(&event.fingerprint),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alert::Severity;
+
+    fn event(fp: &str, status: AlertStatus) -> AlertEvent {
+        AlertEvent {
+            id: "e".into(),
+            fingerprint: fp.into(),
+            rule_id: "r-1".into(),
+            rule_name: "cpu".into(),
+            agent_id: "a-1".into(),
+            metric_name: "cpu".into(),
+            labels: Default::default(),
+            severity: Severity::Warning,
+            status,
+            value: 0.0,
+            threshold: 0.0,
+            fired_at_ms: 0,
+            resolved_at_ms: None,
+            annotations: Default::default(),
+        }
+    }
+
+    fn flip(detector: &FlapDetector, fp: &str, i: i64) -> bool {
+        let status = if i % 2 == 0 {
+            AlertStatus::Firing
+        } else {
+            AlertStatus::Resolved
+        };
+        detector.observe(&event(fp, status), i * 1000)
+    }
+
+    #[test]
+    fn mutes_after_threshold_and_pairs_resolve() {
+        let detector = FlapDetector::new(FlapPolicy {
+            window_secs: 60,
+            threshold: 3,
+        });
+        let muted: Vec<bool> = (0..8).map(|i| flip(&detector, "fp", i)).collect();
+        assert_eq!(muted, [false, false, false, false, true, true, true, true]);
+        assert!(!flip(&detector, "other", 0));
+    }
+
+    #[test]
+    fn unmutes_once_changes_leave_window() {
+        let detector = FlapDetector::new(FlapPolicy {
+            window_secs: 10,
+            threshold: 2,
+        });
+        for i in 0..4 {
+            flip(&detector, "fp", i);
+        }
+        assert!(flip(&detector, "fp", 4));
+        assert!(flip(&detector, "fp", 5));
+        assert!(!detector.observe(&event("fp", AlertStatus::Firing), 60_000));
+    }
+}
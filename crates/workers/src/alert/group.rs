@@ -0,0 +1,221 @@
+use sentinel_common::grouping::GroupPolicy;
+use sentinel_common::template::GroupContext;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+use super::event::{AlertEvent, AlertStatus};
+use super::rule::Severity;
+
+const MAX_BODY_LINES: usize = 20;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertGroup {
+    pub key: String,
+    pub labels: BTreeMap<String, String>,
+    pub alerts: Vec<AlertEvent>,
+    #[serde(default)]
+    pub repeat: bool,
+}
+
+impl AlertGroup {
+    pub fn labels_for(policy: &GroupPolicy, event: &AlertEvent) -> BTreeMap<String, String> {
+        policy
+            .group_by
+            .iter()
+            .map(|k| {
+                let value = match k.as_str() {
+                    "rule_id" => event.rule_id.clone(),
+                    "agent_id" => event.agent_id.clone(),
+                    "severity" => event.severity.as_str().to_string(),
+                    "metric_name" => event.metric_name.clone(),
+                    label => event.labels.get(label).cloned().unwrap_or_default(),
+                };
+                (k.clone(), value)
+            })
+            .collect()
+    }
+
+    pub fn key_for(labels: &BTreeMap<String, String>) -> String {
+        labels
+            .iter()
+            .map(|(k, v)| format!("{k}={v}"))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    pub fn dedup_key(&self) -> String {
+        let digest = Sha256::digest(self.key.as_bytes());
+        let hex: String = digest[..8].iter().map(|b| format!("{b:02x}")).collect();
+        format!("group-{hex}")
+    }
+
+    pub fn firing_count(&self) -> usize {
+        self.alerts
+            .iter()
+            .filter(|a| a.status == AlertStatus::Firing)
+            .count()
+    }
+
+    pub fn resolved_count(&self) -> usize {
+        self.alerts.len() - self.firing_count()
+    }
+
+    pub fn status(&self) -> AlertStatus {
+        if self.firing_count() > 0 {
+            AlertStatus::Firing
+        } else {
+            AlertStatus::Resolved
+        }
+    }
+
+    pub fn status_str(&self) -> &str {
+        match self.status() {
+            AlertStatus::Firing => "firing",
+            AlertStatus::Resolved => "resolved",
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        let status = self.status();
+        self.alerts
+            .iter()
+            .filter(|a| a.status == status)
+            .map(|a| a.severity)
+            .max_by_key(|s| rank(*s))
+            .unwrap_or(Severity::Info)
+    }
+
+    pub fn severity_str(&self) -> &str {
+        match self.severity() {
+            Severity::Info => "INFO",
+            Severity::Warning => "WARN",
+            Severity::Critical => "CRIT",
+        }
+    }
+
+    pub fn name(&self) -> String {
+        let first = self.alerts.first().map(|a| a.rule_name.as_str());
+        match first {
+            Some(name) if self.alerts.iter().all(|a| a.rule_name == name) => name.to_string(),
+            _ => self.key.clone(),
+        }
+    }
+
+    pub fn title(&self) -> String {
+        let firing = self.firing_count();
+        let state = if firing > 0 {
+            format!("FIRING:{firing}")
+        } else {
+            "RESOLVED".to_string()
+        };
+        format!("[{}] {}", state, self.name())
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .alerts
+            .iter()
+            .take(MAX_BODY_LINES)
+            .map(|a| {
+                format!(
+                    "{} {} on {}: {:.2} (threshold {:.2})",
+                    a.status_str(),
+                    a.series_name(),
+                    a.agent_id,
+                    a.value,
+                    a.threshold
+                )
+            })
+            .collect();
+        if self.alerts.len() > MAX_BODY_LINES {
+            lines.push(format!(
+                "... and {} more",
+                self.alerts.len() - MAX_BODY_LINES
+            ));
+        }
+        lines
+    }
+
+    pub fn body(&self) -> String {
+        self.lines().join("\n")
+    }
+
+    pub fn template_context(&self) -> GroupContext {
+        GroupContext {
+            group_key: self.key.clone(),
+            group_labels: self.labels.clone(),
+            status: self.status_str().into(),
+            severity: self.severity().as_str().into(),
+            firing_count: self.firing_count(),
+            resolved_count: self.resolved_count(),
+            repeat: self.repeat,
+            alerts: self.alerts.iter().map(|a| a.template_context()).collect(),
+        }
+    }
+}
+
+fn rank(severity: Severity) -> u8 {
+    match severity {
+        Severity::Info => 0,
+        Severity::Warning => 1,
+        Severity::Critical => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(agent: &str, mount: &str, severity: Severity, status: AlertStatus) -> AlertEvent {
+        AlertEvent {
+            id: format!("e-{agent}-{mount}"),
+            fingerprint: format!("fp-{agent}-{mount}"),
+            rule_id: "r-1".into(),
+            rule_name: "disk full".into(),
+            agent_id: agent.into(),
+            metric_name: "disk.used_pct".into(),
+            labels: BTreeMap::from([("mount".to_string(), mount.to_string())]),
+            severity,
+            status,
+            value: 95.0,
+            threshold: 90.0,
+            fired_at_ms: 1000,
+            resolved_at_ms: None,
+            annotations: Default::default(),
+        }
+    }
+
+    #[test]
+    fn key_uses_policy_fields_and_labels() {
+        let policy = GroupPolicy {
+            group_by: vec!["rule_id".into(), "mount".into(), "team".into()],
+            ..Default::default()
+        };
+        let e = event("a-1", "/", Severity::Warning, AlertStatus::Firing);
+        let labels = AlertGroup::labels_for(&policy, &e);
+        assert_eq!(AlertGroup::key_for(&labels), "mount=/,rule_id=r-1,team=");
+    }
+
+    #[test]
+    fn summary_reflects_members() {
+        let group = AlertGroup {
+            key: "rule_id=r-1".into(),
+            labels: BTreeMap::from([("rule_id".to_string(), "r-1".to_string())]),
+            alerts: vec![
+                event("a-1", "/", Severity::Warning, AlertStatus::Firing),
+                event("a-2", "/", Severity::Critical, AlertStatus::Firing),
+                event("a-3", "/", Severity::Critical, AlertStatus::Resolved),
+            ],
+            repeat: false,
+        };
+        assert_eq!(group.title(), "[FIRING:2] disk full");
+        assert_eq!(group.resolved_count(), 1);
+        assert_eq!(group.severity(), Severity::Critical);
+        assert_eq!(group.lines().len(), 3);
+
+        let ctx = group.template_context();
+        assert_eq!(ctx.alerts.len(), 3);
+        assert_eq!(ctx.status, "firing");
+    }
+}
@@ -1,15 +1,33 @@
+mod builtin;
+mod escalation;
 mod evaluator;
 mod event;
+mod expr;
 mod fingerprint;
+mod flapping;
+mod group;
 mod rule;
 mod state;
+mod state_store;
 mod store;
+mod suppression;
 #[cfg(test)]
 pub mod test_harness;
 
+pub use builtin::{agent_offline_rule, is_builtin, AGENT_OFFLINE_RULE_ID, AGENT_UP_METRIC};
+pub use escalation::{
+    plan_start, plan_step, DueEscalation, EscalationPlan, EscalationStore, Escalations,
+};
 pub use evaluator::Evaluator;
 pub use event::{AlertEvent, AlertStatus};
-pub use fingerprint::{fingerprint, fingerprint_string};
-pub use rule::{Condition, Rule, Severity};
-pub use state::RuleState;
+pub use expr::{ExprContext, ExprResult};
+pub use fingerprint::{
+    fingerprint, fingerprint_string, series_fingerprint, series_fingerprint_string,
+};
+pub use flapping::{FlapDetector, FLAPPING_REASON};
+pub use group::AlertGroup;
+pub use rule::{Condition, Rule, Severity, DEFAULT_MIN_SAMPLES, DEFAULT_WINDOW_MS};
+pub use state::{RuleState, StateChange, TrackedState};
+pub use state_store::AlertStateStore;
 pub use store::AlertStore;
+pub use suppression::{SuppressionStore, Suppressions};
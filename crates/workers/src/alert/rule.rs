@@ -1,4 +1,11 @@
+use sentinel_common::aggregation::Aggregation;
+use sentinel_common::grouping::GroupPolicy;
+use sentinel_common::labels::LabelMatcher;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+pub const DEFAULT_WINDOW_MS: i64 = 120_000;
+pub const DEFAULT_MIN_SAMPLES: usize = 2;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Rule {
@@ -6,13 +13,55 @@ pub struct Rule {
     pub name: String,
     pub agent_pattern: String,
     pub metric_name: String,
+    #[serde(default)]
+    pub label_matchers: Vec<LabelMatcher>,
+    #[serde(default)]
+    pub expression: Option<String>,
+    #[serde(default)]
+    pub absent_for_ms: Option<i64>,
     pub condition: Condition,
     pub threshold: f64,
+    #[serde(default)]
+    pub aggregation: Aggregation,
+    #[serde(default = "default_window_ms")]
+    pub window_ms: i64,
+    #[serde(default = "default_min_samples")]
+    pub min_samples: usize,
     pub for_duration_ms: i64,
     pub severity: Severity,
     pub annotations: std::collections::HashMap<String, String>,
     #[serde(default)]
     pub notifier_ids: Vec<String>,
+    #[serde(default)]
+    pub grouping: Option<GroupPolicy>,
+    #[serde(default)]
+    pub escalation_policy_id: Option<String>,
+}
+
+impl Rule {
+    pub fn series_name(&self) -> &str {
+        self.expression.as_deref().unwrap_or(&self.metric_name)
+    }
+
+    pub fn revision(&self) -> String {
+        let selection = serde_json::json!([
+            self.agent_pattern,
+            self.metric_name,
+            self.label_matchers,
+            self.expression,
+            self.absent_for_ms.is_some(),
+        ]);
+        let digest = Sha256::digest(selection.to_string().as_bytes());
+        digest[..8].iter().map(|b| format!("{b:02x}")).collect()
+    }
+}
+
+fn default_window_ms() -> i64 {
+    DEFAULT_WINDOW_MS
+}
+
+fn default_min_samples() -> usize {
+    DEFAULT_MIN_SAMPLES
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
@@ -31,6 +80,16 @@ pub enum Severity {
     Critical,
 }
 
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Critical => "critical",
+        }
+    }
+}
+
 impl Condition {
     pub fn evaluate(&self, value: f64, threshold: f64) -> bool {
         match self {
@@ -65,6 +124,60 @@ mod tests {
         assert!(!Condition::Equal.evaluate(5.1, 5.0));
     }
 
+    fn rule() -> Rule {
+        Rule {
+            id: "r-1".into(),
+            name: "cpu".into(),
+            agent_pattern: "*".into(),
+            metric_name: "cpu".into(),
+            label_matchers: Vec::new(),
+            expression: None,
+            absent_for_ms: None,
+            condition: Condition::GreaterThan,
+            threshold: 80.0,
+            aggregation: Aggregation::Avg,
+            window_ms: DEFAULT_WINDOW_MS,
+            min_samples: DEFAULT_MIN_SAMPLES,
+            for_duration_ms: 0,
+            severity: Severity::Warning,
+            annotations: std::collections::HashMap::new(),
+            notifier_ids: Vec::new(),
+            grouping: None,
+            escalation_policy_id: None,
+        }
+    }
+
+    #[test]
+    fn revision_tracks_series_selection_only() {
+        let base = rule();
+        let retuned = Rule {
+            name: "renamed".into(),
+            severity: Severity::Critical,
+            notifier_ids: vec!["n-1".into()],
+            threshold: 90.0,
+            for_duration_ms: 60_000,
+            ..rule()
+        };
+        assert_eq!(base.revision(), retuned.revision());
+        assert_eq!(base.revision().len(), 16);
+
+        let narrowed = Rule {
+            label_matchers: vec![LabelMatcher::parse("mount=/").unwrap()],
+            ..rule()
+        };
+        assert_ne!(base.revision(), narrowed.revision());
+        let renamed_metric = Rule {
+            metric_name: "load".into(),
+            ..rule()
+        };
+        assert_ne!(base.revision(), renamed_metric.revision());
+        let absent = Rule {
+            absent_for_ms: Some(60_000),
+            ..rule()
+        };
+        assert_ne!(base.revision(), absent.revision());
+    }
+
     #[test]
     fn condition_boundaries() {
         assert!(Condition::GreaterOrEqual.evaluate(5.0, 5.0));
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum RuleState {
@@ -8,6 +9,21 @@ pub enum RuleState {
     Resolved { at_ms: i64 },
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrackedState {
+    pub rule_id: String,
+    pub revision: String,
+    pub agent_id: String,
+    pub labels: BTreeMap<String, String>,
+    pub state: RuleState,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateChange {
+    pub fingerprint: String,
+    pub state: Option<TrackedState>,
+}
+
 impl RuleState {
     pub fn transition(self, condition_met: bool, now_ms: i64, for_duration_ms: i64) -> Self {
         match (self, condition_met) {
@@ -0,0 +1,102 @@
+use sqlx::PgPool;
+
+use super::state::{StateChange, TrackedState};
+
+pub struct AlertStateStore {
+    pool: PgPool,
+}
+
+impl AlertStateStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn load_all(&self) -> Result<Vec<(String, TrackedState)>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, StateRow>(
+            "SELECT fingerprint, rule_id, rule_revision, agent_id, labels, state FROM alert_states",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|r| {
+                let state = serde_json::from_value(r.state).ok()?;
+                let labels = serde_json::from_value(r.labels).unwrap_or_default();
+                Some((
+                    r.fingerprint,
+                    TrackedState {
+                        rule_id: r.rule_id,
+                        revision: r.rule_revision,
+                        agent_id: r.agent_id,
+                        labels,
+                        state,
+                    },
+                ))
+            })
+            .collect())
+    }
+
+    pub async fn apply(&self, changes: &[StateChange]) -> Result<(), sqlx::Error> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let mut tx = self.pool.begin().await?;
+        for change in changes {
+            match &change.state {
+                Some(tracked) => {
+                    let state = serde_json::to_value(tracked.state).unwrap_or_default();
+                    let labels = serde_json::to_value(&tracked.labels).unwrap_or_default();
+                    sqlx::query(
+                        r#"INSERT INTO alert_states
+                           (fingerprint, rule_id, rule_revision, agent_id, labels, state, updated_at)
+                           VALUES ($1, $2, $3, $4, $5, $6, NOW())
+                           ON CONFLICT (fingerprint) DO UPDATE SET
+                             rule_id = EXCLUDED.rule_id,
+                             rule_revision = EXCLUDED.rule_revision,
+                             agent_id = EXCLUDED.agent_id,
+                             labels = EXCLUDED.labels,
+                             state = EXCLUDED.state,
+                             updated_at = NOW()"#,
+                    )
+                    .bind(&change.fingerprint)
+                    .bind(&tracked.rule_id)
+                    .bind(&tracked.revision)
+                    .bind(&tracked.agent_id)
+                    .bind(&labels)
+                    .bind(&state)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+                None => {
+                    sqlx::query("DELETE FROM alert_states WHERE fingerprint = $1")
+                        .bind(&change.fingerprint)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+            }
+        }
+        tx.commit().await
+    }
+
+    pub async fn delete(&self, fingerprints: &[String]) -> Result<u64, sqlx::Error> {
+        if fingerprints.is_empty() {
+            return Ok(0);
+        }
+        let result = sqlx::query("DELETE FROM alert_states WHERE fingerprint = ANY($1)")
+            .bind(fingerprints)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct StateRow {
+    fingerprint: String,
+    rule_id: String,
+    rule_revision: String,
+    agent_id: String,
+    labels: serde_json::Value,
+    state: serde_json::Value,
+}
@@ -0,0 +1,201 @@
+use std::collections::HashSet;
+
+use sentinel_common::labels::LabelMatcher;
+use sentinel_common::silence::{Recurrence, Silence, SilenceTarget};
+use sqlx::PgPool;
+
+use super::event::{AlertEvent, AlertStatus};
+
+pub struct SuppressionStore {
+    pool: PgPool,
+}
+
+#[derive(Default)]
+pub struct Suppressions {
+    pub silences: Vec<Silence>,
+    pub acked: HashSet<String>,
+}
+
+impl SuppressionStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn load(&self, events: &[AlertEvent]) -> Result<Suppressions, sqlx::Error> {
+        let rows = sqlx::query_as::<_, SilenceRow>(
+            "SELECT id, agent_pattern, rule_id, severity, label_matchers,
+                    (EXTRACT(EPOCH FROM starts_at) * 1000)::bigint AS starts_at_ms,
+                    (EXTRACT(EPOCH FROM ends_at) * 1000)::bigint AS ends_at_ms,
+                    recurrence, comment, created_by
+             FROM silences
+             WHERE starts_at <= NOW() AND (ends_at IS NULL OR ends_at > NOW())",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let fingerprints: Vec<String> = events
+            .iter()
+            .filter(|e| e.status == AlertStatus::Firing)
+            .map(|e| e.fingerprint.clone())
+            .collect();
+        let acked: Vec<String> = if fingerprints.is_empty() {
+            Vec::new()
+        } else {
+            sqlx::query_scalar("SELECT fingerprint FROM alert_acks WHERE fingerprint = ANY($1)")
+                .bind(&fingerprints)
+                .fetch_all(&self.pool)
+                .await?
+        };
+
+        Ok(Suppressions {
+            silences: rows.into_iter().map(Into::into).collect(),
+            acked: acked.into_iter().collect(),
+        })
+    }
+
+    pub async fn clear_acks(&self, fingerprints: &[String]) -> Result<(), sqlx::Error> {
+        if fingerprints.is_empty() {
+            return Ok(());
+        }
+        sqlx::query("DELETE FROM alert_acks WHERE fingerprint = ANY($1)")
+            .bind(fingerprints)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+impl Suppressions {
+    pub fn reason(&self, event: &AlertEvent, now_ms: i64) -> Option<String> {
+        let target = SilenceTarget {
+            agent_id: &event.agent_id,
+            rule_id: &event.rule_id,
+            severity: event.severity.as_str(),
+            labels: &event.labels,
+        };
+        if let Some(s) = self
+            .silences
+            .iter()
+            .find(|s| s.is_active(now_ms) && s.matches(&target))
+        {
+            return Some(format!("silence:{}", s.id));
+        }
+        if event.status == AlertStatus::Firing && self.acked.contains(&event.fingerprint) {
+            return Some("ack".into());
+        }
+        None
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct SilenceRow {
+    id: String,
+    agent_pattern: Option<String>,
+    rule_id: Option<String>,
+    severity: Option<String>,
+    label_matchers: serde_json::Value,
+    starts_at_ms: i64,
+    ends_at_ms: Option<i64>,
+    recurrence: Option<serde_json::Value>,
+    comment: String,
+    created_by: String,
+}
+
+impl From<SilenceRow> for Silence {
+    fn from(r: SilenceRow) -> Self {
+        let label_matchers: Vec<LabelMatcher> =
+            serde_json::from_value(r.label_matchers).unwrap_or_default();
+        let recurrence: Option<Recurrence> =
+            r.recurrence.and_then(|v| serde_json::from_value(v).ok());
+        Self {
+            id: r.id,
+            agent_pattern: r.agent_pattern,
+            rule_id: r.rule_id,
+            severity: r.severity,
+            label_matchers,
+            starts_at_ms: r.starts_at_ms,
+            ends_at_ms: r.ends_at_ms,
+            recurrence,
+            comment: r.comment,
+            created_by: r.created_by,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alert::Severity;
+    use std::collections::{BTreeMap, HashMap};
+
+    fn event(agent_id: &str, status: AlertStatus) -> AlertEvent {
+        AlertEvent {
+            id: "a1".into(),
+            fingerprint: format!("fp-{agent_id}"),
+            rule_id: "r1".into(),
+            rule_name: "cpu".into(),
+            agent_id: agent_id.into(),
+            metric_name: "cpu.usage".into(),
+            labels: BTreeMap::new(),
+            severity: Severity::Critical,
+            status,
+            value: 95.0,
+            threshold: 90.0,
+            fired_at_ms: 0,
+            resolved_at_ms: None,
+            annotations: HashMap::new(),
+        }
+    }
+
+    fn silence(agent_pattern: &str) -> Silence {
+        Silence {
+            id: "s1".into(),
+            agent_pattern: Some(agent_pattern.into()),
+            rule_id: None,
+            severity: None,
+            label_matchers: Vec::new(),
+            starts_at_ms: 0,
+            ends_at_ms: Some(1_000),
+            recurrence: None,
+            comment: String::new(),
+            created_by: String::new(),
+        }
+    }
+
+    #[test]
+    fn active_silence_suppresses_matching_events() {
+        let s = Suppressions {
+            silences: vec![silence("web-*")],
+            acked: HashSet::new(),
+        };
+        assert_eq!(
+            s.reason(&event("web-1", AlertStatus::Firing), 500)
+                .as_deref(),
+            Some("silence:s1")
+        );
+        assert_eq!(
+            s.reason(&event("web-1", AlertStatus::Resolved), 500)
+                .as_deref(),
+            Some("silence:s1")
+        );
+        assert!(s.reason(&event("db-1", AlertStatus::Firing), 500).is_none());
+        assert!(s
+            .reason(&event("web-1", AlertStatus::Firing), 1_000)
+            .is_none());
+    }
+
+    #[test]
+    fn ack_suppresses_firing_but_not_resolution() {
+        let s = Suppressions {
+            silences: Vec::new(),
+            acked: HashSet::from(["fp-web-1".to_string()]),
+        };
+        assert_eq!(
+            s.reason(&event("web-1", AlertStatus::Firing), 0).as_deref(),
+            Some("ack")
+        );
+        assert!(s
+            .reason(&event("web-1", AlertStatus::Resolved), 0)
+            .is_none());
+    }
+}
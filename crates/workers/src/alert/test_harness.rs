@@ -1,11 +1,15 @@
-use crate::aggregator::AggregatorStore;
+use std::collections::BTreeMap;
+
+use crate::aggregator::{AggregatorStore, MetricKey};
 use crate::alert::evaluator::Evaluator;
 use crate::alert::event::{AlertEvent, AlertStatus};
 use crate::alert::rule::Rule;
 
+#[derive(Clone)]
 pub struct MetricSample {
     pub agent_id: String,
     pub metric_name: String,
+    pub labels: BTreeMap<String, String>,
     pub timestamp_ms: i64,
     pub value: f64,
 }
@@ -17,8 +21,8 @@ pub struct HarnessResult {
 }
 
 pub fn run_harness(rules: Vec<Rule>, samples: Vec<MetricSample>) -> HarnessResult {
-    let aggregator = AggregatorStore::new(60_000);
     let evaluator = Evaluator::new(rules);
+    let aggregator = AggregatorStore::new(evaluator.max_window_ms().max(60_000));
     let mut all_events = Vec::new();
 
     let mut agents: Vec<String> = samples.iter().map(|s| s.agent_id.clone()).collect();
@@ -26,12 +30,12 @@ pub fn run_harness(rules: Vec<Rule>, samples: Vec<MetricSample>) -> HarnessResul
     agents.dedup();
 
     for sample in &samples {
-        aggregator.ingest(
-            &sample.agent_id,
-            &sample.metric_name,
-            sample.timestamp_ms,
-            sample.value,
-        );
+        let key = MetricKey {
+            agent_id: sample.agent_id.clone(),
+            name: sample.metric_name.clone(),
+            labels: sample.labels.clone(),
+        };
+        aggregator.ingest_series(key, sample.timestamp_ms, sample.value);
 
         for agent in &agents {
             let events = evaluator.evaluate(agent, &aggregator, sample.timestamp_ms);
@@ -58,7 +62,8 @@ pub fn run_harness(rules: Vec<Rule>, samples: Vec<MetricSample>) -> HarnessResul
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::alert::rule::{Condition, Severity};
+    use crate::alert::rule::{Condition, Severity, DEFAULT_MIN_SAMPLES, DEFAULT_WINDOW_MS};
+    use sentinel_common::aggregation::Aggregation;
     use std::collections::HashMap;
 
     fn high_cpu_rule() -> Rule {
@@ -67,12 +72,20 @@ mod tests {
             name: "High CPU".into(),
             agent_pattern: "*".into(),
             metric_name: "cpu".into(),
+            label_matchers: Vec::new(),
+            expression: None,
+            absent_for_ms: None,
             condition: Condition::GreaterThan,
             threshold: 80.0,
+            aggregation: Aggregation::Avg,
+            window_ms: DEFAULT_WINDOW_MS,
+            min_samples: DEFAULT_MIN_SAMPLES,
             for_duration_ms: 0,
             severity: Severity::Warning,
             annotations: HashMap::new(),
             notifier_ids: Vec::new(),
+            grouping: None,
+            escalation_policy_id: None,
         }
     }
 
@@ -82,12 +95,14 @@ mod tests {
             MetricSample {
                 agent_id: "a1".into(),
                 metric_name: "cpu".into(),
+                labels: BTreeMap::new(),
                 timestamp_ms: 1000,
                 value: 90.0,
             },
             MetricSample {
                 agent_id: "a1".into(),
                 metric_name: "cpu".into(),
+                labels: BTreeMap::new(),
                 timestamp_ms: 2000,
                 value: 95.0,
             },
@@ -103,18 +118,21 @@ mod tests {
             MetricSample {
                 agent_id: "a1".into(),
                 metric_name: "cpu".into(),
+                labels: BTreeMap::new(),
                 timestamp_ms: 500,
                 value: 90.0,
             },
             MetricSample {
                 agent_id: "a1".into(),
                 metric_name: "cpu".into(),
+                labels: BTreeMap::new(),
                 timestamp_ms: 1000,
                 value: 90.0,
             },
             MetricSample {
                 agent_id: "a1".into(),
                 metric_name: "cpu".into(),
+                labels: BTreeMap::new(),
                 timestamp_ms: 2000,
                 value: 50.0,
             },
@@ -130,12 +148,14 @@ mod tests {
             MetricSample {
                 agent_id: "a1".into(),
                 metric_name: "cpu".into(),
+                labels: BTreeMap::new(),
                 timestamp_ms: 1000,
                 value: 50.0,
             },
             MetricSample {
                 agent_id: "a1".into(),
                 metric_name: "cpu".into(),
+                labels: BTreeMap::new(),
                 timestamp_ms: 2000,
                 value: 60.0,
             },
@@ -154,18 +174,21 @@ mod tests {
             MetricSample {
                 agent_id: "a1".into(),
                 metric_name: "cpu".into(),
+                labels: BTreeMap::new(),
                 timestamp_ms: 500,
                 value: 90.0,
             },
             MetricSample {
                 agent_id: "a1".into(),
                 metric_name: "cpu".into(),
+                labels: BTreeMap::new(),
                 timestamp_ms: 1000,
                 value: 90.0,
             },
             MetricSample {
                 agent_id: "a1".into(),
                 metric_name: "cpu".into(),
+                labels: BTreeMap::new(),
                 timestamp_ms: 3000,
                 value: 90.0,
             },
@@ -177,18 +200,21 @@ mod tests {
             MetricSample {
                 agent_id: "a1".into(),
                 metric_name: "cpu".into(),
+                labels: BTreeMap::new(),
                 timestamp_ms: 500,
                 value: 90.0,
             },
             MetricSample {
                 agent_id: "a1".into(),
                 metric_name: "cpu".into(),
+                labels: BTreeMap::new(),
                 timestamp_ms: 1000,
                 value: 90.0,
             },
             MetricSample {
                 agent_id: "a1".into(),
                 metric_name: "cpu".into(),
+                labels: BTreeMap::new(),
                 timestamp_ms: 7000,
                 value: 90.0,
             },
@@ -203,24 +229,28 @@ mod tests {
             MetricSample {
                 agent_id: "a1".into(),
                 metric_name: "cpu".into(),
+                labels: BTreeMap::new(),
                 timestamp_ms: 500,
                 value: 90.0,
             },
             MetricSample {
                 agent_id: "a2".into(),
                 metric_name: "cpu".into(),
+                labels: BTreeMap::new(),
                 timestamp_ms: 500,
                 value: 90.0,
             },
             MetricSample {
                 agent_id: "a1".into(),
                 metric_name: "cpu".into(),
+                labels: BTreeMap::new(),
                 timestamp_ms: 1000,
                 value: 90.0,
             },
             MetricSample {
                 agent_id: "a2".into(),
                 metric_name: "cpu".into(),
+                labels: BTreeMap::new(),
                 timestamp_ms: 1000,
                 value: 90.0,
             },
@@ -236,36 +266,48 @@ mod tests {
             name: "High Memory".into(),
             agent_pattern: "*".into(),
             metric_name: "memory".into(),
+            label_matchers: Vec::new(),
+            expression: None,
+            absent_for_ms: None,
             condition: Condition::GreaterThan,
             threshold: 90.0,
+            aggregation: Aggregation::Avg,
+            window_ms: DEFAULT_WINDOW_MS,
+            min_samples: DEFAULT_MIN_SAMPLES,
             for_duration_ms: 0,
             severity: Severity::Critical,
             annotations: HashMap::new(),
             notifier_ids: Vec::new(),
+            grouping: None,
+            escalation_policy_id: None,
         };
 
         let samples = vec![
             MetricSample {
                 agent_id: "a1".into(),
                 metric_name: "cpu".into(),
+                labels: BTreeMap::new(),
                 timestamp_ms: 500,
                 value: 90.0,
             },
             MetricSample {
                 agent_id: "a1".into(),
                 metric_name: "memory".into(),
+                labels: BTreeMap::new(),
                 timestamp_ms: 500,
                 value: 95.0,
             },
             MetricSample {
                 agent_id: "a1".into(),
                 metric_name: "cpu".into(),
+                labels: BTreeMap::new(),
                 timestamp_ms: 1000,
                 value: 90.0,
             },
             MetricSample {
                 agent_id: "a1".into(),
                 metric_name: "memory".into(),
+                labels: BTreeMap::new(),
                 timestamp_ms: 1000,
                 value: 95.0,
             },
@@ -273,4 +315,183 @@ mod tests {
         let result = run_harness(vec![high_cpu_rule(), mem_rule], samples);
         assert_eq!(result.firing_count, 2);
     }
+
+    fn disk_sample(mount: &str, timestamp_ms: i64, value: f64) -> MetricSample {
+        MetricSample {
+            agent_id: "a1".into(),
+            metric_name: "disk".into(),
+            labels: BTreeMap::from([("mount".to_string(), mount.to_string())]),
+            timestamp_ms,
+            value,
+        }
+    }
+
+    #[test]
+    fn any_mount_above_threshold() {
+        let rule = Rule {
+            id: "r-disk".into(),
+            name: "Disk Full".into(),
+            metric_name: "disk".into(),
+            threshold: 90.0,
+            label_matchers: vec![
+                sentinel_common::labels::LabelMatcher::parse("mount!~/boot.*").unwrap(),
+            ],
+            ..high_cpu_rule()
+        };
+
+        let samples = vec![
+            disk_sample("/", 500, 40.0),
+            disk_sample("/data", 500, 95.0),
+            disk_sample("/boot/efi", 500, 99.0),
+            disk_sample("/", 1000, 40.0),
+            disk_sample("/data", 1000, 96.0),
+            disk_sample("/boot/efi", 1000, 99.0),
+            disk_sample("/data", 2000, 20.0),
+            disk_sample("/data", 2500, 20.0),
+            disk_sample("/data", 3000, 20.0),
+        ];
+        let result = run_harness(vec![rule], samples);
+
+        assert_eq!(result.firing_count, 1);
+        assert_eq!(result.resolved_count, 1);
+        assert!(result
+            .events
+            .iter()
+            .all(|e| e.labels.get("mount").map(String::as_str) == Some("/data")));
+    }
+
+    fn cpu_sample(timestamp_ms: i64, value: f64) -> MetricSample {
+        MetricSample {
+            agent_id: "a1".into(),
+            metric_name: "cpu".into(),
+            labels: BTreeMap::new(),
+            timestamp_ms,
+            value,
+        }
+    }
+
+    fn counter_sample(timestamp_ms: i64, value: f64) -> MetricSample {
+        MetricSample {
+            metric_name: "net.rx_bytes".into(),
+            ..cpu_sample(timestamp_ms, value)
+        }
+    }
+
+    fn reducer_rule(aggregation: Aggregation, threshold: f64) -> Rule {
+        Rule {
+            aggregation,
+            threshold,
+            ..high_cpu_rule()
+        }
+    }
+
+    fn spiky_cpu() -> Vec<MetricSample> {
+        [10.0, 12.0, 11.0, 99.0, 10.0]
+            .iter()
+            .enumerate()
+            .map(|(i, v)| cpu_sample(1000 * (i as i64 + 1), *v))
+            .collect()
+    }
+
+    #[test]
+    fn max_catches_spike_avg_does_not() {
+        let avg = run_harness(vec![reducer_rule(Aggregation::Avg, 80.0)], spiky_cpu());
+        assert_eq!(avg.firing_count, 0);
+
+        let max = run_harness(vec![reducer_rule(Aggregation::Max, 80.0)], spiky_cpu());
+        assert_eq!(max.firing_count, 1);
+    }
+
+    #[test]
+    fn min_last_sum_count_reducers() {
+        let below = Rule {
+            condition: Condition::LessThan,
+            ..reducer_rule(Aggregation::Min, 10.5)
+        };
+        let min = run_harness(vec![below], spiky_cpu());
+        assert_eq!(min.firing_count, 1);
+
+        let last = run_harness(vec![reducer_rule(Aggregation::Last, 90.0)], spiky_cpu());
+        assert_eq!(last.firing_count, 1);
+        assert_eq!(last.resolved_count, 1);
+
+        let sum = run_harness(vec![reducer_rule(Aggregation::Sum, 140.0)], spiky_cpu());
+        assert_eq!(sum.firing_count, 1);
+
+        let count = run_harness(vec![reducer_rule(Aggregation::Count, 4.0)], spiky_cpu());
+        assert_eq!(count.firing_count, 1);
+        assert_eq!(count.events[0].value, 5.0);
+    }
+
+    #[test]
+    fn percentile_and_stddev_reducers() {
+        let p50 = run_harness(
+            vec![reducer_rule(Aggregation::Percentile(50.0), 50.0)],
+            spiky_cpu(),
+        );
+        assert_eq!(p50.firing_count, 0);
+
+        let p95 = run_harness(
+            vec![reducer_rule(Aggregation::Percentile(95.0), 50.0)],
+            spiky_cpu(),
+        );
+        assert_eq!(p95.firing_count, 1);
+
+        let stddev = run_harness(vec![reducer_rule(Aggregation::StdDev, 30.0)], spiky_cpu());
+        assert_eq!(stddev.firing_count, 1);
+    }
+
+    #[test]
+    fn rate_and_increase_on_counter() {
+        let samples: Vec<MetricSample> = [0.0, 1000.0, 2000.0, 500.0, 1500.0]
+            .iter()
+            .enumerate()
+            .map(|(i, v)| counter_sample(10_000 * i as i64, *v))
+            .collect();
+
+        let mut increase = reducer_rule(Aggregation::Increase, 3000.0);
+        increase.metric_name = "net.rx_bytes".into();
+        let result = run_harness(vec![increase], samples.clone());
+        assert_eq!(result.firing_count, 1);
+        assert_eq!(result.events[0].value, 3500.0);
+
+        let mut rate = reducer_rule(Aggregation::Rate, 80.0);
+        rate.metric_name = "net.rx_bytes".into();
+        let result = run_harness(vec![rate], samples);
+        assert_eq!(result.firing_count, 1);
+        assert_eq!(result.events[0].value, 100.0);
+    }
+
+    #[test]
+    fn window_and_min_samples_per_rule() {
+        let samples = vec![
+            cpu_sample(1000, 95.0),
+            cpu_sample(2000, 95.0),
+            cpu_sample(60_000, 10.0),
+            cpu_sample(61_000, 10.0),
+        ];
+
+        let wide = Rule {
+            window_ms: 120_000,
+            min_samples: 4,
+            ..high_cpu_rule()
+        };
+        let result = run_harness(vec![wide], samples);
+        assert_eq!(result.firing_count, 0);
+
+        let samples = vec![
+            cpu_sample(1000, 10.0),
+            cpu_sample(50_000, 95.0),
+            cpu_sample(51_000, 95.0),
+        ];
+        let wide = run_harness(vec![high_cpu_rule()], samples.clone());
+        assert_eq!(wide.firing_count, 0);
+
+        let narrow = Rule {
+            window_ms: 5000,
+            ..high_cpu_rule()
+        };
+        let result = run_harness(vec![narrow], samples);
+        assert_eq!(result.firing_count, 1);
+    }
 }
@@ -19,6 +19,7 @@ pub struct StatusResponse {
     pub pipeline: PipelineStatus,
     pub pool: PoolStatus,
     pub peers: Vec<String>,
+    pub partition_members: Vec<String>,
 }
 
 #[derive(Serialize)]
@@ -63,6 +64,11 @@ pub async fn status(State(state): State<Arc<WorkerState>>) -> Json<StatusRespons
         .as_ref()
         .map(|r| r.peers())
         .unwrap_or_default();
+    let partition_members: Vec<String> = state
+        .registry
+        .as_ref()
+        .map(|r| r.partitioner().members())
+        .unwrap_or_default();
 
     let in_flight = state.in_flight.load(Ordering::Relaxed);
 
@@ -99,5 +105,6 @@ pub async fn status(State(state): State<Arc<WorkerState>>) -> Json<StatusRespons
             active: pool_size.saturating_sub(pool_idle),
         },
         peers,
+        partition_members,
     })
 }
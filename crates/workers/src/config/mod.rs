@@ -1,3 +1,6 @@
 mod schema;
 
-pub use schema::{BackpressureConfig, ConsumerGroupConfig, RegistryConfig, WorkerConfig};
+pub use schema::{
+    AgentOfflineConfig, AlertsConfig, BackpressureConfig, ConsumerGroupConfig, DlqConfig,
+    RegistryConfig, WorkerConfig,
+};
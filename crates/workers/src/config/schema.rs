@@ -1,5 +1,9 @@
 use std::time::Duration;
 
+use sentinel_common::flapping::{FlapPolicy, DEFAULT_THRESHOLD, DEFAULT_WINDOW_SECS};
+
+use crate::alert::Severity;
+
 #[derive(Debug, Clone)]
 pub struct WorkerConfig {
     pub database_url: String,
@@ -9,6 +13,8 @@ pub struct WorkerConfig {
     pub consumer_group: ConsumerGroupConfig,
     pub backpressure: BackpressureConfig,
     pub registry: RegistryConfig,
+    pub alerts: AlertsConfig,
+    pub dlq: DlqConfig,
 }
 
 #[derive(Debug, Clone)]
@@ -33,6 +39,34 @@ pub struct RegistryConfig {
     pub bucket: String,
     pub heartbeat_interval: Duration,
     pub ttl: Duration,
+    pub partitioning: bool,
+    pub virtual_nodes: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct AlertsConfig {
+    pub absent_check_interval: Duration,
+    pub agent_offline: AgentOfflineConfig,
+    pub flap_suppression: Option<FlapPolicy>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AgentOfflineConfig {
+    pub enabled: bool,
+    pub on_stale: bool,
+    pub for_duration: Duration,
+    pub severity: Severity,
+    pub notifier_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DlqConfig {
+    pub poll_interval: Duration,
+    pub batch_size: usize,
+    pub auto_retry: bool,
+    pub retry_base_delay: Duration,
+    pub retry_max_delay: Duration,
+    pub max_age: Duration,
 }
 
 impl Default for ConsumerGroupConfig {
@@ -64,6 +98,43 @@ impl Default for RegistryConfig {
             bucket: "sentinel-worker-registry".into(),
             heartbeat_interval: Duration::from_secs(10),
             ttl: Duration::from_secs(30),
+            partitioning: true,
+            virtual_nodes: crate::registry::DEFAULT_VIRTUAL_NODES,
+        }
+    }
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            absent_check_interval: Duration::from_secs(15),
+            agent_offline: AgentOfflineConfig::default(),
+            flap_suppression: None,
+        }
+    }
+}
+
+impl Default for AgentOfflineConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            on_stale: false,
+            for_duration: Duration::from_secs(60),
+            severity: Severity::Critical,
+            notifier_ids: Vec::new(),
+        }
+    }
+}
+
+impl Default for DlqConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(10),
+            batch_size: 50,
+            auto_retry: false,
+            retry_base_delay: Duration::from_secs(60),
+            retry_max_delay: Duration::from_secs(3600),
+            max_age: Duration::from_secs(86400),
         }
     }
 }
@@ -88,9 +159,49 @@ impl WorkerConfig {
             enabled: env_parse("REGISTRY_ENABLED", true),
             heartbeat_interval: Duration::from_secs(env_parse("REGISTRY_HEARTBEAT_SECS", 10)),
             ttl: Duration::from_secs(env_parse("REGISTRY_TTL_SECS", 30)),
+            partitioning: env_parse("PARTITIONING_ENABLED", true),
+            virtual_nodes: env_parse(
+                "PARTITION_VIRTUAL_NODES",
+                crate::registry::DEFAULT_VIRTUAL_NODES,
+            ),
             ..Default::default()
         };
 
+        let agent_offline = AgentOfflineConfig {
+            enabled: env_parse("AGENT_OFFLINE_ALERTS", true),
+            on_stale: env_parse("AGENT_OFFLINE_ON_STALE", false),
+            for_duration: Duration::from_secs(env_parse("AGENT_OFFLINE_FOR_SECS", 60)),
+            severity: match env_or("AGENT_OFFLINE_SEVERITY", "critical").as_str() {
+                "info" => Severity::Info,
+                "warning" => Severity::Warning,
+                _ => Severity::Critical,
+            },
+            notifier_ids: env_or("AGENT_OFFLINE_NOTIFIER_IDS", "")
+                .split(',')
+                .map(str::trim)
+                .filter(|id| !id.is_empty())
+                .map(String::from)
+                .collect(),
+        };
+
+        let alerts = AlertsConfig {
+            absent_check_interval: Duration::from_secs(env_parse("ABSENT_CHECK_INTERVAL_SECS", 15)),
+            agent_offline,
+            flap_suppression: env_parse("FLAP_SUPPRESSION", false).then(|| FlapPolicy {
+                window_secs: env_parse("FLAP_WINDOW_SECS", DEFAULT_WINDOW_SECS),
+                threshold: env_parse("FLAP_THRESHOLD", DEFAULT_THRESHOLD),
+            }),
+        };
+
+        let dlq = DlqConfig {
+            poll_interval: Duration::from_secs(env_parse("DLQ_POLL_INTERVAL_SECS", 10)),
+            batch_size: env_parse("DLQ_BATCH_SIZE", 50),
+            auto_retry: env_parse("DLQ_AUTO_RETRY", false),
+            retry_base_delay: Duration::from_secs(env_parse("DLQ_RETRY_BASE_SECS", 60)),
+            retry_max_delay: Duration::from_secs(env_parse("DLQ_RETRY_MAX_DELAY_SECS", 3600)),
+            max_age: Duration::from_secs(env_parse("DLQ_MAX_AGE_SECS", 86400)),
+        };
+
         Self {
             database_url: std::env::var("DATABASE_URL").expect("DATABASE_URL must be set"),
             nats_url: env_or("NATS_URL", "nats://127.0.0.1:4222"),
@@ -99,6 +210,8 @@ impl WorkerConfig {
             consumer_group,
             backpressure,
             registry,
+            alerts,
+            dlq,
         }
     }
 }
@@ -132,5 +245,18 @@ mod tests {
         let reg = RegistryConfig::default();
         assert!(reg.enabled);
         assert_eq!(reg.bucket, "sentinel-worker-registry");
+        assert!(reg.partitioning);
+        assert_eq!(reg.virtual_nodes, 64);
+
+        let alerts = AlertsConfig::default();
+        assert_eq!(alerts.absent_check_interval, Duration::from_secs(15));
+        assert!(alerts.agent_offline.enabled);
+        assert!(!alerts.agent_offline.on_stale);
+        assert_eq!(alerts.agent_offline.severity, Severity::Critical);
+
+        let dlq = DlqConfig::default();
+        assert!(!dlq.auto_retry);
+        assert_eq!(dlq.retry_base_delay, Duration::from_secs(60));
+        assert_eq!(dlq.max_age, Duration::from_secs(86400));
     }
 }
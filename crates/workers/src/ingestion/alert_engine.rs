@@ -3,91 +3,430 @@ use std::sync::Arc;
 use sqlx::PgPool;
 use tokio::sync::RwLock;
 
-use crate::aggregator::AggregatorStore;
-use crate::alert::{AlertStore, Evaluator};
+use sentinel_common::envelope::Envelope;
+use sentinel_common::escalation::EscalationPolicy;
+use sentinel_common::grouping::GroupPolicy;
+use sentinel_common::presence::{AgentPresence, PresenceStatus};
+
+use crate::aggregator::{AggregatorStore, MetricKey};
+use crate::alert::{
+    agent_offline_rule, plan_start, plan_step, AlertEvent, AlertStateStore, AlertStatus,
+    AlertStore, EscalationStore, Escalations, Evaluator, FlapDetector, Rule, StateChange,
+    SuppressionStore, Suppressions, FLAPPING_REASON,
+};
+use crate::config::AlertsConfig;
 use crate::notifier::dispatcher::Dispatcher;
+use crate::registry::Partitioner;
 use crate::storage::RuleLoader;
 use crate::transform::MetricRow;
 
 const AGGREGATOR_WINDOW_MS: i64 = 120_000;
 const RULE_RELOAD_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+const GROUP_FLUSH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+const ESCALATION_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+const ESCALATION_BATCH: i64 = 100;
+const ESCALATION_HOLD_MS: i64 = 60_000;
 
 pub struct AlertEngine {
     evaluator: RwLock<Evaluator>,
     aggregator: AggregatorStore,
     alert_store: AlertStore,
+    state_store: AlertStateStore,
+    suppression: SuppressionStore,
+    escalation: EscalationStore,
+    escalations: RwLock<Escalations>,
     rule_loader: RuleLoader,
     dispatcher: Dispatcher,
+    builtins: Vec<Rule>,
+    offline_on_stale: bool,
+    flaps: Option<FlapDetector>,
 }
 
 impl AlertEngine {
-    pub async fn new(pool: PgPool) -> Result<Self, sqlx::Error> {
+    pub async fn new(
+        pool: PgPool,
+        config: &AlertsConfig,
+        envelope: Envelope,
+    ) -> Result<Self, sqlx::Error> {
+        let builtins: Vec<Rule> = if config.agent_offline.enabled {
+            vec![agent_offline_rule(&config.agent_offline)]
+        } else {
+            Vec::new()
+        };
         let rule_loader = RuleLoader::new(pool.clone());
-        let rules = rule_loader.load_enabled().await.unwrap_or_default();
+        let mut rules = rule_loader.load_enabled().await.unwrap_or_default();
 
         let count = rules.len();
-        tracing::info!(target: "alert", count, "Alert rules loaded");
+        tracing::info!(target: "alert", count, builtin = builtins.len(), "Alert rules loaded");
+        rules.extend(builtins.iter().cloned());
+
+        let evaluator = Evaluator::new(rules);
+        let aggregator = AggregatorStore::new(retention_ms(&evaluator));
 
-        Ok(Self {
-            evaluator: RwLock::new(Evaluator::new(rules)),
-            aggregator: AggregatorStore::new(AGGREGATOR_WINDOW_MS),
+        let state_store = AlertStateStore::new(pool.clone());
+        let mut resolved = Vec::new();
+        match state_store.load_all().await {
+            Ok(states) => {
+                let (stale, dropped) = evaluator.restore_states(states, now_ms());
+                resolved = dropped;
+                tracing::info!(
+                    target: "alert",
+                    restored = evaluator.state_count(),
+                    stale = stale.len(),
+                    "Alert states restored"
+                );
+                if let Err(e) = state_store.delete(&stale).await {
+                    tracing::warn!(target: "alert", error = %e, "Failed to prune stale alert states");
+                }
+            }
+            Err(e) => {
+                tracing::warn!(target: "alert", error = %e, "Failed to load alert states");
+            }
+        }
+
+        let escalation = EscalationStore::new(pool.clone());
+        let escalations = escalation.load().await.unwrap_or_else(|e| {
+            tracing::warn!(target: "alert", error = %e, "Failed to load escalation policies");
+            Escalations::default()
+        });
+
+        let engine = Self {
+            evaluator: RwLock::new(evaluator),
+            aggregator,
             alert_store: AlertStore::new(pool.clone()),
+            state_store,
+            suppression: SuppressionStore::new(pool.clone()),
+            escalation,
+            escalations: RwLock::new(escalations),
             rule_loader,
-            dispatcher: Dispatcher::new(pool),
-        })
+            dispatcher: Dispatcher::new(pool, envelope),
+            builtins,
+            offline_on_stale: config.agent_offline.on_stale,
+            flaps: config.flap_suppression.map(FlapDetector::new),
+        };
+        engine.emit(&*engine.evaluator.read().await, resolved).await;
+        Ok(engine)
     }
 
     pub async fn process(&self, agent_id: &str, rows: &[MetricRow]) {
-        let now_ms = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_millis() as i64;
+        let now_ms = now_ms();
 
         for row in rows {
             if let Some(value) = row.value {
-                self.aggregator
-                    .ingest(&row.agent_id, &row.name, row.time_ms, value);
+                let key = MetricKey {
+                    agent_id: row.agent_id.clone(),
+                    name: row.name.clone(),
+                    labels: sentinel_common::labels::sorted(&row.labels),
+                };
+                self.aggregator.observe(&key, now_ms);
+                self.aggregator.ingest_series(key, row.time_ms, value);
             }
         }
 
         let evaluator = self.evaluator.read().await;
         let events = evaluator.evaluate(agent_id, &self.aggregator, now_ms);
-        let nid_map: Vec<Vec<String>> = events
+        self.emit(&evaluator, events).await;
+    }
+
+    pub async fn sweep(&self) {
+        let evaluator = self.evaluator.read().await;
+        let events = evaluator.sweep(&self.aggregator, now_ms());
+        self.emit(&evaluator, events).await;
+    }
+
+    pub async fn handle_presence(&self, presence: &AgentPresence) {
+        let down = match presence.status {
+            PresenceStatus::Online => false,
+            PresenceStatus::Offline => true,
+            PresenceStatus::Stale if self.offline_on_stale => true,
+            PresenceStatus::Stale => return,
+        };
+        tracing::debug!(
+            target: "alert",
+            agent = %presence.agent_id,
+            status = presence.status.as_str(),
+            "Agent presence changed"
+        );
+        let evaluator = self.evaluator.read().await;
+        let events = evaluator.evaluate_presence(&presence.agent_id, down, now_ms());
+        self.emit(&evaluator, events).await;
+    }
+
+    async fn emit(&self, evaluator: &Evaluator, events: Vec<AlertEvent>) {
+        let escalations = self.escalations.read().await;
+        let routes: Vec<(Vec<String>, Option<GroupPolicy>, Vec<EscalationPolicy>)> = events
             .iter()
-            .map(|e| evaluator.notifier_ids_for_rule(&e.rule_id).to_vec())
+            .map(|e| {
+                (
+                    evaluator.notifier_ids_for_rule(&e.rule_id).to_vec(),
+                    evaluator.grouping_for_rule(&e.rule_id).cloned(),
+                    escalations.resolve(evaluator.escalation_policy_for_rule(&e.rule_id), e),
+                )
+            })
             .collect();
-        drop(evaluator);
+        drop(escalations);
+        let changes: Vec<StateChange> = evaluator.drain_state_changes();
+
+        if let Err(e) = self.state_store.apply(&changes).await {
+            tracing::error!(target: "alert", error = %e, "Failed to persist alert states");
+        }
 
-        for (event, nids) in events.iter().zip(nid_map.iter()) {
+        let suppressions = self.load_suppressions(&events).await;
+        let now_ms = now_ms();
+        let mut resolved = Vec::new();
+
+        for (event, (nids, grouping, policies)) in events.iter().zip(routes.iter()) {
+            let flapping = self
+                .flaps
+                .as_ref()
+                .is_some_and(|f| f.observe(event, now_ms));
+            let suppressed_by = suppressions
+                .reason(event, now_ms)
+                .or_else(|| flapping.then(|| FLAPPING_REASON.to_string()));
             tracing::info!(
                 target: "alert",
                 rule = %event.rule_name,
                 agent = %event.agent_id,
+                series = %event.series_name(),
                 status = %event.status_str(),
                 value = event.value,
+                suppressed_by = suppressed_by.as_deref().unwrap_or("-"),
                 "Alert event"
             );
-            if let Err(e) = self.alert_store.persist(event).await {
+            if let Err(e) = self
+                .alert_store
+                .persist(event, suppressed_by.as_deref())
+                .await
+            {
                 tracing::error!(target: "alert", error = %e, "Failed to persist alert");
             }
+            if event.status == AlertStatus::Resolved {
+                resolved.push(event.fingerprint.clone());
+            }
+
+            if !policies.is_empty() || event.status == AlertStatus::Resolved {
+                self.escalate(
+                    event,
+                    policies,
+                    nids,
+                    grouping.as_ref(),
+                    suppressed_by.is_some(),
+                )
+                .await;
+            } else if suppressed_by.is_none() && !nids.is_empty() {
+                self.dispatcher
+                    .dispatch(event, nids, grouping.as_ref())
+                    .await;
+            }
+        }
+
+        if let Err(e) = self.suppression.clear_acks(&resolved).await {
+            tracing::error!(target: "alert", error = %e, "Failed to clear alert acks");
+        }
+    }
+
+    async fn escalate(
+        &self,
+        event: &AlertEvent,
+        policies: &[EscalationPolicy],
+        notifier_ids: &[String],
+        grouping: Option<&GroupPolicy>,
+        suppressed: bool,
+    ) {
+        if event.status == AlertStatus::Resolved {
+            let mut targets = if policies.is_empty() {
+                notifier_ids.to_vec()
+            } else {
+                Vec::new()
+            };
+            match self.escalation.finish(&event.fingerprint).await {
+                Ok(notified) => {
+                    for id in notified {
+                        if !targets.contains(&id) {
+                            targets.push(id);
+                        }
+                    }
+                }
+                Err(e) => {
+                    tracing::error!(target: "alert", error = %e, "Failed to finish escalation");
+                }
+            }
+            if !suppressed && !targets.is_empty() {
+                self.dispatcher.dispatch(event, &targets, grouping).await;
+            }
+            return;
+        }
+        if suppressed {
+            return;
+        }
+
+        let now_ms = now_ms();
+        for policy in policies {
+            let plan = plan_start(policy, now_ms);
+            match self.escalation.start(event, &policy.id, &plan).await {
+                Ok(true) => {
+                    tracing::info!(
+                        target: "alert",
+                        rule = %event.rule_name,
+                        agent = %event.agent_id,
+                        policy = %policy.name,
+                        "Escalation started"
+                    );
+                    if !plan.notify.is_empty() {
+                        self.dispatcher
+                            .dispatch(event, &plan.notify, grouping)
+                            .await;
+                    }
+                }
+                Ok(false) => {}
+                Err(e) => {
+                    tracing::error!(target: "alert", error = %e, policy = %policy.id, "Failed to start escalation");
+                }
+            }
+        }
+    }
+
+    pub async fn advance_escalations(&self) {
+        if let Err(e) = self.escalation.stop_acked().await {
+            tracing::error!(target: "alert", error = %e, "Failed to stop acknowledged escalations");
+        }
+        if let Err(e) = self.escalation.resume_unacked().await {
+            tracing::error!(target: "alert", error = %e, "Failed to resume escalations");
+        }
+        let due = match self.escalation.claim_due(ESCALATION_BATCH).await {
+            Ok(due) => due,
+            Err(e) => {
+                tracing::error!(target: "alert", error = %e, "Failed to claim due escalations");
+                return;
+            }
+        };
+        if due.is_empty() {
+            return;
+        }
+
+        let events: Vec<AlertEvent> = due.iter().map(|d| d.event.clone()).collect();
+        let suppressions = self.load_suppressions(&events).await;
+        let evaluator = self.evaluator.read().await;
+        let escalations = self.escalations.read().await;
+        let now_ms = now_ms();
+
+        for d in due {
+            let result = match escalations.get(&d.policy_id) {
+                None => self.escalation.remove(&d).await,
+                Some(_) if suppressions.reason(&d.event, now_ms).is_some() => {
+                    self.escalation.hold(&d, now_ms + ESCALATION_HOLD_MS).await
+                }
+                Some(policy) => match plan_step(policy, d.step, now_ms) {
+                    None => self.escalation.record(&d, d.step, &d.notified, None).await,
+                    Some(plan) => {
+                        tracing::info!(
+                            target: "alert",
+                            rule = %d.event.rule_name,
+                            agent = %d.event.agent_id,
+                            policy = %policy.name,
+                            step = plan.step,
+                            "Escalating alert"
+                        );
+                        self.dispatcher
+                            .dispatch(
+                                &d.event,
+                                &plan.notify,
+                                evaluator.grouping_for_rule(&d.event.rule_id),
+                            )
+                            .await;
+                        let mut notified = d.notified.clone();
+                        for id in plan.notify {
+                            if !notified.contains(&id) {
+                                notified.push(id);
+                            }
+                        }
+                        self.escalation
+                            .record(&d, plan.step, &notified, plan.next_at_ms)
+                            .await
+                    }
+                },
+            };
+            if let Err(e) = result {
+                tracing::error!(target: "alert", error = %e, policy = %d.policy_id, "Failed to advance escalation");
+            }
+        }
+    }
 
-            if !nids.is_empty() {
-                self.dispatcher.dispatch(event, nids).await;
+    pub async fn flush_groups(&self) {
+        let due = self.dispatcher.take_due_groups(now_ms());
+        if due.is_empty() {
+            return;
+        }
+        let members: Vec<AlertEvent> = due
+            .iter()
+            .flat_map(|(_, g)| g.alerts.iter().cloned())
+            .collect();
+        let suppressions = self.load_suppressions(&members).await;
+        let now_ms = now_ms();
+
+        for (notifier_id, mut group) in due {
+            group
+                .alerts
+                .retain(|a| suppressions.reason(a, now_ms).is_none());
+            if group.alerts.is_empty() {
+                continue;
             }
+            tracing::debug!(
+                target: "alert",
+                notifier = %notifier_id,
+                group = %group.key,
+                alerts = group.alerts.len(),
+                repeat = group.repeat,
+                "Flushing alert group"
+            );
+            self.dispatcher.dispatch_group(&notifier_id, &group).await;
         }
     }
 
+    async fn load_suppressions(&self, events: &[AlertEvent]) -> Suppressions {
+        if events.is_empty() {
+            return Suppressions::default();
+        }
+        self.suppression.load(events).await.unwrap_or_else(|e| {
+            tracing::warn!(
+                target: "alert",
+                error = %e,
+                "Failed to load silences, notifying without suppression"
+            );
+            Suppressions::default()
+        })
+    }
+
     pub async fn reload_rules(&self) {
         match self.rule_loader.load_enabled().await {
-            Ok(rules) => {
+            Ok(mut rules) => {
                 let count = rules.len();
-                self.evaluator.write().await.set_rules(rules);
-                tracing::debug!(target: "alert", count, "Alert rules reloaded");
+                rules.extend(self.builtins.iter().cloned());
+                let mut evaluator = self.evaluator.write().await;
+                let resolved = evaluator.set_rules(rules, now_ms());
+                self.aggregator.set_window_ms(retention_ms(&evaluator));
+                let evaluator = evaluator.downgrade();
+                tracing::debug!(
+                    target: "alert",
+                    count,
+                    resolved = resolved.len(),
+                    "Alert rules reloaded"
+                );
+                self.emit(&evaluator, resolved).await;
             }
             Err(e) => {
                 tracing::error!(target: "alert", error = %e, "Failed to reload rules");
             }
         }
+        match self.escalation.load().await {
+            Ok(escalations) => {
+                tracing::debug!(target: "alert", policies = escalations.len(), "Escalation policies reloaded");
+                *self.escalations.write().await = escalations;
+            }
+            Err(e) => {
+                tracing::error!(target: "alert", error = %e, "Failed to reload escalation policies");
+            }
+        }
     }
 
     pub fn spawn_reload_loop(self: &Arc<Self>, cancel: tokio_util::sync::CancellationToken) {
@@ -103,4 +442,121 @@ impl AlertEngine {
             }
         });
     }
+
+    pub fn spawn_group_loop(self: &Arc<Self>, cancel: tokio_util::sync::CancellationToken) {
+        let engine = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = cancel.cancelled() => break,
+                    _ = tokio::time::sleep(GROUP_FLUSH_INTERVAL) => {
+                        engine.flush_groups().await;
+                    }
+                }
+            }
+        });
+    }
+
+    pub fn spawn_escalation_loop(self: &Arc<Self>, cancel: tokio_util::sync::CancellationToken) {
+        let engine = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = cancel.cancelled() => break,
+                    _ = tokio::time::sleep(ESCALATION_INTERVAL) => {
+                        engine.advance_escalations().await;
+                    }
+                }
+            }
+        });
+    }
+
+    pub fn spawn_sweep_loop(
+        self: &Arc<Self>,
+        interval: std::time::Duration,
+        cancel: tokio_util::sync::CancellationToken,
+    ) {
+        let engine = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = cancel.cancelled() => break,
+                    _ = tokio::time::sleep(interval) => {
+                        engine.sweep().await;
+                    }
+                }
+            }
+        });
+    }
+}
+
+impl AlertEngine {
+    pub fn spawn_rebalance_loop(
+        self: &Arc<Self>,
+        partitioner: Arc<Partitioner>,
+        cancel: tokio_util::sync::CancellationToken,
+    ) {
+        let engine = Arc::clone(self);
+        let mut rx = partitioner.subscribe();
+        tokio::spawn(async move {
+            engine.rebalance(&partitioner).await;
+            loop {
+                tokio::select! {
+                    _ = cancel.cancelled() => break,
+                    changed = rx.changed() => {
+                        if changed.is_err() {
+                            break;
+                        }
+                        engine.rebalance(&partitioner).await;
+                    }
+                }
+            }
+        });
+    }
+
+    pub async fn rebalance(&self, partitioner: &Partitioner) {
+        let evaluator = self.evaluator.read().await;
+        let released = evaluator.retain_agents(|a| partitioner.owns(a));
+        self.aggregator.retain_agents(|a| partitioner.owns(a));
+
+        let states = match self.state_store.load_all().await {
+            Ok(states) => states,
+            Err(e) => {
+                tracing::error!(target: "alert", error = %e, "Failed to load alert states for rebalance");
+                return;
+            }
+        };
+        let before = evaluator.state_count();
+        let owned = states
+            .into_iter()
+            .filter(|(_, t)| partitioner.owns(&t.agent_id))
+            .collect();
+        let (stale, resolved) = evaluator.restore_states(owned, now_ms());
+        let acquired = evaluator.state_count() - before;
+
+        if let Err(e) = self.state_store.delete(&stale).await {
+            tracing::warn!(target: "alert", error = %e, "Failed to prune stale alert states");
+        }
+        self.emit(&evaluator, resolved).await;
+        drop(evaluator);
+        tracing::info!(
+            target: "alert",
+            generation = partitioner.generation(),
+            members = partitioner.members().len(),
+            released,
+            acquired,
+            "Alert ownership rebalanced"
+        );
+    }
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64
+}
+
+fn retention_ms(evaluator: &Evaluator) -> i64 {
+    evaluator.max_window_ms().max(AGGREGATOR_WINDOW_MS)
 }
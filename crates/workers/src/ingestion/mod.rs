@@ -1,5 +1,9 @@
 mod alert_engine;
 mod pipeline;
+mod presence;
+mod routing;
 
 pub use alert_engine::AlertEngine;
 pub use pipeline::IngestPipeline;
+pub use presence::spawn_presence_listener;
+pub use routing::AlertRouting;
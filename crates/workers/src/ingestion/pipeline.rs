@@ -5,9 +5,10 @@ use sentinel_common::proto::Batch;
 use sqlx::PgPool;
 
 use super::alert_engine::AlertEngine;
+use super::routing::AlertRouting;
 use crate::metrics::worker_metrics::WorkerMetrics;
 use crate::storage::{write_with_retry, AgentRepo, MetricWriter};
-use crate::transform::transform_batch;
+use crate::transform::{transform_batch, MetricRow};
 
 type BoxError = Box<dyn std::error::Error + Send + Sync>;
 
@@ -18,6 +19,7 @@ pub struct IngestPipeline {
     agent_repo: AgentRepo,
     metrics: Arc<WorkerMetrics>,
     alert_engine: Option<Arc<AlertEngine>>,
+    routing: Option<Arc<AlertRouting>>,
 }
 
 impl IngestPipeline {
@@ -27,6 +29,7 @@ impl IngestPipeline {
             agent_repo: AgentRepo::new(pool),
             metrics,
             alert_engine: None,
+            routing: None,
         }
     }
 
@@ -35,6 +38,11 @@ impl IngestPipeline {
         self
     }
 
+    pub fn with_alert_routing(mut self, routing: Arc<AlertRouting>) -> Self {
+        self.routing = Some(routing);
+        self
+    }
+
     pub async fn ingest(&self, batch: &Batch) -> Result<(), BoxError> {
         let start = Instant::now();
 
@@ -57,7 +65,7 @@ impl IngestPipeline {
         self.agent_repo.touch_last_seen(&batch.agent_id).await?;
 
         if let Some(ref engine) = self.alert_engine {
-            engine.process(&batch.agent_id, &rows).await;
+            self.evaluate_alerts(engine, batch, &rows).await;
         }
 
         self.metrics.inc_batches_processed();
@@ -74,4 +82,26 @@ impl IngestPipeline {
 
         Ok(())
     }
+
+    async fn evaluate_alerts(&self, engine: &AlertEngine, batch: &Batch, rows: &[MetricRow]) {
+        let owner = self
+            .routing
+            .as_ref()
+            .and_then(|r| Some((r, r.remote_owner(&batch.agent_id)?)));
+        if let Some((routing, owner)) = owner {
+            match routing.forward(&owner, batch).await {
+                Ok(()) => return,
+                Err(e) => {
+                    tracing::warn!(
+                        target: "registry",
+                        agent_id = %batch.agent_id,
+                        %owner,
+                        error = %e,
+                        "Alert routing failed, evaluating locally"
+                    );
+                }
+            }
+        }
+        engine.process(&batch.agent_id, rows).await;
+    }
 }
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use futures::StreamExt;
+use sentinel_common::nats_config::PRESENCE_SUBJECT;
+use sentinel_common::presence::AgentPresence;
+use tokio_util::sync::CancellationToken;
+
+use super::alert_engine::AlertEngine;
+use crate::registry::Partitioner;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+const PRESENCE_QUEUE_GROUP: &str = "sentinel-presence";
+
+pub async fn spawn_presence_listener(
+    client: async_nats::Client,
+    engine: Arc<AlertEngine>,
+    partitioner: Option<Arc<Partitioner>>,
+    cancel: CancellationToken,
+) -> Result<(), BoxError> {
+    let mut sub = match partitioner {
+        Some(_) => client.subscribe(PRESENCE_SUBJECT).await?,
+        None => {
+            client
+                .queue_subscribe(PRESENCE_SUBJECT, PRESENCE_QUEUE_GROUP.into())
+                .await?
+        }
+    };
+    tracing::info!(target: "alert", subject = PRESENCE_SUBJECT, "Listening for agent presence changes");
+
+    tokio::spawn(async move {
+        loop {
+            let msg = tokio::select! {
+                _ = cancel.cancelled() => break,
+                msg = sub.next() => match msg {
+                    Some(m) => m,
+                    None => break,
+                },
+            };
+            let presence = match AgentPresence::from_json(&msg.payload) {
+                Ok(p) => p,
+                Err(e) => {
+                    tracing::warn!(target: "alert", error = %e, "Dropping malformed presence message");
+                    continue;
+                }
+            };
+            if let Some(p) = &partitioner {
+                if !p.owns(&presence.agent_id) {
+                    continue;
+                }
+            }
+            engine.handle_presence(&presence).await;
+        }
+    });
+    Ok(())
+}
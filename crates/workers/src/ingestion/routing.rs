@@ -0,0 +1,92 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use prost::Message;
+use sentinel_common::proto::Batch;
+use tokio_util::sync::CancellationToken;
+
+use super::alert_engine::AlertEngine;
+use crate::metrics::worker_metrics::WorkerMetrics;
+use crate::registry::{route_subject, Partitioner};
+use crate::transform::transform_batch;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+const FORWARD_ACK_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct AlertRouting {
+    partitioner: Arc<Partitioner>,
+    client: async_nats::Client,
+    metrics: Arc<WorkerMetrics>,
+}
+
+impl AlertRouting {
+    pub fn new(
+        partitioner: Arc<Partitioner>,
+        client: async_nats::Client,
+        metrics: Arc<WorkerMetrics>,
+    ) -> Self {
+        Self {
+            partitioner,
+            client,
+            metrics,
+        }
+    }
+
+    pub fn remote_owner(&self, agent_id: &str) -> Option<String> {
+        let owner = self.partitioner.owner(agent_id);
+        (owner != self.partitioner.self_id()).then_some(owner)
+    }
+
+    pub async fn forward(&self, owner: &str, batch: &Batch) -> Result<(), BoxError> {
+        let request = async_nats::Request::new()
+            .payload(batch.encode_to_vec().into())
+            .timeout(Some(FORWARD_ACK_TIMEOUT));
+        self.client
+            .send_request(route_subject(owner), request)
+            .await?;
+        self.metrics.inc_alert_batches_forwarded();
+        Ok(())
+    }
+
+    pub async fn spawn_listener(
+        &self,
+        engine: Arc<AlertEngine>,
+        cancel: CancellationToken,
+    ) -> Result<(), BoxError> {
+        let subject = route_subject(self.partitioner.self_id());
+        let mut sub = self.client.subscribe(subject.clone()).await?;
+        let metrics = Arc::clone(&self.metrics);
+        let client = self.client.clone();
+        tracing::info!(target: "registry", %subject, "Listening for routed alert batches");
+
+        tokio::spawn(async move {
+            use futures::StreamExt;
+            loop {
+                let msg = tokio::select! {
+                    _ = cancel.cancelled() => break,
+                    msg = sub.next() => match msg {
+                        Some(m) => m,
+                        None => break,
+                    },
+                };
+                let batch = match Batch::decode(msg.payload.as_ref()) {
+                    Ok(b) => b,
+                    Err(e) => {
+                        tracing::warn!(target: "registry", error = %e, "Dropping undecodable routed batch");
+                        continue;
+                    }
+                };
+                metrics.inc_alert_batches_received();
+                if let Some(reply) = msg.reply {
+                    if let Err(e) = client.publish(reply, Default::default()).await {
+                        tracing::warn!(target: "registry", error = %e, "Failed to ack routed batch");
+                    }
+                }
+                let rows = transform_batch(&batch);
+                engine.process(&batch.agent_id, &rows).await;
+            }
+        });
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use sentinel_common::envelope::Envelope;
 use sentinel_common::logging::{self, Component, LogConfig};
 use sentinel_common::nats_config::StreamConfig;
 use sentinel_common::pool_config::PoolConfig;
@@ -12,8 +13,11 @@ use sentinel_workers::consumer::{
     connect_jetstream, create_group_consumer, ensure_stream, ConsumerLoop,
 };
 use sentinel_workers::identity::WorkerIdentity;
-use sentinel_workers::ingestion::{AlertEngine, IngestPipeline};
+use sentinel_workers::ingestion::{
+    spawn_presence_listener, AlertEngine, AlertRouting, IngestPipeline,
+};
 use sentinel_workers::metrics::worker_metrics::WorkerMetrics;
+use sentinel_workers::notifier::replay::DlqReplayer;
 use sentinel_workers::registry::WorkerRegistry;
 use sentinel_workers::shutdown::spawn_signal_handler;
 use sentinel_workers::storage::{migrator, wait_for_db, WaitConfig};
@@ -73,10 +77,22 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         config.backpressure.max_concurrent_batches,
     ));
 
-    let alert_engine = match AlertEngine::new(pool.clone()).await {
+    let envelope = Envelope::from_env().unwrap_or_else(|e| {
+        tracing::error!(target: "auth", error = %e, "Failed to load master key");
+        std::process::exit(1);
+    });
+    if let Some(key_id) = envelope.key_id() {
+        tracing::info!(target: "auth", key_id, "Master key loaded for notifier credentials");
+    }
+
+    let alert_engine = match AlertEngine::new(pool.clone(), &config.alerts, envelope.clone()).await
+    {
         Ok(engine) => {
             let engine = Arc::new(engine);
             engine.spawn_reload_loop(cancel.clone());
+            engine.spawn_sweep_loop(config.alerts.absent_check_interval, cancel.clone());
+            engine.spawn_group_loop(cancel.clone());
+            engine.spawn_escalation_loop(cancel.clone());
             tracing::info!(target: "alert", "Alert engine active");
             Some(engine)
         }
@@ -86,18 +102,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         }
     };
 
-    let api_pool = pool.clone();
+    DlqReplayer::new(pool.clone(), config.dlq.clone(), envelope.clone()).spawn(cancel.clone());
+    tracing::info!(
+        target: "notify",
+        auto_retry = config.dlq.auto_retry,
+        "DLQ replayer active"
+    );
 
-    let pipeline = {
-        let p = IngestPipeline::new(pool, worker_metrics.clone());
-        match alert_engine {
-            Some(engine) => Arc::new(p.with_alert_engine(engine)),
-            None => Arc::new(p),
-        }
-    };
+    let api_pool = pool.clone();
 
     let sw = logging::stopwatch();
-    let (js, _client) = match connect_jetstream(&config.nats_url).await {
+    let (js, client) = match connect_jetstream(&config.nats_url).await {
         Ok(pair) => pair,
         Err(e) => {
             tracing::error!(
@@ -143,6 +158,45 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         None
     };
 
+    let mut pipeline = IngestPipeline::new(pool, worker_metrics.clone());
+    if let Some(engine) = alert_engine {
+        let mut owner_filter = None;
+        if let (Some(reg), true) = (&registry, config.registry.partitioning) {
+            let partitioner = reg.partitioner();
+            let routing = Arc::new(AlertRouting::new(
+                Arc::clone(&partitioner),
+                client.clone(),
+                worker_metrics.clone(),
+            ));
+            match routing
+                .spawn_listener(Arc::clone(&engine), cancel.clone())
+                .await
+            {
+                Ok(()) => {
+                    engine.spawn_rebalance_loop(Arc::clone(&partitioner), cancel.clone());
+                    pipeline = pipeline.with_alert_routing(routing);
+                    owner_filter = Some(partitioner);
+                    tracing::info!(target: "registry", "Alert partitioning active");
+                }
+                Err(e) => {
+                    tracing::warn!(target: "registry", error = %e, "Alert routing unavailable, evaluating all agents locally");
+                }
+            }
+        }
+        if let Err(e) = spawn_presence_listener(
+            client.clone(),
+            Arc::clone(&engine),
+            owner_filter,
+            cancel.clone(),
+        )
+        .await
+        {
+            tracing::warn!(target: "alert", error = %e, "Presence listener unavailable, agent-offline alerts disabled");
+        }
+        pipeline = pipeline.with_alert_engine(engine);
+    }
+    let pipeline = Arc::new(pipeline);
+
     let consumer_loop = ConsumerLoop::new(consumer, config.batch_size, cancel.clone());
     let in_flight = consumer_loop.in_flight();
 
@@ -53,6 +53,18 @@ pub fn render_prometheus(m: &Arc<WorkerMetrics>, worker_id: &str, pool: &PgPool)
         m.notifications_failed_val(),
         worker_id,
     );
+    write_counter(
+        &mut out,
+        "sentinel_worker_alert_batches_forwarded_total",
+        m.alert_batches_forwarded_val(),
+        worker_id,
+    );
+    write_counter(
+        &mut out,
+        "sentinel_worker_alert_batches_received_total",
+        m.alert_batches_received_val(),
+        worker_id,
+    );
 
     let (sum, count) = m.processing_latency_vals();
     write_summary(
@@ -40,7 +40,7 @@ impl StageCounter {
         StageSnapshot {
             count,
             total_us: sum,
-            avg_us: if count > 0 { sum / count } else { 0 },
+            avg_us: sum.checked_div(count).unwrap_or(0),
         }
     }
 }
@@ -12,6 +12,8 @@ pub struct WorkerMetrics {
     alerts_fired: AtomicU64,
     notifications_sent: AtomicU64,
     notifications_failed: AtomicU64,
+    alert_batches_forwarded: AtomicU64,
+    alert_batches_received: AtomicU64,
     processing_latency_sum_us: AtomicU64,
     processing_latency_count: AtomicU64,
     db_latency_sum_us: AtomicU64,
@@ -29,6 +31,8 @@ impl WorkerMetrics {
             alerts_fired: AtomicU64::new(0),
             notifications_sent: AtomicU64::new(0),
             notifications_failed: AtomicU64::new(0),
+            alert_batches_forwarded: AtomicU64::new(0),
+            alert_batches_received: AtomicU64::new(0),
             processing_latency_sum_us: AtomicU64::new(0),
             processing_latency_count: AtomicU64::new(0),
             db_latency_sum_us: AtomicU64::new(0),
@@ -68,6 +72,14 @@ impl WorkerMetrics {
         self.notifications_failed.fetch_add(1, Ordering::Relaxed);
     }
 
+    pub fn inc_alert_batches_forwarded(&self) {
+        self.alert_batches_forwarded.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn inc_alert_batches_received(&self) {
+        self.alert_batches_received.fetch_add(1, Ordering::Relaxed);
+    }
+
     pub fn record_processing_latency(&self, start: Instant) {
         let us = start.elapsed().as_micros() as u64;
         self.processing_latency_sum_us
@@ -114,6 +126,14 @@ impl WorkerMetrics {
         self.notifications_failed.load(Ordering::Relaxed)
     }
 
+    pub fn alert_batches_forwarded_val(&self) -> u64 {
+        self.alert_batches_forwarded.load(Ordering::Relaxed)
+    }
+
+    pub fn alert_batches_received_val(&self) -> u64 {
+        self.alert_batches_received.load(Ordering::Relaxed)
+    }
+
     pub fn processing_latency_vals(&self) -> (u64, u64) {
         (
             self.processing_latency_sum_us.load(Ordering::Relaxed),
@@ -140,6 +160,8 @@ impl Default for WorkerMetrics {
             alerts_fired: AtomicU64::new(0),
             notifications_sent: AtomicU64::new(0),
             notifications_failed: AtomicU64::new(0),
+            alert_batches_forwarded: AtomicU64::new(0),
+            alert_batches_received: AtomicU64::new(0),
             processing_latency_sum_us: AtomicU64::new(0),
             processing_latency_count: AtomicU64::new(0),
             db_latency_sum_us: AtomicU64::new(0),
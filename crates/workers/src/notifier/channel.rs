@@ -1,9 +1,25 @@
-use crate::alert::AlertEvent;
+use crate::alert::{AlertEvent, AlertGroup};
 
 #[tonic::async_trait]
 pub trait Notifier: Send + Sync {
     fn name(&self) -> &str;
     async fn send(&self, event: &AlertEvent) -> Result<(), NotifyError>;
+    async fn send_group(&self, group: &AlertGroup) -> Result<(), NotifyError>;
+}
+
+#[tonic::async_trait]
+impl Notifier for Box<dyn Notifier> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    async fn send(&self, event: &AlertEvent) -> Result<(), NotifyError> {
+        (**self).send(event).await
+    }
+
+    async fn send_group(&self, group: &AlertGroup) -> Result<(), NotifyError> {
+        (**self).send_group(group).await
+    }
 }
 
 #[derive(Debug)]
@@ -1,11 +1,14 @@
 use reqwest::Client;
+use sentinel_common::template::MessageTemplate;
 
 use super::channel::{Notifier, NotifyError};
-use crate::alert::AlertEvent;
+use super::registry::{config_str, BuildResult};
+use crate::alert::{AlertEvent, AlertGroup, Severity};
 
 pub struct DiscordNotifier {
     webhook_url: String,
     client: Client,
+    template: MessageTemplate,
 }
 
 impl DiscordNotifier {
@@ -13,8 +16,20 @@ impl DiscordNotifier {
         Self {
             webhook_url,
             client: Client::new(),
+            template: MessageTemplate::default(),
         }
     }
+
+    pub fn with_template(mut self, template: MessageTemplate) -> Self {
+        self.template = template;
+        self
+    }
+}
+
+pub fn from_config(config: &serde_json::Value, template: MessageTemplate) -> BuildResult {
+    Ok(Box::new(
+        DiscordNotifier::new(config_str(config, "webhook_url")?).with_template(template),
+    ))
 }
 
 #[tonic::async_trait]
@@ -24,29 +39,46 @@ impl Notifier for DiscordNotifier {
     }
 
     async fn send(&self, event: &AlertEvent) -> Result<(), NotifyError> {
-        let color = match event.severity {
-            crate::alert::Severity::Info => 0x36a64f,
-            crate::alert::Severity::Warning => 0xf2c744,
-            crate::alert::Severity::Critical => 0xd32f2f,
-        };
-
-        let payload = serde_json::json!({
-            "embeds": [{
-                "title": format!("[{}] {}", event.severity_str(), event.rule_name),
-                "color": color,
-                "fields": [
+        let color = severity_color(event.severity);
+
+        let msg = self.template.render(&event.template_context());
+        let title = msg
+            .title
+            .unwrap_or_else(|| format!("[{}] {}", event.severity_str(), event.rule_name));
+
+        let mut embed = serde_json::json!({ "title": title, "color": color });
+        match msg.body {
+            Some(body) => embed["description"] = body.into(),
+            None => {
+                embed["fields"] = serde_json::json!([
                     { "name": "Status", "value": event.status_str(), "inline": true },
                     { "name": "Agent", "value": &event.agent_id, "inline": true },
-                    { "name": "Metric", "value": &event.metric_name, "inline": true },
+                    { "name": "Metric", "value": event.series_name(), "inline": true },
                     { "name": "Value", "value": format!("{:.2}", event.value), "inline": true },
                     { "name": "Threshold", "value": format!("{:.2}", event.threshold), "inline": true },
-                ],
-            }]
+                ])
+            }
+        }
+        let payload = serde_json::json!({ "embeds": [embed] });
+        self.post(&payload).await
+    }
+
+    async fn send_group(&self, group: &AlertGroup) -> Result<(), NotifyError> {
+        let msg = self.template.render_group(&group.template_context());
+        let embed = serde_json::json!({
+            "title": msg.title.unwrap_or_else(|| group.title()),
+            "description": msg.body.unwrap_or_else(|| group.body()),
+            "color": severity_color(group.severity()),
         });
+        self.post(&serde_json::json!({ "embeds": [embed] })).await
+    }
+}
 
+impl DiscordNotifier {
+    async fn post(&self, payload: &serde_json::Value) -> Result<(), NotifyError> {
         self.client
             .post(&self.webhook_url)
-            .json(&payload)
+            .json(payload)
             .send()
             .await
             .map_err(|e| NotifyError(e.to_string()))?
@@ -56,3 +88,11 @@ impl Notifier for DiscordNotifier {
         Ok(())
     }
 }
+
+fn severity_color(severity: Severity) -> u32 {
+    match severity {
+        Severity::Info => 0x36a64f,
+        Severity::Warning => 0xf2c744,
+        Severity::Critical => 0xd32f2f,
+    }
+}
@@ -1,32 +1,42 @@
+use sentinel_common::envelope::Envelope;
+use sentinel_common::grouping::GroupPolicy;
 use sqlx::PgPool;
 
-use super::discord::DiscordNotifier;
+use super::channel::Notifier;
 use super::dlq::DlqWriter;
-use super::gotify::GotifyNotifier;
-use super::ntfy::NtfyNotifier;
-use super::opsgenie::OpsGenieNotifier;
-use super::pagerduty::PagerDutyNotifier;
+use super::grouper::Grouper;
+use super::history::{DeliveryRecord, DeliveryStatus, HistoryWriter};
+use super::registry::NotifierRegistry;
 use super::retry::RetryNotifier;
-use super::slack::SlackNotifier;
-use super::smtp::SmtpNotifier;
-use super::teams::TeamsNotifier;
-use super::telegram::TelegramNotifier;
-use super::webhook::WebhookNotifier;
-use crate::alert::AlertEvent;
+use crate::alert::{AlertEvent, AlertGroup};
 use crate::storage::{NotifierConfigLoader, NotifierConfigRow};
 
 pub struct Dispatcher {
     loader: NotifierConfigLoader,
+    grouper: Grouper,
+    registry: NotifierRegistry,
 }
 
 impl Dispatcher {
-    pub fn new(pool: PgPool) -> Self {
+    pub fn new(pool: PgPool, envelope: Envelope) -> Self {
         Self {
-            loader: NotifierConfigLoader::new(pool),
+            loader: NotifierConfigLoader::new(pool, envelope),
+            grouper: Grouper::new(),
+            registry: NotifierRegistry::default(),
         }
     }
 
-    pub async fn dispatch(&self, event: &AlertEvent, notifier_ids: &[String]) {
+    pub fn with_registry(mut self, registry: NotifierRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    pub async fn dispatch(
+        &self,
+        event: &AlertEvent,
+        notifier_ids: &[String],
+        grouping: Option<&GroupPolicy>,
+    ) {
         let configs = match self.loader.load_by_ids(notifier_ids).await {
             Ok(c) => c,
             Err(e) => {
@@ -35,7 +45,19 @@ impl Dispatcher {
             }
         };
 
+        let now_ms = chrono::Utc::now().timestamp_millis();
         for cfg in &configs {
+            let policy = match grouping {
+                Some(p) => Some(p.clone()),
+                None => GroupPolicy::from_config(&cfg.config).unwrap_or_else(|e| {
+                    tracing::warn!(target: "notify", notifier = %cfg.name, error = %e, "Ignoring invalid grouping");
+                    None
+                }),
+            };
+            if let Some(policy) = policy {
+                self.grouper.observe(&cfg.id, &policy, event, now_ms);
+                continue;
+            }
             if let Err(e) = self.send_one(cfg, event).await {
                 tracing::error!(
                     target: "notify",
@@ -47,131 +69,127 @@ impl Dispatcher {
         }
     }
 
+    pub fn take_due_groups(&self, now_ms: i64) -> Vec<(String, AlertGroup)> {
+        self.grouper.take_due(now_ms)
+    }
+
+    pub async fn dispatch_group(&self, notifier_id: &str, group: &AlertGroup) {
+        let cfg = match self.loader.load_by_ids(&[notifier_id.to_string()]).await {
+            Ok(c) => c.into_iter().next(),
+            Err(e) => {
+                tracing::error!(target: "notify", error = %e, "failed to load notifier configs");
+                return;
+            }
+        };
+        let Some(cfg) = cfg else {
+            tracing::debug!(target: "notify", notifier = %notifier_id, "Dropping group for removed notifier");
+            return;
+        };
+
+        let result = match self.build_notifier(&cfg) {
+            Ok(notifier) => RetryNotifier::new(notifier, 2, 500)
+                .with_dlq(DlqWriter::new(self.dlq_pool()))
+                .with_history(HistoryWriter::new(self.loader.pool(), &cfg.id, &cfg.ntype))
+                .send_group(group)
+                .await
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = result {
+            tracing::error!(
+                target: "notify",
+                notifier = %cfg.name,
+                group = %group.key,
+                alerts = group.alerts.len(),
+                error = %e,
+                "group notification failed"
+            );
+        }
+    }
+
     async fn send_one(
         &self,
         cfg: &NotifierConfigRow,
         event: &AlertEvent,
     ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-        let dlq = || DlqWriter::new(self.dlq_pool());
-
-        match cfg.ntype.as_str() {
-            "discord" => {
-                let url = extract_str(&cfg.config, "webhook_url")?;
-                RetryNotifier::new(DiscordNotifier::new(url), 2, 500)
-                    .with_dlq(dlq())
-                    .send(event)
-                    .await?;
-            }
-            "slack" => {
-                let url = extract_str(&cfg.config, "webhook_url")?;
-                RetryNotifier::new(SlackNotifier::new(url), 2, 500)
-                    .with_dlq(dlq())
-                    .send(event)
-                    .await?;
-            }
-            "webhook" => {
-                let url = extract_str(&cfg.config, "url")?;
-                let secret = extract_bytes(&cfg.config, "secret");
-                RetryNotifier::new(WebhookNotifier::new(url, secret), 2, 500)
-                    .with_dlq(dlq())
-                    .send(event)
-                    .await?;
-            }
-            "smtp" => {
-                let host = extract_str(&cfg.config, "host")?;
-                let port = cfg
-                    .config
-                    .get("port")
-                    .and_then(|v| v.as_u64())
-                    .unwrap_or(587) as u16;
-                let username = extract_str(&cfg.config, "username").unwrap_or_default();
-                let password = extract_str(&cfg.config, "password").unwrap_or_default();
-                let from = extract_str(&cfg.config, "from")?;
-                let to = extract_str(&cfg.config, "to")?;
-                let notifier = SmtpNotifier::new(&host, port, &username, &password, from, to);
-                RetryNotifier::new(notifier, 2, 500)
-                    .with_dlq(dlq())
-                    .send(event)
-                    .await?;
-            }
-            "telegram" => {
-                let bot_token = extract_str(&cfg.config, "bot_token")?;
-                let chat_id = extract_str(&cfg.config, "chat_id")?;
-                RetryNotifier::new(TelegramNotifier::new(bot_token, chat_id), 2, 500)
-                    .with_dlq(dlq())
-                    .send(event)
-                    .await?;
-            }
-            "pagerduty" => {
-                let routing_key = extract_str(&cfg.config, "routing_key")?;
-                RetryNotifier::new(PagerDutyNotifier::new(routing_key), 2, 500)
-                    .with_dlq(dlq())
-                    .send(event)
-                    .await?;
-            }
-            "teams" => {
-                let url = extract_str(&cfg.config, "webhook_url")?;
-                RetryNotifier::new(TeamsNotifier::new(url), 2, 500)
-                    .with_dlq(dlq())
-                    .send(event)
-                    .await?;
-            }
-            "opsgenie" => {
-                let api_key = extract_str(&cfg.config, "api_key")?;
-                RetryNotifier::new(OpsGenieNotifier::new(api_key), 2, 500)
-                    .with_dlq(dlq())
-                    .send(event)
-                    .await?;
-            }
-            "gotify" => {
-                let server_url = extract_str(&cfg.config, "server_url")?;
-                let token = extract_str(&cfg.config, "token")?;
-                RetryNotifier::new(GotifyNotifier::new(server_url, token), 2, 500)
-                    .with_dlq(dlq())
-                    .send(event)
-                    .await?;
-            }
-            "ntfy" => {
-                let server_url = extract_str(&cfg.config, "server_url")?;
-                let topic = extract_str(&cfg.config, "topic")?;
-                let token = cfg
-                    .config
-                    .get("token")
-                    .and_then(|v| v.as_str())
-                    .map(String::from);
-                RetryNotifier::new(NtfyNotifier::new(server_url, topic, token), 2, 500)
-                    .with_dlq(dlq())
-                    .send(event)
-                    .await?;
-            }
-            other => {
-                tracing::warn!(target: "notify", ntype = other, "unknown notifier type");
+        let history = HistoryWriter::new(self.loader.pool(), &cfg.id, &cfg.ntype);
+
+        let notifier = match self.build_notifier(cfg) {
+            Ok(n) => n,
+            Err(e) => {
+                let record = DeliveryRecord {
+                    id: uuid::Uuid::new_v4().to_string(),
+                    alert_id: event.id.clone(),
+                    status: DeliveryStatus::Failed,
+                    error: Some(e.to_string()),
+                    attempts: 0,
+                    duration_ms: 0,
+                };
+                if let Err(err) = history.record(&record).await {
+                    tracing::error!(target: "notify", error = %err, "Failed to record notification history");
+                }
+                return Err(e);
             }
-        }
+        };
+
+        RetryNotifier::new(notifier, 2, 500)
+            .with_dlq(DlqWriter::new(self.dlq_pool()))
+            .with_history(history)
+            .send(event)
+            .await?;
         Ok(())
     }
 
-    fn dlq_pool(&self) -> PgPool {
-        self.loader.pool()
+    pub async fn replay(
+        &self,
+        notifier_id: &str,
+        event: &AlertEvent,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let cfg = self.load_one(notifier_id).await?;
+        let notifier = self.build_notifier(&cfg)?;
+        RetryNotifier::new(notifier, 0, 0)
+            .with_history(HistoryWriter::new(self.loader.pool(), &cfg.id, &cfg.ntype))
+            .send(event)
+            .await?;
+        Ok(())
     }
-}
 
-fn extract_str(
-    config: &serde_json::Value,
-    key: &str,
-) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-    config
-        .get(key)
-        .and_then(|v| v.as_str())
-        .map(String::from)
-        .ok_or_else(|| format!("missing '{key}' in notifier config").into())
-}
+    pub async fn replay_group(
+        &self,
+        notifier_id: &str,
+        group: &AlertGroup,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let cfg = self.load_one(notifier_id).await?;
+        let notifier = self.build_notifier(&cfg)?;
+        RetryNotifier::new(notifier, 0, 0)
+            .with_history(HistoryWriter::new(self.loader.pool(), &cfg.id, &cfg.ntype))
+            .send_group(group)
+            .await?;
+        Ok(())
+    }
 
-fn extract_bytes(config: &serde_json::Value, key: &str) -> Vec<u8> {
-    config
-        .get(key)
-        .and_then(|v| v.as_str())
-        .unwrap_or("")
-        .as_bytes()
-        .to_vec()
+    async fn load_one(
+        &self,
+        notifier_id: &str,
+    ) -> Result<NotifierConfigRow, Box<dyn std::error::Error + Send + Sync>> {
+        let cfg = self
+            .loader
+            .load_by_ids(&[notifier_id.to_string()])
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| format!("notifier '{notifier_id}' not found or disabled"))?;
+        Ok(cfg)
+    }
+
+    fn build_notifier(
+        &self,
+        cfg: &NotifierConfigRow,
+    ) -> Result<Box<dyn Notifier>, Box<dyn std::error::Error + Send + Sync>> {
+        self.registry.build(&cfg.ntype, &cfg.config)
+    }
+
+    fn dlq_pool(&self) -> PgPool {
+        self.loader.pool()
+    }
 }
@@ -16,12 +16,13 @@ impl DlqWriter {
         payload: &serde_json::Value,
         error: &str,
         attempts: u32,
+        history_id: Option<&str>,
     ) -> Result<(), sqlx::Error> {
         let id = uuid::Uuid::new_v4().to_string();
         sqlx::query(
             r#"INSERT INTO notifications_dlq
-               (id, alert_id, notifier, payload, error, attempts)
-               VALUES ($1, $2, $3, $4, $5, $6)"#,
+               (id, alert_id, notifier, payload, error, attempts, history_id)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)"#,
         )
         .bind(&id)
         .bind(alert_id)
@@ -29,6 +30,7 @@ impl DlqWriter {
         .bind(payload)
         .bind(error)
         .bind(attempts as i32)
+        .bind(history_id)
         .execute(&self.pool)
         .await?;
         Ok(())
@@ -51,6 +53,60 @@ impl DlqWriter {
         Ok(rows)
     }
 
+    pub async fn claim(&self, policy: &ClaimPolicy) -> Result<Vec<ReplayEntry>, sqlx::Error> {
+        let rows = sqlx::query_as::<_, ReplayEntry>(
+            "UPDATE notifications_dlq d
+             SET replay_requested_at = NULL,
+                 next_retry_at = NOW() + ($4::bigint * INTERVAL '1 second')
+             FROM notification_history h
+             WHERE h.id = d.history_id
+               AND d.id IN (
+                   SELECT id FROM notifications_dlq
+                   WHERE history_id IS NOT NULL
+                     AND (replay_requested_at IS NOT NULL
+                          OR ($1
+                              AND created_at > NOW() - ($3::bigint * INTERVAL '1 second')
+                              AND COALESCE(next_retry_at,
+                                           last_attempt + ($2::bigint * INTERVAL '1 second'))
+                                  <= NOW()))
+                   ORDER BY created_at
+                   LIMIT $5
+                   FOR UPDATE SKIP LOCKED)
+             RETURNING d.id, d.alert_id, h.notifier_id, d.payload, d.retry_count",
+        )
+        .bind(policy.auto_retry)
+        .bind(policy.base_delay_secs)
+        .bind(policy.max_age_secs)
+        .bind(policy.lease_secs)
+        .bind(policy.limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows)
+    }
+
+    pub async fn record_failure(
+        &self,
+        id: &str,
+        error: &str,
+        retry_in_secs: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE notifications_dlq
+             SET error = $2,
+                 attempts = attempts + 1,
+                 retry_count = retry_count + 1,
+                 last_attempt = NOW(),
+                 next_retry_at = NOW() + ($3::bigint * INTERVAL '1 second')
+             WHERE id = $1",
+        )
+        .bind(id)
+        .bind(error)
+        .bind(retry_in_secs)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
     pub async fn delete(&self, id: &str) -> Result<bool, sqlx::Error> {
         let result = sqlx::query("DELETE FROM notifications_dlq WHERE id = $1")
             .bind(id)
@@ -60,6 +116,23 @@ impl DlqWriter {
     }
 }
 
+pub struct ClaimPolicy {
+    pub auto_retry: bool,
+    pub base_delay_secs: i64,
+    pub max_age_secs: i64,
+    pub lease_secs: i64,
+    pub limit: i64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub struct ReplayEntry {
+    pub id: String,
+    pub alert_id: String,
+    pub notifier_id: String,
+    pub payload: serde_json::Value,
+    pub retry_count: i32,
+}
+
 #[derive(Debug, sqlx::FromRow)]
 pub struct DlqEntry {
     pub id: String,
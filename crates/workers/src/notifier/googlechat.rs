@@ -0,0 +1,99 @@
+use reqwest::Client;
+use sentinel_common::template::MessageTemplate;
+
+use super::channel::{Notifier, NotifyError};
+use super::registry::{config_str, BuildResult};
+use crate::alert::{AlertEvent, AlertGroup, AlertStatus};
+
+pub struct GoogleChatNotifier {
+    webhook_url: String,
+    client: Client,
+    template: MessageTemplate,
+}
+
+impl GoogleChatNotifier {
+    pub fn new(webhook_url: String) -> Self {
+        Self {
+            webhook_url,
+            client: Client::new(),
+            template: MessageTemplate::default(),
+        }
+    }
+
+    pub fn with_template(mut self, template: MessageTemplate) -> Self {
+        self.template = template;
+        self
+    }
+}
+
+pub fn from_config(config: &serde_json::Value, template: MessageTemplate) -> BuildResult {
+    Ok(Box::new(
+        GoogleChatNotifier::new(config_str(config, "webhook_url")?).with_template(template),
+    ))
+}
+
+#[tonic::async_trait]
+impl Notifier for GoogleChatNotifier {
+    fn name(&self) -> &str {
+        "googlechat"
+    }
+
+    async fn send(&self, event: &AlertEvent) -> Result<(), NotifyError> {
+        let msg = self.template.render(&event.template_context());
+        let title = msg.title.unwrap_or_else(|| {
+            format!(
+                "{} [{}] {}",
+                status_emoji(event.status),
+                event.severity_str(),
+                event.rule_name
+            )
+        });
+        let body = msg.body.unwrap_or_else(|| {
+            format!(
+                "Agent: {}\nMetric: {}\nValue: {:.2}\nThreshold: {:.2}",
+                event.agent_id,
+                event.series_name(),
+                event.value,
+                event.threshold
+            )
+        });
+        self.post(&title, &body, &event.fingerprint).await
+    }
+
+    async fn send_group(&self, group: &AlertGroup) -> Result<(), NotifyError> {
+        let msg = self.template.render_group(&group.template_context());
+        let title = msg
+            .title
+            .unwrap_or_else(|| format!("{} {}", status_emoji(group.status()), group.title()));
+        let body = msg.body.unwrap_or_else(|| group.body());
+        self.post(&title, &body, &group.key).await
+    }
+}
+
+impl GoogleChatNotifier {
+    async fn post(&self, title: &str, body: &str, thread_key: &str) -> Result<(), NotifyError> {
+        let payload = serde_json::json!({
+            "text": format!("*{title}*\n{body}"),
+            "thread": { "threadKey": thread_key },
+        });
+
+        self.client
+            .post(&self.webhook_url)
+            .query(&[("messageReplyOption", "REPLY_MESSAGE_FALLBACK_TO_NEW_THREAD")])
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| NotifyError(e.to_string()))?
+            .error_for_status()
+            .map_err(|e| NotifyError(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+fn status_emoji(status: AlertStatus) -> &'static str {
+    match status {
+        AlertStatus::Firing => "\u{1F6A8}",
+        AlertStatus::Resolved => "\u{2705}",
+    }
+}
@@ -1,12 +1,15 @@
 use reqwest::Client;
+use sentinel_common::template::MessageTemplate;
 
 use super::channel::{Notifier, NotifyError};
-use crate::alert::AlertEvent;
+use super::registry::{config_str, BuildResult};
+use crate::alert::{AlertEvent, AlertGroup, AlertStatus, Severity};
 
 pub struct GotifyNotifier {
     server_url: String,
     token: String,
     client: Client,
+    template: MessageTemplate,
 }
 
 impl GotifyNotifier {
@@ -16,8 +19,22 @@ impl GotifyNotifier {
             server_url,
             token,
             client: Client::new(),
+            template: MessageTemplate::default(),
         }
     }
+
+    pub fn with_template(mut self, template: MessageTemplate) -> Self {
+        self.template = template;
+        self
+    }
+}
+
+pub fn from_config(config: &serde_json::Value, template: MessageTemplate) -> BuildResult {
+    let server_url = config_str(config, "server_url")?;
+    let token = config_str(config, "token")?;
+    Ok(Box::new(
+        GotifyNotifier::new(server_url, token).with_template(template),
+    ))
 }
 
 #[tonic::async_trait]
@@ -27,32 +44,54 @@ impl Notifier for GotifyNotifier {
     }
 
     async fn send(&self, event: &AlertEvent) -> Result<(), NotifyError> {
-        let priority = match event.severity {
-            crate::alert::Severity::Info => 2,
-            crate::alert::Severity::Warning => 5,
-            crate::alert::Severity::Critical => 8,
-        };
+        let priority = priority(event.severity);
 
         let emoji = match event.status {
-            crate::alert::AlertStatus::Firing => "\u{1F6A8}",
-            crate::alert::AlertStatus::Resolved => "\u{2705}",
+            AlertStatus::Firing => "\u{1F6A8}",
+            AlertStatus::Resolved => "\u{2705}",
         };
 
-        let title = format!(
-            "{emoji} [{severity}] {rule}",
-            severity = event.severity_str(),
-            rule = event.rule_name,
-        );
-
-        let message = format!(
-            "Status: {status}\nAgent: {agent}\nMetric: {metric}\nValue: {value:.2}\nThreshold: {threshold:.2}",
-            status = event.status_str(),
-            agent = event.agent_id,
-            metric = event.metric_name,
-            value = event.value,
-            threshold = event.threshold,
-        );
+        let msg = self.template.render(&event.template_context());
+
+        let title = msg.title.unwrap_or_else(|| {
+            format!(
+                "{emoji} [{severity}] {rule}",
+                severity = event.severity_str(),
+                rule = event.rule_name,
+            )
+        });
+
+        let message = msg.body.unwrap_or_else(|| {
+            format!(
+                "Status: {status}\nAgent: {agent}\nMetric: {metric}\nValue: {value:.2}\nThreshold: {threshold:.2}",
+                status = event.status_str(),
+                agent = event.agent_id,
+                metric = event.series_name(),
+                value = event.value,
+                threshold = event.threshold,
+            )
+        });
+
+        self.post(&title, &message, priority).await
+    }
+
+    async fn send_group(&self, group: &AlertGroup) -> Result<(), NotifyError> {
+        let emoji = match group.status() {
+            AlertStatus::Firing => "\u{1F6A8}",
+            AlertStatus::Resolved => "\u{2705}",
+        };
+        let msg = self.template.render_group(&group.template_context());
+        let title = msg
+            .title
+            .unwrap_or_else(|| format!("{emoji} {}", group.title()));
+        let message = msg.body.unwrap_or_else(|| group.body());
+        self.post(&title, &message, priority(group.severity()))
+            .await
+    }
+}
 
+impl GotifyNotifier {
+    async fn post(&self, title: &str, message: &str, priority: u8) -> Result<(), NotifyError> {
         let url = format!("{}/message", self.server_url);
 
         let payload = serde_json::json!({
@@ -77,3 +116,11 @@ impl Notifier for GotifyNotifier {
         Ok(())
     }
 }
+
+fn priority(severity: Severity) -> u8 {
+    match severity {
+        Severity::Info => 2,
+        Severity::Warning => 5,
+        Severity::Critical => 8,
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+use sentinel_common::grouping::GroupPolicy;
+
+use crate::alert::{AlertEvent, AlertGroup, AlertStatus};
+
+#[derive(Default)]
+pub struct Grouper {
+    groups: Mutex<HashMap<(String, String), GroupState>>,
+}
+
+struct GroupState {
+    policy: GroupPolicy,
+    labels: BTreeMap<String, String>,
+    alerts: BTreeMap<String, AlertEvent>,
+    dirty: bool,
+    next_flush_ms: i64,
+    last_sent_ms: Option<i64>,
+}
+
+impl Grouper {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn observe(
+        &self,
+        notifier_id: &str,
+        policy: &GroupPolicy,
+        event: &AlertEvent,
+        now_ms: i64,
+    ) {
+        let labels = AlertGroup::labels_for(policy, event);
+        let key = (notifier_id.to_string(), AlertGroup::key_for(&labels));
+        let mut groups = self.groups.lock().unwrap();
+        let state = groups.entry(key).or_insert_with(|| GroupState {
+            policy: policy.clone(),
+            labels,
+            alerts: BTreeMap::new(),
+            dirty: false,
+            next_flush_ms: now_ms + secs_to_ms(policy.group_wait_secs),
+            last_sent_ms: None,
+        });
+        state.policy = policy.clone();
+        state
+            .alerts
+            .insert(event.fingerprint.clone(), event.clone());
+        state.dirty = true;
+    }
+
+    pub fn take_due(&self, now_ms: i64) -> Vec<(String, AlertGroup)> {
+        let mut groups = self.groups.lock().unwrap();
+        let mut due = Vec::new();
+
+        groups.retain(|(notifier_id, key), state| {
+            if let Some(group) = state.flush(key, now_ms) {
+                due.push((notifier_id.clone(), group));
+            }
+            state.dirty || !state.alerts.is_empty()
+        });
+        due
+    }
+
+    pub fn len(&self) -> usize {
+        self.groups.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl GroupState {
+    fn flush(&mut self, key: &str, now_ms: i64) -> Option<AlertGroup> {
+        let repeat = if self.dirty {
+            if now_ms < self.next_flush_ms {
+                return None;
+            }
+            false
+        } else {
+            let interval = secs_to_ms(self.policy.repeat_interval_secs);
+            let last = self.last_sent_ms?;
+            if interval == 0 || self.alerts.is_empty() || now_ms < last + interval {
+                return None;
+            }
+            true
+        };
+
+        let group = AlertGroup {
+            key: key.to_string(),
+            labels: self.labels.clone(),
+            alerts: self.alerts.values().cloned().collect(),
+            repeat,
+        };
+        self.alerts.retain(|_, a| a.status == AlertStatus::Firing);
+        self.dirty = false;
+        self.last_sent_ms = Some(now_ms);
+        self.next_flush_ms = now_ms + secs_to_ms(self.policy.group_interval_secs);
+        Some(group)
+    }
+}
+
+fn secs_to_ms(secs: u64) -> i64 {
+    secs.saturating_mul(1000).min(i64::MAX as u64) as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alert::Severity;
+
+    fn policy() -> GroupPolicy {
+        GroupPolicy {
+            group_by: vec!["rule_id".into()],
+            group_wait_secs: 30,
+            group_interval_secs: 300,
+            repeat_interval_secs: 3600,
+        }
+    }
+
+    fn event(agent: &str, status: AlertStatus) -> AlertEvent {
+        AlertEvent {
+            id: format!("e-{agent}"),
+            fingerprint: format!("fp-{agent}"),
+            rule_id: "r-1".into(),
+            rule_name: "cpu high".into(),
+            agent_id: agent.into(),
+            metric_name: "cpu".into(),
+            labels: Default::default(),
+            severity: Severity::Warning,
+            status,
+            value: 95.0,
+            threshold: 90.0,
+            fired_at_ms: 0,
+            resolved_at_ms: None,
+            annotations: Default::default(),
+        }
+    }
+
+    #[test]
+    fn waits_before_first_notification() {
+        let g = Grouper::new();
+        g.observe("n-1", &policy(), &event("a-1", AlertStatus::Firing), 0);
+        g.observe("n-1", &policy(), &event("a-2", AlertStatus::Firing), 10_000);
+
+        assert!(g.take_due(29_999).is_empty());
+        let due = g.take_due(30_000);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].0, "n-1");
+        assert_eq!(due[0].1.key, "rule_id=r-1");
+        assert_eq!(due[0].1.alerts.len(), 2);
+        assert!(!due[0].1.repeat);
+    }
+
+    #[test]
+    fn batches_later_changes_per_interval() {
+        let g = Grouper::new();
+        g.observe("n-1", &policy(), &event("a-1", AlertStatus::Firing), 0);
+        assert_eq!(g.take_due(30_000).len(), 1);
+
+        g.observe("n-1", &policy(), &event("a-2", AlertStatus::Firing), 40_000);
+        g.observe(
+            "n-1",
+            &policy(),
+            &event("a-1", AlertStatus::Resolved),
+            50_000,
+        );
+        assert!(g.take_due(100_000).is_empty());
+
+        let due = g.take_due(330_000);
+        assert_eq!(due.len(), 1);
+        let group = &due[0].1;
+        assert_eq!(group.firing_count(), 1);
+        assert_eq!(group.resolved_count(), 1);
+
+        g.observe(
+            "n-1",
+            &policy(),
+            &event("a-2", AlertStatus::Resolved),
+            400_000,
+        );
+        assert_eq!(g.take_due(630_000).len(), 1);
+        assert!(g.is_empty());
+    }
+
+    #[test]
+    fn repeats_still_firing_groups() {
+        let g = Grouper::new();
+        g.observe("n-1", &policy(), &event("a-1", AlertStatus::Firing), 0);
+        assert_eq!(g.take_due(30_000).len(), 1);
+
+        assert!(g.take_due(3_000_000).is_empty());
+        let due = g.take_due(3_630_000);
+        assert_eq!(due.len(), 1);
+        assert!(due[0].1.repeat);
+
+        let no_repeat = GroupPolicy {
+            repeat_interval_secs: 0,
+            ..policy()
+        };
+        let g = Grouper::new();
+        g.observe("n-1", &no_repeat, &event("a-1", AlertStatus::Firing), 0);
+        assert_eq!(g.take_due(30_000).len(), 1);
+        assert!(g.take_due(100_000_000).is_empty());
+    }
+
+    #[test]
+    fn groups_are_per_notifier() {
+        let g = Grouper::new();
+        let e = event("a-1", AlertStatus::Firing);
+        g.observe("n-1", &policy(), &e, 0);
+        g.observe("n-2", &policy(), &e, 0);
+        assert_eq!(g.len(), 2);
+        assert_eq!(g.take_due(30_000).len(), 2);
+    }
+}
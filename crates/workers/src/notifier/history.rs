@@ -0,0 +1,76 @@
+use sqlx::PgPool;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryStatus {
+    Sent,
+    Failed,
+    DeadLettered,
+}
+
+impl DeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sent => "sent",
+            Self::Failed => "failed",
+            Self::DeadLettered => "dead_lettered",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeliveryRecord {
+    pub id: String,
+    pub alert_id: String,
+    pub status: DeliveryStatus,
+    pub error: Option<String>,
+    pub attempts: u32,
+    pub duration_ms: u64,
+}
+
+pub struct HistoryWriter {
+    pool: PgPool,
+    notifier_id: String,
+    ntype: String,
+}
+
+impl HistoryWriter {
+    pub fn new(pool: PgPool, notifier_id: &str, ntype: &str) -> Self {
+        Self {
+            pool,
+            notifier_id: notifier_id.to_string(),
+            ntype: ntype.to_string(),
+        }
+    }
+
+    pub async fn record(&self, r: &DeliveryRecord) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"INSERT INTO notification_history
+               (id, alert_id, notifier_id, ntype, status, error, attempts, duration_ms)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+               ON CONFLICT (id) DO NOTHING"#,
+        )
+        .bind(&r.id)
+        .bind(&r.alert_id)
+        .bind(&self.notifier_id)
+        .bind(&self.ntype)
+        .bind(r.status.as_str())
+        .bind(&r.error)
+        .bind(r.attempts as i32)
+        .bind(r.duration_ms.min(i32::MAX as u64) as i32)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_strings_match_history_stats() {
+        assert_eq!(DeliveryStatus::Sent.as_str(), "sent");
+        assert_eq!(DeliveryStatus::Failed.as_str(), "failed");
+        assert_eq!(DeliveryStatus::DeadLettered.as_str(), "dead_lettered");
+    }
+}
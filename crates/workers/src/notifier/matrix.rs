@@ -0,0 +1,125 @@
+use reqwest::{Client, Url};
+use sentinel_common::template::MessageTemplate;
+
+use super::channel::{Notifier, NotifyError};
+use super::registry::{config_str, BuildResult};
+use crate::alert::{AlertEvent, AlertGroup, AlertStatus};
+
+pub struct MatrixNotifier {
+    homeserver_url: String,
+    access_token: String,
+    room_id: String,
+    client: Client,
+    template: MessageTemplate,
+}
+
+impl MatrixNotifier {
+    pub fn new(homeserver_url: String, access_token: String, room_id: String) -> Self {
+        let homeserver_url = homeserver_url.trim_end_matches('/').to_string();
+        Self {
+            homeserver_url,
+            access_token,
+            room_id,
+            client: Client::new(),
+            template: MessageTemplate::default(),
+        }
+    }
+
+    pub fn with_template(mut self, template: MessageTemplate) -> Self {
+        self.template = template;
+        self
+    }
+}
+
+pub fn from_config(config: &serde_json::Value, template: MessageTemplate) -> BuildResult {
+    let homeserver_url = config_str(config, "homeserver_url")?;
+    let access_token = config_str(config, "access_token")?;
+    let room_id = config_str(config, "room_id")?;
+    Ok(Box::new(
+        MatrixNotifier::new(homeserver_url, access_token, room_id).with_template(template),
+    ))
+}
+
+#[tonic::async_trait]
+impl Notifier for MatrixNotifier {
+    fn name(&self) -> &str {
+        "matrix"
+    }
+
+    async fn send(&self, event: &AlertEvent) -> Result<(), NotifyError> {
+        let msg = self.template.render(&event.template_context());
+        let title = msg.title.unwrap_or_else(|| {
+            format!(
+                "{} [{}] {}",
+                status_emoji(event.status),
+                event.severity_str(),
+                event.rule_name
+            )
+        });
+        let body = msg.body.unwrap_or_else(|| {
+            format!(
+                "Agent: {}\nMetric: {}\nValue: {:.2}\nThreshold: {:.2}",
+                event.agent_id,
+                event.series_name(),
+                event.value,
+                event.threshold
+            )
+        });
+        self.post(&title, &body).await
+    }
+
+    async fn send_group(&self, group: &AlertGroup) -> Result<(), NotifyError> {
+        let msg = self.template.render_group(&group.template_context());
+        let title = msg
+            .title
+            .unwrap_or_else(|| format!("{} {}", status_emoji(group.status()), group.title()));
+        let body = msg.body.unwrap_or_else(|| group.body());
+        self.post(&title, &body).await
+    }
+}
+
+impl MatrixNotifier {
+    fn message_url(&self) -> Result<Url, NotifyError> {
+        let mut url = Url::parse(&self.homeserver_url)
+            .map_err(|e| NotifyError(format!("invalid homeserver_url: {e}")))?;
+        url.path_segments_mut()
+            .map_err(|_| NotifyError("invalid homeserver_url".into()))?
+            .extend([
+                "_matrix",
+                "client",
+                "v3",
+                "rooms",
+                &self.room_id,
+                "send",
+                "m.room.message",
+                &uuid::Uuid::new_v4().to_string(),
+            ]);
+        Ok(url)
+    }
+
+    async fn post(&self, title: &str, body: &str) -> Result<(), NotifyError> {
+        let payload = serde_json::json!({
+            "msgtype": "m.text",
+            "body": format!("{title}\n{body}"),
+        });
+
+        self.client
+            .put(self.message_url()?)
+            .bearer_auth(&self.access_token)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| NotifyError(e.to_string()))?
+            .error_for_status()
+            .map_err(|e| NotifyError(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+fn status_emoji(status: AlertStatus) -> &'static str {
+    match status {
+        AlertStatus::Firing => "\u{1F6A8}",
+        AlertStatus::Resolved => "\u{2705}",
+    }
+}
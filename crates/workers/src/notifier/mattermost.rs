@@ -0,0 +1,159 @@
+use reqwest::Client;
+use sentinel_common::template::MessageTemplate;
+
+use super::channel::{Notifier, NotifyError};
+use super::registry::{config_str, optional_str, BuildResult};
+use crate::alert::{AlertEvent, AlertGroup, AlertStatus, Severity};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatFlavor {
+    Mattermost,
+    RocketChat,
+}
+
+pub struct MattermostNotifier {
+    webhook_url: String,
+    channel: Option<String>,
+    username: Option<String>,
+    flavor: ChatFlavor,
+    client: Client,
+    template: MessageTemplate,
+}
+
+impl MattermostNotifier {
+    pub fn new(webhook_url: String, flavor: ChatFlavor) -> Self {
+        Self {
+            webhook_url,
+            channel: None,
+            username: None,
+            flavor,
+            client: Client::new(),
+            template: MessageTemplate::default(),
+        }
+    }
+
+    pub fn with_channel(mut self, channel: Option<String>) -> Self {
+        self.channel = channel;
+        self
+    }
+
+    pub fn with_username(mut self, username: Option<String>) -> Self {
+        self.username = username;
+        self
+    }
+
+    pub fn with_template(mut self, template: MessageTemplate) -> Self {
+        self.template = template;
+        self
+    }
+}
+
+pub fn from_config(config: &serde_json::Value, template: MessageTemplate) -> BuildResult {
+    build(config, template, ChatFlavor::Mattermost)
+}
+
+pub fn rocketchat_from_config(
+    config: &serde_json::Value,
+    template: MessageTemplate,
+) -> BuildResult {
+    build(config, template, ChatFlavor::RocketChat)
+}
+
+fn build(config: &serde_json::Value, template: MessageTemplate, flavor: ChatFlavor) -> BuildResult {
+    Ok(Box::new(
+        MattermostNotifier::new(config_str(config, "webhook_url")?, flavor)
+            .with_channel(optional_str(config, "channel"))
+            .with_username(optional_str(config, "username"))
+            .with_template(template),
+    ))
+}
+
+#[tonic::async_trait]
+impl Notifier for MattermostNotifier {
+    fn name(&self) -> &str {
+        match self.flavor {
+            ChatFlavor::Mattermost => "mattermost",
+            ChatFlavor::RocketChat => "rocketchat",
+        }
+    }
+
+    async fn send(&self, event: &AlertEvent) -> Result<(), NotifyError> {
+        let msg = self.template.render(&event.template_context());
+        let title = msg.title.unwrap_or_else(|| {
+            format!(
+                "{} [{}] {}",
+                status_emoji(event.status),
+                event.severity_str(),
+                event.rule_name
+            )
+        });
+        let body = msg.body.unwrap_or_else(|| {
+            format!(
+                "Agent: {}\nMetric: {}\nValue: {:.2}\nThreshold: {:.2}",
+                event.agent_id,
+                event.series_name(),
+                event.value,
+                event.threshold
+            )
+        });
+        self.post(&title, &body, event.severity).await
+    }
+
+    async fn send_group(&self, group: &AlertGroup) -> Result<(), NotifyError> {
+        let msg = self.template.render_group(&group.template_context());
+        let title = msg
+            .title
+            .unwrap_or_else(|| format!("{} {}", status_emoji(group.status()), group.title()));
+        let body = msg.body.unwrap_or_else(|| group.body());
+        self.post(&title, &body, group.severity()).await
+    }
+}
+
+impl MattermostNotifier {
+    async fn post(&self, title: &str, body: &str, severity: Severity) -> Result<(), NotifyError> {
+        let mut payload = serde_json::json!({
+            "text": format!("**{title}**"),
+            "attachments": [{
+                "color": severity_color(severity),
+                "title": title,
+                "text": body,
+            }],
+        });
+        if let Some(ref channel) = self.channel {
+            payload["channel"] = channel.as_str().into();
+        }
+        if let Some(ref username) = self.username {
+            let key = match self.flavor {
+                ChatFlavor::Mattermost => "username",
+                ChatFlavor::RocketChat => "alias",
+            };
+            payload[key] = username.as_str().into();
+        }
+
+        self.client
+            .post(&self.webhook_url)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| NotifyError(e.to_string()))?
+            .error_for_status()
+            .map_err(|e| NotifyError(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+fn status_emoji(status: AlertStatus) -> &'static str {
+    match status {
+        AlertStatus::Firing => ":rotating_light:",
+        AlertStatus::Resolved => ":white_check_mark:",
+    }
+}
+
+fn severity_color(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "#36a64f",
+        Severity::Warning => "#f2c744",
+        Severity::Critical => "#d32f2f",
+    }
+}
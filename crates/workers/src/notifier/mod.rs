@@ -2,14 +2,24 @@ pub mod channel;
 pub mod discord;
 pub mod dispatcher;
 pub mod dlq;
+pub mod googlechat;
 pub mod gotify;
+pub mod grouper;
+pub mod history;
+pub mod matrix;
+pub mod mattermost;
 pub mod ntfy;
 pub mod opsgenie;
 pub mod pagerduty;
+pub mod pushover;
+pub mod registry;
+pub mod replay;
 pub mod retry;
 pub mod signer;
 pub mod slack;
 pub mod smtp;
+pub mod sns;
 pub mod teams;
 pub mod telegram;
+pub mod victorops;
 pub mod webhook;
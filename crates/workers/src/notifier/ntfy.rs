@@ -1,13 +1,16 @@
 use reqwest::Client;
+use sentinel_common::template::MessageTemplate;
 
 use super::channel::{Notifier, NotifyError};
-use crate::alert::AlertEvent;
+use super::registry::{config_str, optional_str, BuildResult};
+use crate::alert::{AlertEvent, AlertGroup, AlertStatus, Severity};
 
 pub struct NtfyNotifier {
     server_url: String,
     topic: String,
     token: Option<String>,
     client: Client,
+    template: MessageTemplate,
 }
 
 impl NtfyNotifier {
@@ -18,8 +21,23 @@ impl NtfyNotifier {
             topic,
             token,
             client: Client::new(),
+            template: MessageTemplate::default(),
         }
     }
+
+    pub fn with_template(mut self, template: MessageTemplate) -> Self {
+        self.template = template;
+        self
+    }
+}
+
+pub fn from_config(config: &serde_json::Value, template: MessageTemplate) -> BuildResult {
+    let server_url = config_str(config, "server_url")?;
+    let topic = config_str(config, "topic")?;
+    let token = optional_str(config, "token");
+    Ok(Box::new(
+        NtfyNotifier::new(server_url, topic, token).with_template(template),
+    ))
 }
 
 #[tonic::async_trait]
@@ -29,38 +47,61 @@ impl Notifier for NtfyNotifier {
     }
 
     async fn send(&self, event: &AlertEvent) -> Result<(), NotifyError> {
-        let priority = match event.severity {
-            crate::alert::Severity::Info => "3",
-            crate::alert::Severity::Warning => "4",
-            crate::alert::Severity::Critical => "5",
-        };
-
-        let tags = match event.status {
-            crate::alert::AlertStatus::Firing => "rotating_light,warning",
-            crate::alert::AlertStatus::Resolved => "white_check_mark,resolved",
-        };
-
-        let title = format!(
-            "[{severity}] {rule}",
-            severity = event.severity_str(),
-            rule = event.rule_name,
-        );
-
-        let body = format!(
-            "Status: {status}\nAgent: {agent}\nMetric: {metric}\nValue: {value:.2} (threshold: {threshold:.2})",
-            status = event.status_str(),
-            agent = event.agent_id,
-            metric = event.metric_name,
-            value = event.value,
-            threshold = event.threshold,
-        );
+        let priority = priority(event.severity);
+        let tags = status_tags(event.status);
+
+        let msg = self.template.render(&event.template_context());
 
+        let title = msg.title.unwrap_or_else(|| {
+            format!(
+                "[{severity}] {rule}",
+                severity = event.severity_str(),
+                rule = event.rule_name,
+            )
+        });
+
+        let body = msg.body.unwrap_or_else(|| {
+            format!(
+                "Status: {status}\nAgent: {agent}\nMetric: {metric}\nValue: {value:.2} (threshold: {threshold:.2})",
+                status = event.status_str(),
+                agent = event.agent_id,
+                metric = event.series_name(),
+                value = event.value,
+                threshold = event.threshold,
+            )
+        });
+
+        self.publish(&title, body, priority, tags).await
+    }
+
+    async fn send_group(&self, group: &AlertGroup) -> Result<(), NotifyError> {
+        let msg = self.template.render_group(&group.template_context());
+        let title = msg.title.unwrap_or_else(|| group.title());
+        let body = msg.body.unwrap_or_else(|| group.body());
+        self.publish(
+            &title,
+            body,
+            priority(group.severity()),
+            status_tags(group.status()),
+        )
+        .await
+    }
+}
+
+impl NtfyNotifier {
+    async fn publish(
+        &self,
+        title: &str,
+        body: String,
+        priority: &str,
+        tags: &str,
+    ) -> Result<(), NotifyError> {
         let url = format!("{}/{}", self.server_url, self.topic);
 
         let mut req = self
             .client
             .post(&url)
-            .header("Title", &title)
+            .header("Title", title)
             .header("Priority", priority)
             .header("Tags", tags)
             .body(body);
@@ -78,3 +119,18 @@ impl Notifier for NtfyNotifier {
         Ok(())
     }
 }
+
+fn priority(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "3",
+        Severity::Warning => "4",
+        Severity::Critical => "5",
+    }
+}
+
+fn status_tags(status: AlertStatus) -> &'static str {
+    match status {
+        AlertStatus::Firing => "rotating_light,warning",
+        AlertStatus::Resolved => "white_check_mark,resolved",
+    }
+}
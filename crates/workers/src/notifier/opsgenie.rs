@@ -1,13 +1,16 @@
 use reqwest::Client;
+use sentinel_common::template::MessageTemplate;
 
 use super::channel::{Notifier, NotifyError};
-use crate::alert::AlertEvent;
+use super::registry::{config_str, BuildResult};
+use crate::alert::{AlertEvent, AlertGroup, AlertStatus, Severity};
 
 const ALERTS_API: &str = "https://api.opsgenie.com/v2/alerts";
 
 pub struct OpsGenieNotifier {
     api_key: String,
     client: Client,
+    template: MessageTemplate,
 }
 
 impl OpsGenieNotifier {
@@ -15,8 +18,20 @@ impl OpsGenieNotifier {
         Self {
             api_key,
             client: Client::new(),
+            template: MessageTemplate::default(),
         }
     }
+
+    pub fn with_template(mut self, template: MessageTemplate) -> Self {
+        self.template = template;
+        self
+    }
+}
+
+pub fn from_config(config: &serde_json::Value, template: MessageTemplate) -> BuildResult {
+    Ok(Box::new(
+        OpsGenieNotifier::new(config_str(config, "api_key")?).with_template(template),
+    ))
 }
 
 #[tonic::async_trait]
@@ -26,23 +41,65 @@ impl Notifier for OpsGenieNotifier {
     }
 
     async fn send(&self, event: &AlertEvent) -> Result<(), NotifyError> {
-        let priority = match event.severity {
-            crate::alert::Severity::Info => "P4",
-            crate::alert::Severity::Warning => "P3",
-            crate::alert::Severity::Critical => "P1",
-        };
+        let priority = priority(event.severity);
 
         match event.status {
-            crate::alert::AlertStatus::Firing => self.create_alert(event, priority).await,
-            crate::alert::AlertStatus::Resolved => self.close_alert(event).await,
+            AlertStatus::Firing => self.create_alert(event, priority).await,
+            AlertStatus::Resolved => self.close_alert(event).await,
+        }
+    }
+
+    async fn send_group(&self, group: &AlertGroup) -> Result<(), NotifyError> {
+        let msg = self.template.render_group(&group.template_context());
+        let alias = group.dedup_key();
+        let body = msg.body.unwrap_or_else(|| group.body());
+
+        match group.status() {
+            AlertStatus::Firing => {
+                let agents: Vec<&str> = group.alerts.iter().map(|a| a.agent_id.as_str()).collect();
+                let payload = serde_json::json!({
+                    "message": msg.title.unwrap_or_else(|| group.title()),
+                    "alias": alias,
+                    "priority": priority(group.severity()),
+                    "source": "SentinelRS",
+                    "tags": ["sentinel", "group", &group.severity_str().to_lowercase()],
+                    "description": body,
+                    "details": {
+                        "group": group.key,
+                        "firing": group.firing_count().to_string(),
+                        "resolved": group.resolved_count().to_string(),
+                        "agents": agents.join(","),
+                    }
+                });
+                self.create(&payload).await
+            }
+            AlertStatus::Resolved => self.close(&alias, &body).await,
         }
     }
 }
 
+fn priority(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "P4",
+        Severity::Warning => "P3",
+        Severity::Critical => "P1",
+    }
+}
+
 impl OpsGenieNotifier {
     async fn create_alert(&self, event: &AlertEvent, priority: &str) -> Result<(), NotifyError> {
-        let payload = serde_json::json!({
-            "message": format!("[{}] {} - {}", event.severity_str(), event.rule_name, event.agent_id),
+        let msg = self.template.render(&event.template_context());
+        let message = msg.title.unwrap_or_else(|| {
+            format!(
+                "[{}] {} - {}",
+                event.severity_str(),
+                event.rule_name,
+                event.agent_id
+            )
+        });
+
+        let mut payload = serde_json::json!({
+            "message": message,
             "alias": event.fingerprint,
             "priority": priority,
             "source": "SentinelRS",
@@ -50,16 +107,23 @@ impl OpsGenieNotifier {
             "details": {
                 "agent": event.agent_id,
                 "metric": event.metric_name,
+                "labels": event.labels,
                 "value": format!("{:.2}", event.value),
                 "threshold": format!("{:.2}", event.threshold),
                 "rule_id": event.rule_id,
             }
         });
+        if let Some(body) = msg.body {
+            payload["description"] = body.into();
+        }
+        self.create(&payload).await
+    }
 
+    async fn create(&self, payload: &serde_json::Value) -> Result<(), NotifyError> {
         self.client
             .post(ALERTS_API)
             .header("Authorization", format!("GenieKey {}", self.api_key))
-            .json(&payload)
+            .json(payload)
             .send()
             .await
             .map_err(|e| NotifyError(e.to_string()))?
@@ -70,14 +134,19 @@ impl OpsGenieNotifier {
     }
 
     async fn close_alert(&self, event: &AlertEvent) -> Result<(), NotifyError> {
-        let url = format!(
-            "{}/{}/close?identifierType=alias",
-            ALERTS_API, event.fingerprint
-        );
+        let note = self
+            .template
+            .render(&event.template_context())
+            .body
+            .unwrap_or_else(|| format!("Resolved: {} on {}", event.rule_name, event.agent_id));
+        self.close(&event.fingerprint, &note).await
+    }
 
+    async fn close(&self, alias: &str, note: &str) -> Result<(), NotifyError> {
+        let url = format!("{ALERTS_API}/{alias}/close?identifierType=alias");
         let payload = serde_json::json!({
             "source": "SentinelRS",
-            "note": format!("Resolved: {} on {}", event.rule_name, event.agent_id),
+            "note": note,
         });
 
         self.client
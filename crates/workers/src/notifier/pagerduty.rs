@@ -1,13 +1,16 @@
 use reqwest::Client;
+use sentinel_common::template::MessageTemplate;
 
 use super::channel::{Notifier, NotifyError};
-use crate::alert::AlertEvent;
+use super::registry::{config_str, BuildResult};
+use crate::alert::{AlertEvent, AlertGroup, AlertStatus};
 
 const EVENTS_API: &str = "https://events.pagerduty.com/v2/enqueue";
 
 pub struct PagerDutyNotifier {
     routing_key: String,
     client: Client,
+    template: MessageTemplate,
 }
 
 impl PagerDutyNotifier {
@@ -15,8 +18,20 @@ impl PagerDutyNotifier {
         Self {
             routing_key,
             client: Client::new(),
+            template: MessageTemplate::default(),
         }
     }
+
+    pub fn with_template(mut self, template: MessageTemplate) -> Self {
+        self.template = template;
+        self
+    }
+}
+
+pub fn from_config(config: &serde_json::Value, template: MessageTemplate) -> BuildResult {
+    Ok(Box::new(
+        PagerDutyNotifier::new(config_str(config, "routing_key")?).with_template(template),
+    ))
 }
 
 #[tonic::async_trait]
@@ -27,8 +42,8 @@ impl Notifier for PagerDutyNotifier {
 
     async fn send(&self, event: &AlertEvent) -> Result<(), NotifyError> {
         let event_action = match event.status {
-            crate::alert::AlertStatus::Firing => "trigger",
-            crate::alert::AlertStatus::Resolved => "resolve",
+            AlertStatus::Firing => "trigger",
+            AlertStatus::Resolved => "resolve",
         };
 
         let severity = match event.severity {
@@ -37,17 +52,28 @@ impl Notifier for PagerDutyNotifier {
             crate::alert::Severity::Critical => "critical",
         };
 
-        let payload = serde_json::json!({
+        let msg = self.template.render(&event.template_context());
+        let summary = msg.title.unwrap_or_else(|| {
+            format!(
+                "[{}] {} - {}",
+                event.severity_str(),
+                event.rule_name,
+                event.agent_id
+            )
+        });
+
+        let mut payload = serde_json::json!({
             "routing_key": self.routing_key,
             "event_action": event_action,
             "dedup_key": event.fingerprint,
             "payload": {
-                "summary": format!("[{}] {} - {}", event.severity_str(), event.rule_name, event.agent_id),
+                "summary": summary,
                 "source": event.agent_id,
                 "severity": severity,
                 "component": event.metric_name,
                 "custom_details": {
                     "metric": event.metric_name,
+                    "labels": event.labels,
                     "value": event.value,
                     "threshold": event.threshold,
                     "rule_id": event.rule_id,
@@ -55,10 +81,46 @@ impl Notifier for PagerDutyNotifier {
                 }
             }
         });
+        if let Some(body) = msg.body {
+            payload["payload"]["custom_details"]["message"] = body.into();
+        }
+        self.enqueue(&payload).await
+    }
 
+    async fn send_group(&self, group: &AlertGroup) -> Result<(), NotifyError> {
+        let event_action = match group.status() {
+            AlertStatus::Firing => "trigger",
+            AlertStatus::Resolved => "resolve",
+        };
+        let msg = self.template.render_group(&group.template_context());
+        let sources: Vec<&str> = group.alerts.iter().map(|a| a.agent_id.as_str()).collect();
+
+        let payload = serde_json::json!({
+            "routing_key": self.routing_key,
+            "event_action": event_action,
+            "dedup_key": group.dedup_key(),
+            "payload": {
+                "summary": msg.title.unwrap_or_else(|| group.title()),
+                "source": sources.first().copied().unwrap_or("sentinel"),
+                "severity": group.severity().as_str(),
+                "custom_details": {
+                    "group_labels": group.labels,
+                    "firing": group.firing_count(),
+                    "resolved": group.resolved_count(),
+                    "agents": sources,
+                    "message": msg.body.unwrap_or_else(|| group.body()),
+                }
+            }
+        });
+        self.enqueue(&payload).await
+    }
+}
+
+impl PagerDutyNotifier {
+    async fn enqueue(&self, payload: &serde_json::Value) -> Result<(), NotifyError> {
         self.client
             .post(EVENTS_API)
-            .json(&payload)
+            .json(payload)
             .send()
             .await
             .map_err(|e| NotifyError(e.to_string()))?
@@ -0,0 +1,130 @@
+use reqwest::Client;
+use sentinel_common::template::MessageTemplate;
+
+use super::channel::{Notifier, NotifyError};
+use super::registry::{config_str, optional_str, BuildResult};
+use crate::alert::{AlertEvent, AlertGroup, AlertStatus, Severity};
+
+const PUSHOVER_API_URL: &str = "https://api.pushover.net/1/messages.json";
+
+pub struct PushoverNotifier {
+    token: String,
+    user_key: String,
+    device: Option<String>,
+    api_url: String,
+    client: Client,
+    template: MessageTemplate,
+}
+
+impl PushoverNotifier {
+    pub fn new(token: String, user_key: String) -> Self {
+        Self {
+            token,
+            user_key,
+            device: None,
+            api_url: PUSHOVER_API_URL.into(),
+            client: Client::new(),
+            template: MessageTemplate::default(),
+        }
+    }
+
+    pub fn with_device(mut self, device: Option<String>) -> Self {
+        self.device = device;
+        self
+    }
+
+    pub fn with_api_url(mut self, api_url: String) -> Self {
+        self.api_url = api_url;
+        self
+    }
+
+    pub fn with_template(mut self, template: MessageTemplate) -> Self {
+        self.template = template;
+        self
+    }
+}
+
+pub fn from_config(config: &serde_json::Value, template: MessageTemplate) -> BuildResult {
+    let mut notifier = PushoverNotifier::new(
+        config_str(config, "token")?,
+        config_str(config, "user_key")?,
+    )
+    .with_device(optional_str(config, "device"))
+    .with_template(template);
+    if let Some(api_url) = optional_str(config, "api_url") {
+        notifier = notifier.with_api_url(api_url);
+    }
+    Ok(Box::new(notifier))
+}
+
+#[tonic::async_trait]
+impl Notifier for PushoverNotifier {
+    fn name(&self) -> &str {
+        "pushover"
+    }
+
+    async fn send(&self, event: &AlertEvent) -> Result<(), NotifyError> {
+        let msg = self.template.render(&event.template_context());
+        let title = msg
+            .title
+            .unwrap_or_else(|| format!("[{}] {}", event.severity_str(), event.rule_name));
+        let message = msg.body.unwrap_or_else(|| {
+            format!(
+                "{}\nAgent: {}\nMetric: {}\nValue: {:.2}\nThreshold: {:.2}",
+                event.status_str(),
+                event.agent_id,
+                event.series_name(),
+                event.value,
+                event.threshold
+            )
+        });
+        self.post(&title, &message, priority(event.severity, event.status))
+            .await
+    }
+
+    async fn send_group(&self, group: &AlertGroup) -> Result<(), NotifyError> {
+        let msg = self.template.render_group(&group.template_context());
+        let title = msg.title.unwrap_or_else(|| group.title());
+        let message = msg.body.unwrap_or_else(|| group.body());
+        self.post(&title, &message, priority(group.severity(), group.status()))
+            .await
+    }
+}
+
+impl PushoverNotifier {
+    async fn post(&self, title: &str, message: &str, priority: i8) -> Result<(), NotifyError> {
+        let priority = priority.to_string();
+        let mut form = vec![
+            ("token", self.token.as_str()),
+            ("user", self.user_key.as_str()),
+            ("title", title),
+            ("message", message),
+            ("priority", priority.as_str()),
+        ];
+        if let Some(ref device) = self.device {
+            form.push(("device", device));
+        }
+
+        self.client
+            .post(&self.api_url)
+            .form(&form)
+            .send()
+            .await
+            .map_err(|e| NotifyError(e.to_string()))?
+            .error_for_status()
+            .map_err(|e| NotifyError(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+fn priority(severity: Severity, status: AlertStatus) -> i8 {
+    if status == AlertStatus::Resolved {
+        return -1;
+    }
+    match severity {
+        Severity::Info => -1,
+        Severity::Warning => 0,
+        Severity::Critical => 1,
+    }
+}
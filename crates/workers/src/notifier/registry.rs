@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use sentinel_common::template::MessageTemplate;
+
+use super::channel::Notifier;
+use super::{
+    discord, googlechat, gotify, matrix, mattermost, ntfy, opsgenie, pagerduty, pushover, slack,
+    smtp, sns, teams, telegram, victorops, webhook,
+};
+
+pub type BuildError = Box<dyn std::error::Error + Send + Sync>;
+pub type BuildResult = Result<Box<dyn Notifier>, BuildError>;
+pub type BuildFn = fn(&serde_json::Value, MessageTemplate) -> BuildResult;
+
+const BUILTIN: &[(&str, BuildFn)] = &[
+    ("discord", discord::from_config),
+    ("gotify", gotify::from_config),
+    ("googlechat", googlechat::from_config),
+    ("matrix", matrix::from_config),
+    ("mattermost", mattermost::from_config),
+    ("ntfy", ntfy::from_config),
+    ("opsgenie", opsgenie::from_config),
+    ("pagerduty", pagerduty::from_config),
+    ("pushover", pushover::from_config),
+    ("rocketchat", mattermost::rocketchat_from_config),
+    ("slack", slack::from_config),
+    ("smtp", smtp::from_config),
+    ("sns", sns::from_config),
+    ("teams", teams::from_config),
+    ("telegram", telegram::from_config),
+    ("victorops", victorops::from_config),
+    ("webhook", webhook::from_config),
+];
+
+pub struct NotifierRegistry {
+    builders: HashMap<&'static str, BuildFn>,
+}
+
+impl NotifierRegistry {
+    pub fn empty() -> Self {
+        Self {
+            builders: HashMap::new(),
+        }
+    }
+
+    pub fn register(&mut self, ntype: &'static str, build: BuildFn) {
+        self.builders.insert(ntype, build);
+    }
+
+    pub fn types(&self) -> Vec<&'static str> {
+        let mut types: Vec<_> = self.builders.keys().copied().collect();
+        types.sort_unstable();
+        types
+    }
+
+    pub fn build(&self, ntype: &str, config: &serde_json::Value) -> BuildResult {
+        let build = self
+            .builders
+            .get(ntype)
+            .ok_or_else(|| format!("unknown notifier type '{ntype}'"))?;
+        let template = MessageTemplate::from_config(config)?;
+        build(config, template)
+    }
+}
+
+impl Default for NotifierRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        for (ntype, build) in BUILTIN {
+            registry.register(ntype, *build);
+        }
+        registry
+    }
+}
+
+pub(crate) fn config_str(config: &serde_json::Value, key: &str) -> Result<String, BuildError> {
+    optional_str(config, key).ok_or_else(|| format!("missing '{key}' in notifier config").into())
+}
+
+pub(crate) fn optional_str(config: &serde_json::Value, key: &str) -> Option<String> {
+    config.get(key).and_then(|v| v.as_str()).map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn builds_registered_types() {
+        let registry = NotifierRegistry::default();
+        assert_eq!(registry.types().len(), BUILTIN.len());
+        let n = registry
+            .build(
+                "slack",
+                &json!({"webhook_url": "https://hooks.slack.com/x"}),
+            )
+            .unwrap();
+        assert_eq!(n.name(), "slack");
+        let n = registry
+            .build(
+                "rocketchat",
+                &json!({"webhook_url": "https://chat.example.com/hooks/x"}),
+            )
+            .unwrap();
+        assert_eq!(n.name(), "rocketchat");
+    }
+
+    #[test]
+    fn rejects_unknown_type_and_missing_fields() {
+        let registry = NotifierRegistry::default();
+        let err = registry.build("fax", &json!({})).err().unwrap();
+        assert!(err.to_string().contains("unknown notifier type"));
+        let err = registry.build("matrix", &json!({})).err().unwrap();
+        assert!(err.to_string().contains("homeserver_url"));
+    }
+
+    #[test]
+    fn custom_backends_plug_in() {
+        fn build(config: &serde_json::Value, template: MessageTemplate) -> BuildResult {
+            slack::from_config(config, template)
+        }
+        let mut registry = NotifierRegistry::empty();
+        registry.register("chatops", build);
+        assert_eq!(registry.types(), vec!["chatops"]);
+        assert!(registry
+            .build(
+                "chatops",
+                &json!({"webhook_url": "https://hooks.slack.com/x"})
+            )
+            .is_ok());
+    }
+}
@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use sentinel_common::envelope::Envelope;
+use sqlx::PgPool;
+use tokio_util::sync::CancellationToken;
+
+use super::dispatcher::Dispatcher;
+use super::dlq::{ClaimPolicy, DlqWriter, ReplayEntry};
+use crate::alert::{AlertEvent, AlertGroup};
+use crate::config::DlqConfig;
+
+const CLAIM_LEASE_SECS: i64 = 300;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ReplayOutcome {
+    pub delivered: usize,
+    pub failed: usize,
+}
+
+pub struct DlqReplayer {
+    dlq: DlqWriter,
+    dispatcher: Dispatcher,
+    config: DlqConfig,
+}
+
+impl DlqReplayer {
+    pub fn new(pool: PgPool, config: DlqConfig, envelope: Envelope) -> Self {
+        Self {
+            dlq: DlqWriter::new(pool.clone()),
+            dispatcher: Dispatcher::new(pool, envelope),
+            config,
+        }
+    }
+
+    pub async fn run_once(&self) -> Result<ReplayOutcome, sqlx::Error> {
+        let policy = ClaimPolicy {
+            auto_retry: self.config.auto_retry,
+            base_delay_secs: self.config.retry_base_delay.as_secs() as i64,
+            max_age_secs: self.config.max_age.as_secs() as i64,
+            lease_secs: CLAIM_LEASE_SECS,
+            limit: self.config.batch_size as i64,
+        };
+
+        let mut outcome = ReplayOutcome::default();
+        for entry in self.dlq.claim(&policy).await? {
+            match self.replay(&entry).await {
+                Ok(()) => {
+                    self.dlq.delete(&entry.id).await?;
+                    outcome.delivered += 1;
+                }
+                Err(e) => {
+                    let delay = retry_delay(&self.config, entry.retry_count.max(0) as u32);
+                    self.dlq
+                        .record_failure(&entry.id, &e.to_string(), delay.as_secs() as i64)
+                        .await?;
+                    outcome.failed += 1;
+                }
+            }
+        }
+        Ok(outcome)
+    }
+
+    async fn replay(
+        &self,
+        entry: &ReplayEntry,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        if entry.payload.get("alerts").is_some() {
+            let group: AlertGroup = serde_json::from_value(entry.payload.clone())?;
+            return self
+                .dispatcher
+                .replay_group(&entry.notifier_id, &group)
+                .await;
+        }
+        let event: AlertEvent = serde_json::from_value(entry.payload.clone())?;
+        self.dispatcher.replay(&entry.notifier_id, &event).await
+    }
+
+    pub fn spawn(self, cancel: CancellationToken) {
+        let interval = self.config.poll_interval;
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = cancel.cancelled() => break,
+                    _ = tokio::time::sleep(interval) => {
+                        match self.run_once().await {
+                            Ok(o) if o.delivered + o.failed > 0 => {
+                                tracing::info!(
+                                    target: "notify",
+                                    delivered = o.delivered,
+                                    failed = o.failed,
+                                    "DLQ replay pass finished"
+                                );
+                            }
+                            Ok(_) => {}
+                            Err(e) => {
+                                tracing::error!(target: "notify", error = %e, "DLQ replay failed");
+                            }
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
+
+pub fn retry_delay(config: &DlqConfig, retry_count: u32) -> Duration {
+    let factor = 2u32.saturating_pow(retry_count);
+    config
+        .retry_base_delay
+        .saturating_mul(factor)
+        .min(config.retry_max_delay)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_delay_doubles_up_to_max() {
+        let config = DlqConfig {
+            retry_base_delay: Duration::from_secs(60),
+            retry_max_delay: Duration::from_secs(600),
+            ..Default::default()
+        };
+        assert_eq!(retry_delay(&config, 0), Duration::from_secs(60));
+        assert_eq!(retry_delay(&config, 1), Duration::from_secs(120));
+        assert_eq!(retry_delay(&config, 3), Duration::from_secs(480));
+        assert_eq!(retry_delay(&config, 4), Duration::from_secs(600));
+        assert_eq!(retry_delay(&config, 40), Duration::from_secs(600));
+    }
+}
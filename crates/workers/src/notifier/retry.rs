@@ -1,12 +1,22 @@
+use std::time::Instant;
+
 use super::channel::{Notifier, NotifyError};
 use super::dlq::DlqWriter;
-use crate::alert::AlertEvent;
+use super::history::{DeliveryRecord, DeliveryStatus, HistoryWriter};
+use crate::alert::{AlertEvent, AlertGroup};
 
 pub struct RetryNotifier<N: Notifier> {
     inner: N,
     max_retries: u32,
     base_delay_ms: u64,
     dlq: Option<DlqWriter>,
+    history: Option<HistoryWriter>,
+}
+
+struct Attempts {
+    count: u32,
+    duration_ms: u64,
+    result: Result<(), NotifyError>,
 }
 
 impl<N: Notifier> RetryNotifier<N> {
@@ -16,6 +26,7 @@ impl<N: Notifier> RetryNotifier<N> {
             max_retries,
             base_delay_ms,
             dlq: None,
+            history: None,
         }
     }
 
@@ -24,12 +35,89 @@ impl<N: Notifier> RetryNotifier<N> {
         self
     }
 
+    pub fn with_history(mut self, history: HistoryWriter) -> Self {
+        self.history = Some(history);
+        self
+    }
+
     pub async fn send(&self, event: &AlertEvent) -> Result<(), NotifyError> {
+        let attempts = self.attempt(event).await;
+        let history_id = uuid::Uuid::new_v4().to_string();
+
+        let status = match attempts.result {
+            Ok(()) => DeliveryStatus::Sent,
+            Err(ref err) => {
+                self.dead_letter(event, err, attempts.count, &history_id)
+                    .await
+            }
+        };
+
+        self.record(&history_id, &event.id, status, &attempts).await;
+        attempts.result
+    }
+
+    pub async fn send_group(&self, group: &AlertGroup) -> Result<(), NotifyError> {
+        let attempts = self.retry(|| self.inner.send_group(group)).await;
+        let history_id = uuid::Uuid::new_v4().to_string();
+        let lead_id = group.alerts.first().map(|a| a.id.as_str()).unwrap_or("");
+
+        let status = match attempts.result {
+            Ok(()) => DeliveryStatus::Sent,
+            Err(ref err) => {
+                let payload = serde_json::to_value(group).unwrap_or_default();
+                self.park(lead_id, &payload, err, attempts.count, &history_id)
+                    .await
+            }
+        };
+
+        for (i, alert) in group.alerts.iter().enumerate() {
+            let id = if i == 0 {
+                history_id.clone()
+            } else {
+                uuid::Uuid::new_v4().to_string()
+            };
+            self.record(&id, &alert.id, status, &attempts).await;
+        }
+        attempts.result
+    }
+
+    async fn record(&self, id: &str, alert_id: &str, status: DeliveryStatus, attempts: &Attempts) {
+        let Some(ref history) = self.history else {
+            return;
+        };
+        let record = DeliveryRecord {
+            id: id.to_string(),
+            alert_id: alert_id.to_string(),
+            status,
+            error: attempts.result.as_ref().err().map(|e| e.0.clone()),
+            attempts: attempts.count,
+            duration_ms: attempts.duration_ms,
+        };
+        if let Err(e) = history.record(&record).await {
+            tracing::error!(target: "notify", error = %e, "Failed to record notification history");
+        }
+    }
+
+    async fn attempt(&self, event: &AlertEvent) -> Attempts {
+        self.retry(|| self.inner.send(event)).await
+    }
+
+    async fn retry<'a, F, Fut>(&'a self, mut deliver: F) -> Attempts
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<(), NotifyError>> + 'a,
+    {
+        let started = Instant::now();
+        let mut count = 0;
         let mut last_err = None;
 
         for attempt in 0..=self.max_retries {
-            match self.inner.send(event).await {
-                Ok(()) => return Ok(()),
+            count += 1;
+            match deliver().await {
+                Ok(()) => {
+                    last_err = None;
+                    break;
+                }
                 Err(e) => {
                     last_err = Some(e);
                     if attempt < self.max_retries {
@@ -40,25 +128,54 @@ impl<N: Notifier> RetryNotifier<N> {
             }
         }
 
-        let err = last_err.unwrap();
-
-        if let Some(ref dlq) = self.dlq {
-            let payload = serde_json::to_value(event).unwrap_or_default();
-            if let Err(dlq_err) = dlq
-                .insert(
-                    &event.id,
-                    self.inner.name(),
-                    &payload,
-                    &err.to_string(),
-                    self.max_retries + 1,
-                )
-                .await
-            {
+        Attempts {
+            count,
+            duration_ms: started.elapsed().as_millis() as u64,
+            result: last_err.map_or(Ok(()), Err),
+        }
+    }
+
+    async fn dead_letter(
+        &self,
+        event: &AlertEvent,
+        err: &NotifyError,
+        attempts: u32,
+        history_id: &str,
+    ) -> DeliveryStatus {
+        let payload = serde_json::to_value(event).unwrap_or_default();
+        self.park(&event.id, &payload, err, attempts, history_id)
+            .await
+    }
+
+    async fn park(
+        &self,
+        alert_id: &str,
+        payload: &serde_json::Value,
+        err: &NotifyError,
+        attempts: u32,
+        history_id: &str,
+    ) -> DeliveryStatus {
+        let Some(ref dlq) = self.dlq else {
+            return DeliveryStatus::Failed;
+        };
+        let linked = self.history.as_ref().map(|_| history_id);
+        match dlq
+            .insert(
+                alert_id,
+                self.inner.name(),
+                payload,
+                &err.to_string(),
+                attempts,
+                linked,
+            )
+            .await
+        {
+            Ok(()) => DeliveryStatus::DeadLettered,
+            Err(dlq_err) => {
                 tracing::error!(target: "notify", error = %dlq_err, "Failed to write to DLQ");
+                DeliveryStatus::Failed
             }
         }
-
-        Err(err)
     }
 }
 
@@ -98,6 +215,10 @@ mod tests {
                 Ok(())
             }
         }
+
+        async fn send_group(&self, group: &AlertGroup) -> Result<(), NotifyError> {
+            self.send(&group.alerts[0]).await
+        }
     }
 
     fn sample_event() -> AlertEvent {
@@ -108,6 +229,7 @@ mod tests {
             rule_name: "test rule".into(),
             agent_id: "agent-1".into(),
             metric_name: "cpu".into(),
+            labels: Default::default(),
             severity: Severity::Warning,
             status: AlertStatus::Firing,
             value: 90.0,
@@ -138,4 +260,41 @@ mod tests {
         let retry = RetryNotifier::new(inner, 2, 1);
         assert!(retry.send(&sample_event()).await.is_err());
     }
+
+    #[tokio::test]
+    async fn counts_attempts_per_delivery() {
+        let retry = RetryNotifier::new(FailingNotifier::new(1), 3, 1);
+        let attempts = retry.attempt(&sample_event()).await;
+        assert_eq!(attempts.count, 2);
+        assert!(attempts.result.is_ok());
+
+        let retry = RetryNotifier::new(FailingNotifier::new(10), 2, 1);
+        let attempts = retry.attempt(&sample_event()).await;
+        assert_eq!(attempts.count, 3);
+        assert_eq!(attempts.result.unwrap_err().0, "fail #3");
+    }
+
+    #[tokio::test]
+    async fn groups_are_retried_as_one_delivery() {
+        let group = AlertGroup {
+            key: "rule_id=r-1".into(),
+            labels: Default::default(),
+            alerts: vec![sample_event()],
+            repeat: false,
+        };
+        let retry = RetryNotifier::new(FailingNotifier::new(2), 3, 1);
+        assert!(retry.send_group(&group).await.is_ok());
+        assert_eq!(retry.inner.fail_count.load(Ordering::SeqCst), 3);
+
+        let retry = RetryNotifier::new(FailingNotifier::new(10), 1, 1);
+        assert!(retry.send_group(&group).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn failure_without_dlq_is_recorded_as_failed() {
+        let retry = RetryNotifier::new(FailingNotifier::new(10), 0, 1);
+        let err = NotifyError("boom".into());
+        let status = retry.dead_letter(&sample_event(), &err, 1, "h-1").await;
+        assert_eq!(status, DeliveryStatus::Failed);
+    }
 }
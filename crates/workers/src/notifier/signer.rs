@@ -1,7 +1,7 @@
-use sentinel_common::crypto::sign_data;
+use sentinel_common::webhook;
 
 pub fn sign_payload(secret: &[u8], payload: &[u8]) -> String {
-    sign_data(secret, payload)
+    webhook::sign(secret, payload)
 }
 
 #[cfg(test)]
@@ -1,11 +1,14 @@
 use reqwest::Client;
+use sentinel_common::template::MessageTemplate;
 
 use super::channel::{Notifier, NotifyError};
-use crate::alert::AlertEvent;
+use super::registry::{config_str, BuildResult};
+use crate::alert::{AlertEvent, AlertGroup, Severity};
 
 pub struct SlackNotifier {
     webhook_url: String,
     client: Client,
+    template: MessageTemplate,
 }
 
 impl SlackNotifier {
@@ -13,8 +16,20 @@ impl SlackNotifier {
         Self {
             webhook_url,
             client: Client::new(),
+            template: MessageTemplate::default(),
         }
     }
+
+    pub fn with_template(mut self, template: MessageTemplate) -> Self {
+        self.template = template;
+        self
+    }
+}
+
+pub fn from_config(config: &serde_json::Value, template: MessageTemplate) -> BuildResult {
+    Ok(Box::new(
+        SlackNotifier::new(config_str(config, "webhook_url")?).with_template(template),
+    ))
 }
 
 #[tonic::async_trait]
@@ -24,33 +39,64 @@ impl Notifier for SlackNotifier {
     }
 
     async fn send(&self, event: &AlertEvent) -> Result<(), NotifyError> {
-        let color = match event.severity {
-            crate::alert::Severity::Info => "#36a64f",
-            crate::alert::Severity::Warning => "#f2c744",
-            crate::alert::Severity::Critical => "#d32f2f",
-        };
+        let color = severity_color(event.severity);
 
         let status_emoji = match event.status {
             crate::alert::AlertStatus::Firing => ":fire:",
             crate::alert::AlertStatus::Resolved => ":white_check_mark:",
         };
 
-        let payload = serde_json::json!({
-            "attachments": [{
-                "color": color,
-                "title": format!("{} [{}] {}", status_emoji, event.severity_str(), event.rule_name),
-                "fields": [
+        let msg = self.template.render(&event.template_context());
+        let title = msg.title.unwrap_or_else(|| {
+            format!(
+                "{} [{}] {}",
+                status_emoji,
+                event.severity_str(),
+                event.rule_name
+            )
+        });
+
+        let mut attachment = serde_json::json!({ "color": color, "title": title });
+        match msg.body {
+            Some(body) => attachment["text"] = body.into(),
+            None => {
+                attachment["fields"] = serde_json::json!([
                     { "title": "Agent", "value": &event.agent_id, "short": true },
-                    { "title": "Metric", "value": &event.metric_name, "short": true },
+                    { "title": "Metric", "value": event.series_name(), "short": true },
                     { "title": "Value", "value": format!("{:.2}", event.value), "short": true },
                     { "title": "Threshold", "value": format!("{:.2}", event.threshold), "short": true },
-                ],
-            }]
+                ])
+            }
+        }
+        let payload = serde_json::json!({ "attachments": [attachment] });
+        self.post(&payload).await
+    }
+
+    async fn send_group(&self, group: &AlertGroup) -> Result<(), NotifyError> {
+        let msg = self.template.render_group(&group.template_context());
+        let status_emoji = match group.status() {
+            crate::alert::AlertStatus::Firing => ":fire:",
+            crate::alert::AlertStatus::Resolved => ":white_check_mark:",
+        };
+        let title = msg
+            .title
+            .unwrap_or_else(|| format!("{} {}", status_emoji, group.title()));
+        let text = msg.body.unwrap_or_else(|| group.body());
+        let attachment = serde_json::json!({
+            "color": severity_color(group.severity()),
+            "title": title,
+            "text": text,
         });
+        self.post(&serde_json::json!({ "attachments": [attachment] }))
+            .await
+    }
+}
 
+impl SlackNotifier {
+    async fn post(&self, payload: &serde_json::Value) -> Result<(), NotifyError> {
         self.client
             .post(&self.webhook_url)
-            .json(&payload)
+            .json(payload)
             .send()
             .await
             .map_err(|e| NotifyError(e.to_string()))?
@@ -60,3 +106,11 @@ impl Notifier for SlackNotifier {
         Ok(())
     }
 }
+
+fn severity_color(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "#36a64f",
+        Severity::Warning => "#f2c744",
+        Severity::Critical => "#d32f2f",
+    }
+}
@@ -1,14 +1,17 @@
 use lettre::message::header::ContentType;
 use lettre::transport::smtp::authentication::Credentials;
 use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use sentinel_common::template::MessageTemplate;
 
 use super::channel::{Notifier, NotifyError};
-use crate::alert::AlertEvent;
+use super::registry::{config_str, optional_str, BuildResult};
+use crate::alert::{AlertEvent, AlertGroup};
 
 pub struct SmtpNotifier {
     from: String,
     to: String,
     transport: AsyncSmtpTransport<Tokio1Executor>,
+    template: MessageTemplate,
 }
 
 impl SmtpNotifier {
@@ -30,8 +33,26 @@ impl SmtpNotifier {
             from,
             to,
             transport,
+            template: MessageTemplate::default(),
         }
     }
+
+    pub fn with_template(mut self, template: MessageTemplate) -> Self {
+        self.template = template;
+        self
+    }
+}
+
+pub fn from_config(config: &serde_json::Value, template: MessageTemplate) -> BuildResult {
+    let host = config_str(config, "host")?;
+    let port = config.get("port").and_then(|v| v.as_u64()).unwrap_or(587) as u16;
+    let username = optional_str(config, "username").unwrap_or_default();
+    let password = optional_str(config, "password").unwrap_or_default();
+    let from = config_str(config, "from")?;
+    let to = config_str(config, "to")?;
+    Ok(Box::new(
+        SmtpNotifier::new(&host, port, &username, &password, from, to).with_template(template),
+    ))
 }
 
 #[tonic::async_trait]
@@ -41,23 +62,44 @@ impl Notifier for SmtpNotifier {
     }
 
     async fn send(&self, event: &AlertEvent) -> Result<(), NotifyError> {
-        let subject = format!(
-            "[SentinelRS] [{}] {} - {}",
-            event.severity_str(),
-            event.status_str(),
-            event.rule_name
-        );
+        let msg = self.template.render(&event.template_context());
 
-        let body = format!(
-            "Alert: {}\nAgent: {}\nMetric: {}\nValue: {:.2}\nThreshold: {:.2}\nStatus: {}",
-            event.rule_name,
-            event.agent_id,
-            event.metric_name,
-            event.value,
-            event.threshold,
-            event.status_str(),
-        );
+        let subject = msg.title.unwrap_or_else(|| {
+            format!(
+                "[SentinelRS] [{}] {} - {}",
+                event.severity_str(),
+                event.status_str(),
+                event.rule_name
+            )
+        });
+
+        let body = msg.body.unwrap_or_else(|| {
+            format!(
+                "Alert: {}\nAgent: {}\nMetric: {}\nValue: {:.2}\nThreshold: {:.2}\nStatus: {}",
+                event.rule_name,
+                event.agent_id,
+                event.series_name(),
+                event.value,
+                event.threshold,
+                event.status_str(),
+            )
+        });
 
+        self.deliver(subject, body).await
+    }
+
+    async fn send_group(&self, group: &AlertGroup) -> Result<(), NotifyError> {
+        let msg = self.template.render_group(&group.template_context());
+        let subject = msg
+            .title
+            .unwrap_or_else(|| format!("[SentinelRS] {}", group.title()));
+        let body = msg.body.unwrap_or_else(|| group.body());
+        self.deliver(subject, body).await
+    }
+}
+
+impl SmtpNotifier {
+    async fn deliver(&self, subject: String, body: String) -> Result<(), NotifyError> {
         let email = Message::builder()
             .from(
                 self.from
@@ -0,0 +1,226 @@
+use hmac::{Hmac, Mac};
+use reqwest::{Client, Url};
+use sentinel_common::template::MessageTemplate;
+use sha2::{Digest, Sha256};
+
+use super::channel::{Notifier, NotifyError};
+use super::registry::{config_str, optional_str, BuildResult};
+use crate::alert::{AlertEvent, AlertGroup};
+
+const CONTENT_TYPE: &str = "application/x-www-form-urlencoded; charset=utf-8";
+const MAX_SUBJECT_LEN: usize = 100;
+
+pub struct SnsCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+}
+
+pub struct SnsNotifier {
+    endpoint: String,
+    region: String,
+    topic_arn: String,
+    credentials: SnsCredentials,
+    client: Client,
+    template: MessageTemplate,
+}
+
+impl SnsNotifier {
+    pub fn new(region: String, topic_arn: String, credentials: SnsCredentials) -> Self {
+        Self {
+            endpoint: format!("https://sns.{region}.amazonaws.com/"),
+            region,
+            topic_arn,
+            credentials,
+            client: Client::new(),
+            template: MessageTemplate::default(),
+        }
+    }
+
+    pub fn with_endpoint(mut self, endpoint: String) -> Self {
+        self.endpoint = endpoint;
+        self
+    }
+
+    pub fn with_template(mut self, template: MessageTemplate) -> Self {
+        self.template = template;
+        self
+    }
+}
+
+pub fn from_config(config: &serde_json::Value, template: MessageTemplate) -> BuildResult {
+    let credentials = SnsCredentials {
+        access_key_id: config_str(config, "access_key_id")?,
+        secret_access_key: config_str(config, "secret_access_key")?,
+        session_token: optional_str(config, "session_token"),
+    };
+    let mut notifier = SnsNotifier::new(
+        config_str(config, "region")?,
+        config_str(config, "topic_arn")?,
+        credentials,
+    )
+    .with_template(template);
+    if let Some(endpoint) = optional_str(config, "endpoint") {
+        notifier = notifier.with_endpoint(endpoint);
+    }
+    Ok(Box::new(notifier))
+}
+
+#[tonic::async_trait]
+impl Notifier for SnsNotifier {
+    fn name(&self) -> &str {
+        "sns"
+    }
+
+    async fn send(&self, event: &AlertEvent) -> Result<(), NotifyError> {
+        let msg = self.template.render(&event.template_context());
+        let subject = msg.title.unwrap_or_else(|| {
+            format!(
+                "[{}] {} {}",
+                event.severity_str(),
+                event.status_str(),
+                event.rule_name
+            )
+        });
+        let message = match msg.body {
+            Some(body) => body,
+            None => serde_json::to_string(event).map_err(|e| NotifyError(e.to_string()))?,
+        };
+        self.publish(&subject, &message).await
+    }
+
+    async fn send_group(&self, group: &AlertGroup) -> Result<(), NotifyError> {
+        let msg = self.template.render_group(&group.template_context());
+        let subject = msg.title.unwrap_or_else(|| group.title());
+        let message = msg.body.unwrap_or_else(|| group.body());
+        self.publish(&subject, &message).await
+    }
+}
+
+impl SnsNotifier {
+    async fn publish(&self, subject: &str, message: &str) -> Result<(), NotifyError> {
+        let url = Url::parse(&self.endpoint)
+            .map_err(|e| NotifyError(format!("invalid SNS endpoint: {e}")))?;
+        let body = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("Action", "Publish")
+            .append_pair("Version", "2010-03-31")
+            .append_pair("TopicArn", &self.topic_arn)
+            .append_pair("Subject", &sanitize_subject(subject))
+            .append_pair("Message", message)
+            .finish();
+
+        let amz_date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let authorization = self.authorization(&url, &amz_date, body.as_bytes())?;
+
+        let mut request = self
+            .client
+            .post(url)
+            .header("content-type", CONTENT_TYPE)
+            .header("x-amz-date", &amz_date)
+            .header("authorization", authorization);
+        if let Some(ref token) = self.credentials.session_token {
+            request = request.header("x-amz-security-token", token);
+        }
+
+        request
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| NotifyError(e.to_string()))?
+            .error_for_status()
+            .map_err(|e| NotifyError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn authorization(&self, url: &Url, amz_date: &str, body: &[u8]) -> Result<String, NotifyError> {
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{host}:{port}"),
+            (Some(host), None) => host.to_string(),
+            (None, _) => return Err(NotifyError("SNS endpoint has no host".into())),
+        };
+
+        let mut canonical_headers =
+            format!("content-type:{CONTENT_TYPE}\nhost:{host}\nx-amz-date:{amz_date}\n");
+        let mut signed_headers = String::from("content-type;host;x-amz-date");
+        if let Some(ref token) = self.credentials.session_token {
+            canonical_headers.push_str(&format!("x-amz-security-token:{token}\n"));
+            signed_headers.push_str(";x-amz-security-token");
+        }
+
+        let canonical_request = format!(
+            "POST\n{}\n\n{canonical_headers}\n{signed_headers}\n{}",
+            url.path(),
+            hex(&Sha256::digest(body))
+        );
+        let date = &amz_date[..8];
+        let scope = format!("{date}/{}/sns/aws4_request", self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+        let key = signing_key(
+            &self.credentials.secret_access_key,
+            date,
+            &self.region,
+            "sns",
+        );
+        let signature = hex(&hmac(&key, string_to_sign.as_bytes()));
+
+        Ok(format!(
+            "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+            self.credentials.access_key_id
+        ))
+    }
+}
+
+fn sanitize_subject(subject: &str) -> String {
+    subject
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .take(MAX_SUBJECT_LEN)
+        .collect()
+}
+
+fn signing_key(secret: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let k_date = hmac(format!("AWS4{secret}").as_bytes(), date.as_bytes());
+    let k_region = hmac(&k_date, region.as_bytes());
+    let k_service = hmac(&k_region, service.as_bytes());
+    hmac(&k_service, b"aws4_request")
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derives_aws_example_signing_key() {
+        let key = signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+        assert_eq!(
+            hex(&key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[test]
+    fn subject_is_single_line_and_bounded() {
+        let subject = sanitize_subject(&format!("a\nb{}", "x".repeat(200)));
+        assert!(!subject.contains('\n'));
+        assert_eq!(subject.chars().count(), MAX_SUBJECT_LEN);
+    }
+}
@@ -1,11 +1,14 @@
 use reqwest::Client;
+use sentinel_common::template::MessageTemplate;
 
 use super::channel::{Notifier, NotifyError};
-use crate::alert::AlertEvent;
+use super::registry::{config_str, BuildResult};
+use crate::alert::{AlertEvent, AlertGroup, AlertStatus, Severity};
 
 pub struct TeamsNotifier {
     webhook_url: String,
     client: Client,
+    template: MessageTemplate,
 }
 
 impl TeamsNotifier {
@@ -13,8 +16,20 @@ impl TeamsNotifier {
         Self {
             webhook_url,
             client: Client::new(),
+            template: MessageTemplate::default(),
         }
     }
+
+    pub fn with_template(mut self, template: MessageTemplate) -> Self {
+        self.template = template;
+        self
+    }
+}
+
+pub fn from_config(config: &serde_json::Value, template: MessageTemplate) -> BuildResult {
+    Ok(Box::new(
+        TeamsNotifier::new(config_str(config, "webhook_url")?).with_template(template),
+    ))
 }
 
 #[tonic::async_trait]
@@ -24,50 +39,59 @@ impl Notifier for TeamsNotifier {
     }
 
     async fn send(&self, event: &AlertEvent) -> Result<(), NotifyError> {
-        let color = match event.severity {
-            crate::alert::Severity::Info => "Good",
-            crate::alert::Severity::Warning => "Warning",
-            crate::alert::Severity::Critical => "Attention",
-        };
+        let color = severity_style(event.severity);
 
         let status_emoji = match event.status {
-            crate::alert::AlertStatus::Firing => "\u{1F525}",
-            crate::alert::AlertStatus::Resolved => "\u{2705}",
+            AlertStatus::Firing => "\u{1F525}",
+            AlertStatus::Resolved => "\u{2705}",
         };
 
-        let payload = serde_json::json!({
-            "type": "message",
-            "attachments": [{
-                "contentType": "application/vnd.microsoft.card.adaptive",
-                "content": {
-                    "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
-                    "type": "AdaptiveCard",
-                    "version": "1.4",
-                    "body": [
-                        {
-                            "type": "TextBlock",
-                            "size": "Large",
-                            "weight": "Bolder",
-                            "text": format!("{status_emoji} [{severity}] {rule}",
-                                severity = event.severity_str(),
-                                rule = event.rule_name),
-                            "style": color,
-                        },
-                        {
-                            "type": "FactSet",
-                            "facts": [
-                                { "title": "Status", "value": event.status_str() },
-                                { "title": "Agent", "value": &event.agent_id },
-                                { "title": "Metric", "value": &event.metric_name },
-                                { "title": "Value", "value": format!("{:.2}", event.value) },
-                                { "title": "Threshold", "value": format!("{:.2}", event.threshold) },
-                            ]
-                        }
-                    ]
-                }
-            }]
+        let msg = self.template.render(&event.template_context());
+        let title = msg.title.unwrap_or_else(|| {
+            format!(
+                "{status_emoji} [{severity}] {rule}",
+                severity = event.severity_str(),
+                rule = event.rule_name
+            )
+        });
+        let details = match msg.body {
+            Some(body) => serde_json::json!({ "type": "TextBlock", "text": body, "wrap": true }),
+            None => serde_json::json!({
+                "type": "FactSet",
+                "facts": [
+                    { "title": "Status", "value": event.status_str() },
+                    { "title": "Agent", "value": &event.agent_id },
+                    { "title": "Metric", "value": event.series_name() },
+                    { "title": "Value", "value": format!("{:.2}", event.value) },
+                    { "title": "Threshold", "value": format!("{:.2}", event.threshold) },
+                ]
+            }),
+        };
+
+        self.post(card(&title, color, details)).await
+    }
+
+    async fn send_group(&self, group: &AlertGroup) -> Result<(), NotifyError> {
+        let status_emoji = match group.status() {
+            AlertStatus::Firing => "\u{1F525}",
+            AlertStatus::Resolved => "\u{2705}",
+        };
+        let msg = self.template.render_group(&group.template_context());
+        let title = msg
+            .title
+            .unwrap_or_else(|| format!("{status_emoji} {}", group.title()));
+        let details = serde_json::json!({
+            "type": "TextBlock",
+            "text": msg.body.unwrap_or_else(|| group.lines().join("\n\n")),
+            "wrap": true,
         });
+        self.post(card(&title, severity_style(group.severity()), details))
+            .await
+    }
+}
 
+impl TeamsNotifier {
+    async fn post(&self, payload: serde_json::Value) -> Result<(), NotifyError> {
         self.client
             .post(&self.webhook_url)
             .header("Content-Type", "application/json")
@@ -81,3 +105,35 @@ impl Notifier for TeamsNotifier {
         Ok(())
     }
 }
+
+fn severity_style(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Info => "Good",
+        Severity::Warning => "Warning",
+        Severity::Critical => "Attention",
+    }
+}
+
+fn card(title: &str, color: &str, details: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "type": "message",
+        "attachments": [{
+            "contentType": "application/vnd.microsoft.card.adaptive",
+            "content": {
+                "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                "type": "AdaptiveCard",
+                "version": "1.4",
+                "body": [
+                    {
+                        "type": "TextBlock",
+                        "size": "Large",
+                        "weight": "Bolder",
+                        "text": title,
+                        "style": color,
+                    },
+                    details,
+                ]
+            }
+        }]
+    })
+}
@@ -1,12 +1,15 @@
 use reqwest::Client;
+use sentinel_common::template::MessageTemplate;
 
 use super::channel::{Notifier, NotifyError};
-use crate::alert::AlertEvent;
+use super::registry::{config_str, BuildResult};
+use crate::alert::{AlertEvent, AlertGroup, AlertStatus};
 
 pub struct TelegramNotifier {
     bot_token: String,
     chat_id: String,
     client: Client,
+    template: MessageTemplate,
 }
 
 impl TelegramNotifier {
@@ -15,8 +18,22 @@ impl TelegramNotifier {
             bot_token,
             chat_id,
             client: Client::new(),
+            template: MessageTemplate::default(),
         }
     }
+
+    pub fn with_template(mut self, template: MessageTemplate) -> Self {
+        self.template = template;
+        self
+    }
+}
+
+pub fn from_config(config: &serde_json::Value, template: MessageTemplate) -> BuildResult {
+    let bot_token = config_str(config, "bot_token")?;
+    let chat_id = config_str(config, "chat_id")?;
+    Ok(Box::new(
+        TelegramNotifier::new(bot_token, chat_id).with_template(template),
+    ))
 }
 
 #[tonic::async_trait]
@@ -27,36 +44,71 @@ impl Notifier for TelegramNotifier {
 
     async fn send(&self, event: &AlertEvent) -> Result<(), NotifyError> {
         let emoji = match event.status {
-            crate::alert::AlertStatus::Firing => "\u{1F6A8}",
-            crate::alert::AlertStatus::Resolved => "\u{2705}",
+            AlertStatus::Firing => "\u{1F6A8}",
+            AlertStatus::Resolved => "\u{2705}",
         };
 
-        let text = format!(
-            "{emoji} *\\[{severity}\\] {rule}*\n\
+        let msg = self.template.render(&event.template_context());
+        let templated = !self.template.is_empty();
+        let text = if templated {
+            [msg.title, msg.body]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join("\n")
+        } else {
+            format!(
+                "{emoji} *\\[{severity}\\] {rule}*\n\
              Status: `{status}`\n\
              Agent: `{agent}`\n\
              Metric: `{metric}`\n\
              Value: `{value:.2}` \\(threshold: `{threshold:.2}`\\)",
-            severity = event.severity_str(),
-            rule = escape_markdown(&event.rule_name),
-            status = event.status_str(),
-            agent = escape_markdown(&event.agent_id),
-            metric = escape_markdown(&event.metric_name),
-            value = event.value,
-            threshold = event.threshold,
-        );
+                severity = event.severity_str(),
+                rule = escape_markdown(&event.rule_name),
+                status = event.status_str(),
+                agent = escape_markdown(&event.agent_id),
+                metric = escape_markdown(&event.series_name()),
+                value = event.value,
+                threshold = event.threshold,
+            )
+        };
 
-        let url = format!("https://api.telegram.org/bot{}/sendMessage", self.bot_token);
+        let mut payload = serde_json::json!({
+            "chat_id": self.chat_id,
+            "text": text,
+        });
+        if !templated {
+            payload["parse_mode"] = "MarkdownV2".into();
+        }
+        self.post(&payload).await
+    }
+
+    async fn send_group(&self, group: &AlertGroup) -> Result<(), NotifyError> {
+        let emoji = match group.status() {
+            AlertStatus::Firing => "\u{1F6A8}",
+            AlertStatus::Resolved => "\u{2705}",
+        };
+        let msg = self.template.render_group(&group.template_context());
+        let title = msg
+            .title
+            .unwrap_or_else(|| format!("{emoji} {}", group.title()));
+        let body = msg.body.unwrap_or_else(|| group.body());
 
         let payload = serde_json::json!({
             "chat_id": self.chat_id,
-            "text": text,
-            "parse_mode": "MarkdownV2",
+            "text": format!("{title}\n{body}"),
         });
+        self.post(&payload).await
+    }
+}
+
+impl TelegramNotifier {
+    async fn post(&self, payload: &serde_json::Value) -> Result<(), NotifyError> {
+        let url = format!("https://api.telegram.org/bot{}/sendMessage", self.bot_token);
 
         self.client
             .post(&url)
-            .json(&payload)
+            .json(payload)
             .send()
             .await
             .map_err(|e| NotifyError(e.to_string()))?
@@ -0,0 +1,123 @@
+use reqwest::Client;
+use sentinel_common::template::MessageTemplate;
+
+use super::channel::{Notifier, NotifyError};
+use super::registry::{config_str, optional_str, BuildResult};
+use crate::alert::{AlertEvent, AlertGroup, AlertStatus, Severity};
+
+const VICTOROPS_API_URL: &str = "https://alert.victorops.com/integrations/generic/20131114/alert";
+
+pub struct VictorOpsNotifier {
+    api_key: String,
+    routing_key: String,
+    api_url: String,
+    client: Client,
+    template: MessageTemplate,
+}
+
+impl VictorOpsNotifier {
+    pub fn new(api_key: String, routing_key: String) -> Self {
+        Self {
+            api_key,
+            routing_key,
+            api_url: VICTOROPS_API_URL.into(),
+            client: Client::new(),
+            template: MessageTemplate::default(),
+        }
+    }
+
+    pub fn with_api_url(mut self, api_url: String) -> Self {
+        self.api_url = api_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_template(mut self, template: MessageTemplate) -> Self {
+        self.template = template;
+        self
+    }
+}
+
+pub fn from_config(config: &serde_json::Value, template: MessageTemplate) -> BuildResult {
+    let mut notifier = VictorOpsNotifier::new(
+        config_str(config, "api_key")?,
+        config_str(config, "routing_key")?,
+    )
+    .with_template(template);
+    if let Some(api_url) = optional_str(config, "api_url") {
+        notifier = notifier.with_api_url(api_url);
+    }
+    Ok(Box::new(notifier))
+}
+
+#[tonic::async_trait]
+impl Notifier for VictorOpsNotifier {
+    fn name(&self) -> &str {
+        "victorops"
+    }
+
+    async fn send(&self, event: &AlertEvent) -> Result<(), NotifyError> {
+        let msg = self.template.render(&event.template_context());
+        let display_name = msg
+            .title
+            .unwrap_or_else(|| format!("[{}] {}", event.severity_str(), event.rule_name));
+        let state_message = msg.body.unwrap_or_else(|| {
+            format!(
+                "Agent: {}\nMetric: {}\nValue: {:.2}\nThreshold: {:.2}",
+                event.agent_id,
+                event.series_name(),
+                event.value,
+                event.threshold
+            )
+        });
+        let payload = serde_json::json!({
+            "message_type": message_type(event.severity, event.status),
+            "entity_id": event.fingerprint,
+            "entity_display_name": display_name,
+            "state_message": state_message,
+            "state_start_time": event.fired_at_ms / 1000,
+            "host_name": event.agent_id,
+            "monitoring_tool": "SentinelRS",
+        });
+        self.post(&payload).await
+    }
+
+    async fn send_group(&self, group: &AlertGroup) -> Result<(), NotifyError> {
+        let msg = self.template.render_group(&group.template_context());
+        let payload = serde_json::json!({
+            "message_type": message_type(group.severity(), group.status()),
+            "entity_id": group.key,
+            "entity_display_name": msg.title.unwrap_or_else(|| group.title()),
+            "state_message": msg.body.unwrap_or_else(|| group.body()),
+            "monitoring_tool": "SentinelRS",
+        });
+        self.post(&payload).await
+    }
+}
+
+impl VictorOpsNotifier {
+    async fn post(&self, payload: &serde_json::Value) -> Result<(), NotifyError> {
+        let url = format!("{}/{}/{}", self.api_url, self.api_key, self.routing_key);
+
+        self.client
+            .post(&url)
+            .json(payload)
+            .send()
+            .await
+            .map_err(|e| NotifyError(e.to_string()))?
+            .error_for_status()
+            .map_err(|e| NotifyError(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+fn message_type(severity: Severity, status: AlertStatus) -> &'static str {
+    if status == AlertStatus::Resolved {
+        return "RECOVERY";
+    }
+    match severity {
+        Severity::Info => "INFO",
+        Severity::Warning => "WARNING",
+        Severity::Critical => "CRITICAL",
+    }
+}
@@ -1,13 +1,31 @@
-use reqwest::Client;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::{Certificate, Client, Identity, Method};
+use sentinel_common::template::{MessageTemplate, Template};
+use sentinel_common::webhook::{IDEMPOTENCY_HEADER, SIGNATURE_HEADER};
 
 use super::channel::{Notifier, NotifyError};
+use super::registry::{config_str, optional_str, BuildError, BuildResult};
 use super::signer::sign_payload;
-use crate::alert::AlertEvent;
+use crate::alert::{AlertEvent, AlertGroup};
+
+const DEFAULT_TIMEOUT_SECS: u64 = 10;
+const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 5;
+const IDEMPOTENCY_KEY_TTL: Duration = Duration::from_secs(3600);
 
 pub struct WebhookNotifier {
     url: String,
     secret: Vec<u8>,
     client: Client,
+    template: MessageTemplate,
+    method: Method,
+    headers: HeaderMap,
+    body_template: Option<Template>,
+    group_body_template: Option<Template>,
+    keys: IdempotencyKeys,
 }
 
 impl WebhookNotifier {
@@ -16,8 +34,129 @@ impl WebhookNotifier {
             url,
             secret,
             client: Client::new(),
+            template: MessageTemplate::default(),
+            method: Method::POST,
+            headers: HeaderMap::new(),
+            body_template: None,
+            group_body_template: None,
+            keys: IdempotencyKeys::default(),
         }
     }
+
+    pub fn with_template(mut self, template: MessageTemplate) -> Self {
+        self.template = template;
+        self
+    }
+
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn with_method(mut self, method: Method) -> Self {
+        self.method = method;
+        self
+    }
+
+    pub fn with_headers(mut self, headers: HeaderMap) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    pub fn with_body_template(mut self, event: Option<Template>, group: Option<Template>) -> Self {
+        self.body_template = event;
+        self.group_body_template = group;
+        self
+    }
+}
+
+pub fn from_config(config: &serde_json::Value, template: MessageTemplate) -> BuildResult {
+    let url = config_str(config, "url")?;
+    let secret = optional_str(config, "secret")
+        .unwrap_or_default()
+        .into_bytes();
+    let notifier = WebhookNotifier::new(url, secret)
+        .with_template(template)
+        .with_client(build_client(config)?)
+        .with_method(parse_method(config)?)
+        .with_headers(parse_headers(config)?)
+        .with_body_template(
+            parse_template(config, "body_template")?,
+            parse_template(config, "group_body_template")?,
+        );
+    Ok(Box::new(notifier))
+}
+
+fn parse_method(config: &serde_json::Value) -> Result<Method, BuildError> {
+    match optional_str(config, "method")
+        .map(|m| m.to_ascii_uppercase())
+        .as_deref()
+    {
+        None | Some("POST") => Ok(Method::POST),
+        Some("PUT") => Ok(Method::PUT),
+        Some("PATCH") => Ok(Method::PATCH),
+        Some(other) => Err(format!("unsupported webhook method '{other}'").into()),
+    }
+}
+
+fn parse_headers(config: &serde_json::Value) -> Result<HeaderMap, BuildError> {
+    let mut headers = HeaderMap::new();
+    if let Some(map) = config.get("headers").and_then(|h| h.as_object()) {
+        for (name, value) in map {
+            let value = value
+                .as_str()
+                .ok_or_else(|| format!("header '{name}' must be a string"))?;
+            headers.insert(
+                HeaderName::from_bytes(name.as_bytes())?,
+                HeaderValue::from_str(value)?,
+            );
+        }
+    }
+    if let Some(token) = optional_str(config, "bearer_token") {
+        let mut value = HeaderValue::from_str(&format!("Bearer {token}"))?;
+        value.set_sensitive(true);
+        headers.insert(AUTHORIZATION, value);
+    }
+    Ok(headers)
+}
+
+fn parse_template(config: &serde_json::Value, key: &str) -> Result<Option<Template>, BuildError> {
+    match optional_str(config, key) {
+        Some(src) if !src.trim().is_empty() => Template::parse(&src)
+            .map(Some)
+            .map_err(|e| format!("{key} {e}").into()),
+        _ => Ok(None),
+    }
+}
+
+fn build_client(config: &serde_json::Value) -> Result<Client, BuildError> {
+    let secs =
+        |key: &str, default: u64| config.get(key).and_then(|v| v.as_u64()).unwrap_or(default);
+    let mut builder = Client::builder()
+        .timeout(Duration::from_secs(secs(
+            "timeout_secs",
+            DEFAULT_TIMEOUT_SECS,
+        )))
+        .connect_timeout(Duration::from_secs(secs(
+            "connect_timeout_secs",
+            DEFAULT_CONNECT_TIMEOUT_SECS,
+        )));
+
+    match (
+        optional_str(config, "client_cert"),
+        optional_str(config, "client_key"),
+    ) {
+        (Some(cert), Some(key)) => {
+            let pem = format!("{}\n{}", key.trim(), cert.trim());
+            builder = builder.identity(Identity::from_pem(pem.as_bytes())?);
+        }
+        (None, None) => {}
+        _ => return Err("client_cert and client_key must be set together".into()),
+    }
+    if let Some(ca) = optional_str(config, "ca_cert") {
+        builder = builder.add_root_certificate(Certificate::from_pem(ca.as_bytes())?);
+    }
+    Ok(builder.build()?)
 }
 
 #[tonic::async_trait]
@@ -27,13 +166,68 @@ impl Notifier for WebhookNotifier {
     }
 
     async fn send(&self, event: &AlertEvent) -> Result<(), NotifyError> {
-        let body = serde_json::to_vec(event).map_err(|e| NotifyError(e.to_string()))?;
-        let signature = sign_payload(&self.secret, &body);
+        let ctx = event.template_context();
+        let body = match &self.body_template {
+            Some(t) => t.render(&serde_json::to_value(&ctx).unwrap_or_default()),
+            None => {
+                let mut payload =
+                    serde_json::to_value(event).map_err(|e| NotifyError(e.to_string()))?;
+                let msg = self.template.render(&ctx);
+                if let Some(title) = msg.title {
+                    payload["title"] = title.into();
+                }
+                if let Some(body) = msg.body {
+                    payload["message"] = body.into();
+                }
+                payload.to_string()
+            }
+        };
+        let delivery = format!("event:{}:{}", event.id, event.status_str());
+        self.deliver(&delivery, body).await
+    }
+
+    async fn send_group(&self, group: &AlertGroup) -> Result<(), NotifyError> {
+        let ctx = group.template_context();
+        let body = match &self.group_body_template {
+            Some(t) => t.render(&serde_json::to_value(&ctx).unwrap_or_default()),
+            None => {
+                let mut payload =
+                    serde_json::to_value(group).map_err(|e| NotifyError(e.to_string()))?;
+                payload["status"] = group.status_str().into();
+                let msg = self.template.render_group(&ctx);
+                if let Some(title) = msg.title {
+                    payload["title"] = title.into();
+                }
+                if let Some(body) = msg.body {
+                    payload["message"] = body.into();
+                }
+                payload.to_string()
+            }
+        };
+        let alerts: Vec<String> = group
+            .alerts
+            .iter()
+            .map(|a| format!("{}:{}", a.id, a.status_str()))
+            .collect();
+        let delivery = format!("group:{}:{}:{}", group.key, group.repeat, alerts.join(","));
+        self.deliver(&delivery, body).await
+    }
+}
+
+impl WebhookNotifier {
+    async fn deliver(&self, delivery: &str, body: String) -> Result<(), NotifyError> {
+        let signature = sign_payload(&self.secret, body.as_bytes());
+        let key = self.keys.get(delivery);
+        let mut headers = self.headers.clone();
+        headers
+            .entry(CONTENT_TYPE)
+            .or_insert(HeaderValue::from_static("application/json"));
 
         self.client
-            .post(&self.url)
-            .header("Content-Type", "application/json")
-            .header("X-Sentinel-Signature", &signature)
+            .request(self.method.clone(), &self.url)
+            .headers(headers)
+            .header(SIGNATURE_HEADER, &signature)
+            .header(IDEMPOTENCY_HEADER, &key)
             .body(body)
             .send()
             .await
@@ -41,6 +235,78 @@ impl Notifier for WebhookNotifier {
             .error_for_status()
             .map_err(|e| NotifyError(e.to_string()))?;
 
+        self.keys.complete(delivery);
         Ok(())
     }
 }
+
+#[derive(Default)]
+struct IdempotencyKeys {
+    pending: Mutex<HashMap<String, (String, Instant)>>,
+}
+
+impl IdempotencyKeys {
+    fn get(&self, delivery: &str) -> String {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        pending.retain(|_, (_, created)| created.elapsed() < IDEMPOTENCY_KEY_TTL);
+        pending
+            .entry(delivery.to_string())
+            .or_insert_with(|| (uuid::Uuid::new_v4().to_string(), Instant::now()))
+            .0
+            .clone()
+    }
+
+    fn complete(&self, delivery: &str) {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        pending.remove(delivery);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn idempotency_key_is_kept_until_complete() {
+        let keys = IdempotencyKeys::default();
+        let first = keys.get("event:e-1:firing");
+        assert_eq!(keys.get("event:e-1:firing"), first);
+        assert_ne!(keys.get("event:e-1:resolved"), first);
+        keys.complete("event:e-1:firing");
+        assert_ne!(keys.get("event:e-1:firing"), first);
+    }
+
+    #[test]
+    fn parses_method_and_headers() {
+        let config = json!({
+            "url": "https://hooks.example.com",
+            "method": "put",
+            "headers": {"X-Team": "ops", "Content-Type": "text/plain"},
+            "bearer_token": "tok",
+        });
+        assert_eq!(parse_method(&config).unwrap(), Method::PUT);
+        let headers = parse_headers(&config).unwrap();
+        assert_eq!(headers["x-team"], "ops");
+        assert_eq!(headers[CONTENT_TYPE], "text/plain");
+        assert_eq!(headers[AUTHORIZATION], "Bearer tok");
+
+        assert!(parse_method(&json!({"method": "DELETE"})).is_err());
+        assert!(parse_headers(&json!({"headers": {"bad name": "x"}})).is_err());
+    }
+
+    #[test]
+    fn client_cert_requires_key() {
+        let err = build_client(&json!({"client_cert": "-----BEGIN CERTIFICATE-----"}))
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("client_key"));
+        let err = from_config(
+            &json!({"url": "https://x", "body_template": "{{ a"}),
+            MessageTemplate::default(),
+        )
+        .err()
+        .unwrap();
+        assert!(err.to_string().contains("body_template"));
+    }
+}
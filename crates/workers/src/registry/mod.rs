@@ -1,3 +1,7 @@
+mod partition;
+mod ring;
 mod store;
 
+pub use partition::{route_subject, Partitioner, ROUTE_SUBJECT_PREFIX};
+pub use ring::{HashRing, DEFAULT_VIRTUAL_NODES};
 pub use store::WorkerRegistry;
@@ -0,0 +1,137 @@
+use std::sync::{Arc, RwLock};
+
+use tokio::sync::watch;
+
+use super::ring::HashRing;
+
+pub const ROUTE_SUBJECT_PREFIX: &str = "sentinel.alerts";
+
+pub fn route_subject(worker_id: &str) -> String {
+    format!("{ROUTE_SUBJECT_PREFIX}.{worker_id}")
+}
+
+pub struct Partitioner {
+    self_id: String,
+    virtual_nodes: usize,
+    members: RwLock<Vec<String>>,
+    ring: RwLock<HashRing>,
+    generation: watch::Sender<u64>,
+}
+
+impl Partitioner {
+    pub fn new(self_id: &str, virtual_nodes: usize) -> Arc<Self> {
+        let members = vec![self_id.to_string()];
+        let ring = HashRing::new(&members, virtual_nodes);
+        let (generation, _) = watch::channel(0);
+        Arc::new(Self {
+            self_id: self_id.to_string(),
+            virtual_nodes,
+            members: RwLock::new(members),
+            ring: RwLock::new(ring),
+            generation,
+        })
+    }
+
+    pub fn self_id(&self) -> &str {
+        &self.self_id
+    }
+
+    pub fn update_members(&self, peers: Vec<String>) -> bool {
+        let mut members = peers;
+        members.push(self.self_id.clone());
+        members.sort();
+        members.dedup();
+
+        let mut current = self.members.write().unwrap_or_else(|e| e.into_inner());
+        if *current == members {
+            return false;
+        }
+        let ring = HashRing::new(&members, self.virtual_nodes);
+        tracing::info!(
+            target: "registry",
+            members = members.len(),
+            previous = current.len(),
+            "Partition ring rebalanced"
+        );
+        *current = members;
+        *self.ring.write().unwrap_or_else(|e| e.into_inner()) = ring;
+        drop(current);
+
+        self.generation.send_modify(|g| *g += 1);
+        true
+    }
+
+    pub fn owner(&self, agent_id: &str) -> String {
+        let ring = self.ring.read().unwrap_or_else(|e| e.into_inner());
+        ring.owner(agent_id).unwrap_or(&self.self_id).to_string()
+    }
+
+    pub fn owns(&self, agent_id: &str) -> bool {
+        self.owner(agent_id) == self.self_id
+    }
+
+    pub fn members(&self) -> Vec<String> {
+        self.members.read().map(|m| m.clone()).unwrap_or_default()
+    }
+
+    pub fn generation(&self) -> u64 {
+        *self.generation.borrow()
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<u64> {
+        self.generation.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alone_owns_everything() {
+        let p = Partitioner::new("w1", 16);
+        assert!(p.owns("agent-1"));
+        assert!(p.owns("agent-2"));
+        assert_eq!(p.members(), vec!["w1".to_string()]);
+    }
+
+    #[test]
+    fn membership_changes_bump_generation() {
+        let p = Partitioner::new("w1", 16);
+        let rx = p.subscribe();
+        assert!(p.update_members(vec!["w2".into(), "w1".into()]));
+        assert_eq!(p.generation(), 1);
+        assert!(rx.has_changed().unwrap());
+
+        assert!(!p.update_members(vec!["w1".into(), "w2".into()]));
+        assert!(!p.update_members(vec!["w2".into()]));
+        assert_eq!(p.generation(), 1);
+        assert_eq!(p.members(), vec!["w1".to_string(), "w2".to_string()]);
+    }
+
+    #[test]
+    fn peers_agree_on_owner_and_failover_reassigns() {
+        let w1 = Partitioner::new("w1", 16);
+        let w2 = Partitioner::new("w2", 16);
+        let peers = vec!["w1".to_string(), "w2".to_string()];
+        w1.update_members(peers.clone());
+        w2.update_members(peers);
+
+        let agents: Vec<String> = (0..50).map(|i| format!("agent-{i}")).collect();
+        for a in &agents {
+            assert_eq!(w1.owner(a), w2.owner(a));
+            assert!(w1.owns(a) ^ w2.owns(a));
+        }
+
+        w1.update_members(vec!["w1".into()]);
+        assert!(agents.iter().all(|a| w1.owns(a)));
+    }
+
+    #[test]
+    fn route_subject_per_worker() {
+        assert_eq!(
+            route_subject("host-abcd1234"),
+            "sentinel.alerts.host-abcd1234"
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use sha2::{Digest, Sha256};
+
+pub const DEFAULT_VIRTUAL_NODES: usize = 64;
+
+#[derive(Debug, Clone, Default)]
+pub struct HashRing {
+    points: Vec<(u64, String)>,
+}
+
+impl HashRing {
+    pub fn new(nodes: &[String], virtual_nodes: usize) -> Self {
+        let mut points: Vec<(u64, String)> = nodes
+            .iter()
+            .flat_map(|node| {
+                (0..virtual_nodes.max(1)).map(move |i| (hash(&format!("{node}#{i}")), node.clone()))
+            })
+            .collect();
+        points.sort();
+        Self { points }
+    }
+
+    pub fn owner(&self, key: &str) -> Option<&str> {
+        if self.points.is_empty() {
+            return None;
+        }
+        let h = hash(key);
+        let idx = self.points.partition_point(|(p, _)| *p < h);
+        let (_, node) = &self.points[idx % self.points.len()];
+        Some(node)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+}
+
+fn hash(key: &str) -> u64 {
+    let digest = Sha256::digest(key.as_bytes());
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn nodes(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    fn agents() -> Vec<String> {
+        (0..1000).map(|i| format!("agent-{i}")).collect()
+    }
+
+    #[test]
+    fn empty_ring_has_no_owner() {
+        assert_eq!(HashRing::default().owner("agent-1"), None);
+    }
+
+    #[test]
+    fn single_node_owns_everything() {
+        let ring = HashRing::new(&nodes(&["w1"]), DEFAULT_VIRTUAL_NODES);
+        assert!(agents().iter().all(|a| ring.owner(a) == Some("w1")));
+    }
+
+    #[test]
+    fn keys_spread_across_nodes() {
+        let ring = HashRing::new(&nodes(&["w1", "w2", "w3"]), DEFAULT_VIRTUAL_NODES);
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        let agents = agents();
+        for a in &agents {
+            *counts.entry(ring.owner(a).unwrap()).or_default() += 1;
+        }
+        assert_eq!(counts.len(), 3);
+        assert!(counts.values().all(|c| *c > 200), "{counts:?}");
+    }
+
+    #[test]
+    fn removing_a_node_only_moves_its_keys() {
+        let before = HashRing::new(&nodes(&["w1", "w2", "w3"]), DEFAULT_VIRTUAL_NODES);
+        let after = HashRing::new(&nodes(&["w1", "w3"]), DEFAULT_VIRTUAL_NODES);
+        for a in agents() {
+            let old = before.owner(&a).unwrap();
+            if old != "w2" {
+                assert_eq!(after.owner(&a), Some(old));
+            }
+        }
+    }
+
+    #[test]
+    fn ownership_is_order_independent() {
+        let a = HashRing::new(&nodes(&["w1", "w2", "w3"]), 16);
+        let b = HashRing::new(&nodes(&["w3", "w1", "w2"]), 16);
+        assert!(agents().iter().all(|k| a.owner(k) == b.owner(k)));
+    }
+}
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use tokio_util::sync::CancellationToken;
 
+use super::partition::Partitioner;
 use crate::config::RegistryConfig;
 use crate::identity::WorkerIdentity;
 
@@ -21,6 +22,7 @@ pub struct WorkerRegistry {
     bucket: String,
     identity: Arc<WorkerIdentity>,
     peers: Arc<RwLock<Vec<String>>>,
+    partitioner: Arc<Partitioner>,
     js: jetstream::Context,
 }
 
@@ -39,10 +41,13 @@ impl WorkerRegistry {
         };
         js.create_key_value(kv_config).await?;
 
+        let partitioner = Partitioner::new(identity.id(), config.virtual_nodes);
+
         Ok(Arc::new(Self {
             bucket: config.bucket.clone(),
             identity,
             peers: Arc::new(RwLock::new(Vec::new())),
+            partitioner,
             js,
         }))
     }
@@ -50,6 +55,12 @@ impl WorkerRegistry {
     pub fn spawn_heartbeat(self: &Arc<Self>, interval: Duration, cancel: CancellationToken) {
         let registry = Arc::clone(self);
         tokio::spawn(async move {
+            if let Err(e) = registry.heartbeat().await {
+                tracing::warn!(target: "registry", error = %e, "Registry heartbeat failed");
+            }
+            if let Err(e) = registry.refresh_peers().await {
+                tracing::warn!(target: "registry", error = %e, "Peer refresh failed");
+            }
             loop {
                 tokio::select! {
                     _ = cancel.cancelled() => {
@@ -103,11 +114,16 @@ impl WorkerRegistry {
             }
         }
 
+        self.partitioner.update_members(keys.clone());
         let mut guard = self.peers.write().await;
         *guard = keys;
         Ok(())
     }
 
+    pub fn partitioner(&self) -> Arc<Partitioner> {
+        Arc::clone(&self.partitioner)
+    }
+
     pub fn peers(&self) -> Vec<String> {
         self.peers.try_read().map(|g| g.clone()).unwrap_or_default()
     }
@@ -73,6 +73,54 @@ const MIGRATIONS: &[(&str, &str)] = &[
         "017_metrics_5m_aggregate.sql",
         include_str!("../../../../migrations/017_metrics_5m_aggregate.sql"),
     ),
+    (
+        "019_label_matchers.sql",
+        include_str!("../../../../migrations/019_label_matchers.sql"),
+    ),
+    (
+        "020_rule_aggregation.sql",
+        include_str!("../../../../migrations/020_rule_aggregation.sql"),
+    ),
+    (
+        "021_rule_expressions.sql",
+        include_str!("../../../../migrations/021_rule_expressions.sql"),
+    ),
+    (
+        "022_alert_states.sql",
+        include_str!("../../../../migrations/022_alert_states.sql"),
+    ),
+    (
+        "023_rule_absent_for.sql",
+        include_str!("../../../../migrations/023_rule_absent_for.sql"),
+    ),
+    (
+        "024_dlq_history_link.sql",
+        include_str!("../../../../migrations/024_dlq_history_link.sql"),
+    ),
+    (
+        "025_dlq_replay.sql",
+        include_str!("../../../../migrations/025_dlq_replay.sql"),
+    ),
+    (
+        "026_silences_and_acks.sql",
+        include_str!("../../../../migrations/026_silences_and_acks.sql"),
+    ),
+    (
+        "027_rule_grouping.sql",
+        include_str!("../../../../migrations/027_rule_grouping.sql"),
+    ),
+    (
+        "028_escalation_policies.sql",
+        include_str!("../../../../migrations/028_escalation_policies.sql"),
+    ),
+    (
+        "029_alert_state_labels.sql",
+        include_str!("../../../../migrations/029_alert_state_labels.sql"),
+    ),
+    (
+        "030_agent_pending_keys.sql",
+        include_str!("../../../../migrations/030_agent_pending_keys.sql"),
+    ),
 ];
 
 pub async fn run_migrations(pool: &PgPool) -> Result<Vec<String>, sqlx::Error> {
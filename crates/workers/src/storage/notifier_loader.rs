@@ -1,3 +1,4 @@
+use sentinel_common::envelope::Envelope;
 use sqlx::PgPool;
 
 pub struct NotifierConfigRow {
@@ -9,11 +10,12 @@ pub struct NotifierConfigRow {
 
 pub struct NotifierConfigLoader {
     pool: PgPool,
+    envelope: Envelope,
 }
 
 impl NotifierConfigLoader {
-    pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+    pub fn new(pool: PgPool, envelope: Envelope) -> Self {
+        Self { pool, envelope }
     }
 
     pub fn pool(&self) -> PgPool {
@@ -35,11 +37,22 @@ impl NotifierConfigLoader {
 
         Ok(rows
             .into_iter()
-            .map(|r| NotifierConfigRow {
-                id: r.id,
-                name: r.name,
-                ntype: r.ntype,
-                config: r.config,
+            .filter_map(|r| match self.envelope.open_config(&r.config) {
+                Ok(config) => Some(NotifierConfigRow {
+                    id: r.id,
+                    name: r.name,
+                    ntype: r.ntype,
+                    config,
+                }),
+                Err(e) => {
+                    tracing::error!(
+                        target: "notify",
+                        notifier_id = %r.id,
+                        error = %e,
+                        "cannot decrypt notifier config"
+                    );
+                    None
+                }
             })
             .collect())
     }
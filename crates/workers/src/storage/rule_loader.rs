@@ -1,6 +1,10 @@
 use sqlx::PgPool;
 use std::collections::HashMap;
 
+use sentinel_common::aggregation::Aggregation;
+use sentinel_common::grouping::GroupPolicy;
+use sentinel_common::labels::LabelMatcher;
+
 use crate::alert::{Condition, Rule, Severity};
 
 pub struct RuleLoader {
@@ -14,8 +18,9 @@ impl RuleLoader {
 
     pub async fn load_enabled(&self) -> Result<Vec<Rule>, sqlx::Error> {
         let rows = sqlx::query_as::<_, RuleRow>(
-            "SELECT id, name, agent_pattern, metric_name, condition, threshold,
-                    for_duration_ms, severity, annotations, notifier_ids
+            "SELECT id, name, agent_pattern, metric_name, label_matchers, expression, absent_for_ms, condition,
+                    threshold, aggregation, window_ms, min_samples, for_duration_ms,
+                    severity, annotations, notifier_ids, grouping, escalation_policy_id
              FROM alert_rules WHERE enabled = TRUE",
         )
         .fetch_all(&self.pool)
@@ -31,12 +36,20 @@ struct RuleRow {
     name: String,
     agent_pattern: String,
     metric_name: String,
+    label_matchers: serde_json::Value,
+    expression: Option<String>,
+    absent_for_ms: Option<i64>,
     condition: String,
     threshold: f64,
+    aggregation: String,
+    window_ms: i64,
+    min_samples: i32,
     for_duration_ms: i64,
     severity: String,
     annotations: serde_json::Value,
     notifier_ids: serde_json::Value,
+    grouping: Option<serde_json::Value>,
+    escalation_policy_id: Option<String>,
 }
 
 impl RuleRow {
@@ -49,6 +62,7 @@ impl RuleRow {
             "Equal" => Condition::Equal,
             _ => return None,
         };
+        let aggregation: Aggregation = self.aggregation.parse().ok()?;
         let severity = match self.severity.as_str() {
             "info" => Severity::Info,
             "warning" => Severity::Warning,
@@ -59,18 +73,29 @@ impl RuleRow {
             serde_json::from_value(self.annotations).unwrap_or_default();
         let notifier_ids: Vec<String> =
             serde_json::from_value(self.notifier_ids).unwrap_or_default();
+        let label_matchers: Vec<LabelMatcher> = serde_json::from_value(self.label_matchers).ok()?;
+        let grouping: Option<GroupPolicy> =
+            self.grouping.and_then(|v| serde_json::from_value(v).ok());
 
         Some(Rule {
             id: self.id,
             name: self.name,
             agent_pattern: self.agent_pattern,
             metric_name: self.metric_name,
+            label_matchers,
+            expression: self.expression,
+            absent_for_ms: self.absent_for_ms.filter(|ms| *ms > 0),
             condition,
             threshold: self.threshold,
+            aggregation,
+            window_ms: self.window_ms,
+            min_samples: self.min_samples.max(1) as usize,
             for_duration_ms: self.for_duration_ms,
             severity,
             annotations,
             notifier_ids,
+            grouping,
+            escalation_policy_id: self.escalation_policy_id,
         })
     }
 }
@@ -1,5 +1,5 @@
 mod secret_provider;
 mod verifier;
 
-pub use secret_provider::SecretProvider;
+pub use secret_provider::{DbSecretProvider, SecretProvider};
 pub use verifier::{verify_batch, VerifyResult};
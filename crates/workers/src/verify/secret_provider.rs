@@ -1,4 +1,38 @@
+use sentinel_common::envelope::Envelope;
+use sqlx::PgPool;
+
 #[tonic::async_trait]
 pub trait SecretProvider: Send + Sync {
     async fn get_secret(&self, agent_id: &str) -> Option<Vec<u8>>;
 }
+
+pub struct DbSecretProvider {
+    pool: PgPool,
+    envelope: Envelope,
+}
+
+impl DbSecretProvider {
+    pub fn new(pool: PgPool, envelope: Envelope) -> Self {
+        Self { pool, envelope }
+    }
+}
+
+#[tonic::async_trait]
+impl SecretProvider for DbSecretProvider {
+    async fn get_secret(&self, agent_id: &str) -> Option<Vec<u8>> {
+        let stored: Vec<u8> = sqlx::query_scalar("SELECT secret FROM agents WHERE agent_id = $1")
+            .bind(agent_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| {
+                tracing::error!(target: "db", agent_id, error = %e, "agent secret lookup failed");
+            })
+            .ok()??;
+        self.envelope
+            .open_bytes(&stored)
+            .map_err(|e| {
+                tracing::error!(target: "auth", agent_id, error = %e, "cannot decrypt agent secret");
+            })
+            .ok()
+    }
+}
@@ -0,0 +1,382 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{HeaderMap, Method, StatusCode, Uri};
+use axum::Router;
+use serde_json::json;
+
+use sentinel_workers::alert::{AlertEvent, AlertGroup, AlertStatus, Severity};
+use sentinel_workers::notifier::registry::NotifierRegistry;
+
+#[derive(Debug, Clone)]
+struct Captured {
+    method: Method,
+    uri: String,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl Captured {
+    fn json(&self) -> serde_json::Value {
+        serde_json::from_slice(&self.body).unwrap()
+    }
+
+    fn form(&self) -> BTreeMap<String, String> {
+        url::form_urlencoded::parse(&self.body)
+            .into_owned()
+            .collect()
+    }
+}
+
+#[derive(Clone)]
+struct MockState {
+    status: StatusCode,
+    requests: Arc<Mutex<Vec<Captured>>>,
+}
+
+struct MockServer {
+    url: String,
+    requests: Arc<Mutex<Vec<Captured>>>,
+}
+
+impl MockServer {
+    async fn start(status: StatusCode) -> Self {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let state = MockState {
+            status,
+            requests: requests.clone(),
+        };
+        let app = Router::new().fallback(capture).with_state(state);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        Self {
+            url: format!("http://{addr}"),
+            requests,
+        }
+    }
+
+    fn single(&self) -> Captured {
+        let requests = self.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1, "expected exactly one request");
+        requests[0].clone()
+    }
+}
+
+async fn capture(
+    State(state): State<MockState>,
+    method: Method,
+    uri: Uri,
+    headers: HeaderMap,
+    body: Bytes,
+) -> (StatusCode, &'static str) {
+    state.requests.lock().unwrap().push(Captured {
+        method,
+        uri: uri.to_string(),
+        headers,
+        body,
+    });
+    (state.status, "{}")
+}
+
+fn event(severity: Severity, status: AlertStatus) -> AlertEvent {
+    AlertEvent {
+        id: "e-1".into(),
+        fingerprint: "fp-web-1".into(),
+        rule_id: "r-1".into(),
+        rule_name: "cpu high".into(),
+        agent_id: "web-1".into(),
+        metric_name: "cpu.usage".into(),
+        labels: BTreeMap::new(),
+        severity,
+        status,
+        value: 97.5,
+        threshold: 90.0,
+        fired_at_ms: 1_700_000_000_000,
+        resolved_at_ms: None,
+        annotations: Default::default(),
+    }
+}
+
+fn group() -> AlertGroup {
+    AlertGroup {
+        key: "rule_id=r-1".into(),
+        labels: BTreeMap::from([("rule_id".to_string(), "r-1".to_string())]),
+        alerts: vec![event(Severity::Warning, AlertStatus::Firing)],
+        repeat: false,
+    }
+}
+
+async fn send(ntype: &str, config: serde_json::Value, event: &AlertEvent) {
+    NotifierRegistry::default()
+        .build(ntype, &config)
+        .unwrap()
+        .send(event)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn mattermost_posts_attachment() {
+    let server = MockServer::start(StatusCode::OK).await;
+    let config = json!({
+        "webhook_url": format!("{}/hooks/abc", server.url),
+        "channel": "ops",
+        "username": "sentinel",
+    });
+    send(
+        "mattermost",
+        config,
+        &event(Severity::Critical, AlertStatus::Firing),
+    )
+    .await;
+
+    let req = server.single();
+    assert_eq!(req.method, Method::POST);
+    assert_eq!(req.uri, "/hooks/abc");
+    let body = req.json();
+    assert_eq!(body["channel"], "ops");
+    assert_eq!(body["username"], "sentinel");
+    assert_eq!(body["attachments"][0]["color"], "#d32f2f");
+    assert!(body["text"].as_str().unwrap().contains("cpu high"));
+}
+
+#[tokio::test]
+async fn rocketchat_uses_alias() {
+    let server = MockServer::start(StatusCode::OK).await;
+    let config = json!({
+        "webhook_url": format!("{}/hooks/abc/def", server.url),
+        "username": "sentinel",
+    });
+    send(
+        "rocketchat",
+        config,
+        &event(Severity::Info, AlertStatus::Firing),
+    )
+    .await;
+
+    let body = server.single().json();
+    assert_eq!(body["alias"], "sentinel");
+    assert!(body.get("username").is_none());
+}
+
+#[tokio::test]
+async fn matrix_puts_room_message() {
+    let server = MockServer::start(StatusCode::OK).await;
+    let config = json!({
+        "homeserver_url": server.url,
+        "access_token": "syt_secret",
+        "room_id": "!ops:example.org",
+    });
+    send(
+        "matrix",
+        config,
+        &event(Severity::Warning, AlertStatus::Firing),
+    )
+    .await;
+
+    let req = server.single();
+    assert_eq!(req.method, Method::PUT);
+    assert!(req
+        .uri
+        .starts_with("/_matrix/client/v3/rooms/!ops:example.org/send/m.room.message/"));
+    assert_eq!(req.headers["authorization"], "Bearer syt_secret");
+    let body = req.json();
+    assert_eq!(body["msgtype"], "m.text");
+    assert!(body["body"].as_str().unwrap().contains("web-1"));
+}
+
+#[tokio::test]
+async fn googlechat_threads_by_fingerprint() {
+    let server = MockServer::start(StatusCode::OK).await;
+    let config = json!({ "webhook_url": format!("{}/v1/spaces/AAA/messages?key=k", server.url) });
+    send(
+        "googlechat",
+        config,
+        &event(Severity::Warning, AlertStatus::Resolved),
+    )
+    .await;
+
+    let req = server.single();
+    assert!(req.uri.contains("key=k"));
+    assert!(req
+        .uri
+        .contains("messageReplyOption=REPLY_MESSAGE_FALLBACK_TO_NEW_THREAD"));
+    let body = req.json();
+    assert_eq!(body["thread"]["threadKey"], "fp-web-1");
+    assert!(body["text"].as_str().unwrap().contains("cpu high"));
+}
+
+#[tokio::test]
+async fn pushover_posts_form_with_priority() {
+    let server = MockServer::start(StatusCode::OK).await;
+    let config = json!({
+        "token": "app-token",
+        "user_key": "user-key",
+        "device": "phone",
+        "api_url": format!("{}/1/messages.json", server.url),
+    });
+    send(
+        "pushover",
+        config,
+        &event(Severity::Critical, AlertStatus::Firing),
+    )
+    .await;
+
+    let req = server.single();
+    assert_eq!(req.uri, "/1/messages.json");
+    let form = req.form();
+    assert_eq!(form["token"], "app-token");
+    assert_eq!(form["user"], "user-key");
+    assert_eq!(form["device"], "phone");
+    assert_eq!(form["priority"], "1");
+}
+
+#[tokio::test]
+async fn victorops_maps_resolved_to_recovery() {
+    let server = MockServer::start(StatusCode::OK).await;
+    let config = json!({
+        "api_key": "api-key",
+        "routing_key": "db-team",
+        "api_url": format!("{}/alert", server.url),
+    });
+    send(
+        "victorops",
+        config,
+        &event(Severity::Critical, AlertStatus::Resolved),
+    )
+    .await;
+
+    let req = server.single();
+    assert_eq!(req.uri, "/alert/api-key/db-team");
+    let body = req.json();
+    assert_eq!(body["message_type"], "RECOVERY");
+    assert_eq!(body["entity_id"], "fp-web-1");
+}
+
+#[tokio::test]
+async fn victorops_sends_groups() {
+    let server = MockServer::start(StatusCode::OK).await;
+    let config = json!({
+        "api_key": "api-key",
+        "routing_key": "db-team",
+        "api_url": format!("{}/alert", server.url),
+    });
+    NotifierRegistry::default()
+        .build("victorops", &config)
+        .unwrap()
+        .send_group(&group())
+        .await
+        .unwrap();
+
+    let body = server.single().json();
+    assert_eq!(body["message_type"], "WARNING");
+    assert_eq!(body["entity_id"], "rule_id=r-1");
+}
+
+#[tokio::test]
+async fn sns_publishes_signed_request() {
+    let server = MockServer::start(StatusCode::OK).await;
+    let config = json!({
+        "endpoint": format!("{}/", server.url),
+        "region": "eu-west-1",
+        "topic_arn": "arn:aws:sns:eu-west-1:123456789012:alerts",
+        "access_key_id": "AKIDEXAMPLE",
+        "secret_access_key": "secret",
+        "session_token": "session",
+    });
+    send(
+        "sns",
+        config,
+        &event(Severity::Critical, AlertStatus::Firing),
+    )
+    .await;
+
+    let req = server.single();
+    assert_eq!(req.method, Method::POST);
+    let form = req.form();
+    assert_eq!(form["Action"], "Publish");
+    assert_eq!(
+        form["TopicArn"],
+        "arn:aws:sns:eu-west-1:123456789012:alerts"
+    );
+    assert_eq!(form["Subject"], "[CRIT] firing cpu high");
+    let message: serde_json::Value = serde_json::from_str(&form["Message"]).unwrap();
+    assert_eq!(message["fingerprint"], "fp-web-1");
+
+    let auth = req.headers["authorization"].to_str().unwrap();
+    assert!(auth.starts_with("AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/"));
+    assert!(auth.contains("/eu-west-1/sns/aws4_request"));
+    assert!(auth.contains("SignedHeaders=content-type;host;x-amz-date;x-amz-security-token"));
+    assert_eq!(req.headers["x-amz-security-token"], "session");
+    assert!(req.headers.contains_key("x-amz-date"));
+}
+
+#[tokio::test]
+async fn error_status_is_reported() {
+    let server = MockServer::start(StatusCode::INTERNAL_SERVER_ERROR).await;
+    let config = json!({ "webhook_url": format!("{}/hook", server.url) });
+    let result = NotifierRegistry::default()
+        .build("googlechat", &config)
+        .unwrap()
+        .send(&event(Severity::Info, AlertStatus::Firing))
+        .await;
+    assert!(result.is_err());
+    assert_eq!(server.requests.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn webhook_sends_custom_request() {
+    let server = MockServer::start(StatusCode::OK).await;
+    let config = json!({
+        "url": format!("{}/hooks/alerts", server.url),
+        "secret": "hook-secret",
+        "method": "PUT",
+        "headers": { "X-Team": "ops" },
+        "bearer_token": "tok-123",
+        "body_template": "{\"text\": {{ rule_name | json }}, \"state\": \"{{ status }}\"}",
+    });
+    send(
+        "webhook",
+        config,
+        &event(Severity::Critical, AlertStatus::Firing),
+    )
+    .await;
+
+    let req = server.single();
+    assert_eq!(req.method, Method::PUT);
+    assert_eq!(req.uri, "/hooks/alerts");
+    assert_eq!(req.headers["x-team"], "ops");
+    assert_eq!(req.headers["authorization"], "Bearer tok-123");
+    assert_eq!(req.headers["content-type"], "application/json");
+    assert_eq!(req.json(), json!({"text": "cpu high", "state": "firing"}));
+    assert!(req.headers.contains_key("idempotency-key"));
+
+    let signature = req.headers["x-sentinel-signature"].to_str().unwrap();
+    sentinel_common::webhook::verify(b"hook-secret", &req.body, signature).unwrap();
+    assert!(sentinel_common::webhook::verify(b"other", &req.body, signature).is_err());
+}
+
+#[tokio::test]
+async fn webhook_retries_reuse_idempotency_key() {
+    use sentinel_workers::notifier::retry::RetryNotifier;
+
+    let server = MockServer::start(StatusCode::SERVICE_UNAVAILABLE).await;
+    let config = json!({ "url": format!("{}/hook", server.url) });
+    let notifier = NotifierRegistry::default()
+        .build("webhook", &config)
+        .unwrap();
+    let retry = RetryNotifier::new(notifier, 2, 1);
+
+    assert!(retry.send_group(&group()).await.is_err());
+    let requests = server.requests.lock().unwrap();
+    assert_eq!(requests.len(), 3);
+    let key = requests[0].headers["idempotency-key"].clone();
+    assert!(requests.iter().all(|r| r.headers["idempotency-key"] == key));
+    assert_eq!(requests[0].method, Method::POST);
+    assert_eq!(requests[0].json()["status"], "firing");
+}
@@ -1,12 +1,15 @@
 use std::collections::HashMap;
 
+use sentinel_common::aggregation::Aggregation;
 use sentinel_common::canonicalize::canonical_bytes;
 use sentinel_common::crypto::sign_data;
 use sentinel_common::proto::metric::Value;
 use sentinel_common::proto::{Batch, Metric};
 
 use sentinel_workers::aggregator::AggregatorStore;
-use sentinel_workers::alert::{Condition, Evaluator, Rule, Severity};
+use sentinel_workers::alert::{
+    Condition, Evaluator, Rule, Severity, DEFAULT_MIN_SAMPLES, DEFAULT_WINDOW_MS,
+};
 use sentinel_workers::dedup::BatchDedup;
 use sentinel_workers::transform::transform_batch;
 use sentinel_workers::verify::{verify_batch, SecretProvider, VerifyResult};
@@ -87,12 +90,20 @@ async fn full_pipeline_verify_dedup_transform_alert() {
         name: "High CPU".into(),
         agent_pattern: "*".into(),
         metric_name: "cpu.core.0.usage".into(),
+        label_matchers: Vec::new(),
+        expression: None,
+        absent_for_ms: None,
         condition: Condition::GreaterThan,
         threshold: 90.0,
+        aggregation: Aggregation::Avg,
+        window_ms: DEFAULT_WINDOW_MS,
+        min_samples: DEFAULT_MIN_SAMPLES,
         for_duration_ms: 0,
         severity: Severity::Critical,
         annotations: HashMap::new(),
         notifier_ids: Vec::new(),
+        grouping: None,
+        escalation_policy_id: None,
     };
 
     let evaluator = Evaluator::new(vec![rule]);
@@ -149,12 +160,20 @@ async fn pipeline_alert_respects_for_duration() {
         name: "Delayed Alert".into(),
         agent_pattern: "*".into(),
         metric_name: "cpu.core.0.usage".into(),
+        label_matchers: Vec::new(),
+        expression: None,
+        absent_for_ms: None,
         condition: Condition::GreaterThan,
         threshold: 80.0,
+        aggregation: Aggregation::Avg,
+        window_ms: DEFAULT_WINDOW_MS,
+        min_samples: DEFAULT_MIN_SAMPLES,
         for_duration_ms: 60_000,
         severity: Severity::Warning,
         annotations: HashMap::new(),
         notifier_ids: Vec::new(),
+        grouping: None,
+        escalation_policy_id: None,
     };
 
     let metrics = vec![cpu_metric(0, 90.0)];
@@ -180,12 +199,20 @@ async fn pipeline_no_alert_below_threshold() {
         name: "Safe Rule".into(),
         agent_pattern: "*".into(),
         metric_name: "cpu.core.0.usage".into(),
+        label_matchers: Vec::new(),
+        expression: None,
+        absent_for_ms: None,
         condition: Condition::GreaterThan,
         threshold: 90.0,
+        aggregation: Aggregation::Avg,
+        window_ms: DEFAULT_WINDOW_MS,
+        min_samples: DEFAULT_MIN_SAMPLES,
         for_duration_ms: 0,
         severity: Severity::Info,
         annotations: HashMap::new(),
         notifier_ids: Vec::new(),
+        grouping: None,
+        escalation_policy_id: None,
     };
 
     let metrics = vec![cpu_metric(0, 50.0)];
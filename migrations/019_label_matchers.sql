@@ -0,0 +1,8 @@
+ALTER TABLE alert_rules
+    ADD COLUMN IF NOT EXISTS label_matchers JSONB NOT NULL DEFAULT '[]';
+
+ALTER TABLE alerts
+    ADD COLUMN IF NOT EXISTS labels JSONB NOT NULL DEFAULT '{}';
+
+CREATE INDEX IF NOT EXISTS idx_alerts_labels
+    ON alerts USING GIN (labels);
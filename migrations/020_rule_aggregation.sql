@@ -0,0 +1,4 @@
+ALTER TABLE alert_rules
+    ADD COLUMN IF NOT EXISTS aggregation TEXT    NOT NULL DEFAULT 'avg',
+    ADD COLUMN IF NOT EXISTS window_ms   BIGINT  NOT NULL DEFAULT 120000,
+    ADD COLUMN IF NOT EXISTS min_samples INTEGER NOT NULL DEFAULT 2;
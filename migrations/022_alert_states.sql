@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS alert_states (
+    fingerprint   TEXT PRIMARY KEY,
+    rule_id       TEXT NOT NULL,
+    rule_revision TEXT NOT NULL,
+    agent_id      TEXT NOT NULL,
+    state         JSONB NOT NULL,
+    updated_at    TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_alert_states_rule ON alert_states (rule_id);
@@ -0,0 +1,5 @@
+ALTER TABLE notifications_dlq
+    ADD COLUMN IF NOT EXISTS history_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_notifications_dlq_history
+    ON notifications_dlq (history_id);
@@ -0,0 +1,8 @@
+ALTER TABLE notifications_dlq
+    ADD COLUMN IF NOT EXISTS replay_requested_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS retry_count         INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS next_retry_at       TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_notifications_dlq_replay
+    ON notifications_dlq (replay_requested_at)
+    WHERE replay_requested_at IS NOT NULL;
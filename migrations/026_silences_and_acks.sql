@@ -0,0 +1,31 @@
+CREATE TABLE IF NOT EXISTS silences (
+    id             TEXT        PRIMARY KEY,
+    agent_pattern  TEXT,
+    rule_id        TEXT,
+    severity       TEXT,
+    label_matchers JSONB       NOT NULL DEFAULT '[]',
+    starts_at      TIMESTAMPTZ NOT NULL,
+    ends_at        TIMESTAMPTZ,
+    recurrence     JSONB,
+    comment        TEXT        NOT NULL DEFAULT '',
+    created_by     TEXT        NOT NULL DEFAULT '',
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at     TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_silences_window
+    ON silences (starts_at, ends_at);
+
+CREATE TABLE IF NOT EXISTS alert_acks (
+    fingerprint TEXT        PRIMARY KEY,
+    alert_id    TEXT        NOT NULL,
+    acked_by    TEXT        NOT NULL,
+    comment     TEXT        NOT NULL DEFAULT '',
+    acked_at    TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_alert_acks_alert
+    ON alert_acks (alert_id);
+
+ALTER TABLE alerts
+    ADD COLUMN IF NOT EXISTS suppressed_by TEXT;
@@ -0,0 +1,36 @@
+CREATE TABLE IF NOT EXISTS escalation_policies (
+    id          TEXT        PRIMARY KEY,
+    name        TEXT        NOT NULL UNIQUE,
+    description TEXT        NOT NULL DEFAULT '',
+    steps       JSONB       NOT NULL DEFAULT '[]',
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS escalation_routes (
+    id         TEXT        PRIMARY KEY DEFAULT 'default',
+    tree       JSONB       NOT NULL DEFAULT '{}',
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE alert_rules
+    ADD COLUMN IF NOT EXISTS escalation_policy_id TEXT;
+
+CREATE TABLE IF NOT EXISTS alert_escalations (
+    fingerprint    TEXT        NOT NULL,
+    policy_id      TEXT        NOT NULL,
+    alert_id       TEXT        NOT NULL,
+    rule_id        TEXT        NOT NULL,
+    event          JSONB       NOT NULL,
+    step           INT         NOT NULL DEFAULT 0,
+    notified       JSONB       NOT NULL DEFAULT '[]',
+    started_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    next_at        TIMESTAMPTZ,
+    leased_until   TIMESTAMPTZ,
+    stopped_reason TEXT,
+    PRIMARY KEY (fingerprint, policy_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_alert_escalations_next
+    ON alert_escalations (next_at)
+    WHERE next_at IS NOT NULL;
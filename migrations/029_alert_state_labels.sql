@@ -0,0 +1,4 @@
+DELETE FROM alert_states;
+
+ALTER TABLE alert_states
+    ADD COLUMN IF NOT EXISTS labels JSONB NOT NULL DEFAULT '{}';